@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Print the average brightness of every frame from the first camera
+//!
+//! ```sh
+//! cargo run --example frame_brightness -- 10
+//! ```
+//!
+//! The optional argument is how many seconds to capture (default 5).
+
+use camera::backends::camera::PixelFormat;
+use camera::backends::camera::pipewire::{enumerate_pipewire_cameras, get_pipewire_formats};
+use camera::engine::{self, Capture, FrameView};
+use std::time::Duration;
+
+/// Mean brightness of the frame on a 0-255 scale
+///
+/// YUV and gray frames use their luma, RGB frames the plain channel average.
+fn mean_brightness(frame: &FrameView<'_>) -> Option<f64> {
+    let (mut sum, mut count) = (0u64, 0u64);
+    for row in frame.rows() {
+        match frame.format {
+            PixelFormat::Gray8 | PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => {
+                sum += row.iter().map(|&y| y as u64).sum::<u64>();
+                count += row.len() as u64;
+            }
+            // Y0 U Y1 V and Y0 V Y1 U
+            PixelFormat::YUYV | PixelFormat::YVYU => {
+                sum += row.iter().step_by(2).map(|&y| y as u64).sum::<u64>();
+                count += row.len() as u64 / 2;
+            }
+            // U Y0 V Y1 and V Y0 U Y1
+            PixelFormat::UYVY | PixelFormat::VYUY => {
+                sum += row
+                    .iter()
+                    .skip(1)
+                    .step_by(2)
+                    .map(|&y| y as u64)
+                    .sum::<u64>();
+                count += row.len() as u64 / 2;
+            }
+            PixelFormat::RGB24 => {
+                sum += row.iter().map(|&c| c as u64).sum::<u64>();
+                count += row.len() as u64;
+            }
+            PixelFormat::RGBA => {
+                for pixel in row.chunks_exact(4) {
+                    sum += pixel[..3].iter().map(|&c| c as u64).sum::<u64>();
+                    count += 3;
+                }
+            }
+            // Little-endian 16-bit samples, scaled down to 8 bits
+            PixelFormat::Gray16 => {
+                for sample in row.chunks_exact(2) {
+                    sum += sample[1] as u64;
+                    count += 1;
+                }
+            }
+        }
+    }
+    (count > 0).then(|| sum as f64 / count as f64)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let seconds: u64 = match std::env::args().nth(1) {
+        Some(arg) => arg.parse()?,
+        None => 5,
+    };
+
+    gstreamer::init()?;
+    let cameras = enumerate_pipewire_cameras().unwrap_or_default();
+    let camera = cameras.first().ok_or("No cameras found")?;
+    let formats = get_pipewire_formats(&camera.path, camera.metadata_path.as_deref());
+    let format = formats.first().ok_or("No formats available for camera")?;
+    println!(
+        "{}: {}x{} {}",
+        camera.name, format.width, format.height, format.pixel_format
+    );
+
+    // Runs on the streaming thread: keep it short, the pipeline waits for it
+    engine::set_frame_callback(|frame| {
+        if let Some(brightness) = mean_brightness(frame) {
+            println!(
+                "frame {:>5} {:>4}x{:<4} {:?}: brightness {:.1}",
+                frame.index, frame.width, frame.height, frame.format, brightness
+            );
+        }
+    });
+
+    let capture = Capture::start(camera, format)?;
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(capture.wait_for_first_frame(Duration::from_secs(10)))?;
+    println!("Decoding with {}", capture.decoder());
+
+    std::thread::sleep(Duration::from_secs(seconds));
+    engine::clear_frame_callback();
+    drop(capture);
+    Ok(())
+}
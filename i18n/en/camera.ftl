@@ -32,20 +32,120 @@ light = Light
 settings-camera = Camera
 settings-video = Video
 settings-device = Device
+settings-refresh-previews = Refresh previews
 settings-format = Format
 settings-microphone = Microphone
 settings-record-audio = Record audio
 settings-audio-encoder = Audio encoder
+settings-audio-level = Input level
+settings-audio-mute = Mute microphone
+settings-audio-mute-description = Recordings keep an audio track but it stays silent. Applies immediately, even while recording.
 settings-encoder = Encoder
 settings-quality = Quality
 settings-video-encoder = Video encoder
 settings-video-quality = Video quality
+settings-retro-buffer = Keep recent footage
+settings-retro-buffer-description = Buffer the last few seconds so they can be saved after the fact
+settings-retro-buffer-off = Off
+settings-retro-buffer-seconds = { $seconds } s
+settings-split-duration = Split recordings every
+settings-split-description = Long recordings continue in a new file at the next keyframe, so every file plays on its own. The filename template's {"{"}seq{"}"} token numbers the files.
+settings-split-size = Split recordings at size
+settings-split-off = Never
+settings-split-minutes = { $minutes } min
+settings-split-megabytes = { $mb } MB
+settings-retro-save = Save recent footage
+settings-retro-save-button = Save clip
 settings-manual-override = Manual mode override
 settings-mirror-preview = Mirror preview
-settings-mirror-preview-description = Flip the camera preview horizontally
+settings-mirror-preview-description = Flip the preview of cameras facing you. Photos and recordings are never mirrored
+settings-mirror-preview-rear = Rear-facing cameras are never mirrored. Photos and recordings are never mirrored
+settings-camera-facing = Camera facing
+settings-camera-facing-description = Detected: { $detected }. Rear-facing cameras are shown unmirrored
+settings-camera-facing-auto = Automatic
+settings-camera-source = Camera stack
+settings-camera-source-description = This camera is available through both. V4L2 offers USB cameras' formats directly, libcamera adds image processing for raw sensors
+settings-rotation = Rotation
+settings-rotation-description = Automatic uses the sensor rotation reported by the camera and follows the device orientation
+settings-color-effect = Color effect
+settings-color-effect-description = Applied to the preview on top of the selected filter. Grayscale and high contrast can help with low vision or color blindness.
+settings-color-effect-output = Apply effect to photos
+settings-color-effect-output-description = By default the effect only changes the preview, not what is saved
+settings-scaling-quality = Preview scaling
+settings-scaling-quality-description = How the preview is filtered when it is resized or zoomed. Nearest shows individual pixels, bicubic is sharper when enlarging but costs more GPU time.
+settings-gray-auto-stretch = Auto-stretch 16-bit images
+settings-gray-auto-stretch-description = Map each frame's darkest and brightest values to black and white so dim thermal images become visible
+settings-false-color = False color
+settings-false-color-description = Show grayscale images with a thermal-style color palette
+settings-preview-cap = Maximum preview resolution
+settings-preview-cap-description = Lower values save GPU and CPU time on slow hardware. Applies in video mode; recordings keep the camera resolution.
+settings-low-light = Low light
+settings-low-light-description = When the scene stays dark, use a lower frame rate of the same resolution so the camera can expose longer. Never changes modes during a recording.
+settings-open-timeout = Camera start timeout
+settings-open-timeout-description = How long to wait for the first frame before reporting that the camera didn't start.
+settings-open-timeout-seconds = { $seconds } s
+settings-background-preview = Preview in the background
+settings-background-preview-description = Save power while the window is not focused. Recordings and the virtual camera keep the preview running.
+settings-keep-awake = Keep screen awake
+settings-keep-awake-description = Stop the screen from blanking and the computer from suspending. Always covers the preview, recordings and streams.
+settings-framerate-cap = Limit frame rate
+settings-framerate-cap-description = Show and record fewer frames than the camera sends, dropping them evenly. The camera keeps its mode.
+settings-framerate-cap-off = Camera rate
+settings-framerate-cap-fps = { $fps } fps
+settings-mjpeg-resilience = Drop damaged frames
+settings-mjpeg-resilience-description = For cameras whose picture shows green or gray blocks now and then. Damaged frames are skipped instead of shown. Remembered for this camera.
+settings-deinterlace = Deinterlacing
+settings-deinterlace-description = This camera sends interlaced video. Choose how fields are combined, or Off to show raw fields
+settings-decode-threads = Limit Decoder Threads
+settings-decode-threads-auto = Software H.264/H.265 decoding uses one thread per CPU ({ $threads })
+settings-decode-threads-limited = Software H.264/H.265 decoding uses at most { $threads } threads
+settings-v4l2-io-mode = Capture Path
+settings-v4l2-io-mode-description = For debugging zero-copy capture. The V4L2 choices open the camera directly instead of through PipeWire and force its I/O mode; other apps can't use the camera meanwhile
+settings-power-preference = Power Preference
+settings-power-preference-description = Low power tries software H.264/H.265 decoders first on CPUs with efficiency cores and asks for the integrated GPU
+settings-vaapi-device = Hardware decode GPU
+settings-vaapi-device-description = Render node used for VA-API decoding. Legacy VA-API decoders switch on next launch
+settings-vaapi-device-default = Default
+settings-automation = Automation
+settings-control-socket = Control socket
+settings-control-socket-description = Accept commands like "snapshot" or "set-zoom 2.0" on { $path } (current user only)
+settings-http-stream = MJPEG stream
+settings-http-stream-description = Serve the preview over HTTP so a browser or phone can watch it. Frames are only encoded while someone is watching
+settings-http-stream-port = Stream port
+settings-http-stream-port-invalid = Choose a port between { $min } and 65535
+settings-http-stream-all-interfaces = Allow other devices
+settings-http-stream-all-interfaces-description = Listen on every network interface instead of only this computer. Anyone on the network can watch; there is no password
+settings-http-stream-max-clients = Most viewers
+settings-http-stream-max-clients-description = Viewers beyond this many are turned away
+http-stream-bind-failed = MJPEG stream couldn't start: { $error }
+settings-reset = Reset all settings
+settings-reset-description = Restore every setting to its default. Photos, recordings and capture profiles are kept
+settings-reset-button = Reset
+reset-settings-title = Reset all settings?
+reset-settings-body = Every setting, including formats, shortcuts, decoder and transform choices, goes back to its default. Photos, recordings and capture profiles are not touched.
+reset-settings-confirm = Reset settings
+reset-settings-cancel = Cancel
+reset-settings-done = Settings reset to defaults
+settings-profiles = Profiles
+settings-profiles-apply = Apply
+settings-profiles-delete = Delete
+settings-profiles-save = Save current settings
+settings-profiles-description = Stores the format, rotation, mirroring, zoom, filter, color effect and overlays under a name, e.g. "Meeting" or "Documents"
+settings-profiles-name = Profile name
+settings-profiles-save-button = Save
+settings-shortcuts = Keyboard shortcuts
+settings-shortcuts-press-key = Press a key…
+settings-shortcuts-reset = Reset shortcuts
+settings-shortcuts-description = Click a key to rebind it; Escape cancels. Shortcuts are ignored while typing in a text field
+settings-shortcuts-reset-button = Reset
+settings-nudge-coarse-steps = Exposure and gain step with Shift
+settings-nudge-coarse-steps-description = How far the exposure and gain shortcuts move while Shift is held. Without Shift they move one step of the control
+settings-nudge-coarse-steps-value = { $steps } steps
 settings-bug-reports = Bug reports
 settings-report-bug = Report bug
 settings-show-report = Show Report
+settings-validate-decoders = Validate decoders at startup
+settings-validate-decoders-description = Load every installed video decoder when the app starts so broken plugins are skipped. Slightly slows down startup.
 settings-resolution = Resolution
 settings-version = Version { $version }
 settings-version-flatpak = Version { $version } (Flatpak)
@@ -76,6 +176,26 @@ indicator-sd = SD
 indicator-4k = 4K
 indicator-720p = 720p
 
+# Recording summary
+recording-summary-saved = Recording saved
+recording-summary-incomplete = Recording incomplete
+recording-summary-duration = Duration
+recording-summary-size = File size
+recording-summary-segments = Files
+recording-summary-resolution = Resolution
+recording-summary-encoder = Encoder
+recording-summary-bitrate = Average bitrate
+recording-summary-dropped = Dropped frames
+recording-summary-reveal = Show in Files
+recording-summary-dismiss = Dismiss
+
+# Shutdown problems, shown briefly before the app exits
+shutdown-recording-failed = The recording could not be finalized: { $error }
+shutdown-recording-timeout = The recording wasn't finalized before exit and may be incomplete
+shutdown-virtual-camera-failed = The virtual camera didn't stop cleanly: { $error }
+shutdown-virtual-camera-timeout = The virtual camera didn't stop before exit
+shutdown-camera-busy = The camera may still be in use: { $error }
+
 # QR code actions
 qr-open-link = Open Link
 qr-connect-wifi = Connect to WiFi
@@ -99,9 +219,14 @@ exposure-no-controls = No exposure controls available
 exposure-title = Exposure
 exposure-reset = Reset
 exposure-backlight = Backlight
+exposure-anti-flicker = Flicker
 exposure-manual-mode = Manual
 exposure-auto-mode = Auto
 exposure-not-supported = unsupported
+nudge-value = { $control }: { $value }
+nudge-limit = { $control } is at its limit ({ $value })
+nudge-automatic = { $control } is automatic; switch to manual exposure to adjust it
+nudge-unavailable = { $control } can't be adjusted on this camera
 
 # Color controls
 color-title = Color
@@ -114,6 +239,12 @@ color-temperature = Temp
 color-auto = Auto
 color-manual = Manual
 
+# Focus controls
+focus-autofocus = Autofocus
+focus-continuous = Continuous
+focus-position = Focus
+focus-one-shot = Focus once
+
 # Tools menu
 tools-timer = Timer
 tools-aspect = Aspect
@@ -121,6 +252,21 @@ tools-exposure = Exposure
 tools-color = Color
 tools-filter = Filter
 tools-theatre = Theatre
+tools-copy-frame = Copy
+
+# Clipboard
+clipboard-frame-copied = Frame copied to clipboard
+clipboard-frame-failed = Couldn't copy the frame to the clipboard
+still-burst-progress = Burst { $taken } of { $count }
+still-burst-busy = A photo burst is already running
+still-burst-done = Photo burst saved { $saved } of { $count } photos
+registry-stale = GStreamer's plugin registry looks empty or out of date, so decoders may be missing. Run gst-inspect-1.0 to rebuild it, then restart Camera.
+registry-blacklisted = GStreamer couldn't load { $plugins }, so decoders may be missing. Run gst-inspect-1.0 -b for details; deleting ~/.cache/gstreamer-1.0 forces a rescan.
+pipeline-graph-exported = Pipeline graph saved to { $path }
+pipeline-graph-exported-png = Pipeline graph saved to { $path } and rendered to { $png }
+raw-dump-saved = Saved { $frames } encoded frames to { $path }
+raw-dump-failed = Stream dump failed: { $error }
+pipeline-graph-failed = Couldn't save the pipeline graph
 
 # PTZ controls
 ptz-title = Camera Controls
@@ -128,6 +274,37 @@ ptz-title = Camera Controls
 # Privacy cover warning
 privacy-cover-closed = Privacy cover is closed
 privacy-cover-hint = Open the privacy cover to use the camera
+black-frames-title = Camera may be disabled by a privacy switch
+black-frames-hint = The camera only delivers black frames. Check for a privacy switch or camera key on your device.
+black-frames-dismiss = Dismiss
+
+# Pipeline start-up failures
+pipeline-error-title = The camera could not be started
+pipeline-error-source = The camera could not be opened. Another application may be using it.
+pipeline-error-caps = The camera refused the selected format. Try another resolution or format.
+pipeline-error-decoder = The { $decoder } decoder failed to start. Try another format.
+pipeline-error-decoder-package = The { $decoder } decoder failed to start. Check that { $package } and your GPU's video driver are installed, or try another format.
+pipeline-error-missing = The GStreamer element { $element } is not installed.
+pipeline-error-missing-package = The GStreamer element { $element } is not installed. Install { $package } and restart the app.
+pipeline-error-other = The camera pipeline failed to start. The log has the details.
+pipeline-error-timeout = The camera didn't start within { $seconds } s. It may be in use by another app.
+missing-decoder-title = No { $codec } decoder installed
+missing-decoder-hint = The selected format is { $codec }, and no installed GStreamer plugin can decode it. Install { $packages } with your distribution's package manager (the package names usually contain these, e.g. gstreamer1-plugins-bad or gstreamer1.0-plugins-bad), then restart the app.
+missing-decoder-packages = { $packages } or { $last }
+missing-decoder-switch = Use { $codec } { $format } instead
+
+# Low-light frame rate switching
+low-light-suggest = The scene is dark. { $fps } fps allows longer exposures.
+low-light-switch = Switch
+low-light-switched = Switched to { $fps } fps for low light
+low-light-undo = Undo
+low-light-bright = There is enough light for { $fps } fps again
+low-light-switch-back = Switch back
+low-light-restored = Back to { $fps } fps
+
+# Resolution fallback after repeated start failures
+safe-resolution-downgraded = The selected mode keeps failing to start. Switched to { $resolution } { $format }.
+safe-resolution-exhausted = Lower resolutions failed as well.
 
 # Burst mode / HDR+
 burst-mode-hold-steady = Hold steady...
@@ -147,13 +324,52 @@ hdr-plus-frames-50 = 50 frames
 # Photo settings
 settings-photo = Photo
 settings-photo-format = Output format
-settings-photo-format-description = File format for saved photos. JPEG is compressed, PNG is lossless, DNG preserves raw data for editing.
+settings-photo-format-description = File format for saved photos. JPEG is compressed, PNG and WebP are lossless, DNG preserves raw data for editing.
+settings-jpeg-quality = JPEG quality
+settings-jpeg-quality-description = { $quality } (higher is sharper but larger)
 settings-hdr-plus = HDR+ (experimental)
 settings-hdr-plus-description = Multi-frame capture for improved low-light photos and dynamic range. Auto selects frame count based on scene brightness.
 settings-burst-mode-quality = HDR+ algorithm
 settings-burst-mode-quality-description = Quality uses FFT frequency domain merge for best results. Fast uses spatial merge for quicker processing.
 settings-save-burst-raw = Save raw burst frames
 settings-save-burst-raw-description = Save individual burst frames as DNG files alongside HDR+ photos. Useful for debugging or reprocessing.
+settings-embed-metadata = Embed capture details
+settings-embed-metadata-description = Store the camera name, resolution, framerate, format and decoder in saved JPEG and PNG photos. Turn off for privacy.
+settings-first-frame-snapshot = Photo when the camera starts
+settings-first-frame-snapshot-description = Save a photo of the first frame when the app starts or another camera or format is chosen, to confirm it works and record the scene. Uses the filename template and output settings of manual photos.
+settings-still-burst-count = Photo burst
+settings-still-burst-description = Photos saved each time { $key } is pressed, each from a different frame
+settings-still-burst-count-value = { $count } photos
+settings-still-burst-interval = Time between burst photos
+settings-still-burst-interval-value = { $ms } ms
+
+# Output settings
+settings-output = Output
+settings-output-directory = Output folder
+settings-output-directory-default = Photos go to { $photos }, videos to { $videos }
+settings-output-directory-choose = Choose…
+settings-output-directory-reset = Default
+settings-filename-template = Filename template
+settings-filename-template-example = Example: { $name } · Tokens: { $tokens }
+settings-filename-template-empty = The template is empty
+settings-filename-template-separator = The template can't contain / or \
+settings-filename-template-unknown = Unknown token {"{"}{ $token }{"}"}
+settings-filename-template-unclosed = A {"{"} is missing its closing {"}"}
+settings-filename-template-extension = Leave out the extension, it is added for the output format
+settings-filename-template-hidden = The template can't start with a dot
+
+# Watermark settings
+settings-watermark = Watermark
+settings-watermark-enabled = Burn in watermark
+settings-watermark-description = Draw text into saved photos and recordings. The preview is not affected, and the text can't be removed from the files afterwards.
+settings-watermark-text = Watermark text
+settings-watermark-timestamp = Add timestamp
+settings-watermark-timestamp-description = The capture time for photos, the time of each frame for recordings
+settings-watermark-position = Position
+settings-watermark-size = Text size
+settings-watermark-size-description = { $percent }% of the image height
+settings-watermark-opacity = Opacity
+settings-watermark-opacity-description = { $percent }%
 
 # About page
 about-support = Support & Feedback
@@ -162,25 +378,289 @@ about-support = Support & Feedback
 insights-title = Insights
 insights-pipeline = Pipeline
 insights-pipeline-full = GStreamer Pipeline
+insights-pipeline-graph = Element graph
+insights-pipeline-graph-description = Graphviz file of the running pipeline with element states and caps, rendered to PNG too when Graphviz is installed
+insights-pipeline-graph-export = Export…
+insights-raw-dump = Encoded stream
+insights-raw-dump-description = Save the frames exactly as the camera sends them, before decoding: one .jpg per frame for MJPEG, an .h264/.h265 stream otherwise
+insights-raw-dump-running = Saving { $seconds } s of frames…
+insights-raw-dump-button = Dump
+insights-raw-dump-button-running = Dumping…
+insights-device-quirk = Device Quirk
+insights-v4l2-io-mode = V4L2 I/O Mode
+insights-v4l2-io-mode-fallback = { $mode } ({ $requested } unsupported)
+insights-v4l2-io-mode-dmabuf = Frames reach the GPU as DMABUF
+insights-v4l2-io-mode-mapped = Frames are mapped into CPU memory (no DMABUF)
+insights-elements = Elements
+insights-elements-description = Select an element to show its current property values
+insights-hw-accel = Hardware Acceleration
+insights-hw-accel-full = Fully HW accelerated
+insights-hw-accel-partial = Partially CPU
+insights-hw-accel-cpu-decode = Decoding: a software decoder is selected
+insights-hw-accel-cpu-handoff = GPU handoff: frames are mapped into CPU memory and uploaded from there (no DMABUF)
+insights-hw-accel-cpu-conversion = Color conversion: videoconvert runs on the CPU before the GPU
 insights-decoder-chain = Decoder Fallback Chain
+insights-mjpeg-resilience = MJPEG Error Resilience
+insights-mjpeg-resilience-off = Off
+insights-decode-threads = Decoder Threads
+insights-decode-threads-auto = Automatic ({ $threads })
+insights-decoder-source = Decoder selection
+insights-decoder-source-description = The decoder that last worked is tried first; the chain is probed again if it fails or GStreamer is updated
+insights-decoder-source-cache = Cached from the last run
+insights-decoder-source-probed = Probed from the chain
+insights-parser = Parser
+insights-parser-description = Converts the stream to whole access units before the decoder
+insights-power-preference = Power Preference
+insights-power-decoders-hardware-first = Decoders: hardware first
+insights-power-decoders-software-first = Decoders: software first ({ $cores } efficiency cores)
+insights-power-decoders-no-efficiency-cores = Decoders: hardware first (no efficiency cores)
+insights-power-gpu-low-power = GPU: low-power adapter for new compute devices
+insights-power-gpu-high-performance = GPU: high-performance adapter for new compute devices
+insights-compare = Compare
+insights-compare-stop = Stop
+insights-compare-active = Comparing Decoders
+insights-compare-halves = Left: { $left } · Right: { $right }
 
 insights-performance = Live Performance
 insights-frame-latency = Frame Latency
+insights-latency-history = Recent Latency
+insights-latency-history-range = { $min } – { $max } ms
+insights-raw-value = Raw: { $value }
+insights-presentation-lag = Presentation Lag
+insights-presentation-lag-description = Age of the frame on screen relative to the newest decoded frame. A growing value means drawing, not the camera pipeline, is behind.
+insights-metrics-smoothing = Smooth Metrics
+insights-raw-microseconds = Raw Microseconds
+insights-raw-microseconds-description = Show latency and stage times as the measured integer µs, without rounding or smoothing
+insights-cpu-usage = CPU Usage
+insights-pin-metrics = Pinned Metrics
+insights-pin-metrics-description = Pin the current metrics to see how later changes affect them
+insights-pinned-since = Changes since pinning { $seconds } s ago
+insights-pin = Pin
+insights-clear-pin = Clear Pin
+insights-pinned-delta = { $value } ({ $delta } from pinned)
+insights-pinned-new = { $value } (not pinned)
+insights-pinned-was = { $value } (pinned { $pinned })
+insights-session = Session
+insights-session-summary = { $uptime } · { $frames } frames
+insights-session-fps = { $rolling } fps now · { $average } fps average
+insights-session-stats = Session Stats
+insights-session-stats-description = Min / avg / max per frame since the pipeline started, for comparing decoders and settings
+insights-session-stats-samples = { $count } frames · min / avg / max
+insights-session-stats-reset = Start Over
+insights-session-stats-reset-button = Reset
+insights-framerate-cap = Frame rate cap
+insights-framerate-cap-value = { $source } fps → { $effective } fps
+insights-framerate-cap-measured = Camera rate → shown rate, { $rolling } fps measured
+insights-preview-cap = Preview Cap
+insights-preview-cap-comparison = Before: { $before } · Now: { $after }
+insights-gpu-device = GPU Device
+insights-gpu-ok = OK
+insights-gpu-recovering = Recovering GPU…
+insights-gpu-recovered = Recovered ({ $count } device losses)
+insights-preview = Freeze Preview
+insights-preview-live = Live
+insights-preview-frozen = Frozen
+insights-preview-stepping = Stepping ({ $count } frames)
+insights-frame-pts = Frame PTS
+insights-step-frame = Step
 insights-dropped-frames = Dropped Frames
+insights-frame-drop-policy = Frame Drop Policy
+insights-appsink-buffers = Sink Buffers
+insights-appsink-buffers-description = Frames the appsink holds for the app before dropping or waiting. More buffers keep every frame through short hiccups at the cost of latency.
+insights-appsink-buffers-auto = Automatic
+insights-appsink-drop = Drop at Sink
+insights-appsink-drop-description = When the sink is full, discard its oldest frame. Off slows the camera down instead, and the frame drop policy decides what the preview skips.
+insights-retro-buffer = Retro Buffer
+insights-retro-buffer-fill = { $seconds }s / { $max_seconds }s ({ $mb } MB / { $max_mb } MB)
+insights-recording-segment = Recording Segment
+insights-recording-segment-value = Segment { $index } ({ $finalized } finalized)
+insights-policy-dropped = { $policy } — { $count } frames discarded while the preview was behind
+insights-skip-identical = Skip Identical Frames
+insights-skipped-uploads = { $count } GPU uploads skipped for unchanged frames
+insights-stall-watchdog = Stall Watchdog
+insights-stall-watchdog-off = Off
+insights-stall-watchdog-seconds = After { $seconds } s
+insights-stall-restarts = Restarts the pipeline when no frames arrive · { $count } restarts
+insights-stall-restarting = Stalled — restarting
+insights-stall-gave-up = Stalled — gave up after { $count } restarts in a row
+insights-power-saving = Background power saving
+insights-power-saving-paused = Preview paused
+insights-power-saving-throttled = Throttled to { $fps } fps
+insights-power-saving-overridden = Off while recording or streaming
+insights-power-saving-inactive = Window focused
+insights-keep-awake = Keep awake
+insights-keep-awake-active = { $reason } ({ $backend })
+insights-keep-awake-pending = Requesting inhibition…
+insights-keep-awake-failed = Failed: { $error }
+insights-keep-awake-inactive = Screen may sleep
 insights-frame-size-decoded = Frame Size
+insights-texture-memory = Texture Memory
+insights-texture-memory-description = GPU memory of the preview, conversion and gallery textures
 insights-decode-time-gst = Buffer Processing
 insights-copy-time = Frame Wrap Time
 insights-gpu-upload-time = GPU Upload Time
 insights-gpu-upload-bandwidth = GPU Upload Bandwidth
+insights-display-refresh = Display Refresh Rate
+insights-display-cadence-mismatch = Camera framerate does not divide evenly into the refresh rate, so some frames are shown longer than others
+insights-estimated = estimated
+insights-method-frame-latency = Estimated: age of the newest frame when the metrics refresh. Excludes time in the sensor and driver, and includes up to one refresh interval
+insights-method-presentation-lag = Measured: capture time of the newest decoded frame minus that of the frame on screen
+insights-method-decode-time = Measured: time to pull and map each sample from the pipeline
+insights-method-copy-time = Measured: time to wrap each mapped sample into a frame
+insights-method-gpu-upload = Measured: CPU time to queue each frame's texture upload (the GPU copies it later)
+insights-method-upload-bandwidth = Estimated: frame size divided by the upload time
+insights-method-cpu-usage = Measured: process CPU time between metric refreshes
+insights-method-frame-rate = Measured: frames counted between metric refreshes
+insights-method-display-refresh = Estimated: average interval of the last 60 window frames
+insights-method-texture-memory = Estimated: texture sizes times their texel size; driver padding isn't included
+insights-present-mode = Present Mode
+insights-present-mode-requested = Requested at launch: { $mode }
+insights-present-mode-fifo = waits for display refresh (vsync); no tearing, up to one frame of extra latency
+insights-present-mode-fifo-relaxed = vsync, but late frames are shown immediately; lower latency, occasional tearing
+insights-present-mode-mailbox = newest frame replaces queued ones; low latency without tearing
+insights-present-mode-immediate = frames shown as soon as ready; lowest latency, visible tearing
+insights-present-mode-fallback = { $requested } isn't supported by this display; using { $mode }
+insights-present-mode-restart = Takes effect after restarting the app
+
+insights-focus = Focus
+insights-focus-score = Focus Score
+insights-focus-score-description = Sharpness of the image; the bar fills up at the sharpest frame seen. Compare scores of the same scene only.
+insights-focus-peaking = Focus Peaking
+insights-focus-peaking-description = Highlight in-focus edges in the preview
+insights-pixel-probe = Pixel Probe
+insights-pixel-probe-description = Show the value of the pixel under the cursor; click the preview to lock it
+insights-frame-counter = Frame Counter Overlay
+insights-frame-counter-description = Draw the frame number and PTS on the preview, e.g. to film the screen and measure latency. Photos only include it while the watermark is on.
+pixel-probe-hint = Hover over the preview to probe a pixel
+pixel-probe-position = { $x }, { $y }
+pixel-probe-locked = { $x }, { $y } (locked, click to release)
+pixel-probe-padding = +{ $bytes } padding bytes: { $head }
+
+insights-thermal = Thermal
+insights-thermal-cpu-clock = CPU Clock
+insights-thermal-gpu-clock = GPU Clock
+insights-thermal-temperature = Hottest Sensor
+insights-thermal-throttling = Possible thermal throttling
+insights-thermal-throttling-description = Decoding has slowed down while clock speeds dropped since the stream started
+insights-http-stream = MJPEG Stream
+insights-http-stream-url = Address
+insights-http-stream-localhost = Only this computer can connect
+insights-http-stream-all-interfaces = Other devices on the network can connect
+insights-http-stream-starting = Starting…
+insights-http-stream-failed = Not listening: { $error }
+insights-http-stream-viewers = Viewers
+insights-http-stream-viewers-value = { $count } of { $max }
 
 insights-format = Format
 insights-format-source = Source
 insights-format-resolution = Resolution
+insights-format-resolution-changes = Source changed resolution { $count } times since the stream started
+insights-format-displayed-resolution = Displayed Resolution
+insights-format-row-stride = Row Stride
+insights-format-stride-warning = Edge Artifact
+insights-format-stride-warning-description = Probe the last column of the preview: its readout lists the padding bytes that follow each row
+insights-stride-sheared = Rows uploaded { $uploaded } bytes apart instead of { $stride }, the picture is sheared
+insights-stride-padding-visible = Texture is { $texels } texels wider than the picture, row padding shows as a bar on the right edge
+insights-stride-inferred = Stride of { $stride } bytes is shorter than a row, uploaded { $uploaded } bytes apart from the buffer size
 insights-format-framerate = Framerate
 insights-format-native = Native Format
 insights-format-gstreamer = GStreamer Output
 insights-format-wgpu = GPU Processing
+insights-format-wgpu-no-compute = No compute shader support: frames are converted in a fragment shader and the exposure histogram is disabled
+insights-gpu-path = GPU Path
+insights-gpu-path-unverified = Not verified yet
+insights-gpu-path-passthrough = Passthrough (no conversion)
+insights-gpu-path-conversion = Conversion shader
+insights-gpu-path-needless = Conversion shader on RGB source ({ $format })
+insights-gpu-path-needless-description = Performance issue: the source already delivers RGB, but its frames reach the GPU in another format and get converted back
+insights-format-colorimetry = Colorimetry
+insights-format-scan = Scan
+insights-format-forced-caps = Forced Caps
+insights-usb = USB
+insights-usb-summary = { $speed } · bus { $bus } · { $devices } devices
+insights-usb-downgraded = The camera chose a smaller mode than requested, likely for lack of USB bandwidth. Try another USB port, ideally on a different controller.
+insights-usb-shortfall = Frames arrive slower than requested, likely for lack of USB bandwidth. Try another USB port, ideally on a different controller.
+insights-safe-resolution = Resolution fallback
+insights-safe-resolution-reason = Failed to start { $failures } times: { $reason }
+insights-low-light = Low light
+insights-low-light-luma = Mean luma { $luma }
+insights-low-light-watching = Offers a lower frame rate when the mean luma stays below { $dark } for { $seconds } s
+insights-low-light-switched = { $to } fps instead of { $from } fps: the mean luma stayed below { $dark }. Goes back once it stays above { $bright }.
+insights-low-light-handled = Stayed below { $dark }; checked again once it rises above { $clear }
+insights-low-light-no-mode = Dark, but there is no slower mode at this resolution and pixel format
+insights-orientation = Orientation
+insights-orientation-auto = { $detected } (detected)
+insights-orientation-device = { $detected } (detected) + { $device } (device rotated)
+insights-orientation-override = { $rotation } (manual, overrides detected { $detected })
+insights-format-colorimetry-unspecified = (unspecified)
+
+insights-format-history = Format History
+insights-format-history-changes = Format and decoder changes
+insights-format-history-description = Changes caused by decoder fallback or renegotiation, newest first. Changes within two seconds of each other are merged.
+insights-format-history-empty = No changes since the camera started
+
+insights-benchmark = Upload Benchmark
+insights-benchmark-run = Zero-copy vs Copy
+insights-benchmark-description = Measures frame upload with and without copying, then returns to zero-copy
+insights-benchmark-running = Measuring { $path }: { $frames }/{ $total } frames
+insights-benchmark-start = Run
+insights-benchmark-breakdown = Wrap/copy { $copy } ms + GPU upload { $gpu } ms per frame
+insights-benchmark-faster = Zero-copy is { $factor }× as fast as copying on this system
+insights-benchmark-slower = Copying was faster than zero-copy on this system
+
+insights-advanced = Advanced
+insights-forced-caps = Force Caps
+insights-forced-caps-description = Caps filter inserted before the preview sink, overriding automatic negotiation
+insights-forced-caps-active = Forcing { $caps }
+insights-forced-caps-invalid = { $error }
+insights-forced-caps-apply = Apply
+insights-forced-caps-clear = Clear
 
 insights-selected = Selected
 insights-available = Available
 insights-unavailable = Unavailable
+insights-broken = Installed but failed to load
+insights-decoder-error-rate = { $percent }% error rate ({ $errors } errors since the pipeline started)
+insights-vaapi-device = VA-API device
+insights-vaapi-device-default = Default
+insights-vaapi-device-fallback = Default ({ $node } unavailable)
+
+# Decoder descriptions (keyed by GStreamer element name; keep product names untranslated)
+decoder-jpegdec = GStreamer JPEG (Software)
+decoder-avdec_mjpeg = FFmpeg MJPEG (Software)
+decoder-vaapijpegdec = VA-API JPEG (Intel/AMD HW)
+decoder-nvjpegdec = NVIDIA JPEG (NVDEC)
+decoder-v4l2jpegdec = V4L2 JPEG (Hardware)
+decoder-vah264dec = VA-API H.264 (Modern HW)
+decoder-vaapih264dec = VA-API H.264 (Legacy HW)
+decoder-nvh264dec = NVIDIA H.264 (NVDEC)
+decoder-d3d11h264dec = Direct3D 11 H.264 (HW)
+decoder-v4l2h264dec = V4L2 H.264 (Hardware)
+decoder-avdec_h264 = FFmpeg H.264 (SW, multi-threaded)
+decoder-openh264dec = OpenH264 (SW, single-threaded)
+decoder-vah265dec = VA-API H.265 (Modern HW)
+decoder-vaapih265dec = VA-API H.265 (Legacy HW)
+decoder-nvh265dec = NVIDIA H.265 (NVDEC)
+decoder-d3d11h265dec = Direct3D 11 H.265 (HW)
+decoder-v4l2h265dec = V4L2 H.265 (Hardware)
+decoder-avdec_h265 = FFmpeg H.265 (SW, multi-threaded)
+
+# Diagnostics bundle
+insights-device = Device
+insights-device-node = V4L2 node
+insights-device-no-node = None (libcamera camera)
+insights-device-driver = Kernel driver
+insights-device-usb-id = USB ID
+insights-device-bus-path = USB bus path
+insights-device-serial = Serial
+insights-device-identifiers = Identifiers
+insights-diagnostics = Diagnostics
+insights-diagnostics-bundle = Diagnostics bundle
+insights-diagnostics-copy = Copy
+insights-diagnostics-save = Save
+insights-diagnostics-include-ids = Include device identifiers
+insights-diagnostics-include-ids-description = Device paths and serials are redacted unless enabled
+insights-gstreamer-version = GStreamer
+insights-gstreamer-plugin-dirs = Plugin directories
+insights-gstreamer-custom-path = Custom plugin path
+insights-gstreamer-custom-path-description = Plugins are loaded from directories set in the environment, so available decoders may differ from the system packages
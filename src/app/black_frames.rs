@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Black frame detection
+//!
+//! Some laptops disable the camera with a privacy switch that has no V4L2
+//! control: the camera still enumerates and streams, but every frame is
+//! black. The first seconds of each pipeline are sampled on a coarse luma
+//! grid and, if they are all black, the preview suggests checking for such a
+//! switch.
+//!
+//! A genuinely dark scene still has sensor noise and the odd light source, so
+//! a frame only counts as black when it is near zero *and* flat. The stream
+//! also has to be black from its first frame for a while; a single frame with
+//! any content clears the suspicion for the rest of the pipeline.
+
+use std::time::{Duration, Instant};
+
+use crate::app::insights::focus::LumaGrid;
+
+/// Samples taken along the longer side of the frame
+pub const GRID_SIDE: u32 = 64;
+
+/// Brightest sample a black frame may have (limited-range black is 16)
+const BLACK_MAX_LUMA: u8 = 24;
+
+/// Largest difference between samples of a black frame
+const BLACK_MAX_SPREAD: u8 = 4;
+
+/// How long the stream has to stay black before the hint is shown
+const MIN_BLACK_DURATION: Duration = Duration::from_secs(3);
+
+/// Black frames that have to be sampled before the hint is shown
+const MIN_BLACK_FRAMES: u32 = 10;
+
+/// Whether all samples are near zero and flat
+pub fn is_black(grid: &LumaGrid) -> bool {
+    let (Some(&min), Some(&max)) = (grid.samples.iter().min(), grid.samples.iter().max()) else {
+        return false;
+    };
+    max <= BLACK_MAX_LUMA && max - min <= BLACK_MAX_SPREAD
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Phase {
+    /// Every sampled frame has been black so far
+    Watching { since: Instant, frames: u32 },
+    /// Black for long enough to suggest a privacy switch
+    Suspected,
+    /// A frame had content; nothing more to check for this pipeline
+    Cleared,
+}
+
+/// Black frame tracking for the current pipeline
+#[derive(Debug, Default)]
+pub struct BlackFrameDetector {
+    /// Start of the pipeline the phase belongs to
+    session: Option<Instant>,
+    phase: Option<Phase>,
+    dismissed: bool,
+}
+
+impl BlackFrameDetector {
+    /// Whether frames of the pipeline started at `session` still need sampling
+    pub fn wants_sample(&self, session: Option<Instant>) -> bool {
+        session != self.session || self.phase != Some(Phase::Cleared)
+    }
+
+    /// Record a sampled frame of the pipeline started at `session`
+    ///
+    /// Returns true when the stream has just become suspect.
+    pub fn observe(&mut self, session: Option<Instant>, grid: &LumaGrid, now: Instant) -> bool {
+        if session != self.session {
+            self.session = session;
+            self.phase = None;
+        }
+
+        if !is_black(grid) {
+            self.phase = Some(Phase::Cleared);
+            return false;
+        }
+        match self.phase {
+            None => {
+                self.phase = Some(Phase::Watching {
+                    since: now,
+                    frames: 1,
+                });
+                false
+            }
+            Some(Phase::Watching { since, frames }) => {
+                let frames = frames + 1;
+                if frames >= MIN_BLACK_FRAMES && now.duration_since(since) >= MIN_BLACK_DURATION {
+                    self.phase = Some(Phase::Suspected);
+                    true
+                } else {
+                    self.phase = Some(Phase::Watching { since, frames });
+                    false
+                }
+            }
+            Some(Phase::Suspected) | Some(Phase::Cleared) => false,
+        }
+    }
+
+    /// Whether the stream looks disabled and the hint wasn't dismissed
+    pub fn show_hint(&self) -> bool {
+        self.phase == Some(Phase::Suspected) && !self.dismissed
+    }
+
+    /// Hide the hint until the app is restarted
+    pub fn dismiss(&mut self) {
+        self.dismissed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(samples: Vec<u8>) -> LumaGrid {
+        LumaGrid {
+            width: samples.len(),
+            height: 1,
+            samples,
+        }
+    }
+
+    fn black() -> LumaGrid {
+        grid(vec![16; 64])
+    }
+
+    #[test]
+    fn test_dark_scenes_are_not_black() {
+        assert!(is_black(&black()));
+        assert!(is_black(&grid(vec![0; 64])));
+        // Sensor noise in a dark room
+        let noisy: Vec<u8> = (0..64).map(|i| 12 + (i * 7 % 11) as u8).collect();
+        assert!(!is_black(&grid(noisy)));
+        // A single light in an otherwise black frame
+        let mut lamp = vec![16; 64];
+        lamp[20] = 200;
+        assert!(!is_black(&grid(lamp)));
+    }
+
+    #[test]
+    fn test_suspected_after_persistent_black() {
+        let session = Some(Instant::now());
+        let start = Instant::now();
+        let mut detector = BlackFrameDetector::default();
+        for i in 0..MIN_BLACK_FRAMES {
+            let now = start + Duration::from_millis(100 * i as u64);
+            assert!(!detector.observe(session, &black(), now));
+        }
+        // Enough frames, but not for long enough yet
+        assert!(!detector.show_hint());
+        assert!(detector.observe(session, &black(), start + MIN_BLACK_DURATION));
+        assert!(detector.show_hint());
+
+        detector.dismiss();
+        assert!(!detector.show_hint());
+    }
+
+    #[test]
+    fn test_content_clears_until_next_pipeline() {
+        let start = Instant::now();
+        let session = Some(start);
+        let mut detector = BlackFrameDetector::default();
+        detector.observe(session, &black(), start);
+        detector.observe(session, &grid(vec![128; 64]), start);
+        assert!(!detector.wants_sample(session));
+        for i in 1..=MIN_BLACK_FRAMES * 2 {
+            detector.observe(session, &black(), start + Duration::from_secs(i as u64));
+        }
+        assert!(!detector.show_hint());
+
+        // A new pipeline starts watching again
+        let restarted = Some(start + Duration::from_secs(60));
+        assert!(detector.wants_sample(restarted));
+        detector.observe(restarted, &black(), start);
+        assert!(detector.wants_sample(restarted));
+    }
+}
@@ -4,8 +4,11 @@
 
 use crate::app::format_picker::preferences as format_selection;
 use crate::app::state::{AppModel, CameraMode};
-use crate::backends::camera::types::{CameraFormat, Framerate};
+use crate::backends::camera::types::{
+    CameraDevice, CameraFacing, CameraFormat, CameraSourceKind, Framerate, SensorRotation,
+};
 use cosmic::cosmic_config::CosmicConfigEntry;
+use std::collections::HashMap;
 use tracing::{error, info};
 
 /// Helper to compare Framerate with config's u32 framerate
@@ -18,7 +21,67 @@ fn framerate_matches_config(framerate: Option<&Framerate>, config_fps: Option<u3
     }
 }
 
+/// Switch cameras offered through both libcamera and V4L2 to the stack the
+/// user chose for them
+pub(crate) fn apply_source_overrides(
+    cameras: Vec<CameraDevice>,
+    overrides: &HashMap<String, CameraSourceKind>,
+) -> Vec<CameraDevice> {
+    cameras
+        .into_iter()
+        .map(|camera| match overrides.get(camera.source_key()) {
+            Some(&kind) => camera.through(kind),
+            None => camera,
+        })
+        .collect()
+}
+
 impl AppModel {
+    /// Sensor rotation detected for the current camera
+    pub fn detected_camera_rotation(&self) -> SensorRotation {
+        self.available_cameras
+            .get(self.current_camera_index)
+            .map(|c| c.rotation)
+            .unwrap_or_default()
+    }
+
+    /// Rotation applied to the current camera's preview and captures
+    ///
+    /// A manual override wins outright; otherwise the detected sensor rotation
+    /// is combined with the device orientation reported by the accelerometer.
+    pub fn current_camera_rotation(&self) -> SensorRotation {
+        match self.config.rotation_override.rotation() {
+            Some(rotation) => rotation,
+            None => self
+                .detected_camera_rotation()
+                .rotated_by(self.device_orientation),
+        }
+    }
+
+    /// Which way the current camera faces (a user override wins over detection)
+    pub fn current_camera_facing(&self) -> CameraFacing {
+        let Some(camera) = self.available_cameras.get(self.current_camera_index) else {
+            return CameraFacing::Unknown;
+        };
+        self.config
+            .camera_facing_overrides
+            .get(&camera.path)
+            .copied()
+            .unwrap_or(camera.facing)
+    }
+
+    /// Whether the preview is mirrored
+    ///
+    /// Precedence: file sources are never mirrored, rear-facing cameras are
+    /// never mirrored, and every other camera follows the mirror setting.
+    /// Only the preview is flipped; photos and recordings are saved as the
+    /// camera sees the scene.
+    pub fn preview_mirrored(&self) -> bool {
+        !self.current_frame_is_file_source
+            && self.config.mirror_preview
+            && self.current_camera_facing().mirrors_preview()
+    }
+
     /// Start a blur transition, capturing the current frame rotation for use during blur.
     /// This ensures the blurred frame uses the rotation of the camera that produced it,
     /// not the rotation of the camera being switched to.
@@ -54,6 +117,9 @@ impl AppModel {
             metadata_path: camera.metadata_path.clone(),
             device_info: camera.device_info.clone(),
             rotation: camera.rotation,
+            facing: camera.facing,
+            source_kind: camera.source_kind,
+            alternate_source: camera.alternate_source.clone(),
         };
         let formats_for_new_mode = backend.get_formats(&device, new_mode == CameraMode::Video);
 
@@ -88,7 +154,12 @@ impl AppModel {
             CameraMode::Video => {
                 // Video mode: saved settings > optimal video defaults
                 check_saved_settings(&self.config.video_settings).or_else(|| {
-                    format_selection::select_first_time_video_format(&formats_for_new_mode)
+                    format_selection::select_first_time_video_format(
+                        &format_selection::formats_within_height(
+                            &formats_for_new_mode,
+                            self.config.preview_resolution_cap.max_height(),
+                        ),
+                    )
                 })
             }
         };
@@ -225,7 +296,12 @@ impl AppModel {
         self.restore_video_format_from_settings(camera_path)
             .or_else(|| {
                 info!("First-time video mode: selecting highest resolution with >= 25 fps, prefer up to 60 fps");
-                format_selection::select_first_time_video_format(&self.available_formats)
+                // Never default to a mode above the preview cap
+                let candidates = format_selection::formats_within_height(
+                    &self.available_formats,
+                    self.config.preview_resolution_cap.max_height(),
+                );
+                format_selection::select_first_time_video_format(&candidates)
             })
     }
 
@@ -269,8 +345,13 @@ impl AppModel {
             metadata_path: camera.metadata_path.clone(),
             device_info: camera.device_info.clone(),
             rotation: camera.rotation,
+            facing: camera.facing,
+            source_kind: camera.source_kind,
+            alternate_source: camera.alternate_source.clone(),
         };
-        self.available_formats = backend.get_formats(&device, mode == CameraMode::Video);
+        let capabilities = backend.get_capabilities(&device, mode == CameraMode::Video);
+        self.available_formats = capabilities.formats;
+        self.device_controls = capabilities.controls;
 
         // Format selection logic: both modes use saved settings, current format, or defaults
         // Virtual mode uses the same format selection as Photo mode
@@ -279,6 +360,9 @@ impl AppModel {
             CameraMode::Video => self.select_video_format(&camera_path),
         };
 
+        self.sync_preview_resolution_cap(mode);
+        self.sync_mjpeg_resilience();
+
         // Update all dropdown options
         self.update_all_dropdowns();
 
@@ -287,6 +371,32 @@ impl AppModel {
         self.save_settings();
     }
 
+    /// Apply the preview resolution cap for `mode`
+    ///
+    /// Photos and the virtual camera are taken from preview frames, so those
+    /// modes always run at the native resolution. Rebuilds the preview when
+    /// the effective cap changes.
+    pub fn sync_preview_resolution_cap(&mut self, mode: CameraMode) {
+        let max_height = match mode {
+            CameraMode::Video => self.config.preview_resolution_cap.max_height(),
+            CameraMode::Photo | CameraMode::Virtual => None,
+        };
+        if crate::media::decoders::preview_max_height() != max_height {
+            info!(?max_height, ?mode, "Preview resolution cap changed");
+            crate::media::decoders::set_preview_max_height(max_height);
+            self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+        }
+    }
+
+    /// Apply the current camera's MJPEG error resilience setting to new pipelines
+    pub fn sync_mjpeg_resilience(&mut self) {
+        let enabled = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .is_some_and(|camera| self.config.mjpeg_resilience_cameras.contains(&camera.path));
+        crate::media::decoders::set_mjpeg_resilience(enabled);
+    }
+
     /// Change to a specific format (used by consolidated mode dropdown)
     pub fn change_format(&mut self, format: crate::backends::camera::types::CameraFormat) {
         info!(format = %format, "Switched to format");
@@ -82,9 +82,8 @@ impl AppModel {
                 }
                 crate::app::state::CameraMode::Video => crate::app::state::FilterType::Standard,
             };
-            // File sources should never be mirrored - they display content as-is
-            // Use the flag that tracks if the current frame is actually from a file source
-            let should_mirror = self.config.mirror_preview && !self.current_frame_is_file_source;
+            // File sources and rear cameras are never mirrored
+            let should_mirror = self.preview_mirrored();
 
             // Use the rotation stored with the current frame
             // This ensures correct rotation during camera switch blur transitions
@@ -119,6 +118,14 @@ impl AppModel {
                     crop_uv,
                     zoom_level,
                     scroll_zoom_enabled,
+                    gray_mapping: crate::app::video_primitive::GrayMapping {
+                        auto_stretch: self.config.gray16_auto_stretch,
+                        false_color: self.config.false_color_lut,
+                    },
+                    color_effect: self.config.color_effect,
+                    focus_peaking: self.config.focus_peaking,
+                    scaling: self.config.scaling_quality,
+                    pixel_probe: self.pixel_probe.enabled,
                 },
             );
 
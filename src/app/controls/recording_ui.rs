@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-//! Recording and streaming UI components (indicator and timer)
+//! Recording and streaming UI components (indicator, timer and summary)
 
 use crate::app::state::{AppModel, CameraMode, FileSource, Message};
 use crate::app::view::overlay_container_style;
@@ -90,6 +90,86 @@ impl AppModel {
         )
     }
 
+    /// Build the summary card of the recording that just ended
+    ///
+    /// Lists what was recorded, or why the file is incomplete, with actions to
+    /// show the file and dismiss the card. Returns None when there is no summary.
+    pub fn build_recording_summary<'a>(&self) -> Option<Element<'a, Message>> {
+        let summary = self.recording_summary.as_ref()?;
+        let spacing = cosmic::theme::spacing();
+
+        let title = if summary.error.is_some() {
+            fl!("recording-summary-incomplete")
+        } else {
+            fl!("recording-summary-saved")
+        };
+        let line = |label: String, value: String| {
+            widget::row()
+                .push(widget::text(label).size(12).width(Length::Fixed(110.0)))
+                .push(widget::text(value).size(12))
+        };
+        let mut column = widget::column()
+            .push(widget::text(title).size(14).font(cosmic::font::bold()))
+            .push(line(
+                fl!("recording-summary-duration"),
+                format_duration(summary.duration.as_secs()),
+            ))
+            .push(line(
+                fl!("recording-summary-size"),
+                format!("{:.1} MB", summary.file_size as f64 / (1024.0 * 1024.0)),
+            ))
+            .push(line(
+                fl!("recording-summary-resolution"),
+                format!("{}x{}", summary.width, summary.height),
+            ))
+            .push(line(
+                fl!("recording-summary-encoder"),
+                summary.encoder.clone(),
+            ));
+        if summary.segments.len() > 1 {
+            column = column.push(line(
+                fl!("recording-summary-segments"),
+                summary.segments.len().to_string(),
+            ));
+        }
+        if let Some(kbps) = summary.average_bitrate_kbps() {
+            column = column.push(line(
+                fl!("recording-summary-bitrate"),
+                format!("{:.0} kbit/s", kbps),
+            ));
+        }
+        column = column.push(line(
+            fl!("recording-summary-dropped"),
+            summary.dropped_frames.to_string(),
+        ));
+        if let Some(error) = &summary.error {
+            column = column.push(widget::text(error.clone()).size(12));
+        }
+
+        let actions = widget::row()
+            .push(
+                widget::button::standard(fl!("recording-summary-reveal"))
+                    .on_press_maybe((summary.file_size > 0).then_some(Message::RevealRecording)),
+            )
+            .push(
+                widget::button::standard(fl!("recording-summary-dismiss"))
+                    .on_press(Message::DismissRecordingSummary),
+            )
+            .spacing(spacing.space_xs);
+
+        Some(
+            widget::container(
+                column
+                    .push(actions)
+                    .spacing(spacing.space_xxs)
+                    .width(Length::Shrink),
+            )
+            .padding(spacing.space_s)
+            .style(overlay_container_style)
+            .into(),
+        )
+    }
+
     /// Build a full-width video progress bar for video file streaming
     ///
     /// Shows a slider-style progress bar with current time and duration labels,
@@ -14,15 +14,15 @@ pub mod view;
 
 pub use types::{
     AvailableExposureControls, ColorSettings, ControlRange, ExposureMode, ExposureSettings,
-    MeteringMode,
+    MeteringMode, NudgeTarget, PowerLineFrequency,
 };
 
-use crate::backends::camera::v4l2_controls::{self, ControlInfo};
+use crate::backends::camera::v4l2_controls::{self, ControlInfo, DeviceControls};
 use tracing::{debug, info};
 
 /// Query a range control and return ControlRange if available
-fn query_range_control(device_path: &str, control_id: u32) -> ControlRange {
-    if let Some(info) = v4l2_controls::query_control(device_path, control_id)
+fn query_range_control(probe: &DeviceControls, control_id: u32) -> ControlRange {
+    if let Some(info) = probe.get(control_id)
         && !info.is_disabled()
     {
         return ControlRange::new(info.minimum, info.maximum, info.step, info.default_value);
@@ -31,24 +31,37 @@ fn query_range_control(device_path: &str, control_id: u32) -> ControlRange {
 }
 
 /// Query a boolean control and return if it's available
-fn query_bool_control(device_path: &str, control_id: u32) -> bool {
-    v4l2_controls::query_control(device_path, control_id)
-        .map(|info| !info.is_disabled())
-        .unwrap_or(false)
+fn query_bool_control(probe: &DeviceControls, control_id: u32) -> bool {
+    probe
+        .get(control_id)
+        .is_some_and(|info| !info.is_disabled())
 }
 
 /// Query all available exposure controls for a camera device
-pub fn query_exposure_controls(device_path: &str) -> AvailableExposureControls {
+///
+/// Ranges come from `probe`, the snapshot taken when the camera's formats
+/// were enumerated. The device is probed here only if that snapshot is empty.
+pub fn query_exposure_controls(
+    device_path: &str,
+    probe: &DeviceControls,
+) -> AvailableExposureControls {
     info!(device_path, "Querying exposure controls");
 
+    let fresh_probe;
+    let probe = if probe.is_empty() {
+        fresh_probe = v4l2_controls::probe_controls(device_path);
+        &fresh_probe
+    } else {
+        probe
+    };
+
     let mut controls = AvailableExposureControls {
         device_path: Some(device_path.to_string()),
         ..Default::default()
     };
 
     // Query exposure auto mode
-    if let Some(info) =
-        v4l2_controls::query_control(device_path, v4l2_controls::V4L2_CID_EXPOSURE_AUTO)
+    if let Some(info) = probe.get(v4l2_controls::V4L2_CID_EXPOSURE_AUTO)
         && !info.is_disabled()
     {
         controls.has_exposure_auto = true;
@@ -57,15 +70,13 @@ pub fn query_exposure_controls(device_path: &str) -> AvailableExposureControls {
     }
 
     // Query exposure compensation (EV bias)
-    controls.exposure_bias =
-        query_range_control(device_path, v4l2_controls::V4L2_CID_AUTO_EXPOSURE_BIAS);
+    controls.exposure_bias = query_range_control(probe, v4l2_controls::V4L2_CID_AUTO_EXPOSURE_BIAS);
     if controls.exposure_bias.available {
         debug!(device_path, range = ?controls.exposure_bias, "Exposure bias available");
     }
 
     // Query absolute exposure time
-    controls.exposure_time =
-        query_range_control(device_path, v4l2_controls::V4L2_CID_EXPOSURE_ABSOLUTE);
+    controls.exposure_time = query_range_control(probe, v4l2_controls::V4L2_CID_EXPOSURE_ABSOLUTE);
     if controls.exposure_time.available {
         debug!(device_path, range = ?controls.exposure_time, "Exposure time available");
     }
@@ -75,7 +86,7 @@ pub fn query_exposure_controls(device_path: &str) -> AvailableExposureControls {
         v4l2_controls::V4L2_CID_GAIN,
         v4l2_controls::V4L2_CID_ANALOGUE_GAIN,
     ] {
-        controls.gain = query_range_control(device_path, gain_id);
+        controls.gain = query_range_control(probe, gain_id);
         if controls.gain.available {
             debug!(device_path, control_id = gain_id, range = ?controls.gain, "Gain available");
             break;
@@ -83,14 +94,13 @@ pub fn query_exposure_controls(device_path: &str) -> AvailableExposureControls {
     }
 
     // Query ISO sensitivity
-    controls.iso = query_range_control(device_path, v4l2_controls::V4L2_CID_ISO_SENSITIVITY);
+    controls.iso = query_range_control(probe, v4l2_controls::V4L2_CID_ISO_SENSITIVITY);
     if controls.iso.available {
         debug!(device_path, range = ?controls.iso, "ISO sensitivity available");
     }
 
     // Query exposure metering mode
-    if let Some(info) =
-        v4l2_controls::query_control(device_path, v4l2_controls::V4L2_CID_EXPOSURE_METERING)
+    if let Some(info) = probe.get(v4l2_controls::V4L2_CID_EXPOSURE_METERING)
         && !info.is_disabled()
     {
         controls.has_metering = true;
@@ -98,42 +108,46 @@ pub fn query_exposure_controls(device_path: &str) -> AvailableExposureControls {
         debug!(device_path, modes = ?controls.metering_modes, "Metering modes available");
     }
 
+    // Query power line frequency (anti-flicker)
+    if let Some(info) = probe.get(v4l2_controls::V4L2_CID_POWER_LINE_FREQUENCY)
+        && !info.is_disabled()
+    {
+        controls.has_power_line_frequency = true;
+        controls.power_line_frequencies = query_power_line_frequencies(device_path, &info);
+        debug!(device_path, frequencies = ?controls.power_line_frequencies, "Anti-flicker available");
+    }
+
     // Query boolean controls
     controls.has_auto_priority =
-        query_bool_control(device_path, v4l2_controls::V4L2_CID_EXPOSURE_AUTO_PRIORITY);
-    controls.has_autogain = query_bool_control(device_path, v4l2_controls::V4L2_CID_AUTOGAIN);
+        query_bool_control(probe, v4l2_controls::V4L2_CID_EXPOSURE_AUTO_PRIORITY);
+    controls.has_autogain = query_bool_control(probe, v4l2_controls::V4L2_CID_AUTOGAIN);
     controls.has_white_balance_auto =
-        query_bool_control(device_path, v4l2_controls::V4L2_CID_AUTO_WHITE_BALANCE);
-    controls.has_focus_auto = query_bool_control(device_path, v4l2_controls::V4L2_CID_FOCUS_AUTO);
+        query_bool_control(probe, v4l2_controls::V4L2_CID_AUTO_WHITE_BALANCE);
+    controls.has_focus_auto = query_bool_control(probe, v4l2_controls::V4L2_CID_FOCUS_AUTO);
+    controls.has_focus_start = query_bool_control(probe, v4l2_controls::V4L2_CID_AUTO_FOCUS_START);
 
     // Query range controls
     controls.backlight_compensation =
-        query_range_control(device_path, v4l2_controls::V4L2_CID_BACKLIGHT_COMPENSATION);
-    controls.contrast = query_range_control(device_path, v4l2_controls::V4L2_CID_CONTRAST);
-    controls.saturation = query_range_control(device_path, v4l2_controls::V4L2_CID_SATURATION);
-    controls.sharpness = query_range_control(device_path, v4l2_controls::V4L2_CID_SHARPNESS);
-    controls.hue = query_range_control(device_path, v4l2_controls::V4L2_CID_HUE);
-    controls.white_balance_temperature = query_range_control(
-        device_path,
-        v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE,
-    );
-    controls.focus = query_range_control(device_path, v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE);
+        query_range_control(probe, v4l2_controls::V4L2_CID_BACKLIGHT_COMPENSATION);
+    controls.contrast = query_range_control(probe, v4l2_controls::V4L2_CID_CONTRAST);
+    controls.saturation = query_range_control(probe, v4l2_controls::V4L2_CID_SATURATION);
+    controls.sharpness = query_range_control(probe, v4l2_controls::V4L2_CID_SHARPNESS);
+    controls.hue = query_range_control(probe, v4l2_controls::V4L2_CID_HUE);
+    controls.white_balance_temperature =
+        query_range_control(probe, v4l2_controls::V4L2_CID_WHITE_BALANCE_TEMPERATURE);
+    controls.focus = query_range_control(probe, v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE);
 
     // Query privacy control (hardware privacy switch)
-    controls.has_privacy = query_bool_control(device_path, v4l2_controls::V4L2_CID_PRIVACY);
+    controls.has_privacy = query_bool_control(probe, v4l2_controls::V4L2_CID_PRIVACY);
 
     // Query PTZ (pan/tilt/zoom) controls
-    controls.pan_absolute = query_range_control(device_path, v4l2_controls::V4L2_CID_PAN_ABSOLUTE);
-    controls.tilt_absolute =
-        query_range_control(device_path, v4l2_controls::V4L2_CID_TILT_ABSOLUTE);
-    controls.zoom_absolute =
-        query_range_control(device_path, v4l2_controls::V4L2_CID_ZOOM_ABSOLUTE);
-    controls.has_pan_relative =
-        query_bool_control(device_path, v4l2_controls::V4L2_CID_PAN_RELATIVE);
-    controls.has_tilt_relative =
-        query_bool_control(device_path, v4l2_controls::V4L2_CID_TILT_RELATIVE);
-    controls.has_pan_reset = query_bool_control(device_path, v4l2_controls::V4L2_CID_PAN_RESET);
-    controls.has_tilt_reset = query_bool_control(device_path, v4l2_controls::V4L2_CID_TILT_RESET);
+    controls.pan_absolute = query_range_control(probe, v4l2_controls::V4L2_CID_PAN_ABSOLUTE);
+    controls.tilt_absolute = query_range_control(probe, v4l2_controls::V4L2_CID_TILT_ABSOLUTE);
+    controls.zoom_absolute = query_range_control(probe, v4l2_controls::V4L2_CID_ZOOM_ABSOLUTE);
+    controls.has_pan_relative = query_bool_control(probe, v4l2_controls::V4L2_CID_PAN_RELATIVE);
+    controls.has_tilt_relative = query_bool_control(probe, v4l2_controls::V4L2_CID_TILT_RELATIVE);
+    controls.has_pan_reset = query_bool_control(probe, v4l2_controls::V4L2_CID_PAN_RESET);
+    controls.has_tilt_reset = query_bool_control(probe, v4l2_controls::V4L2_CID_TILT_RESET);
 
     if controls.has_any_ptz() {
         debug!(
@@ -158,6 +172,7 @@ pub fn query_exposure_controls(device_path: &str) -> AvailableExposureControls {
         has_metering = controls.has_metering,
         has_auto_priority = controls.has_auto_priority,
         has_backlight = controls.backlight_compensation.available,
+        has_power_line = controls.has_power_line_frequency,
         has_contrast = controls.contrast.available,
         has_saturation = controls.saturation.available,
         has_sharpness = controls.sharpness.available,
@@ -214,6 +229,28 @@ fn query_metering_modes(device_path: &str, info: &ControlInfo) -> Vec<MeteringMo
     }
 }
 
+/// Query available power line frequencies from menu items
+fn query_power_line_frequencies(device_path: &str, info: &ControlInfo) -> Vec<PowerLineFrequency> {
+    let menu_items = v4l2_controls::query_menu_items(
+        device_path,
+        v4l2_controls::V4L2_CID_POWER_LINE_FREQUENCY,
+        info.maximum,
+    );
+    let frequencies: Vec<_> = menu_items
+        .iter()
+        .filter_map(|item| PowerLineFrequency::from_v4l2_value(item.index))
+        .collect();
+    if frequencies.is_empty() {
+        vec![
+            PowerLineFrequency::Disabled,
+            PowerLineFrequency::Hz50,
+            PowerLineFrequency::Hz60,
+        ]
+    } else {
+        frequencies
+    }
+}
+
 /// Helper to reset a control to its default value
 fn reset_control_to_default(
     device_path: &str,
@@ -232,10 +269,13 @@ fn reset_control_to_default(
 /// Get current exposure settings from a camera device
 ///
 /// This also sets the camera to auto exposure mode on initialization
-/// to ensure a consistent starting state.
+/// to ensure a consistent starting state, and applies `power_line_frequency`
+/// (the user's choice or the locale's mains frequency) when the device
+/// offers it. Without one the device keeps its own setting.
 pub fn get_exposure_settings(
     device_path: &str,
     available: &AvailableExposureControls,
+    power_line_frequency: Option<PowerLineFrequency>,
 ) -> ExposureSettings {
     let mut settings = ExposureSettings::default();
 
@@ -297,6 +337,23 @@ pub fn get_exposure_settings(
         settings.auto_priority = Some(value != 0);
     }
 
+    // Apply the anti-flicker setting, then read back what the device uses
+    if available.has_power_line_frequency {
+        if let Some(frequency) = power_line_frequency
+            && available.power_line_frequencies.contains(&frequency)
+            && let Err(e) = v4l2_controls::set_control(
+                device_path,
+                v4l2_controls::V4L2_CID_POWER_LINE_FREQUENCY,
+                frequency.to_v4l2_value(),
+            )
+        {
+            tracing::warn!("Failed to set power line frequency on init: {}", e);
+        }
+        settings.power_line_frequency =
+            v4l2_controls::get_control(device_path, v4l2_controls::V4L2_CID_POWER_LINE_FREQUENCY)
+                .and_then(PowerLineFrequency::from_v4l2_value);
+    }
+
     // Reset backlight compensation to default
     settings.backlight_compensation = reset_control_to_default(
         device_path,
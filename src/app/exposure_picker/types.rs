@@ -3,9 +3,11 @@
 //! Exposure control types
 //!
 //! This module defines types for V4L2 exposure controls including
-//! exposure mode, metering mode, and exposure settings.
+//! exposure mode, metering mode, anti-flicker and exposure settings.
 
 use crate::backends::camera::v4l2_controls::{
+    V4L2_CID_POWER_LINE_FREQUENCY_50HZ, V4L2_CID_POWER_LINE_FREQUENCY_60HZ,
+    V4L2_CID_POWER_LINE_FREQUENCY_AUTO, V4L2_CID_POWER_LINE_FREQUENCY_DISABLED,
     V4L2_EXPOSURE_APERTURE_PRIORITY, V4L2_EXPOSURE_AUTO, V4L2_EXPOSURE_MANUAL,
     V4L2_EXPOSURE_METERING_AVERAGE, V4L2_EXPOSURE_METERING_CENTER_WEIGHTED,
     V4L2_EXPOSURE_METERING_MATRIX, V4L2_EXPOSURE_METERING_SPOT, V4L2_EXPOSURE_SHUTTER_PRIORITY,
@@ -106,6 +108,94 @@ impl MeteringMode {
     }
 }
 
+/// V4L2 power line frequency (anti-flicker) settings
+///
+/// Fluorescent and LED lights flicker at twice the mains frequency, which
+/// shows up as rolling bands unless the exposure time is a multiple of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PowerLineFrequency {
+    /// No anti-flicker filtering
+    Disabled,
+    /// 50 Hz mains (most of Europe, Africa, Asia and Oceania)
+    Hz50,
+    /// 60 Hz mains (most of the Americas, Korea, Taiwan, ...)
+    Hz60,
+    /// The camera detects the frequency itself
+    Auto,
+}
+
+/// Regions on 60 Hz mains, by ISO 3166 code
+///
+/// Japan is split between 50 and 60 Hz and is left out, like any region
+/// not listed here uses 50 Hz.
+const REGIONS_60HZ: &[&str] = &[
+    "AG", "AI", "AS", "AW", "BM", "BR", "BS", "BZ", "CA", "CO", "CR", "CU", "DO", "EC", "FM", "GT",
+    "GU", "HN", "HT", "KN", "KR", "KY", "LR", "MH", "MP", "MX", "NI", "PA", "PE", "PH", "PR", "PW",
+    "SA", "SR", "SV", "TT", "TW", "US", "VE", "VG", "VI",
+];
+
+impl PowerLineFrequency {
+    /// Convert to V4L2 power line frequency value
+    pub fn to_v4l2_value(self) -> i32 {
+        match self {
+            PowerLineFrequency::Disabled => V4L2_CID_POWER_LINE_FREQUENCY_DISABLED,
+            PowerLineFrequency::Hz50 => V4L2_CID_POWER_LINE_FREQUENCY_50HZ,
+            PowerLineFrequency::Hz60 => V4L2_CID_POWER_LINE_FREQUENCY_60HZ,
+            PowerLineFrequency::Auto => V4L2_CID_POWER_LINE_FREQUENCY_AUTO,
+        }
+    }
+
+    /// Convert from V4L2 power line frequency value
+    pub fn from_v4l2_value(value: i32) -> Option<Self> {
+        match value {
+            V4L2_CID_POWER_LINE_FREQUENCY_DISABLED => Some(PowerLineFrequency::Disabled),
+            V4L2_CID_POWER_LINE_FREQUENCY_50HZ => Some(PowerLineFrequency::Hz50),
+            V4L2_CID_POWER_LINE_FREQUENCY_60HZ => Some(PowerLineFrequency::Hz60),
+            V4L2_CID_POWER_LINE_FREQUENCY_AUTO => Some(PowerLineFrequency::Auto),
+            _ => None,
+        }
+    }
+
+    /// Get display name for UI
+    pub fn display_name(self) -> &'static str {
+        match self {
+            PowerLineFrequency::Disabled => "Off",
+            PowerLineFrequency::Hz50 => "50 Hz",
+            PowerLineFrequency::Hz60 => "60 Hz",
+            PowerLineFrequency::Auto => "Auto",
+        }
+    }
+
+    /// Mains frequency of the region in a POSIX locale like `en_US.UTF-8`
+    ///
+    /// `None` for locales without a region (`C`, `POSIX`, `en`) and for
+    /// Japan, where it depends on the part of the country.
+    pub fn for_locale(locale: &str) -> Option<Self> {
+        let language = locale.split(['.', '@']).next()?;
+        let (_, region) = language.split_once(['_', '-'])?;
+        if region.len() != 2 || region == "JP" {
+            return None;
+        }
+        if REGIONS_60HZ.contains(&region) {
+            Some(PowerLineFrequency::Hz60)
+        } else {
+            Some(PowerLineFrequency::Hz50)
+        }
+    }
+
+    /// Mains frequency guessed from the environment's locale
+    ///
+    /// The measurement locale is asked first, since it follows the region
+    /// even when messages are in another language.
+    pub fn from_environment() -> Option<Self> {
+        ["LC_ALL", "LC_MEASUREMENT", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Self::for_locale(&locale))
+    }
+}
+
 /// Current exposure settings for a camera
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExposureSettings {
@@ -127,6 +217,8 @@ pub struct ExposureSettings {
     pub auto_priority: Option<bool>,
     /// Backlight compensation value
     pub backlight_compensation: Option<i32>,
+    /// Power line frequency (anti-flicker) filter
+    pub power_line_frequency: Option<PowerLineFrequency>,
     /// Auto focus enabled
     pub focus_auto: Option<bool>,
     /// Manual focus position (absolute)
@@ -181,6 +273,39 @@ impl ControlRange {
     pub fn unavailable() -> Self {
         Self::default()
     }
+
+    /// Value `steps` steps away from `current`, kept within the range
+    ///
+    /// Returns `None` when the control is already at its limit in that direction.
+    pub fn nudge(&self, current: i32, steps: i32) -> Option<i32> {
+        let value = current
+            .saturating_add(steps.saturating_mul(self.step.max(1)))
+            .clamp(self.min, self.max);
+        (value != current).then_some(value)
+    }
+}
+
+/// Manual control that can be nudged from the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NudgeTarget {
+    /// Absolute exposure time
+    ExposureTime,
+    /// Sensor gain
+    Gain,
+}
+
+impl ExposureSettings {
+    /// Whether the camera is currently setting the control itself
+    pub fn is_automatic(&self, target: NudgeTarget) -> bool {
+        let auto_exposure = matches!(
+            self.mode,
+            ExposureMode::Auto | ExposureMode::AperturePriority
+        );
+        match target {
+            NudgeTarget::ExposureTime => auto_exposure,
+            NudgeTarget::Gain => auto_exposure || self.autogain == Some(true),
+        }
+    }
 }
 
 /// Describes which exposure controls are available for a camera and their ranges
@@ -221,6 +346,11 @@ pub struct AvailableExposureControls {
     // === Backlight Compensation ===
     pub backlight_compensation: ControlRange,
 
+    // === Power Line Frequency (Anti-Flicker) ===
+    pub has_power_line_frequency: bool,
+    /// Frequencies the device's menu offers
+    pub power_line_frequencies: Vec<PowerLineFrequency>,
+
     // === Contrast ===
     pub contrast: ControlRange,
 
@@ -239,6 +369,8 @@ pub struct AvailableExposureControls {
 
     // === Focus ===
     pub has_focus_auto: bool,
+    /// Whether the device supports triggering a single autofocus run
+    pub has_focus_start: bool,
     pub focus: ControlRange,
 
     // === Privacy ===
@@ -293,7 +425,7 @@ impl AvailableExposureControls {
 
     /// Check if any focus controls are available
     pub fn has_any_focus(&self) -> bool {
-        self.has_focus_auto || self.focus.available
+        self.has_focus_auto || self.has_focus_start || self.focus.available
     }
 
     /// Check if any PTZ (pan/tilt/zoom) controls are available
@@ -308,6 +440,7 @@ impl AvailableExposureControls {
     /// Check if any exposure controls are available at all
     pub fn has_any(&self) -> bool {
         self.has_any_essential()
+            || self.has_power_line_frequency
             || self.has_any_advanced()
             || self.has_any_image_controls()
             || self.has_any_white_balance()
@@ -343,6 +476,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_power_line_frequency_from_locale() {
+        for frequency in [
+            PowerLineFrequency::Disabled,
+            PowerLineFrequency::Hz50,
+            PowerLineFrequency::Hz60,
+            PowerLineFrequency::Auto,
+        ] {
+            assert_eq!(
+                PowerLineFrequency::from_v4l2_value(frequency.to_v4l2_value()),
+                Some(frequency)
+            );
+        }
+        assert_eq!(
+            PowerLineFrequency::for_locale("en_US.UTF-8"),
+            Some(PowerLineFrequency::Hz60)
+        );
+        assert_eq!(
+            PowerLineFrequency::for_locale("de_DE.UTF-8@euro"),
+            Some(PowerLineFrequency::Hz50)
+        );
+        assert_eq!(
+            PowerLineFrequency::for_locale("pt_BR"),
+            Some(PowerLineFrequency::Hz60)
+        );
+        assert_eq!(PowerLineFrequency::for_locale("ja_JP.UTF-8"), None);
+        assert_eq!(PowerLineFrequency::for_locale("C.UTF-8"), None);
+        assert_eq!(PowerLineFrequency::for_locale("en"), None);
+    }
+
     #[test]
     fn test_available_controls_checks() {
         let mut controls = AvailableExposureControls::default();
@@ -371,4 +534,31 @@ mod tests {
         let unavailable = ControlRange::unavailable();
         assert!(!unavailable.available);
     }
+
+    #[test]
+    fn test_nudge_stays_in_range() {
+        let range = ControlRange::new(3, 2047, 4, 250);
+        assert_eq!(range.nudge(250, 1), Some(254));
+        assert_eq!(range.nudge(250, -10), Some(210));
+        // Coarse steps stop at the limits, and nothing happens past them
+        assert_eq!(range.nudge(2040, 10), Some(2047));
+        assert_eq!(range.nudge(2047, 1), None);
+        assert_eq!(range.nudge(5, -1), Some(3));
+        assert_eq!(range.nudge(3, -1), None);
+    }
+
+    #[test]
+    fn test_automatic_controls() {
+        let mut settings = ExposureSettings::default();
+        assert!(settings.is_automatic(NudgeTarget::ExposureTime));
+        assert!(settings.is_automatic(NudgeTarget::Gain));
+
+        settings.mode = ExposureMode::Manual;
+        assert!(!settings.is_automatic(NudgeTarget::ExposureTime));
+        assert!(!settings.is_automatic(NudgeTarget::Gain));
+
+        settings.autogain = Some(true);
+        assert!(settings.is_automatic(NudgeTarget::Gain));
+        assert!(!settings.is_automatic(NudgeTarget::ExposureTime));
+    }
 }
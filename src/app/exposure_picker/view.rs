@@ -64,6 +64,22 @@ impl AppModel {
             column = self.add_auto_controls(column, settings_data);
         }
 
+        // Anti-flicker is independent of exposure mode too, and the usual fix
+        // for banding under artificial light, so it stays in view
+        if self.available_exposure_controls.has_power_line_frequency
+            && !self
+                .available_exposure_controls
+                .power_line_frequencies
+                .is_empty()
+        {
+            column = column.push(self.build_power_line_row(settings_data));
+        }
+
+        // Focus controls are independent of exposure mode (hidden when unsupported)
+        if self.available_exposure_controls.has_any_focus() {
+            column = self.add_focus_controls(column, settings_data);
+        }
+
         // Build picker panel with semi-transparent themed background
         let picker_panel = widget::mouse_area(
             widget::container(column)
@@ -178,6 +194,102 @@ impl AppModel {
         column
     }
 
+    /// Add focus controls (continuous AF toggle, manual focus slider, one-shot AF)
+    fn add_focus_controls<'a>(
+        &'a self,
+        mut column: widget::Column<'a, Message>,
+        settings_data: Option<&'a super::ExposureSettings>,
+    ) -> widget::Column<'a, Message> {
+        let controls = &self.available_exposure_controls;
+        let continuous = settings_data.and_then(|s| s.focus_auto).unwrap_or(false);
+
+        if controls.has_focus_auto {
+            column = column.push(
+                widget::row()
+                    .push(
+                        widget::text(fl!("focus-autofocus"))
+                            .size(13)
+                            .width(Length::Fixed(LABEL_WIDTH)),
+                    )
+                    .push(widget::toggler(continuous).on_toggle(Message::SetContinuousAutofocus))
+                    .push(
+                        widget::text(if continuous {
+                            fl!("focus-continuous")
+                        } else {
+                            fl!("color-manual")
+                        })
+                        .size(12),
+                    )
+                    .spacing(CONTROL_SPACING)
+                    .align_y(Alignment::Center)
+                    .width(Length::Shrink),
+            );
+        }
+
+        if controls.focus.available {
+            column = column.push(self.build_focus_row(settings_data, continuous));
+        }
+
+        if controls.has_focus_start || controls.has_focus_auto {
+            // One-shot AF is meaningless while continuous AF is running
+            let button = widget::button::text(fl!("focus-one-shot"))
+                .on_press_maybe((!continuous).then_some(Message::TriggerAutofocus))
+                .class(cosmic::theme::Button::Standard);
+            column = column.push(
+                widget::row()
+                    .push(widget::Space::new(
+                        Length::Fixed(LABEL_WIDTH),
+                        Length::Shrink,
+                    ))
+                    .push(button)
+                    .spacing(CONTROL_SPACING)
+                    .align_y(Alignment::Center)
+                    .width(Length::Shrink),
+            );
+        }
+
+        column
+    }
+
+    /// Build manual focus row
+    ///
+    /// While continuous autofocus runs the lens position is shown read-only,
+    /// as a bar that can't be dragged.
+    fn build_focus_row(
+        &self,
+        settings_data: Option<&super::ExposureSettings>,
+        continuous: bool,
+    ) -> Element<'_, Message> {
+        let range = &self.available_exposure_controls.focus;
+        let current = settings_data
+            .and_then(|s| s.focus_absolute)
+            .unwrap_or(range.default);
+
+        if continuous {
+            let min = range.min as f32;
+            let max = range.max as f32;
+            let bar = widget::progress_bar(min..=max, (current as f32).clamp(min, max))
+                .width(Length::Fixed(SLIDER_WIDTH_EXPOSURE))
+                .height(Length::Fixed(4.0));
+            return Self::build_control_row(
+                fl!("focus-position"),
+                format!("{}", current),
+                VALUE_WIDTH_EXPOSURE,
+                bar,
+            );
+        }
+
+        Self::build_slider_row(
+            fl!("focus-position"),
+            current,
+            range,
+            SLIDER_WIDTH_EXPOSURE,
+            VALUE_WIDTH_EXPOSURE,
+            |v| format!("{}", v),
+            Message::SetFocusAbsolute,
+        )
+    }
+
     // =========================================================================
     // Control row builders
     // =========================================================================
@@ -390,6 +502,39 @@ impl AppModel {
         row.into()
     }
 
+    /// Build power line frequency (anti-flicker) row
+    fn build_power_line_row(
+        &self,
+        settings_data: Option<&super::ExposureSettings>,
+    ) -> Element<'_, Message> {
+        let controls = &self.available_exposure_controls;
+        let current = settings_data.and_then(|s| s.power_line_frequency);
+
+        let mut row = widget::row()
+            .push(
+                widget::text(fl!("exposure-anti-flicker"))
+                    .size(13)
+                    .width(Length::Fixed(LABEL_WIDTH)),
+            )
+            .spacing(4)
+            .align_y(Alignment::Center)
+            .width(Length::Shrink);
+
+        for frequency in &controls.power_line_frequencies {
+            let btn = widget::button::text(frequency.display_name())
+                .on_press(Message::SetPowerLineFrequency(*frequency))
+                .class(if current == Some(*frequency) {
+                    cosmic::theme::Button::Suggested
+                } else {
+                    cosmic::theme::Button::Text
+                });
+
+            row = row.push(btn);
+        }
+
+        row.into()
+    }
+
     /// Build auto priority row (frame rate variation toggle)
     fn build_auto_priority_row(
         &self,
@@ -611,7 +756,7 @@ impl AppModel {
 }
 
 /// Format exposure time in 100µs units for display
-fn format_exposure_time(time_100us: i32) -> String {
+pub(crate) fn format_exposure_time(time_100us: i32) -> String {
     let seconds = time_100us as f64 / 10000.0;
     if seconds < 0.5 {
         let denominator = (1.0 / seconds).round() as i32;
@@ -67,11 +67,7 @@ impl AppModel {
                 // Use video widget with the specific filter type
                 // The video widget fills its container and handles aspect ratio via Cover mode
                 // Get rotation from current camera
-                let rotation = self
-                    .available_cameras
-                    .get(self.current_camera_index)
-                    .map(|c| c.rotation.gpu_rotation_code())
-                    .unwrap_or(0);
+                let rotation = self.current_camera_rotation().gpu_rotation_code();
 
                 video_widget::video_widget(
                     Arc::clone(frame),
@@ -80,11 +76,19 @@ impl AppModel {
                         content_fit: VideoContentFit::Cover,
                         filter_type,
                         corner_radius,
-                        mirror_horizontal: self.config.mirror_preview,
+                        mirror_horizontal: self.preview_mirrored(),
                         rotation,
                         crop_uv: None,   // No aspect ratio cropping in filter previews
                         zoom_level: 1.0, // No zoom for filter previews
                         scroll_zoom_enabled: false, // No scroll zoom for filter previews
+                        gray_mapping: crate::app::video_primitive::GrayMapping {
+                            auto_stretch: self.config.gray16_auto_stretch,
+                            false_color: self.config.false_color_lut,
+                        },
+                        color_effect: self.config.color_effect,
+                        focus_peaking: false, // Thumbnails are too small to judge focus
+                        pixel_probe: false,
+                        scaling: crate::config::ScalingQuality::Linear, // Always downscaled
                     },
                 )
             } else {
@@ -185,6 +185,29 @@ pub fn select_first_time_video_format(formats: &[CameraFormat]) -> Option<Camera
     best_format.or_else(|| formats.first().cloned())
 }
 
+/// Restrict formats to those no taller than `max_height`
+///
+/// Returns all formats when there is no cap or nothing fits under it, so a
+/// camera that only offers large modes still gets one (downscaled later).
+pub fn formats_within_height(
+    formats: &[CameraFormat],
+    max_height: Option<u32>,
+) -> Vec<CameraFormat> {
+    let Some(max_height) = max_height else {
+        return formats.to_vec();
+    };
+    let within: Vec<CameraFormat> = formats
+        .iter()
+        .filter(|f| f.height <= max_height)
+        .cloned()
+        .collect();
+    if within.is_empty() {
+        formats.to_vec()
+    } else {
+        within
+    }
+}
+
 /// Find a format matching specific criteria
 pub fn find_format_with_criteria<F>(formats: &[CameraFormat], filter: F) -> Option<CameraFormat>
 where
@@ -193,6 +216,71 @@ where
     formats.iter().find(|f| filter(f)).cloned()
 }
 
+/// Closest format to `active` whose pixel format `can_decode` accepts
+///
+/// For a camera whose selected codec can't be decoded: the same resolution
+/// in another pixel format comes first, then the largest resolution, each
+/// at the frame rate closest to `active`'s.
+pub fn decodable_alternative<F>(
+    formats: &[CameraFormat],
+    active: &CameraFormat,
+    can_decode: F,
+) -> Option<CameraFormat>
+where
+    F: Fn(&str) -> bool,
+{
+    let pixels = |f: &CameraFormat| u64::from(f.width) * u64::from(f.height);
+    let fps = |f: &CameraFormat| f.framerate.map_or(0.0, |rate| rate.as_f64());
+    formats
+        .iter()
+        .filter(|f| f.pixel_format != active.pixel_format && can_decode(&f.pixel_format))
+        .max_by(|a, b| {
+            (pixels(a) == pixels(active))
+                .cmp(&(pixels(b) == pixels(active)))
+                .then(pixels(a).cmp(&pixels(b)))
+                .then(
+                    (fps(b) - fps(active))
+                        .abs()
+                        .total_cmp(&(fps(a) - fps(active)).abs()),
+                )
+        })
+        .cloned()
+}
+
+/// Resolution after `current` among those offered in `pixel_format`
+///
+/// Resolutions are ordered by pixel count, ascending, and wrap around after
+/// the largest. Returns `None` when the format offers fewer than two.
+pub fn next_resolution(
+    formats: &[CameraFormat],
+    pixel_format: &str,
+    current: (u32, u32),
+) -> Option<(u32, u32)> {
+    let mut resolutions: Vec<(u32, u32)> = formats
+        .iter()
+        .filter(|f| f.pixel_format == pixel_format)
+        .map(|f| (f.width, f.height))
+        .collect();
+    resolutions.sort_by_key(|&(width, height)| (u64::from(width) * u64::from(height), width));
+    resolutions.dedup();
+    if resolutions.len() < 2 {
+        return None;
+    }
+    // A current resolution not in the list continues from the next larger one
+    let next = resolutions
+        .iter()
+        .position(|&resolution| resolution == current)
+        .map(|index| index + 1)
+        .unwrap_or_else(|| {
+            let pixels = u64::from(current.0) * u64::from(current.1);
+            resolutions
+                .iter()
+                .position(|&(width, height)| u64::from(width) * u64::from(height) > pixels)
+                .unwrap_or(0)
+        });
+    Some(resolutions[next % resolutions.len()])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -229,6 +317,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decodable_alternative_keeps_resolution() {
+        let formats = vec![
+            create_test_format(3840, 2160, "H265", true),
+            create_test_format(1920, 1080, "H265", true),
+            create_test_format_with_fps(1920, 1080, "MJPG", false, 15),
+            create_test_format(1920, 1080, "MJPG", false),
+            create_test_format(3840, 2160, "H264", false),
+            create_test_format(640, 480, "YUYV", false),
+        ];
+        let can_decode = |pixel_format: &str| pixel_format != "H265" && pixel_format != "H264";
+        assert_eq!(
+            decodable_alternative(&formats, &formats[1], can_decode),
+            Some(formats[3].clone())
+        );
+        // Nothing at 4K decodes: the largest that does
+        assert_eq!(
+            decodable_alternative(&formats, &formats[0], can_decode),
+            Some(formats[3].clone())
+        );
+        assert_eq!(
+            decodable_alternative(&formats, &formats[0], |pixel_format| pixel_format == "H265"),
+            None
+        );
+    }
+
     #[test]
     fn test_is_raw_format() {
         assert!(is_raw_format("YUYV"));
@@ -429,4 +543,53 @@ mod tests {
         // Raw format (YUYV) should be preferred
         assert_eq!(selected.pixel_format, "YUYV");
     }
+
+    #[test]
+    fn test_video_format_respects_height_cap() {
+        let formats = vec![
+            create_test_format_with_fps(3840, 2160, "MJPG", true, 30),
+            create_test_format_with_fps(1920, 1080, "MJPG", true, 60),
+            create_test_format_with_fps(1280, 720, "MJPG", true, 60),
+        ];
+
+        let capped = formats_within_height(&formats, Some(1080));
+        let selected = select_first_time_video_format(&capped).unwrap();
+        assert_eq!((selected.width, selected.height), (1920, 1080));
+
+        let uncapped = formats_within_height(&formats, None);
+        let selected = select_first_time_video_format(&uncapped).unwrap();
+        assert_eq!((selected.width, selected.height), (3840, 2160));
+    }
+
+    #[test]
+    fn test_height_cap_keeps_formats_when_none_fit() {
+        let formats = vec![create_test_format(1920, 1080, "MJPG", true)];
+        assert_eq!(formats_within_height(&formats, Some(480)).len(), 1);
+    }
+
+    #[test]
+    fn test_next_resolution_cycles_ascending() {
+        let formats = vec![
+            create_test_format_with_fps(1920, 1080, "MJPG", false, 30),
+            create_test_format_with_fps(640, 480, "MJPG", false, 30),
+            create_test_format_with_fps(640, 480, "MJPG", false, 15),
+            create_test_format_with_fps(1280, 720, "MJPG", false, 30),
+            create_test_format_with_fps(3840, 2160, "YUYV", false, 5),
+        ];
+        assert_eq!(
+            next_resolution(&formats, "MJPG", (640, 480)),
+            Some((1280, 720))
+        );
+        assert_eq!(
+            next_resolution(&formats, "MJPG", (1280, 720)),
+            Some((1920, 1080))
+        );
+        // Wraps after the largest, and never leaves the pixel format
+        assert_eq!(
+            next_resolution(&formats, "MJPG", (1920, 1080)),
+            Some((640, 480))
+        );
+        // Only one resolution: nothing to cycle through
+        assert_eq!(next_resolution(&formats, "YUYV", (3840, 2160)), None);
+    }
 }
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Skipping GPU uploads of unchanged frames
+//!
+//! Static scenes and sources that repeat frames would otherwise upload
+//! identical data on every frame. Consecutive frames are compared on a sparse
+//! sample of bytes whose positions shift by one byte per frame, so a change
+//! missed by one comparison is caught by a later one. The comparison reads a
+//! few thousand bytes, against an upload of several megabytes.
+//!
+//! A skipped frame leaves the previous frame's buffer in place, which the
+//! video primitive recognizes and doesn't upload again.
+
+use crate::app::state::{FilterType, PhotoAspectRatio};
+use crate::app::video_primitive::GrayMapping;
+use crate::backends::camera::types::{CameraFrame, SensorRotation};
+use crate::config::ColorEffect;
+
+/// Bytes compared per frame
+const SAMPLE_COUNT: usize = 4096;
+
+/// Preview state that changes the rendered image for the same source frame
+///
+/// Frames are never skipped across a change of any of these, so the new
+/// state is always drawn from a freshly uploaded frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewKey {
+    pub zoom_level: f32,
+    pub filter: FilterType,
+    pub mirror: bool,
+    pub rotation: SensorRotation,
+    pub aspect_ratio: PhotoAspectRatio,
+    pub gray_mapping: GrayMapping,
+    pub theatre: bool,
+    pub color_effect: ColorEffect,
+}
+
+/// Whether two frames have the same layout and the same bytes at the sampled positions
+///
+/// `phase` selects which positions are sampled.
+pub fn sampled_equal(a: &CameraFrame, b: &CameraFrame, phase: usize) -> bool {
+    if (a.width, a.height, a.format, a.stride) != (b.width, b.height, b.format, b.stride)
+        || a.data.len() != b.data.len()
+    {
+        return false;
+    }
+    let (a, b) = (a.data_slice(), b.data_slice());
+    let step = (a.len() / SAMPLE_COUNT).max(1);
+    (phase % step..a.len())
+        .step_by(step)
+        .all(|index| a[index] == b[index])
+}
+
+/// Identical-frame detection across consecutive preview frames
+#[derive(Debug, Default)]
+pub struct FrameDedup {
+    phase: usize,
+    preview_key: Option<PreviewKey>,
+    skipped: u64,
+}
+
+impl FrameDedup {
+    /// Whether `frame` can be dropped because `previous` already shows the same image
+    pub fn should_skip(
+        &mut self,
+        previous: Option<&CameraFrame>,
+        frame: &CameraFrame,
+        preview_key: PreviewKey,
+    ) -> bool {
+        let preview_unchanged = self.preview_key.replace(preview_key) == Some(preview_key);
+        self.phase = self.phase.wrapping_add(1);
+        let skip = preview_unchanged
+            && previous.is_some_and(|previous| sampled_equal(previous, frame, self.phase));
+        if skip {
+            self.skipped += 1;
+        }
+        skip
+    }
+
+    /// Frames skipped since the last reset
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Forget the previous preview state and the skip count
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::{FrameData, PixelFormat};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    fn frame(data: Vec<u8>) -> CameraFrame {
+        CameraFrame {
+            width: 128,
+            height: 64,
+            data: FrameData::from_bytes(Arc::from(data)),
+            format: PixelFormat::Gray8,
+            stride: 128,
+            yuv_planes: None,
+            captured_at: Instant::now(),
+            stamp: None,
+        }
+    }
+
+    fn key() -> PreviewKey {
+        PreviewKey {
+            zoom_level: 1.0,
+            filter: FilterType::default(),
+            mirror: false,
+            rotation: SensorRotation::None,
+            aspect_ratio: PhotoAspectRatio::default(),
+            gray_mapping: GrayMapping::default(),
+            theatre: false,
+            color_effect: ColorEffect::None,
+        }
+    }
+
+    #[test]
+    fn test_identical_frames_are_skipped() {
+        let a = frame(vec![7; 128 * 64]);
+        let b = frame(vec![7; 128 * 64]);
+        let mut dedup = FrameDedup::default();
+        assert!(!dedup.should_skip(None, &a, key()));
+        assert!(dedup.should_skip(Some(&a), &b, key()));
+        assert_eq!(dedup.skipped(), 1);
+    }
+
+    #[test]
+    fn test_single_byte_change_is_found_within_one_step() {
+        let a = frame(vec![0; 128 * 64]);
+        let mut changed = vec![0; 128 * 64];
+        changed[4097] = 1;
+        let b = frame(changed);
+        // 8192 bytes sampled 4096 times: every other byte, alternating by phase
+        assert!((0..2).any(|phase| !sampled_equal(&a, &b, phase)));
+    }
+
+    #[test]
+    fn test_preview_change_prevents_skip() {
+        let a = frame(vec![7; 128 * 64]);
+        let b = frame(vec![7; 128 * 64]);
+        let mut dedup = FrameDedup::default();
+        dedup.should_skip(None, &a, key());
+        let zoomed = PreviewKey {
+            zoom_level: 2.0,
+            ..key()
+        };
+        assert!(!dedup.should_skip(Some(&a), &b, zoomed));
+        assert!(dedup.should_skip(Some(&a), &b, zoomed));
+    }
+}
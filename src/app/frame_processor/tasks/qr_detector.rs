@@ -221,6 +221,21 @@ fn convert_to_gray(frame: &CameraFrame) -> (Vec<u8>, u32, u32) {
             (gray, frame.width, frame.height)
         }
 
+        // Gray16: Keep the most significant byte of each little-endian sample
+        PixelFormat::Gray16 => {
+            let mut gray = Vec::with_capacity(width * height);
+            for y in 0..height {
+                let row_start = y * stride;
+                for x in 0..width {
+                    let offset = row_start + x * 2 + 1;
+                    if offset < frame.data.len() {
+                        gray.push(frame.data[offset]);
+                    }
+                }
+            }
+            (gray, frame.width, frame.height)
+        }
+
         // RGB24: Convert RGB to grayscale (no alpha)
         PixelFormat::RGB24 => {
             let mut gray = Vec::with_capacity(width * height);
@@ -406,6 +421,7 @@ mod tests {
             stride: 8, // 2 pixels * 4 bytes = 8 bytes per row
             yuv_planes: None,
             captured_at: std::time::Instant::now(),
+            stamp: None,
         };
 
         let (gray, w, h) = convert_to_gray(&frame);
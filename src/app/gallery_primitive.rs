@@ -3,6 +3,7 @@
 //! Custom primitive for rendering gallery thumbnails with rounded corners
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use cosmic::iced::Rectangle;
 use cosmic::iced_core::image::Id as ImageId;
@@ -10,6 +11,14 @@ use cosmic::iced_wgpu::graphics::Viewport;
 use cosmic::iced_wgpu::primitive::{self, Primitive as PrimitiveTrait};
 use cosmic::iced_wgpu::wgpu;
 
+/// GPU memory held by the gallery thumbnail texture in bytes
+static TEXTURE_MEMORY: AtomicU64 = AtomicU64::new(0);
+
+/// Get the GPU memory held by the gallery thumbnail texture in bytes
+pub fn get_texture_memory() -> u64 {
+    TEXTURE_MEMORY.load(Ordering::Relaxed)
+}
+
 /// Custom primitive for gallery thumbnail with rounded corners
 #[derive(Debug, Clone)]
 pub struct GalleryPrimitive {
@@ -225,6 +234,7 @@ impl GalleryPipeline {
         // Clear stale textures - since there's only one gallery thumbnail,
         // remove any old textures before uploading the new one
         self.texture_cache.clear();
+        TEXTURE_MEMORY.store(0, Ordering::Relaxed);
 
         // Use the provided RGBA data directly
         if rgba_data.is_empty() {
@@ -284,6 +294,10 @@ impl GalleryPipeline {
         );
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        TEXTURE_MEMORY.store(
+            super::video_primitive::texture_bytes(&texture),
+            Ordering::Relaxed,
+        );
 
         // Create viewport params buffer (will be updated during render)
         // Contains: size (2 floats), corner_radius (1 float), padding (1 float)
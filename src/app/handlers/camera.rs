@@ -9,7 +9,7 @@ use crate::app::state::{AppModel, CameraMode, Message, PhotoAspectRatio, Virtual
 use crate::backends::camera::v4l2_controls;
 use cosmic::Task;
 use std::sync::Arc;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 impl AppModel {
     // =========================================================================
@@ -66,6 +66,7 @@ impl AppModel {
 
             self.current_camera_index = index;
             self.zoom_level = 1.0; // Reset zoom when switching cameras
+            self.insights.focus.reset();
             // Reset aspect ratio to native when switching cameras
             self.photo_aspect_ratio = crate::app::state::PhotoAspectRatio::Native;
             self.switch_camera_or_mode(index, self.mode);
@@ -103,6 +104,38 @@ impl AppModel {
             return Task::none();
         }
 
+        // A recording or virtual camera started in the background lifts the throttle
+        if !self.window_focused {
+            self.sync_preview_throttle();
+        }
+
+        if crate::events::hub().has_subscribers() {
+            let previous = self
+                .current_frame
+                .as_deref()
+                .map(crate::events::FrameInfo::from);
+            crate::events::emit_frame(previous, frame.as_ref().into());
+        }
+
+        // Log format and decoder changes to the insights history; decoder
+        // changes that keep the frame format are caught by the periodic check
+        let format_changed = self.current_frame.as_deref().is_none_or(|current| {
+            crate::events::FrameInfo::from(current)
+                != crate::events::FrameInfo::from(frame.as_ref())
+        });
+        if format_changed || count.is_multiple_of(30) {
+            self.observe_stream_state(&frame);
+        }
+
+        // Focus score for the insights drawer (decimated, every few frames)
+        if count.is_multiple_of(6)
+            && self.context_page == crate::app::state::ContextPage::Insights
+            && self.core.window.show_context
+            && let Some(score) = crate::app::insights::focus::focus_score(&frame)
+        {
+            self.insights.focus.record(score);
+        }
+
         // Send frame to virtual camera if streaming from camera (not file source)
         if self.virtual_camera.is_streaming()
             && !self.virtual_camera.is_file_source()
@@ -114,14 +147,69 @@ impl AppModel {
         // Track whether this frame is from a file source (for mirror handling)
         let is_file_source = self.virtual_camera.is_file_source();
 
+        // The mode works: remember it as the one to fall back to on this camera
+        if !is_file_source
+            && let (Some(camera), Some(active)) = (
+                self.available_cameras.get(self.current_camera_index),
+                self.active_format.as_ref(),
+            )
+            && self.safe_resolution.record_success(&camera.path, active)
+        {
+            let settings = crate::config::FormatSettings {
+                width: active.width,
+                height: active.height,
+                framerate: active.framerate.map(|fps| fps.as_int()),
+                pixel_format: active.pixel_format.clone(),
+            };
+            if self.config.last_good_formats.get(&camera.path) != Some(&settings) {
+                self.config
+                    .last_good_formats
+                    .insert(camera.path.clone(), settings);
+                if let Some(handler) = self.config_handler.as_ref()
+                    && let Err(err) = self.config.write_entry(handler)
+                {
+                    error!(?err, "Failed to save last known good format");
+                }
+            }
+        }
+
+        // A frame means the pipeline started after all
+        if self.pipeline_error.is_some() {
+            self.pipeline_error = None;
+            self.missing_decoder = None;
+        }
+
+        // Watch for a camera that only delivers black frames (every few frames)
+        let session = crate::backends::camera::pipewire::pipeline::get_session_stats().1;
+        if count.is_multiple_of(3)
+            && !is_file_source
+            && self.black_frames.wants_sample(session)
+            && let Some(grid) = crate::app::insights::focus::LumaGrid::sample(
+                &frame,
+                crate::app::black_frames::GRID_SIDE,
+            )
+            && self
+                .black_frames
+                .observe(session, &grid, std::time::Instant::now())
+        {
+            warn!("Camera only delivers black frames; it may be disabled by a privacy switch");
+        }
+
+        // Session min/avg/max, from the same counters the insights drawer reads
+        if self.config.session_stats {
+            self.insights.session_stats.record(
+                session,
+                frame.captured_at.elapsed().as_micros() as u64,
+                crate::backends::camera::pipewire::pipeline::get_decode_time_us(),
+                crate::app::video_primitive::get_gpu_upload_time_us(),
+            );
+        }
+
         // Get rotation from current camera (None for file sources)
         let frame_rotation = if is_file_source {
             crate::backends::camera::types::SensorRotation::None
         } else {
-            self.available_cameras
-                .get(self.current_camera_index)
-                .map(|c| c.rotation)
-                .unwrap_or_default()
+            self.current_camera_rotation()
         };
 
         if let Some(task) = self.transition_state.on_frame_received() {
@@ -156,26 +244,128 @@ impl AppModel {
             }
         }
 
+        // Photo burst: the next photo is taken from the first frame after the wait
+        let burst_task = self.capture_burst_still(&frame);
+
+        // Upload benchmark: the GPU upload time is that of the previously displayed frame
+        if self.insights.upload_benchmark.running().is_some() {
+            use crate::backends::camera::pipewire::pipeline;
+            if let Some(path) = self.insights.upload_benchmark.record(
+                pipeline::get_copy_time_us(),
+                crate::app::video_primitive::get_gpu_upload_time_us(),
+            ) {
+                info!(
+                    path = path.display_name(),
+                    "Upload benchmark switching path"
+                );
+                pipeline::set_force_copy(path == crate::app::insights::UploadPath::Copy);
+            }
+        }
+
+        // Keep the current frame (and its already uploaded texture) when nothing changed
+        if self.config.skip_identical_frames && self.insights.upload_benchmark.running().is_none() {
+            let preview_key = self.preview_key(frame_rotation);
+            if self
+                .frame_dedup
+                .should_skip(self.current_frame.as_deref(), &frame, preview_key)
+            {
+                return burst_task;
+            }
+        }
+
+        // Keep the readout live while the probed pixel changes
+        if self.pixel_probe.target().is_some() {
+            self.pixel_probe
+                .refresh(&frame, std::time::Instant::now(), false);
+        }
+
+        // Rewritten in place, so the overlay text doesn't allocate per frame
+        if self.config.frame_counter_overlay {
+            match frame.stamp {
+                Some(stamp) => stamp.write_label(&mut self.frame_counter_label),
+                None => self.frame_counter_label.clear(),
+            }
+        }
+
         self.current_frame = Some(frame);
         self.current_frame_is_file_source = is_file_source;
         self.current_frame_rotation = frame_rotation;
-        Task::none()
+        burst_task
+    }
+
+    /// Note the current format and decoder in the insights format history
+    fn observe_stream_state(&mut self, frame: &crate::backends::camera::types::CameraFrame) {
+        use crate::app::insights::{InsightsState, StreamState};
+
+        let decoded = format!("{:?}", frame.format);
+        let state = match &self.active_format {
+            Some(active) => StreamState {
+                format: if active.pixel_format == decoded {
+                    format!("{} {}x{}", decoded, frame.width, frame.height)
+                } else {
+                    format!(
+                        "{} {}x{} → {}",
+                        active.pixel_format, frame.width, frame.height, decoded
+                    )
+                },
+                decoder: crate::media::get_full_pipeline_string().and_then(|pipeline| {
+                    InsightsState::selected_decoder(&active.pixel_format, &pipeline)
+                }),
+            },
+            None => StreamState {
+                format: format!("{} {}x{}", decoded, frame.width, frame.height),
+                decoder: None,
+            },
+        };
+        if self.insights.format_history.observe(
+            state,
+            std::time::Instant::now(),
+            chrono::Local::now(),
+        ) && let Some(change) = self.insights.format_history.entries().next_back()
+        {
+            info!(change = %change.summary(), "Stream format changed");
+        }
+    }
+
+    /// Preview state that affects how a frame is rendered
+    fn preview_key(
+        &self,
+        rotation: crate::backends::camera::types::SensorRotation,
+    ) -> crate::app::frame_dedup::PreviewKey {
+        crate::app::frame_dedup::PreviewKey {
+            zoom_level: self.zoom_level,
+            filter: self.selected_filter,
+            mirror: self.preview_mirrored(),
+            rotation,
+            aspect_ratio: self.photo_aspect_ratio,
+            gray_mapping: crate::app::video_primitive::GrayMapping {
+                auto_stretch: self.config.gray16_auto_stretch,
+                false_color: self.config.false_color_lut,
+            },
+            theatre: self.theatre.enabled,
+            color_effect: self.config.color_effect,
+        }
     }
 
     pub(crate) fn handle_cameras_initialized(
         &mut self,
         cameras: Vec<crate::backends::camera::types::CameraDevice>,
         camera_index: usize,
-        formats: Vec<crate::backends::camera::types::CameraFormat>,
+        capabilities: crate::backends::camera::types::DeviceCapabilities,
     ) -> Task<cosmic::Action<Message>> {
         info!(
             count = cameras.len(),
-            camera_index, "Cameras initialized asynchronously"
+            camera_index,
+            controls = capabilities.controls.len(),
+            "Cameras initialized asynchronously"
         );
 
+        let formats = capabilities.formats;
         self.available_cameras = cameras;
         self.current_camera_index = camera_index;
         self.available_formats = formats.clone();
+        self.device_controls = capabilities.controls;
+        self.sync_mjpeg_resilience();
 
         self.camera_dropdown_options = self
             .available_cameras
@@ -195,11 +385,7 @@ impl AppModel {
 
         // Set default aspect ratio based on selected format dimensions (accounting for rotation)
         if let Some(fmt) = &self.active_format {
-            let rotation = self
-                .available_cameras
-                .get(self.current_camera_index)
-                .map(|c| c.rotation)
-                .unwrap_or_default();
+            let rotation = self.current_camera_rotation();
             self.photo_aspect_ratio =
                 PhotoAspectRatio::default_for_frame_with_rotation(fmt.width, fmt.height, rotation);
         }
@@ -213,34 +399,17 @@ impl AppModel {
         info!("Camera initialization complete, preview will start");
 
         // Query exposure controls for the current camera
-        if let Some(device_path) = self.get_v4l2_device_path() {
-            let path = device_path.clone();
-            return Task::perform(
-                async move {
-                    let controls = crate::app::exposure_picker::query_exposure_controls(&path);
-                    let settings =
-                        crate::app::exposure_picker::get_exposure_settings(&path, &controls);
-                    let color_settings =
-                        crate::app::exposure_picker::get_color_settings(&path, &controls);
-                    (controls, settings, color_settings)
-                },
-                |(controls, settings, color_settings)| {
-                    cosmic::Action::App(Message::ExposureControlsQueried(
-                        Box::new(controls),
-                        settings,
-                        color_settings,
-                    ))
-                },
-            );
-        }
-
-        Task::none()
+        self.query_exposure_controls_task()
     }
 
-    pub(crate) fn handle_camera_list_changed(
+    pub(crate) fn handle_device_list_changed(
         &mut self,
         new_cameras: Vec<crate::backends::camera::types::CameraDevice>,
     ) -> Task<cosmic::Action<Message>> {
+        let new_cameras = crate::app::camera_ops::apply_source_overrides(
+            new_cameras,
+            &self.config.camera_source_overrides,
+        );
         info!(
             old_count = self.available_cameras.len(),
             new_count = new_cameras.len(),
@@ -257,6 +426,8 @@ impl AppModel {
             };
 
         self.available_cameras = new_cameras.clone();
+        self.device_thumbnails
+            .retain(|path, _| new_cameras.iter().any(|c| &c.path == path));
         self.camera_dropdown_options = self
             .available_cameras
             .iter()
@@ -312,6 +483,59 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_refresh_device_thumbnails(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.device_thumbnails_loading || self.available_cameras.is_empty() {
+            return Task::none();
+        }
+        self.device_thumbnails_loading = true;
+
+        // The streaming camera can't be opened a second time; it gets a placeholder
+        let devices = self.available_cameras.clone();
+        let skip_paths: Vec<String> = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .map(|c| vec![c.path.clone()])
+            .unwrap_or_default();
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    crate::backends::camera::pipewire::capture_device_thumbnails(
+                        &devices,
+                        &skip_paths,
+                    )
+                })
+                .await
+                .unwrap_or_default()
+            },
+            |thumbnails| cosmic::Action::App(Message::DeviceThumbnailsLoaded(thumbnails)),
+        )
+    }
+
+    pub(crate) fn handle_device_thumbnails_loaded(
+        &mut self,
+        thumbnails: Vec<(
+            String,
+            Option<crate::backends::camera::pipewire::DeviceThumbnail>,
+        )>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.device_thumbnails_loading = false;
+        for (path, thumbnail) in thumbnails {
+            let handle = thumbnail.map(|t| {
+                cosmic::widget::image::Handle::from_rgba(t.width, t.height, t.rgba.to_vec())
+            });
+            // Keep the last good thumbnail if the device is busy this time
+            if handle.is_some() || !self.device_thumbnails.contains_key(&path) {
+                self.device_thumbnails.insert(path, handle);
+            }
+        }
+        debug!(
+            count = self.device_thumbnails.len(),
+            "Device thumbnails updated"
+        );
+        Task::none()
+    }
+
     pub(crate) fn handle_start_camera_transition(&mut self) -> Task<cosmic::Action<Message>> {
         info!("Starting camera transition with blur effect");
         let _ = self.transition_state.start();
@@ -341,6 +565,168 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_set_rotation_override(
+        &mut self,
+        rotation: crate::config::RotationOverride,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        info!(
+            ?rotation,
+            detected = %self.detected_camera_rotation(),
+            "Rotation override selected"
+        );
+        // The new rotation is picked up with the next frame
+        self.config.rotation_override = rotation;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save rotation override");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_camera_facing(
+        &mut self,
+        facing: Option<crate::backends::camera::types::CameraFacing>,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        let Some(camera) = self.available_cameras.get(self.current_camera_index) else {
+            return Task::none();
+        };
+        info!(
+            ?facing,
+            detected = ?camera.facing,
+            camera = %camera.name,
+            "Camera facing override selected"
+        );
+        match facing {
+            Some(facing) => self
+                .config
+                .camera_facing_overrides
+                .insert(camera.path.clone(), facing),
+            None => self.config.camera_facing_overrides.remove(&camera.path),
+        };
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save camera facing override");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_camera_source(
+        &mut self,
+        kind: crate::backends::camera::types::CameraSourceKind,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        let index = self.current_camera_index;
+        let Some(camera) = self.available_cameras.get(index) else {
+            return Task::none();
+        };
+        if camera.source_kind == Some(kind) || !camera.source_kinds().contains(&kind) {
+            return Task::none();
+        }
+        if self.recording.is_recording() {
+            info!("Recording in progress, keeping the current camera stack");
+            return Task::none();
+        }
+        info!(
+            stack = kind.display_name(),
+            camera = %camera.name,
+            "Camera stack selected"
+        );
+        self.config
+            .camera_source_overrides
+            .insert(camera.source_key().to_string(), kind);
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save camera stack");
+        }
+
+        let camera = self.available_cameras.remove(index).through(kind);
+        self.available_cameras.insert(index, camera);
+
+        let _ = self.transition_state.start();
+        self.camera_cancel_flag
+            .store(true, std::sync::atomic::Ordering::Release);
+        self.camera_cancel_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        self.current_frame = None;
+        self.insights.reset_format_metrics();
+        self.switch_camera_or_mode(index, self.mode);
+        // Both nodes may offer the same format, so force a new pipeline
+        self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+
+        self.query_exposure_controls_task()
+    }
+
+    pub(crate) fn handle_set_color_effect(
+        &mut self,
+        effect: crate::config::ColorEffect,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        info!(?effect, "Color effect selected");
+        self.config.color_effect = effect;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save color effect");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_color_effect_on_output(&mut self) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        self.config.color_effect_on_output = !self.config.color_effect_on_output;
+        info!(
+            on_output = self.config.color_effect_on_output,
+            "Color effect on output toggled"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save color effect output setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_scaling_quality(
+        &mut self,
+        quality: crate::config::ScalingQuality,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        info!(?quality, "Scaling quality selected");
+        self.config.scaling_quality = quality;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save scaling quality");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_device_orientation_changed(
+        &mut self,
+        rotation: crate::backends::camera::types::SensorRotation,
+    ) -> Task<cosmic::Action<Message>> {
+        if rotation != self.device_orientation {
+            info!(%rotation, "Device orientation changed, re-orienting preview");
+            self.device_orientation = rotation;
+        }
+        Task::none()
+    }
+
     pub(crate) fn handle_toggle_virtual_camera_enabled(&mut self) -> Task<cosmic::Action<Message>> {
         use cosmic::cosmic_config::CosmicConfigEntry;
 
@@ -389,6 +775,91 @@ impl AppModel {
         Task::none()
     }
 
+    /// Remember why the preview pipeline failed, for the preview to explain
+    pub(crate) fn handle_pipeline_failed(
+        &mut self,
+        err: crate::media::decoders::PipelineError,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::safe_resolution::Outcome;
+
+        error!(error = %err, "Preview pipeline failed to start");
+        // A cached decoder that broke since must not be tried first again
+        if let Some(cache) = crate::media::decoders::forget_failed_decoder() {
+            use cosmic::cosmic_config::CosmicConfigEntry;
+            self.config.decoder_cache = cache;
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save decoder cache");
+            }
+        }
+        self.missing_decoder = self.active_format.as_ref().and_then(|active| {
+            let missing = crate::media::decoders::missing_decoder(&active.pixel_format)?;
+            let alternative = crate::app::format_picker::preferences::decodable_alternative(
+                &self.available_formats,
+                active,
+                |pixel_format| crate::media::decoders::missing_decoder(pixel_format).is_none(),
+            );
+            warn!(
+                codec = %missing.codec,
+                packages = ?missing.packages,
+                ?alternative,
+                "No decoder installed for the selected format"
+            );
+            Some((missing, alternative))
+        });
+        let outcome = match (
+            self.available_cameras.get(self.current_camera_index),
+            self.active_format.as_ref(),
+        ) {
+            // A missing decoder fails at every resolution
+            (Some(camera), Some(active))
+                if !self.recording.is_recording() && self.missing_decoder.is_none() =>
+            {
+                self.safe_resolution.record_failure(
+                    &camera.path,
+                    active,
+                    &self.available_formats,
+                    self.config.last_good_formats.get(&camera.path),
+                    &err,
+                )
+            }
+            _ => Outcome::Retry,
+        };
+        self.pipeline_error = Some(err);
+
+        match outcome {
+            Outcome::Retry => Task::none(),
+            Outcome::Downgrade(target) => {
+                warn!(
+                    from = ?self.active_format,
+                    to = %target,
+                    "Mode keeps failing to start, falling back to a lower one"
+                );
+                let toast = cosmic::widget::toaster::Toast::new(crate::fl!(
+                    "safe-resolution-downgraded",
+                    resolution = format!("{}x{}", target.width, target.height),
+                    format = target.pixel_format.as_str()
+                ));
+                // Not saved as the selected mode, like a low-light switch
+                self.active_format = Some(target);
+                self.current_frame = None;
+                self.update_all_dropdowns();
+                self.toasts.push(toast).map(cosmic::Action::App)
+            }
+            Outcome::GiveUp => {
+                error!("No lower mode left to fall back to");
+                Task::none()
+            }
+        }
+    }
+
+    /// Hide the black frame hint
+    pub(crate) fn handle_dismiss_black_frame_hint(&mut self) -> Task<cosmic::Action<Message>> {
+        self.black_frames.dismiss();
+        Task::none()
+    }
+
     /// Check privacy cover status for the current camera
     ///
     /// Returns a task that sends PrivacyCoverStatusChanged if camera has privacy control.
@@ -6,6 +6,7 @@
 
 use crate::app::state::{AppModel, CameraMode, Message, RecordingState};
 use crate::backends::camera::v4l2_controls::read_exposure_metadata;
+use crate::pipelines::filename::{CaptureKind, FilenameTemplate, OutputTarget};
 use crate::pipelines::photo::burst_mode::BurstModeConfig;
 use crate::pipelines::photo::burst_mode::burst::{
     calculate_adaptive_params, estimate_scene_brightness,
@@ -20,6 +21,39 @@ const BURST_MODE_SUCCESS_DISPLAY_MS: u64 = 2000;
 /// Delay in ms before resetting burst mode state after an error
 const BURST_MODE_ERROR_DISPLAY_MS: u64 = 3000;
 
+/// Why the pipeline that just delivered its first frame was started
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PipelineStart {
+    /// First pipeline since the app launched
+    Launch,
+    /// Another camera was selected
+    CameraChanged,
+    /// Another format of the same camera was selected
+    FormatChanged,
+    /// Same camera and format again (HDR+ restart, resume from the background,
+    /// stall watchdog, forced caps, decoder comparison, deinterlacing)
+    Rebuild,
+}
+
+/// MIME type a copied frame is offered as
+const CLIPBOARD_PNG_MIME: &str = "image/png";
+
+/// PNG image offered on the clipboard
+///
+/// Wayland and X11 clipboards hand data out by MIME type; PNG is the image
+/// type chat apps and document editors accept.
+struct ClipboardPng(Vec<u8>);
+
+impl cosmic::iced::clipboard::mime::AsMimeTypes for ClipboardPng {
+    fn available(&self) -> std::borrow::Cow<'static, [String]> {
+        std::borrow::Cow::Owned(vec![CLIPBOARD_PNG_MIME.to_string()])
+    }
+
+    fn as_bytes(&self, mime_type: &str) -> Option<std::borrow::Cow<'static, [u8]>> {
+        (mime_type == CLIPBOARD_PNG_MIME).then(|| std::borrow::Cow::Owned(self.0.clone()))
+    }
+}
+
 impl AppModel {
     // =========================================================================
     // Capture Operations Handlers
@@ -36,6 +70,41 @@ impl AppModel {
         )
     }
 
+    /// Describe the active capture pipeline for embedding into saved stills
+    ///
+    /// Returns None when the user disabled capture metadata for privacy.
+    fn capture_info_for_still(&self) -> Option<crate::pipelines::photo::CaptureInfo> {
+        use crate::app::insights::{FallbackState, InsightsState};
+
+        if !self.config.embed_capture_metadata {
+            return None;
+        }
+
+        let camera = self.available_cameras.get(self.current_camera_index)?;
+        let format = self.active_format.as_ref()?;
+
+        // Same decoder detection the insights drawer uses
+        let pipeline = crate::media::get_full_pipeline_string();
+        let decoder = InsightsState::build_decoder_chain(
+            Some(format.pixel_format.as_str()),
+            pipeline.as_deref(),
+        )
+        .into_iter()
+        .find(|d| d.state == FallbackState::Selected)
+        .map(|d| d.name.to_string());
+
+        Some(crate::pipelines::photo::CaptureInfo {
+            device: camera.name.clone(),
+            resolution: format!("{}x{}", format.width, format.height),
+            framerate: format
+                .framerate
+                .map(|fps| format!("{} fps", fps))
+                .unwrap_or_else(|| "N/A".to_string()),
+            native_format: format.pixel_format.clone(),
+            decoder,
+        })
+    }
+
     /// Check if burst mode would be triggered based on current scene brightness
     ///
     /// Returns true if Auto mode would use more than 1 frame (actual burst capture)
@@ -61,6 +130,141 @@ impl AppModel {
         }
     }
 
+    /// Where photos are saved and how they are named
+    fn photo_output_target(&self) -> OutputTarget {
+        self.output_target(crate::app::photo_output_directory(&self.config))
+    }
+
+    /// Where recordings and clips are saved and how they are named
+    fn video_output_target(&self) -> OutputTarget {
+        self.output_target(crate::app::video_output_directory(&self.config))
+    }
+
+    fn output_target(&self, dir: PathBuf) -> OutputTarget {
+        // The settings only store valid templates, but the config file may be edited by hand
+        let template = FilenameTemplate::parse(&self.config.filename_template).unwrap_or_else(
+            |err| {
+                warn!(?err, template = %self.config.filename_template, "Invalid filename template, using the default");
+                FilenameTemplate::default()
+            },
+        );
+        let device = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .map(|camera| camera.name.clone())
+            .unwrap_or_default();
+        OutputTarget::new(dir)
+            .with_template(template)
+            .with_device(device)
+    }
+
+    /// Post-processing that turns the current frame into a still
+    ///
+    /// Shared by saved photos and frames copied to the clipboard, so both get
+    /// the same filter, color effect, aspect ratio crop, zoom, rotation and
+    /// watermark.
+    fn still_processing_config(
+        &self,
+        frame: &crate::backends::camera::types::CameraFrame,
+    ) -> crate::pipelines::photo::PostProcessingConfig {
+        let color_effect = if self.config.color_effect_on_output {
+            self.config.color_effect
+        } else {
+            crate::config::ColorEffect::None
+        };
+
+        // Get camera rotation for photo processing
+        let rotation = self.current_camera_rotation();
+
+        // Calculate crop rectangle based on aspect ratio setting (accounting for rotation)
+        let crop_rect = (self.photo_aspect_ratio != crate::app::state::PhotoAspectRatio::Native)
+            .then(|| {
+                self.photo_aspect_ratio
+                    .crop_rect_with_rotation(frame.width, frame.height, rotation)
+            });
+
+        // The frame counter overlay is only burned in along with the watermark
+        let mut watermark = crate::pipelines::watermark::StillWatermark::new(
+            &self.config.watermark,
+            chrono::Local::now(),
+        );
+        if self.config.frame_counter_overlay
+            && let (Some(watermark), Some(stamp)) = (watermark.as_mut(), frame.stamp)
+        {
+            let mut label = String::new();
+            stamp.write_label(&mut label);
+            watermark.append_text(&label);
+        }
+
+        crate::pipelines::photo::PostProcessingConfig {
+            filter_type: self.selected_filter,
+            color_effect,
+            crop_rect,
+            zoom_level: self.zoom_level,
+            rotation,
+            watermark,
+            ..Default::default()
+        }
+    }
+
+    /// Copy the current frame to the clipboard as a PNG image
+    ///
+    /// The frame goes through the same post-processing as a saved photo.
+    pub(crate) fn handle_copy_frame_to_clipboard(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(frame) = self.current_frame.clone() else {
+            info!("No frame available to copy");
+            return Task::none();
+        };
+        let config = self.still_processing_config(&frame);
+
+        Task::perform(
+            async move {
+                let processed = crate::pipelines::photo::PostProcessor::new(config)
+                    .process(frame)
+                    .await?;
+                tokio::task::spawn_blocking(move || {
+                    let mut png = std::io::Cursor::new(Vec::new());
+                    processed
+                        .image
+                        .write_to(&mut png, image::ImageFormat::Png)
+                        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+                    Ok(png.into_inner())
+                })
+                .await
+                .map_err(|e| format!("Task join error: {}", e))?
+            },
+            |result| cosmic::Action::App(Message::FrameCopyEncoded(result)),
+        )
+    }
+
+    /// Put the encoded frame on the clipboard and report the outcome
+    pub(crate) fn handle_frame_copy_encoded(
+        &mut self,
+        result: Result<Vec<u8>, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            Ok(png) => {
+                info!(bytes = png.len(), "Copying frame to clipboard");
+                Task::batch([
+                    cosmic::iced::clipboard::write_data(ClipboardPng(png)),
+                    self.toasts
+                        .push(cosmic::widget::toaster::Toast::new(crate::fl!(
+                            "clipboard-frame-copied"
+                        )))
+                        .map(cosmic::Action::App),
+                ])
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to copy frame to clipboard");
+                self.toasts
+                    .push(cosmic::widget::toaster::Toast::new(crate::fl!(
+                        "clipboard-frame-failed"
+                    )))
+                    .map(cosmic::Action::App)
+            }
+        }
+    }
+
     /// Capture the current frame as a photo with the selected filter and zoom
     pub(crate) fn capture_photo(&mut self) -> Task<cosmic::Action<Message>> {
         // Use HDR+ burst mode only if it would actually be used (frame_count > 1)
@@ -77,31 +281,79 @@ impl AppModel {
         info!("Capturing photo...");
         self.is_capturing = true;
 
-        let frame_arc = Arc::clone(frame);
-        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
-        let filter_type = self.selected_filter;
-        let zoom_level = self.zoom_level;
+        let save_task = self.save_still(Arc::clone(frame), Message::PhotoSaved);
+        let animation_task = Self::delay_task(150, Message::ClearCaptureAnimation);
+        Task::batch([save_task, animation_task])
+    }
 
-        // Get camera rotation for photo processing
-        let rotation = self
+    /// Save the first frame of a freshly started pipeline, when enabled
+    ///
+    /// Goes through the same path as a manual capture, so the filename
+    /// template and the transform and overlay settings apply, but skips
+    /// timer, flash and HDR+. Renegotiation within a running pipeline
+    /// doesn't announce a new first frame, a rebuilt pipeline does.
+    pub(crate) fn handle_pipeline_first_frame(&mut self) -> Task<cosmic::Action<Message>> {
+        // The decoder works: try it first next time
+        if let Some(cache) = crate::media::decoders::remember_active_decoder() {
+            use cosmic::cosmic_config::CosmicConfigEntry;
+            self.config.decoder_cache = cache;
+            if let Some(handler) = self.config_handler.as_ref()
+                && let Err(err) = self.config.write_entry(handler)
+            {
+                error!(?err, "Failed to save decoder cache");
+            }
+        }
+        // Interlacing shows in the first pipeline's caps; rebuild it with the stage
+        if crate::media::decoders::deinterlace_needs_rebuild() {
+            info!("Source is interlaced, rebuilding the preview with deinterlacing");
+            self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+        }
+
+        let start = self.note_pipeline_start();
+        if !self.config.first_frame_snapshot || start == PipelineStart::Rebuild {
+            return Task::none();
+        }
+        let Some(frame) = &self.current_frame else {
+            warn!("First frame announced before it reached the preview");
+            return Task::none();
+        };
+        info!(?start, "Saving first frame snapshot");
+        self.save_still(Arc::clone(frame), Message::PhotoSaved)
+    }
+
+    /// Record the camera and format now delivering frames and tell why it started
+    fn note_pipeline_start(&mut self) -> PipelineStart {
+        let camera = self
             .available_cameras
             .get(self.current_camera_index)
-            .map(|cam| cam.rotation)
+            .map(|camera| camera.path.clone())
             .unwrap_or_default();
-
-        // Calculate crop rectangle based on aspect ratio setting (accounting for rotation)
-        let crop_rect =
-            self.photo_aspect_ratio
-                .crop_rect_with_rotation(frame.width, frame.height, rotation);
-        let crop_rect = if self.photo_aspect_ratio == crate::app::state::PhotoAspectRatio::Native {
-            None
-        } else {
-            Some(crop_rect)
+        let source = (camera, self.active_format.clone());
+        let start = match &self.pipeline_source {
+            None => PipelineStart::Launch,
+            Some((camera, _)) if *camera != source.0 => PipelineStart::CameraChanged,
+            Some((_, format)) if *format != source.1 => PipelineStart::FormatChanged,
+            Some(_) => PipelineStart::Rebuild,
         };
+        debug!(?start, "Pipeline delivering frames");
+        self.pipeline_source = Some(source);
+        start
+    }
 
-        // Get the encoding format from config
-        let encoding_format: crate::pipelines::photo::EncodingFormat =
-            self.config.photo_output_format.into();
+    /// Save a frame as a photo, reporting the saved path (or error) with `on_saved`
+    fn save_still(
+        &self,
+        frame: Arc<crate::backends::camera::types::CameraFrame>,
+        on_saved: fn(Result<String, String>) -> Message,
+    ) -> Task<cosmic::Action<Message>> {
+        let target = self.photo_output_target();
+        let config = self.still_processing_config(&frame);
+
+        // Get the output format and JPEG quality from config
+        let still_format = crate::pipelines::photo::StillFormat::from_config(
+            self.config.photo_output_format,
+            self.config.photo_jpeg_quality,
+        );
 
         // Get camera metadata for DNG encoding (including exposure info)
         let camera_metadata = self
@@ -111,6 +363,7 @@ impl AppModel {
                 let mut metadata = crate::pipelines::photo::CameraMetadata {
                     camera_name: Some(cam.name.clone()),
                     camera_driver: cam.device_info.as_ref().map(|info| info.driver.clone()),
+                    capture_info: self.capture_info_for_still(),
                     ..Default::default()
                 };
                 // Read exposure metadata from V4L2 device if available
@@ -126,31 +379,165 @@ impl AppModel {
             })
             .unwrap_or_default();
 
-        let save_task = Task::perform(
+        Task::perform(
             async move {
-                use crate::pipelines::photo::{
-                    EncodingQuality, PhotoPipeline, PostProcessingConfig,
-                };
-                let config = PostProcessingConfig {
-                    filter_type,
-                    crop_rect,
-                    zoom_level,
-                    rotation,
-                    ..Default::default()
-                };
-                let mut pipeline =
-                    PhotoPipeline::with_config(config, encoding_format, EncodingQuality::High);
+                use crate::pipelines::photo::{EncodingQuality, PhotoPipeline};
+                let mut pipeline = PhotoPipeline::with_config(
+                    config,
+                    still_format.encoding_format(),
+                    EncodingQuality::High,
+                );
+                pipeline.set_still_format(still_format);
                 pipeline.set_camera_metadata(camera_metadata);
                 pipeline
-                    .capture_and_save(frame_arc, save_dir)
+                    .capture_and_save(frame, target)
                     .await
                     .map(|p| p.display().to_string())
             },
-            |result| cosmic::Action::App(Message::PhotoSaved(result)),
+            move |result| cosmic::Action::App(on_saved(result)),
+        )
+    }
+
+    /// Start a still burst: `count` photos, `interval` apart
+    ///
+    /// Only one burst runs at a time, and not while HDR+ collects frames.
+    pub(crate) fn handle_capture_burst(
+        &mut self,
+        count: u32,
+        interval: std::time::Duration,
+    ) -> Task<cosmic::Action<Message>> {
+        if self.still_burst.is_some() || self.burst_mode.is_active() {
+            warn!("Cannot start a photo burst while another burst is running");
+            return self
+                .toasts
+                .push(cosmic::widget::toaster::Toast::new(crate::fl!(
+                    "still-burst-busy"
+                )))
+                .map(cosmic::Action::App);
+        }
+        if self.current_frame.is_none() {
+            info!("No frame available to start a photo burst");
+            return Task::none();
+        }
+        info!(
+            count,
+            interval_ms = interval.as_millis() as u64,
+            "Starting photo burst"
         );
+        self.still_burst = Some(crate::app::still_burst::StillBurst::new(count, interval));
+        Task::none()
+    }
 
-        let animation_task = Self::delay_task(150, Message::ClearCaptureAnimation);
-        Task::batch([save_task, animation_task])
+    /// Save `frame` if the running still burst is waiting for a frame
+    ///
+    /// Called for every new frame, so each photo of a burst comes from a
+    /// different frame.
+    pub(crate) fn capture_burst_still(
+        &mut self,
+        frame: &Arc<crate::backends::camera::types::CameraFrame>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(burst) = self.still_burst.as_mut() else {
+            return Task::none();
+        };
+        if !burst.wants(frame.captured_at) {
+            return Task::none();
+        }
+        let next = burst.take(frame.captured_at);
+        debug!(
+            taken = burst.taken(),
+            count = burst.count(),
+            "Photo burst still taken"
+        );
+
+        let save_task = self.save_still(Arc::clone(frame), Message::BurstStillSaved);
+        match next {
+            Some(interval) => Task::batch([
+                save_task,
+                Self::delay_task(interval.as_millis() as u64, Message::BurstStillDue),
+            ]),
+            None => save_task,
+        }
+    }
+
+    pub(crate) fn handle_burst_still_due(&mut self) -> Task<cosmic::Action<Message>> {
+        if let Some(burst) = self.still_burst.as_mut() {
+            burst.arm();
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_burst_still_saved(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(burst) = self.still_burst.as_mut() else {
+            return Task::none();
+        };
+        let refresh = match &result {
+            Ok(path) => {
+                debug!(path = %path, "Photo burst still saved");
+                Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail))
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to save photo burst still");
+                Task::none()
+            }
+        };
+        burst.record_saved(result.is_ok());
+        if !burst.is_finished() {
+            return refresh;
+        }
+
+        let (saved, count) = (burst.saved(), burst.count());
+        self.still_burst = None;
+        info!(saved, count, "Photo burst finished");
+        Task::batch([
+            refresh,
+            self.toasts
+                .push(cosmic::widget::toaster::Toast::new(crate::fl!(
+                    "still-burst-done",
+                    saved = saved,
+                    count = count
+                )))
+                .map(cosmic::Action::App),
+        ])
+    }
+
+    pub(crate) fn handle_select_still_burst_count(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&count) = crate::constants::still_burst::COUNT_OPTIONS.get(index) else {
+            return Task::none();
+        };
+        info!(count, "Selected photo burst count");
+        self.config.still_burst_count = count;
+        self.save_still_burst_settings();
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_still_burst_interval(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&interval_ms) = crate::constants::still_burst::INTERVAL_OPTIONS_MS.get(index)
+        else {
+            return Task::none();
+        };
+        info!(interval_ms, "Selected photo burst interval");
+        self.config.still_burst_interval_ms = interval_ms;
+        self.save_still_burst_settings();
+        Task::none()
+    }
+
+    fn save_still_burst_settings(&self) {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save photo burst settings");
+        }
     }
 
     /// Capture a burst mode photo using multi-frame burst capture
@@ -233,7 +620,7 @@ impl AppModel {
             return Task::none();
         }
 
-        let save_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let target = self.photo_output_target();
 
         // Get encoding format and camera metadata (including exposure info)
         let encoding_format: crate::pipelines::photo::EncodingFormat =
@@ -253,6 +640,7 @@ impl AppModel {
                 let mut metadata = crate::pipelines::photo::CameraMetadata {
                     camera_name: Some(cam.name.clone()),
                     camera_driver: cam.device_info.as_ref().map(|info| info.driver.clone()),
+                    capture_info: self.capture_info_for_still(),
                     ..Default::default()
                 };
                 // Copy exposure metadata if available
@@ -266,11 +654,7 @@ impl AppModel {
             .unwrap_or_default();
 
         // Get camera rotation for photo processing
-        let rotation = self
-            .available_cameras
-            .get(self.current_camera_index)
-            .map(|cam| cam.rotation)
-            .unwrap_or_default();
+        let rotation = self.current_camera_rotation();
 
         // Calculate crop rectangle based on aspect ratio setting (accounting for rotation)
         let crop_rect = if let Some(frame) = frames.first() {
@@ -295,6 +679,10 @@ impl AppModel {
         config.camera_metadata = camera_metadata;
         config.save_burst_raw_dng = self.config.save_burst_raw;
         config.rotation = rotation;
+        config.watermark = crate::pipelines::watermark::StillWatermark::new(
+            &self.config.watermark,
+            chrono::Local::now(),
+        );
 
         // Calculate adaptive processing parameters based on scene brightness
         if let Some(first_frame) = frames.first() {
@@ -330,7 +718,7 @@ impl AppModel {
             let result = rt.block_on(async move {
                 process_burst_mode_frames_with_atomic(
                     frames,
-                    save_dir,
+                    target,
                     config,
                     progress_atomic,
                     selected_filter,
@@ -633,7 +1021,7 @@ impl AppModel {
                 return Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail));
             }
             Err(err) => {
-                let expected_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+                let expected_dir = crate::app::photo_output_directory(&self.config);
                 error!(
                     error = %err,
                     expected_directory = %expected_dir.display(),
@@ -673,6 +1061,7 @@ impl AppModel {
                 error!("No active format for recording");
                 return Task::none();
             }
+            self.recording_summary = None;
             return Task::done(cosmic::Action::App(Message::StartRecordingAfterDelay));
         }
         Task::none()
@@ -683,22 +1072,45 @@ impl AppModel {
         path: String,
     ) -> Task<cosmic::Action<Message>> {
         info!(path = %path, "Recording started successfully");
+        crate::events::hub().emit(crate::events::CameraEvent::RecordingStarted(path));
         Self::delay_task(1000, Message::UpdateRecordingDuration)
     }
 
     pub(crate) fn handle_recording_stopped(
         &mut self,
-        result: Result<String, String>,
+        result: Result<crate::pipelines::video::RecordingSummary, String>,
     ) -> Task<cosmic::Action<Message>> {
         self.recording = RecordingState::Idle;
+        let outcome = match &result {
+            Ok(summary) => match &summary.error {
+                None => Ok(summary.path.display().to_string()),
+                Some(err) => Err(err.clone()),
+            },
+            Err(err) => Err(err.clone()),
+        };
+        crate::events::hub().emit(crate::events::CameraEvent::RecordingStopped(
+            outcome.clone(),
+        ));
+
+        if self.shutdown.is_some() {
+            return self.continue_shutdown(true, outcome.err());
+        }
 
         match result {
-            Ok(path) => {
-                info!(path = %path, "Recording saved successfully");
-                return Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail));
+            Ok(summary) => {
+                if let Some(err) = &summary.error {
+                    error!(error = %err, path = %summary.path.display(), "Recording ended with an error");
+                } else {
+                    info!(path = %summary.path.display(), "Recording saved successfully");
+                }
+                let has_file = summary.file_size > 0;
+                self.recording_summary = Some(summary);
+                if has_file {
+                    return Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail));
+                }
             }
             Err(err) => {
-                let expected_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+                let expected_dir = crate::app::video_output_directory(&self.config);
                 error!(
                     error = %err,
                     expected_directory = %expected_dir.display(),
@@ -721,6 +1133,9 @@ impl AppModel {
     }
 
     pub(crate) fn handle_start_recording_after_delay(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.shutdown.is_some() {
+            return Task::none();
+        }
         let Some(camera) = self.available_cameras.get(self.current_camera_index) else {
             error!("Camera disappeared");
             self.recording = RecordingState::Idle;
@@ -733,10 +1148,29 @@ impl AppModel {
             return Task::none();
         };
 
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("VID_{}.mp4", timestamp);
-        let save_dir = crate::app::get_video_directory(&self.config.save_folder_name);
-        let output_path = save_dir.join(&filename);
+        let selected_encoder = self
+            .available_video_encoders
+            .get(self.current_video_encoder_index)
+            .cloned();
+        // The recorder settles the container once encoders are selected; this is its likely pick
+        let extension = selected_encoder
+            .as_ref()
+            .map_or("mp4", |encoder| encoder.codec.file_extension());
+        // An explicitly requested path names a single file, so it is never split
+        let path_override = self.recording_path_override.take();
+        let split = crate::pipelines::video::RecordingSplit::from_limits(
+            self.config.recording_split_minutes,
+            self.config.recording_split_mb,
+            self.video_output_target(),
+        )
+        .filter(|_| path_override.is_none());
+        let output_path = match (path_override, &split) {
+            (Some(path), _) => path,
+            (None, Some(split)) => split.target.segment_path(CaptureKind::Video, extension, 1),
+            (None, None) => self
+                .video_output_target()
+                .path(CaptureKind::Video, extension),
+        };
 
         info!(
             device = %camera.path,
@@ -744,16 +1178,26 @@ impl AppModel {
             height = format.height,
             fps = ?format.framerate,
             output = %output_path.display(),
+            split = ?split.as_ref().map(|split| (split.max_duration, split.max_bytes)),
             "Starting video recording"
         );
 
         let device_path = camera.path.clone();
         let metadata_path = camera.metadata_path.clone();
-        let sensor_rotation = camera.rotation;
+        let sensor_rotation = self.current_camera_rotation();
         let width = format.width;
         let height = format.height;
         let framerate = format.framerate.map(|f| f.as_int()).unwrap_or(30);
         let pixel_format = format.pixel_format.clone();
+        let max_framerate = Some(self.config.framerate_cap_fps).filter(|&fps| fps > 0);
+        // Measured on the preview, which runs until the recorder takes over
+        let variable_framerate = crate::backends::camera::pipewire::pipeline::get_frame_cadence()
+            .is_some_and(|cadence| cadence.variable);
+        let watermark = self
+            .config
+            .watermark
+            .enabled
+            .then(|| self.config.watermark.clone());
 
         // Only get audio device if audio recording is enabled in settings
         let audio_device = if self.config.record_audio {
@@ -764,11 +1208,6 @@ impl AppModel {
             None
         };
 
-        let selected_encoder = self
-            .available_video_encoders
-            .get(self.current_video_encoder_index)
-            .cloned();
-
         let bitrate_kbps = self.config.bitrate_preset.bitrate_kbps(width, height);
 
         let (stop_tx, stop_rx) = tokio::sync::oneshot::channel();
@@ -798,13 +1237,17 @@ impl AppModel {
                     height,
                     framerate,
                     pixel_format: &pixel_format,
-                    output_path: output_path.clone(),
+                    output_path,
                     encoder_config: config,
                     enable_audio: audio_device.is_some(),
                     audio_device: audio_device.as_deref(),
                     preview_sender: None,
                     encoder_info: selected_encoder.as_ref(),
                     rotation: sensor_rotation,
+                    watermark,
+                    split,
+                    max_framerate,
+                    variable_framerate,
                 }) {
                     Ok(r) => r,
                     Err(e) => return Err(e),
@@ -812,14 +1255,11 @@ impl AppModel {
 
                 recorder.start()?;
 
-                let path = output_path.display().to_string();
                 let _ = stop_rx.await;
 
-                tokio::task::spawn_blocking(move || {
-                    recorder.stop().map(|_| path).map_err(|e| e.to_string())
-                })
-                .await
-                .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+                tokio::task::spawn_blocking(move || Ok(recorder.stop()))
+                    .await
+                    .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
             },
             |result| cosmic::Action::App(Message::RecordingStopped(result)),
         );
@@ -831,6 +1271,114 @@ impl AppModel {
         Task::batch([start_signal, recording_task])
     }
 
+    pub(crate) fn handle_select_retro_buffer_duration(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&seconds) = crate::constants::retro_buffer::DURATION_OPTIONS_SECS.get(index)
+        else {
+            return Task::none();
+        };
+        info!(seconds, "Selected retro buffer duration");
+        self.config.retro_buffer_seconds = seconds;
+        crate::pipelines::video::retro::configure(seconds, self.config.retro_buffer_memory_mb);
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save retro buffer duration");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_recording_split_duration(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&minutes) = crate::constants::recording_split::DURATION_OPTIONS_MINS.get(index)
+        else {
+            return Task::none();
+        };
+        info!(minutes, "Selected recording segment length");
+        self.config.recording_split_minutes = minutes;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save recording segment length");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_recording_split_size(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&megabytes) = crate::constants::recording_split::SIZE_OPTIONS_MB.get(index) else {
+            return Task::none();
+        };
+        info!(megabytes, "Selected recording segment size");
+        self.config.recording_split_mb = megabytes;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save recording segment size");
+        }
+        Task::none()
+    }
+
+    /// Flush the retro buffer (last N seconds of frames) to a video file
+    pub(crate) fn handle_save_retro_clip(&mut self) -> Task<cosmic::Action<Message>> {
+        if !crate::pipelines::video::retro::is_enabled() {
+            warn!("Retro clip requested but the retro buffer is disabled");
+            return Task::none();
+        }
+
+        let target = self.video_output_target();
+        let (width, height) = self
+            .active_format
+            .as_ref()
+            .map(|f| (f.width, f.height))
+            .unwrap_or((1920, 1080));
+        let bitrate_kbps = self.config.bitrate_preset.bitrate_kbps(width, height);
+
+        Task::perform(
+            async move {
+                use crate::pipelines::video::{EncoderConfig, retro};
+
+                let config = EncoderConfig {
+                    width,
+                    height,
+                    bitrate_override_kbps: Some(bitrate_kbps),
+                    ..Default::default()
+                };
+                tokio::task::spawn_blocking(move || {
+                    retro::save_clip(&target, config).map(|path| path.display().to_string())
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+            },
+            |result| cosmic::Action::App(Message::RetroClipSaved(result)),
+        )
+    }
+
+    pub(crate) fn handle_retro_clip_saved(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            Ok(path) => {
+                info!(path = %path, "Retro clip saved");
+                Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail))
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to save retro clip");
+                Task::none()
+            }
+        }
+    }
+
     /// Handle burst mode progress update
     pub(crate) fn handle_burst_mode_progress(
         &mut self,
@@ -906,7 +1454,7 @@ impl AppModel {
 /// Progress updates are sent via the provided atomic counter (progress * 1000).
 async fn process_burst_mode_frames_with_atomic(
     frames: Vec<Arc<crate::backends::camera::types::CameraFrame>>,
-    save_dir: PathBuf,
+    target: OutputTarget,
     config: BurstModeConfig,
     progress_atomic: Arc<std::sync::atomic::AtomicU32>,
     filter: crate::app::FilterType,
@@ -930,10 +1478,11 @@ async fn process_burst_mode_frames_with_atomic(
     let camera_metadata = config.camera_metadata.clone();
     let save_burst_raw_dng = config.save_burst_raw_dng;
     let rotation = config.rotation;
+    let watermark = config.watermark.clone();
 
     // Export raw burst frames as DNG if enabled (before processing)
     if save_burst_raw_dng {
-        match export_burst_frames_dng(&frames, save_dir.clone(), &camera_metadata).await {
+        match export_burst_frames_dng(&frames, target.dir.clone(), &camera_metadata).await {
             Ok(burst_dir) => {
                 info!(burst_dir = %burst_dir.display(), "Raw burst frames saved as DNG");
             }
@@ -948,12 +1497,13 @@ async fn process_burst_mode_frames_with_atomic(
     if let Some(first_frame) = frames.first()
         && let Err(e) = save_first_burst_frame(
             first_frame,
-            &save_dir,
+            &target,
             crop_rect,
             encoding_format,
             &camera_metadata,
             filter,
             rotation,
+            watermark.as_ref(),
         )
         .await
     {
@@ -973,12 +1523,13 @@ async fn process_burst_mode_frames_with_atomic(
     // Save output with optional crop, filter, rotation, and selected encoding format
     let output_path = save_output(
         &merged,
-        save_dir,
+        target,
         crop_rect,
         encoding_format,
         camera_metadata,
         Some(filter),
         rotation,
+        watermark.as_ref(),
         Some("_HDR+"),
     )
     .await?;
@@ -990,12 +1541,13 @@ async fn process_burst_mode_frames_with_atomic(
 /// Save the first frame of a burst as a separate file for comparison
 async fn save_first_burst_frame(
     frame: &crate::backends::camera::types::CameraFrame,
-    save_dir: &std::path::Path,
+    target: &OutputTarget,
     crop_rect: Option<(u32, u32, u32, u32)>,
     encoding_format: crate::pipelines::photo::EncodingFormat,
     camera_metadata: &crate::pipelines::photo::CameraMetadata,
     filter: crate::app::FilterType,
     rotation: crate::backends::camera::types::SensorRotation,
+    watermark: Option<&crate::pipelines::watermark::StillWatermark>,
 ) -> Result<PathBuf, String> {
     use crate::pipelines::photo::burst_mode::{MergedFrame, save_output};
 
@@ -1006,15 +1558,16 @@ async fn save_first_burst_frame(
         height: frame.height,
     };
 
-    // Reuse save_output with no filename suffix (plain IMG_{timestamp})
+    // Reuse save_output with no filename suffix (the plain template name)
     let path = save_output(
         &merged,
-        save_dir.to_path_buf(),
+        target.clone(),
         crop_rect,
         encoding_format,
         camera_metadata.clone(),
         Some(filter),
         rotation,
+        watermark,
         None, // No suffix for first frame
     )
     .await?;
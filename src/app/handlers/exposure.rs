@@ -2,15 +2,20 @@
 
 //! Exposure control handlers
 //!
-//! Handles exposure mode, compensation, time, gain, ISO, metering, and backlight.
+//! Handles exposure mode, compensation, time, gain, ISO, metering, backlight,
+//! anti-flicker and focus.
 
 use crate::app::exposure_picker::{
     AvailableExposureControls, ColorSettings, ExposureMode, ExposureSettings, MeteringMode,
+    NudgeTarget, PowerLineFrequency,
 };
 use crate::app::state::{AppModel, Message};
 use crate::backends::camera::v4l2_controls;
+use crate::fl;
 use cosmic::Task;
-use tracing::{debug, info};
+use cosmic::cosmic_config::CosmicConfigEntry;
+use std::time::Instant;
+use tracing::{debug, error, info};
 
 impl AppModel {
     // =========================================================================
@@ -291,6 +296,94 @@ impl AppModel {
         )
     }
 
+    pub(crate) fn handle_nudge_control(
+        &mut self,
+        target: NudgeTarget,
+        steps: i32,
+    ) -> Task<cosmic::Action<Message>> {
+        let controls = &self.available_exposure_controls;
+        let (name, range, current) = match target {
+            NudgeTarget::ExposureTime => (
+                fl!("exposure-time"),
+                &controls.exposure_time,
+                self.exposure_settings
+                    .as_ref()
+                    .and_then(|s| s.exposure_time),
+            ),
+            NudgeTarget::Gain => (
+                fl!("exposure-gain"),
+                &controls.gain,
+                self.exposure_settings.as_ref().and_then(|s| s.gain),
+            ),
+        };
+        let format_value = |value: i32| match target {
+            NudgeTarget::ExposureTime => {
+                crate::app::exposure_picker::view::format_exposure_time(value)
+            }
+            NudgeTarget::Gain => value.to_string(),
+        };
+
+        // Refusals are shown too, so a press that does nothing isn't mistaken for a lost key
+        let text = match &self.exposure_settings {
+            Some(settings) if range.available => {
+                if settings.is_automatic(target) {
+                    fl!("nudge-automatic", control = name)
+                } else {
+                    let current = current.unwrap_or(range.default);
+                    match range.nudge(current, steps) {
+                        Some(value) => {
+                            debug!(?target, steps, value, "Nudging control");
+                            let set = match target {
+                                NudgeTarget::ExposureTime => Message::SetExposureTime(value),
+                                NudgeTarget::Gain => Message::SetGain(value),
+                            };
+                            let text =
+                                fl!("nudge-value", control = name, value = format_value(value));
+                            return Task::batch([
+                                Task::done(cosmic::Action::App(set)),
+                                self.show_control_nudge(text),
+                            ]);
+                        }
+                        None => fl!("nudge-limit", control = name, value = format_value(current)),
+                    }
+                }
+            }
+            _ => fl!("nudge-unavailable", control = name),
+        };
+        debug!(?target, steps, %text, "Control nudge refused");
+        self.show_control_nudge(text)
+    }
+
+    pub(crate) fn handle_hide_control_nudge(
+        &mut self,
+        shown_at: Instant,
+    ) -> Task<cosmic::Action<Message>> {
+        // A newer nudge keeps the indicator up for its own full duration
+        if self
+            .control_nudge
+            .as_ref()
+            .is_some_and(|(at, _)| *at == shown_at)
+        {
+            self.control_nudge = None;
+        }
+        Task::none()
+    }
+
+    /// Show `text` over the preview for a moment
+    fn show_control_nudge(&mut self, text: String) -> Task<cosmic::Action<Message>> {
+        let shown_at = Instant::now();
+        self.control_nudge = Some((shown_at, text));
+        Task::perform(
+            async {
+                tokio::time::sleep(std::time::Duration::from_millis(
+                    crate::constants::exposure_nudge::INDICATOR_MS,
+                ))
+                .await;
+            },
+            move |_| cosmic::Action::App(Message::HideControlNudge(shown_at)),
+        )
+    }
+
     pub(crate) fn handle_set_iso_sensitivity(
         &mut self,
         value: i32,
@@ -358,6 +451,29 @@ impl AppModel {
         )
     }
 
+    pub(crate) fn handle_set_power_line_frequency(
+        &mut self,
+        frequency: PowerLineFrequency,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(ref mut settings) = self.exposure_settings {
+            settings.power_line_frequency = Some(frequency);
+        }
+
+        // Applied to every camera that offers it from now on
+        self.config.power_line_frequency = Some(frequency);
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save power line frequency");
+        }
+
+        debug!(frequency = ?frequency, "Setting power line frequency");
+        self.set_v4l2_control(
+            v4l2_controls::V4L2_CID_POWER_LINE_FREQUENCY,
+            frequency.to_v4l2_value(),
+        )
+    }
+
     pub(crate) fn handle_toggle_auto_exposure_priority(&mut self) -> Task<cosmic::Action<Message>> {
         // Update local state
         let new_value = if let Some(ref mut settings) = self.exposure_settings {
@@ -511,6 +627,157 @@ impl AppModel {
         Task::none()
     }
 
+    // =========================================================================
+    // Focus Control Handlers
+    // =========================================================================
+
+    pub(crate) fn handle_set_continuous_autofocus(
+        &mut self,
+        enabled: bool,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(ref mut settings) = self.exposure_settings {
+            settings.focus_auto = Some(enabled);
+        }
+
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            return Task::none();
+        };
+
+        info!(enabled, "Setting continuous autofocus");
+        let has_focus_position = self.available_exposure_controls.focus.available;
+
+        Task::perform(
+            async move {
+                v4l2_controls::set_control(
+                    &device_path,
+                    v4l2_controls::V4L2_CID_FOCUS_AUTO,
+                    if enabled { 1 } else { 0 },
+                )?;
+
+                // When switching to manual, pick up the position autofocus settled on
+                if !enabled && has_focus_position {
+                    return Ok(v4l2_controls::get_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE,
+                    ));
+                }
+                Ok(None)
+            },
+            |result: Result<Option<i32>, String>| {
+                cosmic::Action::App(match result {
+                    Ok(position) => Message::FocusPositionRead(position),
+                    Err(e) => Message::ExposureControlFailed(e),
+                })
+            },
+        )
+    }
+
+    /// Run a single autofocus pass
+    ///
+    /// Uses `V4L2_CID_AUTO_FOCUS_START` when the device has it. Otherwise
+    /// continuous autofocus is pulsed on and off, which locks the focus
+    /// position it settled on (the usual approach for UVC webcams).
+    pub(crate) fn handle_trigger_autofocus(&mut self) -> Task<cosmic::Action<Message>> {
+        let continuous = self
+            .exposure_settings
+            .as_ref()
+            .and_then(|s| s.focus_auto)
+            .unwrap_or(false);
+        if continuous {
+            debug!("Ignoring one-shot autofocus while continuous autofocus is enabled");
+            return Task::none();
+        }
+
+        let Some(device_path) = self.get_v4l2_device_path() else {
+            return Task::none();
+        };
+
+        let controls = &self.available_exposure_controls;
+        let has_focus_start = controls.has_focus_start;
+        let has_focus_auto = controls.has_focus_auto;
+        let has_focus_position = controls.focus.available;
+        if !has_focus_start && !has_focus_auto {
+            return Task::none();
+        }
+
+        info!(has_focus_start, "Triggering one-shot autofocus");
+        let settle = std::time::Duration::from_millis(
+            crate::constants::timing::ONE_SHOT_AUTOFOCUS_SETTLE_MS,
+        );
+
+        Task::perform(
+            async move {
+                if has_focus_start {
+                    v4l2_controls::set_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_AUTO_FOCUS_START,
+                        1,
+                    )?;
+                    tokio::time::sleep(settle).await;
+                } else {
+                    v4l2_controls::set_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_FOCUS_AUTO,
+                        1,
+                    )?;
+                    tokio::time::sleep(settle).await;
+                    v4l2_controls::set_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_FOCUS_AUTO,
+                        0,
+                    )?;
+                }
+
+                if has_focus_position {
+                    return Ok(v4l2_controls::get_control(
+                        &device_path,
+                        v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE,
+                    ));
+                }
+                Ok(None)
+            },
+            |result: Result<Option<i32>, String>| {
+                cosmic::Action::App(match result {
+                    Ok(position) => Message::FocusPositionRead(position),
+                    Err(e) => Message::ExposureControlFailed(e),
+                })
+            },
+        )
+    }
+
+    pub(crate) fn handle_set_focus_absolute(
+        &mut self,
+        value: i32,
+    ) -> Task<cosmic::Action<Message>> {
+        let continuous = self
+            .exposure_settings
+            .as_ref()
+            .and_then(|s| s.focus_auto)
+            .unwrap_or(false);
+        if continuous {
+            return Task::none();
+        }
+
+        if let Some(ref mut settings) = self.exposure_settings {
+            settings.focus_absolute = Some(value);
+        }
+        debug!(focus = value, "Setting focus position");
+        self.set_v4l2_control(v4l2_controls::V4L2_CID_FOCUS_ABSOLUTE, value)
+    }
+
+    pub(crate) fn handle_focus_position_read(
+        &mut self,
+        position: Option<i32>,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(position) = position {
+            if let Some(ref mut settings) = self.exposure_settings {
+                settings.focus_absolute = Some(position);
+            }
+            info!(position, "Focus position updated from camera");
+        }
+        Task::none()
+    }
+
     // =========================================================================
     // V4L2 Helpers (used by exposure and color handlers)
     // =========================================================================
@@ -523,6 +790,15 @@ impl AppModel {
             .map(|info| info.path.clone())
     }
 
+    /// Probed V4L2 control ranges as they apply to the active format
+    pub(crate) fn active_device_controls(&self) -> v4l2_controls::DeviceControls {
+        let framerate = self
+            .active_format
+            .as_ref()
+            .and_then(|f| f.framerate.as_ref());
+        self.device_controls.for_framerate(framerate)
+    }
+
     /// Helper to set a V4L2 control value asynchronously
     ///
     /// This reduces duplication across exposure and color control handlers.
@@ -551,11 +827,20 @@ impl AppModel {
     pub(crate) fn query_exposure_controls_task(&self) -> Task<cosmic::Action<Message>> {
         if let Some(device_path) = self.get_v4l2_device_path() {
             let path = device_path.clone();
+            let probe = self.active_device_controls();
+            let power_line_frequency = self
+                .config
+                .power_line_frequency
+                .or_else(PowerLineFrequency::from_environment);
             Task::perform(
                 async move {
-                    let controls = crate::app::exposure_picker::query_exposure_controls(&path);
-                    let settings =
-                        crate::app::exposure_picker::get_exposure_settings(&path, &controls);
+                    let controls =
+                        crate::app::exposure_picker::query_exposure_controls(&path, &probe);
+                    let settings = crate::app::exposure_picker::get_exposure_settings(
+                        &path,
+                        &controls,
+                        power_line_frequency,
+                    );
                     let color_settings =
                         crate::app::exposure_picker::get_color_settings(&path, &controls);
                     (controls, settings, color_settings)
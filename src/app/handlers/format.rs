@@ -12,21 +12,16 @@ use crate::app::utils::{parse_codec, parse_resolution};
 use cosmic::Task;
 use cosmic::cosmic_config::CosmicConfigEntry;
 use std::sync::Arc;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
+
+/// Quiet time after the last resolution cycle press before the pipeline is rebuilt
+const CYCLE_SETTLE: std::time::Duration = std::time::Duration::from_millis(350);
 
 impl AppModel {
     // =========================================================================
     // Format Selection Handlers
     // =========================================================================
 
-    /// Get the current camera's sensor rotation
-    fn current_camera_rotation(&self) -> crate::backends::camera::types::SensorRotation {
-        self.available_cameras
-            .get(self.current_camera_index)
-            .map(|c| c.rotation)
-            .unwrap_or_default()
-    }
-
     pub(crate) fn handle_set_mode(&mut self, mode: CameraMode) -> Task<cosmic::Action<Message>> {
         if self.mode == mode {
             return Task::none();
@@ -302,6 +297,89 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_cycle_resolution(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(active) = &self.active_format else {
+            return Task::none();
+        };
+        // Continue from a target still waiting to be applied, so quick presses keep stepping
+        let current = self
+            .pending_cycle_resolution
+            .unwrap_or((active.width, active.height));
+        let Some(next) = crate::app::format_picker::preferences::next_resolution(
+            &self.available_formats,
+            &active.pixel_format,
+            current,
+        ) else {
+            debug!(pixel_format = %active.pixel_format, "Only one resolution, nothing to cycle");
+            return Task::none();
+        };
+
+        // Rebuild once the presses settle instead of once per press
+        self.pending_cycle_resolution = Some(next);
+        self.resolution_cycle_generation += 1;
+        let generation = self.resolution_cycle_generation;
+        Task::perform(
+            async move {
+                tokio::time::sleep(CYCLE_SETTLE).await;
+                generation
+            },
+            |generation| cosmic::Action::App(Message::ApplyCycledResolution(generation)),
+        )
+    }
+
+    pub(crate) fn handle_apply_cycled_resolution(
+        &mut self,
+        generation: u64,
+    ) -> Task<cosmic::Action<Message>> {
+        if generation != self.resolution_cycle_generation {
+            return Task::none();
+        }
+        let Some((width, height)) = self.pending_cycle_resolution.take() else {
+            return Task::none();
+        };
+        let Some(active) = &self.active_format else {
+            return Task::none();
+        };
+        if (active.width, active.height) == (width, height) {
+            return Task::none();
+        }
+
+        // Stay in the current pixel format, at the same frame rate when offered
+        let candidates: Vec<_> = self
+            .available_formats
+            .iter()
+            .filter(|f| {
+                f.width == width && f.height == height && f.pixel_format == active.pixel_format
+            })
+            .collect();
+        let Some(format) = candidates
+            .iter()
+            .find(|f| f.framerate == active.framerate)
+            .or_else(|| {
+                candidates
+                    .iter()
+                    .max_by_key(|f| f.framerate.map(|fps| fps.as_int()))
+            })
+            .copied()
+            .cloned()
+        else {
+            return Task::none();
+        };
+
+        info!(format = %format, "Cycled resolution");
+        self.active_format = Some(format);
+        self.current_frame = None;
+        self.update_framerate_options();
+        self.update_pixel_format_options();
+        self.update_codec_options();
+        self.save_settings();
+        self.insights.format_chain.resolution = format!("{}x{}", width, height);
+        self.insights.reset_format_metrics();
+        self.zoom_level = 1.0;
+        self.start_blur_transition();
+        self.query_exposure_controls_task()
+    }
+
     pub(crate) fn handle_select_bitrate_preset(
         &mut self,
         index: usize,
@@ -319,4 +397,183 @@ impl AppModel {
         }
         Task::none()
     }
+
+    pub(crate) fn handle_set_low_light_mode(
+        &mut self,
+        mode: crate::config::LowLightMode,
+    ) -> Task<cosmic::Action<Message>> {
+        if mode == self.config.low_light_mode {
+            return Task::none();
+        }
+        info!(mode = mode.display_name(), "Selected low-light mode");
+        self.config.low_light_mode = mode;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save low-light mode");
+        }
+
+        // Turning it off goes back to the mode the user picked
+        if mode == crate::config::LowLightMode::Off {
+            let task = self.revert_low_light_format();
+            self.low_light.reset();
+            return task;
+        }
+        Task::none()
+    }
+
+    /// Check the mean luma and offer, apply or undo a low-light mode
+    pub(crate) fn handle_low_light_tick(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::low_light::{self, LowLightSwitch, Verdict};
+        use crate::config::LowLightMode;
+        use cosmic::widget::toaster::Toast;
+
+        let mode = self.config.low_light_mode;
+        if mode == LowLightMode::Off
+            || self.recording.is_recording()
+            || self.current_frame_is_file_source
+        {
+            return Task::none();
+        }
+        let (Some(frame), Some(active)) = (&self.current_frame, self.active_format.clone()) else {
+            return Task::none();
+        };
+        let Some(grid) = crate::app::insights::focus::LumaGrid::sample(frame, low_light::GRID_SIDE)
+        else {
+            return Task::none();
+        };
+        let luma = low_light::mean_luma(&grid);
+
+        match self
+            .low_light
+            .observe(&active, luma, std::time::Instant::now())
+        {
+            None => Task::none(),
+            Some(Verdict::Dark) => {
+                let Some(target) = low_light::low_light_format(&self.available_formats, &active)
+                else {
+                    info!(luma, format = %active, "Scene stayed dark, no slower mode to switch to");
+                    return Task::none();
+                };
+                info!(
+                    luma,
+                    threshold = low_light::DARK_LUMA,
+                    from = %active,
+                    to = %target,
+                    "Scene stayed dark, a lower frame rate allows longer exposures"
+                );
+                let fps = framerate_label(&target);
+                if mode == LowLightMode::Switch {
+                    self.low_light.switched(LowLightSwitch {
+                        from: active,
+                        to: target.clone(),
+                    });
+                    self.apply_low_light_format(target);
+                    let toast = Toast::new(crate::fl!("low-light-switched", fps = fps))
+                        .action(crate::fl!("low-light-undo"), Message::RevertLowLightFormat);
+                    Task::batch([
+                        self.toasts.push(toast).map(cosmic::Action::App),
+                        self.query_exposure_controls_task(),
+                    ])
+                } else {
+                    let toast = Toast::new(crate::fl!("low-light-suggest", fps = fps))
+                        .action(crate::fl!("low-light-switch"), Message::ApplyLowLightFormat);
+                    self.toasts.push(toast).map(cosmic::Action::App)
+                }
+            }
+            Some(Verdict::Bright) => {
+                let Some(from) = self.low_light.switch().map(|switch| switch.from.clone()) else {
+                    return Task::none();
+                };
+                info!(
+                    luma,
+                    threshold = low_light::BRIGHT_LUMA,
+                    to = %from,
+                    "Scene is bright again, the original frame rate is enough"
+                );
+                let fps = framerate_label(&from);
+                if mode == LowLightMode::Switch {
+                    let task = self.revert_low_light_format();
+                    let toast = Toast::new(crate::fl!("low-light-restored", fps = fps));
+                    Task::batch([self.toasts.push(toast).map(cosmic::Action::App), task])
+                } else {
+                    let toast = Toast::new(crate::fl!("low-light-bright", fps = fps)).action(
+                        crate::fl!("low-light-switch-back"),
+                        Message::RevertLowLightFormat,
+                    );
+                    self.toasts.push(toast).map(cosmic::Action::App)
+                }
+            }
+        }
+    }
+
+    /// Accept the lower frame rate offered for low light
+    pub(crate) fn handle_apply_low_light_format(
+        &mut self,
+        toast: cosmic::widget::ToastId,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::low_light::{LowLightSwitch, low_light_format};
+
+        self.toasts.remove(toast);
+        if self.low_light.switch().is_some() || self.recording.is_recording() {
+            return Task::none();
+        }
+        let Some(active) = self.active_format.clone() else {
+            return Task::none();
+        };
+        let Some(target) = low_light_format(&self.available_formats, &active) else {
+            return Task::none();
+        };
+        info!(from = %active, to = %target, "Switching to a lower frame rate for low light");
+        self.low_light.switched(LowLightSwitch {
+            from: active,
+            to: target.clone(),
+        });
+        self.apply_low_light_format(target);
+        self.query_exposure_controls_task()
+    }
+
+    /// Undo a low-light switch from its toast
+    pub(crate) fn handle_revert_low_light_format(
+        &mut self,
+        toast: cosmic::widget::ToastId,
+    ) -> Task<cosmic::Action<Message>> {
+        self.toasts.remove(toast);
+        if self.recording.is_recording() {
+            return Task::none();
+        }
+        self.revert_low_light_format()
+    }
+
+    /// Go back to the mode a low-light switch replaced, if it is still active
+    fn revert_low_light_format(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(switch) = self.low_light.revert() else {
+            return Task::none();
+        };
+        if self.active_format.as_ref() != Some(&switch.to) {
+            return Task::none();
+        }
+        info!(format = %switch.from, "Restoring the mode replaced for low light");
+        self.apply_low_light_format(switch.from);
+        self.query_exposure_controls_task()
+    }
+
+    /// Change modes for low light without saving it as the selected mode
+    ///
+    /// The next start opens the mode the user picked, not the slower one.
+    fn apply_low_light_format(&mut self, format: crate::backends::camera::types::CameraFormat) {
+        self.active_format = Some(format);
+        self.current_frame = None;
+        self.update_all_dropdowns();
+        self.start_blur_transition();
+    }
+}
+
+/// Frame rate of `format` as shown in low-light toasts
+fn framerate_label(format: &crate::backends::camera::types::CameraFormat) -> String {
+    format
+        .framerate
+        .map(|fps| fps.to_string())
+        .unwrap_or_default()
 }
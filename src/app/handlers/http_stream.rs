@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! MJPEG HTTP stream handlers
+//!
+//! The server lives in [`crate::http_stream`] and runs as a subscription
+//! while enabled; these handlers change its settings and feed it frames.
+
+use crate::app::state::{AppModel, Message};
+use crate::backends::camera::types::CameraFrame;
+use crate::fl;
+use cosmic::Task;
+use cosmic::cosmic_config::CosmicConfigEntry;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+
+impl AppModel {
+    pub(crate) fn handle_toggle_http_stream(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.http_stream_enabled = !self.config.http_stream_enabled;
+        info!(
+            enabled = self.config.http_stream_enabled,
+            "Toggled MJPEG stream"
+        );
+        self.http_stream_status = None;
+        self.save_http_stream_settings();
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_http_stream_all_interfaces(
+        &mut self,
+    ) -> Task<cosmic::Action<Message>> {
+        self.config.http_stream_all_interfaces = !self.config.http_stream_all_interfaces;
+        info!(
+            all_interfaces = self.config.http_stream_all_interfaces,
+            "Toggled MJPEG stream interfaces"
+        );
+        // The subscription rebinds on the new address
+        self.http_stream_status = None;
+        self.save_http_stream_settings();
+        Task::none()
+    }
+
+    pub(crate) fn handle_apply_http_stream_port(&mut self) -> Task<cosmic::Action<Message>> {
+        let port = self
+            .http_stream_port_input
+            .trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|&port| port >= crate::constants::http_stream::MIN_PORT);
+        let Some(port) = port else {
+            warn!(input = %self.http_stream_port_input, "Rejected MJPEG stream port");
+            self.http_stream_port_input = self.config.http_stream_port.to_string();
+            return self
+                .toasts
+                .push(cosmic::widget::toaster::Toast::new(fl!(
+                    "settings-http-stream-port-invalid",
+                    min = crate::constants::http_stream::MIN_PORT
+                )))
+                .map(cosmic::Action::App);
+        };
+        if port == self.config.http_stream_port {
+            return Task::none();
+        }
+        info!(port, "Changed MJPEG stream port");
+        self.config.http_stream_port = port;
+        self.http_stream_port_input = port.to_string();
+        self.http_stream_status = None;
+        self.save_http_stream_settings();
+        Task::none()
+    }
+
+    pub(crate) fn handle_http_stream_bound(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        let toast = match &result {
+            Ok(_) => None,
+            Err(err) => {
+                error!(error = %err, "MJPEG stream failed to start");
+                Some(fl!("http-stream-bind-failed", error = err.as_str()))
+            }
+        };
+        self.http_stream_status = Some(result);
+        match toast {
+            Some(text) => self
+                .toasts
+                .push(cosmic::widget::toaster::Toast::new(text))
+                .map(cosmic::Action::App),
+            None => Task::none(),
+        }
+    }
+
+    /// Encode a frame for MJPEG viewers
+    ///
+    /// The frame goes through the same post-processing and JPEG encoder as a
+    /// photo. Only one frame is encoded at a time and at most
+    /// [`MAX_FPS`](crate::constants::http_stream::MAX_FPS) per second, and
+    /// nothing is encoded while no viewer is connected.
+    pub(crate) fn encode_http_stream_frame(
+        &mut self,
+        frame: &Arc<CameraFrame>,
+    ) -> Task<cosmic::Action<Message>> {
+        if !self.config.http_stream_enabled
+            || self.http_stream_encoding
+            || !crate::http_stream::wants_frames()
+        {
+            return Task::none();
+        }
+        let now = Instant::now();
+        let interval = Duration::from_secs(1) / crate::constants::http_stream::MAX_FPS;
+        if self
+            .http_stream_last_frame
+            .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            return Task::none();
+        }
+        self.http_stream_encoding = true;
+        self.http_stream_last_frame = Some(now);
+
+        // What the preview shows; the watermark and aspect crop are for saved photos only
+        let config = crate::pipelines::photo::PostProcessingConfig {
+            filter_type: self.selected_filter,
+            color_effect: self.config.color_effect,
+            zoom_level: self.zoom_level,
+            rotation: self.current_camera_rotation(),
+            ..Default::default()
+        };
+        let frame = Arc::clone(frame);
+
+        Task::perform(
+            async move {
+                use crate::pipelines::photo::{EncodingQuality, PhotoEncoder, PostProcessor};
+
+                let processed = PostProcessor::new(config).process(frame).await?;
+                let mut encoder = PhotoEncoder::new();
+                encoder.set_quality(EncodingQuality::Medium);
+                let encoded = encoder.encode(processed).await?;
+                crate::http_stream::publish(encoded.data);
+                Ok(())
+            },
+            |result| cosmic::Action::App(Message::HttpStreamFrameEncoded(result)),
+        )
+    }
+
+    pub(crate) fn handle_http_stream_frame_encoded(
+        &mut self,
+        result: Result<(), String>,
+    ) -> Task<cosmic::Action<Message>> {
+        self.http_stream_encoding = false;
+        if let Err(err) = result {
+            warn!(error = %err, "Failed to encode MJPEG stream frame");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_http_stream_max_clients(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&max) = crate::constants::http_stream::MAX_CLIENTS_OPTIONS.get(index) else {
+            return Task::none();
+        };
+        info!(max, "Selected MJPEG stream viewer limit");
+        self.config.http_stream_max_clients = max;
+        crate::http_stream::set_max_clients(max);
+        self.save_http_stream_settings();
+        Task::none()
+    }
+
+    fn save_http_stream_settings(&self) {
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save MJPEG stream settings");
+        }
+    }
+}
@@ -10,6 +10,8 @@ pub mod capture;
 pub mod color;
 pub mod exposure;
 pub mod format;
+pub mod http_stream;
+pub mod profiles;
 pub mod system;
 pub mod ui;
 pub mod virtual_camera;
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Capture profile handlers
+//!
+//! Saving, applying and deleting named profiles (see `app::profiles`).
+
+use crate::app::profiles::{self, FormatMatch};
+use crate::app::state::{AppModel, Message};
+use crate::config::{CaptureProfile, FormatSettings};
+use cosmic::Task;
+use cosmic::cosmic_config::CosmicConfigEntry;
+use tracing::{error, info, warn};
+
+impl AppModel {
+    pub(crate) fn handle_profile_name_input(
+        &mut self,
+        text: String,
+    ) -> Task<cosmic::Action<Message>> {
+        self.profile_name_input = text;
+        Task::none()
+    }
+
+    pub(crate) fn handle_save_profile(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(name) = profiles::validate_name(&self.profile_name_input).map(str::to_string)
+        else {
+            return Task::none();
+        };
+        let profile = CaptureProfile {
+            format: self.active_format.as_ref().map(|format| FormatSettings {
+                width: format.width,
+                height: format.height,
+                framerate: format.framerate.map(|fps| fps.as_int()),
+                pixel_format: format.pixel_format.clone(),
+            }),
+            rotation_override: self.config.rotation_override,
+            mirror_preview: self.config.mirror_preview,
+            zoom_percent: (self.zoom_level * 100.0).round() as u32,
+            filter: self.selected_filter,
+            color_effect: self.config.color_effect,
+            qr_detection: self.qr_detection_enabled,
+            false_color: self.config.false_color_lut,
+        };
+        info!(name = %name, ?profile, "Saved capture profile");
+        self.config.capture_profiles.insert(name, profile);
+        self.profile_name_input.clear();
+        self.save_profiles();
+        Task::none()
+    }
+
+    pub(crate) fn handle_delete_profile(&mut self, name: String) -> Task<cosmic::Action<Message>> {
+        if self.config.capture_profiles.remove(&name).is_some() {
+            info!(name = %name, "Deleted capture profile");
+            self.save_profiles();
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_apply_profile(&mut self, name: String) -> Task<cosmic::Action<Message>> {
+        let Some(profile) = self.config.capture_profiles.get(&name).cloned() else {
+            warn!(name = %name, "No capture profile with this name");
+            return Task::none();
+        };
+        info!(name = %name, "Applying capture profile");
+
+        // Format first: a format change resets the zoom
+        let mut tasks = vec![self.apply_profile_format(&name, profile.format.as_ref())];
+
+        // Render-only settings take effect with the next frame
+        self.config.rotation_override = profile.rotation_override;
+        self.config.mirror_preview = profile.mirror_preview;
+        self.config.color_effect = profile.color_effect;
+        self.config.false_color_lut = profile.false_color;
+        self.zoom_level = profile.zoom_level();
+        tasks.push(self.handle_select_filter(profile.filter));
+        if self.qr_detection_enabled != profile.qr_detection {
+            tasks.push(self.handle_toggle_qr_detection());
+        }
+        self.save_profiles();
+
+        Task::batch(tasks)
+    }
+
+    /// Switch to a profile's format, renegotiating only if it differs
+    fn apply_profile_format(
+        &mut self,
+        name: &str,
+        wanted: Option<&FormatSettings>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(wanted) = wanted else {
+            return Task::none();
+        };
+        if self.recording.is_recording() {
+            info!(name = %name, "Recording in progress, keeping the current format");
+            return Task::none();
+        }
+        let format = match profiles::resolve_format(&self.available_formats, wanted) {
+            FormatMatch::Exact(format) => format,
+            FormatMatch::Fallback(format) => {
+                warn!(
+                    name = %name,
+                    saved = ?wanted,
+                    using = %format,
+                    "Profile format isn't offered by this camera, using the closest one"
+                );
+                format
+            }
+            FormatMatch::Unsupported => {
+                warn!(
+                    name = %name,
+                    saved = ?wanted,
+                    "Profile resolution isn't offered by this camera, keeping the current format"
+                );
+                return Task::none();
+            }
+        };
+        if self.active_format.as_ref() == Some(&format) {
+            return Task::none();
+        }
+
+        info!(format = %format, "Profile changes the format");
+        self.insights.format_chain.resolution = format!("{}x{}", format.width, format.height);
+        self.active_format = Some(format);
+        self.current_frame = None;
+        self.update_all_dropdowns();
+        self.save_settings();
+        self.insights.reset_format_metrics();
+        self.start_blur_transition();
+        self.query_exposure_controls_task()
+    }
+
+    fn save_profiles(&self) {
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save capture profiles");
+        }
+    }
+}
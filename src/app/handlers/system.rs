@@ -5,11 +5,11 @@
 //! Handles gallery operations, filter selection, settings, recovery, bug reports,
 //! and QR code detection.
 
-use crate::app::state::{AppModel, FilterType, Message};
+use crate::app::state::{AppModel, FilterType, Message, ShutdownState, VirtualCameraState};
 use cosmic::Task;
 use cosmic::cosmic_config::CosmicConfigEntry;
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
 
 impl AppModel {
     // =========================================================================
@@ -17,7 +17,7 @@ impl AppModel {
     // =========================================================================
 
     pub(crate) fn handle_open_gallery(&self) -> Task<cosmic::Action<Message>> {
-        let photo_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let photo_dir = crate::app::photo_output_directory(&self.config);
         info!(path = %photo_dir.display(), "Opening gallery directory");
 
         if let Err(e) = open::that(&photo_dir) {
@@ -29,8 +29,8 @@ impl AppModel {
     }
 
     pub(crate) fn handle_refresh_gallery_thumbnail(&self) -> Task<cosmic::Action<Message>> {
-        let photos_dir = crate::app::get_photo_directory(&self.config.save_folder_name);
-        let videos_dir = crate::app::get_video_directory(&self.config.save_folder_name);
+        let photos_dir = crate::app::photo_output_directory(&self.config);
+        let videos_dir = crate::app::video_output_directory(&self.config);
         Task::perform(
             async move { crate::storage::load_latest_thumbnail(photos_dir, videos_dir).await },
             |handle| cosmic::Action::App(Message::GalleryThumbnailLoaded(handle)),
@@ -105,17 +105,99 @@ impl AppModel {
         cosmic::command::set_theme(app_theme.theme())
     }
 
-    pub(crate) fn handle_select_audio_device(
+    /// Reset every setting to its default (see [`crate::config::Config::reset`])
+    ///
+    /// Settings that are only read when a pipeline is built are applied by
+    /// rebuilding it; photos and recordings are left alone.
+    pub(crate) fn handle_reset_settings(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::config::LowLightMode;
+
+        self.reset_settings_dialog = false;
+        info!("Resetting all settings to their defaults");
+        let defaults = self.config.reset();
+
+        // Undo what the old settings changed beyond the config itself
+        let low_light = self.handle_set_low_light_mode(LowLightMode::Off);
+        if !defaults.virtual_camera_enabled && self.config.virtual_camera_enabled {
+            let _ = self.handle_toggle_virtual_camera_enabled();
+        }
+
+        self.config = defaults;
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save reset settings");
+        }
+
+        // Pipeline options kept outside the config go back to automatic as well
+        crate::pipelines::video::retro::configure(
+            self.config.retro_buffer_seconds,
+            self.config.retro_buffer_memory_mb,
+        );
+        crate::media::decoders::set_deinterlace_method(self.config.deinterlace_method.gst_method());
+        crate::media::decoders::set_v4l2_io_mode(self.config.v4l2_io_mode.gst_io_mode());
+        crate::media::decoders::set_decode_threads(self.config.decode_threads);
+        crate::backends::camera::pipewire::pipeline::set_appsink_buffering(
+            Some(self.config.appsink_max_buffers).filter(|&count| count > 0),
+            self.config.appsink_drop,
+        );
+        crate::media::decoders::set_low_power_decode(self.config.power_preference.is_low_power());
+        crate::gpu::set_low_power_adapter(self.config.power_preference.is_low_power());
+        crate::media::decoders::set_render_node(None);
+        crate::media::decoders::set_forced_caps(None);
+        crate::media::decoders::set_compare_decoder(None);
+        crate::app::insights::invalidate_decoder_availability();
+        crate::http_stream::set_max_clients(self.config.http_stream_max_clients);
+        self.forced_caps_input.clear();
+        self.forced_caps_error = None;
+        self.sync_mjpeg_resilience();
+        self.sync_preview_resolution_cap(self.mode);
+        self.sync_preview_throttle();
+
+        // Inputs shown in the settings
+        self.filename_template_input = self.config.filename_template.clone();
+        self.filename_template_error = None;
+        self.http_stream_port_input = self.config.http_stream_port.to_string();
+        self.http_stream_status = None;
+        self.rebinding_shortcut = None;
+        self.update_all_dropdowns();
+
+        // Preview state that isn't persisted but came from settings
+        let filter = self.handle_select_filter(FilterType::default());
+        self.zoom_level = 1.0;
+
+        self.rebuild_preview_pipeline();
+        Task::batch([
+            low_light,
+            filter,
+            cosmic::command::set_theme(self.config.app_theme.theme()),
+            self.toasts
+                .push(cosmic::widget::toaster::Toast::new(crate::fl!(
+                    "reset-settings-done"
+                )))
+                .map(cosmic::Action::App),
+        ])
+    }
+
+    pub(crate) fn handle_set_audio_source(
         &mut self,
         index: usize,
     ) -> Task<cosmic::Action<Message>> {
         if index < self.available_audio_devices.len() {
             info!(index, "Selected audio device index");
             self.current_audio_device_index = index;
+            // The meter restarts on the new source; don't show the old level meanwhile
+            self.audio_level = 0.0;
         }
         Task::none()
     }
 
+    pub(crate) fn handle_toggle_audio_mute(&mut self) -> Task<cosmic::Action<Message>> {
+        self.audio_muted = !self.audio_muted;
+        crate::pipelines::video::set_audio_muted(self.audio_muted);
+        Task::none()
+    }
+
     pub(crate) fn handle_select_video_encoder(
         &mut self,
         index: usize,
@@ -154,6 +236,94 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_set_photo_jpeg_quality(
+        &mut self,
+        quality: u8,
+    ) -> Task<cosmic::Action<Message>> {
+        self.config.photo_jpeg_quality = quality.clamp(1, 100);
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save JPEG quality");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_choose_output_directory(&self) -> Task<cosmic::Action<Message>> {
+        let start_dir = crate::app::photo_output_directory(&self.config);
+        Task::perform(
+            async move {
+                rfd::AsyncFileDialog::new()
+                    .set_directory(start_dir)
+                    .pick_folder()
+                    .await
+                    .map(|folder| folder.path().to_path_buf())
+            },
+            |dir| cosmic::Action::App(Message::OutputDirectorySelected(dir)),
+        )
+    }
+
+    pub(crate) fn handle_output_directory_selected(
+        &mut self,
+        dir: Option<std::path::PathBuf>,
+    ) -> Task<cosmic::Action<Message>> {
+        match dir {
+            Some(dir) => self.set_output_directory(Some(dir.display().to_string())),
+            None => Task::none(),
+        }
+    }
+
+    /// Save captures to `dir`, or to the default folders when `None`
+    pub(crate) fn set_output_directory(
+        &mut self,
+        dir: Option<String>,
+    ) -> Task<cosmic::Action<Message>> {
+        if dir == self.config.output_directory {
+            return Task::none();
+        }
+        info!(?dir, "Changed capture output directory");
+        self.config.output_directory = dir;
+
+        for created in [
+            std::fs::create_dir_all(crate::app::photo_output_directory(&self.config)),
+            std::fs::create_dir_all(crate::app::video_output_directory(&self.config)),
+        ] {
+            if let Err(err) = created {
+                error!(%err, "Failed to create capture output directory");
+            }
+        }
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save output directory");
+        }
+        // The gallery button shows the latest capture of the new location
+        Task::done(cosmic::Action::App(Message::RefreshGalleryThumbnail))
+    }
+
+    pub(crate) fn handle_filename_template_input(
+        &mut self,
+        text: String,
+    ) -> Task<cosmic::Action<Message>> {
+        match crate::pipelines::filename::FilenameTemplate::parse(&text) {
+            Ok(template) => {
+                self.filename_template_error = None;
+                if template.as_str() != self.config.filename_template {
+                    self.config.filename_template = template.as_str().to_string();
+                    if let Some(handler) = self.config_handler.as_ref()
+                        && let Err(err) = self.config.write_entry(handler)
+                    {
+                        error!(?err, "Failed to save filename template");
+                    }
+                }
+            }
+            Err(err) => self.filename_template_error = Some(err),
+        }
+        self.filename_template_input = text;
+        Task::none()
+    }
+
     pub(crate) fn handle_toggle_record_audio(&mut self) -> Task<cosmic::Action<Message>> {
         use cosmic::cosmic_config::CosmicConfigEntry;
 
@@ -207,6 +377,325 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_toggle_embed_capture_metadata(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.embed_capture_metadata = !self.config.embed_capture_metadata;
+        info!(
+            embed_capture_metadata = self.config.embed_capture_metadata,
+            "Toggled capture metadata embedding"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save capture metadata setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_first_frame_snapshot(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.first_frame_snapshot = !self.config.first_frame_snapshot;
+        info!(
+            first_frame_snapshot = self.config.first_frame_snapshot,
+            "Toggled first frame snapshot"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save first frame snapshot setting");
+        }
+        Task::none()
+    }
+
+    /// Change the watermark settings and save them
+    ///
+    /// Takes effect with the next photo or recording.
+    pub(crate) fn update_watermark(
+        &mut self,
+        change: impl FnOnce(&mut crate::config::WatermarkSettings),
+    ) -> Task<cosmic::Action<Message>> {
+        let before = self.config.watermark.enabled;
+        change(&mut self.config.watermark);
+        if self.config.watermark.enabled != before {
+            info!(
+                watermark = self.config.watermark.enabled,
+                "Toggled watermark burn-in"
+            );
+        }
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save watermark settings");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_validate_decoders(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.validate_decoders_at_startup = !self.config.validate_decoders_at_startup;
+        info!(
+            validate_decoders = self.config.validate_decoders_at_startup,
+            "Toggled startup decoder validation"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save decoder validation setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_control_socket(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.control_socket_enabled = !self.config.control_socket_enabled;
+        info!(
+            enabled = self.config.control_socket_enabled,
+            "Toggled control socket"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save control socket setting");
+        }
+        Task::none()
+    }
+
+    /// Map a control socket command onto the regular messages
+    pub(crate) fn handle_control_command(
+        &mut self,
+        command: crate::control_socket::ControlCommand,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::control_socket::ControlCommand;
+
+        info!(?command, "Control socket command");
+        match command {
+            ControlCommand::Snapshot => Task::done(cosmic::Action::App(Message::Capture)),
+            ControlCommand::StartRecording(path) => {
+                if self.recording.is_recording() {
+                    return Task::none();
+                }
+                self.recording_path_override = path;
+                Task::done(cosmic::Action::App(Message::ToggleRecording))
+            }
+            ControlCommand::StopRecording => {
+                if self.recording.is_recording() {
+                    Task::done(cosmic::Action::App(Message::ToggleRecording))
+                } else {
+                    Task::none()
+                }
+            }
+            ControlCommand::SetZoom(zoom) => {
+                self.zoom_level = zoom;
+                Task::none()
+            }
+            ControlCommand::Status => Task::none(),
+        }
+    }
+
+    pub(crate) fn handle_toggle_gray_auto_stretch(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.gray16_auto_stretch = !self.config.gray16_auto_stretch;
+        info!(
+            auto_stretch = self.config.gray16_auto_stretch,
+            "Toggled Y16 auto-stretch"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save auto-stretch setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_false_color(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.false_color_lut = !self.config.false_color_lut;
+        info!(
+            false_color = self.config.false_color_lut,
+            "Toggled false-color grayscale preview"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save false-color setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_mjpeg_resilience(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(camera) = self.available_cameras.get(self.current_camera_index) else {
+            return Task::none();
+        };
+        let cameras = &mut self.config.mjpeg_resilience_cameras;
+        let enabled = !cameras.remove(&camera.path);
+        if enabled {
+            cameras.insert(camera.path.clone());
+        }
+        info!(enabled, camera = %camera.name, "Toggled MJPEG error resilience");
+        crate::media::decoders::set_mjpeg_resilience(enabled);
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save MJPEG resilience setting");
+        }
+
+        // The decoder properties are part of the pipeline string, so rebuild it
+        self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_deinterlace_method(
+        &mut self,
+        method: crate::config::DeinterlaceMethod,
+    ) -> Task<cosmic::Action<Message>> {
+        info!(?method, "Selected deinterlace method");
+        self.config.deinterlace_method = method;
+        crate::media::decoders::set_deinterlace_method(method.gst_method());
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save deinterlace method");
+        }
+
+        // The deinterlace element is part of the pipeline string, so rebuild it
+        self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_v4l2_io_mode(
+        &mut self,
+        mode: crate::config::V4l2IoMode,
+    ) -> Task<cosmic::Action<Message>> {
+        info!(?mode, "Selected capture path");
+        self.config.v4l2_io_mode = mode;
+        crate::media::decoders::set_v4l2_io_mode(mode.gst_io_mode());
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save V4L2 io-mode");
+        }
+
+        // The source element is part of the pipeline string, so rebuild it
+        self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_decode_threads(
+        &mut self,
+        threads: Option<u32>,
+    ) -> Task<cosmic::Action<Message>> {
+        let toggled = threads.is_some() != self.config.decode_threads.is_some();
+        self.config.decode_threads = threads;
+        // Slider moves are applied on release, so dragging doesn't restart the preview
+        if toggled {
+            return self.handle_apply_decode_threads();
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_apply_decode_threads(&mut self) -> Task<cosmic::Action<Message>> {
+        let threads = self.config.decode_threads;
+        if threads == crate::media::decoders::decode_threads() {
+            return Task::none();
+        }
+        info!(?threads, "Selected decode thread cap");
+        crate::media::decoders::set_decode_threads(threads);
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save decode thread cap");
+        }
+
+        // The thread count is a decoder property in the pipeline string, so
+        // rebuild it when the running pipeline has a decoder it applies to
+        if crate::media::decoders::active_decode_threads().is_some() {
+            self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_power_preference(
+        &mut self,
+        preference: crate::config::PowerPreference,
+    ) -> Task<cosmic::Action<Message>> {
+        if preference == self.config.power_preference {
+            return Task::none();
+        }
+        info!(?preference, "Selected power preference");
+        self.config.power_preference = preference;
+        crate::media::decoders::set_low_power_decode(preference.is_low_power());
+        crate::gpu::set_low_power_adapter(preference.is_low_power());
+        // The cached decoders were picked in the old order
+        self.config.decoder_cache = crate::media::decoders::clear_decoder_cache();
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save power preference");
+        }
+
+        // Decoder order only changes on CPUs with efficiency cores; the
+        // decoder is part of the pipeline string, so rebuild it then
+        if crate::media::decoders::efficiency_cores().is_some() {
+            self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_preview_resolution_cap(
+        &mut self,
+        cap: crate::config::PreviewResolutionCap,
+    ) -> Task<cosmic::Action<Message>> {
+        if cap == self.config.preview_resolution_cap {
+            return Task::none();
+        }
+        info!(?cap, "Selected preview resolution cap");
+
+        // Keep the current figures so insights can show what the change bought
+        self.insights.preview_cap_baseline = Some(self.insights.performance_snapshot());
+        self.config.preview_resolution_cap = cap;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save preview resolution cap");
+        }
+
+        self.sync_preview_resolution_cap(self.mode);
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_render_node(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let requested = index
+            .checked_sub(1)
+            .and_then(|i| self.available_render_nodes.get(i))
+            .map(|node| node.path.clone());
+        info!(node = ?requested, "Selected VA-API render node");
+        self.config.vaapi_render_node = requested;
+        crate::media::decoders::set_render_node(self.config.vaapi_render_node.as_deref());
+        self.config.decoder_cache = crate::media::decoders::clear_decoder_cache();
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save VA-API render node");
+        }
+
+        // Decoder element names depend on the node: re-probe and rebuild the pipeline
+        crate::app::insights::invalidate_decoder_availability();
+        self.insights.full_pipeline_string = None;
+        self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+        Task::none()
+    }
+
     // =========================================================================
     // System & Recovery Handlers
     // =========================================================================
@@ -314,6 +803,16 @@ impl AppModel {
         Task::none()
     }
 
+    pub(crate) fn handle_reveal_recording(&self) -> Task<cosmic::Action<Message>> {
+        if let Some(summary) = &self.recording_summary {
+            let path = summary.path.display().to_string();
+            if let Err(e) = Self::show_in_file_manager(&path) {
+                error!(error = %e, path = %path, "Failed to show recording in file manager");
+            }
+        }
+        Task::none()
+    }
+
     // =========================================================================
     // Helper Functions
     // =========================================================================
@@ -448,8 +947,10 @@ impl AppModel {
 
     pub(crate) fn handle_update_insights_metrics(&mut self) -> Task<cosmic::Action<Message>> {
         use crate::app::insights::InsightsState;
+        use crate::app::upload_layout::{self, StrideProblem};
         use crate::app::video_primitive;
         use crate::backends::camera::pipewire::pipeline;
+        use crate::backends::camera::usb;
 
         // Update pipeline info and rebuild decoder chain only if changed
         let new_pipeline = crate::media::get_full_pipeline_string();
@@ -458,34 +959,67 @@ impl AppModel {
             self.insights.decoder_chain =
                 InsightsState::build_decoder_chain(pixel_format, new_pipeline.as_deref());
             self.insights.full_pipeline_string = new_pipeline;
+            // Timings from two different pipelines can't be compared
+            if self.insights.upload_benchmark.running().is_some() {
+                warn!("Pipeline changed during the upload benchmark, cancelling it");
+                self.insights.upload_benchmark.cancel();
+                pipeline::set_force_copy(false);
+            }
+            // Topology only changes with the device, so it is read once per pipeline
+            let v4l2_path = self
+                .available_cameras
+                .get(self.current_camera_index)
+                .and_then(|camera| camera.device_info.as_ref())
+                .map(|info| info.path.as_str());
+            self.insights.usb_topology = v4l2_path.and_then(usb::read_topology);
+            self.insights.device_identity = v4l2_path.and_then(usb::read_identity);
         }
+        self.insights.dmabuf_handoff = pipeline::get_active_pipeline()
+            .is_some_and(|active| crate::media::decoders::negotiated_dmabuf(&active));
+        self.insights.vaapi_device = match (
+            crate::media::decoders::render_node(),
+            &self.config.vaapi_render_node,
+        ) {
+            (Some(node), _) => node,
+            (None, Some(requested)) => {
+                crate::fl!("insights-vaapi-device-fallback", node = requested.as_str())
+            }
+            (None, None) => crate::fl!("insights-vaapi-device-default"),
+        };
 
         // Update format chain from active format and pipeline
         if let Some(format) = &self.active_format {
             let codec = crate::media::Codec::from_fourcc(&format.pixel_format);
             let needs_decoder = codec.needs_decoder();
 
-            // Determine source type from pipeline
+            // Determine source type from the camera's node, then the pipeline
+            let source_kind = self
+                .available_cameras
+                .get(self.current_camera_index)
+                .and_then(|camera| camera.source_kind);
             let source = self
                 .insights
                 .full_pipeline_string
                 .as_ref()
                 .map(|p| {
                     if !p.contains("pipewiresrc") {
-                        "Unknown"
+                        "Unknown".to_string()
+                    } else if let Some(kind) = source_kind {
+                        format!("{} via PipeWire", kind.display_name())
                     } else if p.contains("v4l2:") || p.contains("path=v4l2") {
-                        "V4L2 via PipeWire"
+                        "V4L2 via PipeWire".to_string()
                     } else if p.contains("libcamera") {
-                        "libcamera via PipeWire"
+                        "libcamera via PipeWire".to_string()
                     } else {
-                        "PipeWire"
+                        "PipeWire".to_string()
                     }
                 })
-                .unwrap_or("Unknown")
-                .to_string();
+                .unwrap_or_else(|| "Unknown".to_string());
 
-            // Get GStreamer output format (if decoding is involved)
-            let gstreamer_output = if needs_decoder {
+            // Get GStreamer output format (if decoding or forced caps are involved)
+            let forced_caps = pipeline::get_active_pipeline()
+                .and_then(|active| crate::media::decoders::negotiated_forced_caps(&active));
+            let gstreamer_output = if needs_decoder || forced_caps.is_some() {
                 pipeline::get_output_format()
             } else {
                 None
@@ -493,33 +1027,177 @@ impl AppModel {
 
             // Determine WGPU processing based on the format reaching the GPU
             let gpu_input_format = gstreamer_output.as_deref().unwrap_or(&format.pixel_format);
+            let shader = crate::gpu::conversion_path().unwrap_or_default().label();
+            // What the GPU actually did with the newest frame, not what the caps suggest
+            let gpu_path = crate::app::insights::passthrough::check_passthrough(
+                crate::media::Codec::from_fourcc(gpu_input_format),
+                self.current_frame
+                    .as_ref()
+                    .and_then(|_| video_primitive::get_gpu_path()),
+            );
+            if let crate::app::insights::PassthroughCheck::NeedlessConversion(converted) = gpu_path
+                && self.insights.format_chain.gpu_path != gpu_path
+            {
+                warn!(
+                    source = gpu_input_format,
+                    ?converted,
+                    "RGB frames go through the conversion shader"
+                );
+            }
+            self.insights.format_chain.gpu_path = gpu_path;
             let wgpu_processing = match gpu_input_format {
-                "I420" => "I420 → RGBA (compute shader)".to_string(),
-                "NV12" => "NV12 → RGBA (compute shader)".to_string(),
-                "YUYV" | "YUY2" => "YUYV → RGBA (compute shader)".to_string(),
-                "RGBA" => "Passthrough".to_string(),
-                other => format!("{} → RGBA (compute shader)", other),
+                _ if gpu_path == crate::app::insights::PassthroughCheck::Passthrough => {
+                    "Passthrough (no conversion)".to_string()
+                }
+                "I420" => format!("I420 → RGBA ({})", shader),
+                "NV12" => format!("NV12 → RGBA ({})", shader),
+                // Packed 4:2:2 formats differ only in byte order, so name it
+                "YUYV" | "YUY2" | "UYVY" | "YVYU" | "VYUY" => {
+                    let codec = crate::media::Codec::from_fourcc(gpu_input_format);
+                    format!(
+                        "{} ({}) → RGBA ({})",
+                        codec.fourcc(),
+                        codec.packed_layout().unwrap_or_default(),
+                        shader
+                    )
+                }
+                "GRAY8" | "GREY" | "Y8" => {
+                    let mapping = if self.config.false_color_lut {
+                        "false-color"
+                    } else {
+                        "grayscale"
+                    };
+                    format!("GREY → {} RGBA ({})", mapping, shader)
+                }
+                "GRAY16_LE" | "Y16" => {
+                    let mapping = if self.config.false_color_lut {
+                        "false-color"
+                    } else {
+                        "grayscale"
+                    };
+                    let range = if self.config.gray16_auto_stretch {
+                        "min/max stretch"
+                    } else {
+                        "full range"
+                    };
+                    format!("Y16 → {} RGBA ({}, {})", mapping, range, shader)
+                }
+                "RGBA" => "Passthrough (unverified)".to_string(),
+                other => format!("{} → RGBA ({})", other, shader),
+            };
+            // An RGB source converted on the way shows where the detour happens
+            let wgpu_processing = match gpu_path {
+                crate::app::insights::PassthroughCheck::NeedlessConversion(converted) => {
+                    format!("{} → {:?} → RGBA ({})", gpu_input_format, converted, shader)
+                }
+                _ => wgpu_processing,
             };
 
             self.insights.format_chain.source = source;
-            self.insights.format_chain.resolution = format!("{}x{}", format.width, format.height);
-            self.insights.format_chain.framerate = format
-                .framerate
-                .map(|fps| format!("{} fps", fps))
-                .unwrap_or_else(|| "N/A".to_string());
-            self.insights.format_chain.native_format = format.pixel_format.clone();
+            // The source may have changed resolution since the format was selected
+            self.insights.format_chain.update_resolution(
+                (format.width, format.height),
+                pipeline::get_negotiated_mode().map(|mode| (mode.width, mode.height)),
+                self.current_frame
+                    .as_ref()
+                    .map(|frame| (frame.width, frame.height)),
+            );
+            self.insights
+                .format_chain
+                .update_framerate(format.framerate, pipeline::get_frame_cadence());
+            // Report grayscale formats by their V4L2 names rather than GStreamer's,
+            // and packed 4:2:2 formats with their byte order (YUY2 is YUYV)
+            self.insights.format_chain.native_format = match (codec, codec.packed_layout()) {
+                (crate::media::Codec::GREY | crate::media::Codec::Y16, _) => {
+                    codec.fourcc().to_string()
+                }
+                (_, Some(layout)) => format!("{} ({})", codec.fourcc(), layout),
+                _ => format.pixel_format.clone(),
+            };
             self.insights.format_chain.gstreamer_output = gstreamer_output;
+            // Deinterlacing happens on the CPU before the GPU conversion
+            let interlace = pipeline::get_interlace_info();
+            let deinterlace_method = self.config.deinterlace_method.gst_method();
+            let wgpu_processing = match (&interlace, deinterlace_method) {
+                (Some(info), Some(method)) if info.is_interlaced() => {
+                    format!("Deinterlace ({}, CPU) → {}", method, wgpu_processing)
+                }
+                _ => wgpu_processing,
+            };
+            self.insights.format_chain.scan = interlace.map(|info| {
+                if info.is_interlaced() && deinterlace_method.is_none() {
+                    format!("{} — not deinterlaced", info.display())
+                } else {
+                    info.display()
+                }
+            });
+            let wgpu_processing = match self.config.color_effect {
+                crate::config::ColorEffect::None => wgpu_processing,
+                effect => format!("{} → {} effect", wgpu_processing, effect.display_name()),
+            };
+            let wgpu_processing = format!(
+                "{} → {} scaling",
+                wgpu_processing,
+                self.config.scaling_quality.display_name().to_lowercase()
+            );
             self.insights.format_chain.wgpu_processing = wgpu_processing;
+            self.insights.format_chain.compute_unavailable = !crate::gpu::compute_available();
+            self.insights.format_chain.colorimetry = pipeline::get_colorimetry();
+            self.insights.format_chain.forced_caps = forced_caps;
         }
 
+        self.insights.device_quirk = pipeline::get_applied_quirk();
+        self.insights.bandwidth_hint = match (&self.active_format, &self.insights.usb_topology) {
+            (Some(format), Some(topology)) => {
+                let requested = usb::VideoMode {
+                    width: format.width,
+                    height: format.height,
+                    fps: format.framerate.map(|fps| fps.as_f64() as f32),
+                };
+                let codec = crate::media::Codec::from_fourcc(&format.pixel_format);
+                let required_mbps = (!codec.needs_decoder())
+                    .then(|| usb::raw_bandwidth_mbps(&requested, codec.bits_per_pixel() / 8.0))
+                    .flatten();
+                usb::assess(
+                    &requested,
+                    pipeline::get_negotiated_mode().as_ref(),
+                    self.insights.rolling_fps,
+                    required_mbps,
+                    topology,
+                )
+            }
+            _ => None,
+        };
+        self.refresh_pipeline_elements();
+
         // Update performance metrics
         self.insights.gstreamer_decode_time_us = pipeline::get_decode_time_us();
         self.insights.dropped_frames = pipeline::get_dropped_frame_count();
+        self.insights.resolution_changes = pipeline::get_resolution_changes();
+        self.insights.frame_drop_policy = self.config.frame_drop_policy;
+        self.insights.policy_dropped_frames =
+            crate::backends::camera::frame_queue::get_policy_dropped_count();
+        self.insights.skipped_uploads = self.frame_dedup.skipped();
+        self.insights.retro_fill = crate::pipelines::video::retro::fill();
+        self.insights.recording_segment = crate::pipelines::video::active_segment();
+        self.insights.frozen = pipeline::is_frozen();
+        self.insights.stepped_frames = pipeline::get_steps_since_freeze();
+        self.insights.last_pts = pipeline::get_last_pts();
+        self.insights.record_cpu_sample();
+        let (frames, started_at) = pipeline::get_session_stats();
+        self.insights.record_session_frames(frames, started_at);
+        self.insights.decoder_errors =
+            crate::app::insights::DecoderErrorStats::new(pipeline::get_decoder_errors(), frames);
+        self.insights.record_thermal_sample();
+        self.insights.gpu_recovering = crate::gpu::is_recovering();
+        self.insights.gpu_device_losses = crate::gpu::device_losses();
         self.insights.frame_size_decoded = pipeline::get_last_frame_size() as usize;
         self.insights.copy_time_us = pipeline::get_copy_time_us();
 
         // Get GPU upload metrics from video_primitive
         self.insights.gpu_conversion_time_us = video_primitive::get_gpu_upload_time_us();
+        self.insights.texture_memory = video_primitive::get_texture_memory()
+            + crate::app::gallery_primitive::get_texture_memory();
         let gpu_frame_size = video_primitive::get_gpu_frame_size() as usize;
 
         // Calculate GPU upload bandwidth if we have meaningful upload time (> 10us)
@@ -531,11 +1209,681 @@ impl AppModel {
             self.insights.copy_bandwidth_mbps = 0.0;
         }
 
-        // Update frame latency from last frame capture time
+        // Forget an inhibition whose reason has passed (its subscription is gone)
+        let inhibit_reason = self.idle_inhibit_reason();
+        if self
+            .insights
+            .idle_inhibit
+            .as_ref()
+            .is_some_and(|(reason, _)| Some(*reason) != inhibit_reason)
+        {
+            self.insights.idle_inhibit = None;
+        }
+
+        // Padded rows are uploaded at their stride; shown so shearing can be
+        // ruled out, with a warning when the upload didn't skip the padding
+        let stride_check = self.current_frame.as_ref().and_then(|frame| {
+            upload_layout::check_stride(
+                frame.format,
+                frame.width,
+                frame.height,
+                frame.stride,
+                video_primitive::get_luma_upload(),
+            )
+        });
+        self.insights.format_chain.row_stride = stride_check.map(|check| {
+            format!(
+                "{} bytes ({} bytes of pixels at width {}, {} bytes padding)",
+                check.stride, check.row_bytes, check.width, check.padding_bytes
+            )
+        });
+        let stride_warning = stride_check
+            .and_then(|check| check.problem.map(|problem| (check, problem)))
+            .map(|(check, problem)| match problem {
+                StrideProblem::Sheared { bytes_per_row } => crate::fl!(
+                    "insights-stride-sheared",
+                    uploaded = bytes_per_row,
+                    stride = check.stride
+                ),
+                StrideProblem::PaddingVisible { extra_texels } => {
+                    crate::fl!("insights-stride-padding-visible", texels = extra_texels)
+                }
+                StrideProblem::StrideInferred { bytes_per_row } => crate::fl!(
+                    "insights-stride-inferred",
+                    stride = check.stride,
+                    uploaded = bytes_per_row
+                ),
+            });
+        if stride_warning.is_some() && self.insights.format_chain.stride_warning.is_none() {
+            warn!(
+                ?stride_check,
+                "Frame upload doesn't match the source stride"
+            );
+        }
+        self.insights.format_chain.stride_warning = stride_warning;
+
+        // Update frame latency from last frame capture time
         if let Some(frame) = &self.current_frame {
-            self.insights.frame_latency_us = frame.captured_at.elapsed().as_micros() as u64;
+            self.insights
+                .record_latency(frame.captured_at.elapsed().as_micros() as u64);
+        }
+
+        // A frame on screen trailing the newest decoded one means the render
+        // loop, not the pipeline, is behind
+        self.insights.presentation_lag_us = pipeline::get_last_sample_at()
+            .zip(video_primitive::get_presented_captured_at())
+            .map(|(newest, presented)| {
+                newest.saturating_duration_since(presented).as_micros() as u64
+            });
+
+        self.insights
+            .smooth_metrics(self.config.metrics_smoothing.alpha());
+
+        Task::none()
+    }
+
+    pub(crate) fn handle_display_frame(
+        &mut self,
+        at: std::time::Instant,
+    ) -> Task<cosmic::Action<Message>> {
+        // The surface is configured and drawing, so its present mode works
+        if self.present_mode_pending {
+            self.present_mode_pending = false;
+            crate::config::PresentMode::confirm_pending();
+            info!("Present mode confirmed by the first frame");
+        }
+        self.insights.record_display_frame(at);
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_present_mode(
+        &mut self,
+        mode: crate::config::PresentMode,
+    ) -> Task<cosmic::Action<Message>> {
+        info!(?mode, "Selected present mode (applies on next launch)");
+        self.config.present_mode = mode;
+        // Choosing a mode explicitly gives it another try on the next launch
+        self.config.unsupported_present_modes.retain(|m| *m != mode);
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save present mode selection");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_metrics_smoothing(
+        &mut self,
+        smoothing: crate::config::MetricsSmoothing,
+    ) -> Task<cosmic::Action<Message>> {
+        info!(?smoothing, "Selected metrics smoothing");
+        self.config.metrics_smoothing = smoothing;
+        // Start from the current raw values rather than blending in stale history
+        self.insights.smooth_metrics(None);
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save metrics smoothing");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_frame_drop_policy(
+        &mut self,
+        policy: crate::backends::camera::FrameDropPolicy,
+    ) -> Task<cosmic::Action<Message>> {
+        info!(?policy, "Selected frame drop policy");
+        // The camera subscription is keyed on the policy, so the queue is rebuilt
+        self.config.frame_drop_policy = policy;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save frame drop policy");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_appsink_max_buffers(
+        &mut self,
+        count: u32,
+    ) -> Task<cosmic::Action<Message>> {
+        let count = count.min(crate::constants::pipeline::APPSINK_MAX_BUFFERS_LIMIT);
+        info!(count, "Selected appsink max-buffers");
+        self.config.appsink_max_buffers = count;
+        self.apply_appsink_buffering();
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_appsink_drop(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.appsink_drop = !self.config.appsink_drop;
+        info!(enabled = self.config.appsink_drop, "Toggled appsink drop");
+        self.apply_appsink_buffering();
+        Task::none()
+    }
+
+    /// Reconfigure the running appsink and save its buffering settings
+    fn apply_appsink_buffering(&mut self) {
+        crate::backends::camera::pipewire::pipeline::set_appsink_buffering(
+            Some(self.config.appsink_max_buffers).filter(|&count| count > 0),
+            self.config.appsink_drop,
+        );
+        // Show the new values in the element inspector right away
+        self.refresh_pipeline_elements();
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save appsink buffering");
+        }
+    }
+
+    pub(crate) fn handle_toggle_skip_identical_frames(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.skip_identical_frames = !self.config.skip_identical_frames;
+        info!(
+            enabled = self.config.skip_identical_frames,
+            "Toggled skipping identical frames"
+        );
+        self.frame_dedup.reset();
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save identical frame setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_focus_peaking(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.focus_peaking = !self.config.focus_peaking;
+        info!(enabled = self.config.focus_peaking, "Toggled focus peaking");
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save focus peaking setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_pixel_probe(&mut self) -> Task<cosmic::Action<Message>> {
+        let enabled = !self.pixel_probe.enabled;
+        self.pixel_probe.reset();
+        self.pixel_probe.enabled = enabled;
+        info!(enabled, "Toggled pixel probe");
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_session_stats(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.session_stats = !self.config.session_stats;
+        info!(enabled = self.config.session_stats, "Toggled session stats");
+        self.insights.session_stats.reset();
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save session stats setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_reset_session_stats(&mut self) -> Task<cosmic::Action<Message>> {
+        info!("Reset session stats");
+        self.insights.session_stats.reset();
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_raw_microseconds(&mut self) -> Task<cosmic::Action<Message>> {
+        self.insights.raw_microseconds = !self.insights.raw_microseconds;
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_frame_counter_overlay(&mut self) -> Task<cosmic::Action<Message>> {
+        self.config.frame_counter_overlay = !self.config.frame_counter_overlay;
+        info!(
+            enabled = self.config.frame_counter_overlay,
+            "Toggled frame counter overlay"
+        );
+        // Show the frame on screen right away rather than waiting for the next one
+        match self.current_frame.as_ref().and_then(|frame| frame.stamp) {
+            Some(stamp) => stamp.write_label(&mut self.frame_counter_label),
+            None => self.frame_counter_label.clear(),
+        }
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save frame counter overlay setting");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_pixel_probe_hovered(
+        &mut self,
+        pixel: Option<(u32, u32)>,
+    ) -> Task<cosmic::Action<Message>> {
+        if !self.pixel_probe.enabled || pixel == self.pixel_probe.hovered {
+            return Task::none();
+        }
+        self.pixel_probe.hovered = pixel;
+        // A locked pixel keeps being read; moving the cursor doesn't change it
+        if self.pixel_probe.locked.is_some() {
+            return Task::none();
+        }
+        match (&self.current_frame, pixel) {
+            (Some(frame), Some(_)) => {
+                self.pixel_probe
+                    .refresh(frame, std::time::Instant::now(), false);
+            }
+            _ => self.pixel_probe.sample = None,
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_pixel_probe_lock(
+        &mut self,
+        pixel: (u32, u32),
+    ) -> Task<cosmic::Action<Message>> {
+        if !self.pixel_probe.enabled {
+            return Task::none();
+        }
+        self.pixel_probe.locked = match self.pixel_probe.locked {
+            Some(_) => None,
+            None => Some(pixel),
+        };
+        debug!(locked = ?self.pixel_probe.locked, "Pixel probe lock changed");
+        if let Some(frame) = &self.current_frame {
+            self.pixel_probe
+                .refresh(frame, std::time::Instant::now(), true);
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_stall_watchdog(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&seconds) = crate::constants::stall_watchdog::THRESHOLD_OPTIONS_SECS.get(index)
+        else {
+            return Task::none();
+        };
+        info!(seconds, "Selected stall watchdog threshold");
+        self.config.stall_watchdog_secs = seconds;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save stall watchdog threshold");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_camera_open_timeout(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&seconds) = crate::constants::camera_open::TIMEOUT_OPTIONS_SECS.get(index) else {
+            return Task::none();
+        };
+        info!(seconds, "Selected camera start timeout");
+        self.config.camera_open_timeout_secs = seconds;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save camera start timeout");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_background_preview(
+        &mut self,
+        behavior: crate::config::BackgroundPreview,
+    ) -> Task<cosmic::Action<Message>> {
+        info!(
+            behavior = behavior.display_name(),
+            "Selected background preview behavior"
+        );
+        self.config.background_preview = behavior;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save background preview behavior");
+        }
+        self.sync_preview_throttle();
+        Task::none()
+    }
+
+    pub(crate) fn handle_set_keep_awake(
+        &mut self,
+        behavior: crate::config::KeepAwake,
+    ) -> Task<cosmic::Action<Message>> {
+        info!(
+            behavior = behavior.display_name(),
+            "Selected keep awake behavior"
+        );
+        self.config.keep_awake = behavior;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save keep awake behavior");
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_idle_inhibit_changed(
+        &mut self,
+        reason: &'static str,
+        result: Result<crate::idle_inhibit::InhibitBackend, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        // The inhibition may have been dropped again before it was taken
+        if self.idle_inhibit_reason() == Some(reason) {
+            self.insights.idle_inhibit = Some((reason, result));
+        }
+        Task::none()
+    }
+
+    /// Why the screen is kept awake right now, if it is
+    ///
+    /// Preview frames only count while the camera runs; a paused background
+    /// preview lets the screen sleep.
+    pub(crate) fn idle_inhibit_reason(&self) -> Option<&'static str> {
+        let previewing = self.current_frame.is_some()
+            && self.background_power_saving() != Some(crate::config::BackgroundPreview::Pause);
+        let streaming = self.virtual_camera.is_streaming()
+            || (self.config.http_stream_enabled && crate::http_stream::wants_frames());
+        crate::idle_inhibit::inhibit_reason(
+            self.config.keep_awake,
+            crate::idle_inhibit::Activity {
+                previewing,
+                recording: self.recording.is_recording(),
+                streaming,
+            },
+        )
+    }
+
+    /// Warn about registry problems that hide installed decoders
+    pub(crate) fn handle_registry_checked(
+        &mut self,
+        problems: Vec<crate::media::decoders::RegistryProblem>,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::media::decoders::RegistryProblem;
+
+        let toasts = problems.into_iter().map(|problem| {
+            let text = match problem {
+                RegistryProblem::Stale => crate::fl!("registry-stale"),
+                RegistryProblem::Blacklisted(plugins) => {
+                    crate::fl!("registry-blacklisted", plugins = plugins.join(", "))
+                }
+            };
+            self.toasts
+                .push(cosmic::widget::toaster::Toast::new(text))
+                .map(cosmic::Action::App)
+        });
+        Task::batch(toasts.collect::<Vec<_>>())
+    }
+
+    pub(crate) fn handle_select_framerate_cap(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&fps) = crate::constants::framerate_cap::OPTIONS_FPS.get(index) else {
+            return Task::none();
+        };
+        info!(fps, "Selected framerate cap");
+        self.config.framerate_cap_fps = fps;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save framerate cap");
+        }
+        self.sync_preview_throttle();
+        Task::none()
+    }
+
+    pub(crate) fn handle_window_visibility_changed(
+        &mut self,
+        visible: bool,
+    ) -> Task<cosmic::Action<Message>> {
+        if self.window_focused == visible {
+            return Task::none();
+        }
+        debug!(visible, "Window focus changed");
+        self.window_focused = visible;
+        self.sync_preview_throttle();
+        Task::none()
+    }
+
+    /// Power saving in effect for the preview right now, if any
+    ///
+    /// A recording or a virtual camera stream keeps the preview running, as
+    /// it may be what they are fed from.
+    pub(crate) fn background_power_saving(&self) -> Option<crate::config::BackgroundPreview> {
+        let behavior = self.config.background_preview;
+        let busy = self.recording.is_recording() || self.virtual_camera.is_streaming();
+        (behavior != crate::config::BackgroundPreview::KeepRunning && !self.window_focused && !busy)
+            .then_some(behavior)
+    }
+
+    /// Throttle the preview pipeline to the framerate cap, or further if the
+    /// window is in the background
+    ///
+    /// Pausing needs no call here: the camera subscription is dropped while
+    /// [`Self::background_power_saving`] says so.
+    pub(crate) fn sync_preview_throttle(&self) {
+        use crate::backends::camera::pipewire::pipeline;
+
+        let background = (self.background_power_saving()
+            == Some(crate::config::BackgroundPreview::Throttle))
+        .then_some(crate::constants::background_preview::THROTTLED_FPS);
+        let cap = Some(self.config.framerate_cap_fps).filter(|&fps| fps > 0);
+        pipeline::set_throttle(background.into_iter().chain(cap).min());
+    }
+
+    pub(crate) fn handle_start_upload_benchmark(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::insights::UploadPath;
+        use crate::backends::camera::pipewire::pipeline;
+
+        let path = self.insights.upload_benchmark.start();
+        info!("Starting zero-copy vs copy upload benchmark");
+        pipeline::set_force_copy(path == UploadPath::Copy);
+        // Every frame must be uploaded to be measured
+        self.frame_dedup.reset();
+        Task::none()
+    }
+
+    /// Restart the pipeline if it stopped delivering frames without an error
+    ///
+    /// Frozen previews and intentionally stopped streams (HDR+ processing,
+    /// file source playback) never count as stalled. Counting starts at the
+    /// first frame; a pipeline that never delivers one is left to the open
+    /// timeout. Restarts in a row wait twice as long each time, and after
+    /// [`MAX_RESTARTS_IN_A_ROW`](crate::constants::stall_watchdog::MAX_RESTARTS_IN_A_ROW)
+    /// the watchdog gives up until frames flow again.
+    pub(crate) fn handle_stall_watchdog_tick(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::backends::camera::pipewire::pipeline;
+        use crate::constants::stall_watchdog::MAX_RESTARTS_IN_A_ROW;
+
+        let threshold = std::time::Duration::from_secs(self.config.stall_watchdog_secs.into());
+        let streak = self.insights.stall_restart_streak;
+        let backoff = threshold * (1 << streak.min(MAX_RESTARTS_IN_A_ROW));
+        let Some(since_last_sample) = pipeline::get_time_since_last_sample() else {
+            return Task::none();
+        };
+        if since_last_sample < threshold {
+            self.insights.stall_restarting = false;
+            // Frames kept coming for a whole backoff window, or came back after giving up
+            if streak >= MAX_RESTARTS_IN_A_ROW
+                || self
+                    .insights
+                    .last_stall_restart
+                    .is_none_or(|at| at.elapsed() >= backoff)
+            {
+                self.insights.stall_restart_streak = 0;
+            }
+            return Task::none();
+        }
+
+        let intentionally_stopped = pipeline::is_frozen()
+            || self.background_power_saving().is_some()
+            || self.burst_mode.stage == crate::app::state::BurstModeStage::Processing
+            || (self.mode == crate::app::state::CameraMode::Virtual
+                && self.virtual_camera_file_source.is_some());
+        // Back off between restarts of a pipeline that keeps stalling
+        let restart_pending = self
+            .insights
+            .last_stall_restart
+            .is_some_and(|at| at.elapsed() < backoff);
+        if threshold.is_zero()
+            || intentionally_stopped
+            || restart_pending
+            || streak >= MAX_RESTARTS_IN_A_ROW
+        {
+            return Task::none();
         }
 
+        self.insights.stall_restarts += 1;
+        self.insights.stall_restart_streak += 1;
+        self.insights.last_stall_restart = Some(std::time::Instant::now());
+        self.insights.stall_restarting = true;
+        if self.insights.stall_restart_streak == MAX_RESTARTS_IN_A_ROW {
+            warn!(
+                stalled_for = ?since_last_sample,
+                restarts = self.insights.stall_restarts,
+                "No frames from the pipeline, restarting it a last time"
+            );
+        } else {
+            warn!(
+                stalled_for = ?since_last_sample,
+                restarts = self.insights.stall_restarts,
+                "No frames from the pipeline, restarting it"
+            );
+        }
+        self.rebuild_preview_pipeline();
+        Task::none()
+    }
+
+    pub(crate) fn handle_apply_forced_caps(&mut self) -> Task<cosmic::Action<Message>> {
+        match crate::media::decoders::parse_forced_caps(&self.forced_caps_input) {
+            Ok(caps) => {
+                info!(%caps, "Forcing caps in front of the preview sink");
+                crate::media::decoders::set_forced_caps(Some(&caps));
+                self.forced_caps_error = None;
+                self.rebuild_preview_pipeline();
+            }
+            Err(err) => {
+                warn!(%err, "Rejected forced caps");
+                self.forced_caps_error = Some(err);
+            }
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_clear_forced_caps(&mut self) -> Task<cosmic::Action<Message>> {
+        info!("Cleared forced caps, back to automatic negotiation");
+        crate::media::decoders::set_forced_caps(None);
+        self.forced_caps_input.clear();
+        self.forced_caps_error = None;
+        self.rebuild_preview_pipeline();
+        Task::none()
+    }
+
+    pub(crate) fn handle_compare_decoder(
+        &mut self,
+        decoder: Option<&'static str>,
+    ) -> Task<cosmic::Action<Message>> {
+        match decoder {
+            Some(name) => info!(decoder = name, "Comparing decoders side by side"),
+            None => info!("Ended the decoder comparison"),
+        }
+        crate::media::decoders::set_compare_decoder(decoder);
+        self.rebuild_preview_pipeline();
+        Task::none()
+    }
+
+    /// Tear down a running decoder comparison (e.g. when the insights drawer closes)
+    pub(crate) fn end_decoder_comparison(&mut self) {
+        if crate::media::decoders::compare_decoder().is_some() {
+            info!("Insights closed, ending the decoder comparison");
+            crate::media::decoders::set_compare_decoder(None);
+            self.rebuild_preview_pipeline();
+        }
+    }
+
+    /// Restart the camera stream so a new pipeline is built with the current options
+    fn rebuild_preview_pipeline(&mut self) {
+        self.insights.full_pipeline_string = None;
+        self.camera_stream_restart_counter = self.camera_stream_restart_counter.wrapping_add(1);
+    }
+
+    pub(crate) fn handle_toggle_inspect_element(
+        &mut self,
+        name: String,
+    ) -> Task<cosmic::Action<Message>> {
+        let expanded = &mut self.insights.expanded_elements;
+        if !expanded.remove(&name) {
+            expanded.insert(name);
+        }
+        // Show the values right away instead of on the next metrics refresh
+        self.refresh_pipeline_elements();
+        Task::none()
+    }
+
+    /// Re-read the live pipeline's elements and the properties of expanded ones
+    fn refresh_pipeline_elements(&mut self) {
+        self.insights.pipeline_elements =
+            match crate::backends::camera::pipewire::pipeline::get_active_pipeline() {
+                Some(pipeline) => crate::app::insights::elements::inspect(
+                    &pipeline,
+                    &self.insights.expanded_elements,
+                ),
+                None => Vec::new(),
+            };
+    }
+
+    pub(crate) fn handle_toggle_freeze_preview(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::backends::camera::pipewire::pipeline;
+
+        pipeline::set_frozen(!pipeline::is_frozen());
+        self.insights.frozen = pipeline::is_frozen();
+        self.insights.stepped_frames = 0;
+        // Resuming restarts the sample clock, which would read as a lag spike
+        crate::app::video_primitive::reset_presented_captured_at();
+        self.insights.presentation_lag_us = None;
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_pin_metrics(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::app::insights::PinnedMetrics;
+
+        self.insights.pinned_metrics = match self.insights.pinned_metrics {
+            Some(_) => {
+                info!("Cleared pinned metrics");
+                None
+            }
+            None => {
+                let smoothed = self.config.metrics_smoothing.alpha().is_some();
+                let values = self.insights.metric_values(smoothed);
+                info!(?values, "Pinned metrics");
+                Some(PinnedMetrics::new(values))
+            }
+        };
+        Task::none()
+    }
+
+    pub(crate) fn handle_step_frame(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::backends::camera::pipewire::pipeline;
+
+        if !pipeline::is_frozen() {
+            return Task::none();
+        }
+        pipeline::step_frame();
+        self.insights.stepped_frames = pipeline::get_steps_since_freeze();
         Task::none()
     }
 
@@ -548,4 +1896,393 @@ impl AppModel {
             Task::none()
         }
     }
+
+    pub(crate) fn handle_copy_format_history(&self) -> Task<cosmic::Action<Message>> {
+        if self.insights.format_history.is_empty() {
+            return Task::none();
+        }
+        info!("Copying format history to clipboard");
+        cosmic::iced::clipboard::write(self.insights.format_history.to_text())
+            .map(|_: ()| cosmic::Action::App(Message::Noop))
+    }
+
+    pub(crate) fn handle_copy_device_identity(&self) -> Task<cosmic::Action<Message>> {
+        let Some(identity) = &self.insights.device_identity else {
+            return Task::none();
+        };
+        info!("Copying device identifiers to clipboard");
+        let text = crate::app::insights::diagnostics::identity_text(
+            identity,
+            self.config.diagnostics_include_device_ids,
+        );
+        cosmic::iced::clipboard::write(text).map(|_: ()| cosmic::Action::App(Message::Noop))
+    }
+
+    /// Assemble the diagnostics bundle from the current insights snapshot
+    fn build_diagnostics_bundle(&self) -> String {
+        crate::app::insights::diagnostics::DiagnosticsBundle {
+            insights: &self.insights,
+            device: self.available_cameras.get(self.current_camera_index),
+            gpu_adapter: crate::gpu::adapter_summary(),
+            gstreamer: crate::media::decoders::environment(),
+            bus_errors: crate::backends::camera::pipewire::pipeline::get_recent_bus_errors(),
+            include_device_ids: self.config.diagnostics_include_device_ids,
+        }
+        .render()
+    }
+
+    pub(crate) fn handle_copy_diagnostics_bundle(&self) -> Task<cosmic::Action<Message>> {
+        info!("Copying diagnostics bundle to clipboard");
+        cosmic::iced::clipboard::write(self.build_diagnostics_bundle())
+            .map(|_: ()| cosmic::Action::App(Message::Noop))
+    }
+
+    pub(crate) fn handle_save_diagnostics_bundle(&self) -> Task<cosmic::Action<Message>> {
+        let bundle = self.build_diagnostics_bundle();
+        let dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let path = dir.join(format!("camera-diagnostics-{}.md", timestamp));
+
+        Task::perform(
+            async move {
+                tokio::fs::create_dir_all(&dir)
+                    .await
+                    .map_err(|e| format!("Failed to create directory: {}", e))?;
+                tokio::fs::write(&path, bundle)
+                    .await
+                    .map_err(|e| format!("Failed to write diagnostics bundle: {}", e))?;
+                Ok(path.display().to_string())
+            },
+            |result| cosmic::Action::App(Message::DiagnosticsBundleSaved(result)),
+        )
+    }
+
+    pub(crate) fn handle_diagnostics_bundle_saved(
+        &mut self,
+        result: Result<String, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        match result {
+            Ok(path) => {
+                info!(path = %path, "Diagnostics bundle saved");
+                if let Err(e) = Self::show_in_file_manager(&path) {
+                    error!(error = %e, path = %path, "Failed to show diagnostics bundle");
+                }
+            }
+            Err(err) => error!(error = %err, "Failed to save diagnostics bundle"),
+        }
+        Task::none()
+    }
+
+    pub(crate) fn handle_toggle_diagnostics_include_device_ids(
+        &mut self,
+    ) -> Task<cosmic::Action<Message>> {
+        self.config.diagnostics_include_device_ids = !self.config.diagnostics_include_device_ids;
+        info!(
+            include = self.config.diagnostics_include_device_ids,
+            "Toggled device identifiers in diagnostics bundle"
+        );
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save diagnostics setting");
+        }
+        Task::none()
+    }
+
+    /// Export the live pipeline graph as a Graphviz file the user picks
+    ///
+    /// The graph is taken right away, so it shows the element states of the
+    /// moment the button was pressed rather than after the dialog.
+    pub(crate) fn handle_export_pipeline_dot(&self) -> Task<cosmic::Action<Message>> {
+        let Some(dot) = crate::backends::camera::pipewire::pipeline::get_active_pipeline_dot()
+        else {
+            info!("No pipeline active, nothing to export");
+            return Task::none();
+        };
+        let dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let file_name = format!("camera-pipeline-{}.dot", timestamp);
+
+        Task::perform(
+            async move {
+                let Some(file) = rfd::AsyncFileDialog::new()
+                    .set_directory(dir)
+                    .set_file_name(file_name)
+                    .add_filter("Graphviz", &["dot", "gv"])
+                    .save_file()
+                    .await
+                else {
+                    return Ok(None);
+                };
+                let path = file.path().to_path_buf();
+                tokio::fs::write(&path, dot)
+                    .await
+                    .map_err(|e| format!("Failed to write pipeline graph: {}", e))?;
+
+                // Render next to the .dot file when Graphviz is installed
+                let png = path.with_extension("png");
+                let rendered = match tokio::process::Command::new("dot")
+                    .arg("-Tpng")
+                    .arg("-o")
+                    .arg(&png)
+                    .arg(&path)
+                    .status()
+                    .await
+                {
+                    Ok(status) if status.success() => Some(png.display().to_string()),
+                    Ok(status) => {
+                        warn!(%status, "Graphviz failed to render the pipeline graph");
+                        None
+                    }
+                    Err(err) => {
+                        debug!(%err, "Graphviz not available, pipeline graph not rendered");
+                        None
+                    }
+                };
+                Ok(Some((path.display().to_string(), rendered)))
+            },
+            |result| cosmic::Action::App(Message::PipelineDotExported(result)),
+        )
+    }
+
+    pub(crate) fn handle_pipeline_dot_exported(
+        &mut self,
+        result: Result<Option<(String, Option<String>)>, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::widget::toaster::Toast;
+
+        let text = match result {
+            Ok(None) => return Task::none(),
+            Ok(Some((path, rendered))) => {
+                info!(path = %path, png = ?rendered, "Pipeline graph exported");
+                match rendered {
+                    Some(png) => crate::fl!("pipeline-graph-exported-png", path = path, png = png),
+                    None => crate::fl!("pipeline-graph-exported", path = path),
+                }
+            }
+            Err(err) => {
+                error!(error = %err, "Failed to export pipeline graph");
+                crate::fl!("pipeline-graph-failed")
+            }
+        };
+        self.toasts.push(Toast::new(text)).map(cosmic::Action::App)
+    }
+
+    pub(crate) fn handle_dump_raw_stream(
+        &mut self,
+        path: std::path::PathBuf,
+        duration: std::time::Duration,
+    ) -> Task<cosmic::Action<Message>> {
+        if self.insights.raw_dump_running {
+            info!("Stream dump already running");
+            return Task::none();
+        }
+        self.insights.raw_dump_running = true;
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    crate::backends::camera::pipewire::raw_dump::dump_raw_stream(&path, duration)
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+            },
+            |result| cosmic::Action::App(Message::RawStreamDumped(result)),
+        )
+    }
+
+    pub(crate) fn handle_start_raw_stream_dump(&mut self) -> Task<cosmic::Action<Message>> {
+        let dir = crate::app::get_photo_directory(&self.config.save_folder_name);
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        self.handle_dump_raw_stream(
+            dir.join(format!("camera-raw-{}", timestamp)),
+            crate::backends::camera::pipewire::raw_dump::DEFAULT_DURATION,
+        )
+    }
+
+    pub(crate) fn handle_raw_stream_dumped(
+        &mut self,
+        result: Result<crate::backends::camera::pipewire::raw_dump::RawDump, String>,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::widget::toaster::Toast;
+
+        self.insights.raw_dump_running = false;
+        let text = match result {
+            Ok(dump) => crate::fl!(
+                "raw-dump-saved",
+                frames = dump.frames,
+                path = dump.path.display().to_string()
+            ),
+            Err(err) => {
+                error!(error = %err, "Failed to dump the encoded stream");
+                crate::fl!("raw-dump-failed", error = err)
+            }
+        };
+        self.toasts.push(Toast::new(text)).map(cosmic::Action::App)
+    }
+
+    pub(crate) fn handle_close_requested(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.shutdown.is_some() {
+            return Task::none();
+        }
+        info!(
+            recording = self.recording.is_recording(),
+            virtual_camera = self.virtual_camera.is_streaming(),
+            "Window closed, shutting down"
+        );
+        let mut shutdown = ShutdownState::default();
+
+        // Stop the preview; nothing to finalize there
+        self.camera_cancel_flag
+            .store(true, std::sync::atomic::Ordering::Release);
+        self.current_frame = None;
+
+        // The virtual camera stops once its channels close; VirtualCameraStopped reports it
+        if self.virtual_camera.is_streaming() {
+            if let Some(sender) = self.virtual_camera.take_stop_sender() {
+                let _ = sender.send(());
+            }
+            self.virtual_camera = VirtualCameraState::Idle;
+            shutdown.virtual_camera = true;
+        }
+
+        // A recording is finalized first; RecordingStopped reports it
+        if let Some(sender) = self.recording.take_stop_sender() {
+            info!("Finalizing the active recording before exit");
+            let _ = sender.send(());
+            shutdown.recording = true;
+        }
+
+        let ready = shutdown.is_ready_to_release();
+        self.shutdown = Some(shutdown);
+        if ready {
+            return self.release_camera_for_shutdown();
+        }
+        Self::delay_task(
+            crate::constants::timing::SHUTDOWN_TIMEOUT_SECS * 1000,
+            Message::ShutdownTimeout,
+        )
+    }
+
+    pub(crate) fn handle_shutdown_timeout(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(shutdown) = self
+            .shutdown
+            .as_mut()
+            .filter(|shutdown| !shutdown.releasing)
+        else {
+            return Task::none();
+        };
+        warn!(
+            timeout_secs = crate::constants::timing::SHUTDOWN_TIMEOUT_SECS,
+            recording = shutdown.recording,
+            virtual_camera = shutdown.virtual_camera,
+            "Shutdown didn't finish in time, forcing it"
+        );
+        if std::mem::take(&mut shutdown.recording) {
+            shutdown
+                .errors
+                .push(crate::fl!("shutdown-recording-timeout"));
+        }
+        if std::mem::take(&mut shutdown.virtual_camera) {
+            shutdown
+                .errors
+                .push(crate::fl!("shutdown-virtual-camera-timeout"));
+        }
+        self.release_camera_for_shutdown()
+    }
+
+    /// Note that the recording (`recording`) or else the virtual camera
+    /// stopped during shutdown
+    ///
+    /// Releases the camera once nothing else is left to stop.
+    pub(crate) fn continue_shutdown(
+        &mut self,
+        recording: bool,
+        error: Option<String>,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(shutdown) = self.shutdown.as_mut() else {
+            return Task::none();
+        };
+        if recording {
+            shutdown.recording = false;
+        } else {
+            shutdown.virtual_camera = false;
+        }
+        if let Some(err) = error {
+            error!(error = %err, recording, "Stopping failed during shutdown");
+            shutdown.errors.push(if recording {
+                crate::fl!("shutdown-recording-failed", error = err)
+            } else {
+                crate::fl!("shutdown-virtual-camera-failed", error = err)
+            });
+        }
+        if shutdown.is_ready_to_release() {
+            return self.release_camera_for_shutdown();
+        }
+        Task::none()
+    }
+
+    /// Stop the camera pipeline off the UI thread and check the device is free
+    fn release_camera_for_shutdown(&mut self) -> Task<cosmic::Action<Message>> {
+        use crate::backends::camera::pipewire::pipeline;
+
+        if let Some(shutdown) = self.shutdown.as_mut() {
+            shutdown.releasing = true;
+        }
+        let device_node = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .and_then(|camera| camera.device_info.as_ref())
+            .map(|info| info.path.clone());
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    pipeline::release_active_pipeline(device_node.as_deref())
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Task join error: {}", e)))
+            },
+            |result| cosmic::Action::App(Message::ShutdownCameraReleased(result)),
+        )
+    }
+
+    /// Exit, first showing any problem the shutdown ran into
+    pub(crate) fn handle_shutdown_camera_released(
+        &mut self,
+        result: Result<(), String>,
+    ) -> Task<cosmic::Action<Message>> {
+        use cosmic::widget::toaster::Toast;
+
+        // Stays set, so nothing restarts while a problem is shown
+        let Some(shutdown) = self.shutdown.as_mut() else {
+            return Task::none();
+        };
+        let mut errors = std::mem::take(&mut shutdown.errors);
+        match result {
+            Ok(()) => info!("Camera released"),
+            Err(err) => {
+                error!(error = %err, "Camera not released at exit");
+                errors.push(crate::fl!("shutdown-camera-busy", error = err));
+            }
+        }
+        // Getting here means the surface never aborted, even without a frame
+        crate::config::PresentMode::confirm_pending();
+
+        if errors.is_empty() {
+            info!("Shutdown complete");
+            return cosmic::iced::exit();
+        }
+        // The window is still open, so the problem can be shown before it goes
+        info!(problems = errors.len(), "Shutdown complete with problems");
+        Task::batch([
+            self.toasts
+                .push(Toast::new(errors.join("\n")))
+                .map(cosmic::Action::App),
+            Self::delay_task(
+                crate::constants::timing::SHUTDOWN_ERROR_DISPLAY_MS,
+                Message::ShutdownExit,
+            ),
+        ])
+    }
 }
@@ -6,7 +6,7 @@
 
 use crate::app::state::{AppModel, ContextPage, Message};
 use cosmic::Task;
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 impl AppModel {
     // =========================================================================
@@ -36,6 +36,10 @@ impl AppModel {
             self.context_page = context_page;
             self.core.window.show_context = true;
         }
+        // The decoder comparison is only shown while insights are open
+        if self.context_page != ContextPage::Insights || !self.core.window.show_context {
+            self.end_decoder_comparison();
+        }
         Task::none()
     }
 
@@ -108,6 +112,112 @@ impl AppModel {
         Task::none()
     }
 
+    // =========================================================================
+    // Keyboard Shortcut Handlers
+    // =========================================================================
+
+    pub(crate) fn handle_key_pressed(
+        &mut self,
+        key: cosmic::iced::keyboard::Key,
+        modifiers: cosmic::iced::keyboard::Modifiers,
+    ) -> Task<cosmic::Action<Message>> {
+        use crate::app::shortcuts;
+
+        if !shortcuts::plain_press(modifiers) {
+            return Task::none();
+        }
+
+        if let Some(action) = self.rebinding_shortcut.take() {
+            if shortcuts::is_cancel(&key) {
+                return Task::none();
+            }
+            if let Some(name) = shortcuts::key_name(&key) {
+                info!(?action, key = %name, "Rebound keyboard shortcut");
+                shortcuts::rebind(&mut self.config.keyboard_shortcuts, action, name);
+                self.save_keyboard_shortcuts();
+            }
+            return Task::none();
+        }
+
+        let Some(action) = shortcuts::key_name(&key)
+            .and_then(|name| shortcuts::lookup(&self.config.keyboard_shortcuts, &name))
+        else {
+            return Task::none();
+        };
+        debug!(?action, "Keyboard shortcut");
+        let steps = if modifiers.shift() {
+            self.config.nudge_coarse_steps as i32
+        } else {
+            1
+        };
+        Task::done(cosmic::Action::App(shortcuts::message(
+            action,
+            steps,
+            &self.config,
+        )))
+    }
+
+    pub(crate) fn handle_start_rebind_shortcut(
+        &mut self,
+        action: crate::config::ShortcutAction,
+    ) -> Task<cosmic::Action<Message>> {
+        // Pressing the button again cancels
+        self.rebinding_shortcut = (self.rebinding_shortcut != Some(action)).then_some(action);
+        Task::none()
+    }
+
+    pub(crate) fn handle_reset_shortcuts(&mut self) -> Task<cosmic::Action<Message>> {
+        info!("Reset keyboard shortcuts");
+        self.rebinding_shortcut = None;
+        self.config.keyboard_shortcuts.clear();
+        self.save_keyboard_shortcuts();
+        Task::none()
+    }
+
+    pub(crate) fn handle_select_nudge_coarse_steps(
+        &mut self,
+        index: usize,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(&steps) = crate::constants::exposure_nudge::COARSE_STEP_OPTIONS.get(index) else {
+            return Task::none();
+        };
+        info!(steps, "Selected coarse nudge step");
+        self.config.nudge_coarse_steps = steps;
+        self.save_keyboard_shortcuts();
+        Task::none()
+    }
+
+    fn save_keyboard_shortcuts(&self) {
+        use cosmic::cosmic_config::CosmicConfigEntry;
+
+        if let Some(handler) = self.config_handler.as_ref()
+            && let Err(err) = self.config.write_entry(handler)
+        {
+            error!(?err, "Failed to save keyboard shortcuts");
+        }
+    }
+
+    pub(crate) fn handle_cycle_preview_overlay(&mut self) -> Task<cosmic::Action<Message>> {
+        // Off -> QR detection -> false color -> off; false color only exists for gray formats
+        let gray_format = self.active_format.as_ref().is_some_and(|format| {
+            matches!(
+                crate::media::Codec::from_fourcc(&format.pixel_format),
+                crate::media::Codec::GREY | crate::media::Codec::Y16
+            )
+        });
+        if self.config.false_color_lut {
+            return self.handle_toggle_false_color();
+        }
+        if !self.qr_detection_enabled {
+            return self.handle_toggle_qr_detection();
+        }
+        let qr_off = self.handle_toggle_qr_detection();
+        if !gray_format {
+            return qr_off;
+        }
+        Task::batch([qr_off, self.handle_toggle_false_color()])
+    }
+
     // =========================================================================
     // Tools Menu Handlers
     // =========================================================================
@@ -569,6 +569,9 @@ impl AppModel {
         result: Result<(), String>,
     ) -> Task<cosmic::Action<Message>> {
         self.virtual_camera = VirtualCameraState::Idle;
+        if self.shutdown.is_some() {
+            return self.continue_shutdown(false, result.err());
+        }
         // Clear the file source preview receiver (only relevant for file source streaming)
         self.file_source_preview_receiver = None;
 
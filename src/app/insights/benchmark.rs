@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Zero-copy vs copy upload benchmark
+//!
+//! Frames normally reach the GPU straight from the mapped GStreamer buffer.
+//! The benchmark measures a fixed number of frames on that path, then the
+//! same number with the pipeline copying each frame into owned memory first,
+//! and reports the frame wrap/copy time and GPU upload time of both paths.
+//! The first frames after each switch are not measured, so frames still
+//! queued from the previous path don't skew the results.
+
+/// Frames measured on each path
+pub const FRAMES_PER_PATH: usize = 120;
+
+/// Frames ignored after switching paths
+const WARMUP_FRAMES: usize = 5;
+
+/// How frames get from the GStreamer buffer to the GPU upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadPath {
+    /// Upload directly from the mapped buffer
+    ZeroCopy,
+    /// Copy into owned memory, then upload
+    Copy,
+}
+
+impl UploadPath {
+    /// Get display name for this path
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            UploadPath::ZeroCopy => "Zero-copy",
+            UploadPath::Copy => "Copy",
+        }
+    }
+}
+
+/// Average timings measured on one path
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PathResult {
+    /// Frames measured
+    pub frames: usize,
+    /// Average frame wrap/copy time in microseconds
+    pub copy_time_us: f64,
+    /// Average GPU upload time in microseconds
+    pub gpu_conversion_time_us: f64,
+}
+
+impl PathResult {
+    /// Average time from buffer to uploaded texture in microseconds
+    pub fn total_us(&self) -> f64 {
+        self.copy_time_us + self.gpu_conversion_time_us
+    }
+
+    fn from_samples(samples: &[(u64, u64)]) -> Self {
+        let frames = samples.len();
+        let average = |sum: u64| sum as f64 / frames.max(1) as f64;
+        Self {
+            frames,
+            copy_time_us: average(samples.iter().map(|(copy, _)| copy).sum()),
+            gpu_conversion_time_us: average(samples.iter().map(|(_, gpu)| gpu).sum()),
+        }
+    }
+}
+
+/// Benchmark progress and results
+#[derive(Debug, Clone, Default)]
+pub struct UploadBenchmark {
+    /// Path being measured (None when idle)
+    running: Option<UploadPath>,
+    /// Frames still to be ignored on the current path
+    warmup_left: usize,
+    /// (copy_time_us, gpu_conversion_time_us) per measured frame
+    samples: Vec<(u64, u64)>,
+    /// Result of the zero-copy run
+    pub zero_copy: Option<PathResult>,
+    /// Result of the copy run
+    pub copy: Option<PathResult>,
+}
+
+impl UploadBenchmark {
+    /// Discard previous results and start measuring the zero-copy path
+    ///
+    /// Returns the path the pipeline should use.
+    pub fn start(&mut self) -> UploadPath {
+        *self = Self {
+            running: Some(UploadPath::ZeroCopy),
+            warmup_left: WARMUP_FRAMES,
+            ..Self::default()
+        };
+        UploadPath::ZeroCopy
+    }
+
+    /// Stop without results (e.g. the pipeline was rebuilt mid-run)
+    pub fn cancel(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Path being measured, if the benchmark is running
+    pub fn running(&self) -> Option<UploadPath> {
+        self.running
+    }
+
+    /// Frames measured on the current path
+    pub fn progress(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Record the timings of one displayed frame
+    ///
+    /// Returns the path the pipeline should switch to when a run completes:
+    /// `Copy` after the zero-copy run, and `ZeroCopy` again once both are done.
+    pub fn record(&mut self, copy_time_us: u64, gpu_conversion_time_us: u64) -> Option<UploadPath> {
+        let path = self.running?;
+        if self.warmup_left > 0 {
+            self.warmup_left -= 1;
+            return None;
+        }
+        self.samples.push((copy_time_us, gpu_conversion_time_us));
+        if self.samples.len() < FRAMES_PER_PATH {
+            return None;
+        }
+
+        let result = PathResult::from_samples(&self.samples);
+        self.samples.clear();
+        self.warmup_left = WARMUP_FRAMES;
+        match path {
+            UploadPath::ZeroCopy => {
+                self.zero_copy = Some(result);
+                self.running = Some(UploadPath::Copy);
+                Some(UploadPath::Copy)
+            }
+            UploadPath::Copy => {
+                self.copy = Some(result);
+                self.running = None;
+                Some(UploadPath::ZeroCopy)
+            }
+        }
+    }
+
+    /// How many times faster zero-copy is than copying (below 1.0 = slower)
+    pub fn zero_copy_speedup(&self) -> Option<f64> {
+        let (zero_copy, copy) = (self.zero_copy?, self.copy?);
+        (zero_copy.total_us() > 0.0).then(|| copy.total_us() / zero_copy.total_us())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(benchmark: &mut UploadBenchmark, copy_us: u64, gpu_us: u64) -> Option<UploadPath> {
+        (0..WARMUP_FRAMES + FRAMES_PER_PATH)
+            .filter_map(|_| benchmark.record(copy_us, gpu_us))
+            .last()
+    }
+
+    #[test]
+    fn test_runs_both_paths_and_returns_to_zero_copy() {
+        let mut benchmark = UploadBenchmark::default();
+        assert_eq!(benchmark.start(), UploadPath::ZeroCopy);
+        assert_eq!(run(&mut benchmark, 5, 1000), Some(UploadPath::Copy));
+        assert_eq!(benchmark.running(), Some(UploadPath::Copy));
+        assert_eq!(run(&mut benchmark, 995, 1000), Some(UploadPath::ZeroCopy));
+        assert_eq!(benchmark.running(), None);
+
+        let zero_copy = benchmark.zero_copy.expect("zero-copy result");
+        assert_eq!(zero_copy.frames, FRAMES_PER_PATH);
+        assert_eq!(zero_copy.total_us(), 1005.0);
+        assert_eq!(benchmark.zero_copy_speedup(), Some(1995.0 / 1005.0));
+    }
+
+    #[test]
+    fn test_warmup_frames_are_not_measured() {
+        let mut benchmark = UploadBenchmark::default();
+        benchmark.start();
+        for _ in 0..WARMUP_FRAMES {
+            benchmark.record(1_000_000, 1_000_000);
+        }
+        assert_eq!(benchmark.progress(), 0);
+        benchmark.record(10, 20);
+        assert_eq!(benchmark.progress(), 1);
+    }
+}
@@ -0,0 +1,648 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Diagnostics bundle for bug reports
+//!
+//! Collects the pipeline string, decoder fallback chain, format chain,
+//! performance metrics (with whether each is measured or estimated), GPU
+//! adapter and recent bus errors into a single markdown document.
+//!
+//! The layout is versioned: the first line is always
+//! `<!-- camera-diagnostics format=N -->`, sections are `## ` headings with
+//! fixed names, and values are `- key: value` lines with snake_case keys.
+//! Bump [`BUNDLE_FORMAT_VERSION`] whenever a section or key is renamed or removed.
+
+use super::{FallbackState, InsightsState, ReportedMetric};
+use crate::backends::camera::types::CameraDevice;
+use crate::backends::camera::usb::DeviceIdentity;
+use crate::media::decoders::GstEnvironment;
+
+/// Version of the bundle layout
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Placeholder for identifiers removed from the bundle
+pub const REDACTED: &str = "<redacted>";
+
+/// Pipeline element properties that identify the physical device
+const IDENTIFYING_PROPERTIES: &[&str] = &["path=", "target-object=", "serial=", "device="];
+
+/// Inputs for a diagnostics bundle
+pub struct DiagnosticsBundle<'a> {
+    /// Current insights snapshot
+    pub insights: &'a InsightsState,
+    /// Active camera, if any
+    pub device: Option<&'a CameraDevice>,
+    /// GPU adapter description
+    pub gpu_adapter: Option<String>,
+    /// Linked GStreamer version and plugin locations
+    pub gstreamer: Option<&'a GstEnvironment>,
+    /// Recent GStreamer bus errors and warnings
+    pub bus_errors: Vec<String>,
+    /// Keep device paths and serials instead of redacting them
+    pub include_device_ids: bool,
+}
+
+/// Replace identifying element properties in a gst-launch pipeline string
+pub fn redact_pipeline(pipeline: &str) -> String {
+    pipeline
+        .split(' ')
+        .map(|token| {
+            match IDENTIFYING_PROPERTIES
+                .iter()
+                .find(|prefix| token.starts_with(**prefix))
+            {
+                Some(prefix) => format!("{}{}", prefix, REDACTED),
+                None => token.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `key: value` lines of a camera's identifiers, the serial redacted unless included
+pub fn identity_text(identity: &DeviceIdentity, include_serial: bool) -> String {
+    let mut lines = vec![format!("node: {}", identity.node)];
+    let mut push = |key: &str, value: Option<String>| {
+        if let Some(value) = value {
+            lines.push(format!("{}: {}", key, value));
+        }
+    };
+    push("driver", identity.driver.clone());
+    push("usb_id", identity.usb_id.map(|id| id.to_string()));
+    push("bus_path", identity.bus_path.clone());
+    push("manufacturer", identity.manufacturer.clone());
+    push("product", identity.product.clone());
+    push(
+        "serial",
+        identity.serial.as_ref().map(|serial| {
+            if include_serial {
+                serial.clone()
+            } else {
+                REDACTED.to_string()
+            }
+        }),
+    );
+    lines.join("\n")
+}
+
+fn state_name(state: FallbackState) -> &'static str {
+    match state {
+        FallbackState::Selected => "selected",
+        FallbackState::Available => "available",
+        FallbackState::Broken => "broken",
+        FallbackState::Unavailable => "unavailable",
+    }
+}
+
+impl DiagnosticsBundle<'_> {
+    fn identifier(&self, value: &str) -> String {
+        if self.include_device_ids {
+            value.to_string()
+        } else {
+            REDACTED.to_string()
+        }
+    }
+
+    /// Render the bundle as markdown
+    pub fn render(&self) -> String {
+        let insights = self.insights;
+        let mut out = String::new();
+        let mut line = |text: String| {
+            out.push_str(&text);
+            out.push('\n');
+        };
+
+        line(format!(
+            "<!-- camera-diagnostics format={} -->",
+            BUNDLE_FORMAT_VERSION
+        ));
+        line("# Camera Diagnostics".to_string());
+        line(String::new());
+        line(format!("- format_version: {}", BUNDLE_FORMAT_VERSION));
+        line(format!("- app_version: {}", env!("CARGO_PKG_VERSION")));
+        line(format!(
+            "- generated: {}",
+            chrono::Local::now().to_rfc3339()
+        ));
+        line(format!(
+            "- device_ids: {}",
+            if self.include_device_ids {
+                "included"
+            } else {
+                "redacted"
+            }
+        ));
+
+        line(String::new());
+        line("## Device".to_string());
+        match self.device {
+            Some(device) => {
+                line(format!("- name: {}", device.name));
+                line(format!("- path: {}", self.identifier(&device.path)));
+                if let Some(metadata_path) = &device.metadata_path {
+                    line(format!(
+                        "- metadata_path: {}",
+                        self.identifier(metadata_path)
+                    ));
+                }
+                if let Some(info) = &device.device_info {
+                    line(format!("- card: {}", info.card));
+                    line(format!("- driver: {}", info.driver));
+                    line(format!("- v4l2_path: {}", self.identifier(&info.path)));
+                    line(format!(
+                        "- v4l2_real_path: {}",
+                        self.identifier(&info.real_path)
+                    ));
+                    if let Some(usb_id) = info.usb_id {
+                        line(format!("- usb_id: {}", usb_id));
+                    }
+                }
+                line(format!("- rotation: {}", device.rotation));
+                if let Some(kind) = device.source_kind {
+                    let stacks: Vec<_> = device
+                        .source_kinds()
+                        .iter()
+                        .map(|kind| kind.display_name())
+                        .collect();
+                    line(format!(
+                        "- stack: {} (offered: {})",
+                        kind.display_name(),
+                        stacks.join(", ")
+                    ));
+                }
+            }
+            None => line("- name: (none)".to_string()),
+        }
+
+        line(String::new());
+        line("## Pipeline".to_string());
+        line("```text".to_string());
+        let pipeline = insights.full_pipeline_string.as_deref().unwrap_or("(none)");
+        if self.include_device_ids {
+            line(pipeline.to_string());
+        } else {
+            line(redact_pipeline(pipeline));
+        }
+        line("```".to_string());
+        line(format!(
+            "- device_quirk: {}",
+            insights.device_quirk.unwrap_or("(none)")
+        ));
+        line(match crate::media::decoders::active_v4l2_capture() {
+            Some(capture) => format!(
+                "- capture: v4l2src io-mode={} (requested {}, dmabuf {})",
+                capture.io_mode, capture.requested, insights.dmabuf_handoff
+            ),
+            None => "- capture: pipewiresrc".to_string(),
+        });
+        if let Some(topology) = &insights.usb_topology {
+            line(format!("- usb_speed_mbps: {}", topology.speed_mbps));
+            line(format!(
+                "- usb_bus: {} (port {})",
+                topology.bus, topology.port_path
+            ));
+            line(format!(
+                "- usb_devices_on_bus: {} ({} cameras)",
+                topology.devices_on_bus, topology.cameras_on_bus
+            ));
+        }
+        if let Some(hint) = insights.bandwidth_hint {
+            line(format!("- bandwidth_hint: {:?}", hint));
+        }
+        if let Some(identity) = &insights.device_identity {
+            if let Some(driver) = &identity.driver {
+                line(format!("- kernel_driver: {}", driver));
+            }
+            if let Some(bus_path) = &identity.bus_path {
+                line(format!("- usb_bus_path: {}", bus_path));
+            }
+            if let Some(serial) = &identity.serial {
+                line(format!("- usb_serial: {}", self.identifier(serial)));
+            }
+        }
+
+        line(String::new());
+        line("## Decoders".to_string());
+        if insights.decoder_chain.is_empty() {
+            line("- (not required for this format)".to_string());
+        }
+        for decoder in &insights.decoder_chain {
+            line(format!(
+                "- {}: {} ({})",
+                decoder.name,
+                state_name(decoder.state),
+                decoder.description
+            ));
+        }
+        for (decoder, errors) in insights.decoder_errors.iter() {
+            let rate = insights.decoder_errors.error_rate(decoder).unwrap_or(0.0);
+            line(format!(
+                "- decode_errors[{}]: {} in {} frames ({:.1}%)",
+                decoder,
+                errors,
+                insights.decoder_errors.frames(),
+                rate * 100.0
+            ));
+        }
+
+        if let Some(threads) = crate::media::decoders::active_decode_threads() {
+            let threads = match threads {
+                0 => "auto".to_string(),
+                threads => threads.to_string(),
+            };
+            line(format!("- decode_threads: {}", threads));
+        }
+        line(format!(
+            "- power_preference: {} (decoders {}, efficiency_cores {}, gpu {:?})",
+            if crate::media::decoders::low_power_decode() {
+                "low_power"
+            } else {
+                "high_performance"
+            },
+            if crate::media::decoders::software_first_decode() {
+                "software_first"
+            } else {
+                "hardware_first"
+            },
+            crate::media::decoders::efficiency_cores().unwrap_or(0),
+            crate::gpu::adapter_power_preference()
+        ));
+        if let Some(source) = crate::media::decoders::active_decoder_source() {
+            line(format!("- decoder_source: {:?}", source));
+        }
+        if let Some(parser) = crate::media::decoders::active_parser() {
+            line(format!("- parser: {}", parser));
+        }
+        if let Some(props) = crate::media::decoders::active_mjpeg_resilience() {
+            line(format!("- mjpeg_resilience: {}", props));
+        }
+
+        let cpu_stages = insights.acceleration().cpu_stages();
+        line(format!("- fully_hw_accelerated: {}", cpu_stages.is_empty()));
+        if !cpu_stages.is_empty() {
+            line(format!("- cpu_stages: {:?}", cpu_stages));
+        }
+
+        let chain = &insights.format_chain;
+        line(String::new());
+        line("## Format".to_string());
+        line(format!("- source: {}", chain.source));
+        line(format!("- resolution: {}", chain.resolution));
+        line(format!(
+            "- displayed_resolution: {}",
+            chain.displayed_resolution.as_deref().unwrap_or("-")
+        ));
+        line(format!(
+            "- row_stride: {}",
+            chain.row_stride.as_deref().unwrap_or("-")
+        ));
+        if let Some(warning) = &chain.stride_warning {
+            line(format!("- stride_warning: {}", warning));
+        }
+        line(format!("- framerate: {}", chain.framerate));
+        if let Some(cadence) = crate::backends::camera::pipewire::pipeline::get_frame_cadence() {
+            line(format!(
+                "- frame_cadence: {} ({:.1} fps measured)",
+                if cadence.variable { "VFR" } else { "CFR" },
+                cadence.average_fps
+            ));
+        }
+        line(format!("- native_format: {}", chain.native_format));
+        line(format!(
+            "- gstreamer_output: {}",
+            chain.gstreamer_output.as_deref().unwrap_or("-")
+        ));
+        line(format!("- wgpu_processing: {}", chain.wgpu_processing));
+        line(format!("- gpu_path: {:?}", chain.gpu_path));
+        line(format!(
+            "- compute_shaders: {}",
+            if chain.compute_unavailable {
+                "unavailable (histogram disabled)"
+            } else {
+                "available"
+            }
+        ));
+        line(format!(
+            "- colorimetry: {}",
+            chain.colorimetry.as_deref().unwrap_or("-")
+        ));
+        line(format!("- scan: {}", chain.scan.as_deref().unwrap_or("-")));
+
+        line(String::new());
+        line("## Format history".to_string());
+        if insights.format_history.is_empty() {
+            line("- none".to_string());
+        }
+        for change in insights.format_history.entries() {
+            line(format!("- {}", change.summary()));
+        }
+
+        line(String::new());
+        line("## Metrics".to_string());
+        line(format!("- frame_latency_us: {}", insights.frame_latency_us));
+        if let Some(lag_us) = insights.presentation_lag_us {
+            line(format!("- presentation_lag_us: {}", lag_us));
+        }
+        if let Some(score) = insights.focus.score() {
+            line(format!("- focus_score: {:.1}", score));
+        }
+        line(format!("- dropped_frames: {}", insights.dropped_frames));
+        line(format!("- stall_restarts: {}", insights.stall_restarts));
+        line(format!(
+            "- resolution_changes: {}",
+            insights.resolution_changes
+        ));
+        line(format!("- frames_processed: {}", insights.frames_processed));
+        line(format!(
+            "- uptime_s: {}",
+            insights.uptime().map_or(0, |uptime| uptime.as_secs())
+        ));
+        line(format!(
+            "- frame_drop_policy: {:?}",
+            insights.frame_drop_policy
+        ));
+        line(format!(
+            "- policy_dropped_frames: {}",
+            insights.policy_dropped_frames
+        ));
+        if let Some((max_buffers, drop)) =
+            crate::backends::camera::pipewire::pipeline::get_appsink_buffering()
+        {
+            line(format!(
+                "- appsink: max-buffers={} drop={}",
+                max_buffers, drop
+            ));
+        }
+        line(format!(
+            "- frame_size_decoded: {}",
+            insights.frame_size_decoded
+        ));
+        line(format!("- texture_memory: {}", insights.texture_memory));
+        line(format!(
+            "- gstreamer_decode_time_us: {}",
+            insights.gstreamer_decode_time_us
+        ));
+        line(format!(
+            "- gpu_conversion_time_us: {}",
+            insights.gpu_conversion_time_us
+        ));
+        line(format!("- copy_time_us: {}", insights.copy_time_us));
+        line(format!(
+            "- copy_bandwidth_mbps: {:.1}",
+            insights.copy_bandwidth_mbps
+        ));
+        line(format!(
+            "- cpu_percent: {}",
+            insights
+                .cpu_percent
+                .map(|cpu| format!("{:.0}", cpu))
+                .unwrap_or_else(|| "-".to_string())
+        ));
+        for (name, accumulator) in [
+            ("latency", &insights.session_stats.latency),
+            ("decode_time", &insights.session_stats.decode_time),
+            ("gpu_upload", &insights.session_stats.gpu_upload),
+        ] {
+            if accumulator.count() > 0 {
+                line(format!(
+                    "- session_{}_min_avg_max: {} ({} samples)",
+                    name,
+                    crate::app::insights::session_stats::format_summary(accumulator),
+                    accumulator.count()
+                ));
+            }
+        }
+        line(format!(
+            "- rolling_fps: {}",
+            insights
+                .rolling_fps
+                .map(|fps| format!("{:.1}", fps))
+                .unwrap_or_else(|| "-".to_string())
+        ));
+        line(format!(
+            "- display_refresh_hz: {}",
+            insights
+                .display_refresh_hz
+                .map(|hz| format!("{:.1}", hz))
+                .unwrap_or_else(|| "-".to_string())
+        ));
+        line(format!(
+            "- present_mode_requested: {:?}",
+            insights.requested_present_mode
+        ));
+        line(format!(
+            "- idle_inhibit: {}",
+            match &insights.idle_inhibit {
+                Some((reason, Ok(backend))) => format!("{} ({:?})", reason, backend),
+                Some((reason, Err(err))) => format!("failed for {}: {}", reason, err),
+                None => "-".to_string(),
+            }
+        ));
+        line(format!(
+            "- retro_buffer_frames: {}",
+            insights.retro_fill.frames
+        ));
+
+        // Derived figures shouldn't be read as precise measurements
+        line(String::new());
+        line("## Metric provenance".to_string());
+        for metric in ReportedMetric::ALL {
+            line(format!("- {}: {}", metric.key(), metric.provenance().key()));
+        }
+
+        line(String::new());
+        line("## GPU".to_string());
+        line(format!(
+            "- adapter: {}",
+            self.gpu_adapter.as_deref().unwrap_or("(not initialized)")
+        ));
+        line(format!("- device_losses: {}", insights.gpu_device_losses));
+        line(format!("- recovering: {}", insights.gpu_recovering));
+
+        line(String::new());
+        line("## GStreamer".to_string());
+        match self.gstreamer {
+            Some(gstreamer) => {
+                line(format!("- version: {}", gstreamer.version));
+                line(format!(
+                    "- custom_plugin_path: {}",
+                    gstreamer.uses_custom_plugin_path()
+                ));
+                for (name, value) in &gstreamer.overrides {
+                    line(format!("- env {}: {}", name, value));
+                }
+                for dir in &gstreamer.plugin_dirs {
+                    line(format!("- plugin_dir: {}", dir));
+                }
+            }
+            None => line("- version: (not detected)".to_string()),
+        }
+
+        let thermal = &insights.thermal;
+        line(String::new());
+        line("## Thermal".to_string());
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        line(format!(
+            "- cpu_freq_mhz: {}",
+            optional(thermal.cpu_freq_mhz.map(|mhz| mhz.to_string()))
+        ));
+        line(format!(
+            "- gpu_freq_mhz: {}",
+            optional(thermal.gpu_freq_mhz.map(|mhz| mhz.to_string()))
+        ));
+        line(format!(
+            "- max_temp_c: {}",
+            optional(thermal.max_temp_c.map(|temp| format!("{:.1}", temp)))
+        ));
+        line(format!(
+            "- throttling_suspected: {}",
+            insights.throttling_suspected
+        ));
+
+        line(String::new());
+        line("## Bus Errors".to_string());
+        if self.bus_errors.is_empty() {
+            line("- (none)".to_string());
+        }
+        for entry in &self.bus_errors {
+            if self.include_device_ids {
+                line(format!("- {}", entry));
+            } else {
+                line(format!("- {}", redact_pipeline(entry)));
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::SensorRotation;
+
+    fn device() -> CameraDevice {
+        CameraDevice {
+            name: "Test Camera".to_string(),
+            path: "pipewire-serial-4242".to_string(),
+            metadata_path: Some("/dev/video1".to_string()),
+            device_info: None,
+            rotation: SensorRotation::default(),
+            facing: Default::default(),
+            source_kind: None,
+            alternate_source: None,
+        }
+    }
+
+    fn insights() -> InsightsState {
+        InsightsState {
+            full_pipeline_string: Some(
+                "pipewiresrc target-object=4242 do-timestamp=true ! jpegdec ! appsink".to_string(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_identity_serial_redacted() {
+        let identity = DeviceIdentity {
+            node: "/dev/video0".to_string(),
+            driver: Some("uvcvideo".to_string()),
+            usb_id: Some(crate::backends::camera::types::UsbId::new(0x046d, 0x085e)),
+            bus_path: Some("3-2.1".to_string()),
+            serial: Some("A1B2C3D4".to_string()),
+            ..Default::default()
+        };
+        let text = identity_text(&identity, false);
+        assert!(text.contains("usb_id: 046d:085e"));
+        assert!(text.contains("driver: uvcvideo"));
+        assert!(text.contains(&format!("serial: {}", REDACTED)));
+        assert!(!text.contains("A1B2C3D4"));
+        assert!(identity_text(&identity, true).contains("serial: A1B2C3D4"));
+
+        // Platform cameras have a driver but nothing USB
+        let platform = DeviceIdentity {
+            node: "/dev/video2".to_string(),
+            driver: Some("ipu6".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            identity_text(&platform, false),
+            "node: /dev/video2\ndriver: ipu6"
+        );
+    }
+
+    #[test]
+    fn test_bundle_starts_with_version_header() {
+        let insights = insights();
+        let bundle = DiagnosticsBundle {
+            insights: &insights,
+            device: None,
+            gpu_adapter: None,
+            gstreamer: None,
+            bus_errors: Vec::new(),
+            include_device_ids: false,
+        }
+        .render();
+        assert_eq!(
+            bundle.lines().next(),
+            Some(
+                format!(
+                    "<!-- camera-diagnostics format={} -->",
+                    BUNDLE_FORMAT_VERSION
+                )
+                .as_str()
+            )
+        );
+        for section in [
+            "## Device",
+            "## Pipeline",
+            "## Decoders",
+            "## Format history",
+            "## Metrics",
+            "## Metric provenance",
+            "## GStreamer",
+            "## Bus Errors",
+        ] {
+            assert!(bundle.contains(section), "missing {}", section);
+        }
+        assert!(bundle.contains("- copy_bandwidth_mbps: estimated"));
+        assert!(bundle.contains("- copy_time_us: measured"));
+    }
+
+    #[test]
+    fn test_device_ids_redacted_by_default() {
+        let insights = insights();
+        let device = device();
+        let bundle = DiagnosticsBundle {
+            insights: &insights,
+            device: Some(&device),
+            gpu_adapter: None,
+            gstreamer: None,
+            bus_errors: Vec::new(),
+            include_device_ids: false,
+        }
+        .render();
+        assert!(!bundle.contains("4242"));
+        assert!(!bundle.contains("/dev/video1"));
+        assert!(bundle.contains("target-object=<redacted> do-timestamp=true"));
+        assert!(bundle.contains("- name: Test Camera"));
+    }
+
+    #[test]
+    fn test_device_ids_included_on_opt_in() {
+        let insights = insights();
+        let device = device();
+        let bundle = DiagnosticsBundle {
+            insights: &insights,
+            device: Some(&device),
+            gpu_adapter: None,
+            gstreamer: None,
+            bus_errors: Vec::new(),
+            include_device_ids: true,
+        }
+        .render();
+        assert!(bundle.contains("- path: pipewire-serial-4242"));
+        assert!(bundle.contains("target-object=4242"));
+        assert!(!bundle.contains(REDACTED));
+    }
+}
@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Live property values of the pipeline's elements
+//!
+//! A lightweight gst-inspect for the running pipeline: every element is
+//! listed by name and factory, and the readable properties of expanded
+//! elements are read through GObject introspection. Values are only read
+//! when the insights metrics refresh, and only for expanded elements.
+
+use gstreamer::glib;
+use gstreamer::prelude::*;
+use std::collections::HashSet;
+
+/// Properties every element has that add nothing to the inspector
+const SKIPPED_PROPERTIES: &[&str] = &["name", "parent"];
+
+/// Properties shown next to the factory even when the element is collapsed
+const SUMMARY_PROPERTIES: &[(&str, &[&str])] = &[("appsink", &["max-buffers", "drop"])];
+
+/// One element of the live pipeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementInfo {
+    /// Element instance name (e.g. "sink", "jpegdec0")
+    pub name: String,
+    /// Factory the element was created from (e.g. "jpegdec")
+    pub factory: String,
+    /// Readable properties as (name, value); empty unless the element is expanded
+    pub properties: Vec<(String, String)>,
+    /// Key properties as "name=value" pairs, for factories that have any
+    pub summary: Option<String>,
+}
+
+/// List the pipeline's elements from source to sink
+///
+/// Properties are read for elements whose name is in `expanded`.
+pub fn inspect(pipeline: &gstreamer::Pipeline, expanded: &HashSet<String>) -> Vec<ElementInfo> {
+    let mut elements: Vec<gstreamer::Element> =
+        pipeline.iterate_sorted().into_iter().flatten().collect();
+    // Sorted iteration yields sinks first
+    elements.reverse();
+
+    elements
+        .iter()
+        .map(|element| {
+            let name = element.name().to_string();
+            let properties = if expanded.contains(&name) {
+                read_properties(element)
+            } else {
+                Vec::new()
+            };
+            let factory = element
+                .factory()
+                .map(|factory| factory.name().to_string())
+                .unwrap_or_default();
+            ElementInfo {
+                summary: summarize(element, &factory),
+                factory,
+                name,
+                properties,
+            }
+        })
+        .collect()
+}
+
+/// The [`SUMMARY_PROPERTIES`] of `element`, e.g. "max-buffers=2 drop=true"
+fn summarize(element: &gstreamer::Element, factory: &str) -> Option<String> {
+    let (_, names) = SUMMARY_PROPERTIES
+        .iter()
+        .find(|(summarized, _)| *summarized == factory)?;
+    let pairs: Vec<String> = names
+        .iter()
+        .filter(|&&name| element.find_property(name).is_some())
+        .map(|&name| format!("{}={}", name, format_value(&element.property_value(name))))
+        .collect();
+    (!pairs.is_empty()).then(|| pairs.join(" "))
+}
+
+fn read_properties(element: &gstreamer::Element) -> Vec<(String, String)> {
+    element
+        .list_properties()
+        .iter()
+        .filter(|pspec| pspec.flags().contains(glib::ParamFlags::READABLE))
+        .filter(|pspec| !SKIPPED_PROPERTIES.contains(&pspec.name()))
+        .map(|pspec| {
+            let value = element.property_value(pspec.name());
+            (pspec.name().to_string(), format_value(&value))
+        })
+        .collect()
+}
+
+/// Human-readable rendering of a property value
+///
+/// Enums show their nick, flags their nicks joined by `+`, and anything
+/// GStreamer can serialize (numbers, strings, caps) its serialized form.
+fn format_value(value: &glib::Value) -> String {
+    if let Some((_, enum_value)) = glib::EnumValue::from_value(value) {
+        return enum_value.nick().to_string();
+    }
+    if let Some((_, flags)) = glib::FlagsValue::from_value(value) {
+        if flags.is_empty() {
+            return "(none)".to_string();
+        }
+        return flags
+            .iter()
+            .map(|flag| flag.nick())
+            .collect::<Vec<_>>()
+            .join("+");
+    }
+    if let Ok(Some(object)) = value.get::<Option<glib::Object>>() {
+        return format!("<{}>", object.type_().name());
+    }
+    match value.serialize() {
+        Ok(serialized) if !serialized.is_empty() => serialized.to_string(),
+        Ok(_) => "\"\"".to_string(),
+        Err(_) => format!("<{}>", value.type_().name()),
+    }
+}
@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Focus assist score
+//!
+//! Scores sharpness as the variance of the Laplacian of the frame's luma:
+//! in-focus detail gives strong second derivatives in both directions, blur
+//! flattens them. The luma is sampled on a decimated grid straight from the
+//! frame (no conversion or copy of the full frame), so scoring costs a few
+//! thousand reads. The score only means something relative to other frames of
+//! the same scene, which is why [`FocusMeter`] keeps the peak seen so far.
+
+use crate::backends::camera::types::{CameraFrame, PixelFormat};
+
+/// Samples taken along the longer side of the frame
+pub const GRID_SIDE: u32 = 256;
+
+/// Frame luma sampled on a regular grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct LumaGrid {
+    pub width: usize,
+    pub height: usize,
+    /// Row-major luma samples
+    pub samples: Vec<u8>,
+}
+
+impl LumaGrid {
+    /// Sample the luma of `frame` with at most `max_side` samples per side
+    ///
+    /// Reads the Y bytes directly for YUV and grayscale formats (the high byte
+    /// for Gray16). Returns `None` for frames too small or too short for their
+    /// size.
+    pub fn sample(frame: &CameraFrame, max_side: u32) -> Option<Self> {
+        let step = frame
+            .width
+            .max(frame.height)
+            .div_ceil(max_side.max(1))
+            .max(1) as usize;
+        let width = frame.width as usize / step;
+        let height = frame.height as usize / step;
+        if width < 3 || height < 3 {
+            return None;
+        }
+
+        let data = frame.data_slice();
+        let stride = frame.stride as usize;
+        let mut samples = Vec::with_capacity(width * height);
+        for gy in 0..height {
+            let row = gy * step * stride;
+            for gx in 0..width {
+                let x = gx * step;
+                let luma = match frame.format {
+                    PixelFormat::NV12
+                    | PixelFormat::NV21
+                    | PixelFormat::I420
+                    | PixelFormat::Gray8 => *data.get(row + x)?,
+                    PixelFormat::Gray16 => *data.get(row + x * 2 + 1)?,
+                    PixelFormat::YUYV | PixelFormat::YVYU => *data.get(row + x * 2)?,
+                    PixelFormat::UYVY | PixelFormat::VYUY => *data.get(row + x * 2 + 1)?,
+                    PixelFormat::RGB24 | PixelFormat::RGBA => {
+                        let bpp = if frame.format == PixelFormat::RGB24 {
+                            3
+                        } else {
+                            4
+                        };
+                        let px = data.get(row + x * bpp..row + x * bpp + 3)?;
+                        ((px[0] as u32 * 77 + px[1] as u32 * 150 + px[2] as u32 * 29) >> 8) as u8
+                    }
+                };
+                samples.push(luma);
+            }
+        }
+        Some(Self {
+            width,
+            height,
+            samples,
+        })
+    }
+
+    /// Variance of the 4-neighbour Laplacian over the interior samples
+    pub fn laplacian_variance(&self) -> f32 {
+        let w = self.width;
+        let at = |x: usize, y: usize| self.samples[y * w + x] as i64;
+        let (mut sum, mut sum_sq, mut n) = (0i64, 0i64, 0i64);
+        for y in 1..self.height - 1 {
+            for x in 1..w - 1 {
+                let lap = 4 * at(x, y) - at(x - 1, y) - at(x + 1, y) - at(x, y - 1) - at(x, y + 1);
+                sum += lap;
+                sum_sq += lap * lap;
+                n += 1;
+            }
+        }
+        let mean = sum as f64 / n as f64;
+        (sum_sq as f64 / n as f64 - mean * mean) as f32
+    }
+}
+
+/// Focus score of `frame` (higher is sharper), `None` if it can't be sampled
+pub fn focus_score(frame: &CameraFrame) -> Option<f32> {
+    LumaGrid::sample(frame, GRID_SIDE).map(|grid| grid.laplacian_variance())
+}
+
+/// Latest focus score and the best one since the last reset
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FocusMeter {
+    score: Option<f32>,
+    peak: f32,
+}
+
+impl FocusMeter {
+    /// Record a new score
+    pub fn record(&mut self, score: f32) {
+        self.score = Some(score);
+        self.peak = self.peak.max(score);
+    }
+
+    /// Latest score
+    pub fn score(&self) -> Option<f32> {
+        self.score
+    }
+
+    /// Latest score as a fraction of the peak (1.0 = sharpest seen)
+    pub fn relative(&self) -> Option<f32> {
+        let score = self.score?;
+        (self.peak > 0.0).then(|| (score / self.peak).clamp(0.0, 1.0))
+    }
+
+    /// Forget the scores (new scene, camera or format)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::FrameData;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    const W: usize = 640;
+    const H: usize = 480;
+
+    fn checkerboard() -> Vec<u8> {
+        (0..W * H)
+            .map(|i| {
+                if ((i % W) / 16 + (i / W) / 16) % 2 == 0 {
+                    32
+                } else {
+                    224
+                }
+            })
+            .collect()
+    }
+
+    /// Separable box blur with the given radius
+    fn blurred(luma: &[u8], radius: usize) -> Vec<u8> {
+        let pass = |src: &[u8], dx: usize, dy: usize| -> Vec<u8> {
+            (0..W * H)
+                .map(|i| {
+                    let (x, y) = (i % W, i / W);
+                    let (mut sum, mut n) = (0u32, 0u32);
+                    for d in 0..=2 * radius {
+                        let sx = (x + d * dx).checked_sub(radius * dx);
+                        let sy = (y + d * dy).checked_sub(radius * dy);
+                        if let (Some(sx), Some(sy)) = (sx, sy)
+                            && sx < W
+                            && sy < H
+                        {
+                            sum += src[sy * W + sx] as u32;
+                            n += 1;
+                        }
+                    }
+                    (sum / n) as u8
+                })
+                .collect()
+        };
+        pass(&pass(luma, 1, 0), 0, 1)
+    }
+
+    fn frame(data: Vec<u8>, format: PixelFormat, stride: usize) -> CameraFrame {
+        CameraFrame {
+            width: W as u32,
+            height: H as u32,
+            data: FrameData::from_bytes(Arc::from(data)),
+            format,
+            stride: stride as u32,
+            yuv_planes: None,
+            captured_at: Instant::now(),
+            stamp: None,
+        }
+    }
+
+    #[test]
+    fn test_sharp_frames_score_higher() {
+        let sharp = focus_score(&frame(checkerboard(), PixelFormat::Gray8, W)).unwrap();
+        let soft = focus_score(&frame(blurred(&checkerboard(), 6), PixelFormat::Gray8, W)).unwrap();
+        let flat = focus_score(&frame(vec![128; W * H], PixelFormat::Gray8, W)).unwrap();
+        assert!(sharp > soft, "sharp {sharp} vs soft {soft}");
+        assert!(soft > flat, "soft {soft} vs flat {flat}");
+        assert_eq!(flat, 0.0);
+    }
+
+    #[test]
+    fn test_packed_yuv_samples_luma() {
+        let luma = checkerboard();
+        let yuyv: Vec<u8> = luma.iter().flat_map(|&y| [y, 128]).collect();
+        let uyvy: Vec<u8> = luma.iter().flat_map(|&y| [128, y]).collect();
+        let gray = LumaGrid::sample(&frame(luma, PixelFormat::Gray8, W), GRID_SIDE).unwrap();
+        assert_eq!(gray.width, 213);
+        assert_eq!(
+            LumaGrid::sample(&frame(yuyv, PixelFormat::YUYV, W * 2), GRID_SIDE),
+            Some(gray.clone())
+        );
+        assert_eq!(
+            LumaGrid::sample(&frame(uyvy, PixelFormat::UYVY, W * 2), GRID_SIDE),
+            Some(gray)
+        );
+        // Truncated buffers are rejected instead of read out of bounds
+        assert_eq!(
+            LumaGrid::sample(&frame(vec![0; W], PixelFormat::Gray8, W), GRID_SIDE),
+            None
+        );
+    }
+
+    #[test]
+    fn test_meter_tracks_peak() {
+        let mut meter = FocusMeter::default();
+        assert_eq!(meter.relative(), None);
+        meter.record(50.0);
+        meter.record(200.0);
+        meter.record(100.0);
+        assert_eq!(meter.score(), Some(100.0));
+        assert_eq!(meter.relative(), Some(0.5));
+        meter.reset();
+        assert_eq!(meter.score(), None);
+    }
+}
@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Bounded log of format and decoder changes
+//!
+//! Decoder fallback and caps renegotiation change what the preview shows
+//! without any user action. Each change is kept here with its time, so "why
+//! did the preview suddenly change quality" can be answered afterwards.
+//! Changes that follow each other quickly (e.g. while a pipeline restarts)
+//! are merged into one entry instead of flooding the log.
+
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Entries kept before the oldest is dropped
+pub const MAX_ENTRIES: usize = 32;
+
+/// Changes within this long of the previous one are merged into its entry
+const MERGE_WINDOW: Duration = Duration::from_secs(2);
+
+/// What the preview is receiving
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamState {
+    /// Camera format, size and the format reaching the GPU (e.g. "MJPG 1920x1080 → I420")
+    pub format: String,
+    /// Decoder element, `None` for raw formats
+    pub decoder: Option<&'static str>,
+}
+
+impl StreamState {
+    fn decoder_name(&self) -> &'static str {
+        self.decoder.unwrap_or("none")
+    }
+}
+
+/// One change of format and/or decoder
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatChange {
+    /// When the change was seen
+    pub at: DateTime<Local>,
+    /// State before the change
+    pub from: StreamState,
+    /// State after the change
+    pub to: StreamState,
+    /// Further changes merged into this entry
+    pub merged: u32,
+}
+
+impl FormatChange {
+    /// One line for display and the clipboard
+    pub fn summary(&self) -> String {
+        let mut line = format!(
+            "{}  format: {} → {}; decoder: {} → {}",
+            self.at.format("%H:%M:%S"),
+            self.from.format,
+            self.to.format,
+            self.from.decoder_name(),
+            self.to.decoder_name()
+        );
+        if self.merged > 0 {
+            line.push_str(&format!(" (+{} merged)", self.merged));
+        }
+        line
+    }
+}
+
+/// Recent format/decoder changes, newest last
+#[derive(Debug, Clone, Default)]
+pub struct FormatHistory {
+    entries: VecDeque<FormatChange>,
+    current: Option<StreamState>,
+    last_change: Option<Instant>,
+}
+
+impl FormatHistory {
+    /// Note the current state, logging it if it differs from the last one
+    ///
+    /// The first state seen is only remembered. Returns whether the log changed.
+    pub fn observe(&mut self, state: StreamState, now: Instant, at: DateTime<Local>) -> bool {
+        let Some(previous) = self.current.replace(state.clone()) else {
+            return false;
+        };
+        if previous == state {
+            return false;
+        }
+
+        let recent = self
+            .last_change
+            .is_some_and(|last| now.duration_since(last) < MERGE_WINDOW);
+        self.last_change = Some(now);
+        if recent && let Some(last) = self.entries.back_mut() {
+            last.to = state;
+            last.merged += 1;
+            // Flapped back to where it started: nothing changed after all
+            if last.from == last.to {
+                self.entries.pop_back();
+            }
+            return true;
+        }
+
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(FormatChange {
+            at,
+            from: previous,
+            to: state,
+            merged: 0,
+        });
+        true
+    }
+
+    /// Logged changes, oldest first
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &FormatChange> {
+        self.entries.iter()
+    }
+
+    /// Whether no change has been logged yet
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The whole log as text, one change per line
+    pub fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(FormatChange::summary)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(format: &str, decoder: Option<&'static str>) -> StreamState {
+        StreamState {
+            format: format.to_string(),
+            decoder,
+        }
+    }
+
+    #[test]
+    fn test_only_changes_are_logged() {
+        let mut history = FormatHistory::default();
+        let start = Instant::now();
+        let hd = state("MJPG 1920x1080 → I420", Some("vaapijpegdec"));
+        assert!(!history.observe(hd.clone(), start, Local::now()));
+        assert!(!history.observe(hd.clone(), start, Local::now()));
+        assert!(history.is_empty());
+
+        let fallback = state("MJPG 1920x1080 → I420", Some("jpegdec"));
+        assert!(history.observe(fallback.clone(), start, Local::now()));
+        let entry = history.entries().next().unwrap();
+        assert_eq!((&entry.from, &entry.to), (&hd, &fallback));
+        assert!(entry.summary().ends_with("decoder: vaapijpegdec → jpegdec"));
+    }
+
+    #[test]
+    fn test_quick_changes_are_merged() {
+        let mut history = FormatHistory::default();
+        let start = Instant::now();
+        history.observe(state("YUYV 1280x720", None), start, Local::now());
+        history.observe(state("YUYV 640x480", None), start, Local::now());
+        history.observe(
+            state("MJPG 640x480 → I420", Some("jpegdec")),
+            start + Duration::from_millis(500),
+            Local::now(),
+        );
+        assert_eq!(history.entries().count(), 1);
+        let entry = history.entries().next().unwrap();
+        assert_eq!(entry.to.format, "MJPG 640x480 → I420");
+        assert_eq!(entry.merged, 1);
+
+        // Flapping back within the window cancels the entry
+        history.observe(
+            state("YUYV 1280x720", None),
+            start + Duration::from_secs(1),
+            Local::now(),
+        );
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = FormatHistory::default();
+        let start = Instant::now();
+        for i in 0..=MAX_ENTRIES as u64 + 5 {
+            history.observe(
+                state(&format!("YUYV {}x480", 640 + i), None),
+                start + MERGE_WINDOW * i as u32,
+                Local::now(),
+            );
+        }
+        assert_eq!(history.entries().count(), MAX_ENTRIES);
+        assert_eq!(
+            history.entries().last().unwrap().to.format,
+            format!("YUYV {}x480", 640 + MAX_ENTRIES as u64 + 5)
+        );
+        assert_eq!(history.to_text().lines().count(), MAX_ENTRIES);
+    }
+}
@@ -3,7 +3,26 @@
 //! Insights drawer for displaying diagnostic information about camera pipeline,
 //! performance metrics, and format capabilities.
 
+pub mod benchmark;
+pub mod diagnostics;
+pub mod elements;
+pub mod focus;
+pub mod history;
+pub mod passthrough;
+pub mod pinned;
+pub mod provenance;
+pub mod session_stats;
+pub mod thermal;
 pub mod types;
 pub mod view;
 
+pub use benchmark::{UploadBenchmark, UploadPath};
+pub use elements::ElementInfo;
+pub use focus::FocusMeter;
+pub use history::{FormatHistory, StreamState};
+pub use passthrough::{GpuPath, PassthroughCheck};
+pub use pinned::{Comparison, Metric, MetricValues, PinnedMetrics};
+pub use provenance::{Provenance, ReportedMetric};
+pub use session_stats::SessionStats;
+pub use thermal::ThermalSample;
 pub use types::*;
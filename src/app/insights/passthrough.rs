@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Verification of the GPU passthrough
+//!
+//! RGB frames need no conversion on the GPU: they are uploaded as an RGBA
+//! texture and sampled directly. Rather than inferring that from the
+//! negotiated format, the video primitive records which path the newest
+//! frame actually took and the format chain reports it. An RGB source that
+//! still ends up in the conversion shader (e.g. converted to NV12 by the
+//! pipeline only to be converted back on the GPU) is flagged as wasted work.
+
+use crate::backends::camera::types::PixelFormat;
+use crate::media::{Codec, FormatClass};
+
+/// GPU path a frame took in the preview
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuPath {
+    /// Uploaded as RGBA and sampled directly
+    Passthrough,
+    /// Uploaded as planes and run through the YUV/grayscale conversion shader
+    Conversion(PixelFormat),
+}
+
+/// Whether the GPU stage does the work the format reaching it needs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PassthroughCheck {
+    /// No frame has reached the GPU yet
+    #[default]
+    Unverified,
+    /// Sampled directly, without any conversion
+    Passthrough,
+    /// Converted by the shader, as YUV and grayscale formats need
+    Conversion,
+    /// An RGB source converted by the shader anyway, from this format
+    NeedlessConversion(PixelFormat),
+}
+
+/// Check the path the newest frame took against the format reaching the GPU
+pub fn check_passthrough(gpu_input: Codec, path: Option<GpuPath>) -> PassthroughCheck {
+    let rgb = gpu_input.class() == Some(FormatClass::RawRgb);
+    match path {
+        None => PassthroughCheck::Unverified,
+        Some(GpuPath::Passthrough) => PassthroughCheck::Passthrough,
+        Some(GpuPath::Conversion(format)) if rgb => PassthroughCheck::NeedlessConversion(format),
+        Some(GpuPath::Conversion(_)) => PassthroughCheck::Conversion,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb_sources_must_pass_through() {
+        assert_eq!(
+            check_passthrough(Codec::from_fourcc("BGRx"), Some(GpuPath::Passthrough)),
+            PassthroughCheck::Passthrough
+        );
+        assert_eq!(
+            check_passthrough(
+                Codec::from_fourcc("RGBA"),
+                Some(GpuPath::Conversion(PixelFormat::NV12))
+            ),
+            PassthroughCheck::NeedlessConversion(PixelFormat::NV12)
+        );
+        assert_eq!(
+            check_passthrough(
+                Codec::from_fourcc("NV12"),
+                Some(GpuPath::Conversion(PixelFormat::NV12))
+            ),
+            PassthroughCheck::Conversion
+        );
+        assert_eq!(
+            check_passthrough(Codec::from_fourcc("RGBA"), None),
+            PassthroughCheck::Unverified
+        );
+    }
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Pinned performance metrics
+//!
+//! Pinning snapshots the performance figures so the drawer can show how they
+//! moved since, e.g. after changing the decoder or the buffer depth. Metrics
+//! can appear or disappear between the pin and now (CPU usage is only sampled
+//! once the drawer has been open for a moment, presentation lag needs a
+//! presented frame), so each one is compared on its own.
+
+use std::time::Instant;
+
+/// A performance figure that can be compared against the pinned one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    FrameLatency,
+    PresentationLag,
+    DecodeTime,
+    GpuUpload,
+    CpuUsage,
+    FrameRate,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 6] = [
+        Metric::FrameLatency,
+        Metric::PresentationLag,
+        Metric::DecodeTime,
+        Metric::GpuUpload,
+        Metric::CpuUsage,
+        Metric::FrameRate,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Value in the metric's display precision, without the unit
+    pub fn format_value(self, value: f64) -> String {
+        match self {
+            Metric::CpuUsage => format!("{:.0}", value),
+            Metric::FrameRate => format!("{:.1}", value),
+            _ => format!("{:.2}", value),
+        }
+    }
+
+    /// Signed difference in the metric's display unit and precision
+    ///
+    /// Uses a real minus sign so negative deltas line up with positive ones.
+    pub fn format_delta(self, delta: f64) -> String {
+        let text = match self {
+            Metric::CpuUsage => format!("{:+.0}", delta),
+            Metric::FrameRate => format!("{:+.1}", delta),
+            _ => format!("{:+.2}", delta),
+        };
+        text.replace('-', "−")
+    }
+}
+
+/// Metric values at one point in time, `None` where a metric is unavailable
+///
+/// Times are in milliseconds, CPU usage in percent and the frame rate in fps,
+/// as displayed.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricValues([Option<f64>; Metric::ALL.len()]);
+
+impl MetricValues {
+    pub fn get(&self, metric: Metric) -> Option<f64> {
+        self.0[metric.index()]
+    }
+
+    pub fn set(&mut self, metric: Metric, value: Option<f64>) {
+        self.0[metric.index()] = value;
+    }
+}
+
+/// How a current metric relates to the pinned one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    /// Both available: current minus pinned
+    Delta(f64),
+    /// Available now, but wasn't when the metrics were pinned
+    NotPinned,
+    /// Was pinned with this value, unavailable now
+    Unavailable(f64),
+}
+
+/// Metrics snapshot taken by the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct PinnedMetrics {
+    pub values: MetricValues,
+    pub pinned_at: Instant,
+}
+
+impl PinnedMetrics {
+    pub fn new(values: MetricValues) -> Self {
+        Self {
+            values,
+            pinned_at: Instant::now(),
+        }
+    }
+
+    /// Compare `metric` in `now` to the pinned value, `None` if neither has it
+    pub fn compare(&self, now: &MetricValues, metric: Metric) -> Option<Comparison> {
+        match (self.values.get(metric), now.get(metric)) {
+            (Some(pinned), Some(current)) => Some(Comparison::Delta(current - pinned)),
+            (None, Some(_)) => Some(Comparison::NotPinned),
+            (Some(pinned), None) => Some(Comparison::Unavailable(pinned)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_compared_individually() {
+        let mut pinned = MetricValues::default();
+        pinned.set(Metric::FrameLatency, Some(14.4));
+        pinned.set(Metric::PresentationLag, Some(3.0));
+        let pin = PinnedMetrics::new(pinned);
+
+        let mut now = MetricValues::default();
+        now.set(Metric::FrameLatency, Some(12.3));
+        now.set(Metric::CpuUsage, Some(18.0));
+
+        let Some(Comparison::Delta(delta)) = pin.compare(&now, Metric::FrameLatency) else {
+            panic!("latency should be compared");
+        };
+        assert!((delta + 2.1).abs() < 1e-9);
+        // CPU usage became available after pinning
+        assert_eq!(
+            pin.compare(&now, Metric::CpuUsage),
+            Some(Comparison::NotPinned)
+        );
+        assert_eq!(
+            pin.compare(&now, Metric::PresentationLag),
+            Some(Comparison::Unavailable(3.0))
+        );
+        assert_eq!(pin.compare(&now, Metric::FrameRate), None);
+    }
+
+    #[test]
+    fn test_delta_formatting() {
+        assert_eq!(Metric::FrameLatency.format_delta(-2.1), "−2.10");
+        assert_eq!(Metric::CpuUsage.format_delta(4.4), "+4");
+        assert_eq!(Metric::FrameRate.format_delta(0.0), "+0.0");
+        assert_eq!(Metric::PresentationLag.format_value(3.0), "3.00");
+    }
+}
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Where each performance figure comes from
+//!
+//! Some figures are timed directly around the work they describe, others are
+//! derived from other values or sampled at a coarser rate than the thing
+//! they describe. The drawer marks derived figures as estimated and explains
+//! each method in a tooltip, and the diagnostics bundle lists the provenance
+//! of every figure it reports, so a derived number isn't read as a precise
+//! measurement.
+
+use super::pinned::Metric;
+
+/// How much a figure can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provenance {
+    /// Timed or counted directly
+    Measured,
+    /// Derived from other values or sampled coarsely
+    Estimated,
+}
+
+impl Provenance {
+    /// Name used in the diagnostics bundle
+    pub fn key(self) -> &'static str {
+        match self {
+            Provenance::Measured => "measured",
+            Provenance::Estimated => "estimated",
+        }
+    }
+}
+
+/// A performance figure shown in the drawer and written to the diagnostics bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportedMetric {
+    /// Age of the newest frame when the metrics refresh
+    FrameLatency,
+    /// Newest decoded frame vs the frame on screen
+    PresentationLag,
+    /// Pulling and mapping a sample in the appsink
+    DecodeTime,
+    /// Wrapping a mapped sample into a frame
+    CopyTime,
+    /// Queueing a frame's texture upload
+    GpuUpload,
+    /// Frame size divided by the upload time
+    UploadBandwidth,
+    /// Process CPU time between refreshes
+    CpuUsage,
+    /// Frames counted between refreshes
+    FrameRate,
+    /// Window frame callback rate
+    DisplayRefresh,
+    /// Texture sizes times their texel size
+    TextureMemory,
+}
+
+impl ReportedMetric {
+    pub const ALL: [ReportedMetric; 10] = [
+        ReportedMetric::FrameLatency,
+        ReportedMetric::PresentationLag,
+        ReportedMetric::DecodeTime,
+        ReportedMetric::CopyTime,
+        ReportedMetric::GpuUpload,
+        ReportedMetric::UploadBandwidth,
+        ReportedMetric::CpuUsage,
+        ReportedMetric::FrameRate,
+        ReportedMetric::DisplayRefresh,
+        ReportedMetric::TextureMemory,
+    ];
+
+    /// Key of the figure in the diagnostics bundle's metrics section
+    pub fn key(self) -> &'static str {
+        match self {
+            ReportedMetric::FrameLatency => "frame_latency_us",
+            ReportedMetric::PresentationLag => "presentation_lag_us",
+            ReportedMetric::DecodeTime => "gstreamer_decode_time_us",
+            ReportedMetric::CopyTime => "copy_time_us",
+            ReportedMetric::GpuUpload => "gpu_conversion_time_us",
+            ReportedMetric::UploadBandwidth => "copy_bandwidth_mbps",
+            ReportedMetric::CpuUsage => "cpu_percent",
+            ReportedMetric::FrameRate => "rolling_fps",
+            ReportedMetric::DisplayRefresh => "display_refresh_hz",
+            ReportedMetric::TextureMemory => "texture_memory",
+        }
+    }
+
+    pub fn provenance(self) -> Provenance {
+        match self {
+            // Sampled when the metrics refresh, not when the frame is shown,
+            // and blind to time spent in the sensor and driver
+            ReportedMetric::FrameLatency => Provenance::Estimated,
+            // Upload size over the time it took to queue it
+            ReportedMetric::UploadBandwidth => Provenance::Estimated,
+            // Averaged over the last few dozen window frames
+            ReportedMetric::DisplayRefresh => Provenance::Estimated,
+            // Driver padding and alignment aren't visible to the app
+            ReportedMetric::TextureMemory => Provenance::Estimated,
+            ReportedMetric::PresentationLag
+            | ReportedMetric::DecodeTime
+            | ReportedMetric::CopyTime
+            | ReportedMetric::GpuUpload
+            | ReportedMetric::CpuUsage
+            | ReportedMetric::FrameRate => Provenance::Measured,
+        }
+    }
+}
+
+impl From<Metric> for ReportedMetric {
+    fn from(metric: Metric) -> Self {
+        match metric {
+            Metric::FrameLatency => ReportedMetric::FrameLatency,
+            Metric::PresentationLag => ReportedMetric::PresentationLag,
+            Metric::DecodeTime => ReportedMetric::DecodeTime,
+            Metric::GpuUpload => ReportedMetric::GpuUpload,
+            Metric::CpuUsage => ReportedMetric::CpuUsage,
+            Metric::FrameRate => ReportedMetric::FrameRate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived_figures_are_estimated() {
+        assert_eq!(
+            ReportedMetric::UploadBandwidth.provenance(),
+            Provenance::Estimated
+        );
+        assert_eq!(
+            ReportedMetric::from(Metric::FrameLatency).provenance(),
+            Provenance::Estimated
+        );
+        assert_eq!(
+            ReportedMetric::from(Metric::DecodeTime).provenance(),
+            Provenance::Measured
+        );
+        // Keys are unique so the bundle can't list a figure twice
+        let keys: std::collections::HashSet<_> = ReportedMetric::ALL
+            .iter()
+            .map(|metric| metric.key())
+            .collect();
+        assert_eq!(keys.len(), ReportedMetric::ALL.len());
+    }
+}
@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Min/avg/max of the performance metrics over a pipeline session
+//!
+//! The raw and smoothed values show how the preview is doing right now;
+//! comparing two decoders or settings needs figures over a longer window.
+//! Each metric keeps a running count, sum, minimum and maximum in
+//! microseconds, updated for every frame while enabled. Everything starts
+//! over when the pipeline restarts or on request.
+
+use std::time::Instant;
+
+/// Running minimum, maximum and mean of a metric in microseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Accumulator {
+    count: u64,
+    sum: u64,
+    min: u64,
+    max: u64,
+}
+
+impl Accumulator {
+    /// Fold in a sample
+    pub fn record(&mut self, value_us: u64) {
+        if self.count == 0 {
+            self.min = value_us;
+            self.max = value_us;
+        } else {
+            self.min = self.min.min(value_us);
+            self.max = self.max.max(value_us);
+        }
+        self.count += 1;
+        self.sum = self.sum.saturating_add(value_us);
+    }
+
+    /// (min, avg, max) in microseconds, `None` before the first sample
+    pub fn summary(&self) -> Option<(u64, u64, u64)> {
+        (self.count > 0).then(|| (self.min, self.sum / self.count, self.max))
+    }
+
+    /// Number of samples folded in
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Session figures for the per-frame metrics
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SessionStats {
+    /// Start of the pipeline session the figures belong to
+    session: Option<Instant>,
+    /// Capture to app hand-off latency
+    pub latency: Accumulator,
+    /// GStreamer decode/conversion time
+    pub decode_time: Accumulator,
+    /// GPU upload and conversion time
+    pub gpu_upload: Accumulator,
+}
+
+impl SessionStats {
+    /// Fold in one frame's metrics
+    ///
+    /// A different `session` means the pipeline was rebuilt, which starts
+    /// the figures over. Stage times of 0 haven't been measured yet (or the
+    /// stage doesn't run) and are left out.
+    pub fn record(
+        &mut self,
+        session: Option<Instant>,
+        latency_us: u64,
+        decode_time_us: u64,
+        gpu_upload_us: u64,
+    ) {
+        if session != self.session {
+            *self = Self {
+                session,
+                ..Self::default()
+            };
+        }
+        self.latency.record(latency_us);
+        if decode_time_us > 0 {
+            self.decode_time.record(decode_time_us);
+        }
+        if gpu_upload_us > 0 {
+            self.gpu_upload.record(gpu_upload_us);
+        }
+    }
+
+    /// Start the figures over within the current session
+    pub fn reset(&mut self) {
+        *self = Self {
+            session: self.session,
+            ..Self::default()
+        };
+    }
+}
+
+/// "min / avg / max ms" for display and export, "-" before the first sample
+pub fn format_summary(accumulator: &Accumulator) -> String {
+    match accumulator.summary() {
+        Some((min, avg, max)) => format!(
+            "{:.2} / {:.2} / {:.2} ms",
+            min as f64 / 1000.0,
+            avg as f64 / 1000.0,
+            max as f64 / 1000.0
+        ),
+        None => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_stats_accumulate_and_restart() {
+        let first = Instant::now();
+        let mut stats = SessionStats::default();
+        stats.record(Some(first), 12_000, 0, 800);
+        stats.record(Some(first), 18_000, 2_500, 1_200);
+        stats.record(Some(first), 15_000, 3_500, 1_000);
+        assert_eq!(stats.latency.summary(), Some((12_000, 15_000, 18_000)));
+        // Unmeasured stage times are skipped
+        assert_eq!(stats.decode_time.count(), 2);
+        assert_eq!(stats.decode_time.summary(), Some((2_500, 3_000, 3_500)));
+        assert_eq!(format_summary(&stats.gpu_upload), "0.80 / 1.00 / 1.20 ms");
+
+        stats.reset();
+        assert_eq!(stats.latency.summary(), None);
+        assert_eq!(format_summary(&stats.latency), "-");
+
+        // A new pipeline session starts over
+        stats.record(Some(first), 10_000, 0, 0);
+        stats.record(
+            Some(first + std::time::Duration::from_secs(1)),
+            20_000,
+            0,
+            0,
+        );
+        assert_eq!(stats.latency.summary(), Some((20_000, 20_000, 20_000)));
+    }
+}
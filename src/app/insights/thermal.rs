@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! CPU/GPU clock and temperature readings for throttling hints
+//!
+//! Sustained high-resolution decoding can heat the machine until the
+//! firmware lowers clocks, which shows up as slowly rising decode time.
+//! Values come from sysfs and are `None` wherever the platform doesn't
+//! expose them; they are only read while the insights drawer is open.
+
+use std::path::Path;
+
+/// Decode time must rise by this factor over the reference to raise the hint
+const DECODE_TIME_RISE: f64 = 1.25;
+
+/// Clock must fall below this fraction of the reference to raise the hint
+const FREQUENCY_DROP: f64 = 0.85;
+
+/// One reading of clocks and temperature
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThermalSample {
+    /// Average current CPU clock across cores in MHz
+    pub cpu_freq_mhz: Option<u32>,
+    /// Current GPU clock in MHz (i915/xe or amdgpu)
+    pub gpu_freq_mhz: Option<u32>,
+    /// Hottest thermal zone in °C
+    pub max_temp_c: Option<f32>,
+}
+
+impl ThermalSample {
+    /// Read the current values from sysfs
+    pub fn read() -> Self {
+        Self {
+            cpu_freq_mhz: read_cpu_freq_mhz(),
+            gpu_freq_mhz: read_gpu_freq_mhz(),
+            max_temp_c: read_max_temp_c(),
+        }
+    }
+
+    /// Whether any value could be read
+    pub fn is_available(&self) -> bool {
+        self.cpu_freq_mhz.is_some() || self.gpu_freq_mhz.is_some() || self.max_temp_c.is_some()
+    }
+}
+
+/// Whether rising decode time coincides with falling CPU or GPU clocks
+///
+/// `reference` is the sample and decode time taken when the pipeline started.
+pub fn throttling_suspected(
+    reference: &ThermalSample,
+    reference_decode_us: u64,
+    now: &ThermalSample,
+    decode_us: u64,
+) -> bool {
+    if reference_decode_us == 0
+        || (decode_us as f64) < reference_decode_us as f64 * DECODE_TIME_RISE
+    {
+        return false;
+    }
+    let dropped = |before: Option<u32>, after: Option<u32>| match (before, after) {
+        (Some(before), Some(after)) => (after as f64) < before as f64 * FREQUENCY_DROP,
+        _ => false,
+    };
+    dropped(reference.cpu_freq_mhz, now.cpu_freq_mhz)
+        || dropped(reference.gpu_freq_mhz, now.gpu_freq_mhz)
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn read_cpu_freq_mhz() -> Option<u32> {
+    let entries = std::fs::read_dir("/sys/devices/system/cpu").ok()?;
+    let freqs_khz: Vec<u64> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.strip_prefix("cpu")
+                .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .filter_map(|entry| read_u64(&entry.path().join("cpufreq/scaling_cur_freq")))
+        .collect();
+    if freqs_khz.is_empty() {
+        return None;
+    }
+    Some((freqs_khz.iter().sum::<u64>() / freqs_khz.len() as u64 / 1000) as u32)
+}
+
+fn read_gpu_freq_mhz() -> Option<u32> {
+    let entries = std::fs::read_dir("/sys/class/drm").ok()?;
+    entries.flatten().find_map(|entry| {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with("card") || name.contains('-') {
+            return None;
+        }
+        let card = entry.path();
+        // Intel reports the actual clock directly
+        if let Some(mhz) = read_u64(&card.join("gt_act_freq_mhz")) {
+            return Some(mhz as u32);
+        }
+        // AMD lists the clock levels and marks the active one
+        let levels = std::fs::read_to_string(card.join("device/pp_dpm_sclk")).ok()?;
+        parse_active_dpm_level(&levels)
+    })
+}
+
+/// Parse the active level from an amdgpu `pp_dpm_sclk` listing
+///
+/// Lines look like `1: 1200Mhz *`, with `*` marking the active level.
+fn parse_active_dpm_level(levels: &str) -> Option<u32> {
+    levels
+        .lines()
+        .find(|line| line.trim_end().ends_with('*'))?
+        .split_whitespace()
+        .nth(1)?
+        .to_ascii_lowercase()
+        .strip_suffix("mhz")?
+        .parse()
+        .ok()
+}
+
+fn read_max_temp_c() -> Option<f32> {
+    let entries = std::fs::read_dir("/sys/class/thermal").ok()?;
+    entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("thermal_zone")
+        })
+        .filter_map(|entry| {
+            let millidegrees: i64 = std::fs::read_to_string(entry.path().join("temp"))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            // Unpopulated zones report 0 or negative sentinel values
+            (millidegrees > 0).then_some(millidegrees as f32 / 1000.0)
+        })
+        .reduce(f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_active_dpm_level() {
+        let levels = "0: 500Mhz \n1: 1200Mhz *\n2: 2100Mhz \n";
+        assert_eq!(parse_active_dpm_level(levels), Some(1200));
+        assert_eq!(parse_active_dpm_level("0: 500Mhz \n"), None);
+    }
+
+    #[test]
+    fn test_throttling_needs_slower_decode_and_lower_clock() {
+        let reference = ThermalSample {
+            cpu_freq_mhz: Some(3000),
+            gpu_freq_mhz: None,
+            max_temp_c: Some(55.0),
+        };
+        let throttled = ThermalSample {
+            cpu_freq_mhz: Some(2000),
+            ..reference
+        };
+        assert!(throttling_suspected(&reference, 4000, &throttled, 6000));
+        // Clock dropped but decode kept up (e.g. idle power saving)
+        assert!(!throttling_suspected(&reference, 4000, &throttled, 4200));
+        // Decode slowed but clocks held (e.g. busier scene)
+        assert!(!throttling_suspected(&reference, 4000, &reference, 6000));
+        // No clock data means no hint
+        let unknown = ThermalSample::default();
+        assert!(!throttling_suspected(&unknown, 4000, &unknown, 9000));
+    }
+}
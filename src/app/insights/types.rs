@@ -2,13 +2,64 @@
 
 //! Types for the Insights drawer diagnostic information.
 
-use crate::media::decoders::{DecoderDef, H264_DECODERS, H265_DECODERS, MJPEG_DECODERS};
-use std::sync::OnceLock;
+use crate::backends::camera::FrameDropPolicy;
+use crate::backends::camera::usb::{BandwidthHint, DeviceIdentity, UsbTopology};
 
-/// Cached decoder availability (checked once at startup, per codec)
-static MJPEG_AVAILABILITY: OnceLock<Vec<bool>> = OnceLock::new();
-static H264_AVAILABILITY: OnceLock<Vec<bool>> = OnceLock::new();
-static H265_AVAILABILITY: OnceLock<Vec<bool>> = OnceLock::new();
+use super::benchmark::UploadBenchmark;
+use super::elements::ElementInfo;
+use super::focus::FocusMeter;
+use super::history::FormatHistory;
+use super::pinned::{Metric, MetricValues, PinnedMetrics};
+use super::session_stats::SessionStats;
+use super::thermal::{self, ThermalSample};
+use crate::config::PresentMode;
+use crate::media::Codec;
+use crate::media::decoders::{
+    DecoderDef, H264_DECODERS, H265_DECODERS, MJPEG_DECODERS, is_decoder_broken, preferred_order,
+    resolve_decoder_element,
+};
+use crate::pipelines::video::retro::RetroFill;
+use std::collections::{HashSet, VecDeque};
+use std::sync::RwLock;
+use std::time::Instant;
+
+/// Number of display frame timestamps kept for refresh rate estimation
+const DISPLAY_FRAME_SAMPLES: usize = 61;
+
+/// Number of frame latency samples kept for the sparkline
+const LATENCY_SAMPLES: usize = 60;
+
+/// Pipeline uptime before the thermal reference is taken (lets decode settle)
+const THERMAL_REFERENCE_DELAY_SECS: u64 = 5;
+
+/// Cached decoder availability (per codec, re-probed when the VA-API node changes)
+static MJPEG_AVAILABILITY: RwLock<Option<Vec<FallbackState>>> = RwLock::new(None);
+static H264_AVAILABILITY: RwLock<Option<Vec<FallbackState>>> = RwLock::new(None);
+static H265_AVAILABILITY: RwLock<Option<Vec<FallbackState>>> = RwLock::new(None);
+
+/// User + system CPU time consumed by this process so far
+fn process_cpu_time() -> Option<std::time::Duration> {
+    // SAFETY: getrusage only writes into the zeroed struct we pass it.
+    let usage = unsafe {
+        let mut usage: libc::rusage = std::mem::zeroed();
+        if libc::getrusage(libc::RUSAGE_SELF, &mut usage) != 0 {
+            return None;
+        }
+        usage
+    };
+    let to_duration =
+        |tv: libc::timeval| std::time::Duration::new(tv.tv_sec as u64, (tv.tv_usec as u32) * 1000);
+    Some(to_duration(usage.ru_utime) + to_duration(usage.ru_stime))
+}
+
+/// Drop cached decoder availability so it is probed again on next use
+pub fn invalidate_decoder_availability() {
+    for cache in [&MJPEG_AVAILABILITY, &H264_AVAILABILITY, &H265_AVAILABILITY] {
+        if let Ok(mut guard) = cache.write() {
+            *guard = None;
+        }
+    }
+}
 
 /// State for Insights drawer diagnostic information
 #[derive(Debug, Clone, Default)]
@@ -18,18 +69,89 @@ pub struct InsightsState {
     pub full_pipeline_string: Option<String>,
     /// Decoder fallback chain status
     pub decoder_chain: Vec<DecoderStatus>,
+    /// Decode errors per decoder since the pipeline last started
+    pub decoder_errors: DecoderErrorStats,
+    /// VA-API render node description (e.g., "/dev/dri/renderD129", "Default")
+    pub vaapi_device: String,
+    /// Device quirk applied while building the pipeline
+    pub device_quirk: Option<&'static str>,
+    /// The encoded stream is being dumped to disk
+    pub raw_dump_running: bool,
+    /// Elements of the live pipeline, source first
+    pub pipeline_elements: Vec<ElementInfo>,
+    /// Elements whose properties are shown in the inspector
+    pub expanded_elements: HashSet<String>,
 
     // Current format chain
     /// Current format pipeline information
     pub format_chain: FormatChain,
+    /// Where the camera sits in the USB topology (None for non-USB cameras)
+    pub usb_topology: Option<UsbTopology>,
+    /// Node, driver and USB identifiers from sysfs (None without a V4L2 node)
+    pub device_identity: Option<DeviceIdentity>,
+    /// USB bandwidth is the likely reason the camera falls short of the requested mode
+    pub bandwidth_hint: Option<BandwidthHint>,
+    /// Recent format and decoder changes
+    pub format_history: FormatHistory,
+    /// Frames reach the GPU as DMABUF instead of mapped CPU memory
+    pub dmabuf_handoff: bool,
 
     // Performance metrics
     /// Frame latency in microseconds
     pub frame_latency_us: u64,
+    /// Recent frame latencies in microseconds, oldest first (for the sparkline)
+    pub latency_history: VecDeque<u64>,
+    /// How far the presented frame trails the newest decoded one, in microseconds
+    pub presentation_lag_us: Option<u64>,
+    /// Focus score of recent frames (sampled while the drawer is open)
+    pub focus: FocusMeter,
+    /// Min/avg/max of the per-frame metrics since the pipeline started
+    pub session_stats: SessionStats,
+    /// Show stage times as raw integer microseconds instead of smoothed ms
+    pub raw_microseconds: bool,
     /// Total dropped frames count
     pub dropped_frames: u64,
+    /// Drop policy of the active frame queue
+    pub frame_drop_policy: FrameDropPolicy,
+    /// Frames discarded by the frame queue's drop policy
+    pub policy_dropped_frames: u64,
+    /// Preview frames not uploaded because they matched the previous frame
+    pub skipped_uploads: u64,
+    /// Pipeline restarts triggered by the stall watchdog since startup
+    pub stall_restarts: u64,
+    /// Times the source changed resolution while the current pipeline ran
+    pub resolution_changes: u64,
+    /// When the stall watchdog last restarted the pipeline
+    pub last_stall_restart: Option<Instant>,
+    /// The watchdog restarted the pipeline and no frame has arrived since
+    pub stall_restarting: bool,
+    /// Watchdog restarts in a row without frames flowing in between
+    pub stall_restart_streak: u32,
+    /// Zero-copy vs copy upload benchmark progress and results
+    pub upload_benchmark: UploadBenchmark,
+    /// Idle inhibition taken for the given reason, or why it failed
+    pub idle_inhibit: Option<(
+        &'static str,
+        Result<crate::idle_inhibit::InhibitBackend, String>,
+    )>,
+    /// Fill level of the retroactive clip buffer
+    pub retro_fill: RetroFill,
+    /// Segment the running recording writes to, if it is split
+    pub recording_segment: Option<u32>,
+    /// Whether the preview is frozen
+    pub frozen: bool,
+    /// Frames stepped since the preview was frozen
+    pub stepped_frames: u64,
+    /// Presentation timestamp of the last frame shown
+    pub last_pts: Option<std::time::Duration>,
+    /// Whether the GPU pipelines are being rebuilt after a device loss
+    pub gpu_recovering: bool,
+    /// GPU device losses since startup
+    pub gpu_device_losses: u64,
     /// Frame size after decoding in bytes
     pub frame_size_decoded: usize,
+    /// GPU memory held by the app's textures in bytes (preview and gallery)
+    pub texture_memory: u64,
     /// GStreamer decode/conversion time in microseconds
     pub gstreamer_decode_time_us: u64,
     /// GPU compute shader conversion time in microseconds
@@ -38,6 +160,123 @@ pub struct InsightsState {
     pub copy_time_us: u64,
     /// Copy bandwidth in MB/s
     pub copy_bandwidth_mbps: f64,
+    /// Process CPU usage (100% = one core fully busy)
+    pub cpu_percent: Option<f32>,
+    /// Frames pulled from the pipeline since it was last (re)started
+    pub frames_processed: u64,
+    /// When the current pipeline was started
+    pub pipeline_started_at: Option<Instant>,
+    /// Frame rate between the last two metric updates
+    pub rolling_fps: Option<f32>,
+    /// Smoothed frame latency for display
+    pub latency_ema: Ema,
+    /// Smoothed GStreamer decode time for display
+    pub decode_time_ema: Ema,
+    /// Smoothed GPU upload time for display
+    pub gpu_upload_ema: Ema,
+    /// Latest CPU/GPU clock and temperature reading
+    pub thermal: ThermalSample,
+    /// Thermal reading and decode time shortly after the pipeline started
+    thermal_reference: Option<(ThermalSample, u64)>,
+    /// Decode time rose while clocks dropped since the reference was taken
+    pub throttling_suspected: bool,
+    /// Previous frame count sample (for the rolling frame rate)
+    last_frames_sample: Option<(Instant, u64)>,
+    /// Metrics captured just before the preview resolution cap last changed
+    pub preview_cap_baseline: Option<PerformanceSnapshot>,
+    /// Metrics pinned by the user for before/after comparisons
+    pub pinned_metrics: Option<PinnedMetrics>,
+    /// Previous CPU time sample (for CPU usage between updates)
+    last_cpu_sample: Option<(Instant, std::time::Duration)>,
+
+    // Display metrics
+    /// Display refresh rate estimated from window frame callbacks
+    pub display_refresh_hz: Option<f32>,
+    /// Present mode requested from the renderer when the window was created
+    pub requested_present_mode: PresentMode,
+    /// Recent window frame timestamps (for refresh rate estimation)
+    display_frame_times: VecDeque<Instant>,
+}
+
+/// Exponential moving average of a displayed metric
+///
+/// Display-only: the raw values in [`InsightsState`] stay unsmoothed for
+/// export and logging.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Ema {
+    value: Option<f64>,
+}
+
+impl Ema {
+    /// Fold in a sample; the first sample is taken as-is
+    pub fn update(&mut self, sample: f64, alpha: f64) -> f64 {
+        let value = match self.value {
+            Some(previous) => previous + alpha * (sample - previous),
+            None => sample,
+        };
+        self.value = Some(value);
+        value
+    }
+
+    /// Current smoothed value
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Forget the history (e.g. when smoothing is turned off)
+    pub fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+/// Point-in-time performance figures for before/after comparisons
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerformanceSnapshot {
+    /// Resolution of the frames reaching the preview
+    pub resolution: String,
+    /// Frame latency in microseconds
+    pub frame_latency_us: u64,
+    /// Process CPU usage, if it was being sampled
+    pub cpu_percent: Option<f32>,
+}
+
+/// Stage of the preview path that ran on the CPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuStage {
+    /// Software decoder selected
+    Decode,
+    /// Frames mapped into CPU memory and uploaded from there
+    Handoff,
+    /// `videoconvert` converts colors before the GPU
+    Conversion,
+}
+
+/// Where each stage of the preview path runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Acceleration {
+    /// Hardware decoder selected (`None` for raw formats, which need no decoding)
+    pub hardware_decode: Option<bool>,
+    /// Frames handed to the GPU as DMABUF, without a copy through CPU memory
+    pub dmabuf_handoff: bool,
+    /// Color conversion runs in a GPU shader only
+    pub gpu_conversion: bool,
+}
+
+impl Acceleration {
+    /// Stages that break the hardware chain, in pipeline order
+    pub fn cpu_stages(&self) -> Vec<CpuStage> {
+        let mut stages = Vec::new();
+        if self.hardware_decode == Some(false) {
+            stages.push(CpuStage::Decode);
+        }
+        if !self.dmabuf_handoff {
+            stages.push(CpuStage::Handoff);
+        }
+        if !self.gpu_conversion {
+            stages.push(CpuStage::Conversion);
+        }
+        stages
+    }
 }
 
 /// Status of a decoder in the fallback chain
@@ -45,10 +284,69 @@ pub struct InsightsState {
 pub struct DecoderStatus {
     /// Decoder element name (e.g., "vaapijpegdec")
     pub name: &'static str,
-    /// Human-readable description
+    /// English description (for diagnostics)
     pub description: &'static str,
+    /// Description in the UI language
+    pub localized_description: String,
     /// Current state in the fallback chain
     pub state: FallbackState,
+    /// Whether this is a hardware decoder
+    pub is_hardware: bool,
+}
+
+/// Decode errors per decoder since the pipeline last started
+///
+/// Errors are the warnings and errors a decoder posted on the bus plus
+/// corrupted buffers that reached the preview. The rate relates them to the
+/// frames delivered over the same time, which gives evidence for a decoder
+/// that keeps failing on a camera's stream.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecoderErrorStats {
+    /// Errors by decoder factory name
+    errors: Vec<(String, u64)>,
+    /// Frames delivered since the pipeline started
+    frames: u64,
+}
+
+impl DecoderErrorStats {
+    pub fn new(errors: Vec<(String, u64)>, frames: u64) -> Self {
+        Self { errors, frames }
+    }
+
+    /// Decode errors reported by `decoder`
+    pub fn errors(&self, decoder: &str) -> u64 {
+        self.errors
+            .iter()
+            .find(|(name, _)| name == decoder)
+            .map_or(0, |(_, errors)| *errors)
+    }
+
+    /// Share of frames `decoder` failed on (None while it reported no errors)
+    ///
+    /// A failed frame usually isn't delivered, so errors and delivered frames
+    /// together make up the attempts.
+    pub fn error_rate(&self, decoder: &str) -> Option<f64> {
+        let errors = self.errors(decoder);
+        (errors > 0).then(|| errors as f64 / (self.frames + errors) as f64)
+    }
+
+    /// Whether `decoder` fails often enough to be flagged
+    pub fn is_unreliable(&self, decoder: &str) -> bool {
+        self.error_rate(decoder)
+            .is_some_and(|rate| rate > crate::constants::pipeline::DECODER_ERROR_RATE_WARNING)
+    }
+
+    /// Decoders that reported errors, with their error counts
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.errors
+            .iter()
+            .map(|(decoder, errors)| (decoder.as_str(), *errors))
+    }
+
+    /// Frames delivered since the pipeline started
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
 }
 
 /// State of a decoder in the fallback chain
@@ -58,6 +356,8 @@ pub enum FallbackState {
     Selected,
     /// Available but not selected
     Available,
+    /// Registered, but failed to instantiate during startup validation
+    Broken,
     /// Not present on the system
     #[default]
     Unavailable,
@@ -70,97 +370,653 @@ pub struct FormatChain {
     pub source: String,
     /// Current resolution
     pub resolution: String,
-    /// Current framerate
+    /// Resolution after the preview downscale (`None` when shown at native size)
+    pub displayed_resolution: Option<String>,
+    /// Source row stride vs pixel bytes per row, when rows are padded
+    pub row_stride: Option<String>,
+    /// How the upload likely misrenders the padded rows, if it does
+    pub stride_warning: Option<String>,
+    /// Current framerate, measured and labelled "VFR" for variable-rate sources
     pub framerate: String,
     /// Native format from camera (e.g., "MJPG", "YUYV", "NV12")
     pub native_format: String,
     /// GStreamer output format after decoding (if applicable, e.g., "I420", "NV12")
     pub gstreamer_output: Option<String>,
-    /// WGPU processing description (e.g., "I420 → RGBA", "Passthrough")
+    /// WGPU processing description (e.g., "I420 → RGBA", "Passthrough (no conversion)")
     pub wgpu_processing: String,
+    /// Path the newest frame took on the GPU, checked against the format
+    pub gpu_path: super::PassthroughCheck,
+    /// GPU lacks compute shaders: fragment-shader conversion, histogram disabled
+    pub compute_unavailable: bool,
+    /// Raw colorimetry field from the camera source caps (e.g., "bt709", "2:4:7:1")
+    pub colorimetry: Option<String>,
+    /// Scan type declared by the source (e.g., "Progressive", "Interlaced (tff)")
+    pub scan: Option<String>,
+    /// Caps negotiated on the forced-caps filter, when caps are forced
+    pub forced_caps: Option<String>,
+}
+
+impl FormatChain {
+    /// Update the source and displayed resolutions
+    ///
+    /// `source` is the size negotiated with the camera, which follows caps
+    /// changes mid-stream and then differs from the `requested` format.
+    /// `displayed` is the size of the frames reaching the preview.
+    pub fn update_resolution(
+        &mut self,
+        requested: (u32, u32),
+        source: Option<(u32, u32)>,
+        displayed: Option<(u32, u32)>,
+    ) {
+        let source = source.unwrap_or(requested);
+        self.resolution = if source == requested {
+            format!("{}x{}", source.0, source.1)
+        } else {
+            format!(
+                "{}x{} (requested {}x{})",
+                source.0, source.1, requested.0, requested.1
+            )
+        };
+        self.displayed_resolution = displayed
+            .filter(|&displayed| displayed != source)
+            .map(|(width, height)| format!("{}x{} (downscaled)", width, height));
+    }
+
+    /// Update the frame rate from the negotiated one and the measured cadence
+    ///
+    /// A variable-rate source only delivers up to its negotiated rate, so it
+    /// is shown with the measured average and current rate instead.
+    pub fn update_framerate(
+        &mut self,
+        negotiated: Option<crate::backends::camera::types::Framerate>,
+        cadence: Option<crate::backends::camera::pipewire::FrameCadence>,
+    ) {
+        self.framerate = match (negotiated, cadence) {
+            (_, Some(cadence)) if cadence.variable => {
+                let mut framerate = format!(
+                    "VFR · {:.1} fps avg, {:.1} fps now",
+                    cadence.average_fps, cadence.instant_fps
+                );
+                if let Some(fps) = negotiated {
+                    framerate.push_str(&format!(" (up to {} fps)", fps));
+                }
+                framerate
+            }
+            (Some(fps), _) => format!("{} fps", fps),
+            (None, _) => "N/A".to_string(),
+        };
+    }
 }
 
 /// Get cached decoder availability for a decoder list
+///
+/// Each entry is `Available`, `Broken` (if startup validation ran and the
+/// element failed to instantiate) or `Unavailable`.
 fn get_cached_availability(
     decoders: &[DecoderDef],
-    cache: &'static OnceLock<Vec<bool>>,
-) -> &'static Vec<bool> {
-    cache.get_or_init(|| {
-        decoders
-            .iter()
-            .map(|d| gstreamer::ElementFactory::find(d.name).is_some())
-            .collect()
-    })
+    cache: &'static RwLock<Option<Vec<FallbackState>>>,
+) -> Vec<FallbackState> {
+    if let Some(cached) = cache.read().ok().and_then(|guard| guard.clone()) {
+        return cached;
+    }
+
+    let availability: Vec<FallbackState> = decoders
+        .iter()
+        .map(|d| {
+            if gstreamer::ElementFactory::find(&resolve_decoder_element(d.name)).is_none() {
+                FallbackState::Unavailable
+            } else if is_decoder_broken(d.name) {
+                FallbackState::Broken
+            } else {
+                FallbackState::Available
+            }
+        })
+        .collect();
+    if let Ok(mut guard) = cache.write() {
+        *guard = Some(availability.clone());
+    }
+    availability
 }
 
 /// Build a decoder status chain from decoder definitions
 ///
 /// This is the generic builder that replaces the three format-specific methods.
+/// Which of `decoders` is actually used in the pipeline
+fn active_decoder(decoders: &'static [DecoderDef], pipeline: &str) -> Option<&'static str> {
+    decoders.iter().find_map(|d| {
+        // Check for decoder name followed by space, '!', or end of string
+        let name = resolve_decoder_element(d.name);
+        if pipeline.contains(&format!("{} ", name))
+            || pipeline.contains(&format!("{}!", name))
+            || pipeline.ends_with(name.as_ref())
+        {
+            Some(d.name)
+        } else {
+            None
+        }
+    })
+}
+
 fn build_chain_from_defs(
     decoders: &'static [DecoderDef],
-    availability: &[bool],
+    availability: &[FallbackState],
     full_pipeline: Option<&str>,
 ) -> Vec<DecoderStatus> {
-    // Find which decoder is actually used in the pipeline
-    let active_decoder = full_pipeline.and_then(|pipeline| {
-        decoders.iter().find_map(|d| {
-            // Check for decoder name followed by space, '!', or end of string
-            if pipeline.contains(&format!("{} ", d.name))
-                || pipeline.contains(&format!("{}!", d.name))
-                || pipeline.ends_with(d.name)
-            {
-                Some(d.name)
-            } else {
-                None
-            }
-        })
-    });
+    // Shown in the order the pipeline tries them; availability is cached in
+    // the built-in order
+    let ordered = preferred_order(decoders);
+    let active_decoder = full_pipeline.and_then(|pipeline| active_decoder(ordered, pipeline));
 
-    decoders
+    ordered
         .iter()
-        .enumerate()
-        .map(|(i, decoder)| {
+        .map(|decoder| {
             let state = if active_decoder == Some(decoder.name) {
                 FallbackState::Selected
-            } else if availability.get(i).copied().unwrap_or(false) {
-                FallbackState::Available
             } else {
-                FallbackState::Unavailable
+                decoders
+                    .iter()
+                    .position(|d| d.name == decoder.name)
+                    .and_then(|i| availability.get(i).copied())
+                    .unwrap_or_default()
             };
             DecoderStatus {
                 name: decoder.name,
                 description: decoder.description,
+                localized_description: decoder.localized_description(),
                 state,
+                is_hardware: decoder.is_hardware,
             }
         })
         .collect()
 }
 
 impl InsightsState {
+    /// Record a window frame callback and update the refresh rate estimate
+    ///
+    /// Frame callbacks are paced by the compositor, so the average interval
+    /// between them tracks the display refresh rate.
+    pub fn record_display_frame(&mut self, at: Instant) {
+        // Samples from before the drawer was last closed would skew the average
+        if self
+            .display_frame_times
+            .back()
+            .is_some_and(|last| at.duration_since(*last).as_secs() >= 1)
+        {
+            self.display_frame_times.clear();
+        }
+        if self.display_frame_times.len() == DISPLAY_FRAME_SAMPLES {
+            self.display_frame_times.pop_front();
+        }
+        self.display_frame_times.push_back(at);
+
+        if let (Some(first), Some(last)) = (
+            self.display_frame_times.front(),
+            self.display_frame_times.back(),
+        ) {
+            let intervals = self.display_frame_times.len() - 1;
+            let span = last.duration_since(*first).as_secs_f32();
+            if intervals > 0 && span > 0.0 {
+                self.display_refresh_hz = Some(intervals as f32 / span);
+            }
+        }
+    }
+
+    /// Set the frame latency and add it to the sparkline history
+    pub fn record_latency(&mut self, latency_us: u64) {
+        self.frame_latency_us = latency_us;
+        if self.latency_history.len() == LATENCY_SAMPLES {
+            self.latency_history.pop_front();
+        }
+        self.latency_history.push_back(latency_us);
+    }
+
+    /// Recent latencies scaled to 0..=1 between the lowest and highest of them
+    ///
+    /// Scaling to the visible range rather than from zero makes a spike of a
+    /// few milliseconds stand out on a 30 ms baseline. Flat history sits in
+    /// the middle.
+    pub fn latency_levels(&self) -> Vec<f32> {
+        let (Some(&min), Some(&max)) = (
+            self.latency_history.iter().min(),
+            self.latency_history.iter().max(),
+        ) else {
+            return Vec::new();
+        };
+        let range = max - min;
+        self.latency_history
+            .iter()
+            .map(|&us| {
+                if range == 0 {
+                    0.5
+                } else {
+                    (us - min) as f32 / range as f32
+                }
+            })
+            .collect()
+    }
+
+    /// Update CPU usage from the process CPU time consumed since the last call
+    pub fn record_cpu_sample(&mut self) {
+        let Some(cpu_time) = process_cpu_time() else {
+            return;
+        };
+        let now = Instant::now();
+        if let Some((last_at, last_cpu)) = self.last_cpu_sample {
+            let wall = now.duration_since(last_at).as_secs_f32();
+            // Samples from before the drawer was last closed would average over idle time
+            if wall > 0.0 && wall < 2.0 {
+                let busy = cpu_time.saturating_sub(last_cpu).as_secs_f32();
+                self.cpu_percent = Some(busy / wall * 100.0);
+            }
+        }
+        self.last_cpu_sample = Some((now, cpu_time));
+    }
+
+    /// Update the session frame counter and rolling frame rate
+    ///
+    /// A different `started_at` means the pipeline was rebuilt, which resets
+    /// the rolling average along with the counter.
+    pub fn record_session_frames(&mut self, frames: u64, started_at: Option<Instant>) {
+        if started_at != self.pipeline_started_at {
+            self.last_frames_sample = None;
+            self.rolling_fps = None;
+            self.latency_history.clear();
+            self.thermal_reference = None;
+            self.throttling_suspected = false;
+        }
+        let now = Instant::now();
+        if let Some((last_at, last_frames)) = self.last_frames_sample {
+            let wall = now.duration_since(last_at).as_secs_f32();
+            if wall > 0.0 && wall < 2.0 {
+                self.rolling_fps = Some(frames.saturating_sub(last_frames) as f32 / wall);
+            }
+        }
+        self.last_frames_sample = Some((now, frames));
+        self.frames_processed = frames;
+        self.pipeline_started_at = started_at;
+    }
+
+    /// Forget smoothed and rolling metrics measured at the previous format
+    pub fn reset_format_metrics(&mut self) {
+        self.latency_ema.reset();
+        self.decode_time_ema.reset();
+        self.gpu_upload_ema.reset();
+        self.last_frames_sample = None;
+        self.rolling_fps = None;
+        self.latency_history.clear();
+        self.thermal_reference = None;
+        self.throttling_suspected = false;
+        self.format_chain.displayed_resolution = None;
+        self.format_chain.row_stride = None;
+        self.format_chain.stride_warning = None;
+        self.focus.reset();
+    }
+
+    /// Update the display-smoothed metrics from the latest raw values
+    pub fn smooth_metrics(&mut self, alpha: Option<f64>) {
+        let Some(alpha) = alpha else {
+            self.latency_ema.reset();
+            self.decode_time_ema.reset();
+            self.gpu_upload_ema.reset();
+            return;
+        };
+        self.latency_ema.update(self.frame_latency_us as f64, alpha);
+        self.decode_time_ema
+            .update(self.gstreamer_decode_time_us as f64, alpha);
+        self.gpu_upload_ema
+            .update(self.gpu_conversion_time_us as f64, alpha);
+    }
+
+    /// Read clocks and temperature and update the throttling hint
+    ///
+    /// Compares against a reference taken a few seconds into the pipeline's
+    /// life, so call after the decode time and session stats are updated.
+    pub fn record_thermal_sample(&mut self) {
+        self.thermal = ThermalSample::read();
+        if !self.thermal.is_available() {
+            return;
+        }
+
+        let settled = self
+            .uptime()
+            .is_some_and(|uptime| uptime.as_secs() >= THERMAL_REFERENCE_DELAY_SECS);
+        match &self.thermal_reference {
+            Some((reference, reference_decode_us)) => {
+                self.throttling_suspected = thermal::throttling_suspected(
+                    reference,
+                    *reference_decode_us,
+                    &self.thermal,
+                    self.gstreamer_decode_time_us,
+                );
+            }
+            None if settled && self.gstreamer_decode_time_us > 0 => {
+                self.thermal_reference = Some((self.thermal, self.gstreamer_decode_time_us));
+            }
+            None => {}
+        }
+    }
+
+    /// Time since the current pipeline was started
+    pub fn uptime(&self) -> Option<std::time::Duration> {
+        self.pipeline_started_at.map(|started| started.elapsed())
+    }
+
+    /// Average frame rate over the whole pipeline session
+    pub fn session_fps(&self) -> Option<f32> {
+        let secs = self.uptime()?.as_secs_f32();
+        (secs >= 1.0).then(|| self.frames_processed as f32 / secs)
+    }
+
+    /// Current metrics as displayed, for comparison with pinned ones
+    pub fn metric_values(&self, smoothed: bool) -> MetricValues {
+        let ms = |raw_us: u64, ema: &Ema| {
+            let smoothed_value = if smoothed { ema.value() } else { None };
+            smoothed_value.unwrap_or(raw_us as f64) / 1000.0
+        };
+        let mut values = MetricValues::default();
+        values.set(
+            Metric::FrameLatency,
+            Some(ms(self.frame_latency_us, &self.latency_ema)),
+        );
+        values.set(
+            Metric::PresentationLag,
+            self.presentation_lag_us
+                .map(|lag_us| lag_us as f64 / 1000.0),
+        );
+        values.set(
+            Metric::DecodeTime,
+            Some(ms(self.gstreamer_decode_time_us, &self.decode_time_ema)),
+        );
+        values.set(
+            Metric::GpuUpload,
+            Some(ms(self.gpu_conversion_time_us, &self.gpu_upload_ema)),
+        );
+        values.set(Metric::CpuUsage, self.cpu_percent.map(f64::from));
+        values.set(Metric::FrameRate, self.rolling_fps.map(f64::from));
+        values
+    }
+
+    /// Current figures for a before/after comparison
+    pub fn performance_snapshot(&self) -> PerformanceSnapshot {
+        PerformanceSnapshot {
+            resolution: self
+                .format_chain
+                .displayed_resolution
+                .clone()
+                .unwrap_or_else(|| self.format_chain.resolution.clone()),
+            frame_latency_us: self.frame_latency_us,
+            cpu_percent: self.cpu_percent,
+        }
+    }
+
     /// Build the decoder fallback chain based on pixel format
     ///
-    /// `pixel_format` is the camera's native format (e.g., "MJPG", "H264", "YUYV")
+    /// `pixel_format` is the camera's native fourcc (e.g., "MJPG", "H264", "YUYV"), parsed
+    /// with [`Codec::from_fourcc`] so aliases like "JPEG" and "HEVC" are recognized.
     /// `full_pipeline` is the actual GStreamer pipeline string to parse for the active decoder.
-    /// Decoder availability is cached on first call since it doesn't change at runtime.
+    /// Decoder availability is cached until the VA-API render node changes.
+    /// Where decode, GPU handoff and color conversion of the current pipeline run
+    pub fn acceleration(&self) -> Acceleration {
+        let hardware_decode = (!self.decoder_chain.is_empty()).then(|| {
+            self.decoder_chain
+                .iter()
+                .any(|decoder| decoder.state == FallbackState::Selected && decoder.is_hardware)
+        });
+        let gpu_conversion = !self
+            .full_pipeline_string
+            .as_deref()
+            .is_some_and(|pipeline| pipeline.contains("videoconvert"));
+        Acceleration {
+            hardware_decode,
+            dmabuf_handoff: self.dmabuf_handoff,
+            gpu_conversion,
+        }
+    }
+
+    /// Whether the preview runs on hardware from decoder to display
+    ///
+    /// True only when the selected decoder is a hardware one (or none is
+    /// needed), frames reach the GPU as DMABUF and colors are converted on
+    /// the GPU.
+    pub fn is_fully_hw_accelerated(&self) -> bool {
+        self.acceleration().cpu_stages().is_empty()
+    }
+
+    /// Decoder the pipeline uses for `pixel_format` (`None` for raw formats)
+    pub fn selected_decoder(pixel_format: &str, full_pipeline: &str) -> Option<&'static str> {
+        let decoders = match Codec::from_fourcc(pixel_format) {
+            Codec::MJPEG => MJPEG_DECODERS,
+            Codec::H264 => H264_DECODERS,
+            Codec::H265 => H265_DECODERS,
+            _ => return None,
+        };
+        active_decoder(preferred_order(decoders), full_pipeline)
+    }
+
     pub fn build_decoder_chain(
         pixel_format: Option<&str>,
         full_pipeline: Option<&str>,
     ) -> Vec<DecoderStatus> {
-        match pixel_format {
-            Some("MJPG") | Some("MJPEG") => {
+        let codec = pixel_format.map(Codec::from_fourcc);
+        match codec {
+            Some(Codec::MJPEG) => {
                 let availability = get_cached_availability(MJPEG_DECODERS, &MJPEG_AVAILABILITY);
-                build_chain_from_defs(MJPEG_DECODERS, availability, full_pipeline)
+                build_chain_from_defs(MJPEG_DECODERS, &availability, full_pipeline)
             }
-            Some("H264") => {
+            Some(Codec::H264) => {
                 let availability = get_cached_availability(H264_DECODERS, &H264_AVAILABILITY);
-                build_chain_from_defs(H264_DECODERS, availability, full_pipeline)
+                build_chain_from_defs(H264_DECODERS, &availability, full_pipeline)
             }
-            Some("H265") | Some("HEVC") => {
+            Some(Codec::H265) => {
                 let availability = get_cached_availability(H265_DECODERS, &H265_AVAILABILITY);
-                build_chain_from_defs(H265_DECODERS, availability, full_pipeline)
+                build_chain_from_defs(H265_DECODERS, &availability, full_pipeline)
             }
             // Raw formats don't need decoders
             _ => Vec::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolution_follows_source() {
+        use gstreamer::prelude::*;
+
+        if gstreamer::init().is_err() || gstreamer::ElementFactory::find("videotestsrc").is_none() {
+            println!("Skipping test (no videotestsrc)");
+            return;
+        }
+        // `camera` stands in for a camera switching size on its own, the
+        // second filter is the one built from the selected 720p format
+        let pipeline = gstreamer::parse::launch(
+            "videotestsrc is-live=true ! \
+             capsfilter name=camera caps=video/x-raw,format=NV12,width=1280,height=720 ! \
+             video/x-raw,format=NV12,width=(int)1280,height=(int)720 ! \
+             queue max-size-buffers=1 leaky=downstream ! \
+             appsink name=sink sync=false",
+        )
+        .unwrap()
+        .downcast::<gstreamer::Pipeline>()
+        .unwrap();
+        crate::media::decoders::allow_size_changes(&pipeline);
+        let camera = pipeline.by_name("camera").unwrap();
+        let sink = pipeline
+            .by_name("sink")
+            .unwrap()
+            .downcast::<gstreamer_app::AppSink>()
+            .unwrap();
+        pipeline.set_state(gstreamer::State::Playing).unwrap();
+
+        let next_size = || {
+            let sample = sink.try_pull_sample(gstreamer::ClockTime::from_seconds(5))?;
+            let structure = sample.caps()?.structure(0)?.to_owned();
+            let width = structure.get::<i32>("width").ok()?;
+            let height = structure.get::<i32>("height").ok()?;
+            Some((width as u32, height as u32))
+        };
+        let mut chain = FormatChain::default();
+        let size = next_size();
+        assert_eq!(size, Some((1280, 720)));
+        chain.update_resolution((1280, 720), size, size);
+        assert_eq!(chain.resolution, "1280x720");
+        assert_eq!(chain.displayed_resolution, None);
+
+        // The source switches to 1080p without a new format being selected
+        camera.set_property(
+            "caps",
+            gstreamer::Caps::builder("video/x-raw")
+                .field("format", "NV12")
+                .field("width", 1920i32)
+                .field("height", 1080i32)
+                .build(),
+        );
+        let size = (0..30)
+            .map_while(|_| next_size())
+            .find(|&size| size == (1920, 1080));
+        assert_eq!(size, Some((1920, 1080)), "frames stopped after the switch");
+        // Frames keep flowing at the new size
+        assert_eq!(next_size(), Some((1920, 1080)));
+        pipeline.set_state(gstreamer::State::Null).unwrap();
+
+        chain.update_resolution((1280, 720), size, size);
+        assert_eq!(chain.resolution, "1920x1080 (requested 1280x720)");
+        assert_eq!(chain.displayed_resolution, None);
+
+        chain.update_resolution((1280, 720), size, Some((960, 540)));
+        assert_eq!(
+            chain.displayed_resolution.as_deref(),
+            Some("960x540 (downscaled)")
+        );
+
+        // Nothing negotiated yet
+        chain.update_resolution((1280, 720), None, None);
+        assert_eq!(chain.resolution, "1280x720");
+    }
+
+    #[test]
+    fn test_variable_framerate_is_labelled() {
+        use crate::backends::camera::pipewire::FrameCadence;
+        use crate::backends::camera::types::Framerate;
+
+        let mut chain = FormatChain::default();
+        let mut cadence = FrameCadence {
+            variable: false,
+            average_fps: 29.9,
+            instant_fps: 30.2,
+        };
+        chain.update_framerate(Some(Framerate::new(30, 1)), Some(cadence));
+        assert_eq!(chain.framerate, "30 fps");
+
+        cadence.variable = true;
+        cadence.average_fps = 12.04;
+        chain.update_framerate(Some(Framerate::new(30, 1)), Some(cadence));
+        assert_eq!(
+            chain.framerate,
+            "VFR · 12.0 fps avg, 30.2 fps now (up to 30 fps)"
+        );
+
+        chain.update_framerate(None, None);
+        assert_eq!(chain.framerate, "N/A");
+    }
+
+    #[test]
+    fn test_latency_levels_follow_visible_range() {
+        let mut insights = InsightsState::default();
+        assert!(insights.latency_levels().is_empty());
+        for us in [30_000, 30_000, 30_000] {
+            insights.record_latency(us);
+        }
+        assert_eq!(insights.latency_levels(), vec![0.5; 3]);
+
+        insights.record_latency(34_000);
+        insights.record_latency(32_000);
+        assert_eq!(insights.latency_levels(), vec![0.0, 0.0, 0.0, 1.0, 0.5]);
+
+        // Bounded, oldest samples go first
+        for _ in 0..LATENCY_SAMPLES {
+            insights.record_latency(1_000);
+        }
+        assert_eq!(insights.latency_history.len(), LATENCY_SAMPLES);
+        assert!(insights.latency_history.iter().all(|&us| us == 1_000));
+
+        // A rebuilt pipeline starts a new history
+        insights.record_session_frames(0, Some(Instant::now()));
+        assert!(insights.latency_history.is_empty());
+    }
+
+    fn decoder(name: &'static str, state: FallbackState, is_hardware: bool) -> DecoderStatus {
+        DecoderStatus {
+            name,
+            description: "",
+            localized_description: String::new(),
+            state,
+            is_hardware,
+        }
+    }
+
+    #[test]
+    fn test_hw_acceleration_requires_every_stage() {
+        let mut insights = InsightsState {
+            decoder_chain: vec![
+                decoder("vajpegdec", FallbackState::Selected, true),
+                decoder("jpegdec", FallbackState::Available, false),
+            ],
+            full_pipeline_string: Some("pipewiresrc ! vajpegdec ! appsink name=sink".to_string()),
+            dmabuf_handoff: true,
+            ..Default::default()
+        };
+        assert!(insights.is_fully_hw_accelerated());
+
+        // Mapped buffers are uploaded through CPU memory
+        insights.dmabuf_handoff = false;
+        assert_eq!(
+            insights.acceleration().cpu_stages(),
+            vec![CpuStage::Handoff]
+        );
+
+        insights.decoder_chain = vec![
+            decoder("vajpegdec", FallbackState::Unavailable, true),
+            decoder("jpegdec", FallbackState::Selected, false),
+        ];
+        insights.full_pipeline_string =
+            Some("pipewiresrc ! jpegdec ! videoconvert ! appsink name=sink".to_string());
+        insights.dmabuf_handoff = true;
+        assert_eq!(
+            insights.acceleration().cpu_stages(),
+            vec![CpuStage::Decode, CpuStage::Conversion]
+        );
+        assert!(!insights.is_fully_hw_accelerated());
+    }
+
+    #[test]
+    fn test_decoder_error_rate() {
+        let stats = DecoderErrorStats::new(vec![("vaapijpegdec".to_string(), 12)], 88);
+        assert_eq!(stats.errors("vaapijpegdec"), 12);
+        assert_eq!(stats.error_rate("vaapijpegdec"), Some(0.12));
+        assert!(stats.is_unreliable("vaapijpegdec"));
+        // Decoders without errors get no rate rather than 0%
+        assert_eq!(stats.error_rate("jpegdec"), None);
+        assert!(!stats.is_unreliable("jpegdec"));
+        // Errors before any frame was delivered are a 100% rate
+        let failing = DecoderErrorStats::new(vec![("jpegdec".to_string(), 3)], 0);
+        assert_eq!(failing.error_rate("jpegdec"), Some(1.0));
+    }
+
+    #[test]
+    fn test_ema_update_sequence() {
+        let mut ema = Ema::default();
+        assert_eq!(ema.value(), None);
+        assert_eq!(ema.update(100.0, 0.5), 100.0);
+        assert_eq!(ema.update(200.0, 0.5), 150.0);
+        assert_eq!(ema.update(200.0, 0.5), 175.0);
+        assert_eq!(ema.update(0.0, 0.5), 87.5);
+        assert_eq!(ema.value(), Some(87.5));
+
+        ema.reset();
+        assert_eq!(ema.update(40.0, 0.2), 40.0);
+        assert!((ema.update(90.0, 0.2) - 50.0).abs() < 1e-9);
+    }
+}
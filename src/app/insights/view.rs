@@ -3,24 +3,103 @@
 //! Insights drawer view for displaying diagnostic information
 
 use crate::app::state::{AppModel, ContextPage, Message};
+use crate::backends::camera::FrameDropPolicy;
+use crate::backends::camera::usb::BandwidthHint;
+use crate::config::{MetricsSmoothing, PresentMode};
 use crate::fl;
 use cosmic::Element;
 use cosmic::app::context_drawer;
 use cosmic::iced::{Alignment, Length};
 use cosmic::widget;
 
-use super::types::FallbackState;
+use super::PassthroughCheck;
+use super::benchmark::UploadPath;
+use super::pinned::{Comparison, Metric};
+use super::provenance::{Provenance, ReportedMetric};
+use super::types::{CpuStage, FallbackState};
+
+/// Height of the latency sparkline in pixels
+const SPARKLINE_HEIGHT: f32 = 24.0;
+
+/// Value of a performance figure with a tooltip on how it's obtained
+///
+/// Estimated figures get a small "estimated" mark next to the value.
+fn metric_value<'a>(text: String, metric: ReportedMetric) -> Element<'a, Message> {
+    let mut value = widget::row()
+        .push(widget::text::body(text))
+        .spacing(4)
+        .align_y(Alignment::End);
+    if metric.provenance() == Provenance::Estimated {
+        value = value.push(widget::text::caption(fl!("insights-estimated")));
+    }
+    let method = match metric {
+        ReportedMetric::FrameLatency => fl!("insights-method-frame-latency"),
+        ReportedMetric::PresentationLag => fl!("insights-method-presentation-lag"),
+        ReportedMetric::DecodeTime => fl!("insights-method-decode-time"),
+        ReportedMetric::CopyTime => fl!("insights-method-copy-time"),
+        ReportedMetric::GpuUpload => fl!("insights-method-gpu-upload"),
+        ReportedMetric::UploadBandwidth => fl!("insights-method-upload-bandwidth"),
+        ReportedMetric::CpuUsage => fl!("insights-method-cpu-usage"),
+        ReportedMetric::FrameRate => fl!("insights-method-frame-rate"),
+        ReportedMetric::DisplayRefresh => fl!("insights-method-display-refresh"),
+        ReportedMetric::TextureMemory => fl!("insights-method-texture-memory"),
+    };
+    widget::tooltip(
+        value,
+        widget::text::body(method),
+        widget::tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// Bar chart of `levels` (0..=1), one thin bar per sample
+///
+/// Plain containers rather than a canvas: a few dozen quads per redraw.
+fn sparkline<'a>(levels: &[f32]) -> Element<'a, Message> {
+    let bars = levels.iter().map(|&level| {
+        // Keep the lowest bars visible
+        let height = 2.0 + level.clamp(0.0, 1.0) * (SPARKLINE_HEIGHT - 2.0);
+        widget::container(widget::Space::new(
+            Length::Fixed(2.0),
+            Length::Fixed(height),
+        ))
+        .style(|theme| widget::container::Style {
+            background: Some(cosmic::iced::Background::Color(
+                theme.cosmic().accent_color().into(),
+            )),
+            ..Default::default()
+        })
+        .into()
+    });
+    widget::row::with_children(bars.collect())
+        .spacing(1)
+        .height(Length::Fixed(SPARKLINE_HEIGHT))
+        .align_y(Alignment::End)
+        .into()
+}
 
 impl AppModel {
     /// Create the insights view for the context drawer
     ///
     /// Shows pipeline information, performance metrics, and format capabilities.
     pub fn insights_view(&self) -> context_drawer::ContextDrawer<'_, Message> {
-        let sections = vec![
+        let mut sections = vec![
             self.build_pipeline_section().into(),
             self.build_performance_section().into(),
-            self.build_formats_section().into(),
+            self.build_focus_section().into(),
         ];
+        if let Some(thermal) = self.build_thermal_section() {
+            sections.push(thermal.into());
+        }
+        if let Some(http_stream) = self.build_http_stream_section() {
+            sections.push(http_stream.into());
+        }
+        sections.push(self.build_formats_section().into());
+        sections.push(self.build_format_history_section().into());
+        sections.push(self.build_benchmark_section().into());
+        sections.push(self.build_device_section().into());
+        sections.push(self.build_diagnostics_section().into());
+        sections.push(self.build_advanced_section().into());
 
         let content: Element<'_, Message> = widget::settings::view_column(sections).into();
 
@@ -60,6 +139,191 @@ impl AppModel {
 
         section = section.add(widget::settings::item_row(vec![pipeline_content.into()]));
 
+        // Element graph of the running pipeline, for Graphviz
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-pipeline-graph"))
+                .description(fl!("insights-pipeline-graph-description"))
+                .control(
+                    widget::button::standard(fl!("insights-pipeline-graph-export")).on_press_maybe(
+                        self.insights
+                            .full_pipeline_string
+                            .is_some()
+                            .then_some(Message::ExportPipelineDot),
+                    ),
+                ),
+        );
+
+        // Encoded frames as the camera sent them, for reproducing decoder bugs
+        let compressed = self.active_format.as_ref().is_some_and(|format| {
+            crate::media::Codec::from_fourcc(&format.pixel_format).needs_decoder()
+        });
+        if compressed {
+            let (description, label) = if self.insights.raw_dump_running {
+                (
+                    fl!(
+                        "insights-raw-dump-running",
+                        seconds =
+                            crate::backends::camera::pipewire::raw_dump::DEFAULT_DURATION.as_secs()
+                    ),
+                    fl!("insights-raw-dump-button-running"),
+                )
+            } else {
+                (
+                    fl!("insights-raw-dump-description"),
+                    fl!("insights-raw-dump-button"),
+                )
+            };
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-raw-dump"))
+                    .description(description)
+                    .control(
+                        widget::button::standard(label).on_press_maybe(
+                            (!self.insights.raw_dump_running
+                                && self.insights.full_pipeline_string.is_some())
+                            .then_some(Message::StartRawStreamDump),
+                        ),
+                    ),
+            );
+        }
+
+        // Device-specific workaround applied while building the pipeline
+        if let Some(quirk) = self.insights.device_quirk {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-device-quirk"))
+                    .control(widget::text::body(quirk)),
+            );
+        }
+
+        // Direct V4L2 capture and whether its io-mode got frames to the GPU as DMABUF
+        if let Some(capture) = crate::media::decoders::active_v4l2_capture() {
+            let text = if capture.fell_back() {
+                fl!(
+                    "insights-v4l2-io-mode-fallback",
+                    mode = capture.io_mode,
+                    requested = capture.requested
+                )
+            } else {
+                capture.io_mode.to_string()
+            };
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-v4l2-io-mode"))
+                    .description(if self.insights.dmabuf_handoff {
+                        fl!("insights-v4l2-io-mode-dmabuf")
+                    } else {
+                        fl!("insights-v4l2-io-mode-mapped")
+                    })
+                    .control(widget::text::body(text).font(cosmic::font::mono())),
+            );
+        }
+
+        // Element inspector: expand an element to see its current property values
+        if !self.insights.pipeline_elements.is_empty() {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-elements"))
+                    .description(fl!("insights-elements-description"))
+                    .control(widget::Space::new(0, 0)),
+            );
+        }
+        for element in &self.insights.pipeline_elements {
+            let expanded = self.insights.expanded_elements.contains(&element.name);
+            let expander_icon = if expanded {
+                "pan-down-symbolic"
+            } else {
+                "pan-end-symbolic"
+            };
+            let header = widget::button::custom(
+                widget::row()
+                    .push(
+                        widget::icon::from_name(expander_icon)
+                            .symbolic(true)
+                            .size(16),
+                    )
+                    .push(widget::horizontal_space().width(Length::Fixed(8.0)))
+                    .push(widget::text::body(&element.name).font(cosmic::font::mono()))
+                    .push(widget::horizontal_space())
+                    .push(widget::text::caption(match &element.summary {
+                        Some(summary) => format!("{}  {}", element.factory, summary),
+                        None => element.factory.clone(),
+                    }))
+                    .align_y(Alignment::Center),
+            )
+            .class(cosmic::theme::Button::Text)
+            .width(Length::Fill)
+            .on_press(Message::ToggleInspectElement(element.name.clone()));
+            section = section.add(widget::settings::item_row(vec![header.into()]));
+
+            if expanded {
+                for (name, value) in &element.properties {
+                    section = section.add(
+                        widget::settings::item::builder(name.clone()).control(
+                            widget::text::body(value)
+                                .font(cosmic::font::mono())
+                                .size(11),
+                        ),
+                    );
+                }
+            }
+        }
+
+        // Hardware acceleration badge, naming the stages that run on the CPU
+        if self.insights.full_pipeline_string.is_some() {
+            let cpu_stages = self.insights.acceleration().cpu_stages();
+            let (icon_name, badge) = if cpu_stages.is_empty() {
+                ("emblem-ok-symbolic", fl!("insights-hw-accel-full"))
+            } else {
+                ("dialog-warning-symbolic", fl!("insights-hw-accel-partial"))
+            };
+            let mut item = widget::settings::item::builder(fl!("insights-hw-accel"));
+            if !cpu_stages.is_empty() {
+                let stages = cpu_stages
+                    .iter()
+                    .map(|stage| match stage {
+                        CpuStage::Decode => fl!("insights-hw-accel-cpu-decode"),
+                        CpuStage::Handoff => fl!("insights-hw-accel-cpu-handoff"),
+                        CpuStage::Conversion => fl!("insights-hw-accel-cpu-conversion"),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                item = item.description(stages);
+            }
+            section = section.add(
+                item.control(
+                    widget::container(
+                        widget::row()
+                            .push(widget::icon::from_name(icon_name).symbolic(true).size(16))
+                            .push(widget::text::body(badge))
+                            .spacing(6)
+                            .align_y(Alignment::Center),
+                    )
+                    .padding([2, 8])
+                    .class(cosmic::style::Container::Card),
+                ),
+            );
+        }
+
+        // Power preference and what it changed
+        let decoder_order = if crate::media::decoders::software_first_decode() {
+            fl!(
+                "insights-power-decoders-software-first",
+                cores = crate::media::decoders::efficiency_cores().unwrap_or(0)
+            )
+        } else if self.config.power_preference.is_low_power() {
+            fl!("insights-power-decoders-no-efficiency-cores")
+        } else {
+            fl!("insights-power-decoders-hardware-first")
+        };
+        let gpu = match crate::gpu::adapter_power_preference() {
+            crate::gpu::wgpu::PowerPreference::LowPower => fl!("insights-power-gpu-low-power"),
+            _ => fl!("insights-power-gpu-high-performance"),
+        };
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-power-preference"))
+                .description(format!("{}\n{}", decoder_order, gpu))
+                .control(widget::text::body(
+                    self.config.power_preference.display_name(),
+                )),
+        );
+
         // Decoder fallback chain
         if !self.insights.decoder_chain.is_empty() {
             section = section.add(
@@ -67,50 +331,768 @@ impl AppModel {
                     .control(widget::Space::new(0, 0)),
             );
 
+            // Error resilience of the MJPEG decoder (properties actually applied)
+            if crate::media::Codec::from_fourcc(&self.insights.format_chain.native_format)
+                == crate::media::Codec::MJPEG
+            {
+                let resilience = crate::media::decoders::active_mjpeg_resilience()
+                    .unwrap_or_else(|| fl!("insights-mjpeg-resilience-off"));
+                section = section.add(
+                    widget::settings::item::builder(fl!("insights-mjpeg-resilience"))
+                        .control(widget::text::body(resilience).font(cosmic::font::mono())),
+                );
+            }
+
+            // Whether the decoder was the cached one or probed from the chain
+            if let Some(source) = crate::media::decoders::active_decoder_source() {
+                let source = match source {
+                    crate::media::decoders::DecoderSource::Cache => {
+                        fl!("insights-decoder-source-cache")
+                    }
+                    crate::media::decoders::DecoderSource::Probed => {
+                        fl!("insights-decoder-source-probed")
+                    }
+                };
+                section = section.add(
+                    widget::settings::item::builder(fl!("insights-decoder-source"))
+                        .description(fl!("insights-decoder-source-description"))
+                        .control(widget::text::body(source)),
+                );
+            }
+
+            // Parser ahead of the decoder (H.264/H.265)
+            if let Some(parser) = crate::media::decoders::active_parser() {
+                section = section.add(
+                    widget::settings::item::builder(fl!("insights-parser"))
+                        .description(fl!("insights-parser-description"))
+                        .control(widget::text::body(parser).font(cosmic::font::mono())),
+                );
+            }
+
+            // Threads of the software decoder (FFmpeg H.264/H.265)
+            if let Some(threads) = crate::media::decoders::active_decode_threads() {
+                let text = match threads {
+                    0 => fl!(
+                        "insights-decode-threads-auto",
+                        threads = crate::constants::pipeline::videoconvert_threads()
+                    ),
+                    threads => threads.to_string(),
+                };
+                section = section.add(
+                    widget::settings::item::builder(fl!("insights-decode-threads"))
+                        .control(widget::text::body(text)),
+                );
+            }
+
+            // Split-screen comparison against another available decoder
+            let compared = crate::media::decoders::compare_decoder();
+            if let Some(comparison) = crate::media::decoders::active_decoder_comparison() {
+                section = section.add(
+                    widget::settings::item::builder(fl!("insights-compare-active")).control(
+                        widget::text::body(fl!(
+                            "insights-compare-halves",
+                            left = comparison.left,
+                            right = comparison.right
+                        ))
+                        .font(cosmic::font::mono()),
+                    ),
+                );
+            }
+
             for decoder in &self.insights.decoder_chain {
                 let (icon_name, status_text) = match decoder.state {
+                    // A selected decoder that keeps failing is flagged
+                    FallbackState::Selected
+                        if self.insights.decoder_errors.is_unreliable(decoder.name) =>
+                    {
+                        ("dialog-warning-symbolic", fl!("insights-selected"))
+                    }
                     FallbackState::Selected => ("emblem-ok-symbolic", fl!("insights-selected")),
                     FallbackState::Available => {
                         ("media-record-symbolic", fl!("insights-available"))
                     }
+                    FallbackState::Broken => ("dialog-warning-symbolic", fl!("insights-broken")),
                     FallbackState::Unavailable => {
                         ("window-close-symbolic", fl!("insights-unavailable"))
                     }
                 };
 
-                let row = widget::row()
+                let mut caption = format!("{} - {}", decoder.localized_description, status_text);
+                if let Some(rate) = self.insights.decoder_errors.error_rate(decoder.name) {
+                    caption = format!(
+                        "{} - {}",
+                        caption,
+                        fl!(
+                            "insights-decoder-error-rate",
+                            percent = format!("{:.0}", rate * 100.0),
+                            errors = self.insights.decoder_errors.errors(decoder.name)
+                        )
+                    );
+                }
+
+                let mut row = widget::row()
                     .push(widget::icon::from_name(icon_name).symbolic(true).size(16))
                     .push(widget::horizontal_space().width(Length::Fixed(8.0)))
                     .push(
                         widget::column()
                             .push(widget::text::body(decoder.name).font(cosmic::font::mono()))
-                            .push(
-                                widget::text::caption(format!(
-                                    "{} - {}",
-                                    decoder.description, status_text
-                                ))
-                                .size(11),
-                            ),
+                            .push(widget::text::caption(caption).size(11)),
                     )
                     .align_y(Alignment::Center)
                     .padding(4);
+                if compared == Some(decoder.name) {
+                    row = row.push(widget::horizontal_space()).push(
+                        widget::button::standard(fl!("insights-compare-stop"))
+                            .on_press(Message::CompareDecoder(None)),
+                    );
+                } else if decoder.state == FallbackState::Available {
+                    row = row.push(widget::horizontal_space()).push(
+                        widget::button::standard(fl!("insights-compare"))
+                            .on_press(Message::CompareDecoder(Some(decoder.name))),
+                    );
+                }
 
                 section = section.add(widget::settings::item_row(vec![row.into()]));
             }
+
+            // VA-API render node the VA decoders above were probed on
+            if self
+                .insights
+                .decoder_chain
+                .iter()
+                .any(|decoder| decoder.name.starts_with("va"))
+            {
+                section = section.add(
+                    widget::settings::item::builder(fl!("insights-vaapi-device")).control(
+                        widget::text::body(&self.insights.vaapi_device).font(cosmic::font::mono()),
+                    ),
+                );
+            }
+        }
+
+        section
+    }
+
+    /// Build the Focus section (focus score and peaking)
+    fn build_focus_section(&self) -> widget::settings::Section<'_, Message> {
+        let focus = &self.insights.focus;
+        let score_text = focus
+            .score()
+            .map(|score| format!("{:.0}", score))
+            .unwrap_or_else(|| "-".to_string());
+        // Bar relative to the sharpest frame seen, full when focus is best
+        let bar = widget::progress_bar(0.0..=1.0, focus.relative().unwrap_or(0.0))
+            .width(Length::Fixed(120.0))
+            .height(Length::Fixed(6.0));
+
+        widget::settings::section()
+            .title(fl!("insights-focus"))
+            .add(
+                widget::settings::item::builder(fl!("insights-focus-score"))
+                    .description(fl!("insights-focus-score-description"))
+                    .control(
+                        widget::row()
+                            .push(bar)
+                            .push(widget::text::body(score_text).font(cosmic::font::mono()))
+                            .spacing(8)
+                            .align_y(Alignment::Center),
+                    ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("insights-focus-peaking"))
+                    .description(fl!("insights-focus-peaking-description"))
+                    .toggler(self.config.focus_peaking, |_| Message::ToggleFocusPeaking),
+            )
+            .add(
+                widget::settings::item::builder(fl!("insights-pixel-probe"))
+                    .description(fl!("insights-pixel-probe-description"))
+                    .toggler(self.pixel_probe.enabled, |_| Message::TogglePixelProbe),
+            )
+            .add(
+                widget::settings::item::builder(fl!("insights-frame-counter"))
+                    .description(fl!("insights-frame-counter-description"))
+                    .toggler(self.config.frame_counter_overlay, |_| {
+                        Message::ToggleFrameCounterOverlay
+                    }),
+            )
+    }
+
+    /// Build the Thermal section (only when sysfs exposes clocks or temperatures)
+    fn build_thermal_section(&self) -> Option<widget::settings::Section<'_, Message>> {
+        let thermal = &self.insights.thermal;
+        if !thermal.is_available() {
+            return None;
+        }
+
+        let mut section = widget::settings::section().title(fl!("insights-thermal"));
+        if let Some(mhz) = thermal.cpu_freq_mhz {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-thermal-cpu-clock"))
+                    .control(widget::text::body(format!("{} MHz", mhz))),
+            );
+        }
+        if let Some(mhz) = thermal.gpu_freq_mhz {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-thermal-gpu-clock"))
+                    .control(widget::text::body(format!("{} MHz", mhz))),
+            );
+        }
+        if let Some(temp) = thermal.max_temp_c {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-thermal-temperature"))
+                    .control(widget::text::body(format!("{:.0} °C", temp))),
+            );
+        }
+        if self.insights.throttling_suspected {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-thermal-throttling"))
+                    .description(fl!("insights-thermal-throttling-description"))
+                    .control(
+                        widget::icon::from_name("dialog-warning-symbolic")
+                            .symbolic(true)
+                            .size(16),
+                    ),
+            );
+        }
+        Some(section)
+    }
+
+    /// Build the MJPEG stream section (only while the stream is enabled)
+    fn build_http_stream_section(&self) -> Option<widget::settings::Section<'_, Message>> {
+        if !self.config.http_stream_enabled {
+            return None;
+        }
+
+        let address: Element<'_, Message> = match &self.http_stream_status {
+            Some(Ok(url)) => widget::text::body(url.clone())
+                .font(cosmic::font::mono())
+                .into(),
+            Some(Err(err)) => {
+                widget::text::body(fl!("insights-http-stream-failed", error = err.as_str())).into()
+            }
+            None => widget::text::body(fl!("insights-http-stream-starting")).into(),
+        };
+        Some(
+            widget::settings::section()
+                .title(fl!("insights-http-stream"))
+                .add(
+                    widget::settings::item::builder(fl!("insights-http-stream-url"))
+                        .description(if self.config.http_stream_all_interfaces {
+                            fl!("insights-http-stream-all-interfaces")
+                        } else {
+                            fl!("insights-http-stream-localhost")
+                        })
+                        .control(address),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("insights-http-stream-viewers")).control(
+                        widget::text::body(fl!(
+                            "insights-http-stream-viewers-value",
+                            count = crate::http_stream::client_count(),
+                            max = crate::http_stream::max_clients()
+                        )),
+                    ),
+                ),
+        )
+    }
+
+    /// Build the format history section (recent format/decoder changes)
+    fn build_format_history_section(&self) -> widget::settings::Section<'_, Message> {
+        let history = &self.insights.format_history;
+        let copy_button =
+            widget::button::icon(widget::icon::from_name("edit-copy-symbolic").symbolic(true))
+                .extra_small()
+                .on_press_maybe((!history.is_empty()).then_some(Message::CopyFormatHistory));
+        let mut section = widget::settings::section()
+            .title(fl!("insights-format-history"))
+            .add(
+                widget::settings::item::builder(fl!("insights-format-history-changes"))
+                    .description(fl!("insights-format-history-description"))
+                    .control(copy_button),
+            );
+
+        if history.is_empty() {
+            return section.add(widget::settings::item_row(vec![
+                widget::text::body(fl!("insights-format-history-empty")).into(),
+            ]));
+        }
+        // Newest first
+        for change in history.entries().rev() {
+            section = section.add(widget::settings::item_row(vec![
+                widget::text::body(change.summary())
+                    .font(cosmic::font::mono())
+                    .size(10)
+                    .into(),
+            ]));
+        }
+        section
+    }
+
+    /// Build the upload benchmark section (zero-copy vs copy)
+    fn build_benchmark_section(&self) -> widget::settings::Section<'_, Message> {
+        let benchmark = &self.insights.upload_benchmark;
+        let running = benchmark.running();
+        let status = match running {
+            Some(path) => fl!(
+                "insights-benchmark-running",
+                path = path.display_name(),
+                frames = benchmark.progress(),
+                total = super::benchmark::FRAMES_PER_PATH
+            ),
+            None => fl!("insights-benchmark-description"),
+        };
+        let mut section = widget::settings::section()
+            .title(fl!("insights-benchmark"))
+            .add(
+                widget::settings::item::builder(fl!("insights-benchmark-run"))
+                    .description(status)
+                    .control(
+                        widget::button::standard(fl!("insights-benchmark-start")).on_press_maybe(
+                            running.is_none().then_some(Message::StartUploadBenchmark),
+                        ),
+                    ),
+            );
+
+        for (path, result) in [
+            (UploadPath::ZeroCopy, benchmark.zero_copy),
+            (UploadPath::Copy, benchmark.copy),
+        ] {
+            let Some(result) = result else {
+                continue;
+            };
+            section = section.add(
+                widget::settings::item::builder(path.display_name())
+                    .description(fl!(
+                        "insights-benchmark-breakdown",
+                        copy = format!("{:.2}", result.copy_time_us / 1000.0),
+                        gpu = format!("{:.2}", result.gpu_conversion_time_us / 1000.0)
+                    ))
+                    .control(widget::text::body(format!(
+                        "{:.2} ms",
+                        result.total_us() / 1000.0
+                    ))),
+            );
+        }
+        if let Some(speedup) = benchmark.zero_copy_speedup() {
+            let verdict = if speedup >= 1.0 {
+                fl!(
+                    "insights-benchmark-faster",
+                    factor = format!("{:.2}", speedup)
+                )
+            } else {
+                fl!("insights-benchmark-slower")
+            };
+            section = section.add(widget::settings::item_row(vec![
+                widget::text::body(verdict).into(),
+            ]));
+        }
+        section
+    }
+
+    /// Build the Diagnostics section (bundle export for bug reports)
+    fn build_diagnostics_section(&self) -> widget::settings::Section<'_, Message> {
+        let buttons = widget::row()
+            .push(
+                widget::button::standard(fl!("insights-diagnostics-copy"))
+                    .on_press(Message::CopyDiagnosticsBundle),
+            )
+            .push(
+                widget::button::standard(fl!("insights-diagnostics-save"))
+                    .on_press(Message::SaveDiagnosticsBundle),
+            )
+            .spacing(8);
+
+        let mut section = widget::settings::section().title(fl!("insights-diagnostics"));
+
+        // GStreamer the app runs against (gathered at startup)
+        if let Some(gstreamer) = crate::media::decoders::environment() {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-gstreamer-version"))
+                    .control(widget::text::body(gstreamer.version.clone())),
+            );
+            let mut dirs = widget::column();
+            for dir in &gstreamer.plugin_dirs {
+                dirs = dirs.push(widget::text::caption(dir.clone()).font(cosmic::font::mono()));
+            }
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-gstreamer-plugin-dirs"))
+                    .control(dirs),
+            );
+            if gstreamer.uses_custom_plugin_path() {
+                let mut overrides = widget::column();
+                for (name, value) in &gstreamer.overrides {
+                    overrides = overrides.push(
+                        widget::text::caption(format!("{}={}", name, value))
+                            .font(cosmic::font::mono()),
+                    );
+                }
+                section = section.add(
+                    widget::settings::item::builder(fl!("insights-gstreamer-custom-path"))
+                        .description(fl!("insights-gstreamer-custom-path-description"))
+                        .control(overrides),
+                );
+            }
         }
 
         section
+            .add(
+                widget::settings::item::builder(fl!("insights-diagnostics-include-ids"))
+                    .description(fl!("insights-diagnostics-include-ids-description"))
+                    .toggler(self.config.diagnostics_include_device_ids, |_| {
+                        Message::ToggleDiagnosticsIncludeDeviceIds
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("insights-diagnostics-bundle"))
+                    .control(buttons),
+            )
+    }
+
+    /// Build the Device section: node, driver and USB identifiers for bug reports
+    fn build_device_section(&self) -> widget::settings::Section<'_, Message> {
+        let mut section = widget::settings::section().title(fl!("insights-device"));
+        let Some(identity) = &self.insights.device_identity else {
+            // libcamera-only cameras (and the file source) have no V4L2 node
+            return section.add(
+                widget::settings::item::builder(fl!("insights-device-node"))
+                    .control(widget::text::body(fl!("insights-device-no-node"))),
+            );
+        };
+
+        let mut rows = vec![(fl!("insights-device-node"), identity.node.clone())];
+        if let Some(driver) = &identity.driver {
+            rows.push((fl!("insights-device-driver"), driver.clone()));
+        }
+        if let Some(usb_id) = identity.usb_id {
+            rows.push((fl!("insights-device-usb-id"), usb_id.to_string()));
+        }
+        if let Some(bus_path) = &identity.bus_path {
+            rows.push((fl!("insights-device-bus-path"), bus_path.clone()));
+        }
+        if let Some(serial) = &identity.serial {
+            let serial = if self.config.diagnostics_include_device_ids {
+                serial.clone()
+            } else {
+                super::diagnostics::REDACTED.to_string()
+            };
+            rows.push((fl!("insights-device-serial"), serial));
+        }
+        for (label, value) in rows {
+            section = section.add(
+                widget::settings::item::builder(label).control(
+                    widget::text::body(value)
+                        .font(cosmic::font::mono())
+                        .size(11),
+                ),
+            );
+        }
+
+        section.add(
+            widget::settings::item::builder(fl!("insights-device-identifiers")).control(
+                widget::button::standard(fl!("insights-diagnostics-copy"))
+                    .on_press(Message::CopyDeviceIdentity),
+            ),
+        )
     }
 
     /// Build the Performance section
     fn build_performance_section(&self) -> widget::settings::Section<'_, Message> {
         let mut section = widget::settings::section().title(fl!("insights-performance"));
 
+        // Smoothed values replace the raw ones while smoothing is enabled
+        let smoothing = self.config.metrics_smoothing;
+        let displayed_ms = |raw_us: u64, ema: &crate::app::insights::Ema| {
+            smoothing.alpha().and(ema.value()).unwrap_or(raw_us as f64) / 1000.0
+        };
+        // Raw microseconds bypass smoothing and rounding, for comparing fast paths
+        let raw_us = self.insights.raw_microseconds;
+        let time_text = |value_us: u64, ms: f64| {
+            if raw_us {
+                format!("{} µs", value_us)
+            } else {
+                format!("{:.2} ms", ms)
+            }
+        };
+
+        // Pinned metrics: values are shown with their change since the pin
+        let current = self.insights.metric_values(smoothing.alpha().is_some());
+        let compared = |text: String, metric: Metric| {
+            let pin = self.insights.pinned_metrics.as_ref();
+            match pin.and_then(|pin| pin.compare(&current, metric)) {
+                Some(Comparison::Delta(delta)) => fl!(
+                    "insights-pinned-delta",
+                    value = text,
+                    delta = metric.format_delta(delta)
+                ),
+                Some(Comparison::NotPinned) => fl!("insights-pinned-new", value = text),
+                Some(Comparison::Unavailable(pinned)) => fl!(
+                    "insights-pinned-was",
+                    value = text,
+                    pinned = metric.format_value(pinned)
+                ),
+                None => text,
+            }
+        };
+        let pin_description = match &self.insights.pinned_metrics {
+            Some(pin) => fl!(
+                "insights-pinned-since",
+                seconds = pin.pinned_at.elapsed().as_secs()
+            ),
+            None => fl!("insights-pin-metrics-description"),
+        };
+        let pin_label = if self.insights.pinned_metrics.is_some() {
+            fl!("insights-clear-pin")
+        } else {
+            fl!("insights-pin")
+        };
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-pin-metrics"))
+                .description(pin_description)
+                .control(widget::button::standard(pin_label).on_press(Message::TogglePinMetrics)),
+        );
+
         // Frame latency
-        let latency_ms = self.insights.frame_latency_us as f64 / 1000.0;
+        let latency_ms = displayed_ms(self.insights.frame_latency_us, &self.insights.latency_ema);
+        let mut latency_item = widget::settings::item::builder(fl!("insights-frame-latency"));
+        if smoothing.alpha().is_some() && !raw_us {
+            latency_item = latency_item.description(fl!(
+                "insights-raw-value",
+                value = format!("{:.2} ms", self.insights.frame_latency_us as f64 / 1000.0)
+            ));
+        }
+        section = section.add(latency_item.control(metric_value(
+            compared(
+                time_text(self.insights.frame_latency_us, latency_ms),
+                Metric::FrameLatency,
+            ),
+            ReportedMetric::FrameLatency,
+        )));
+
+        // Recent latency, scaled to its own range so spikes stand out
+        let history = &self.insights.latency_history;
+        if let (Some(min), Some(max)) = (history.iter().min(), history.iter().max()) {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-latency-history"))
+                    .description(fl!(
+                        "insights-latency-history-range",
+                        min = format!("{:.1}", *min as f64 / 1000.0),
+                        max = format!("{:.1}", *max as f64 / 1000.0)
+                    ))
+                    .control(sparkline(&self.insights.latency_levels())),
+            );
+        }
+        // Presentation lag (frame on screen vs newest decoded frame)
+        let lag_text = self
+            .insights
+            .presentation_lag_us
+            .map(|lag_us| time_text(lag_us, lag_us as f64 / 1000.0))
+            .unwrap_or_else(|| "-".to_string());
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-presentation-lag"))
+                .description(fl!("insights-presentation-lag-description"))
+                .control(metric_value(
+                    compared(lag_text, Metric::PresentationLag),
+                    ReportedMetric::PresentationLag,
+                )),
+        );
+        let selected_smoothing_index = MetricsSmoothing::ALL
+            .iter()
+            .position(|s| *s == smoothing)
+            .unwrap_or(0);
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-metrics-smoothing")).control(
+                widget::dropdown(
+                    &self.metrics_smoothing_dropdown_options,
+                    Some(selected_smoothing_index),
+                    |index| Message::SetMetricsSmoothing(MetricsSmoothing::ALL[index]),
+                ),
+            ),
+        );
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-raw-microseconds"))
+                .description(fl!("insights-raw-microseconds-description"))
+                .toggler(raw_us, |_| Message::ToggleRawMicroseconds),
+        );
+
+        // Session totals since the pipeline last (re)started
+        let fps_text = |fps: Option<f32>| {
+            fps.map(|fps| format!("{:.1}", fps))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        let uptime_secs = self.insights.uptime().map_or(0, |uptime| uptime.as_secs());
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-session"))
+                .description(fl!(
+                    "insights-session-fps",
+                    rolling = compared(fps_text(self.insights.rolling_fps), Metric::FrameRate),
+                    average = fps_text(self.insights.session_fps())
+                ))
+                .control(widget::text::body(fl!(
+                    "insights-session-summary",
+                    uptime = format!(
+                        "{}:{:02}:{:02}",
+                        uptime_secs / 3600,
+                        uptime_secs / 60 % 60,
+                        uptime_secs % 60
+                    ),
+                    frames = self.insights.frames_processed
+                ))),
+        );
+
+        // Min/avg/max of the per-frame metrics over the session
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-session-stats"))
+                .description(fl!("insights-session-stats-description"))
+                .toggler(self.config.session_stats, |_| Message::ToggleSessionStats),
+        );
+        if self.config.session_stats {
+            use crate::app::insights::session_stats::format_summary;
+
+            let stats = &self.insights.session_stats;
+            for (label, accumulator) in [
+                (fl!("insights-frame-latency"), &stats.latency),
+                (fl!("insights-decode-time-gst"), &stats.decode_time),
+                (fl!("insights-gpu-upload-time"), &stats.gpu_upload),
+            ] {
+                section = section.add(
+                    widget::settings::item::builder(label)
+                        .description(fl!(
+                            "insights-session-stats-samples",
+                            count = accumulator.count()
+                        ))
+                        .control(
+                            widget::text::body(format_summary(accumulator))
+                                .font(cosmic::font::mono()),
+                        ),
+                );
+            }
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-session-stats-reset")).control(
+                    widget::button::standard(fl!("insights-session-stats-reset-button"))
+                        .on_press(Message::ResetSessionStats),
+                ),
+            );
+        }
+
+        // Software framerate cap (only when it drops frames)
+        if let Some(source_fps) = self
+            .active_format
+            .as_ref()
+            .and_then(|f| f.framerate)
+            .map(|f| f.as_int())
+            && self.config.framerate_cap_fps > 0
+            && self.config.framerate_cap_fps < source_fps
+        {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-framerate-cap"))
+                    .description(fl!(
+                        "insights-framerate-cap-measured",
+                        rolling = fps_text(self.insights.rolling_fps)
+                    ))
+                    .control(widget::text::body(fl!(
+                        "insights-framerate-cap-value",
+                        source = source_fps,
+                        effective = self.config.framerate_cap_fps
+                    ))),
+            );
+        }
+
+        // Process CPU usage
+        let cpu_text = self
+            .insights
+            .cpu_percent
+            .map(|cpu| format!("{:.0}%", cpu))
+            .unwrap_or_else(|| "-".to_string());
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-cpu-usage")).control(metric_value(
+                compared(cpu_text, Metric::CpuUsage),
+                ReportedMetric::CpuUsage,
+            )),
+        );
+
+        // Effect of the last preview resolution cap change
+        if let Some(before) = &self.insights.preview_cap_baseline {
+            let now = self.insights.performance_snapshot();
+            let describe = |snapshot: &crate::app::insights::PerformanceSnapshot| {
+                format!(
+                    "{}, {:.1} ms, {}",
+                    snapshot.resolution,
+                    snapshot.frame_latency_us as f64 / 1000.0,
+                    snapshot
+                        .cpu_percent
+                        .map(|cpu| format!("{:.0}% CPU", cpu))
+                        .unwrap_or_else(|| "- CPU".to_string())
+                )
+            };
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-preview-cap"))
+                    .description(fl!(
+                        "insights-preview-cap-comparison",
+                        before = describe(before),
+                        after = describe(&now)
+                    ))
+                    .control(widget::text::body(
+                        self.config.preview_resolution_cap.display_name(),
+                    )),
+            );
+        }
+
+        // GPU device health (recovery after device loss)
+        let gpu_state = if self.insights.gpu_recovering {
+            fl!("insights-gpu-recovering")
+        } else if self.insights.gpu_device_losses > 0 {
+            fl!(
+                "insights-gpu-recovered",
+                count = self.insights.gpu_device_losses
+            )
+        } else {
+            fl!("insights-gpu-ok")
+        };
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-gpu-device"))
+                .control(widget::text::body(gpu_state)),
+        );
+
+        // Freeze / frame stepping
+        let preview_state = if !self.insights.frozen {
+            fl!("insights-preview-live")
+        } else if self.insights.stepped_frames > 0 {
+            fl!(
+                "insights-preview-stepping",
+                count = self.insights.stepped_frames
+            )
+        } else {
+            fl!("insights-preview-frozen")
+        };
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-preview"))
+                .description(preview_state)
+                .toggler(self.insights.frozen, |_| Message::ToggleFreezePreview),
+        );
+        // The frame on screen, labeled like the frame counter overlay
+        let pts_text = match self.current_frame.as_ref().and_then(|frame| frame.stamp) {
+            Some(stamp) => {
+                let mut label = String::new();
+                stamp.write_label(&mut label);
+                label
+            }
+            None => self
+                .insights
+                .last_pts
+                .map(|pts| format!("{:.3} s", pts.as_secs_f64()))
+                .unwrap_or_else(|| "-".to_string()),
+        };
         section = section.add(
-            widget::settings::item::builder(fl!("insights-frame-latency"))
-                .control(widget::text::body(format!("{:.2} ms", latency_ms))),
+            widget::settings::item::builder(fl!("insights-frame-pts")).control(
+                widget::row()
+                    .push(widget::text::body(pts_text).font(cosmic::font::mono()))
+                    .push(
+                        widget::button::standard(fl!("insights-step-frame"))
+                            .on_press_maybe(self.insights.frozen.then_some(Message::StepFrame)),
+                    )
+                    .spacing(8)
+                    .align_y(Alignment::Center),
+            ),
         );
 
         // Dropped frames
@@ -120,6 +1102,160 @@ impl AppModel {
             ),
         );
 
+        // Frame drop policy and the frames it discarded
+        let selected_policy_index = FrameDropPolicy::ALL
+            .iter()
+            .position(|p| *p == self.config.frame_drop_policy)
+            .unwrap_or(0);
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-frame-drop-policy"))
+                .description(fl!(
+                    "insights-policy-dropped",
+                    policy = self.insights.frame_drop_policy.display_name(),
+                    count = self.insights.policy_dropped_frames
+                ))
+                .control(widget::dropdown(
+                    &self.frame_drop_policy_dropdown_options,
+                    Some(selected_policy_index),
+                    |index| Message::SetFrameDropPolicy(FrameDropPolicy::ALL[index]),
+                )),
+        );
+
+        // Appsink buffering, ahead of the frame drop policy
+        let selected_appsink_buffers_index = crate::constants::pipeline::APPSINK_BUFFER_OPTIONS
+            .iter()
+            .position(|&count| count == self.config.appsink_max_buffers)
+            .unwrap_or(0);
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-appsink-buffers"))
+                .description(fl!("insights-appsink-buffers-description"))
+                .control(widget::dropdown(
+                    &self.appsink_buffers_dropdown_options,
+                    Some(selected_appsink_buffers_index),
+                    |index| {
+                        Message::SetAppsinkMaxBuffers(
+                            crate::constants::pipeline::APPSINK_BUFFER_OPTIONS[index],
+                        )
+                    },
+                )),
+        );
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-appsink-drop"))
+                .description(fl!("insights-appsink-drop-description"))
+                .toggler(self.config.appsink_drop, |_| Message::ToggleAppsinkDrop),
+        );
+
+        // Identical frames kept off the GPU
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-skip-identical"))
+                .description(fl!(
+                    "insights-skipped-uploads",
+                    count = self.insights.skipped_uploads
+                ))
+                .toggler(self.config.skip_identical_frames, |_| {
+                    Message::ToggleSkipIdenticalFrames
+                }),
+        );
+
+        // Stall watchdog threshold and the restarts it triggered
+        let selected_watchdog_index = crate::constants::stall_watchdog::THRESHOLD_OPTIONS_SECS
+            .iter()
+            .position(|&secs| secs == self.config.stall_watchdog_secs)
+            .unwrap_or(0);
+        let watchdog_status = if self.insights.stall_restart_streak
+            >= crate::constants::stall_watchdog::MAX_RESTARTS_IN_A_ROW
+        {
+            fl!(
+                "insights-stall-gave-up",
+                count = self.insights.stall_restart_streak
+            )
+        } else if self.insights.stall_restarting {
+            fl!("insights-stall-restarting")
+        } else {
+            fl!(
+                "insights-stall-restarts",
+                count = self.insights.stall_restarts
+            )
+        };
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-stall-watchdog"))
+                .description(watchdog_status)
+                .control(widget::dropdown(
+                    &self.stall_watchdog_dropdown_options,
+                    Some(selected_watchdog_index),
+                    Message::SelectStallWatchdog,
+                )),
+        );
+
+        // Background power saving (only when configured)
+        if self.config.background_preview != crate::config::BackgroundPreview::KeepRunning {
+            let power_saving = match self.background_power_saving() {
+                Some(crate::config::BackgroundPreview::Pause) => {
+                    fl!("insights-power-saving-paused")
+                }
+                Some(_) => fl!(
+                    "insights-power-saving-throttled",
+                    fps = crate::constants::background_preview::THROTTLED_FPS
+                ),
+                None if !self.window_focused => fl!("insights-power-saving-overridden"),
+                None => fl!("insights-power-saving-inactive"),
+            };
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-power-saving"))
+                    .control(widget::text::body(power_saving)),
+            );
+        }
+
+        // Screen kept awake (only while enabled)
+        if self.config.keep_awake != crate::config::KeepAwake::Never {
+            let keep_awake = match (&self.insights.idle_inhibit, self.idle_inhibit_reason()) {
+                (_, None) => fl!("insights-keep-awake-inactive"),
+                (Some((_, Ok(backend))), Some(reason)) => fl!(
+                    "insights-keep-awake-active",
+                    reason = reason,
+                    backend = backend.display_name()
+                ),
+                (Some((_, Err(err))), Some(_)) => {
+                    fl!("insights-keep-awake-failed", error = err.as_str())
+                }
+                (None, Some(_)) => fl!("insights-keep-awake-pending"),
+            };
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-keep-awake"))
+                    .control(widget::text::body(keep_awake)),
+            );
+        }
+
+        // Retroactive clip buffer fill level (only while enabled)
+        let retro = &self.insights.retro_fill;
+        if !retro.max_duration.is_zero() {
+            let to_mb = |bytes: usize| format!("{:.1}", bytes as f64 / (1024.0 * 1024.0));
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-retro-buffer")).control(
+                    widget::text::body(fl!(
+                        "insights-retro-buffer-fill",
+                        seconds = format!("{:.1}", retro.duration.as_secs_f64()),
+                        max_seconds = retro.max_duration.as_secs(),
+                        mb = to_mb(retro.bytes),
+                        max_mb = to_mb(retro.max_bytes)
+                    )),
+                ),
+            );
+        }
+
+        // Segment of a split recording
+        if let Some(segment) = self.insights.recording_segment {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-recording-segment")).control(
+                    widget::text::body(fl!(
+                        "insights-recording-segment-value",
+                        index = segment,
+                        finalized = segment - 1
+                    )),
+                ),
+            );
+        }
+
         // Frame size
         let decoded_mb = self.insights.frame_size_decoded as f64 / (1024.0 * 1024.0);
         section = section.add(
@@ -127,30 +1263,59 @@ impl AppModel {
                 .control(widget::text::body(format!("{:.2} MB", decoded_mb))),
         );
 
+        // GPU memory of the textures (source frame, YUV planes, blur, gallery)
+        let texture_mb = self.insights.texture_memory as f64 / (1024.0 * 1024.0);
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-texture-memory"))
+                .description(fl!("insights-texture-memory-description"))
+                .control(metric_value(
+                    format!("{:.1} MB", texture_mb),
+                    ReportedMetric::TextureMemory,
+                )),
+        );
+
         // Buffer processing time (time to pull sample and map buffer)
-        let gst_decode_ms = self.insights.gstreamer_decode_time_us as f64 / 1000.0;
+        let gst_decode_ms = displayed_ms(
+            self.insights.gstreamer_decode_time_us,
+            &self.insights.decode_time_ema,
+        );
         section = section.add(
-            widget::settings::item::builder(fl!("insights-decode-time-gst"))
-                .control(widget::text::body(format!("{:.2} ms", gst_decode_ms))),
+            widget::settings::item::builder(fl!("insights-decode-time-gst")).control(metric_value(
+                compared(
+                    time_text(self.insights.gstreamer_decode_time_us, gst_decode_ms),
+                    Metric::DecodeTime,
+                ),
+                ReportedMetric::DecodeTime,
+            )),
         );
 
         // Frame wrap time (zero-copy: just offset extraction)
         let copy_ms = self.insights.copy_time_us as f64 / 1000.0;
-        let copy_text = if copy_ms < 0.01 {
+        let copy_text = if raw_us {
+            time_text(self.insights.copy_time_us, copy_ms)
+        } else if copy_ms < 0.01 {
             "< 0.01 ms (zero-copy)".to_string()
         } else {
             format!("{:.2} ms", copy_ms)
         };
         section = section.add(
             widget::settings::item::builder(fl!("insights-copy-time"))
-                .control(widget::text::body(copy_text)),
+                .control(metric_value(copy_text, ReportedMetric::CopyTime)),
         );
 
         // GPU upload time
-        let gpu_upload_ms = self.insights.gpu_conversion_time_us as f64 / 1000.0;
+        let gpu_upload_ms = displayed_ms(
+            self.insights.gpu_conversion_time_us,
+            &self.insights.gpu_upload_ema,
+        );
         section = section.add(
-            widget::settings::item::builder(fl!("insights-gpu-upload-time"))
-                .control(widget::text::body(format!("{:.2} ms", gpu_upload_ms))),
+            widget::settings::item::builder(fl!("insights-gpu-upload-time")).control(metric_value(
+                compared(
+                    time_text(self.insights.gpu_conversion_time_us, gpu_upload_ms),
+                    Metric::GpuUpload,
+                ),
+                ReportedMetric::GpuUpload,
+            )),
         );
 
         // GPU upload bandwidth (based on GPU upload time)
@@ -160,10 +1325,77 @@ impl AppModel {
             "N/A".to_string()
         };
         section = section.add(
-            widget::settings::item::builder(fl!("insights-gpu-upload-bandwidth"))
-                .control(widget::text::body(bandwidth_text)),
+            widget::settings::item::builder(fl!("insights-gpu-upload-bandwidth")).control(
+                metric_value(bandwidth_text, ReportedMetric::UploadBandwidth),
+            ),
         );
 
+        // Display refresh rate, with a hint when the camera cadence can't map evenly onto it
+        let refresh_text = self
+            .insights
+            .display_refresh_hz
+            .map(|hz| format!("{:.1} Hz", hz))
+            .unwrap_or_else(|| "N/A".to_string());
+        let mut refresh_item = widget::settings::item::builder(fl!("insights-display-refresh"));
+        if let Some(hz) = self.insights.display_refresh_hz
+            && let Some(fps) = self
+                .active_format
+                .as_ref()
+                .and_then(|f| f.framerate)
+                .map(|f| f.as_f64() as f32)
+            && fps > 0.0
+        {
+            let ratio = hz / fps;
+            if ratio < 0.95 || (ratio - ratio.round()).abs() > 0.05 {
+                refresh_item = refresh_item.description(fl!("insights-display-cadence-mismatch"));
+            }
+        }
+        section = section
+            .add(refresh_item.control(metric_value(refresh_text, ReportedMetric::DisplayRefresh)));
+
+        // Present mode - selection takes effect on next launch
+        let launch_mode = self.insights.requested_present_mode;
+        let requested_mode = self.config.present_mode;
+        let selected_mode_index = PresentMode::ALL
+            .iter()
+            .position(|m| *m == requested_mode)
+            .unwrap_or(0);
+        let description = if self
+            .config
+            .unsupported_present_modes
+            .contains(&requested_mode)
+        {
+            fl!(
+                "insights-present-mode-fallback",
+                requested = requested_mode.display_name(),
+                mode = launch_mode.display_name()
+            )
+        } else if requested_mode != launch_mode {
+            fl!("insights-present-mode-restart")
+        } else {
+            let tradeoff = match launch_mode {
+                PresentMode::Fifo => fl!("insights-present-mode-fifo"),
+                PresentMode::FifoRelaxed => fl!("insights-present-mode-fifo-relaxed"),
+                PresentMode::Mailbox => fl!("insights-present-mode-mailbox"),
+                PresentMode::Immediate => fl!("insights-present-mode-immediate"),
+            };
+            format!(
+                "{} — {}",
+                fl!(
+                    "insights-present-mode-requested",
+                    mode = launch_mode.display_name()
+                ),
+                tradeoff
+            )
+        };
+        let present_item =
+            widget::settings::item::builder(fl!("insights-present-mode")).description(description);
+        section = section.add(present_item.control(widget::dropdown(
+            &self.present_mode_dropdown_options,
+            Some(selected_mode_index),
+            |index| Message::SetPresentMode(PresentMode::ALL[index]),
+        )));
+
         section
     }
 
@@ -179,11 +1411,35 @@ impl AppModel {
                 .control(widget::text::body(&chain.source)),
         );
 
-        // Resolution
-        section = section.add(
-            widget::settings::item::builder(fl!("insights-format-resolution"))
-                .control(widget::text::body(&chain.resolution)),
-        );
+        // Resolution (native, and what the preview shows when downscaled)
+        let mut resolution_item =
+            widget::settings::item::builder(fl!("insights-format-resolution"));
+        if self.insights.resolution_changes > 0 {
+            resolution_item = resolution_item.description(fl!(
+                "insights-format-resolution-changes",
+                count = self.insights.resolution_changes
+            ));
+        }
+        section = section.add(resolution_item.control(widget::text::body(&chain.resolution)));
+        if let Some(displayed) = &chain.displayed_resolution {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-format-displayed-resolution"))
+                    .control(widget::text::body(displayed)),
+            );
+        }
+        if let Some(row_stride) = &chain.row_stride {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-format-row-stride"))
+                    .control(widget::text::body(row_stride)),
+            );
+        }
+        if let Some(warning) = &chain.stride_warning {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-format-stride-warning"))
+                    .description(fl!("insights-format-stride-warning-description"))
+                    .control(widget::text::body(warning)),
+            );
+        }
 
         // Framerate
         section = section.add(
@@ -191,6 +1447,58 @@ impl AppModel {
                 .control(widget::text::body(&chain.framerate)),
         );
 
+        // Resolution fallback after repeated start failures, and why
+        if let Some(downgrade) = self.safe_resolution.last_downgrade() {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-safe-resolution"))
+                    .description(fl!(
+                        "insights-safe-resolution-reason",
+                        failures = crate::app::safe_resolution::FAILURES_BEFORE_DOWNGRADE,
+                        reason = downgrade.reason.as_str()
+                    ))
+                    .control(widget::text::body(format!(
+                        "{} {} → {} {}",
+                        downgrade.from.pixel_format,
+                        downgrade.from,
+                        downgrade.to.pixel_format,
+                        downgrade.to
+                    ))),
+            );
+        }
+
+        // Low-light switching: why the frame rate was (or wasn't) lowered
+        if self.config.low_light_mode != crate::config::LowLightMode::Off {
+            section = section.add(self.build_low_light_item());
+        }
+
+        // Scan type (progressive or interlaced)
+        if let Some(scan) = &chain.scan {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-format-scan"))
+                    .control(widget::text::body(scan)),
+            );
+        }
+
+        // Orientation: detected sensor rotation, device orientation and any override
+        let detected = self.detected_camera_rotation();
+        let orientation_text = match self.config.rotation_override.rotation() {
+            Some(rotation) => fl!(
+                "insights-orientation-override",
+                rotation = rotation.to_string(),
+                detected = detected.to_string()
+            ),
+            None if self.device_orientation != Default::default() => fl!(
+                "insights-orientation-device",
+                detected = detected.to_string(),
+                device = self.device_orientation.to_string()
+            ),
+            None => fl!("insights-orientation-auto", detected = detected.to_string()),
+        };
+        section = section.add(
+            widget::settings::item::builder(fl!("insights-orientation"))
+                .control(widget::text::body(orientation_text)),
+        );
+
         // Native format (what the camera sends)
         section = section.add(
             widget::settings::item::builder(fl!("insights-format-native"))
@@ -205,11 +1513,165 @@ impl AppModel {
             );
         }
 
-        // WGPU processing
+        // WGPU processing, noting what is disabled without compute shaders
+        let mut wgpu_item = widget::settings::item::builder(fl!("insights-format-wgpu"));
+        if chain.compute_unavailable {
+            wgpu_item = wgpu_item.description(fl!("insights-format-wgpu-no-compute"));
+        }
+        section = section.add(wgpu_item.control(widget::text::body(&chain.wgpu_processing)));
+        let gpu_path = match chain.gpu_path {
+            PassthroughCheck::Unverified => fl!("insights-gpu-path-unverified"),
+            PassthroughCheck::Passthrough => fl!("insights-gpu-path-passthrough"),
+            PassthroughCheck::Conversion => fl!("insights-gpu-path-conversion"),
+            PassthroughCheck::NeedlessConversion(format) => fl!(
+                "insights-gpu-path-needless",
+                format = format!("{:?}", format)
+            ),
+        };
+        let mut gpu_path_item = widget::settings::item::builder(fl!("insights-gpu-path"));
+        if matches!(chain.gpu_path, PassthroughCheck::NeedlessConversion(_)) {
+            gpu_path_item =
+                gpu_path_item.description(fl!("insights-gpu-path-needless-description"));
+        }
+        section = section.add(gpu_path_item.control(widget::text::body(gpu_path)));
+
+        // Colorimetry as declared by the source caps
+        let colorimetry = chain
+            .colorimetry
+            .clone()
+            .unwrap_or_else(|| fl!("insights-format-colorimetry-unspecified"));
         section = section.add(
-            widget::settings::item::builder(fl!("insights-format-wgpu"))
-                .control(widget::text::body(&chain.wgpu_processing)),
+            widget::settings::item::builder(fl!("insights-format-colorimetry"))
+                .control(widget::text::body(colorimetry)),
+        );
+
+        // USB link, and a hint when bandwidth is the likely cause of a shortfall
+        if let Some(topology) = &self.insights.usb_topology {
+            let mut item = widget::settings::item::builder(fl!("insights-usb"));
+            if let Some(hint) = self.insights.bandwidth_hint {
+                item = item.description(match hint {
+                    BandwidthHint::ModeDowngraded => fl!("insights-usb-downgraded"),
+                    BandwidthHint::FramerateShortfall => fl!("insights-usb-shortfall"),
+                });
+            }
+            section = section.add(item.control(widget::text::body(fl!(
+                "insights-usb-summary",
+                speed = topology.speed_name(),
+                bus = topology.bus,
+                devices = topology.devices_on_bus
+            ))));
+        }
+
+        // Caps negotiated on the forced-caps filter (advanced override)
+        if let Some(forced) = &chain.forced_caps {
+            section = section.add(
+                widget::settings::item::builder(fl!("insights-format-forced-caps")).control(
+                    widget::text::body(forced)
+                        .font(cosmic::font::mono())
+                        .size(11),
+                ),
+            );
+        }
+
+        section
+    }
+
+    /// Build the low-light row: the mean luma and the reasoning behind the mode
+    fn build_low_light_item(&self) -> Element<'_, Message> {
+        use crate::app::low_light;
+
+        let description = if let Some(switch) = self.low_light.switch() {
+            let fps = |format: &crate::backends::camera::types::CameraFormat| {
+                format
+                    .framerate
+                    .map(|fps| fps.to_string())
+                    .unwrap_or_default()
+            };
+            fl!(
+                "insights-low-light-switched",
+                from = fps(&switch.from),
+                to = fps(&switch.to),
+                dark = low_light::DARK_LUMA,
+                bright = low_light::BRIGHT_LUMA
+            )
+        } else if self.active_format.as_ref().is_some_and(|active| {
+            low_light::low_light_format(&self.available_formats, active).is_none()
+        }) {
+            fl!("insights-low-light-no-mode")
+        } else if self.low_light.reported() {
+            fl!(
+                "insights-low-light-handled",
+                dark = low_light::DARK_LUMA,
+                clear = low_light::DARK_CLEAR_LUMA
+            )
+        } else {
+            fl!(
+                "insights-low-light-watching",
+                dark = low_light::DARK_LUMA,
+                seconds = low_light::SETTLE.as_secs()
+            )
+        };
+        let luma = self
+            .low_light
+            .last_luma
+            .map(|luma| fl!("insights-low-light-luma", luma = luma))
+            .unwrap_or_else(|| "-".to_string());
+        widget::settings::item::builder(fl!("insights-low-light"))
+            .description(description)
+            .control(widget::text::body(luma))
+            .into()
+    }
+
+    /// Build the Advanced section (collapsed by default)
+    fn build_advanced_section(&self) -> widget::settings::Section<'_, Message> {
+        let expander_icon = if self.insights_advanced_visible {
+            "pan-down-symbolic"
+        } else {
+            "pan-end-symbolic"
+        };
+        let mut section = widget::settings::section().add(
+            widget::settings::item::builder(fl!("insights-advanced")).control(
+                widget::button::icon(widget::icon::from_name(expander_icon).symbolic(true))
+                    .extra_small()
+                    .on_press(Message::ToggleInsightsAdvanced),
+            ),
         );
+        if !self.insights_advanced_visible {
+            return section;
+        }
+
+        // Force caps: a caps filter in front of the appsink, overriding negotiation
+        let active = crate::media::decoders::forced_caps();
+        let mut caps_item = widget::settings::item::builder(fl!("insights-forced-caps"));
+        caps_item = match (&self.forced_caps_error, &active) {
+            (Some(err), _) => {
+                caps_item.description(fl!("insights-forced-caps-invalid", error = err.as_str()))
+            }
+            (None, Some(caps)) => {
+                caps_item.description(fl!("insights-forced-caps-active", caps = caps.as_str()))
+            }
+            (None, None) => caps_item.description(fl!("insights-forced-caps-description")),
+        };
+        section = section.add(caps_item.control(widget::Space::new(0, 0)));
+        section = section.add(widget::settings::item_row(vec![
+            widget::text_input(
+                "video/x-raw,format=NV12,width=1280,height=720",
+                &self.forced_caps_input,
+            )
+            .font(cosmic::font::mono())
+            .on_input(Message::ForcedCapsInput)
+            .on_submit(|_| Message::ApplyForcedCaps)
+            .width(Length::Fill)
+            .into(),
+            widget::button::standard(fl!("insights-forced-caps-apply"))
+                .on_press_maybe(
+                    (!self.forced_caps_input.trim().is_empty()).then_some(Message::ApplyForcedCaps),
+                )
+                .into(),
+            widget::button::standard(fl!("insights-forced-caps-clear"))
+                .on_press_maybe(active.is_some().then_some(Message::ClearForcedCaps))
+                .into(),
+        ]));
 
         section
     }
@@ -0,0 +1,285 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Low-light frame rate switching
+//!
+//! A camera's auto exposure can't expose a frame for longer than the frame
+//! interval, so at 30 fps a dim room stays dark and noisy while 15 fps would
+//! allow twice the exposure. When the mean luma stays low for a while, a mode
+//! with the same resolution and pixel format at a lower frame rate is offered
+//! (or switched to), and the original mode comes back once the scene is
+//! bright again.
+//!
+//! The mean is taken from a decimated luma grid of the current frame, like
+//! the focus score; the GPU brightness histogram only reads RGBA frames.
+//!
+//! Two thresholds keep the mode from flapping: the slower mode exposes longer,
+//! so the scene has to get much brighter than the "dark" threshold before the
+//! original mode is restored, and each side has to hold for a few seconds.
+
+use std::time::{Duration, Instant};
+
+use crate::app::insights::focus::LumaGrid;
+use crate::backends::camera::types::CameraFormat;
+
+/// Samples taken along the longer side of the frame
+pub const GRID_SIDE: u32 = 32;
+
+/// Mean luma below which the scene counts as dark
+pub const DARK_LUMA: u8 = 40;
+
+/// Mean luma a dark scene has to exceed before it stops counting as dark
+pub const DARK_CLEAR_LUMA: u8 = 55;
+
+/// Mean luma above which the slower mode is no longer needed
+pub const BRIGHT_LUMA: u8 = 110;
+
+/// Mean luma the slower mode has to fall below before it stops counting as bright
+const BRIGHT_CLEAR_LUMA: u8 = 95;
+
+/// How long the scene has to stay dark (or bright again) before acting
+pub const SETTLE: Duration = Duration::from_secs(5);
+
+/// Lowest frame rate switched to, below that motion gets too choppy
+pub const MIN_FPS: u32 = 10;
+
+/// Mean of the samples of `grid`
+pub fn mean_luma(grid: &LumaGrid) -> u8 {
+    if grid.samples.is_empty() {
+        return 0;
+    }
+    let sum: u64 = grid.samples.iter().map(|&s| s as u64).sum();
+    (sum / grid.samples.len() as u64) as u8
+}
+
+/// Mode to use in low light instead of `active`, `None` if there is no slower one
+///
+/// Keeps the resolution and pixel format and prefers the fastest rate that
+/// at least doubles the frame interval.
+pub fn low_light_format(available: &[CameraFormat], active: &CameraFormat) -> Option<CameraFormat> {
+    let fps = active.framerate?.as_f64();
+    let slower: Vec<&CameraFormat> = available
+        .iter()
+        .filter(|f| {
+            f.width == active.width
+                && f.height == active.height
+                && f.pixel_format == active.pixel_format
+                && f.framerate
+                    .is_some_and(|rate| rate.as_f64() < fps && rate.as_f64() >= MIN_FPS as f64)
+        })
+        .collect();
+    let rate = |f: &&CameraFormat| f.framerate.map(|r| r.as_f64()).unwrap_or_default();
+    slower
+        .iter()
+        .filter(|f| rate(f) <= fps / 2.0)
+        .max_by(|a, b| rate(a).total_cmp(&rate(b)))
+        .or_else(|| slower.iter().min_by(|a, b| rate(a).total_cmp(&rate(b))))
+        .map(|f| (*f).clone())
+}
+
+/// What the scene asks for, given once per stretch of darkness or brightness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Dark at the original mode: a slower mode would help
+    Dark,
+    /// Bright at the slower mode: the original mode can come back
+    Bright,
+}
+
+/// Slower mode in use and the mode it replaced
+#[derive(Debug, Clone, PartialEq)]
+pub struct LowLightSwitch {
+    pub from: CameraFormat,
+    pub to: CameraFormat,
+}
+
+/// Mean luma tracking for the active mode
+#[derive(Debug, Default)]
+pub struct LowLightMonitor {
+    /// Mean luma of the last sampled frame
+    pub last_luma: Option<u8>,
+    /// Mode the samples were taken in
+    format: Option<CameraFormat>,
+    switch: Option<LowLightSwitch>,
+    /// Since when the scene has been past the threshold of the current state
+    crossed_since: Option<Instant>,
+    /// The verdict for the current stretch was given
+    reported: bool,
+}
+
+impl LowLightMonitor {
+    /// Record the mean luma of a frame taken in `format`
+    ///
+    /// A mode the monitor didn't switch to (the user picked another one,
+    /// or the camera changed) starts over without a switch to undo.
+    pub fn observe(&mut self, format: &CameraFormat, luma: u8, now: Instant) -> Option<Verdict> {
+        if self.format.as_ref() != Some(format) {
+            if self
+                .switch
+                .as_ref()
+                .is_none_or(|switch| &switch.to != format)
+            {
+                self.switch = None;
+            }
+            self.format = Some(format.clone());
+            self.crossed_since = None;
+            self.reported = false;
+        }
+        self.last_luma = Some(luma);
+
+        let (past, clear, verdict) = if self.switch.is_some() {
+            (
+                luma > BRIGHT_LUMA,
+                luma < BRIGHT_CLEAR_LUMA,
+                Verdict::Bright,
+            )
+        } else {
+            (luma < DARK_LUMA, luma > DARK_CLEAR_LUMA, Verdict::Dark)
+        };
+        if clear {
+            self.crossed_since = None;
+            self.reported = false;
+            return None;
+        }
+        if !past {
+            // Between the two thresholds: keep whatever state the scene was in
+            return None;
+        }
+        let since = *self.crossed_since.get_or_insert(now);
+        if self.reported || now.duration_since(since) < SETTLE {
+            return None;
+        }
+        self.reported = true;
+        Some(verdict)
+    }
+
+    /// Note that the slower mode replaced the original one
+    pub fn switched(&mut self, switch: LowLightSwitch) {
+        self.format = Some(switch.to.clone());
+        self.switch = Some(switch);
+        self.crossed_since = None;
+        self.reported = false;
+    }
+
+    /// Forget the slower mode, returning it so the original can be restored
+    ///
+    /// The current stretch counts as handled, so a scene that is still dark
+    /// in the original mode (or an undone switch) isn't switched again until
+    /// it clearly brightens.
+    pub fn revert(&mut self) -> Option<LowLightSwitch> {
+        let switch = self.switch.take()?;
+        self.format = Some(switch.from.clone());
+        self.crossed_since = None;
+        self.reported = true;
+        Some(switch)
+    }
+
+    /// Slower mode in use, if the monitor switched to one
+    pub fn switch(&self) -> Option<&LowLightSwitch> {
+        self.switch.as_ref()
+    }
+
+    /// Whether the current stretch of darkness or brightness was acted upon
+    pub fn reported(&self) -> bool {
+        self.reported
+    }
+
+    /// Start over, e.g. when low-light switching is turned off
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::Framerate;
+
+    fn format(fps: u32) -> CameraFormat {
+        CameraFormat {
+            width: 1280,
+            height: 720,
+            framerate: Some(Framerate::from_int(fps)),
+            hardware_accelerated: true,
+            pixel_format: "YUYV".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_low_light_format() {
+        let mut available: Vec<_> = [60, 30, 20, 15, 10, 5].map(format).into();
+        available.push(CameraFormat {
+            pixel_format: "MJPG".to_string(),
+            ..format(25)
+        });
+        // Fastest rate at or below half the current one
+        assert_eq!(low_light_format(&available, &format(60)), Some(format(30)));
+        assert_eq!(low_light_format(&available, &format(30)), Some(format(15)));
+        // Nothing at half the rate: the slowest one above the floor
+        assert_eq!(low_light_format(&available, &format(15)), Some(format(10)));
+        assert_eq!(low_light_format(&available, &format(10)), None);
+    }
+
+    #[test]
+    fn test_dark_needs_to_settle() {
+        let start = Instant::now();
+        let mut monitor = LowLightMonitor::default();
+        let fast = format(30);
+        assert_eq!(monitor.observe(&fast, 20, start), None);
+        // Between the thresholds does not interrupt a dark stretch
+        assert_eq!(monitor.observe(&fast, 48, start + SETTLE / 2), None);
+        assert_eq!(
+            monitor.observe(&fast, 20, start + SETTLE),
+            Some(Verdict::Dark)
+        );
+        // Given once per stretch
+        assert_eq!(monitor.observe(&fast, 20, start + SETTLE * 2), None);
+        // A clearly brighter frame ends the stretch
+        assert_eq!(monitor.observe(&fast, 80, start + SETTLE * 2), None);
+        assert_eq!(monitor.observe(&fast, 20, start + SETTLE * 3), None);
+    }
+
+    #[test]
+    fn test_hysteresis_after_switch() {
+        let start = Instant::now();
+        let mut monitor = LowLightMonitor::default();
+        let (fast, slow) = (format(30), format(15));
+        monitor.observe(&fast, 20, start);
+        monitor.switched(LowLightSwitch {
+            from: fast.clone(),
+            to: slow.clone(),
+        });
+
+        // Brighter with the longer exposure, but not bright enough to go back
+        for secs in 0..20 {
+            let now = start + Duration::from_secs(secs);
+            assert_eq!(monitor.observe(&slow, 70, now), None);
+        }
+        assert_eq!(monitor.observe(&slow, 130, start), None);
+        assert_eq!(
+            monitor.observe(&slow, 130, start + SETTLE),
+            Some(Verdict::Bright)
+        );
+        assert_eq!(monitor.revert().map(|s| s.from.clone()), Some(fast.clone()));
+
+        // Darker in the original mode again: no switch until it clears
+        let later = start + SETTLE * 4;
+        assert_eq!(monitor.observe(&fast, 20, later), None);
+        assert_eq!(monitor.observe(&fast, 20, later + SETTLE), None);
+    }
+
+    #[test]
+    fn test_manual_mode_change_forgets_switch() {
+        let start = Instant::now();
+        let mut monitor = LowLightMonitor::default();
+        monitor.switched(LowLightSwitch {
+            from: format(30),
+            to: format(15),
+        });
+        monitor.observe(&format(60), 20, start);
+        assert!(monitor.switch().is_none());
+        assert_eq!(
+            monitor.observe(&format(60), 20, start + SETTLE),
+            Some(Verdict::Dark)
+        );
+    }
+}
@@ -25,6 +25,7 @@
 //! - `Message`: All possible user interactions and system events
 //! - `CameraMode`: Photo or Video capture modes
 
+mod black_frames;
 mod bottom_bar;
 mod camera_ops;
 mod camera_preview;
@@ -33,18 +34,26 @@ mod dropdowns;
 pub mod exposure_picker;
 mod filter_picker;
 mod format_picker;
+mod frame_dedup;
 pub mod frame_processor;
 mod gallery_primitive;
 mod gallery_widget;
 mod handlers;
 mod insights;
+mod low_light;
 mod menu;
 mod motor_picker;
+mod pixel_probe;
+mod profiles;
 pub mod qr_overlay;
+mod safe_resolution;
 pub mod settings;
+mod shortcuts;
 mod state;
+mod still_burst;
 mod ui;
 mod update;
+mod upload_layout;
 mod utils;
 mod video_primitive;
 mod video_widget;
@@ -112,17 +121,39 @@ pub fn get_video_directory(folder_name: &str) -> std::path::PathBuf {
     video_dir
 }
 
+/// Directory captured photos are saved to
+///
+/// The configured output directory when one is set, otherwise the save
+/// folder inside the Pictures directory.
+pub fn photo_output_directory(config: &Config) -> std::path::PathBuf {
+    match config.output_directory.as_deref() {
+        Some(dir) if !dir.is_empty() => std::path::PathBuf::from(dir),
+        _ => get_photo_directory(&config.save_folder_name),
+    }
+}
+
+/// Directory recordings and clips are saved to
+///
+/// The configured output directory when one is set, otherwise the save
+/// folder inside the Videos directory.
+pub fn video_output_directory(config: &Config) -> std::path::PathBuf {
+    match config.output_directory.as_deref() {
+        Some(dir) if !dir.is_empty() => std::path::PathBuf::from(dir),
+        _ => get_video_directory(&config.save_folder_name),
+    }
+}
+
 /// Ensure the photo directory exists, creating it if necessary
-fn ensure_photo_directory(folder_name: &str) -> Result<std::path::PathBuf, std::io::Error> {
-    let photo_dir = get_photo_directory(folder_name);
+fn ensure_photo_directory(config: &Config) -> Result<std::path::PathBuf, std::io::Error> {
+    let photo_dir = photo_output_directory(config);
     std::fs::create_dir_all(&photo_dir)?;
     info!(path = %photo_dir.display(), "Photo directory ready");
     Ok(photo_dir)
 }
 
 /// Ensure the video directory exists, creating it if necessary
-fn ensure_video_directory(folder_name: &str) -> Result<std::path::PathBuf, std::io::Error> {
-    let video_dir = get_video_directory(folder_name);
+fn ensure_video_directory(config: &Config) -> Result<std::path::PathBuf, std::io::Error> {
+    let video_dir = video_output_directory(config);
     std::fs::create_dir_all(&video_dir)?;
     info!(path = %video_dir.display(), "Video directory ready");
     Ok(video_dir)
@@ -198,10 +229,10 @@ impl cosmic::Application for AppModel {
             };
 
         // Ensure photo and video directories exist
-        if let Err(e) = ensure_photo_directory(&config.save_folder_name) {
+        if let Err(e) = ensure_photo_directory(&config) {
             error!(error = %e, "Failed to create photo directory");
         }
-        if let Err(e) = ensure_video_directory(&config.save_folder_name) {
+        if let Err(e) = ensure_video_directory(&config) {
             error!(error = %e, "Failed to create video directory");
         }
 
@@ -211,6 +242,24 @@ impl cosmic::Application for AppModel {
             error!(error = %e, "Failed to initialize GStreamer");
         }
 
+        crate::pipelines::video::retro::configure(
+            config.retro_buffer_seconds,
+            config.retro_buffer_memory_mb,
+        );
+        crate::media::decoders::set_deinterlace_method(config.deinterlace_method.gst_method());
+        crate::media::decoders::set_v4l2_io_mode(config.v4l2_io_mode.gst_io_mode());
+        crate::media::decoders::set_decode_threads(config.decode_threads);
+        crate::backends::camera::pipewire::pipeline::set_appsink_buffering(
+            Some(config.appsink_max_buffers).filter(|&count| count > 0),
+            config.appsink_drop,
+        );
+        crate::media::decoders::set_low_power_decode(config.power_preference.is_low_power());
+        crate::gpu::set_low_power_adapter(config.power_preference.is_low_power());
+        crate::media::decoders::set_render_node(config.vaapi_render_node.as_deref());
+        crate::media::decoders::set_decoder_cache(config.decoder_cache.clone());
+        crate::http_stream::set_max_clients(config.http_stream_max_clients);
+        let available_render_nodes = crate::media::decoders::list_render_nodes();
+
         // Start with empty camera list - will be populated by async task
         let available_cameras = Vec::new();
         let current_camera_index = 0;
@@ -278,6 +327,8 @@ impl cosmic::Application for AppModel {
             }
         });
         let has_preview_source = preview_file_source.is_some();
+        let filename_template_input = config.filename_template.clone();
+        let http_stream_port_input = config.http_stream_port.to_string();
 
         // Construct the app model with the runtime's core.
         let mut app = AppModel {
@@ -288,6 +339,8 @@ impl cosmic::Application for AppModel {
             config_handler,
             mode: CameraMode::Photo,
             recording: RecordingState::default(),
+            recording_path_override: None,
+            recording_summary: None,
             virtual_camera: VirtualCameraState::default(),
             virtual_camera_file_source: preview_file_source,
             current_frame_is_file_source: has_preview_source,
@@ -321,6 +374,7 @@ impl cosmic::Application for AppModel {
             base_exposure_time: None,
             theatre: TheatreState::default(),
             burst_mode: BurstModeState::default(),
+            still_burst: None,
             auto_detected_frame_count: 1, // Start with 1 (no HDR+) until first brightness evaluation
             hdr_override_disabled: false,
             selected_filter: FilterType::default(),
@@ -335,16 +389,30 @@ impl cosmic::Application for AppModel {
             gallery_thumbnail: None,
             gallery_thumbnail_rgba: None,
             picker_selected_resolution: None,
+            pending_cycle_resolution: None,
+            resolution_cycle_generation: 0,
             backend_manager: Some(backend_manager),
             camera_cancel_flag: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
             camera_stream_restart_counter: 0,
+            shutdown: None,
+            toasts: cosmic::widget::Toasts::new(Message::CloseToast),
             current_frame: None,
+            frame_dedup: Default::default(),
+            black_frames: Default::default(),
+            low_light: Default::default(),
+            safe_resolution: Default::default(),
+            pixel_probe: Default::default(),
+            frame_counter_label: String::new(),
             available_cameras,
             current_camera_index,
             available_formats: available_formats.clone(),
+            device_controls: Default::default(),
             active_format: initial_format,
+            pipeline_source: None,
             available_audio_devices,
             current_audio_device_index,
+            audio_muted: false,
+            audio_level: 0.0,
             available_video_encoders,
             current_video_encoder_index,
             mode_list: Vec::new(), // Will be updated below
@@ -373,6 +441,15 @@ impl cosmic::Application for AppModel {
                 fl!("hdr-plus-frames-8"),
                 fl!("hdr-plus-frames-50"),
             ],
+            still_burst_count_dropdown_options: crate::constants::still_burst::COUNT_OPTIONS
+                .iter()
+                .map(|&count| fl!("settings-still-burst-count-value", count = count))
+                .collect(),
+            still_burst_interval_dropdown_options:
+                crate::constants::still_burst::INTERVAL_OPTIONS_MS
+                    .iter()
+                    .map(|&ms| fl!("settings-still-burst-interval-value", ms = ms))
+                    .collect(),
             photo_output_format_dropdown_options: crate::config::PhotoOutputFormat::ALL
                 .iter()
                 .map(|f| f.display_name().to_string())
@@ -381,7 +458,178 @@ impl cosmic::Application for AppModel {
                 .iter()
                 .map(|e| e.display_name().to_string())
                 .collect(),
+            present_mode_dropdown_options: crate::config::PresentMode::ALL
+                .iter()
+                .map(|m| m.display_name().to_string())
+                .collect(),
+            metrics_smoothing_dropdown_options: crate::config::MetricsSmoothing::ALL
+                .iter()
+                .map(|s| s.display_name().to_string())
+                .collect(),
+            retro_buffer_dropdown_options: crate::constants::retro_buffer::DURATION_OPTIONS_SECS
+                .iter()
+                .map(|&secs| {
+                    if secs == 0 {
+                        fl!("settings-retro-buffer-off")
+                    } else {
+                        fl!("settings-retro-buffer-seconds", seconds = secs)
+                    }
+                })
+                .collect(),
+            recording_split_duration_dropdown_options:
+                crate::constants::recording_split::DURATION_OPTIONS_MINS
+                    .iter()
+                    .map(|&minutes| {
+                        if minutes == 0 {
+                            fl!("settings-split-off")
+                        } else {
+                            fl!("settings-split-minutes", minutes = minutes)
+                        }
+                    })
+                    .collect(),
+            recording_split_size_dropdown_options:
+                crate::constants::recording_split::SIZE_OPTIONS_MB
+                    .iter()
+                    .map(|&mb| {
+                        if mb == 0 {
+                            fl!("settings-split-off")
+                        } else {
+                            fl!("settings-split-megabytes", mb = mb)
+                        }
+                    })
+                    .collect(),
+            deinterlace_dropdown_options: crate::config::DeinterlaceMethod::ALL
+                .iter()
+                .map(|m| m.display_name().to_string())
+                .collect(),
+            v4l2_io_mode_dropdown_options: crate::config::V4l2IoMode::ALL
+                .iter()
+                .map(|mode| mode.display_name().to_string())
+                .collect(),
+            power_preference_dropdown_options: crate::config::PowerPreference::ALL
+                .iter()
+                .map(|preference| preference.display_name().to_string())
+                .collect(),
+            preview_cap_dropdown_options: crate::config::PreviewResolutionCap::ALL
+                .iter()
+                .map(|cap| cap.display_name().to_string())
+                .collect(),
+            low_light_dropdown_options: crate::config::LowLightMode::ALL
+                .iter()
+                .map(|mode| mode.display_name().to_string())
+                .collect(),
+            render_node_dropdown_options: std::iter::once(fl!("settings-vaapi-device-default"))
+                .chain(
+                    available_render_nodes
+                        .iter()
+                        .map(|node| node.display_name()),
+                )
+                .collect(),
+            available_render_nodes,
+            rotation_override_dropdown_options: crate::config::RotationOverride::ALL
+                .iter()
+                .map(|r| r.display_name().to_string())
+                .collect(),
+            color_effect_dropdown_options: crate::config::ColorEffect::ALL
+                .iter()
+                .map(|e| e.display_name().to_string())
+                .collect(),
+            scaling_quality_dropdown_options: crate::config::ScalingQuality::ALL
+                .iter()
+                .map(|q| q.display_name().to_string())
+                .collect(),
+            watermark_position_dropdown_options: crate::config::WatermarkPosition::ALL
+                .iter()
+                .map(|p| p.display_name().to_string())
+                .collect(),
+            camera_facing_dropdown_options: std::iter::once(fl!("settings-camera-facing-auto"))
+                .chain(
+                    crate::backends::camera::types::CameraFacing::ALL
+                        .iter()
+                        .map(|f| f.display_name().to_string()),
+                )
+                .collect(),
+            camera_source_dropdown_options: crate::backends::camera::types::CameraSourceKind::ALL
+                .iter()
+                .map(|kind| kind.display_name().to_string())
+                .collect(),
+            device_orientation: crate::backends::camera::types::SensorRotation::None,
+            frame_drop_policy_dropdown_options: crate::backends::camera::FrameDropPolicy::ALL
+                .iter()
+                .map(|p| p.display_name().to_string())
+                .collect(),
+            appsink_buffers_dropdown_options: crate::constants::pipeline::APPSINK_BUFFER_OPTIONS
+                .iter()
+                .map(|&count| {
+                    if count == 0 {
+                        fl!("insights-appsink-buffers-auto")
+                    } else {
+                        count.to_string()
+                    }
+                })
+                .collect(),
+            stall_watchdog_dropdown_options:
+                crate::constants::stall_watchdog::THRESHOLD_OPTIONS_SECS
+                    .iter()
+                    .map(|&secs| {
+                        if secs == 0 {
+                            fl!("insights-stall-watchdog-off")
+                        } else {
+                            fl!("insights-stall-watchdog-seconds", seconds = secs)
+                        }
+                    })
+                    .collect(),
+            background_preview_dropdown_options: crate::config::BackgroundPreview::ALL
+                .iter()
+                .map(|b| b.display_name().to_string())
+                .collect(),
+            keep_awake_dropdown_options: crate::config::KeepAwake::ALL
+                .iter()
+                .map(|k| k.display_name().to_string())
+                .collect(),
+            http_stream_max_clients_dropdown_options:
+                crate::constants::http_stream::MAX_CLIENTS_OPTIONS
+                    .iter()
+                    .map(|count| count.to_string())
+                    .collect(),
+            framerate_cap_dropdown_options: crate::constants::framerate_cap::OPTIONS_FPS
+                .iter()
+                .map(|&fps| {
+                    if fps == 0 {
+                        fl!("settings-framerate-cap-off")
+                    } else {
+                        fl!("settings-framerate-cap-fps", fps = fps)
+                    }
+                })
+                .collect(),
+            window_focused: true,
+            present_mode_pending: false,
+            camera_open_timeout_dropdown_options:
+                crate::constants::camera_open::TIMEOUT_OPTIONS_SECS
+                    .iter()
+                    .map(|&secs| fl!("settings-open-timeout-seconds", seconds = secs))
+                    .collect(),
             device_info_visible: false,
+            rebinding_shortcut: None,
+            reset_settings_dialog: false,
+            nudge_coarse_steps_dropdown_options:
+                crate::constants::exposure_nudge::COARSE_STEP_OPTIONS
+                    .iter()
+                    .map(|&steps| fl!("settings-nudge-coarse-steps-value", steps = steps))
+                    .collect(),
+            control_nudge: None,
+            insights_advanced_visible: false,
+            forced_caps_input: String::new(),
+            http_stream_port_input,
+            http_stream_status: None,
+            http_stream_encoding: false,
+            http_stream_last_frame: None,
+            forced_caps_error: None,
+            filename_template_input,
+            profile_name_input: String::new(),
+            filename_template_error: None,
+            device_thumbnails: std::collections::HashMap::new(),
+            device_thumbnails_loading: false,
             transition_state: crate::app::state::TransitionState::default(),
             // QR detection enabled by default
             qr_detection_enabled: true,
@@ -389,12 +637,18 @@ impl cosmic::Application for AppModel {
             last_qr_detection_time: None,
             // Privacy cover detection
             privacy_cover_closed: false,
+            pipeline_error: None,
+            missing_decoder: None,
             // Insights drawer
             insights: Default::default(),
         };
 
         // Make context drawer overlay the content instead of reserving space
         app.core.window.context_is_overlay = true;
+
+        // Fixed for the lifetime of the window surface
+        app.insights.requested_present_mode = crate::config::PresentMode::launch_requested();
+        app.present_mode_pending = crate::config::PresentMode::is_pending();
         // Disable content container to prevent layout gaps
         app.core.window.content_container = false;
 
@@ -404,20 +658,32 @@ impl cosmic::Application for AppModel {
         app.update_pixel_format_options();
         app.update_framerate_options();
         app.update_codec_options();
+        app.sync_preview_throttle();
 
         // Initialize cameras and video encoders asynchronously (non-blocking)
         let backend_type = app.config.backend;
         let last_camera_path = app.config.last_camera_path.clone();
+        let source_overrides = app.config.camera_source_overrides.clone();
+        let validate_decoders = app.config.validate_decoders_at_startup;
 
         let init_task = Task::perform(
             async move {
                 // Check available video encoders (can be slow)
                 crate::pipelines::video::check_available_encoders();
 
+                // Optionally instantiate decoders so broken plugins are skipped
+                // before the first pipeline is built
+                if validate_decoders {
+                    crate::media::decoders::validate_decoders();
+                }
+
                 // Enumerate cameras (can be slow, especially with multiple devices)
                 info!(backend = %backend_type, "Enumerating cameras asynchronously");
                 let backend = crate::backends::camera::get_backend();
-                let cameras = backend.enumerate_cameras();
+                let cameras = camera_ops::apply_source_overrides(
+                    backend.enumerate_cameras(),
+                    &source_overrides,
+                );
                 info!(count = cameras.len(), backend = %backend_type, "Found camera(s)");
 
                 // Find the last used camera or default to first
@@ -440,35 +706,29 @@ impl cosmic::Application for AppModel {
                     0
                 };
 
-                // Get formats for selected camera
-                let formats = if let Some(camera) = cameras.get(camera_index) {
+                // Get formats and controls for selected camera
+                let capabilities = if let Some(camera) = cameras.get(camera_index) {
                     if !camera.path.is_empty() {
-                        backend.get_formats(camera, false)
+                        backend.get_capabilities(camera, false)
                     } else {
-                        Vec::new()
+                        Default::default()
                     }
                 } else {
-                    Vec::new()
+                    Default::default()
                 };
 
-                (cameras, camera_index, formats)
+                (cameras, camera_index, capabilities)
             },
-            |(cameras, index, formats)| {
-                cosmic::Action::App(Message::CamerasInitialized(cameras, index, formats))
+            |(cameras, index, capabilities)| {
+                cosmic::Action::App(Message::CamerasInitialized(cameras, index, capabilities))
             },
         );
 
         // Load initial gallery thumbnail
-        let folder_name = app.config.save_folder_name.clone();
-        let folder_name2 = folder_name.clone();
+        let photos_dir = photo_output_directory(&app.config);
+        let videos_dir = video_output_directory(&app.config);
         let load_thumbnail_task = Task::perform(
-            async move {
-                crate::storage::load_latest_thumbnail(
-                    get_photo_directory(&folder_name),
-                    get_video_directory(&folder_name2),
-                )
-                .await
-            },
+            async move { crate::storage::load_latest_thumbnail(photos_dir, videos_dir).await },
             |handle| cosmic::Action::App(Message::GalleryThumbnailLoaded(handle)),
         );
 
@@ -482,9 +742,28 @@ impl cosmic::Application for AppModel {
             Task::none()
         };
 
+        // Note the GStreamer version and plugin locations, and look for
+        // registry problems that would hide installed decoders
+        let registry_check_task = Task::perform(
+            async {
+                tokio::task::spawn_blocking(|| {
+                    crate::media::decoders::detect_environment();
+                    crate::media::decoders::check_registry().to_vec()
+                })
+                .await
+                .unwrap_or_default()
+            },
+            |problems| cosmic::Action::App(Message::RegistryChecked(problems)),
+        );
+
         (
             app,
-            Task::batch([init_task, load_thumbnail_task, preview_source_task]),
+            Task::batch([
+                init_task,
+                load_thumbnail_task,
+                preview_source_task,
+                registry_check_task,
+            ]),
         )
     }
 
@@ -516,8 +795,43 @@ impl cosmic::Application for AppModel {
         })
     }
 
+    /// Shut down cleanly instead of relying on process teardown
+    fn on_close_requested(&self, _id: cosmic::iced::window::Id) -> Option<Self::Message> {
+        Some(Message::CloseRequested)
+    }
+
     /// Handle escape key - close any open drawers or pickers
+    /// Confirmation shown before resetting every setting
+    fn dialog(&self) -> Option<Element<'_, Self::Message>> {
+        if !self.reset_settings_dialog {
+            return None;
+        }
+        let dialog = widget::dialog()
+            .title(fl!("reset-settings-title"))
+            .body(fl!("reset-settings-body"))
+            .primary_action(
+                widget::button::destructive(fl!("reset-settings-confirm"))
+                    .on_press(Message::ResetSettings),
+            )
+            .secondary_action(
+                widget::button::standard(fl!("reset-settings-cancel"))
+                    .on_press(Message::CancelResetSettings),
+            );
+        Some(dialog.into())
+    }
+
     fn on_escape(&mut self) -> Task<cosmic::Action<Self::Message>> {
+        // Dismiss the reset confirmation without resetting
+        if self.reset_settings_dialog {
+            self.reset_settings_dialog = false;
+            return Task::none();
+        }
+
+        // Cancel waiting for a shortcut key, keeping the settings drawer open
+        if self.rebinding_shortcut.take().is_some() {
+            return Task::none();
+        }
+
         // Close color picker and return to tools menu
         if self.color_picker_visible {
             self.color_picker_visible = false;
@@ -588,6 +902,12 @@ impl cosmic::Application for AppModel {
         // Restart counter forces subscription to restart (e.g., after HDR+ processing)
         let restart_counter = self.camera_stream_restart_counter;
 
+        // Frame queue drop policy (changing it rebuilds the queue)
+        let frame_drop_policy = self.config.frame_drop_policy;
+
+        // Time the camera gets to deliver its first frame
+        let open_timeout_secs = self.config.camera_open_timeout_secs;
+
         // Check if file source is active - if so, don't run camera subscription
         // This applies in Virtual mode OR when --preview-source was used (any mode)
         let file_source_active = self.virtual_camera_file_source.is_some();
@@ -595,6 +915,9 @@ impl cosmic::Application for AppModel {
         let camera_sub = if file_source_active {
             // No camera subscription when file source is active (file source handles preview)
             Subscription::none()
+        } else if self.background_power_saving() == Some(crate::config::BackgroundPreview::Pause) {
+            // Paused in the background: dropping the subscription releases the camera
+            Subscription::none()
         } else {
             Subscription::run_with_id(
                 (
@@ -607,10 +930,26 @@ impl cosmic::Application for AppModel {
                     // Camera only needs to restart when actual format changes, not on mode switch
                     cameras_initialized,
                     restart_counter, // Forces restart after HDR+ processing
+                    frame_drop_policy,
                 ),
                 cosmic::iced::stream::channel(100, move |mut output| async move {
                     info!(camera_index, "Camera subscription started (PipeWire)");
 
+                    // A new camera or format drops the preview's kept frame of the old one
+                    video_primitive::set_preview_source({
+                        use std::hash::{Hash, Hasher};
+                        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                        current_camera
+                            .as_ref()
+                            .map(|cam| &cam.path)
+                            .hash(&mut hasher);
+                        current_format
+                            .as_ref()
+                            .map(|f| (f.width, f.height, f.framerate, &f.pixel_format))
+                            .hash(&mut hasher);
+                        hasher.finish()
+                    });
+
                     // No artificial delay needed - PipelineManager serializes all operations
                     // and ensures proper cleanup before creating new pipelines
 
@@ -691,8 +1030,9 @@ impl cosmic::Application for AppModel {
                             use crate::backends::camera::types::{CameraDevice, CameraFormat};
 
                             let (sender, mut receiver) =
-                                cosmic::iced::futures::channel::mpsc::channel(
+                                crate::backends::camera::frame_queue::channel(
                                     crate::constants::latency::FRAME_CHANNEL_CAPACITY,
+                                    frame_drop_policy,
                                 );
 
                             // Build device and format objects for backend
@@ -712,6 +1052,12 @@ impl cosmic::Application for AppModel {
                                     .as_ref()
                                     .map(|c| c.rotation)
                                     .unwrap_or_default(),
+                                facing: current_camera
+                                    .as_ref()
+                                    .map(|c| c.facing)
+                                    .unwrap_or_default(),
+                                source_kind: current_camera.as_ref().and_then(|c| c.source_kind),
+                                alternate_source: None,
                             };
 
                             let format = CameraFormat {
@@ -730,12 +1076,21 @@ impl cosmic::Application for AppModel {
                                 }
                                 Err(e) => {
                                     error!(error = %e, "Failed to initialize pipeline");
+                                    if let crate::backends::camera::types::BackendError::Pipeline(
+                                        err,
+                                    ) = e
+                                    {
+                                        let _ = output.try_send(Message::PipelineFailed(err));
+                                    }
                                     None
                                 }
                             };
 
                             if let Some(pipeline) = pipeline_opt {
                                 info!("Waiting for frames from pipeline...");
+                                let opened_at = std::time::Instant::now();
+                                let mut timed_out = false;
+                                let mut first_frame_announced = false;
                                 // Keep pipeline alive and forward frames
                                 loop {
                                     // Check cancel flag first (set when switching cameras/modes)
@@ -746,6 +1101,20 @@ impl cosmic::Application for AppModel {
                                         break;
                                     }
 
+                                    // A camera held by another app (or a portal that never
+                                    // answers) may start without ever delivering a frame
+                                    if let Some(err) =
+                                        pipeline.first_frame_timeout(opened_at, open_timeout_secs)
+                                    {
+                                        warn!(
+                                            timeout_secs = open_timeout_secs,
+                                            "Camera didn't deliver a frame in time, giving up"
+                                        );
+                                        let _ = output.try_send(Message::PipelineFailed(err));
+                                        timed_out = true;
+                                        break;
+                                    }
+
                                     // Check if subscription is still active before processing next frame
                                     if output.is_closed() {
                                         info!(
@@ -765,13 +1134,10 @@ impl cosmic::Application for AppModel {
                                     .await
                                     {
                                         Ok(Some(frame)) => {
-                                            // Drain any queued frames to get the most recent one (reduces latency)
-                                            let mut latest_frame = frame;
-                                            let mut drained_count = 0u32;
-                                            while let Ok(Some(newer_frame)) = receiver.try_next() {
-                                                latest_frame = newer_frame;
-                                                drained_count += 1;
-                                            }
+                                            // The frame queue's drop policy already decided which
+                                            // frame to hand over (SkipToLatest = freshest)
+                                            let latest_frame = frame;
+                                            let policy_dropped = receiver.dropped();
 
                                             frame_count += 1;
                                             // Calculate frame latency (time from capture to subscription delivery)
@@ -784,7 +1150,7 @@ impl cosmic::Application for AppModel {
                                                     width = latest_frame.width,
                                                     height = latest_frame.height,
                                                     latency_ms = latency_us as f64 / 1000.0,
-                                                    drained = drained_count,
+                                                    policy_dropped,
                                                     "Received frame from pipeline"
                                                 );
                                             }
@@ -796,7 +1162,7 @@ impl cosmic::Application for AppModel {
                                                 tracing::warn!(
                                                     frame = frame_count,
                                                     latency_ms = latency_us as f64 / 1000.0,
-                                                    drained = drained_count,
+                                                    policy_dropped,
                                                     "High frame latency detected - possible stuttering"
                                                 );
                                             }
@@ -813,6 +1179,14 @@ impl cosmic::Application for AppModel {
                                                             "Frame forwarded to UI"
                                                         );
                                                     }
+                                                    // Once per pipeline, right behind the frame it's about
+                                                    if !first_frame_announced
+                                                        && pipeline.has_first_frame()
+                                                    {
+                                                        first_frame_announced = true;
+                                                        let _ = output
+                                                            .try_send(Message::PipelineFirstFrame);
+                                                    }
                                                 }
                                                 Err(e) => {
                                                     // Always log dropped frames for diagnostics
@@ -844,6 +1218,14 @@ impl cosmic::Application for AppModel {
                                 info!("Cleaning up PipeWire pipeline");
                                 // Pipeline will be dropped here, stopping the camera
                                 drop(pipeline);
+
+                                if timed_out {
+                                    // Try again later, the other app may let go of the camera
+                                    tokio::time::sleep(tokio::time::Duration::from_secs(
+                                        crate::constants::camera_open::RETRY_DELAY_SECS,
+                                    ))
+                                    .await;
+                                }
                             } else {
                                 error!("Failed to initialize pipeline");
                                 info!("Waiting 5 seconds before retry...");
@@ -872,58 +1254,64 @@ impl cosmic::Application for AppModel {
                     return;
                 };
 
-                loop {
-                    // Wait 2 seconds between checks
-                    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-
-                    // Enumerate current cameras
-                    if let Ok(new_cameras) = backend_mgr.enumerate_cameras() {
-                        // Compare with last known list
-                        // Check if camera list changed (different count or different cameras)
-                        let cameras_changed = last_cameras.len() != new_cameras.len()
-                            || !last_cameras.iter().all(|c| {
-                                new_cameras
-                                    .iter()
-                                    .any(|nc| nc.path == c.path && nc.name == c.name)
-                            });
-
-                        if cameras_changed {
-                            info!(
-                                old_count = last_cameras.len(),
-                                new_count = new_cameras.len(),
-                                "Camera list changed - hotplug event detected"
-                            );
+                use crate::backends::camera::pipewire::hotplug;
+                use crate::constants::hotplug as timing;
 
-                            last_cameras = new_cameras.clone();
-
-                            // Send camera list changed message
-                            if output
-                                .send(Message::CameraListChanged(new_cameras))
-                                .await
-                                .is_err()
-                            {
-                                warn!(
-                                    "Failed to send camera list changed message - channel closed"
-                                );
-                                break;
-                            }
-                        }
+                // Device events when available, polling as the fallback
+                let mut events = hotplug::watch_devices();
+                let poll_interval = |events: &Option<_>| {
+                    std::time::Duration::from_secs(if events.is_some() {
+                        timing::FALLBACK_POLL_INTERVAL_SECS
                     } else {
-                        // No cameras available - treat as empty list
-                        if !last_cameras.is_empty() {
-                            info!("All cameras disconnected");
-                            last_cameras = Vec::new();
-                            if output
-                                .send(Message::CameraListChanged(Vec::new()))
-                                .await
-                                .is_err()
-                            {
-                                warn!(
-                                    "Failed to send camera list changed message - channel closed"
-                                );
-                                break;
-                            }
+                        timing::POLL_INTERVAL_SECS
+                    })
+                };
+
+                loop {
+                    let interval = poll_interval(&events);
+                    let event = match events.as_mut() {
+                        Some(receiver) => tokio::select! {
+                            received = receiver.recv() => Some(
+                                received.is_some()
+                                    && hotplug::settle(
+                                        receiver,
+                                        std::time::Duration::from_millis(timing::DEBOUNCE_MS),
+                                        std::time::Duration::from_millis(timing::MAX_DEBOUNCE_MS),
+                                    )
+                                    .await,
+                            ),
+                            _ = tokio::time::sleep(interval) => None,
+                        },
+                        None => {
+                            tokio::time::sleep(interval).await;
+                            None
                         }
+                    };
+                    if event == Some(false) {
+                        warn!("Device monitor stopped, polling the camera list instead");
+                        events = None;
+                    }
+
+                    // Enumeration fails when no cameras are left
+                    let new_cameras = backend_mgr.enumerate_cameras().unwrap_or_default();
+                    if !hotplug::device_list_differs(&last_cameras, &new_cameras) {
+                        continue;
+                    }
+                    info!(
+                        old_count = last_cameras.len(),
+                        new_count = new_cameras.len(),
+                        device_event = event.is_some(),
+                        "Camera list changed - hotplug event detected"
+                    );
+                    last_cameras = new_cameras.clone();
+
+                    if output
+                        .send(Message::DeviceListChanged(new_cameras))
+                        .await
+                        .is_err()
+                    {
+                        warn!("Failed to send device list changed message - channel closed");
+                        break;
                     }
                 }
 
@@ -1063,6 +1451,17 @@ impl cosmic::Application for AppModel {
             Subscription::none()
         };
 
+        // Low-light check (every 1 second while opted in, never during a recording)
+        let low_light_sub = if self.config.low_light_mode != crate::config::LowLightMode::Off
+            && self.current_frame.is_some()
+            && !self.recording.is_recording()
+        {
+            cosmic::iced::time::every(std::time::Duration::from_secs(1))
+                .map(|_| Message::LowLightTick)
+        } else {
+            Subscription::none()
+        };
+
         // Update insights metrics every 500ms when the Insights drawer is open
         let insights_update_sub =
             if self.context_page == ContextPage::Insights && self.core.window.show_context {
@@ -1072,8 +1471,229 @@ impl cosmic::Application for AppModel {
                 Subscription::none()
             };
 
+        // Stall watchdog: restart the pipeline when frames stop arriving
+        let stall_watchdog_sub =
+            if self.config.stall_watchdog_secs > 0 && !self.available_cameras.is_empty() {
+                let interval = std::time::Duration::from_millis(
+                    crate::constants::stall_watchdog::CHECK_INTERVAL_MS,
+                );
+                cosmic::iced::time::every(interval).map(|_| Message::StallWatchdogTick)
+            } else {
+                Subscription::none()
+            };
+
+        // Track window frame callbacks while Insights is open to estimate display
+        // refresh, and until the first one confirms the present mode
+        let display_frames_sub = if self.present_mode_pending
+            || (self.context_page == ContextPage::Insights && self.core.window.show_context)
+        {
+            cosmic::iced::window::frames().map(Message::DisplayFrame)
+        } else {
+            Subscription::none()
+        };
+
+        // Microphone level meter, only while its settings row is visible
+        let audio_level_sub = if self.config.record_audio
+            && self.context_page == ContextPage::Settings
+            && self.core.window.show_context
+        {
+            let serial = self
+                .available_audio_devices
+                .get(self.current_audio_device_index)
+                .map(|device| device.serial.clone());
+            Subscription::run_with_id(
+                ("audio_level", serial.clone()),
+                cosmic::iced::stream::channel(4, move |mut output| async move {
+                    use crate::backends::audio;
+                    use gstreamer as gst;
+
+                    let monitor = match audio::AudioLevelMonitor::start(serial.as_deref()) {
+                        Ok(monitor) => monitor,
+                        Err(e) => {
+                            warn!(error = %e, "Failed to start audio level monitor");
+                            return;
+                        }
+                    };
+                    let Some(bus) = monitor.bus() else {
+                        return;
+                    };
+
+                    let mut messages = bus.stream();
+                    while let Some(msg) = messages.next().await {
+                        if let gst::MessageView::Element(element) = msg.view()
+                            && let Some(db) = element.structure().and_then(audio::peak_db)
+                            && output
+                                .send(Message::AudioLevel(audio::db_to_meter(db)))
+                                .await
+                                .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    drop(monitor);
+                }),
+            )
+        } else {
+            Subscription::none()
+        };
+
+        // Local automation socket (opt-in)
+        let control_socket_sub = if self.config.control_socket_enabled {
+            Subscription::run_with_id(
+                "control_socket",
+                cosmic::iced::stream::channel(10, |output| async move {
+                    use crate::control_socket;
+
+                    let path = control_socket::socket_path();
+                    let listener = match control_socket::bind(&path) {
+                        Ok(listener) => listener,
+                        Err(e) => {
+                            error!(error = %e, path = %path.display(), "Failed to bind control socket");
+                            return;
+                        }
+                    };
+
+                    while let Ok((stream, _)) = listener.accept().await {
+                        let mut output = output.clone();
+                        tokio::spawn(async move {
+                            use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+                            let (reader, mut writer) = stream.into_split();
+                            let mut lines = BufReader::new(reader).lines();
+                            while let Ok(Some(line)) = lines.next_line().await {
+                                let mut result = control_socket::parse_command(&line);
+                                if let Ok(command) = &result
+                                    && output
+                                        .send(Message::ControlCommand(command.clone()))
+                                        .await
+                                        .is_err()
+                                {
+                                    result = Err("app is shutting down".to_string());
+                                }
+                                let reply = control_socket::response_line(&result) + "\n";
+                                if writer.write_all(reply.as_bytes()).await.is_err() {
+                                    break;
+                                }
+                            }
+                        });
+                    }
+                }),
+            )
+        } else {
+            Subscription::none()
+        };
+
+        // MJPEG stream over HTTP (opt-in; rebinds when the port or interfaces change)
+        let http_stream_sub = if self.config.http_stream_enabled {
+            let port = self.config.http_stream_port;
+            let all_interfaces = self.config.http_stream_all_interfaces;
+            Subscription::run_with_id(
+                ("http_stream", port, all_interfaces),
+                cosmic::iced::stream::channel(1, move |mut output| async move {
+                    use crate::http_stream;
+
+                    let listener = match http_stream::bind(port, all_interfaces).await {
+                        Ok(listener) => listener,
+                        Err(e) => {
+                            error!(error = %e, port, "Failed to bind MJPEG stream");
+                            let _ = output
+                                .send(Message::HttpStreamBound(Err(e.to_string())))
+                                .await;
+                            return;
+                        }
+                    };
+                    let url = listener
+                        .local_addr()
+                        .map(http_stream::stream_url)
+                        .map_err(|e| e.to_string());
+                    let _ = output.send(Message::HttpStreamBound(url)).await;
+
+                    // Serves until the subscription is dropped, which disconnects every viewer
+                    http_stream::serve(listener).await;
+                }),
+            )
+        } else {
+            Subscription::none()
+        };
+
+        // Device orientation on convertibles (only while rotation is automatic)
+        let orientation_sub = if self.config.rotation_override.rotation().is_none() {
+            Subscription::run_with_id(
+                "device_orientation",
+                cosmic::iced::stream::channel(4, |mut output| async move {
+                    use crate::orientation::Accelerometer;
+
+                    let accelerometer = match Accelerometer::claim().await {
+                        Ok(accelerometer) => accelerometer,
+                        Err(e) => {
+                            debug!(error = %e, "Device orientation unavailable");
+                            return;
+                        }
+                    };
+                    let mut rotations = std::pin::pin!(accelerometer.rotations().await);
+                    while let Some(rotation) = rotations.next().await {
+                        if output
+                            .send(Message::DeviceOrientationChanged(rotation))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                }),
+            )
+        } else {
+            Subscription::none()
+        };
+
+        // Keep the screen awake while the camera is in use; dropping the
+        // subscription releases the inhibition
+        let idle_inhibit_sub = match self.idle_inhibit_reason() {
+            Some(reason) => Subscription::run_with_id(
+                ("idle_inhibit", reason),
+                cosmic::iced::stream::channel(1, move |mut output| async move {
+                    use crate::idle_inhibit::IdleInhibitor;
+
+                    let inhibitor = IdleInhibitor::acquire(reason).await;
+                    let status = match &inhibitor {
+                        Ok(inhibitor) => Ok(inhibitor.backend()),
+                        Err(e) => {
+                            warn!(error = %e, "Failed to keep the screen awake");
+                            Err(e.clone())
+                        }
+                    };
+                    let _ = output
+                        .send(Message::IdleInhibitChanged(reason, status))
+                        .await;
+                    // Held until the subscription is dropped
+                    let _inhibitor = inhibitor;
+                    std::future::pending::<()>().await;
+                }),
+            ),
+            None => Subscription::none(),
+        };
+
+        // Window focus, for pausing or throttling the preview in the background
+        let window_focus_sub =
+            cosmic::iced::event::listen_with(|event, _status, _window| match event {
+                cosmic::iced::Event::Window(cosmic::iced::window::Event::Focused) => {
+                    Some(Message::WindowVisibilityChanged(true))
+                }
+                cosmic::iced::Event::Window(cosmic::iced::window::Event::Unfocused) => {
+                    Some(Message::WindowVisibilityChanged(false))
+                }
+                _ => None,
+            });
+
+        // Keyboard shortcuts (only presses no widget captured, e.g. not typing in a text input)
+        let keyboard_sub = cosmic::iced::keyboard::on_key_press(|key, modifiers| {
+            Some(Message::KeyPressed(key, modifiers))
+        });
+
         Subscription::batch([
             config_sub,
+            window_focus_sub,
+            keyboard_sub,
             camera_sub,
             hotplug_sub,
             qr_detection_sub,
@@ -1081,7 +1701,15 @@ impl cosmic::Application for AppModel {
             timer_animation_sub,
             privacy_polling_sub,
             brightness_eval_sub,
+            low_light_sub,
             insights_update_sub,
+            stall_watchdog_sub,
+            display_frames_sub,
+            audio_level_sub,
+            control_socket_sub,
+            http_stream_sub,
+            orientation_sub,
+            idle_inhibit_sub,
         ])
     }
 
@@ -0,0 +1,427 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Pixel probe
+//!
+//! Reads back the source pixel under the cursor for checking the color
+//! conversion. The cursor position is mapped through the same mirror,
+//! rotation, aspect crop, cover fit and zoom steps as the preview shader, and
+//! the pixel is read straight from the frame the GPU uploads (no texture
+//! readback). YUV pixels are converted with the shader's BT.601 limited-range
+//! matrix, so the readout shows both what the camera sent and what the
+//! conversion should turn it into. Filters, color effects and the grayscale
+//! display mapping are not applied.
+//!
+//! Probing the last column also reads the row padding that follows it, so
+//! an edge that looks wrong can be compared against the padding bytes.
+
+use crate::app::upload_layout;
+use crate::backends::camera::types::{CameraFrame, PixelFormat};
+use std::time::{Duration, Instant};
+
+/// How the preview maps the widget onto the frame (mirrors `video_shader.wgsl`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreviewMapping {
+    /// Mirrored horizontally on screen
+    pub mirror: bool,
+    /// Sensor rotation code: 0=None, 1=90CW, 2=180, 3=270CW
+    pub rotation: u32,
+    /// Aspect crop (u_min, v_min, u_max, v_max)
+    pub crop_uv: Option<(f32, f32, f32, f32)>,
+    /// Widget width over height when the frame covers the widget (None = letterboxed)
+    pub cover_aspect: Option<f32>,
+    /// Digital zoom (1.0 = none)
+    pub zoom_level: f32,
+}
+
+impl PreviewMapping {
+    /// Frame pixel shown at `pos`, relative to the widget (0..1 on each axis)
+    ///
+    /// `None` when the position falls outside the frame.
+    pub fn source_pixel(&self, pos: (f32, f32), width: u32, height: u32) -> Option<(u32, u32)> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let (mut u, mut v) = pos;
+        if self.mirror {
+            u = 1.0 - u;
+        }
+        let swapped = self.rotation == 1 || self.rotation == 3;
+        (u, v) = match self.rotation {
+            1 => (1.0 - v, u),
+            2 => (1.0 - u, 1.0 - v),
+            3 => (v, 1.0 - u),
+            _ => (u, v),
+        };
+        if let Some((u_min, v_min, u_max, v_max)) = self.crop_uv {
+            u = u_min + (u_max - u_min) * u;
+            v = v_min + (v_max - v_min) * v;
+        }
+        if let Some(viewport_aspect) = self.cover_aspect {
+            let (tex_w, tex_h) = if swapped {
+                (height as f32, width as f32)
+            } else {
+                (width as f32, height as f32)
+            };
+            let tex_aspect = tex_w / tex_h;
+            let mut scale = if tex_aspect > viewport_aspect {
+                (viewport_aspect / tex_aspect, 1.0)
+            } else {
+                (1.0, tex_aspect / viewport_aspect)
+            };
+            if swapped {
+                scale = (scale.1, scale.0);
+            }
+            u = (u - 0.5) * scale.0 + 0.5;
+            v = (v - 0.5) * scale.1 + 0.5;
+        }
+        if self.zoom_level > 1.0 {
+            u = (u - 0.5) / self.zoom_level + 0.5;
+            v = (v - 0.5) / self.zoom_level + 0.5;
+        }
+        if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) {
+            return None;
+        }
+        let x = ((u * width as f32) as u32).min(width - 1);
+        let y = ((v * height as f32) as u32).min(height - 1);
+        Some((x, y))
+    }
+}
+
+/// Value of one frame pixel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelSample {
+    pub x: u32,
+    pub y: u32,
+    /// Color after the YUV conversion (the source bytes for RGB and grayscale)
+    pub rgb: [u8; 3],
+    /// Y, U and V as sent by the camera, for YUV formats
+    pub yuv: Option<[u8; 3]>,
+    /// Full 16-bit value of Gray16 frames
+    pub raw16: Option<u16>,
+    /// Padding after the pixel, when it is the last of a padded row
+    pub padding: Option<RowPadding>,
+}
+
+/// Bytes between the end of a row's pixels and the start of the next row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowPadding {
+    /// Padding bytes per row
+    pub bytes: u32,
+    /// The first padding bytes of the probed row (up to 4)
+    pub head: [u8; 4],
+}
+
+impl RowPadding {
+    /// The first padding bytes as hex, e.g. `00 00 00 00`
+    pub fn head_hex(&self) -> String {
+        self.head[..(self.bytes as usize).min(4)]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Padding after row `y` of the first plane, `None` for packed rows
+fn row_padding(frame: &CameraFrame, y: u32) -> Option<RowPadding> {
+    let (texels, texel_bytes) = upload_layout::luma_texels(frame.format, frame.width);
+    let row_bytes = texels * texel_bytes;
+    let bytes = frame
+        .stride
+        .checked_sub(row_bytes)
+        .filter(|&bytes| bytes > 0)?;
+    let y_offset = match (frame.format, frame.yuv_planes) {
+        (PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420, Some(planes)) => {
+            planes.y_offset
+        }
+        _ => 0,
+    };
+    let start = y_offset + y as usize * frame.stride as usize + row_bytes as usize;
+    let len = (bytes as usize).min(4);
+    let mut head = [0; 4];
+    // The last row may end without padding
+    head[..len].copy_from_slice(frame.data_slice().get(start..start + len)?);
+    Some(RowPadding { bytes, head })
+}
+
+impl PixelSample {
+    /// Color as `#rrggbb`
+    pub fn hex(&self) -> String {
+        let [r, g, b] = self.rgb;
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+/// BT.601 limited-range YUV to RGB, as done by `yuv_convert.wgsl`
+fn yuv_to_rgb_bt601(y: u8, u: u8, v: u8) -> [u8; 3] {
+    let y = (y as f32 / 255.0 - 16.0 / 255.0) * (255.0 / 219.0);
+    let u = u as f32 / 255.0 - 0.5;
+    let v = v as f32 / 255.0 - 0.5;
+    let to_byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    [
+        to_byte(y + 1.402 * v),
+        to_byte(y - 0.344136 * u - 0.714136 * v),
+        to_byte(y + 1.772 * u),
+    ]
+}
+
+/// Read the pixel at (`x`, `y`) of `frame`
+///
+/// Returns `None` outside the frame or when the buffer is too short for its
+/// layout.
+pub fn sample(frame: &CameraFrame, x: u32, y: u32) -> Option<PixelSample> {
+    if x >= frame.width || y >= frame.height {
+        return None;
+    }
+    let data = frame.data_slice();
+    let stride = frame.stride as usize;
+    let (xu, yu) = (x as usize, y as usize);
+    let row = yu * stride;
+    let mut sample = PixelSample {
+        x,
+        y,
+        rgb: [0; 3],
+        yuv: None,
+        raw16: None,
+        padding: None,
+    };
+    if x + 1 == frame.width {
+        sample.padding = row_padding(frame, y);
+    }
+
+    let yuv = match frame.format {
+        PixelFormat::RGBA | PixelFormat::RGB24 => {
+            let bpp = if frame.format == PixelFormat::RGB24 {
+                3
+            } else {
+                4
+            };
+            let px = data.get(row + xu * bpp..row + xu * bpp + 3)?;
+            sample.rgb = [px[0], px[1], px[2]];
+            return Some(sample);
+        }
+        PixelFormat::Gray8 => {
+            let gray = *data.get(row + xu)?;
+            sample.rgb = [gray; 3];
+            return Some(sample);
+        }
+        PixelFormat::Gray16 => {
+            let px = data.get(row + xu * 2..row + xu * 2 + 2)?;
+            let value = u16::from_le_bytes([px[0], px[1]]);
+            sample.rgb = [px[1]; 3];
+            sample.raw16 = Some(value);
+            return Some(sample);
+        }
+        // Packed 4:2:2: each 4 bytes hold two pixels sharing U and V
+        PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
+            let pair = row + xu / 2 * 4;
+            let b = data.get(pair..pair + 4)?;
+            let odd = xu % 2 == 1;
+            match frame.format {
+                PixelFormat::YUYV => [if odd { b[2] } else { b[0] }, b[1], b[3]],
+                PixelFormat::UYVY => [if odd { b[3] } else { b[1] }, b[0], b[2]],
+                PixelFormat::YVYU => [if odd { b[2] } else { b[0] }, b[3], b[1]],
+                _ => [if odd { b[3] } else { b[1] }, b[2], b[0]],
+            }
+        }
+        // 4:2:0: chroma at half resolution in one or two further planes
+        PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => {
+            let (y_offset, uv_offset, uv_stride, v_offset, v_stride) = match frame.yuv_planes {
+                Some(planes) => (
+                    planes.y_offset,
+                    planes.uv_offset,
+                    planes.uv_stride as usize,
+                    planes.v_offset,
+                    planes.v_stride as usize,
+                ),
+                // Tightly packed planes one after the other
+                None => {
+                    let y_size = stride * frame.height as usize;
+                    let chroma_stride = if frame.format == PixelFormat::I420 {
+                        stride / 2
+                    } else {
+                        stride
+                    };
+                    let chroma_size = chroma_stride * (frame.height as usize).div_ceil(2);
+                    (
+                        0,
+                        y_size,
+                        chroma_stride,
+                        y_size + chroma_size,
+                        chroma_stride,
+                    )
+                }
+            };
+            let luma = *data.get(y_offset + row + xu)?;
+            let chroma_row = yu / 2;
+            match frame.format {
+                PixelFormat::I420 => {
+                    let u = *data.get(uv_offset + chroma_row * uv_stride + xu / 2)?;
+                    let v = *data.get(v_offset + chroma_row * v_stride + xu / 2)?;
+                    [luma, u, v]
+                }
+                _ => {
+                    let at = uv_offset + chroma_row * uv_stride + xu / 2 * 2;
+                    let pair = data.get(at..at + 2)?;
+                    if frame.format == PixelFormat::NV12 {
+                        [luma, pair[0], pair[1]]
+                    } else {
+                        [luma, pair[1], pair[0]]
+                    }
+                }
+            }
+        }
+    };
+    sample.rgb = yuv_to_rgb_bt601(yuv[0], yuv[1], yuv[2]);
+    sample.yuv = Some(yuv);
+    Some(sample)
+}
+
+/// Probe state: the pixel being watched and its last reading
+#[derive(Debug, Clone, Default)]
+pub struct PixelProbe {
+    /// Whether the probe follows the cursor over the preview
+    pub enabled: bool,
+    /// Pixel under the cursor
+    pub hovered: Option<(u32, u32)>,
+    /// Pixel clicked on, read instead of the hovered one
+    pub locked: Option<(u32, u32)>,
+    /// Latest reading of the probed pixel
+    pub sample: Option<PixelSample>,
+    /// When the pixel was last read
+    last_sampled: Option<Instant>,
+}
+
+impl PixelProbe {
+    /// Pixel being probed
+    pub fn target(&self) -> Option<(u32, u32)> {
+        self.locked.or(self.hovered)
+    }
+
+    /// Read the probed pixel of `frame`
+    ///
+    /// Readings are throttled to one per
+    /// [`REFRESH_MS`](crate::constants::pixel_probe::REFRESH_MS) unless
+    /// `force` is set (e.g. when the probed pixel changed).
+    pub fn refresh(&mut self, frame: &CameraFrame, now: Instant, force: bool) {
+        let interval = Duration::from_millis(crate::constants::pixel_probe::REFRESH_MS);
+        if !force
+            && self
+                .last_sampled
+                .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            return;
+        }
+        self.last_sampled = Some(now);
+        self.sample = self.target().and_then(|(x, y)| sample(frame, x, y));
+    }
+
+    /// Stop probing and forget the last reading
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::FrameData;
+    use std::sync::Arc;
+
+    fn frame(data: Vec<u8>, format: PixelFormat, width: u32, stride: u32) -> CameraFrame {
+        CameraFrame {
+            width,
+            height: 2,
+            data: FrameData::from_bytes(Arc::from(data)),
+            format,
+            stride,
+            yuv_planes: None,
+            captured_at: Instant::now(),
+            stamp: None,
+        }
+    }
+
+    #[test]
+    fn test_mapping_follows_the_shader() {
+        let plain = PreviewMapping {
+            mirror: false,
+            rotation: 0,
+            crop_uv: None,
+            cover_aspect: None,
+            zoom_level: 1.0,
+        };
+        assert_eq!(plain.source_pixel((0.0, 0.0), 640, 480), Some((0, 0)));
+        assert_eq!(plain.source_pixel((1.0, 1.0), 640, 480), Some((639, 479)));
+        let mirrored = PreviewMapping {
+            mirror: true,
+            ..plain
+        };
+        assert_eq!(
+            mirrored.source_pixel((0.0, 0.5), 640, 480),
+            Some((639, 240))
+        );
+        // 90° CW: the top-left of the screen is the bottom-left of the sensor
+        let rotated = PreviewMapping {
+            rotation: 1,
+            ..plain
+        };
+        assert_eq!(rotated.source_pixel((0.0, 0.0), 640, 480), Some((639, 0)));
+        // 2x zoom shows the middle half
+        let zoomed = PreviewMapping {
+            zoom_level: 2.0,
+            ..plain
+        };
+        assert_eq!(zoomed.source_pixel((0.0, 0.0), 640, 480), Some((160, 120)));
+        // A 4:3 frame covering a 16:9 widget loses its top and bottom
+        let cover = PreviewMapping {
+            cover_aspect: Some(16.0 / 9.0),
+            ..plain
+        };
+        assert_eq!(cover.source_pixel((0.5, 0.0), 640, 480), Some((320, 60)));
+        assert_eq!(plain.source_pixel((1.5, 0.5), 640, 480), None);
+    }
+
+    #[test]
+    fn test_yuv_samples_match_the_conversion() {
+        // Two pixels, Y0=235 (white) and Y1=16 (black), neutral chroma
+        let yuyv = frame(
+            vec![235, 128, 16, 128, 235, 128, 16, 128],
+            PixelFormat::YUYV,
+            2,
+            4,
+        );
+        // 128 is a hair above the shader's 0.5 chroma center, so allow one step
+        let near = |rgb: [u8; 3], expected: u8| rgb.iter().all(|&c| c.abs_diff(expected) <= 1);
+        let white = sample(&yuyv, 0, 0).unwrap();
+        assert!(near(white.rgb, 255), "{:?}", white.rgb);
+        assert_eq!(white.yuv, Some([235, 128, 128]));
+        let black = sample(&yuyv, 1, 1).unwrap();
+        assert!(near(black.rgb, 0), "{:?}", black.rgb);
+        assert_eq!(sample(&yuyv, 2, 0), None);
+
+        // NV12 without plane offsets: Y plane, then interleaved UV
+        let nv12 = frame(vec![81, 81, 81, 81, 90, 240], PixelFormat::NV12, 2, 2);
+        let red = sample(&nv12, 1, 1).unwrap();
+        assert_eq!(red.yuv, Some([81, 90, 240]));
+        assert!(red.rgb[0] > 200 && red.rgb[1] < 20 && red.rgb[2] < 20);
+        assert_eq!(red.hex().len(), 7);
+
+        let gray16 = frame(
+            vec![0x34, 0x12, 0, 0, 0, 0, 0, 0],
+            PixelFormat::Gray16,
+            2,
+            4,
+        );
+        let value = sample(&gray16, 0, 0).unwrap();
+        assert_eq!(value.raw16, Some(0x1234));
+        assert_eq!(value.rgb, [0x12; 3]);
+
+        // The last column of a padded row reads the padding after it
+        let padded = frame(vec![10, 20, 0, 0, 30, 40], PixelFormat::Gray8, 2, 4);
+        let edge = sample(&padded, 1, 0).unwrap().padding.unwrap();
+        assert_eq!((edge.bytes, edge.head_hex()), (2, "00 00".to_string()));
+        assert_eq!(sample(&padded, 0, 0).unwrap().padding, None);
+        // The final row ends without padding
+        assert_eq!(sample(&padded, 1, 1).unwrap().padding, None);
+    }
+}
@@ -0,0 +1,139 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Named capture profiles
+//!
+//! A profile bundles a camera format with the render-only settings that go
+//! with it (rotation, mirroring, zoom, filter, color effect and overlays), so
+//! setups like "meeting" or "scanning documents" are one action away.
+//! Applying a profile only renegotiates the pipeline when the format actually
+//! changes; everything else takes effect with the next frame.
+//!
+//! Profiles are shared by all cameras, so a saved format may not be offered
+//! by the current one. [`resolve_format`] then picks the closest format the
+//! camera does offer, or none at all.
+
+use crate::backends::camera::types::CameraFormat;
+use crate::config::FormatSettings;
+
+/// Longest profile name accepted
+pub const MAX_NAME_LEN: usize = 40;
+
+/// How a profile's format maps onto the formats of the current camera
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatMatch {
+    /// Offered exactly as saved
+    Exact(CameraFormat),
+    /// Not offered as saved; this is the closest format the camera has
+    Fallback(CameraFormat),
+    /// Nothing at the saved resolution; the current format is kept
+    Unsupported,
+}
+
+/// Map a profile's format onto `formats`
+///
+/// Falls back to the saved resolution in the saved pixel format at the
+/// nearest frame rate, then to the saved resolution in any pixel format.
+pub fn resolve_format(formats: &[CameraFormat], wanted: &FormatSettings) -> FormatMatch {
+    let at_resolution =
+        |format: &&CameraFormat| format.width == wanted.width && format.height == wanted.height;
+    let fps_distance = |format: &&CameraFormat| {
+        let fps = format.framerate.map_or(0, |fps| fps.as_int());
+        fps.abs_diff(wanted.framerate.unwrap_or(0))
+    };
+
+    if let Some(format) = formats.iter().filter(at_resolution).find(|format| {
+        format.pixel_format == wanted.pixel_format
+            && format.framerate.map(|fps| fps.as_int()) == wanted.framerate
+    }) {
+        return FormatMatch::Exact(format.clone());
+    }
+
+    let same_pixel_format = formats
+        .iter()
+        .filter(at_resolution)
+        .filter(|format| format.pixel_format == wanted.pixel_format)
+        .min_by_key(fps_distance);
+    match same_pixel_format.or_else(|| {
+        formats
+            .iter()
+            .filter(at_resolution)
+            .min_by_key(fps_distance)
+    }) {
+        Some(format) => FormatMatch::Fallback(format.clone()),
+        None => FormatMatch::Unsupported,
+    }
+}
+
+/// Trimmed profile name, or `None` if it can't be used
+pub fn validate_name(name: &str) -> Option<&str> {
+    let name = name.trim();
+    (!name.is_empty() && name.chars().count() <= MAX_NAME_LEN).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::Framerate;
+
+    fn format(width: u32, height: u32, pixel_format: &str, fps: u32) -> CameraFormat {
+        CameraFormat {
+            width,
+            height,
+            framerate: Some(Framerate::from_int(fps)),
+            hardware_accelerated: false,
+            pixel_format: pixel_format.to_string(),
+        }
+    }
+
+    fn wanted(width: u32, height: u32, pixel_format: &str, fps: u32) -> FormatSettings {
+        FormatSettings {
+            width,
+            height,
+            framerate: Some(fps),
+            pixel_format: pixel_format.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_exact_format_is_used() {
+        let formats = [
+            format(1920, 1080, "MJPG", 30),
+            format(1920, 1080, "MJPG", 60),
+            format(1920, 1080, "YUYV", 5),
+        ];
+        assert_eq!(
+            resolve_format(&formats, &wanted(1920, 1080, "MJPG", 60)),
+            FormatMatch::Exact(formats[1].clone())
+        );
+    }
+
+    #[test]
+    fn test_fallback_keeps_resolution() {
+        let formats = [
+            format(1920, 1080, "MJPG", 30),
+            format(1920, 1080, "YUYV", 5),
+            format(1280, 720, "YUYV", 10),
+        ];
+        // Same pixel format at the nearest frame rate
+        assert_eq!(
+            resolve_format(&formats, &wanted(1920, 1080, "YUYV", 10)),
+            FormatMatch::Fallback(formats[1].clone())
+        );
+        // Another pixel format at the saved resolution
+        assert_eq!(
+            resolve_format(&formats, &wanted(1920, 1080, "H264", 30)),
+            FormatMatch::Fallback(formats[0].clone())
+        );
+        assert_eq!(
+            resolve_format(&formats, &wanted(3840, 2160, "MJPG", 30)),
+            FormatMatch::Unsupported
+        );
+    }
+
+    #[test]
+    fn test_profile_names() {
+        assert_eq!(validate_name("  Meeting "), Some("Meeting"));
+        assert_eq!(validate_name("   "), None);
+        assert_eq!(validate_name(&"x".repeat(MAX_NAME_LEN + 1)), None);
+    }
+}
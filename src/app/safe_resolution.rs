@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Safe resolution fallback
+//!
+//! Cameras advertise modes they can't always deliver: a 4K mode on a USB
+//! hub shared with another camera, or a raw mode a driver rejects. The
+//! preview then fails to start and the subscription retries the same mode
+//! forever. Failures are counted per pixel format and resolution, and after
+//! [`FAILURES_BEFORE_DOWNGRADE`] in a row the preview switches to the last
+//! mode known to work on this camera, or else to the next lower resolution
+//! (same pixel format first). Like a low-light switch, the downgrade is not
+//! saved as the selected mode.
+//!
+//! The fallback is bounded: a mode given up on is not tried again, and after
+//! [`MAX_DOWNGRADES`] steps or when no lower mode is left it stops and the
+//! error stays on screen.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::backends::camera::types::CameraFormat;
+use crate::config::FormatSettings;
+use crate::media::decoders::PipelineError;
+
+/// Failures of one mode before a lower one is tried
+pub const FAILURES_BEFORE_DOWNGRADE: u32 = 2;
+
+/// Downgrades per camera before giving up
+pub const MAX_DOWNGRADES: u32 = 4;
+
+/// Pixel format and resolution failures are counted for
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ModeKey {
+    pixel_format: String,
+    width: u32,
+    height: u32,
+}
+
+impl ModeKey {
+    fn of(format: &CameraFormat) -> Self {
+        Self {
+            pixel_format: format.pixel_format.clone(),
+            width: format.width,
+            height: format.height,
+        }
+    }
+
+    fn matches(&self, format: &CameraFormat) -> bool {
+        self.width == format.width
+            && self.height == format.height
+            && self.pixel_format == format.pixel_format
+    }
+}
+
+/// A switch to a lower mode and why it was made
+#[derive(Debug, Clone, PartialEq)]
+pub struct Downgrade {
+    pub from: CameraFormat,
+    pub to: CameraFormat,
+    /// The error the original mode kept failing with
+    pub reason: String,
+}
+
+/// What to do about a failed start
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Keep retrying the current mode
+    Retry,
+    /// Switch to this mode
+    Downgrade(CameraFormat),
+    /// Nothing lower is left to try
+    GiveUp,
+}
+
+/// Start failures of the current camera's modes
+#[derive(Debug, Default)]
+pub struct SafeResolution {
+    /// Camera the counts belong to
+    camera: Option<String>,
+    failures: HashMap<ModeKey, u32>,
+    /// Modes given up on, not switched to again
+    given_up: HashSet<ModeKey>,
+    downgrades: u32,
+    last_downgrade: Option<Downgrade>,
+    exhausted: bool,
+    /// Mode that delivered frames since the last failure
+    confirmed: Option<ModeKey>,
+}
+
+impl SafeResolution {
+    /// Forget everything when `camera` isn't the camera the counts are for
+    fn select_camera(&mut self, camera: &str) {
+        if self.camera.as_deref() != Some(camera) {
+            *self = Self {
+                camera: Some(camera.to_string()),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Note that `active` failed to start on `camera` with `err`
+    ///
+    /// `last_good` is the mode that last delivered frames on this camera.
+    pub fn record_failure(
+        &mut self,
+        camera: &str,
+        active: &CameraFormat,
+        available: &[CameraFormat],
+        last_good: Option<&FormatSettings>,
+        err: &PipelineError,
+    ) -> Outcome {
+        self.select_camera(camera);
+        self.confirmed = None;
+        if !err.may_depend_on_mode() || self.exhausted {
+            return Outcome::Retry;
+        }
+
+        let key = ModeKey::of(active);
+        let failures = self.failures.entry(key.clone()).or_default();
+        *failures += 1;
+        if *failures < FAILURES_BEFORE_DOWNGRADE {
+            return Outcome::Retry;
+        }
+        self.given_up.insert(key);
+
+        let target = (self.downgrades < MAX_DOWNGRADES)
+            .then(|| {
+                last_good
+                    .and_then(|settings| {
+                        available.iter().find(|f| {
+                            settings_match(settings, f)
+                                && !self.given_up.iter().any(|key| key.matches(f))
+                        })
+                    })
+                    .cloned()
+                    .or_else(|| next_lower(available, active, &self.given_up))
+            })
+            .flatten();
+        match target {
+            Some(to) => {
+                self.downgrades += 1;
+                self.last_downgrade = Some(Downgrade {
+                    from: active.clone(),
+                    to: to.clone(),
+                    reason: err.to_string(),
+                });
+                Outcome::Downgrade(to)
+            }
+            None => {
+                self.exhausted = true;
+                Outcome::GiveUp
+            }
+        }
+    }
+
+    /// Note that `active` delivers frames on `camera`
+    ///
+    /// Returns true the first time since the last failure, when the mode
+    /// should be remembered as the last known good one.
+    pub fn record_success(&mut self, camera: &str, active: &CameraFormat) -> bool {
+        if self.camera.as_deref() == Some(camera)
+            && self
+                .confirmed
+                .as_ref()
+                .is_some_and(|key| key.matches(active))
+        {
+            return false;
+        }
+        self.select_camera(camera);
+        let key = ModeKey::of(active);
+        self.failures.remove(&key);
+        self.given_up.remove(&key);
+        self.downgrades = 0;
+        self.exhausted = false;
+        self.confirmed = Some(key);
+        true
+    }
+
+    /// The last switch to a lower mode, if any
+    pub fn last_downgrade(&self) -> Option<&Downgrade> {
+        self.last_downgrade.as_ref()
+    }
+
+    /// Whether the fallback ran out of modes to try
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+fn settings_match(settings: &FormatSettings, format: &CameraFormat) -> bool {
+    settings.width == format.width
+        && settings.height == format.height
+        && settings.pixel_format == format.pixel_format
+        && match (format.framerate, settings.framerate) {
+            (Some(rate), Some(fps)) => rate.matches_int(fps),
+            (None, None) => true,
+            _ => false,
+        }
+}
+
+/// The largest mode below `active`'s resolution that wasn't given up on
+///
+/// Modes in `active`'s pixel format come first, and among modes of one
+/// resolution the frame rate closest to `active`'s.
+fn next_lower(
+    available: &[CameraFormat],
+    active: &CameraFormat,
+    given_up: &HashSet<ModeKey>,
+) -> Option<CameraFormat> {
+    let pixels = |f: &CameraFormat| u64::from(f.width) * u64::from(f.height);
+    let fps = |f: &CameraFormat| f.framerate.map_or(0.0, |rate| rate.as_f64());
+    available
+        .iter()
+        .filter(|f| pixels(f) < pixels(active))
+        .filter(|f| !given_up.iter().any(|key| key.matches(f)))
+        .max_by(|a, b| {
+            (a.pixel_format == active.pixel_format)
+                .cmp(&(b.pixel_format == active.pixel_format))
+                .then(pixels(a).cmp(&pixels(b)))
+                .then(
+                    (fps(b) - fps(active))
+                        .abs()
+                        .total_cmp(&(fps(a) - fps(active)).abs()),
+                )
+        })
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::Framerate;
+
+    fn mode(pixel_format: &str, width: u32, height: u32, fps: u32) -> CameraFormat {
+        CameraFormat {
+            width,
+            height,
+            framerate: Some(Framerate::new(fps, 1)),
+            hardware_accelerated: true,
+            pixel_format: pixel_format.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_downgrades_step_down_and_give_up() {
+        let available = [
+            mode("MJPG", 3840, 2160, 30),
+            mode("MJPG", 1920, 1080, 30),
+            mode("MJPG", 1920, 1080, 60),
+            mode("YUYV", 1280, 720, 10),
+            mode("YUYV", 640, 480, 30),
+        ];
+        let err = PipelineError::FirstFrameTimeout { secs: 10 };
+        let mut fallback = SafeResolution::default();
+        let camera = "/dev/video0";
+
+        assert_eq!(
+            fallback.record_failure(camera, &available[0], &available, None, &err),
+            Outcome::Retry
+        );
+        // Same format first, at the frame rate closest to the failed mode's
+        assert_eq!(
+            fallback.record_failure(camera, &available[0], &available, None, &err),
+            Outcome::Downgrade(available[1].clone())
+        );
+        for _ in 0..2 {
+            fallback.record_failure(camera, &available[1], &available, None, &err);
+        }
+        // 1080p was given up on at any frame rate
+        assert_eq!(
+            fallback.last_downgrade().map(|d| &d.to),
+            Some(&available[3])
+        );
+        for _ in 0..2 {
+            fallback.record_failure(camera, &available[3], &available, None, &err);
+        }
+        assert_eq!(
+            fallback.record_failure(camera, &available[4], &available, None, &err),
+            Outcome::Retry
+        );
+        // Nothing below 640x480: the error stays
+        assert_eq!(
+            fallback.record_failure(camera, &available[4], &available, None, &err),
+            Outcome::GiveUp
+        );
+        assert!(fallback.is_exhausted());
+
+        // A busy camera isn't a resolution problem
+        let mut fallback = SafeResolution::default();
+        let busy = PipelineError::SourceUnavailable("Device is busy".to_string());
+        for _ in 0..3 {
+            assert_eq!(
+                fallback.record_failure(camera, &available[0], &available, None, &busy),
+                Outcome::Retry
+            );
+        }
+    }
+
+    #[test]
+    fn test_last_good_mode_is_preferred() {
+        let available = [
+            mode("MJPG", 3840, 2160, 30),
+            mode("MJPG", 1920, 1080, 30),
+            mode("MJPG", 1280, 720, 30),
+        ];
+        let last_good = FormatSettings {
+            width: 1280,
+            height: 720,
+            framerate: Some(30),
+            pixel_format: "MJPG".to_string(),
+        };
+        let err = PipelineError::CapsNegotiationFailed("not-negotiated".to_string());
+        let mut fallback = SafeResolution::default();
+        fallback.record_failure("cam", &available[0], &available, Some(&last_good), &err);
+        assert_eq!(
+            fallback.record_failure("cam", &available[0], &available, Some(&last_good), &err),
+            Outcome::Downgrade(available[2].clone())
+        );
+
+        assert!(fallback.record_success("cam", &available[2]));
+        assert!(!fallback.record_success("cam", &available[2]));
+        assert!(fallback.record_success("other", &available[2]));
+    }
+}
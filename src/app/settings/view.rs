@@ -3,9 +3,13 @@
 //! Settings drawer view
 
 use crate::app::state::{AppModel, Message};
-use crate::config::{AppTheme, AudioEncoder, PhotoOutputFormat};
+use crate::backends::camera::types::{CameraFacing, CameraSourceKind};
+use crate::config::{AppTheme, AudioEncoder, PhotoOutputFormat, ShortcutAction};
 use crate::constants::BitratePreset;
 use crate::fl;
+use crate::pipelines::filename::{
+    self, CaptureKind, FilenameContext, FilenameTemplate, TemplateError,
+};
 use cosmic::Element;
 use cosmic::app::context_drawer;
 use cosmic::iced::{Alignment, Length};
@@ -83,6 +87,29 @@ impl AppModel {
             camera_section = camera_section.add(self.build_device_info_panel());
         }
 
+        // Device previews (only useful when there is more than one camera to pick from)
+        if self.available_cameras.len() > 1 {
+            camera_section = camera_section.add(self.build_device_thumbnails());
+        }
+
+        // Stack choice for cameras offered through both libcamera and V4L2
+        if let Some(camera) = self.available_cameras.get(self.current_camera_index)
+            && camera.source_kinds().len() > 1
+        {
+            let selected_source_index = camera
+                .source_kind
+                .and_then(|kind| CameraSourceKind::ALL.iter().position(|k| *k == kind));
+            camera_section = camera_section.add(
+                widget::settings::item::builder(fl!("settings-camera-source"))
+                    .description(fl!("settings-camera-source-description"))
+                    .control(widget::dropdown(
+                        &self.camera_source_dropdown_options,
+                        selected_source_index,
+                        |index| Message::SelectCameraSource(CameraSourceKind::ALL[index]),
+                    )),
+            );
+        }
+
         camera_section = camera_section.add(
             widget::settings::item::builder(fl!("settings-format")).control(widget::dropdown(
                 &self.mode_dropdown_options,
@@ -91,6 +118,246 @@ impl AppModel {
             )),
         );
 
+        // Grayscale display options (only relevant for monochrome/thermal formats)
+        let active_codec = self
+            .active_format
+            .as_ref()
+            .map(|format| crate::media::Codec::from_fourcc(&format.pixel_format));
+        if active_codec == Some(crate::media::Codec::Y16) {
+            camera_section = camera_section.add(
+                widget::settings::item::builder(fl!("settings-gray-auto-stretch"))
+                    .description(fl!("settings-gray-auto-stretch-description"))
+                    .toggler(self.config.gray16_auto_stretch, |_| {
+                        Message::ToggleGrayAutoStretch
+                    }),
+            );
+        }
+        if matches!(
+            active_codec,
+            Some(crate::media::Codec::GREY | crate::media::Codec::Y16)
+        ) {
+            camera_section = camera_section.add(
+                widget::settings::item::builder(fl!("settings-false-color"))
+                    .description(fl!("settings-false-color-description"))
+                    .toggler(self.config.false_color_lut, |_| Message::ToggleFalseColor),
+            );
+        }
+
+        // MJPEG error resilience, remembered per camera
+        if active_codec == Some(crate::media::Codec::MJPEG) {
+            let enabled = self
+                .available_cameras
+                .get(self.current_camera_index)
+                .is_some_and(|camera| self.config.mjpeg_resilience_cameras.contains(&camera.path));
+            camera_section = camera_section.add(
+                widget::settings::item::builder(fl!("settings-mjpeg-resilience"))
+                    .description(fl!("settings-mjpeg-resilience-description"))
+                    .toggler(enabled, |_| Message::ToggleMjpegResilience),
+            );
+        }
+
+        // Deinterlacing (only offered when the source declares interlaced caps)
+        if crate::backends::camera::pipewire::pipeline::get_interlace_info()
+            .is_some_and(|info| info.is_interlaced())
+        {
+            let current_deinterlace_index = crate::config::DeinterlaceMethod::ALL
+                .iter()
+                .position(|m| *m == self.config.deinterlace_method)
+                .unwrap_or(0);
+            camera_section = camera_section.add(
+                widget::settings::item::builder(fl!("settings-deinterlace"))
+                    .description(fl!("settings-deinterlace-description"))
+                    .control(widget::dropdown(
+                        &self.deinterlace_dropdown_options,
+                        Some(current_deinterlace_index),
+                        |index| {
+                            Message::SetDeinterlaceMethod(
+                                crate::config::DeinterlaceMethod::ALL[index],
+                            )
+                        },
+                    )),
+            );
+        }
+
+        // Thread cap for software H.264/H.265 decoding
+        let cpu_threads = crate::constants::pipeline::videoconvert_threads();
+        camera_section = camera_section.add(
+            widget::settings::item::builder(fl!("settings-decode-threads"))
+                .description(match self.config.decode_threads {
+                    Some(threads) => fl!("settings-decode-threads-limited", threads = threads),
+                    None => fl!("settings-decode-threads-auto", threads = cpu_threads),
+                })
+                .toggler(self.config.decode_threads.is_some(), move |limited| {
+                    Message::SetDecodeThreads(limited.then_some(cpu_threads.div_ceil(2)))
+                }),
+        );
+        if let Some(threads) = self.config.decode_threads {
+            camera_section = camera_section.add(widget::settings::item_row(vec![
+                widget::slider(1..=cpu_threads.max(threads), threads, |threads| {
+                    Message::SetDecodeThreads(Some(threads))
+                })
+                .on_release(Message::ApplyDecodeThreads)
+                .width(Length::Fill)
+                .into(),
+            ]));
+        }
+
+        // Power preference (decoder order and GPU adapter)
+        let current_power_index = crate::config::PowerPreference::ALL
+            .iter()
+            .position(|preference| *preference == self.config.power_preference)
+            .unwrap_or(0);
+        camera_section = camera_section.add(
+            widget::settings::item::builder(fl!("settings-power-preference"))
+                .description(fl!("settings-power-preference-description"))
+                .control(widget::dropdown(
+                    &self.power_preference_dropdown_options,
+                    Some(current_power_index),
+                    |index| Message::SetPowerPreference(crate::config::PowerPreference::ALL[index]),
+                )),
+        );
+
+        // Capture path: PipeWire, or v4l2src with a forced io-mode (only with a V4L2 node)
+        if self
+            .available_cameras
+            .get(self.current_camera_index)
+            .is_some_and(|camera| camera.device_info.is_some())
+        {
+            let current_io_mode_index = crate::config::V4l2IoMode::ALL
+                .iter()
+                .position(|mode| *mode == self.config.v4l2_io_mode)
+                .unwrap_or(0);
+            camera_section = camera_section.add(
+                widget::settings::item::builder(fl!("settings-v4l2-io-mode"))
+                    .description(fl!("settings-v4l2-io-mode-description"))
+                    .control(widget::dropdown(
+                        &self.v4l2_io_mode_dropdown_options,
+                        Some(current_io_mode_index),
+                        |index| Message::SetV4l2IoMode(crate::config::V4l2IoMode::ALL[index]),
+                    )),
+            );
+        }
+
+        // Preview resolution cap (video mode only; photos need full resolution)
+        let current_preview_cap_index = crate::config::PreviewResolutionCap::ALL
+            .iter()
+            .position(|cap| *cap == self.config.preview_resolution_cap)
+            .unwrap_or(0);
+        camera_section = camera_section.add(
+            widget::settings::item::builder(fl!("settings-preview-cap"))
+                .description(fl!("settings-preview-cap-description"))
+                .control(widget::dropdown(
+                    &self.preview_cap_dropdown_options,
+                    Some(current_preview_cap_index),
+                    |index| {
+                        Message::SetPreviewResolutionCap(
+                            crate::config::PreviewResolutionCap::ALL[index],
+                        )
+                    },
+                )),
+        );
+
+        // Lower frame rate in low light
+        let current_low_light_index = crate::config::LowLightMode::ALL
+            .iter()
+            .position(|mode| *mode == self.config.low_light_mode)
+            .unwrap_or(0);
+        camera_section = camera_section.add(
+            widget::settings::item::builder(fl!("settings-low-light"))
+                .description(fl!("settings-low-light-description"))
+                .control(widget::dropdown(
+                    &self.low_light_dropdown_options,
+                    Some(current_low_light_index),
+                    |index| Message::SetLowLightMode(crate::config::LowLightMode::ALL[index]),
+                )),
+        );
+
+        // How long a camera may take to start before giving up
+        let current_open_timeout_index = crate::constants::camera_open::TIMEOUT_OPTIONS_SECS
+            .iter()
+            .position(|&secs| secs == self.config.camera_open_timeout_secs)
+            .unwrap_or(0);
+        camera_section = camera_section.add(
+            widget::settings::item::builder(fl!("settings-open-timeout"))
+                .description(fl!("settings-open-timeout-description"))
+                .control(widget::dropdown(
+                    &self.camera_open_timeout_dropdown_options,
+                    Some(current_open_timeout_index),
+                    Message::SelectCameraOpenTimeout,
+                )),
+        );
+
+        // Power saving while the window is in the background
+        let current_background_index = crate::config::BackgroundPreview::ALL
+            .iter()
+            .position(|behavior| *behavior == self.config.background_preview)
+            .unwrap_or(0);
+        camera_section = camera_section.add(
+            widget::settings::item::builder(fl!("settings-background-preview"))
+                .description(fl!("settings-background-preview-description"))
+                .control(widget::dropdown(
+                    &self.background_preview_dropdown_options,
+                    Some(current_background_index),
+                    |index| {
+                        Message::SetBackgroundPreview(crate::config::BackgroundPreview::ALL[index])
+                    },
+                )),
+        );
+
+        // Screen blanking and suspend while the camera is in use
+        let current_keep_awake_index = crate::config::KeepAwake::ALL
+            .iter()
+            .position(|behavior| *behavior == self.config.keep_awake)
+            .unwrap_or(0);
+        camera_section = camera_section.add(
+            widget::settings::item::builder(fl!("settings-keep-awake"))
+                .description(fl!("settings-keep-awake-description"))
+                .control(widget::dropdown(
+                    &self.keep_awake_dropdown_options,
+                    Some(current_keep_awake_index),
+                    |index| Message::SetKeepAwake(crate::config::KeepAwake::ALL[index]),
+                )),
+        );
+
+        // Software framerate cap
+        let current_framerate_cap_index = crate::constants::framerate_cap::OPTIONS_FPS
+            .iter()
+            .position(|&fps| fps == self.config.framerate_cap_fps)
+            .unwrap_or(0);
+        camera_section = camera_section.add(
+            widget::settings::item::builder(fl!("settings-framerate-cap"))
+                .description(fl!("settings-framerate-cap-description"))
+                .control(widget::dropdown(
+                    &self.framerate_cap_dropdown_options,
+                    Some(current_framerate_cap_index),
+                    Message::SelectFramerateCap,
+                )),
+        );
+
+        // VA-API render node (only worth choosing with more than one GPU)
+        if self.available_render_nodes.len() > 1 {
+            let current_node_index = self
+                .config
+                .vaapi_render_node
+                .as_ref()
+                .and_then(|path| {
+                    self.available_render_nodes
+                        .iter()
+                        .position(|node| &node.path == path)
+                })
+                .map(|i| i + 1)
+                .unwrap_or(0);
+            camera_section = camera_section.add(
+                widget::settings::item::builder(fl!("settings-vaapi-device"))
+                    .description(fl!("settings-vaapi-device-description"))
+                    .control(widget::dropdown(
+                        &self.render_node_dropdown_options,
+                        Some(current_node_index),
+                        Message::SelectRenderNode,
+                    )),
+            );
+        }
+
         // Audio encoder index
         let current_audio_encoder_index = AudioEncoder::ALL
             .iter()
@@ -119,6 +386,58 @@ impl AppModel {
                     .toggler(self.config.record_audio, |_| Message::ToggleRecordAudio),
             );
 
+        // Split long recordings into segments
+        let current_split_duration_index = crate::constants::recording_split::DURATION_OPTIONS_MINS
+            .iter()
+            .position(|&minutes| minutes == self.config.recording_split_minutes)
+            .unwrap_or(0);
+        let current_split_size_index = crate::constants::recording_split::SIZE_OPTIONS_MB
+            .iter()
+            .position(|&mb| mb == self.config.recording_split_mb)
+            .unwrap_or(0);
+        video_section = video_section
+            .add(
+                widget::settings::item::builder(fl!("settings-split-duration"))
+                    .description(fl!("settings-split-description"))
+                    .control(widget::dropdown(
+                        &self.recording_split_duration_dropdown_options,
+                        Some(current_split_duration_index),
+                        Message::SelectRecordingSplitDuration,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-split-size")).control(
+                    widget::dropdown(
+                        &self.recording_split_size_dropdown_options,
+                        Some(current_split_size_index),
+                        Message::SelectRecordingSplitSize,
+                    ),
+                ),
+            );
+
+        // Retroactive clip buffer ("save the last N seconds")
+        let current_retro_index = crate::constants::retro_buffer::DURATION_OPTIONS_SECS
+            .iter()
+            .position(|&secs| secs == self.config.retro_buffer_seconds)
+            .unwrap_or(0);
+        video_section = video_section.add(
+            widget::settings::item::builder(fl!("settings-retro-buffer"))
+                .description(fl!("settings-retro-buffer-description"))
+                .control(widget::dropdown(
+                    &self.retro_buffer_dropdown_options,
+                    Some(current_retro_index),
+                    Message::SelectRetroBufferDuration,
+                )),
+        );
+        if self.config.retro_buffer_seconds > 0 {
+            video_section = video_section.add(
+                widget::settings::item::builder(fl!("settings-retro-save")).control(
+                    widget::button::standard(fl!("settings-retro-save-button"))
+                        .on_press(Message::SaveRetroClip),
+                ),
+            );
+        }
+
         // Only show audio encoder and microphone selection when audio is enabled
         if self.config.record_audio {
             video_section = video_section
@@ -136,9 +455,21 @@ impl AppModel {
                         widget::dropdown(
                             &self.audio_dropdown_options,
                             Some(self.current_audio_device_index),
-                            Message::SelectAudioDevice,
+                            Message::SetAudioSource,
                         ),
                     ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-audio-level")).control(
+                        cosmic::iced::widget::progress_bar(0.0..=1.0, self.audio_level)
+                            .width(Length::Fixed(160.0))
+                            .height(Length::Fixed(8.0)),
+                    ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-audio-mute"))
+                        .description(fl!("settings-audio-mute-description"))
+                        .toggler(self.audio_muted, |_| Message::ToggleAudioMute),
                 );
         }
 
@@ -160,7 +491,7 @@ impl AppModel {
             .position(|f| *f == self.config.photo_output_format)
             .unwrap_or(0); // Default to JPEG (index 0)
 
-        let photo_section = widget::settings::section()
+        let mut photo_section = widget::settings::section()
             .title(fl!("settings-photo"))
             .add(
                 widget::settings::item::builder(fl!("settings-photo-format"))
@@ -170,7 +501,25 @@ impl AppModel {
                         Some(current_photo_format_index),
                         Message::SelectPhotoOutputFormat,
                     )),
-            )
+            );
+        if self.config.photo_output_format == PhotoOutputFormat::Jpeg {
+            photo_section = photo_section.add(
+                widget::settings::item::builder(fl!("settings-jpeg-quality"))
+                    .description(fl!(
+                        "settings-jpeg-quality-description",
+                        quality = self.config.photo_jpeg_quality
+                    ))
+                    .control(
+                        widget::slider(
+                            1..=100,
+                            self.config.photo_jpeg_quality,
+                            Message::SetPhotoJpegQuality,
+                        )
+                        .width(Length::Fixed(160.0)),
+                    ),
+            );
+        }
+        photo_section = photo_section
             .add(
                 widget::settings::item::builder(fl!("settings-hdr-plus"))
                     .description(fl!("settings-hdr-plus-description"))
@@ -184,13 +533,285 @@ impl AppModel {
                 widget::settings::item::builder(fl!("settings-save-burst-raw"))
                     .description(fl!("settings-save-burst-raw-description"))
                     .toggler(self.config.save_burst_raw, |_| Message::ToggleSaveBurstRaw),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-embed-metadata"))
+                    .description(fl!("settings-embed-metadata-description"))
+                    .toggler(self.config.embed_capture_metadata, |_| {
+                        Message::ToggleEmbedCaptureMetadata
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-first-frame-snapshot"))
+                    .description(fl!("settings-first-frame-snapshot-description"))
+                    .toggler(self.config.first_frame_snapshot, |_| {
+                        Message::ToggleFirstFrameSnapshot
+                    }),
             );
+        let current_burst_count_index = crate::constants::still_burst::COUNT_OPTIONS
+            .iter()
+            .position(|&count| count == self.config.still_burst_count);
+        let current_burst_interval_index = crate::constants::still_burst::INTERVAL_OPTIONS_MS
+            .iter()
+            .position(|&ms| ms == self.config.still_burst_interval_ms);
+        photo_section = photo_section
+            .add(
+                widget::settings::item::builder(fl!("settings-still-burst-count"))
+                    .description(fl!(
+                        "settings-still-burst-description",
+                        key = crate::app::shortcuts::bound_key(
+                            &self.config.keyboard_shortcuts,
+                            ShortcutAction::CaptureBurst
+                        )
+                    ))
+                    .control(widget::dropdown(
+                        &self.still_burst_count_dropdown_options,
+                        current_burst_count_index,
+                        Message::SelectStillBurstCount,
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-still-burst-interval")).control(
+                    widget::dropdown(
+                        &self.still_burst_interval_dropdown_options,
+                        current_burst_interval_index,
+                        Message::SelectStillBurstInterval,
+                    ),
+                ),
+            );
+
+        // Output section: where captures go and how they are named
+        let output_directory_description = match &self.config.output_directory {
+            Some(dir) => dir.clone(),
+            None => fl!(
+                "settings-output-directory-default",
+                photos = crate::app::photo_output_directory(&self.config)
+                    .display()
+                    .to_string(),
+                videos = crate::app::video_output_directory(&self.config)
+                    .display()
+                    .to_string()
+            ),
+        };
+        let template_description = match &self.filename_template_error {
+            Some(err) => filename_template_error_text(err),
+            None => {
+                let template =
+                    FilenameTemplate::parse(&self.filename_template_input).unwrap_or_default();
+                let extension = self.config.photo_output_format.extension();
+                let context = FilenameContext {
+                    kind: CaptureKind::Photo,
+                    time: chrono::Local::now(),
+                    device: self
+                        .available_cameras
+                        .get(self.current_camera_index)
+                        .map_or("", |camera| camera.name.as_str()),
+                    extension,
+                };
+                fl!(
+                    "settings-filename-template-example",
+                    name = format!("{}.{}", template.render(&context, 1), extension),
+                    tokens = filename::TOKENS.join(" ")
+                )
+            }
+        };
+        let output_section = widget::settings::section()
+            .title(fl!("settings-output"))
+            .add(
+                widget::settings::item::builder(fl!("settings-output-directory"))
+                    .description(output_directory_description)
+                    .control(
+                        widget::row()
+                            .push(
+                                widget::button::standard(fl!("settings-output-directory-choose"))
+                                    .on_press(Message::ChooseOutputDirectory),
+                            )
+                            .push(
+                                widget::button::standard(fl!("settings-output-directory-reset"))
+                                    .on_press_maybe(
+                                        self.config
+                                            .output_directory
+                                            .is_some()
+                                            .then_some(Message::ResetOutputDirectory),
+                                    ),
+                            )
+                            .spacing(8),
+                    ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-filename-template"))
+                    .description(template_description)
+                    .control(widget::horizontal_space()),
+            )
+            .add(widget::settings::item_row(vec![
+                widget::text_input(filename::DEFAULT_TEMPLATE, &self.filename_template_input)
+                    .font(cosmic::font::mono())
+                    .on_input(Message::FilenameTemplateInput)
+                    .width(Length::Fill)
+                    .into(),
+            ]));
+
+        // Watermark section: burned into saved files, unlike the preview overlays
+        let watermark = &self.config.watermark;
+        let mut watermark_section = widget::settings::section()
+            .title(fl!("settings-watermark"))
+            .add(
+                widget::settings::item::builder(fl!("settings-watermark-enabled"))
+                    .description(fl!("settings-watermark-description"))
+                    .toggler(watermark.enabled, |_| Message::ToggleWatermark),
+            );
+        if watermark.enabled {
+            let selected_position_index = crate::config::WatermarkPosition::ALL
+                .iter()
+                .position(|p| *p == watermark.position)
+                .unwrap_or(0);
+            watermark_section = watermark_section
+                .add(widget::settings::item_row(vec![
+                    widget::text_input(fl!("settings-watermark-text"), &watermark.text)
+                        .on_input(Message::WatermarkTextInput)
+                        .width(Length::Fill)
+                        .into(),
+                ]))
+                .add(
+                    widget::settings::item::builder(fl!("settings-watermark-timestamp"))
+                        .description(fl!("settings-watermark-timestamp-description"))
+                        .toggler(watermark.timestamp, |_| Message::ToggleWatermarkTimestamp),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-watermark-position")).control(
+                        widget::dropdown(
+                            &self.watermark_position_dropdown_options,
+                            Some(selected_position_index),
+                            |index| {
+                                Message::SetWatermarkPosition(
+                                    crate::config::WatermarkPosition::ALL[index],
+                                )
+                            },
+                        ),
+                    ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-watermark-size"))
+                        .description(fl!(
+                            "settings-watermark-size-description",
+                            percent = watermark.font_size_percent
+                        ))
+                        .control(
+                            widget::slider(
+                                1..=20,
+                                watermark.font_size_percent,
+                                Message::SetWatermarkFontSize,
+                            )
+                            .width(Length::Fixed(160.0)),
+                        ),
+                )
+                .add(
+                    widget::settings::item::builder(fl!("settings-watermark-opacity"))
+                        .description(fl!(
+                            "settings-watermark-opacity-description",
+                            percent = watermark.opacity_percent
+                        ))
+                        .control(
+                            widget::slider(
+                                10..=100,
+                                watermark.opacity_percent,
+                                Message::SetWatermarkOpacity,
+                            )
+                            .width(Length::Fixed(160.0)),
+                        ),
+                );
+        }
 
         // Mirror preview section
-        let mirror_section = widget::settings::section().add(
-            widget::settings::item::builder(fl!("settings-mirror-preview"))
-                .description(fl!("settings-mirror-preview-description"))
-                .toggler(self.config.mirror_preview, |_| Message::ToggleMirrorPreview),
+        let selected_rotation_index = crate::config::RotationOverride::ALL
+            .iter()
+            .position(|r| *r == self.config.rotation_override)
+            .unwrap_or(0);
+        let detected_facing = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .map(|camera| camera.facing)
+            .unwrap_or_default();
+        let selected_facing_index = self
+            .available_cameras
+            .get(self.current_camera_index)
+            .and_then(|camera| self.config.camera_facing_overrides.get(&camera.path))
+            .and_then(|facing| CameraFacing::ALL.iter().position(|f| f == facing))
+            .map_or(0, |index| index + 1);
+        let mirror_description = if self.current_camera_facing().mirrors_preview() {
+            fl!("settings-mirror-preview-description")
+        } else {
+            fl!("settings-mirror-preview-rear")
+        };
+        let mirror_section = widget::settings::section()
+            .add(
+                widget::settings::item::builder(fl!("settings-mirror-preview"))
+                    .description(mirror_description)
+                    .toggler(self.config.mirror_preview, |_| Message::ToggleMirrorPreview),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-camera-facing"))
+                    .description(fl!(
+                        "settings-camera-facing-description",
+                        detected = detected_facing.display_name()
+                    ))
+                    .control(widget::dropdown(
+                        &self.camera_facing_dropdown_options,
+                        Some(selected_facing_index),
+                        |index| {
+                            Message::SetCameraFacing(
+                                index.checked_sub(1).map(|i| CameraFacing::ALL[i]),
+                            )
+                        },
+                    )),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-rotation"))
+                    .description(fl!("settings-rotation-description"))
+                    .control(widget::dropdown(
+                        &self.rotation_override_dropdown_options,
+                        Some(selected_rotation_index),
+                        |index| {
+                            Message::SetRotationOverride(
+                                crate::config::RotationOverride::ALL[index],
+                            )
+                        },
+                    )),
+            );
+        let selected_effect_index = crate::config::ColorEffect::ALL
+            .iter()
+            .position(|e| *e == self.config.color_effect)
+            .unwrap_or(0);
+        let mut mirror_section = mirror_section.add(
+            widget::settings::item::builder(fl!("settings-color-effect"))
+                .description(fl!("settings-color-effect-description"))
+                .control(widget::dropdown(
+                    &self.color_effect_dropdown_options,
+                    Some(selected_effect_index),
+                    |index| Message::SetColorEffect(crate::config::ColorEffect::ALL[index]),
+                )),
+        );
+        if self.config.color_effect != crate::config::ColorEffect::None {
+            mirror_section = mirror_section.add(
+                widget::settings::item::builder(fl!("settings-color-effect-output"))
+                    .description(fl!("settings-color-effect-output-description"))
+                    .toggler(self.config.color_effect_on_output, |_| {
+                        Message::ToggleColorEffectOnOutput
+                    }),
+            );
+        }
+        let selected_scaling_index = crate::config::ScalingQuality::ALL
+            .iter()
+            .position(|q| *q == self.config.scaling_quality)
+            .unwrap_or(0);
+        let mirror_section = mirror_section.add(
+            widget::settings::item::builder(fl!("settings-scaling-quality"))
+                .description(fl!("settings-scaling-quality-description"))
+                .control(widget::dropdown(
+                    &self.scaling_quality_dropdown_options,
+                    Some(selected_scaling_index),
+                    |index| Message::SetScalingQuality(crate::config::ScalingQuality::ALL[index]),
+                )),
         );
 
         // Virtual camera section
@@ -221,17 +842,169 @@ impl AppModel {
 
         let bug_reports_section = widget::settings::section()
             .title(fl!("settings-bug-reports"))
+            .add(
+                widget::settings::item::builder(fl!("settings-validate-decoders"))
+                    .description(fl!("settings-validate-decoders-description"))
+                    .toggler(self.config.validate_decoders_at_startup, |_| {
+                        Message::ToggleValidateDecoders
+                    }),
+            )
             .add(widget::settings::item_row(vec![bug_report_control]));
 
+        let automation_section = widget::settings::section()
+            .title(fl!("settings-automation"))
+            .add(
+                widget::settings::item::builder(fl!("settings-control-socket"))
+                    .description(fl!(
+                        "settings-control-socket-description",
+                        path = crate::control_socket::socket_path().display().to_string()
+                    ))
+                    .toggler(self.config.control_socket_enabled, |_| {
+                        Message::ToggleControlSocket
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-http-stream"))
+                    .description(fl!("settings-http-stream-description"))
+                    .toggler(self.config.http_stream_enabled, |_| {
+                        Message::ToggleHttpStream
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-http-stream-port")).control(
+                    widget::text_input("", &self.http_stream_port_input)
+                        .on_input(Message::HttpStreamPortInput)
+                        .on_submit(|_| Message::ApplyHttpStreamPort)
+                        .width(Length::Fixed(100.0)),
+                ),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-http-stream-all-interfaces"))
+                    .description(fl!("settings-http-stream-all-interfaces-description"))
+                    .toggler(self.config.http_stream_all_interfaces, |_| {
+                        Message::ToggleHttpStreamAllInterfaces
+                    }),
+            )
+            .add(
+                widget::settings::item::builder(fl!("settings-http-stream-max-clients"))
+                    .description(fl!("settings-http-stream-max-clients-description"))
+                    .control(widget::dropdown(
+                        &self.http_stream_max_clients_dropdown_options,
+                        crate::constants::http_stream::MAX_CLIENTS_OPTIONS
+                            .iter()
+                            .position(|&count| count == self.config.http_stream_max_clients),
+                        Message::SelectHttpStreamMaxClients,
+                    )),
+            );
+
+        // Capture profiles section (saved from the current settings)
+        let mut profiles_section = widget::settings::section().title(fl!("settings-profiles"));
+        for name in self.config.capture_profiles.keys() {
+            profiles_section = profiles_section.add(
+                widget::settings::item::builder(name.clone()).control(
+                    widget::row()
+                        .push(
+                            widget::button::standard(fl!("settings-profiles-apply"))
+                                .on_press(Message::ApplyProfile(name.clone())),
+                        )
+                        .push(
+                            widget::button::destructive(fl!("settings-profiles-delete"))
+                                .on_press(Message::DeleteProfile(name.clone())),
+                        )
+                        .spacing(8),
+                ),
+            );
+        }
+        profiles_section = profiles_section.add(
+            widget::settings::item::builder(fl!("settings-profiles-save"))
+                .description(fl!("settings-profiles-description"))
+                .control(
+                    widget::row()
+                        .push(
+                            widget::text_input(
+                                fl!("settings-profiles-name"),
+                                &self.profile_name_input,
+                            )
+                            .on_input(Message::ProfileNameInput)
+                            .on_submit(|_| Message::SaveProfile)
+                            .width(Length::Fixed(160.0)),
+                        )
+                        .push(
+                            widget::button::standard(fl!("settings-profiles-save-button"))
+                                .on_press_maybe(
+                                    crate::app::profiles::validate_name(&self.profile_name_input)
+                                        .map(|_| Message::SaveProfile),
+                                ),
+                        )
+                        .spacing(8),
+                ),
+        );
+
+        // Keyboard shortcuts section (click a key, then press the new one)
+        let mut shortcuts_section = widget::settings::section().title(fl!("settings-shortcuts"));
+        for action in ShortcutAction::ALL {
+            let label = if self.rebinding_shortcut == Some(action) {
+                fl!("settings-shortcuts-press-key")
+            } else {
+                crate::app::shortcuts::bound_key(&self.config.keyboard_shortcuts, action)
+                    .to_string()
+            };
+            shortcuts_section = shortcuts_section.add(
+                widget::settings::item::builder(action.display_name()).control(
+                    widget::button::standard(label).on_press(Message::StartRebindShortcut(action)),
+                ),
+            );
+        }
+        let current_coarse_steps_index = crate::constants::exposure_nudge::COARSE_STEP_OPTIONS
+            .iter()
+            .position(|&steps| steps == self.config.nudge_coarse_steps)
+            .unwrap_or(0);
+        shortcuts_section = shortcuts_section.add(
+            widget::settings::item::builder(fl!("settings-nudge-coarse-steps"))
+                .description(fl!("settings-nudge-coarse-steps-description"))
+                .control(widget::dropdown(
+                    &self.nudge_coarse_steps_dropdown_options,
+                    Some(current_coarse_steps_index),
+                    Message::SelectNudgeCoarseSteps,
+                )),
+        );
+        shortcuts_section = shortcuts_section.add(
+            widget::settings::item::builder(fl!("settings-shortcuts-reset"))
+                .description(fl!("settings-shortcuts-description"))
+                .control(
+                    widget::button::standard(fl!("settings-shortcuts-reset-button"))
+                        .on_press_maybe(
+                            (!self.config.keyboard_shortcuts.is_empty())
+                                .then_some(Message::ResetShortcuts),
+                        ),
+                ),
+        );
+
+        // Reset goes last, away from the settings it undoes
+        let reset_section = widget::settings::section().add(
+            widget::settings::item::builder(fl!("settings-reset"))
+                .description(fl!("settings-reset-description"))
+                .control(
+                    widget::button::destructive(fl!("settings-reset-button"))
+                        .on_press(Message::RequestResetSettings),
+                ),
+        );
+
         // Combine all sections
         let sections = vec![
             appearance_section.into(),
             camera_section.into(),
             photo_section.into(),
             video_section.into(),
+            output_section.into(),
+            watermark_section.into(),
             mirror_section.into(),
+            profiles_section.into(),
             virtual_camera_section.into(),
+            automation_section.into(),
+            shortcuts_section.into(),
             bug_reports_section.into(),
+            reset_section.into(),
         ];
 
         let settings_content: Element<'_, Message> = widget::settings::view_column(sections).into();
@@ -243,6 +1016,62 @@ impl AppModel {
         .title(fl!("settings-title"))
     }
 
+    /// Build the row of cached device thumbnails (click to select a camera)
+    fn build_device_thumbnails(&self) -> Element<'_, Message> {
+        use crate::constants::thumbnail;
+
+        let mut thumbnails = widget::row().spacing(8);
+        for (index, camera) in self.available_cameras.iter().enumerate() {
+            let preview: Element<'_, Message> =
+                match self.device_thumbnails.get(&camera.path).cloned().flatten() {
+                    Some(handle) => widget::image(handle)
+                        .width(Length::Fixed(thumbnail::WIDTH as f32))
+                        .height(Length::Fixed(thumbnail::HEIGHT as f32))
+                        .into(),
+                    // Busy, not captured yet, or currently streaming
+                    None => widget::container(
+                        icon::from_name("camera-web-symbolic")
+                            .symbolic(true)
+                            .size(24),
+                    )
+                    .width(Length::Fixed(thumbnail::WIDTH as f32))
+                    .height(Length::Fixed(thumbnail::HEIGHT as f32))
+                    .align_x(Alignment::Center)
+                    .align_y(Alignment::Center)
+                    .class(cosmic::theme::Container::Card)
+                    .into(),
+                };
+
+            let label = camera
+                .name
+                .strip_suffix(" (V4L2)")
+                .unwrap_or(&camera.name)
+                .to_string();
+            let tile = widget::column()
+                .push(preview)
+                .push(widget::text::caption(label).width(Length::Fixed(thumbnail::WIDTH as f32)))
+                .spacing(4);
+
+            thumbnails = thumbnails.push(
+                widget::button::custom(tile)
+                    .class(cosmic::theme::Button::Image)
+                    .selected(index == self.current_camera_index)
+                    .on_press(Message::SelectCamera(index)),
+            );
+        }
+
+        let mut refresh_button = widget::button::standard(fl!("settings-refresh-previews"));
+        if !self.device_thumbnails_loading {
+            refresh_button = refresh_button.on_press(Message::RefreshDeviceThumbnails);
+        }
+
+        widget::column()
+            .push(widget::scrollable::horizontal(thumbnails))
+            .push(refresh_button)
+            .spacing(8)
+            .into()
+    }
+
     /// Build the device info panel (shown when info button is clicked)
     fn build_device_info_panel(&self) -> Element<'_, Message> {
         // Helper to build a label: value row
@@ -286,3 +1115,17 @@ impl AppModel {
             .into()
     }
 }
+
+/// Why a filename template was rejected, for the template row's description
+fn filename_template_error_text(err: &TemplateError) -> String {
+    match err {
+        TemplateError::Empty => fl!("settings-filename-template-empty"),
+        TemplateError::PathSeparator => fl!("settings-filename-template-separator"),
+        TemplateError::UnknownToken(token) => {
+            fl!("settings-filename-template-unknown", token = token.as_str())
+        }
+        TemplateError::UnclosedToken => fl!("settings-filename-template-unclosed"),
+        TemplateError::Extension => fl!("settings-filename-template-extension"),
+        TemplateError::Hidden => fl!("settings-filename-template-hidden"),
+    }
+}
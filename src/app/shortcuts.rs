@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Keyboard shortcuts for core actions
+//!
+//! Every action has a default key, and can be rebound in settings. Keys are
+//! stored by name ("Space", "F", "F5") so the config stays readable. Only key
+//! presses no widget has captured reach the table, so typing into a text
+//! input (such as the forced caps field in insights) never triggers a
+//! shortcut. Presses with Ctrl, Alt or Super held are left to the toolkit;
+//! Shift makes the exposure and gain shortcuts take coarse steps.
+
+use crate::app::exposure_picker::NudgeTarget;
+use crate::app::state::{ContextPage, Message};
+use crate::config::{Config, ShortcutAction};
+use cosmic::iced::keyboard::{Key, Modifiers, key::Named};
+use std::collections::HashMap;
+
+/// Key that cancels rebinding and can't be bound itself
+pub const CANCEL_KEY: &str = "Escape";
+
+/// Name of a key as stored in the config
+///
+/// Characters are upper-cased so Shift doesn't change the binding.
+pub fn key_name(key: &Key) -> Option<String> {
+    match key {
+        Key::Character(c) => Some(c.to_uppercase()),
+        Key::Named(named) => Some(format!("{named:?}")),
+        Key::Unidentified => None,
+    }
+}
+
+/// Whether a press with these modifiers may trigger a shortcut
+pub fn plain_press(modifiers: Modifiers) -> bool {
+    !(modifiers.control() || modifiers.alt() || modifiers.logo())
+}
+
+/// Key bound to an action
+pub fn bound_key(bindings: &HashMap<ShortcutAction, String>, action: ShortcutAction) -> &str {
+    bindings
+        .get(&action)
+        .map(String::as_str)
+        .unwrap_or(action.default_key())
+}
+
+/// Action bound to a key
+pub fn lookup(bindings: &HashMap<ShortcutAction, String>, key: &str) -> Option<ShortcutAction> {
+    ShortcutAction::ALL
+        .into_iter()
+        .find(|action| bound_key(bindings, *action) == key)
+}
+
+/// Bind `key` to `action`
+///
+/// An action already using the key takes over `action`'s previous key, so
+/// no two actions ever share a key.
+pub fn rebind(bindings: &mut HashMap<ShortcutAction, String>, action: ShortcutAction, key: String) {
+    let previous = bound_key(bindings, action).to_string();
+    if let Some(other) = lookup(bindings, &key)
+        && other != action
+    {
+        bindings.insert(other, previous);
+    }
+    bindings.insert(action, key);
+    // Keep only the keys that differ from the defaults
+    bindings.retain(|action, key| key != action.default_key());
+}
+
+/// Message sent when an action's key is pressed
+///
+/// `steps` is how far the exposure and gain actions move their control. A
+/// photo burst uses the configured count and interval.
+pub fn message(action: ShortcutAction, steps: i32, config: &Config) -> Message {
+    match action {
+        ShortcutAction::Snapshot => Message::Capture,
+        ShortcutAction::CaptureBurst => Message::CaptureBurst {
+            count: config.still_burst_count,
+            interval: std::time::Duration::from_millis(config.still_burst_interval_ms),
+        },
+        ShortcutAction::ToggleFreeze => Message::ToggleFreezePreview,
+        ShortcutAction::ToggleInsights => Message::ToggleContextPage(ContextPage::Insights),
+        ShortcutAction::ToggleRecording => Message::ToggleRecording,
+        ShortcutAction::CycleOverlay => Message::CyclePreviewOverlay,
+        ShortcutAction::CycleResolution => Message::CycleResolution,
+        ShortcutAction::CopyFrame => Message::CopyFrameToClipboard,
+        ShortcutAction::ExposureDown => Message::NudgeControl(NudgeTarget::ExposureTime, -steps),
+        ShortcutAction::ExposureUp => Message::NudgeControl(NudgeTarget::ExposureTime, steps),
+        ShortcutAction::GainDown => Message::NudgeControl(NudgeTarget::Gain, -steps),
+        ShortcutAction::GainUp => Message::NudgeControl(NudgeTarget::Gain, steps),
+    }
+}
+
+/// Whether a key is the one that cancels rebinding
+pub fn is_cancel(key: &Key) -> bool {
+    matches!(key, Key::Named(Named::Escape))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_and_key_names() {
+        let bindings = HashMap::new();
+        let space = key_name(&Key::Named(Named::Space)).unwrap();
+        assert_eq!(lookup(&bindings, &space), Some(ShortcutAction::Snapshot));
+        // Shift+f and f are the same binding
+        let f = key_name(&Key::Character("f".into())).unwrap();
+        assert_eq!(f, "F");
+        assert_eq!(lookup(&bindings, &f), Some(ShortcutAction::ToggleFreeze));
+        let bracket = key_name(&Key::Character("]".into())).unwrap();
+        assert_eq!(
+            lookup(&bindings, &bracket),
+            Some(ShortcutAction::ExposureUp)
+        );
+        assert_eq!(
+            key_name(&Key::Named(Named::Escape)).as_deref(),
+            Some(CANCEL_KEY)
+        );
+    }
+
+    #[test]
+    fn test_rebinding_to_a_used_key_swaps() {
+        let mut bindings = HashMap::new();
+        rebind(
+            &mut bindings,
+            ShortcutAction::ToggleRecording,
+            "F".to_string(),
+        );
+        assert_eq!(bound_key(&bindings, ShortcutAction::ToggleRecording), "F");
+        assert_eq!(bound_key(&bindings, ShortcutAction::ToggleFreeze), "R");
+
+        // Rebinding back to the defaults leaves nothing to store
+        rebind(
+            &mut bindings,
+            ShortcutAction::ToggleRecording,
+            "R".to_string(),
+        );
+        assert!(bindings.is_empty());
+    }
+}
@@ -4,6 +4,7 @@
 
 use crate::app::exposure_picker::{
     AvailableExposureControls, ColorSettings, ExposureMode, ExposureSettings, MeteringMode,
+    NudgeTarget, PowerLineFrequency,
 };
 use crate::app::frame_processor::QrDetection;
 use crate::backends::audio::AudioDevice;
@@ -80,6 +81,26 @@ impl RecordingState {
     }
 }
 
+/// Progress of the shutdown started by closing the window
+#[derive(Debug, Default)]
+pub struct ShutdownState {
+    /// The active recording is still being finalized
+    pub recording: bool,
+    /// The virtual camera is still stopping
+    pub virtual_camera: bool,
+    /// The camera is being released, the last step before exit
+    pub releasing: bool,
+    /// Problems to show before the app exits
+    pub errors: Vec<String>,
+}
+
+impl ShutdownState {
+    /// Whether the camera can be released (nothing left to stop first)
+    pub fn is_ready_to_release(&self) -> bool {
+        !self.recording && !self.virtual_camera && !self.releasing
+    }
+}
+
 /// Virtual camera streaming state machine
 #[derive(Default)]
 pub enum VirtualCameraState {
@@ -512,6 +533,10 @@ pub struct AppModel {
     pub mode: CameraMode,
     /// Recording state (idle, recording, or paused)
     pub recording: RecordingState,
+    /// Output file for the next recording (set by the control socket)
+    pub recording_path_override: Option<std::path::PathBuf>,
+    /// Summary of the recording that just ended, shown until dismissed
+    pub recording_summary: Option<crate::pipelines::video::RecordingSummary>,
     /// Virtual camera state (idle or streaming)
     pub virtual_camera: VirtualCameraState,
     /// File source for virtual camera (image or video to stream instead of camera)
@@ -570,6 +595,8 @@ pub struct AppModel {
     pub theatre: TheatreState,
     /// Burst mode state (enabled, capture/processing progress)
     pub burst_mode: BurstModeState,
+    /// Still burst in progress (several photos from one trigger)
+    pub still_burst: Option<crate::app::still_burst::StillBurst>,
     /// Auto-detected frame count based on current scene brightness (1-8)
     /// Updated every 1 second when in Auto mode via BrightnessEvaluationTick
     pub auto_detected_frame_count: usize,
@@ -600,26 +627,54 @@ pub struct AppModel {
     pub gallery_thumbnail_rgba: Option<(Arc<Vec<u8>>, u32, u32)>,
     /// Currently selected resolution in the picker (width for grouping)
     pub picker_selected_resolution: Option<u32>,
+    /// Resolution the cycle shortcut is heading for, applied once presses settle
+    pub pending_cycle_resolution: Option<(u32, u32)>,
+    /// Bumped on every cycle press so only the last one is applied
+    pub resolution_cycle_generation: u64,
     /// Camera backend manager (PipeWire)
     pub backend_manager: Option<CameraBackendManager>,
     /// Flag to cancel camera subscription (used when switching backends/cameras)
     pub camera_cancel_flag: std::sync::Arc<std::sync::atomic::AtomicBool>,
     /// Counter to force camera stream restart (incremented to change subscription ID)
     pub camera_stream_restart_counter: u32,
+    /// Set once the window was closed; the app exits when shutdown completes
+    pub shutdown: Option<ShutdownState>,
+    /// Short notifications shown over the preview (e.g. frame copied)
+    pub toasts: cosmic::widget::Toasts<Message>,
     /// Current camera frame
     pub current_frame: Option<Arc<CameraFrame>>,
+    /// Detects frames identical to the current one (when skipping them is enabled)
+    pub frame_dedup: crate::app::frame_dedup::FrameDedup,
+    /// Detects cameras that only deliver black frames (e.g. disabled by a privacy switch)
+    pub black_frames: crate::app::black_frames::BlackFrameDetector,
+    /// Mean luma tracking for switching to a lower frame rate in low light
+    pub low_light: crate::app::low_light::LowLightMonitor,
+    /// Start failures per mode, for falling back to a lower resolution
+    pub safe_resolution: crate::app::safe_resolution::SafeResolution,
+    /// Pixel value readout under the cursor
+    pub pixel_probe: crate::app::pixel_probe::PixelProbe,
+    /// Text of the frame counter overlay, rewritten in place for each frame
+    pub frame_counter_label: String,
     /// Available camera devices
     pub available_cameras: Vec<CameraDevice>,
     /// Current camera index
     pub current_camera_index: usize,
     /// Available formats for current camera
     pub available_formats: Vec<CameraFormat>,
+    /// V4L2 controls probed together with the formats
+    pub device_controls: crate::backends::camera::v4l2_controls::DeviceControls,
     /// Currently active format being used by camera
     pub active_format: Option<CameraFormat>,
+    /// Camera path and format of the pipeline that delivered frames last
+    pub pipeline_source: Option<(String, Option<CameraFormat>)>,
     /// Available audio input devices
     pub available_audio_devices: Vec<AudioDevice>,
     /// Current audio device index
     pub current_audio_device_index: usize,
+    /// Whether recorded audio is muted
+    pub audio_muted: bool,
+    /// Input level of the selected microphone (0.0-1.0, for the settings meter)
+    pub audio_level: f32,
     /// Available video encoders
     pub available_video_encoders: Vec<EncoderInfo>,
     /// Current video encoder index
@@ -643,12 +698,106 @@ pub struct AppModel {
     pub burst_mode_merge_dropdown_options: Vec<String>,
     /// Burst mode frame count dropdown options (Auto, 4, 6, 8 frames)
     pub burst_mode_frame_count_dropdown_options: Vec<String>,
+    /// Still burst photo count dropdown options
+    pub still_burst_count_dropdown_options: Vec<String>,
+    /// Still burst interval dropdown options
+    pub still_burst_interval_dropdown_options: Vec<String>,
     /// Photo output format dropdown options (JPEG, PNG, DNG)
     pub photo_output_format_dropdown_options: Vec<String>,
     /// Audio encoder dropdown options (Opus, AAC)
     pub audio_encoder_dropdown_options: Vec<String>,
+    /// Present mode dropdown options (Fifo, Fifo relaxed, Mailbox, Immediate)
+    pub present_mode_dropdown_options: Vec<String>,
+    /// Metrics smoothing dropdown options (Off, Light, Medium, Heavy)
+    pub metrics_smoothing_dropdown_options: Vec<String>,
+    /// Frame drop policy dropdown options (Drop newest, Drop oldest, Skip to latest)
+    pub frame_drop_policy_dropdown_options: Vec<String>,
+    /// Appsink max-buffers dropdown options (Automatic, 1, 2, ...)
+    pub appsink_buffers_dropdown_options: Vec<String>,
+    /// Retro buffer duration dropdown options (Off, 5 s, 10 s, ...)
+    pub retro_buffer_dropdown_options: Vec<String>,
+    /// Recording segment length dropdown options (Off, 5 min, ...)
+    pub recording_split_duration_dropdown_options: Vec<String>,
+    /// Recording segment size dropdown options (Off, 500 MB, ...)
+    pub recording_split_size_dropdown_options: Vec<String>,
+    /// Stall watchdog threshold dropdown options (Off, 3 s, 5 s, ...)
+    pub stall_watchdog_dropdown_options: Vec<String>,
+    /// Camera start timeout dropdown options (5 s, 10 s, ...)
+    pub camera_open_timeout_dropdown_options: Vec<String>,
+    /// Background preview dropdown options (Keep running, Lower frame rate, Pause)
+    pub background_preview_dropdown_options: Vec<String>,
+    /// Keep awake dropdown options (Always, Only while recording, Never)
+    pub keep_awake_dropdown_options: Vec<String>,
+    /// Framerate cap dropdown options
+    pub framerate_cap_dropdown_options: Vec<String>,
+    /// MJPEG stream viewer limit dropdown options
+    pub http_stream_max_clients_dropdown_options: Vec<String>,
+    /// Whether the main window has keyboard focus
+    pub window_focused: bool,
+    /// Whether the present mode still waits for the first frame to confirm it
+    pub present_mode_pending: bool,
+    /// Deinterlace method dropdown options (Off, Bob, Linear, Yadif)
+    pub deinterlace_dropdown_options: Vec<String>,
+    /// Capture path dropdown options (PipeWire, then the V4L2 io-modes)
+    pub v4l2_io_mode_dropdown_options: Vec<String>,
+    /// Preview resolution cap dropdown options
+    pub preview_cap_dropdown_options: Vec<String>,
+    /// Power preference dropdown options (High performance, Low power)
+    pub power_preference_dropdown_options: Vec<String>,
+    /// Low-light mode dropdown options (Off, Suggest, Switch automatically)
+    pub low_light_dropdown_options: Vec<String>,
+    /// DRM render nodes available for VA-API decoding
+    pub available_render_nodes: Vec<crate::media::decoders::RenderNode>,
+    /// VA-API render node dropdown options ("Default" followed by each node)
+    pub render_node_dropdown_options: Vec<String>,
+    /// Rotation override dropdown options (Automatic, 0°, 90°, 180°, 270°)
+    pub rotation_override_dropdown_options: Vec<String>,
+    /// Color effect dropdown options (None, Grayscale, Sepia, ...)
+    pub color_effect_dropdown_options: Vec<String>,
+    /// Scaling quality dropdown options (Nearest, Linear, Bicubic)
+    pub scaling_quality_dropdown_options: Vec<String>,
+    /// Watermark position dropdown options (Top left, Top right, ...)
+    pub watermark_position_dropdown_options: Vec<String>,
+    /// Camera facing dropdown options (Automatic, Front, Rear, External)
+    pub camera_facing_dropdown_options: Vec<String>,
+    /// Camera stack dropdown options (libcamera, V4L2)
+    pub camera_source_dropdown_options: Vec<String>,
+    /// Extra preview rotation from the accelerometer on convertibles
+    pub device_orientation: crate::backends::camera::types::SensorRotation,
     /// Whether the device info panel is visible
     pub device_info_visible: bool,
+    /// Action waiting for a key press to be bound to
+    pub rebinding_shortcut: Option<crate::config::ShortcutAction>,
+    /// Whether the reset settings confirmation is shown
+    pub reset_settings_dialog: bool,
+    /// Coarse nudge step dropdown options (5 steps, 10 steps, ...)
+    pub nudge_coarse_steps_dropdown_options: Vec<String>,
+    /// Value shown on the preview after an exposure or gain shortcut, and when
+    pub control_nudge: Option<(Instant, String)>,
+    /// Whether the advanced section of the insights drawer is expanded
+    pub insights_advanced_visible: bool,
+    /// Caps string typed into the forced-caps field
+    pub forced_caps_input: String,
+    /// Port typed into the MJPEG stream port field
+    pub http_stream_port_input: String,
+    /// URL the MJPEG stream is served at, or why it couldn't listen (None = not started)
+    pub http_stream_status: Option<Result<String, String>>,
+    /// Whether a frame for MJPEG viewers is being encoded
+    pub http_stream_encoding: bool,
+    /// When the last frame for MJPEG viewers was started
+    pub http_stream_last_frame: Option<Instant>,
+    /// Why the typed caps were rejected
+    pub forced_caps_error: Option<String>,
+    /// Name typed for saving the current settings as a capture profile
+    pub profile_name_input: String,
+    /// Filename template as typed in the settings (may be invalid)
+    pub filename_template_input: String,
+    /// Why the typed filename template was rejected
+    pub filename_template_error: Option<crate::pipelines::filename::TemplateError>,
+    /// Cached picker thumbnails by device path (None = busy or unavailable, show placeholder)
+    pub device_thumbnails: std::collections::HashMap<String, Option<cosmic::widget::image::Handle>>,
+    /// Whether device thumbnails are currently being captured
+    pub device_thumbnails_loading: bool,
 
     /// Transition state for camera/settings changes
     pub transition_state: TransitionState,
@@ -664,6 +813,14 @@ pub struct AppModel {
     // ===== Privacy Cover Detection =====
     /// Whether the camera privacy cover is closed (blocking the camera)
     pub privacy_cover_closed: bool,
+    /// Why the preview pipeline failed to start (cleared by the next frame)
+    pub pipeline_error: Option<crate::media::decoders::PipelineError>,
+    /// The failed format's codec has no decoder installed, and the closest
+    /// format that can be decoded instead (cleared with `pipeline_error`)
+    pub missing_decoder: Option<(
+        crate::media::decoders::MissingDecoder,
+        Option<crate::backends::camera::types::CameraFormat>,
+    )>,
 
     // ===== Insights Drawer =====
     /// Insights drawer diagnostic state
@@ -959,7 +1116,7 @@ impl PhotoAspectRatio {
 }
 
 /// Filter types for camera preview
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub enum FilterType {
     /// No filter applied (displays as "ORIGINAL")
     #[default]
@@ -1061,6 +1218,17 @@ pub enum Message {
     TheatreHideUI,
     /// Toggle device info panel visibility
     ToggleDeviceInfo,
+    /// Key press no widget captured (shortcuts and rebinding)
+    KeyPressed(
+        cosmic::iced::keyboard::Key,
+        cosmic::iced::keyboard::Modifiers,
+    ),
+    /// Wait for the next key press to bind to an action
+    StartRebindShortcut(crate::config::ShortcutAction),
+    /// Restore the default keyboard shortcuts
+    ResetShortcuts,
+    /// Cycle preview overlays: off -> QR detection -> false color -> off
+    CyclePreviewOverlay,
 
     // ===== Tools Menu =====
     /// Toggle tools menu visibility
@@ -1083,10 +1251,16 @@ pub enum Message {
     SetExposureTime(i32),
     /// Set gain value
     SetGain(i32),
+    /// Move a manual control by a number of its steps (negative = down)
+    NudgeControl(NudgeTarget, i32),
+    /// Hide the nudge indicator if it still shows the nudge made at this time
+    HideControlNudge(Instant),
     /// Set ISO sensitivity
     SetIsoSensitivity(i32),
     /// Set metering mode
     SetMeteringMode(MeteringMode),
+    /// Set the anti-flicker power line frequency (remembered across cameras)
+    SetPowerLineFrequency(PowerLineFrequency),
     /// Toggle auto exposure priority (allow frame rate variation)
     ToggleAutoExposurePriority,
     /// Exposure controls queried from camera (boxed to reduce enum size)
@@ -1110,6 +1284,16 @@ pub enum Message {
     /// Exposure mode selected via segmented button
     ExposureModeSelected(cosmic::widget::segmented_button::Entity),
 
+    // ===== Focus Controls =====
+    /// Enable or disable continuous autofocus
+    SetContinuousAutofocus(bool),
+    /// Run a single autofocus pass and lock the result
+    TriggerAutofocus,
+    /// Set manual focus position (only when continuous autofocus is off)
+    SetFocusAbsolute(i32),
+    /// Focus position read back from the camera after an autofocus change
+    FocusPositionRead(Option<i32>),
+
     // ===== Color Controls =====
     /// Toggle color picker visibility
     ToggleColorPicker,
@@ -1137,20 +1321,47 @@ pub enum Message {
     SelectCamera(usize),
     /// New camera frame received from pipeline
     CameraFrame(Arc<CameraFrame>),
+    /// The preview pipeline could not be started
+    PipelineFailed(crate::media::decoders::PipelineError),
     /// Cameras initialized asynchronously during startup
     CamerasInitialized(
         Vec<crate::backends::camera::types::CameraDevice>,
         usize,
-        Vec<crate::backends::camera::types::CameraFormat>,
+        crate::backends::camera::types::DeviceCapabilities,
     ),
+    /// Startup check of the GStreamer registry finished
+    RegistryChecked(Vec<crate::media::decoders::RegistryProblem>),
     /// Camera list changed (hotplug event)
-    CameraListChanged(Vec<crate::backends::camera::types::CameraDevice>),
+    DeviceListChanged(Vec<crate::backends::camera::types::CameraDevice>),
     /// Start camera transition (capture last frame and show blur)
     StartCameraTransition,
     /// Clear blur transition after delay
     ClearTransitionBlur,
     /// Toggle mirror preview (horizontal flip)
     ToggleMirrorPreview,
+    /// Select a manual preview rotation (or automatic)
+    SetRotationOverride(crate::config::RotationOverride),
+    /// Override the facing of the current camera (None = use the detected facing)
+    SetCameraFacing(Option<crate::backends::camera::types::CameraFacing>),
+    /// Use the current camera through libcamera or V4L2 (when offered through both)
+    SelectCameraSource(crate::backends::camera::types::CameraSourceKind),
+    /// Select the full-frame color effect for the preview
+    SetColorEffect(crate::config::ColorEffect),
+    /// Toggle applying the color effect to captured photos
+    ToggleColorEffectOnOutput,
+    /// Select the filter used to scale the preview
+    SetScalingQuality(crate::config::ScalingQuality),
+    /// The accelerometer reported a new device orientation
+    DeviceOrientationChanged(crate::backends::camera::types::SensorRotation),
+    /// Capture fresh picker thumbnails for all enumerated cameras
+    RefreshDeviceThumbnails,
+    /// Device thumbnails captured (device path, thumbnail or None when busy/unavailable)
+    DeviceThumbnailsLoaded(
+        Vec<(
+            String,
+            Option<crate::backends::camera::pipewire::DeviceThumbnail>,
+        )>,
+    ),
 
     // ===== Motor/PTZ Controls =====
     /// Toggle motor controls picker visibility
@@ -1183,6 +1394,10 @@ pub enum Message {
     PickerSelectResolution(u32),
     /// Select specific format in picker
     PickerSelectFormat(usize),
+    /// Step to the next resolution of the current pixel format (wrapping)
+    CycleResolution,
+    /// Apply the cycled resolution if no press came after this one
+    ApplyCycledResolution(u64),
     /// Select bitrate preset
     SelectBitratePreset(usize),
 
@@ -1205,6 +1420,19 @@ pub enum Message {
     PollBurstModeProgress,
     /// Reset burst mode state after completion/error
     ResetBurstModeState,
+    /// Save `count` photos `interval` apart from a single trigger (still burst)
+    CaptureBurst {
+        count: u32,
+        interval: std::time::Duration,
+    },
+    /// The wait after a still burst photo is over; the next frame is taken
+    BurstStillDue,
+    /// A still burst photo was saved (path or error)
+    BurstStillSaved(Result<String, String>),
+    /// Select the number of photos per still burst (index into the options)
+    SelectStillBurstCount(usize),
+    /// Select the time between still burst photos (index into the options)
+    SelectStillBurstInterval(usize),
     /// Periodic brightness evaluation tick (every 1 second in Auto mode)
     /// Updates auto_detected_frame_count based on scene brightness
     BrightnessEvaluationTick,
@@ -1234,12 +1462,34 @@ pub enum Message {
     ToggleRecording,
     /// Video recording started successfully
     RecordingStarted(String),
-    /// Video recording stopped successfully
-    RecordingStopped(Result<String, String>),
+    /// Video recording stopped (Err if it never got going)
+    RecordingStopped(Result<crate::pipelines::video::RecordingSummary, String>),
+    /// The window is closing: finalize recordings and release the camera, then exit
+    CloseRequested,
+    /// The recording didn't finalize in time during shutdown, exit anyway
+    ShutdownTimeout,
+    /// The camera pipeline was stopped on shutdown (Err if the device is still busy)
+    ShutdownCameraReleased(Result<(), String>),
+    /// Exit after a shutdown problem was shown
+    ShutdownExit,
+    /// Show the file of the last recording summary in the file manager
+    RevealRecording,
+    /// Hide the recording summary
+    DismissRecordingSummary,
     /// Update recording duration (every second)
     UpdateRecordingDuration,
     /// Start recording after camera is released
     StartRecordingAfterDelay,
+    /// Select how many seconds the retroactive clip buffer keeps (index into options)
+    SelectRetroBufferDuration(usize),
+    /// Select the longest a recording segment may run (index into options)
+    SelectRecordingSplitDuration(usize),
+    /// Select the largest a recording segment may grow (index into options)
+    SelectRecordingSplitSize(usize),
+    /// Save the buffered last N seconds to a clip
+    SaveRetroClip,
+    /// Retroactive clip was written (path) or failed (error)
+    RetroClipSaved(Result<String, String>),
 
     // ===== Virtual Camera =====
     /// Toggle virtual camera streaming (start/stop)
@@ -1291,20 +1541,120 @@ pub enum Message {
     UpdateConfig(Config),
     /// Set application theme (System, Dark, Light)
     SetAppTheme(usize),
+    /// Ask for confirmation before resetting every setting
+    RequestResetSettings,
+    /// Close the reset confirmation without changing anything
+    CancelResetSettings,
+    /// Reset every setting to its default and apply it to the running camera
+    ResetSettings,
     /// Select audio input device
-    SelectAudioDevice(usize),
+    SetAudioSource(usize),
+    /// Mute or unmute recorded audio
+    ToggleAudioMute,
+    /// Input level update from the microphone meter (0.0-1.0)
+    AudioLevel(f32),
     /// Select video encoder
     SelectVideoEncoder(usize),
     /// Select photo output format (JPEG, PNG, DNG)
     SelectPhotoOutputFormat(usize),
+    /// Set JPEG quality for still captures (1-100)
+    SetPhotoJpegQuality(u8),
+    /// Open a folder picker for the capture output directory
+    ChooseOutputDirectory,
+    /// Output directory picked (None = picker cancelled)
+    OutputDirectorySelected(Option<std::path::PathBuf>),
+    /// Save captures to the default Pictures/Videos folders again
+    ResetOutputDirectory,
+    /// Edit the filename template (saved whenever it is valid)
+    FilenameTemplateInput(String),
     /// Toggle recording audio with video
     ToggleRecordAudio,
     /// Select audio encoder (Opus, AAC)
     SelectAudioEncoder(usize),
     /// Toggle saving raw burst frames as DNG (debugging feature)
     ToggleSaveBurstRaw,
+    /// Toggle embedding capture metadata into saved stills
+    ToggleEmbedCaptureMetadata,
+    /// Toggle saving a still when the preview pipeline delivers its first frame
+    ToggleFirstFrameSnapshot,
+    /// The preview pipeline delivered its first frame (once per pipeline start)
+    PipelineFirstFrame,
+    /// Copy the current frame to the clipboard as an image
+    CopyFrameToClipboard,
+    /// Frame for the clipboard processed and encoded as PNG
+    FrameCopyEncoded(Result<Vec<u8>, String>),
+    /// Close a toast notification
+    CloseToast(cosmic::widget::ToastId),
+    /// Toggle burning the watermark into photos and recordings
+    ToggleWatermark,
+    /// Edit the watermark text
+    WatermarkTextInput(String),
+    /// Toggle the timestamp in the watermark
+    ToggleWatermarkTimestamp,
+    /// Set the corner the watermark is drawn in
+    SetWatermarkPosition(crate::config::WatermarkPosition),
+    /// Set the watermark text height (percent of the frame height)
+    SetWatermarkFontSize(u32),
+    /// Set the watermark opacity (percent)
+    SetWatermarkOpacity(u32),
+    /// Toggle decoder validation at startup (detects broken plugins)
+    ToggleValidateDecoders,
+    /// Toggle the local automation control socket
+    ToggleControlSocket,
+    /// Command received on the control socket
+    ControlCommand(crate::control_socket::ControlCommand),
+    /// Start or stop serving the preview as an MJPEG stream over HTTP
+    ToggleHttpStream,
+    /// Toggle accepting MJPEG viewers from other devices
+    ToggleHttpStreamAllInterfaces,
+    /// Port typed into the MJPEG stream port field
+    HttpStreamPortInput(String),
+    /// Listen on the typed port
+    ApplyHttpStreamPort,
+    /// Select the most MJPEG viewers served at once (index into the options)
+    SelectHttpStreamMaxClients(usize),
+    /// MJPEG stream started listening (URL) or failed to (error)
+    HttpStreamBound(Result<String, String>),
+    /// A frame for MJPEG viewers was encoded and published (or failed to)
+    HttpStreamFrameEncoded(Result<(), String>),
     /// Toggle virtual camera feature enabled
     ToggleVirtualCameraEnabled,
+    /// Toggle min/max auto-stretch for Y16 previews
+    ToggleGrayAutoStretch,
+    /// Toggle false-color rendering for grayscale previews
+    ToggleFalseColor,
+    /// Apply the capture profile with this name
+    ApplyProfile(String),
+    /// Save the current format and preview settings as a profile named after the input
+    SaveProfile,
+    /// Delete the capture profile with this name
+    DeleteProfile(String),
+    /// Profile name input changed
+    ProfileNameInput(String),
+    /// Toggle MJPEG error resilience for the current camera (restarts the preview)
+    ToggleMjpegResilience,
+    /// Select the deinterlacing method for interlaced sources (restarts the preview)
+    SetDeinterlaceMethod(crate::config::DeinterlaceMethod),
+    /// Capture through PipeWire or with a forced V4L2 io-mode (restarts the preview)
+    SetV4l2IoMode(crate::config::V4l2IoMode),
+    /// Cap the software decoder threads (None = automatic); a toggle applies at once
+    SetDecodeThreads(Option<u32>),
+    /// Apply the decoder thread cap after the slider is released (restarts the preview)
+    ApplyDecodeThreads,
+    /// Choose between low-power and high-performance decoding and GPU selection
+    SetPowerPreference(crate::config::PowerPreference),
+    /// Set the maximum preview resolution
+    SetPreviewResolutionCap(crate::config::PreviewResolutionCap),
+    /// Choose whether a dark scene lowers the frame rate
+    SetLowLightMode(crate::config::LowLightMode),
+    /// Periodic mean luma check for low-light switching (every 1 second)
+    LowLightTick,
+    /// Switch to the lower frame rate offered by a low-light toast
+    ApplyLowLightFormat(cosmic::widget::ToastId),
+    /// Go back to the mode the low-light switch replaced
+    RevertLowLightFormat(cosmic::widget::ToastId),
+    /// Select the VA-API render node (0 = default, otherwise index + 1 into the node list)
+    SelectRenderNode(usize),
 
     // ===== System & Recovery =====
     /// Camera backend recovery started
@@ -1346,12 +1696,110 @@ pub enum Message {
     // ===== Privacy Cover Detection =====
     /// Privacy cover status changed (true = cover closed/camera blocked)
     PrivacyCoverStatusChanged(bool),
+    /// Hide the hint shown when the camera only delivers black frames
+    DismissBlackFrameHint,
 
     // ===== Insights Drawer =====
     /// Update insights metrics from pipeline
     UpdateInsightsMetrics,
     /// Copy pipeline string to clipboard
     CopyPipelineString,
+    /// Copy the format/decoder change history to the clipboard
+    CopyFormatHistory,
+    /// Copy the camera's node, driver and USB identifiers to the clipboard
+    CopyDeviceIdentity,
+    /// Window frame callback (for display refresh rate estimation)
+    DisplayFrame(Instant),
+    /// Select the preview surface present mode (applied on next launch)
+    SetPresentMode(crate::config::PresentMode),
+    /// Select smoothing for the live metrics in the insights drawer
+    SetMetricsSmoothing(crate::config::MetricsSmoothing),
+    /// Select how frames are discarded when the preview falls behind
+    SetFrameDropPolicy(crate::backends::camera::FrameDropPolicy),
+    /// Select the preview appsink's max-buffers (0 = automatic)
+    SetAppsinkMaxBuffers(u32),
+    /// Toggle whether the preview appsink drops old frames when full
+    ToggleAppsinkDrop,
+    /// Toggle skipping the GPU upload of frames identical to the previous one
+    ToggleSkipIdenticalFrames,
+    /// Toggle the focus peaking overlay on the preview
+    ToggleFocusPeaking,
+    /// Toggle the pixel value readout under the cursor
+    TogglePixelProbe,
+    /// Toggle the frame number and PTS overlay on the preview
+    ToggleFrameCounterOverlay,
+    /// Toggle accumulating min/avg/max metrics over the pipeline session
+    ToggleSessionStats,
+    /// Start the session min/avg/max metrics over
+    ResetSessionStats,
+    /// Toggle raw microsecond values for the performance metrics (not persisted)
+    ToggleRawMicroseconds,
+    /// Frame pixel under the cursor changed (None = cursor left the frame)
+    PixelProbeHovered(Option<(u32, u32)>),
+    /// Lock the probe to a frame pixel, or unlock it if already locked
+    TogglePixelProbeLock((u32, u32)),
+    /// Select the stall watchdog threshold (index into the threshold options)
+    SelectStallWatchdog(usize),
+    /// Periodic check for a pipeline that stopped delivering frames
+    StallWatchdogTick,
+    /// Select how long a camera may take to deliver its first frame (index into the options)
+    SelectCameraOpenTimeout(usize),
+    /// Select what the preview does while the window is in the background
+    SetBackgroundPreview(crate::config::BackgroundPreview),
+    /// Select when the screen is kept awake
+    SetKeepAwake(crate::config::KeepAwake),
+    /// An idle inhibition for the reason was taken (or failed)
+    IdleInhibitChanged(
+        &'static str,
+        Result<crate::idle_inhibit::InhibitBackend, String>,
+    ),
+    /// Select the software frame rate cap (index into options)
+    SelectFramerateCap(usize),
+    /// Select how many steps a Shift+shortcut nudge moves (index into options)
+    SelectNudgeCoarseSteps(usize),
+    /// The window gained (true) or lost (false) focus
+    WindowVisibilityChanged(bool),
+    /// Measure the zero-copy and copy upload paths one after the other
+    StartUploadBenchmark,
+    /// Expand or collapse an element's properties in the pipeline inspector
+    ToggleInspectElement(String),
+    /// Show or hide the advanced section of the insights drawer
+    ToggleInsightsAdvanced,
+    /// Edit the forced-caps field
+    ForcedCapsInput(String),
+    /// Validate the forced caps and rebuild the pipeline with them
+    ApplyForcedCaps,
+    /// Remove the forced caps and return to automatic negotiation
+    ClearForcedCaps,
+    /// Decode the stream with a second decoder next to the selected one (`None` ends it)
+    CompareDecoder(Option<&'static str>),
+    /// Freeze or resume the live preview
+    ToggleFreezePreview,
+    /// Pin the current performance metrics for comparison, or clear the pin
+    TogglePinMetrics,
+    /// Advance a frozen preview by one frame
+    StepFrame,
+    /// Copy the diagnostics bundle to the clipboard
+    CopyDiagnosticsBundle,
+    /// Save the diagnostics bundle to a file
+    SaveDiagnosticsBundle,
+    /// Diagnostics bundle saved (path or error)
+    DiagnosticsBundleSaved(Result<String, String>),
+    /// Toggle including device paths/serials in the diagnostics bundle
+    ToggleDiagnosticsIncludeDeviceIds,
+    /// Save the live pipeline's element graph as a Graphviz file
+    ExportPipelineDot,
+    /// Pipeline graph written (.dot path and rendered PNG path, or error)
+    PipelineDotExported(Result<Option<(String, Option<String>)>, String>),
+    /// Dump the camera's encoded frames (before decoding) to `path` for `duration`
+    DumpRawStream {
+        path: std::path::PathBuf,
+        duration: std::time::Duration,
+    },
+    /// Dump the encoded frames next to the photos for the default duration
+    StartRawStreamDump,
+    /// Encoded stream dump finished
+    RawStreamDumped(Result<crate::backends::camera::pipewire::raw_dump::RawDump, String>),
 
     /// No-op message for async tasks that don't need a response
     Noop,
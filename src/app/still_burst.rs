@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Still burst capture
+//!
+//! A still burst saves a fixed number of photos at a fixed interval from a
+//! single trigger, each through the normal photo pipeline and filename
+//! template. Unlike HDR+ burst mode nothing is merged; every still is kept.
+//!
+//! When the interval has passed, the next frame that arrives is saved, so
+//! two stills never come from the same frame even when the camera delivers
+//! fewer frames than the burst asks for (the effective interval grows
+//! instead). While the preview is frozen no frames arrive and the burst
+//! waits.
+
+use std::time::{Duration, Instant};
+
+/// Progress of a running still burst
+#[derive(Debug, Clone, PartialEq)]
+pub struct StillBurst {
+    /// Stills to take
+    count: u32,
+    /// Time between two stills
+    interval: Duration,
+    /// Stills handed to the photo pipeline
+    taken: u32,
+    /// Stills saved to disk
+    saved: u32,
+    /// Stills that failed to save
+    failed: u32,
+    /// The interval has passed and the next frame should be taken
+    due: bool,
+    /// Capture time of the frame the last still came from
+    last_frame: Option<Instant>,
+}
+
+impl StillBurst {
+    /// Start a burst; the first still is taken from the next frame
+    pub fn new(count: u32, interval: Duration) -> Self {
+        Self {
+            count: count.max(1),
+            interval,
+            taken: 0,
+            saved: 0,
+            failed: 0,
+            due: true,
+            last_frame: None,
+        }
+    }
+
+    /// Whether a frame captured at `captured_at` should become the next still
+    pub fn wants(&self, captured_at: Instant) -> bool {
+        self.due && self.last_frame.is_none_or(|last| captured_at > last)
+    }
+
+    /// Note that the frame captured at `captured_at` was taken
+    ///
+    /// Returns how long to wait before the next still, or `None` after the last.
+    pub fn take(&mut self, captured_at: Instant) -> Option<Duration> {
+        self.due = false;
+        self.last_frame = Some(captured_at);
+        self.taken += 1;
+        (self.taken < self.count).then_some(self.interval)
+    }
+
+    /// The interval after a still has passed
+    pub fn arm(&mut self) {
+        if self.taken < self.count {
+            self.due = true;
+        }
+    }
+
+    /// Note the outcome of saving a still
+    pub fn record_saved(&mut self, saved: bool) {
+        if saved {
+            self.saved += 1;
+        } else {
+            self.failed += 1;
+        }
+    }
+
+    /// Whether every still has been taken and saved (or failed to)
+    pub fn is_finished(&self) -> bool {
+        self.saved + self.failed >= self.count
+    }
+
+    /// Stills taken so far
+    pub fn taken(&self) -> u32 {
+        self.taken
+    }
+
+    /// Stills the burst takes in total
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Stills saved so far
+    pub fn saved(&self) -> u32 {
+        self.saved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_takes_distinct_frames() {
+        let start = Instant::now();
+        let mut burst = StillBurst::new(3, Duration::from_millis(200));
+        assert!(burst.wants(start));
+        assert_eq!(burst.take(start), Some(Duration::from_millis(200)));
+
+        // Nothing is taken until the interval has passed
+        assert!(!burst.wants(start + Duration::from_millis(33)));
+        burst.arm();
+        // The frame already saved doesn't count as the next one
+        assert!(!burst.wants(start));
+        let second = start + Duration::from_millis(233);
+        assert!(burst.wants(second));
+        assert_eq!(burst.take(second), Some(Duration::from_millis(200)));
+
+        burst.arm();
+        assert_eq!(burst.take(second + Duration::from_millis(200)), None);
+        // No more stills after the last one
+        burst.arm();
+        assert!(!burst.wants(second + Duration::from_millis(400)));
+        assert_eq!(burst.taken(), 3);
+
+        burst.record_saved(true);
+        burst.record_saved(false);
+        assert!(!burst.is_finished());
+        burst.record_saved(true);
+        assert!(burst.is_finished());
+        assert_eq!(burst.saved(), 2);
+    }
+}
@@ -37,6 +37,10 @@ impl AppModel {
             Message::TheatreShowUI => self.handle_theatre_show_ui(),
             Message::TheatreHideUI => self.handle_theatre_hide_ui(),
             Message::ToggleDeviceInfo => self.handle_toggle_device_info(),
+            Message::KeyPressed(key, modifiers) => self.handle_key_pressed(key, modifiers),
+            Message::StartRebindShortcut(action) => self.handle_start_rebind_shortcut(action),
+            Message::ResetShortcuts => self.handle_reset_shortcuts(),
+            Message::CyclePreviewOverlay => self.handle_cycle_preview_overlay(),
 
             // ===== Tools Menu =====
             Message::ToggleToolsMenu => self.handle_toggle_tools_menu(),
@@ -82,8 +86,13 @@ impl AppModel {
             Message::ResetExposureCompensation => self.handle_reset_exposure_compensation(),
             Message::SetExposureTime(value) => self.handle_set_exposure_time(value),
             Message::SetGain(value) => self.handle_set_gain(value),
+            Message::NudgeControl(target, steps) => self.handle_nudge_control(target, steps),
+            Message::HideControlNudge(shown_at) => self.handle_hide_control_nudge(shown_at),
             Message::SetIsoSensitivity(value) => self.handle_set_iso_sensitivity(value),
             Message::SetMeteringMode(mode) => self.handle_set_metering_mode(mode),
+            Message::SetPowerLineFrequency(frequency) => {
+                self.handle_set_power_line_frequency(frequency)
+            }
             Message::ToggleAutoExposurePriority => self.handle_toggle_auto_exposure_priority(),
             Message::ExposureControlsQueried(controls, settings, color_settings) => {
                 self.handle_exposure_controls_queried(controls, settings, color_settings)
@@ -116,6 +125,14 @@ impl AppModel {
             Message::ResetExposureSettings => self.handle_reset_exposure_settings(),
             Message::ExposureModeSelected(entity) => self.handle_exposure_mode_selected(entity),
 
+            // ===== Focus Controls =====
+            Message::SetContinuousAutofocus(enabled) => {
+                self.handle_set_continuous_autofocus(enabled)
+            }
+            Message::TriggerAutofocus => self.handle_trigger_autofocus(),
+            Message::SetFocusAbsolute(value) => self.handle_set_focus_absolute(value),
+            Message::FocusPositionRead(position) => self.handle_focus_position_read(position),
+
             // ===== Color Controls =====
             Message::ToggleColorPicker => self.handle_toggle_color_picker(),
             Message::CloseColorPicker => self.handle_close_color_picker(),
@@ -132,14 +149,31 @@ impl AppModel {
             // ===== Camera Control =====
             Message::SwitchCamera => self.handle_switch_camera(),
             Message::SelectCamera(index) => self.handle_select_camera(index),
-            Message::CameraFrame(frame) => self.handle_camera_frame(frame),
-            Message::CamerasInitialized(cameras, index, formats) => {
-                self.handle_cameras_initialized(cameras, index, formats)
+            Message::CameraFrame(frame) => {
+                let http_stream = self.encode_http_stream_frame(&frame);
+                Task::batch([http_stream, self.handle_camera_frame(frame)])
             }
-            Message::CameraListChanged(cameras) => self.handle_camera_list_changed(cameras),
+            Message::PipelineFailed(err) => self.handle_pipeline_failed(err),
+            Message::CamerasInitialized(cameras, index, capabilities) => {
+                self.handle_cameras_initialized(cameras, index, capabilities)
+            }
+            Message::DeviceListChanged(cameras) => self.handle_device_list_changed(cameras),
             Message::StartCameraTransition => self.handle_start_camera_transition(),
             Message::ClearTransitionBlur => self.handle_clear_transition_blur(),
             Message::ToggleMirrorPreview => self.handle_toggle_mirror_preview(),
+            Message::SetRotationOverride(rotation) => self.handle_set_rotation_override(rotation),
+            Message::SetCameraFacing(facing) => self.handle_set_camera_facing(facing),
+            Message::SelectCameraSource(kind) => self.handle_select_camera_source(kind),
+            Message::SetColorEffect(effect) => self.handle_set_color_effect(effect),
+            Message::ToggleColorEffectOnOutput => self.handle_toggle_color_effect_on_output(),
+            Message::SetScalingQuality(quality) => self.handle_set_scaling_quality(quality),
+            Message::DeviceOrientationChanged(rotation) => {
+                self.handle_device_orientation_changed(rotation)
+            }
+            Message::RefreshDeviceThumbnails => self.handle_refresh_device_thumbnails(),
+            Message::DeviceThumbnailsLoaded(thumbnails) => {
+                self.handle_device_thumbnails_loaded(thumbnails)
+            }
             Message::ToggleVirtualCameraEnabled => self.handle_toggle_virtual_camera_enabled(),
 
             // ===== Format Selection =====
@@ -151,6 +185,10 @@ impl AppModel {
             Message::SelectCodec(codec) => self.handle_select_codec(codec),
             Message::PickerSelectResolution(width) => self.handle_picker_select_resolution(width),
             Message::PickerSelectFormat(index) => self.handle_picker_select_format(index),
+            Message::CycleResolution => self.handle_cycle_resolution(),
+            Message::ApplyCycledResolution(generation) => {
+                self.handle_apply_cycled_resolution(generation)
+            }
             Message::SelectBitratePreset(index) => self.handle_select_bitrate_preset(index),
 
             // ===== Capture Operations =====
@@ -169,6 +207,13 @@ impl AppModel {
                 debug!("Burst mode state reset");
                 Task::none()
             }
+            Message::CaptureBurst { count, interval } => self.handle_capture_burst(count, interval),
+            Message::BurstStillDue => self.handle_burst_still_due(),
+            Message::BurstStillSaved(result) => self.handle_burst_still_saved(result),
+            Message::SelectStillBurstCount(index) => self.handle_select_still_burst_count(index),
+            Message::SelectStillBurstInterval(index) => {
+                self.handle_select_still_burst_interval(index)
+            }
             Message::BrightnessEvaluationTick => self.handle_brightness_evaluation_tick(),
             Message::CyclePhotoAspectRatio => self.handle_cycle_photo_aspect_ratio(),
             Message::FlashComplete => self.handle_flash_complete(),
@@ -184,8 +229,28 @@ impl AppModel {
             Message::ToggleRecording => self.handle_toggle_recording(),
             Message::RecordingStarted(path) => self.handle_recording_started(path),
             Message::RecordingStopped(result) => self.handle_recording_stopped(result),
+            Message::CloseRequested => self.handle_close_requested(),
+            Message::ShutdownTimeout => self.handle_shutdown_timeout(),
+            Message::ShutdownCameraReleased(result) => self.handle_shutdown_camera_released(result),
+            Message::ShutdownExit => cosmic::iced::exit(),
+            Message::RevealRecording => self.handle_reveal_recording(),
+            Message::DismissRecordingSummary => {
+                self.recording_summary = None;
+                Task::none()
+            }
             Message::UpdateRecordingDuration => self.handle_update_recording_duration(),
             Message::StartRecordingAfterDelay => self.handle_start_recording_after_delay(),
+            Message::SelectRetroBufferDuration(index) => {
+                self.handle_select_retro_buffer_duration(index)
+            }
+            Message::SelectRecordingSplitDuration(index) => {
+                self.handle_select_recording_split_duration(index)
+            }
+            Message::SelectRecordingSplitSize(index) => {
+                self.handle_select_recording_split_size(index)
+            }
+            Message::SaveRetroClip => self.handle_save_retro_clip(),
+            Message::RetroClipSaved(result) => self.handle_retro_clip_saved(result),
 
             // ===== Virtual Camera =====
             Message::ToggleVirtualCamera => self.handle_toggle_virtual_camera(),
@@ -223,14 +288,98 @@ impl AppModel {
             // ===== Settings =====
             Message::UpdateConfig(config) => self.handle_update_config(config),
             Message::SetAppTheme(index) => self.handle_set_app_theme(index),
-            Message::SelectAudioDevice(index) => self.handle_select_audio_device(index),
+            Message::RequestResetSettings => {
+                self.reset_settings_dialog = true;
+                Task::none()
+            }
+            Message::CancelResetSettings => {
+                self.reset_settings_dialog = false;
+                Task::none()
+            }
+            Message::ResetSettings => self.handle_reset_settings(),
+            Message::SetAudioSource(index) => self.handle_set_audio_source(index),
+            Message::ToggleAudioMute => self.handle_toggle_audio_mute(),
+            Message::AudioLevel(level) => {
+                self.audio_level = level;
+                Task::none()
+            }
             Message::SelectVideoEncoder(index) => self.handle_select_video_encoder(index),
             Message::SelectPhotoOutputFormat(index) => {
                 self.handle_select_photo_output_format(index)
             }
+            Message::SetPhotoJpegQuality(quality) => self.handle_set_photo_jpeg_quality(quality),
+            Message::ChooseOutputDirectory => self.handle_choose_output_directory(),
+            Message::OutputDirectorySelected(dir) => self.handle_output_directory_selected(dir),
+            Message::ResetOutputDirectory => self.set_output_directory(None),
+            Message::FilenameTemplateInput(text) => self.handle_filename_template_input(text),
             Message::ToggleRecordAudio => self.handle_toggle_record_audio(),
             Message::SelectAudioEncoder(index) => self.handle_select_audio_encoder(index),
             Message::ToggleSaveBurstRaw => self.handle_toggle_save_burst_raw(),
+            Message::ToggleEmbedCaptureMetadata => self.handle_toggle_embed_capture_metadata(),
+            Message::ToggleFirstFrameSnapshot => self.handle_toggle_first_frame_snapshot(),
+            Message::PipelineFirstFrame => self.handle_pipeline_first_frame(),
+            Message::CopyFrameToClipboard => self.handle_copy_frame_to_clipboard(),
+            Message::FrameCopyEncoded(result) => self.handle_frame_copy_encoded(result),
+            Message::RegistryChecked(problems) => self.handle_registry_checked(problems),
+            Message::CloseToast(id) => {
+                self.toasts.remove(id);
+                Task::none()
+            }
+            Message::ToggleWatermark => self.update_watermark(|watermark| {
+                watermark.enabled = !watermark.enabled;
+            }),
+            Message::WatermarkTextInput(text) => {
+                self.update_watermark(|watermark| watermark.text = text)
+            }
+            Message::ToggleWatermarkTimestamp => self.update_watermark(|watermark| {
+                watermark.timestamp = !watermark.timestamp;
+            }),
+            Message::SetWatermarkPosition(position) => {
+                self.update_watermark(|watermark| watermark.position = position)
+            }
+            Message::SetWatermarkFontSize(percent) => {
+                self.update_watermark(|watermark| watermark.font_size_percent = percent)
+            }
+            Message::SetWatermarkOpacity(percent) => {
+                self.update_watermark(|watermark| watermark.opacity_percent = percent)
+            }
+            Message::ToggleValidateDecoders => self.handle_toggle_validate_decoders(),
+            Message::ToggleControlSocket => self.handle_toggle_control_socket(),
+            Message::ControlCommand(command) => self.handle_control_command(command),
+            Message::ToggleHttpStream => self.handle_toggle_http_stream(),
+            Message::ToggleHttpStreamAllInterfaces => {
+                self.handle_toggle_http_stream_all_interfaces()
+            }
+            Message::HttpStreamPortInput(text) => {
+                self.http_stream_port_input = text;
+                Task::none()
+            }
+            Message::ApplyHttpStreamPort => self.handle_apply_http_stream_port(),
+            Message::SelectHttpStreamMaxClients(index) => {
+                self.handle_select_http_stream_max_clients(index)
+            }
+            Message::HttpStreamBound(result) => self.handle_http_stream_bound(result),
+            Message::HttpStreamFrameEncoded(result) => {
+                self.handle_http_stream_frame_encoded(result)
+            }
+            Message::ToggleGrayAutoStretch => self.handle_toggle_gray_auto_stretch(),
+            Message::ToggleFalseColor => self.handle_toggle_false_color(),
+            Message::ToggleMjpegResilience => self.handle_toggle_mjpeg_resilience(),
+            Message::ApplyProfile(name) => self.handle_apply_profile(name),
+            Message::SaveProfile => self.handle_save_profile(),
+            Message::DeleteProfile(name) => self.handle_delete_profile(name),
+            Message::ProfileNameInput(text) => self.handle_profile_name_input(text),
+            Message::SetDeinterlaceMethod(method) => self.handle_set_deinterlace_method(method),
+            Message::SetV4l2IoMode(mode) => self.handle_set_v4l2_io_mode(mode),
+            Message::SetDecodeThreads(threads) => self.handle_set_decode_threads(threads),
+            Message::ApplyDecodeThreads => self.handle_apply_decode_threads(),
+            Message::SetPowerPreference(preference) => self.handle_set_power_preference(preference),
+            Message::SetPreviewResolutionCap(cap) => self.handle_set_preview_resolution_cap(cap),
+            Message::SetLowLightMode(mode) => self.handle_set_low_light_mode(mode),
+            Message::LowLightTick => self.handle_low_light_tick(),
+            Message::ApplyLowLightFormat(toast) => self.handle_apply_low_light_format(toast),
+            Message::RevertLowLightFormat(toast) => self.handle_revert_low_light_format(toast),
+            Message::SelectRenderNode(index) => self.handle_select_render_node(index),
 
             // ===== System & Recovery =====
             Message::CameraRecoveryStarted {
@@ -267,10 +416,73 @@ impl AppModel {
             Message::PrivacyCoverStatusChanged(is_closed) => {
                 self.handle_privacy_cover_status_changed(is_closed)
             }
+            Message::DismissBlackFrameHint => self.handle_dismiss_black_frame_hint(),
 
             // ===== Insights Drawer =====
             Message::UpdateInsightsMetrics => self.handle_update_insights_metrics(),
             Message::CopyPipelineString => self.handle_copy_pipeline_string(),
+            Message::CopyFormatHistory => self.handle_copy_format_history(),
+            Message::CopyDeviceIdentity => self.handle_copy_device_identity(),
+            Message::DisplayFrame(at) => self.handle_display_frame(at),
+            Message::SetPresentMode(mode) => self.handle_set_present_mode(mode),
+            Message::SetMetricsSmoothing(smoothing) => self.handle_set_metrics_smoothing(smoothing),
+            Message::SetFrameDropPolicy(policy) => self.handle_set_frame_drop_policy(policy),
+            Message::SetAppsinkMaxBuffers(count) => self.handle_set_appsink_max_buffers(count),
+            Message::ToggleAppsinkDrop => self.handle_toggle_appsink_drop(),
+            Message::ToggleSkipIdenticalFrames => self.handle_toggle_skip_identical_frames(),
+            Message::ToggleFocusPeaking => self.handle_toggle_focus_peaking(),
+            Message::TogglePixelProbe => self.handle_toggle_pixel_probe(),
+            Message::ToggleFrameCounterOverlay => self.handle_toggle_frame_counter_overlay(),
+            Message::ToggleSessionStats => self.handle_toggle_session_stats(),
+            Message::ToggleRawMicroseconds => self.handle_toggle_raw_microseconds(),
+            Message::ResetSessionStats => self.handle_reset_session_stats(),
+            Message::PixelProbeHovered(pixel) => self.handle_pixel_probe_hovered(pixel),
+            Message::TogglePixelProbeLock(pixel) => self.handle_toggle_pixel_probe_lock(pixel),
+            Message::SelectStallWatchdog(index) => self.handle_select_stall_watchdog(index),
+            Message::StallWatchdogTick => self.handle_stall_watchdog_tick(),
+            Message::SelectCameraOpenTimeout(index) => {
+                self.handle_select_camera_open_timeout(index)
+            }
+            Message::SetBackgroundPreview(behavior) => self.handle_set_background_preview(behavior),
+            Message::SetKeepAwake(behavior) => self.handle_set_keep_awake(behavior),
+            Message::IdleInhibitChanged(reason, result) => {
+                self.handle_idle_inhibit_changed(reason, result)
+            }
+            Message::SelectFramerateCap(index) => self.handle_select_framerate_cap(index),
+            Message::SelectNudgeCoarseSteps(index) => self.handle_select_nudge_coarse_steps(index),
+            Message::WindowVisibilityChanged(visible) => {
+                self.handle_window_visibility_changed(visible)
+            }
+            Message::StartUploadBenchmark => self.handle_start_upload_benchmark(),
+            Message::ToggleInspectElement(name) => self.handle_toggle_inspect_element(name),
+            Message::ToggleInsightsAdvanced => {
+                self.insights_advanced_visible = !self.insights_advanced_visible;
+                Task::none()
+            }
+            Message::ForcedCapsInput(text) => {
+                self.forced_caps_input = text;
+                self.forced_caps_error = None;
+                Task::none()
+            }
+            Message::ApplyForcedCaps => self.handle_apply_forced_caps(),
+            Message::ClearForcedCaps => self.handle_clear_forced_caps(),
+            Message::CompareDecoder(decoder) => self.handle_compare_decoder(decoder),
+            Message::ToggleFreezePreview => self.handle_toggle_freeze_preview(),
+            Message::TogglePinMetrics => self.handle_toggle_pin_metrics(),
+            Message::StepFrame => self.handle_step_frame(),
+            Message::CopyDiagnosticsBundle => self.handle_copy_diagnostics_bundle(),
+            Message::SaveDiagnosticsBundle => self.handle_save_diagnostics_bundle(),
+            Message::DiagnosticsBundleSaved(result) => self.handle_diagnostics_bundle_saved(result),
+            Message::ToggleDiagnosticsIncludeDeviceIds => {
+                self.handle_toggle_diagnostics_include_device_ids()
+            }
+            Message::ExportPipelineDot => self.handle_export_pipeline_dot(),
+            Message::PipelineDotExported(result) => self.handle_pipeline_dot_exported(result),
+            Message::DumpRawStream { path, duration } => {
+                self.handle_dump_raw_stream(path, duration)
+            }
+            Message::StartRawStreamDump => self.handle_start_raw_stream_dump(),
+            Message::RawStreamDumped(result) => self.handle_raw_stream_dumped(result),
 
             Message::Noop => Task::none(),
 
@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Row layout of frame planes for GPU upload
+//!
+//! Frames rarely have tightly packed rows: GStreamer pads each row to the
+//! stride in the buffer's video info, and sensors with odd sizes (e.g.
+//! 642×482) produce rows whose length matches no alignment at all. Uploads
+//! therefore always pass the source's actual stride as `bytes_per_row`;
+//! `Queue::write_texture` accepts any stride and repacks rows to the 256-byte
+//! alignment GPU copies need itself. Assuming `width × bytes per texel`
+//! instead shears the image by the padding on every row.
+//!
+//! Chroma planes of 4:2:0 and packed 4:2:2 formats round odd sizes up, so
+//! the last column and row of an odd-sized frame still have chroma.
+//!
+//! [`check_stride`] compares a frame's stride with how its first plane was
+//! actually uploaded. Padding is harmless as long as the upload skips it;
+//! when it doesn't, the padding bytes (usually zeros, which the YUV
+//! conversion turns green) end up on screen as a bar along the right edge.
+
+use crate::backends::camera::types::PixelFormat;
+
+/// Texels, rows and stride of one plane as handed to the GPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlaneLayout {
+    /// Texels per row
+    pub width: u32,
+    /// Rows
+    pub height: u32,
+    /// Distance between the starts of consecutive rows in bytes
+    pub bytes_per_row: u32,
+    /// Bytes per texel
+    pub texel_bytes: u32,
+}
+
+impl PlaneLayout {
+    /// Layout of a plane stored in `len` bytes, or `None` if it doesn't fit
+    ///
+    /// A `stride` shorter than a row (missing or wrong caps) is replaced by the
+    /// stride implied by the buffer size, when that is long enough.
+    pub fn new(width: u32, height: u32, texel_bytes: u32, stride: u32, len: usize) -> Option<Self> {
+        let row_bytes = width.checked_mul(texel_bytes)?;
+        if row_bytes == 0 || height == 0 {
+            return None;
+        }
+        let bytes_per_row = if stride >= row_bytes {
+            stride
+        } else {
+            u32::try_from(len / height as usize)
+                .ok()
+                .filter(|&inferred| inferred >= row_bytes)?
+        };
+        let layout = Self {
+            width,
+            height,
+            bytes_per_row,
+            texel_bytes,
+        };
+        (len >= layout.required_len()).then_some(layout)
+    }
+
+    /// Bytes of pixel data in one row
+    pub fn row_bytes(&self) -> u32 {
+        self.width * self.texel_bytes
+    }
+
+    /// Bytes needed to hold every row (the last row needs no padding)
+    pub fn required_len(&self) -> usize {
+        (self.height as usize - 1) * self.bytes_per_row as usize + self.row_bytes() as usize
+    }
+}
+
+/// Texel width and bytes per texel of the first (luma or only) plane
+///
+/// Packed 4:2:2 formats store two pixels per RGBA texel.
+pub fn luma_texels(format: PixelFormat, width: u32) -> (u32, u32) {
+    match format {
+        PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
+            (width.div_ceil(2), 4)
+        }
+        PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 | PixelFormat::Gray8 => {
+            (width, 1)
+        }
+        PixelFormat::Gray16 => (width, 2),
+        PixelFormat::RGB24 => (width, 3),
+        PixelFormat::RGBA => (width, 4),
+    }
+}
+
+/// Size of the chroma planes, `(1, 1)` for formats without one
+pub fn chroma_size(format: PixelFormat, width: u32, height: u32) -> (u32, u32) {
+    match format {
+        PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => {
+            (width.div_ceil(2), height.div_ceil(2))
+        }
+        PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
+            (width.div_ceil(2), height)
+        }
+        PixelFormat::Gray8 | PixelFormat::Gray16 | PixelFormat::RGB24 | PixelFormat::RGBA => (1, 1),
+    }
+}
+
+/// How the first plane of the latest frame was handed to the GPU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LumaUpload {
+    /// Layout passed to the texture write
+    pub layout: PlaneLayout,
+    /// Width and height of the texture written to
+    pub texture_size: (u32, u32),
+}
+
+/// Why row padding is likely to show on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrideProblem {
+    /// Rows were read `bytes_per_row` apart instead of the source stride,
+    /// shifting every row a little further (sheared picture)
+    Sheared { bytes_per_row: u32 },
+    /// The texture is `extra_texels` wider than the picture, so the padding
+    /// is sampled as a bar along the right edge
+    PaddingVisible { extra_texels: u32 },
+    /// The stride is shorter than a row; the upload used the
+    /// `bytes_per_row` implied by the buffer size instead
+    StrideInferred { bytes_per_row: u32 },
+}
+
+/// Stride of a frame's first plane and how the upload dealt with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrideCheck {
+    /// Frame width in pixels
+    pub width: u32,
+    /// Source stride in bytes
+    pub stride: u32,
+    /// Bytes of pixels in a row
+    pub row_bytes: u32,
+    /// Bytes of padding after the pixels of a row
+    pub padding_bytes: u32,
+    /// Likely misrendering, when the upload doesn't match the stride
+    pub problem: Option<StrideProblem>,
+}
+
+/// Check the first plane of a `width`×`height` frame stored at `stride`
+///
+/// `upload` is how the latest frame was uploaded; one of another size is
+/// ignored. `None` when rows are tightly packed and nothing is wrong.
+pub fn check_stride(
+    format: PixelFormat,
+    width: u32,
+    height: u32,
+    stride: u32,
+    upload: Option<LumaUpload>,
+) -> Option<StrideCheck> {
+    let (texels, texel_bytes) = luma_texels(format, width);
+    let row_bytes = texels * texel_bytes;
+    let upload = upload.filter(|upload| {
+        upload.layout.height == height && upload.layout.texel_bytes == texel_bytes
+    });
+    let problem = upload.and_then(|upload| {
+        let layout = upload.layout;
+        let shown_texels = upload.texture_size.0.max(layout.width);
+        if stride < row_bytes {
+            Some(StrideProblem::StrideInferred {
+                bytes_per_row: layout.bytes_per_row,
+            })
+        } else if layout.bytes_per_row != stride {
+            Some(StrideProblem::Sheared {
+                bytes_per_row: layout.bytes_per_row,
+            })
+        } else if shown_texels > texels {
+            Some(StrideProblem::PaddingVisible {
+                extra_texels: shown_texels - texels,
+            })
+        } else {
+            None
+        }
+    });
+    (stride != row_bytes || problem.is_some()).then_some(StrideCheck {
+        width,
+        stride,
+        row_bytes,
+        padding_bytes: stride.saturating_sub(row_bytes),
+        problem,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unaligned_layout_keeps_source_stride() {
+        // 642×482 with rows padded to 704 bytes, uploaded in video_primitive
+        let layout = PlaneLayout::new(642, 482, 1, 704, 704 * 482).unwrap();
+        assert_eq!(layout.bytes_per_row, 704);
+        assert_eq!(layout.bytes_per_row - layout.row_bytes(), 62);
+    }
+
+    #[test]
+    fn test_last_row_needs_no_padding() {
+        // Some producers don't pad the final row
+        let len = 703 * 481 + 642;
+        assert!(PlaneLayout::new(642, 482, 1, 703, len).is_some());
+        assert!(PlaneLayout::new(642, 482, 1, 703, len - 1).is_none());
+    }
+
+    #[test]
+    fn test_short_stride_is_inferred_from_buffer() {
+        let layout = PlaneLayout::new(642, 482, 1, 0, 704 * 482).unwrap();
+        assert_eq!(layout.bytes_per_row, 704);
+        // A buffer too small for the frame is rejected instead of read past
+        assert!(PlaneLayout::new(642, 482, 1, 0, 600 * 482).is_none());
+    }
+
+    #[test]
+    fn test_odd_sizes_round_chroma_up() {
+        assert_eq!(chroma_size(PixelFormat::NV12, 641, 481), (321, 241));
+        assert_eq!(chroma_size(PixelFormat::YUYV, 641, 481), (321, 481));
+        assert_eq!(luma_texels(PixelFormat::YUYV, 641), (321, 4));
+        assert_eq!(check_stride(PixelFormat::YUYV, 642, 480, 1284, None), None);
+        assert_eq!(
+            check_stride(PixelFormat::YUYV, 642, 480, 1344, None).map(|c| c.padding_bytes),
+            Some(60)
+        );
+    }
+
+    #[test]
+    fn test_stride_check_flags_uploads_that_show_padding() {
+        let layout = PlaneLayout::new(642, 482, 1, 704, 704 * 482).unwrap();
+        let upload = LumaUpload {
+            layout,
+            texture_size: (642, 482),
+        };
+        let check = check_stride(PixelFormat::NV12, 642, 482, 704, Some(upload)).unwrap();
+        assert_eq!(check.padding_bytes, 62);
+        assert_eq!(check.problem, None);
+
+        // Padding read as pixels: the texture spans the whole stride
+        let wide = LumaUpload {
+            layout: PlaneLayout {
+                width: 704,
+                ..layout
+            },
+            texture_size: (704, 482),
+        };
+        assert_eq!(
+            check_stride(PixelFormat::NV12, 642, 482, 704, Some(wide))
+                .unwrap()
+                .problem,
+            Some(StrideProblem::PaddingVisible { extra_texels: 62 })
+        );
+        let packed = LumaUpload {
+            layout: PlaneLayout {
+                bytes_per_row: 642,
+                ..layout
+            },
+            ..upload
+        };
+        assert_eq!(
+            check_stride(PixelFormat::NV12, 642, 482, 704, Some(packed))
+                .unwrap()
+                .problem,
+            Some(StrideProblem::Sheared { bytes_per_row: 642 })
+        );
+        // An upload of another frame size says nothing about this one
+        assert_eq!(
+            check_stride(PixelFormat::NV12, 642, 480, 704, Some(wide))
+                .unwrap()
+                .problem,
+            None
+        );
+        assert_eq!(check_stride(PixelFormat::NV12, 640, 480, 640, None), None);
+    }
+}
@@ -7,18 +7,37 @@
 //! - RGBA textures for native RGB processing
 //! - Persistent textures across frames
 
+use crate::app::insights::GpuPath;
 use crate::app::state::FilterType;
-use crate::backends::camera::types::{FrameData, PixelFormat, YuvPlanes};
+use crate::app::upload_layout;
+use crate::backends::camera::types::{FrameData, PixelFormat, YuvPlanes, gray16_range};
+use crate::config::{ColorEffect, ScalingQuality};
 use cosmic::iced::Rectangle;
 use cosmic::iced_wgpu::graphics::Viewport;
 use cosmic::iced_wgpu::primitive::{self, Primitive as PrimitiveTrait};
 use cosmic::iced_wgpu::wgpu;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 // Static for GPU upload time tracking (insights)
 static GPU_UPLOAD_TIME_US: AtomicU64 = AtomicU64::new(0);
 static GPU_FRAME_SIZE: AtomicU64 = AtomicU64::new(0);
+/// GPU memory held by the preview textures in bytes
+static TEXTURE_MEMORY: AtomicU64 = AtomicU64::new(0);
+/// Capture time of the newest frame uploaded for presentation
+static PRESENTED_CAPTURED_AT: Mutex<Option<std::time::Instant>> = Mutex::new(None);
+/// GPU path the newest frame took
+static GPU_PATH: Mutex<Option<GpuPath>> = Mutex::new(None);
+/// How the first plane of the newest frame was uploaded
+static LUMA_UPLOAD: Mutex<Option<upload_layout::LumaUpload>> = Mutex::new(None);
+/// Key of the camera and format feeding the preview
+static PREVIEW_SOURCE: AtomicU64 = AtomicU64::new(0);
+
+/// `sample_video` of the default preview shader: the sampler does the filtering
+const SAMPLE_VIDEO_SAMPLER: &str = "fn sample_video(uv: vec2<f32>) -> vec4<f32> {
+    return textureSample(texture_rgba, sampler_video, uv);
+}
+";
 
 /// Get the last GPU upload time in microseconds
 pub fn get_gpu_upload_time_us() -> u64 {
@@ -30,6 +49,135 @@ pub fn get_gpu_frame_size() -> u64 {
     GPU_FRAME_SIZE.load(Ordering::Relaxed)
 }
 
+/// Get the GPU memory held by the preview textures in bytes
+///
+/// Source textures, YUV planes and blur intermediates, as allocated; updated
+/// whenever they are (re)created.
+pub fn get_texture_memory() -> u64 {
+    TEXTURE_MEMORY.load(Ordering::Relaxed)
+}
+
+/// Bytes a texture occupies, from its size and format
+pub(crate) fn texture_bytes(texture: &wgpu::Texture) -> u64 {
+    let size = texture.size();
+    let texel_bytes = texture.format().block_copy_size(None).unwrap_or(4);
+    size.width as u64 * size.height as u64 * size.depth_or_array_layers as u64 * texel_bytes as u64
+}
+
+/// Capture time of the frame currently presented (None = none since the last reset)
+pub fn get_presented_captured_at() -> Option<std::time::Instant> {
+    PRESENTED_CAPTURED_AT.lock().ok().and_then(|guard| *guard)
+}
+
+/// Forget the presented frame, e.g. when the preview is frozen or resumed
+pub fn reset_presented_captured_at() {
+    if let Ok(mut guard) = PRESENTED_CAPTURED_AT.lock() {
+        *guard = None;
+    }
+}
+
+/// Note the camera and format feeding the preview
+///
+/// The last frame kept for re-upload after a device loss holds on to a mapped
+/// buffer of the old source, so a new key drops it on the next render.
+pub fn set_preview_source(source: u64) {
+    PREVIEW_SOURCE.store(source, Ordering::Relaxed);
+}
+
+/// GPU path the newest frame took (None = no frame uploaded yet)
+pub fn get_gpu_path() -> Option<GpuPath> {
+    GPU_PATH.lock().ok().and_then(|guard| *guard)
+}
+
+fn set_gpu_path(path: GpuPath) {
+    if let Ok(mut guard) = GPU_PATH.lock() {
+        *guard = Some(path);
+    }
+}
+
+/// How the first (luma or only) plane of the newest frame was uploaded
+///
+/// Checked against the frame's stride by the insights drawer, see
+/// [`upload_layout::check_stride`].
+pub fn get_luma_upload() -> Option<upload_layout::LumaUpload> {
+    LUMA_UPLOAD.lock().ok().and_then(|guard| *guard)
+}
+
+/// Remember the layout `write_plane` used for a frame's first plane
+fn record_luma_upload(
+    texture: &wgpu::Texture,
+    data: &[u8],
+    (width, height, texel_bytes): (u32, u32, u32),
+    stride: u32,
+) {
+    let size = texture.size();
+    let upload = upload_layout::PlaneLayout::new(width, height, texel_bytes, stride, data.len())
+        .map(|layout| upload_layout::LumaUpload {
+            layout,
+            texture_size: (size.width, size.height),
+        });
+    if let Ok(mut guard) = LUMA_UPLOAD.lock() {
+        *guard = upload;
+    }
+}
+
+/// Upload one plane of `(width, height, bytes per texel)` stored at `stride`
+///
+/// Returns false, without uploading, when `data` is too short for the plane.
+fn write_plane(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    data: &[u8],
+    (width, height, texel_bytes): (u32, u32, u32),
+    stride: u32,
+) -> bool {
+    static WARNED: AtomicBool = AtomicBool::new(false);
+    let Some(layout) =
+        upload_layout::PlaneLayout::new(width, height, texel_bytes, stride, data.len())
+    else {
+        if !WARNED.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                width,
+                height,
+                texel_bytes,
+                stride,
+                len = data.len(),
+                "Frame plane doesn't fit its buffer, skipping upload"
+            );
+        }
+        return false;
+    };
+    queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        data,
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(layout.bytes_per_row),
+            rows_per_image: None,
+        },
+        wgpu::Extent3d {
+            width: layout.width,
+            height: layout.height,
+            depth_or_array_layers: 1,
+        },
+    );
+    true
+}
+
+/// Display mapping for single-channel (GREY/Y16) frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GrayMapping {
+    /// Stretch Y16 frames to their own min/max so dim images become visible
+    pub auto_stretch: bool,
+    /// Render intensity through a false-color palette instead of grayscale
+    pub false_color: bool,
+}
+
 /// Video frame data for GPU upload
 ///
 /// Supports both RGBA and YUV formats. For YUV formats, the data is converted
@@ -47,6 +195,10 @@ pub struct VideoFrame {
     pub stride: u32,
     /// Additional YUV planes (for NV12/I420 formats)
     pub yuv_planes: Option<YuvPlanes>,
+    /// Display mapping for grayscale formats (ignored for color formats)
+    pub gray_mapping: GrayMapping,
+    /// When the frame left the pipeline (for the presentation lag metric)
+    pub captured_at: std::time::Instant,
 }
 
 impl VideoFrame {
@@ -72,6 +224,12 @@ impl VideoFrame {
     pub fn is_yuv(&self) -> bool {
         self.format.is_yuv()
     }
+
+    /// Check if this frame must go through the compute shader (YUV or grayscale)
+    #[inline]
+    pub fn needs_gpu_conversion(&self) -> bool {
+        self.format.is_yuv() || self.format.is_grayscale()
+    }
 }
 
 /// Viewport and content fit data for Cover mode
@@ -100,6 +258,10 @@ struct ViewportUniform {
     zoom_level: f32,
     /// Sensor rotation: 0=None, 1=90CW, 2=180, 3=270CW
     rotation: u32,
+    /// Color effect applied after the filter (0 = none)
+    color_effect: u32,
+    /// 1 = highlight in-focus edges
+    focus_peaking: u32,
 }
 
 /// Combined frame and viewport data to reduce mutex contention
@@ -135,6 +297,12 @@ pub struct VideoPrimitive {
     pub crop_uv: Option<(f32, f32, f32, f32)>,
     /// Zoom level (1.0 = no zoom, 2.0 = 2x zoom, etc.)
     pub zoom_level: f32,
+    /// Color effect applied after the filter
+    pub color_effect: ColorEffect,
+    /// Highlight in-focus edges (focus peaking)
+    pub focus_peaking: bool,
+    /// Filter used to scale the frame to the widget
+    pub scaling: ScalingQuality,
 }
 
 /// Video texture (shared across filter variations)
@@ -153,6 +321,8 @@ struct VideoTexture {
 struct FilterBinding {
     bind_group: wgpu::BindGroup,
     viewport_buffer: wgpu::Buffer,
+    /// Bound to the nearest-neighbour sampler instead of the linear one
+    nearest: bool,
 }
 
 /// YUV conversion parameters uniform (must match shader struct)
@@ -165,7 +335,10 @@ struct YuvConvertParams {
     y_stride: u32,
     uv_stride: u32,
     v_stride: u32,
-    _pad: [u32; 2],
+    gray_min: f32,
+    gray_max: f32,
+    false_color: u32,
+    _pad: [u32; 3],
 }
 
 /// YUV textures for a video source (for YUV→RGBA conversion)
@@ -184,10 +357,16 @@ struct YuvTextures {
 /// Custom pipeline for efficient video rendering
 pub struct VideoPipeline {
     pipeline_rgba: wgpu::RenderPipeline,
-    pipeline_rgb_blur: wgpu::RenderPipeline, // RGB blur for multi-pass
+    pipeline_rgba_effect: wgpu::RenderPipeline, // Same shader with the color effect stage
+    pipeline_rgb_blur: wgpu::RenderPipeline,    // RGB blur for multi-pass
+    // Bicubic variants (plain, effect), only compiled once bicubic scaling is selected
+    pipelines_rgba_bicubic: Option<[wgpu::RenderPipeline; 2]>,
+    pipeline_layout_rgba: wgpu::PipelineLayout,
+    target_format: wgpu::TextureFormat,
     bind_group_layout_rgba: wgpu::BindGroupLayout,
     bind_group_layout_rgb: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
+    sampler_nearest: wgpu::Sampler,
     // Shared textures by video_id (single upload per source)
     textures: std::collections::HashMap<u64, VideoTexture>,
     // Per-filter bindings keyed by (video_id, filter_mode)
@@ -200,12 +379,20 @@ pub struct VideoPipeline {
     // GPU timing tracking to detect and handle stalls
     last_upload_duration: std::cell::Cell<std::time::Duration>,
     frames_skipped: std::cell::Cell<u32>,
-    // YUV→RGBA conversion compute pipeline
+    // YUV→RGBA conversion: compute pipeline, or render pipeline without compute support
+    yuv_conversion: crate::gpu::ConversionPath,
     yuv_compute_pipeline: Option<wgpu::ComputePipeline>,
+    yuv_render_pipeline: Option<wgpu::RenderPipeline>,
     yuv_bind_group_layout: Option<wgpu::BindGroupLayout>,
     yuv_uniform_buffer: Option<wgpu::Buffer>,
     // YUV textures per video_id
     yuv_textures: std::collections::HashMap<u64, YuvTextures>,
+    // Device this pipeline's resources belong to
+    device_id: wgpu::Id<wgpu::Device>,
+    // Last uploaded frame per video_id, re-uploaded after the pipeline is rebuilt,
+    // and the preview source they came from
+    last_frames: std::collections::HashMap<u64, VideoFrame>,
+    last_frames_source: u64,
 }
 
 /// Intermediate texture for multi-pass blur
@@ -215,6 +402,8 @@ struct BlurIntermediateTexture {
     viewport_buffer: wgpu::Buffer,
     width: u32,
     height: u32,
+    /// Texture size in GPU memory
+    bytes: u64,
 }
 
 impl VideoPrimitive {
@@ -235,6 +424,9 @@ impl VideoPrimitive {
             rotation: 0,
             crop_uv: None,
             zoom_level: 1.0,
+            color_effect: ColorEffect::None,
+            focus_peaking: false,
+            scaling: ScalingQuality::default(),
         }
     }
 
@@ -269,8 +461,31 @@ impl PrimitiveTrait for VideoPrimitive {
         use std::time::Instant;
         let prepare_start = Instant::now();
 
-        // Get or create pipeline
-        if !storage.has::<VideoPipeline>() {
+        // Get or create pipeline. When iced hands us a new device (its old one
+        // was lost) every resource of the old pipeline is invalid, so rebuild
+        // it from scratch and re-upload the last frame of each video source.
+        // Zoom, rotation, crop and filter live on the primitive, not the
+        // pipeline, so they carry over unchanged.
+        let rebind_frames = storage
+            .get_mut::<VideoPipeline>()
+            .filter(|pipeline| pipeline.is_device_lost(device))
+            .map(|pipeline| {
+                (
+                    std::mem::take(&mut pipeline.last_frames),
+                    pipeline.last_frames_source,
+                )
+            });
+        if let Some((frames, source)) = rebind_frames {
+            tracing::warn!(
+                sources = frames.len(),
+                "Rebuilding video pipeline after GPU device loss"
+            );
+            let mut pipeline = VideoPipeline::new(device, _format);
+            pipeline.last_frames = frames;
+            pipeline.last_frames_source = source;
+            // Replacing the stored pipeline drops the old one and its resources
+            storage.store(pipeline);
+        } else if !storage.has::<VideoPipeline>() {
             storage.store(VideoPipeline::new(device, _format));
         }
 
@@ -334,6 +549,20 @@ impl PrimitiveTrait for VideoPrimitive {
         let lock_time = prepare_start.elapsed();
 
         if let Some(pipeline) = storage.get_mut::<VideoPipeline>() {
+            // Frames of a previous camera or format are never re-uploaded
+            let source = PREVIEW_SOURCE.load(Ordering::Relaxed);
+            if pipeline.last_frames_source != source {
+                pipeline.last_frames.clear();
+                pipeline.last_frames_source = source;
+            }
+
+            // Without a new frame, rebind the last one if the pipeline was rebuilt
+            let frame_opt = frame_opt.or_else(|| {
+                (!pipeline.textures.contains_key(&self.video_id))
+                    .then(|| pipeline.last_frames.get(&self.video_id).cloned())
+                    .flatten()
+            });
+
             // Upload frame if available
             if let Some(frame) = frame_opt {
                 let upload_start = Instant::now();
@@ -348,6 +577,8 @@ impl PrimitiveTrait for VideoPrimitive {
                     );
                 }
                 pipeline.upload(device, queue, frame);
+                // A frame reaching the screen again ends any device-loss recovery
+                crate::gpu::mark_recovered();
 
                 let upload_time = upload_start.elapsed();
                 if upload_time.as_millis() > 16 {
@@ -373,7 +604,11 @@ impl PrimitiveTrait for VideoPrimitive {
 
             // Get or create binding for this (video_id, filter_mode) combination
             // This allows sharing the source texture while having per-filter uniforms
-            pipeline.get_or_create_binding(device, self.video_id, filter_mode);
+            let nearest = self.scaling == ScalingQuality::Nearest;
+            pipeline.get_or_create_binding(device, self.video_id, filter_mode, nearest);
+            if self.scaling == ScalingQuality::Bicubic {
+                pipeline.ensure_bicubic_pipelines(device);
+            }
 
             // Get texture dimensions for blur passes
             let tex_dims = pipeline
@@ -416,6 +651,8 @@ impl PrimitiveTrait for VideoPrimitive {
                             crop_uv_max: crop_max,
                             zoom_level: 1.0, // No zoom for blur passes
                             rotation: self.rotation,
+                            color_effect: 0, // Effects only apply to the live preview
+                            focus_peaking: 0,
                         };
                         queue.write_buffer(
                             &binding.viewport_buffer,
@@ -437,6 +674,8 @@ impl PrimitiveTrait for VideoPrimitive {
                         crop_uv_max: crop_max,
                         zoom_level: self.zoom_level,
                         rotation: self.rotation,
+                        color_effect: self.color_effect.gpu_code(),
+                        focus_peaking: self.focus_peaking as u32,
                     };
                     queue.write_buffer(
                         &binding.viewport_buffer,
@@ -461,6 +700,8 @@ impl PrimitiveTrait for VideoPrimitive {
                         crop_uv_max: [1.0, 1.0],
                         zoom_level: 1.0, // No zoom for intermediate passes
                         rotation: 0,     // Already rotated in pass 1
+                        color_effect: 0,
+                        focus_peaking: 0,
                     };
                     queue.write_buffer(
                         &intermediate_1.viewport_buffer,
@@ -483,6 +724,8 @@ impl PrimitiveTrait for VideoPrimitive {
                         crop_uv_max: [1.0, 1.0],
                         zoom_level: 1.0, // No zoom for blur
                         rotation: 0,     // Already rotated in pass 1
+                        color_effect: 0,
+                        focus_peaking: 0,
                     };
                     queue.write_buffer(
                         &intermediate_2.viewport_buffer,
@@ -522,6 +765,8 @@ impl PrimitiveTrait for VideoPrimitive {
             pipeline.render(
                 self.video_id,
                 filter_mode,
+                self.color_effect,
+                self.scaling,
                 encoder,
                 target,
                 clip_bounds,
@@ -531,19 +776,87 @@ impl PrimitiveTrait for VideoPrimitive {
     }
 }
 
+/// Preview shader module with the given `sample_video` implementation
+fn create_video_shader(
+    device: &wgpu::Device,
+    label: &str,
+    sample_video: &str,
+) -> wgpu::ShaderModule {
+    let shader_source = format!(
+        "{}\n{}\n{}",
+        crate::shaders::FILTER_FUNCTIONS,
+        include_str!("video_shader.wgsl"),
+        sample_video
+    );
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+    })
+}
+
+/// Preview render pipeline, with or without the color effect stage
+fn create_rgba_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    label: &str,
+    effects_enabled: bool,
+) -> wgpu::RenderPipeline {
+    let constants = std::collections::HashMap::from([(
+        "color_effects_enabled".to_string(),
+        if effects_enabled { 1.0 } else { 0.0 },
+    )]);
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vs_main",
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "fs_main",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: &constants,
+                ..Default::default()
+            },
+        }),
+        multiview: None,
+        cache: None,
+    })
+}
+
 impl VideoPipeline {
     pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        // Compute shader where available, fullscreen fragment shader otherwise
+        let yuv_conversion = crate::gpu::choose_conversion_path(&device.limits());
+        Self::with_conversion(device, format, yuv_conversion)
+    }
+
+    /// Build the pipeline with the YUV→RGBA conversion on a given path
+    fn with_conversion(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        yuv_conversion: crate::gpu::ConversionPath,
+    ) -> Self {
         // ===== Video Pipeline =====
         // Shader for video rendering with shared filter functions
-        let shader_source = format!(
-            "{}\n{}",
-            crate::shaders::FILTER_FUNCTIONS,
-            include_str!("video_shader.wgsl")
-        );
-        let shader_rgba = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("camera video shader"),
-            source: wgpu::ShaderSource::Wgsl(shader_source.into()),
-        });
+        let shader_rgba = create_video_shader(device, "camera video shader", SAMPLE_VIDEO_SAMPLER);
 
         // Bind group layout for video texture, sampler, and viewport
         let bind_group_layout_rgba =
@@ -588,35 +901,24 @@ impl VideoPipeline {
             push_constant_ranges: &[],
         });
 
-        let pipeline_rgba = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("camera video pipeline"),
-            layout: Some(&pipeline_layout_rgba),
-            vertex: wgpu::VertexState {
-                module: &shader_rgba,
-                entry_point: "vs_main",
-                buffers: &[],
-                compilation_options: Default::default(),
-            },
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader_rgba,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            multiview: None,
-            cache: None,
-        });
+        // The effect variant only differs in the `color_effects_enabled` override,
+        // so the default pipeline carries no effect code at all
+        let pipeline_rgba = create_rgba_pipeline(
+            device,
+            &pipeline_layout_rgba,
+            &shader_rgba,
+            format,
+            "camera video pipeline",
+            false,
+        );
+        let pipeline_rgba_effect = create_rgba_pipeline(
+            device,
+            &pipeline_layout_rgba,
+            &shader_rgba,
+            format,
+            "camera video effect pipeline",
+            true,
+        );
 
         // ===== Blur Pipeline (for multi-pass blur) =====
         let shader_blur_source = include_str!("video_shader_blur.wgsl");
@@ -709,73 +1011,68 @@ impl VideoPipeline {
             mipmap_filter: wgpu::FilterMode::Nearest,
             ..Default::default()
         });
-
-        // ===== YUV→RGBA Conversion Compute Pipeline =====
-        let yuv_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("yuv_convert_shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("../shaders/yuv_convert.wgsl").into()),
+        let sampler_nearest = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("camera video nearest sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
         });
 
+        // ===== YUV→RGBA Conversion Pipeline =====
+        let yuv_visibility = match yuv_conversion {
+            crate::gpu::ConversionPath::Compute => wgpu::ShaderStages::COMPUTE,
+            crate::gpu::ConversionPath::Fragment => wgpu::ShaderStages::FRAGMENT,
+        };
+        let plane_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: yuv_visibility,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let mut yuv_layout_entries = vec![
+            // tex_y: Y plane or packed YUYV
+            plane_entry(0),
+            // tex_uv: UV plane (NV12) or U plane (I420)
+            plane_entry(1),
+            // tex_v: V plane (I420 only)
+            plane_entry(2),
+            // params: uniform buffer
+            wgpu::BindGroupLayoutEntry {
+                binding: 4,
+                visibility: yuv_visibility,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ];
+        if yuv_conversion == crate::gpu::ConversionPath::Compute {
+            // output: RGBA storage texture (the fragment path renders to it instead)
+            yuv_layout_entries.push(wgpu::BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::StorageTexture {
+                    access: wgpu::StorageTextureAccess::WriteOnly,
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+                count: None,
+            });
+        }
         let yuv_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("yuv_convert_bind_group_layout"),
-                entries: &[
-                    // tex_y: Y plane or packed YUYV
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::Texture {
-                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                            multisampled: false,
-                        },
-                        count: None,
-                    },
-                    // tex_uv: UV plane (NV12) or U plane (I420)
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 1,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::Texture {
-                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                            multisampled: false,
-                        },
-                        count: None,
-                    },
-                    // tex_v: V plane (I420 only)
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 2,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::Texture {
-                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                            multisampled: false,
-                        },
-                        count: None,
-                    },
-                    // output: RGBA storage texture
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 3,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::StorageTexture {
-                            access: wgpu::StorageTextureAccess::WriteOnly,
-                            format: wgpu::TextureFormat::Rgba8Unorm,
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                        },
-                        count: None,
-                    },
-                    // params: uniform buffer
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 4,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                ],
+                entries: &yuv_layout_entries,
             });
 
         let yuv_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -784,15 +1081,55 @@ impl VideoPipeline {
             push_constant_ranges: &[],
         });
 
-        let yuv_compute_pipeline =
-            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
-                label: Some("yuv_convert_compute_pipeline"),
-                layout: Some(&yuv_pipeline_layout),
-                module: &yuv_shader,
-                entry_point: "main",
-                compilation_options: Default::default(),
-                cache: None,
-            });
+        let (yuv_compute_pipeline, yuv_render_pipeline) = match yuv_conversion {
+            crate::gpu::ConversionPath::Compute => {
+                let yuv_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("yuv_convert_shader"),
+                    source: wgpu::ShaderSource::Wgsl(crate::shaders::YUV_CONVERT_COMPUTE.into()),
+                });
+                let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: Some("yuv_convert_compute_pipeline"),
+                    layout: Some(&yuv_pipeline_layout),
+                    module: &yuv_shader,
+                    entry_point: "main",
+                    compilation_options: Default::default(),
+                    cache: None,
+                });
+                (Some(pipeline), None)
+            }
+            crate::gpu::ConversionPath::Fragment => {
+                let yuv_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some("yuv_convert_fragment_shader"),
+                    source: wgpu::ShaderSource::Wgsl(crate::shaders::YUV_CONVERT_FRAGMENT.into()),
+                });
+                let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: Some("yuv_convert_render_pipeline"),
+                    layout: Some(&yuv_pipeline_layout),
+                    vertex: wgpu::VertexState {
+                        module: &yuv_shader,
+                        entry_point: "vs_main",
+                        buffers: &[],
+                        compilation_options: Default::default(),
+                    },
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    fragment: Some(wgpu::FragmentState {
+                        module: &yuv_shader,
+                        entry_point: "fs_main",
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format: wgpu::TextureFormat::Rgba8Unorm,
+                            blend: None,
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                        compilation_options: Default::default(),
+                    }),
+                    multiview: None,
+                    cache: None,
+                });
+                (None, Some(pipeline))
+            }
+        };
 
         let yuv_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("yuv_convert_uniform_buffer"),
@@ -801,23 +1138,71 @@ impl VideoPipeline {
             mapped_at_creation: false,
         });
 
+        // Textures of a previous pipeline (before a device loss) are gone
+        TEXTURE_MEMORY.store(0, Ordering::Relaxed);
+
         Self {
             pipeline_rgba,
+            pipeline_rgba_effect,
             pipeline_rgb_blur,
+            pipelines_rgba_bicubic: None,
+            pipeline_layout_rgba,
+            target_format: format,
             bind_group_layout_rgba,
             bind_group_layout_rgb,
             sampler,
+            sampler_nearest,
             textures: std::collections::HashMap::new(),
             bindings: std::collections::HashMap::new(),
             blur_intermediate_1: std::cell::RefCell::new(None),
             blur_intermediate_2: std::cell::RefCell::new(None),
             last_upload_duration: std::cell::Cell::new(std::time::Duration::ZERO),
             frames_skipped: std::cell::Cell::new(0),
-            yuv_compute_pipeline: Some(yuv_compute_pipeline),
+            yuv_conversion,
+            yuv_compute_pipeline,
+            yuv_render_pipeline,
             yuv_bind_group_layout: Some(yuv_bind_group_layout),
             yuv_uniform_buffer: Some(yuv_uniform_buffer),
             yuv_textures: std::collections::HashMap::new(),
+            device_id: device.global_id(),
+            last_frames: std::collections::HashMap::new(),
+            last_frames_source: PREVIEW_SOURCE.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Compile the bicubic preview pipelines on first use
+    fn ensure_bicubic_pipelines(&mut self, device: &wgpu::Device) {
+        if self.pipelines_rgba_bicubic.is_some() {
+            return;
         }
+        let shader = create_video_shader(
+            device,
+            "camera video bicubic shader",
+            include_str!("video_shader_bicubic.wgsl"),
+        );
+        let create = |label, effects_enabled| {
+            create_rgba_pipeline(
+                device,
+                &self.pipeline_layout_rgba,
+                &shader,
+                self.target_format,
+                label,
+                effects_enabled,
+            )
+        };
+        self.pipelines_rgba_bicubic = Some([
+            create("camera video bicubic pipeline", false),
+            create("camera video bicubic effect pipeline", true),
+        ]);
+        tracing::debug!("Created bicubic preview pipelines");
+    }
+
+    /// Whether iced replaced this pipeline's device since creation
+    ///
+    /// The device belongs to the renderer, which recreates it after a loss, so
+    /// a new device id is what signals the loss here.
+    fn is_device_lost(&self, device: &wgpu::Device) -> bool {
+        self.device_id != device.global_id()
     }
 
     /// Upload frame data directly to GPU textures (texture only, bindings created separately)
@@ -869,11 +1254,21 @@ impl VideoPipeline {
 
         // Create or resize texture if needed (invalidates all bindings for this video_id)
         if needs_creation {
+            // A resize means the source renegotiated its caps mid-stream
+            if let Some(old) = self.textures.get(&frame.id) {
+                tracing::info!(
+                    video_id = frame.id,
+                    from = %format!("{}x{}", old.width, old.height),
+                    to = %format!("{}x{}", frame.width, frame.height),
+                    "Frame size changed, reallocating video textures"
+                );
+            }
             let create_start = Instant::now();
             let new_tex = self.create_texture(device, frame.width, frame.height);
             self.textures.insert(frame.id, new_tex);
             // Remove all bindings for this video_id since texture changed
             self.bindings.retain(|(vid, _), _| *vid != frame.id);
+            self.update_texture_memory();
             let create_time = create_start.elapsed();
             if create_time.as_millis() > 5 {
                 tracing::warn!(
@@ -885,11 +1280,13 @@ impl VideoPipeline {
             }
         }
 
+        self.last_frames.insert(frame.id, frame.clone());
+
         // Handle YUV or RGBA upload
         let gpu_copy_start = Instant::now();
 
-        if frame.is_yuv() {
-            // YUV path: Update last frame pointer, then do YUV conversion
+        if frame.needs_gpu_conversion() {
+            // YUV/grayscale path: Update last frame pointer, then do GPU conversion
             {
                 let tex = self
                     .textures
@@ -907,31 +1304,31 @@ impl VideoPipeline {
                 .expect("Texture should exist");
             tex.last_frame_ptr = frame_data_ptr;
 
-            queue.write_texture(
-                wgpu::ImageCopyTexture {
-                    texture: &tex.texture,
-                    mip_level: 0,
-                    origin: wgpu::Origin3d::ZERO,
-                    aspect: wgpu::TextureAspect::All,
-                },
+            if write_plane(
+                queue,
+                &tex.texture,
                 frame.rgba_data(),
-                wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: Some(frame.stride),
-                    rows_per_image: None,
-                },
-                wgpu::Extent3d {
-                    width: frame.width,
-                    height: frame.height,
-                    depth_or_array_layers: 1,
-                },
-            );
+                (frame.width, frame.height, 4),
+                frame.stride,
+            ) {
+                record_luma_upload(
+                    &tex.texture,
+                    frame.rgba_data(),
+                    (frame.width, frame.height, 4),
+                    frame.stride,
+                );
+                set_gpu_path(GpuPath::Passthrough);
+            }
         }
         let gpu_copy_time = gpu_copy_start.elapsed();
 
         // Store GPU upload metrics for insights
         GPU_UPLOAD_TIME_US.store(gpu_copy_time.as_micros() as u64, Ordering::Relaxed);
         GPU_FRAME_SIZE.store(frame.data_slice().len() as u64, Ordering::Relaxed);
+        if let Ok(mut guard) = PRESENTED_CAPTURED_AT.lock() {
+            // Several widgets may show the same source; keep the newest frame
+            *guard = (*guard).max(Some(frame.captured_at));
+        }
 
         // Track upload duration for frame skipping decisions
         let upload_duration = upload_start.elapsed();
@@ -962,8 +1359,14 @@ impl VideoPipeline {
     }
 
     /// Create a texture for a video source (shared across filter variations)
-    /// Includes STORAGE_BINDING usage for YUV→RGBA compute shader output
+    /// Also usable as the YUV→RGBA conversion output (storage texture for the
+    /// compute path, render attachment for the fragment path), and readable
+    /// back for comparing the two paths
     fn create_texture(&self, device: &wgpu::Device, width: u32, height: u32) -> VideoTexture {
+        let conversion_output = match self.yuv_conversion {
+            crate::gpu::ConversionPath::Compute => wgpu::TextureUsages::STORAGE_BINDING,
+            crate::gpu::ConversionPath::Fragment => wgpu::TextureUsages::RENDER_ATTACHMENT,
+        };
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("camera RGBA texture"),
             size: wgpu::Extent3d {
@@ -975,10 +1378,10 @@ impl VideoPipeline {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba8Unorm,
-            // Include STORAGE_BINDING for YUV→RGBA compute shader output
             usage: wgpu::TextureUsages::TEXTURE_BINDING
                 | wgpu::TextureUsages::COPY_DST
-                | wgpu::TextureUsages::STORAGE_BINDING,
+                | wgpu::TextureUsages::COPY_SRC
+                | conversion_output,
             view_formats: &[],
         });
 
@@ -993,11 +1396,11 @@ impl VideoPipeline {
         }
     }
 
-    /// Upload YUV frame data and convert to RGBA using GPU compute shader
+    /// Upload YUV frame data and convert to RGBA on the GPU
     ///
     /// This method:
     /// 1. Uploads YUV plane data to GPU textures
-    /// 2. Runs compute shader to convert YUV→RGBA
+    /// 2. Converts YUV→RGBA in a compute shader (or fragment shader without compute support)
     /// 3. Outputs directly to the RGBA texture used for rendering
     ///
     /// All processing stays on GPU - no CPU round-trip between YUV conversion and rendering.
@@ -1013,7 +1416,7 @@ impl VideoPipeline {
         // Ensure YUV textures exist
         self.ensure_yuv_textures(device, frame.id, frame.width, frame.height, frame.format);
 
-        // Get output texture for compute shader
+        // Get output texture for the conversion
         let output_texture = match self.textures.get(&frame.id) {
             Some(tex) => &tex.texture,
             None => {
@@ -1033,181 +1436,78 @@ impl VideoPipeline {
         // Get the full buffer data (zero-copy from GStreamer)
         let buffer_data = frame.data_slice();
 
-        // Upload planes using offsets (zero-copy: we slice from the mapped buffer)
-        match frame.format {
+        // Upload planes using offsets (zero-copy: we slice from the mapped buffer).
+        // Every plane is uploaded at the source's own stride, see `upload_layout`.
+        let (luma_width, luma_texel_bytes) = upload_layout::luma_texels(frame.format, frame.width);
+        let (chroma_width, chroma_height) =
+            upload_layout::chroma_size(frame.format, frame.width, frame.height);
+        let plane = |offset: usize| buffer_data.get(offset..).unwrap_or_default();
+        let uploaded = match frame.format {
             // Packed 4:2:2 formats: YUYV, UYVY, YVYU, VYUY
             // All packed as RGBA8 where each texel encodes 2 pixels
             PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
-                let packed_width = frame.width / 2;
-                queue.write_texture(
-                    wgpu::ImageCopyTexture {
-                        texture: &yuv_textures.tex_y,
-                        mip_level: 0,
-                        origin: wgpu::Origin3d::ZERO,
-                        aspect: wgpu::TextureAspect::All,
-                    },
+                write_plane(
+                    queue,
+                    &yuv_textures.tex_y,
                     buffer_data,
-                    wgpu::ImageDataLayout {
-                        offset: 0,
-                        bytes_per_row: Some(frame.stride),
-                        rows_per_image: None,
-                    },
-                    wgpu::Extent3d {
-                        width: packed_width,
-                        height: frame.height,
-                        depth_or_array_layers: 1,
-                    },
-                );
+                    (luma_width, frame.height, luma_texel_bytes),
+                    frame.stride,
+                )
             }
             // Semi-planar 4:2:0 formats: NV12, NV21
-            PixelFormat::NV12 | PixelFormat::NV21 => {
-                // NV12: Use offsets to slice Y and UV planes from buffer
-                if let Some(ref yuv_planes) = frame.yuv_planes {
-                    let uv_width = frame.width / 2;
-                    let uv_height = frame.height / 2;
-
-                    // Y plane: full resolution, R8 format
-                    let y_end = yuv_planes.y_offset + yuv_planes.y_size;
-                    queue.write_texture(
-                        wgpu::ImageCopyTexture {
-                            texture: &yuv_textures.tex_y,
-                            mip_level: 0,
-                            origin: wgpu::Origin3d::ZERO,
-                            aspect: wgpu::TextureAspect::All,
-                        },
-                        &buffer_data[yuv_planes.y_offset..y_end],
-                        wgpu::ImageDataLayout {
-                            offset: 0,
-                            bytes_per_row: Some(frame.stride),
-                            rows_per_image: None,
-                        },
-                        wgpu::Extent3d {
-                            width: frame.width,
-                            height: frame.height,
-                            depth_or_array_layers: 1,
-                        },
-                    );
-
-                    // UV plane: interleaved UV as RG8
-                    let uv_end = yuv_planes.uv_offset + yuv_planes.uv_size;
-                    queue.write_texture(
-                        wgpu::ImageCopyTexture {
-                            texture: &yuv_textures.tex_uv,
-                            mip_level: 0,
-                            origin: wgpu::Origin3d::ZERO,
-                            aspect: wgpu::TextureAspect::All,
-                        },
-                        &buffer_data[yuv_planes.uv_offset..uv_end],
-                        wgpu::ImageDataLayout {
-                            offset: 0,
-                            bytes_per_row: Some(yuv_planes.uv_stride),
-                            rows_per_image: None,
-                        },
-                        wgpu::Extent3d {
-                            width: uv_width,
-                            height: uv_height,
-                            depth_or_array_layers: 1,
-                        },
-                    );
+            PixelFormat::NV12 | PixelFormat::NV21 => match frame.yuv_planes {
+                // Y plane at full resolution (R8), interleaved UV/VU at half resolution (RG8)
+                Some(ref yuv_planes) => {
+                    write_plane(
+                        queue,
+                        &yuv_textures.tex_y,
+                        plane(yuv_planes.y_offset),
+                        (frame.width, frame.height, 1),
+                        frame.stride,
+                    ) && write_plane(
+                        queue,
+                        &yuv_textures.tex_uv,
+                        plane(yuv_planes.uv_offset),
+                        (chroma_width, chroma_height, 2),
+                        yuv_planes.uv_stride,
+                    )
                 }
-            }
-            PixelFormat::I420 => {
-                // I420: Use offsets to slice Y, U, V planes from buffer
-                if let Some(ref yuv_planes) = frame.yuv_planes {
-                    let uv_width = frame.width / 2;
-                    let uv_height = frame.height / 2;
-
-                    // Y plane: full resolution, R8 format
-                    let y_end = yuv_planes.y_offset + yuv_planes.y_size;
-                    queue.write_texture(
-                        wgpu::ImageCopyTexture {
-                            texture: &yuv_textures.tex_y,
-                            mip_level: 0,
-                            origin: wgpu::Origin3d::ZERO,
-                            aspect: wgpu::TextureAspect::All,
-                        },
-                        &buffer_data[yuv_planes.y_offset..y_end],
-                        wgpu::ImageDataLayout {
-                            offset: 0,
-                            bytes_per_row: Some(frame.stride),
-                            rows_per_image: None,
-                        },
-                        wgpu::Extent3d {
-                            width: frame.width,
-                            height: frame.height,
-                            depth_or_array_layers: 1,
-                        },
-                    );
-
-                    // U plane: R8 format
-                    let u_end = yuv_planes.uv_offset + yuv_planes.uv_size;
-                    queue.write_texture(
-                        wgpu::ImageCopyTexture {
-                            texture: &yuv_textures.tex_uv,
-                            mip_level: 0,
-                            origin: wgpu::Origin3d::ZERO,
-                            aspect: wgpu::TextureAspect::All,
-                        },
-                        &buffer_data[yuv_planes.uv_offset..u_end],
-                        wgpu::ImageDataLayout {
-                            offset: 0,
-                            bytes_per_row: Some(yuv_planes.uv_stride),
-                            rows_per_image: None,
-                        },
-                        wgpu::Extent3d {
-                            width: uv_width,
-                            height: uv_height,
-                            depth_or_array_layers: 1,
-                        },
-                    );
-
-                    // V plane: R8 format
-                    if yuv_planes.v_size > 0 {
-                        let v_end = yuv_planes.v_offset + yuv_planes.v_size;
-                        queue.write_texture(
-                            wgpu::ImageCopyTexture {
-                                texture: &yuv_textures.tex_v,
-                                mip_level: 0,
-                                origin: wgpu::Origin3d::ZERO,
-                                aspect: wgpu::TextureAspect::All,
-                            },
-                            &buffer_data[yuv_planes.v_offset..v_end],
-                            wgpu::ImageDataLayout {
-                                offset: 0,
-                                bytes_per_row: Some(yuv_planes.v_stride),
-                                rows_per_image: None,
-                            },
-                            wgpu::Extent3d {
-                                width: uv_width,
-                                height: uv_height,
-                                depth_or_array_layers: 1,
-                            },
-                        );
-                    }
+                None => false,
+            },
+            PixelFormat::I420 => match frame.yuv_planes {
+                // Y, U and V planes, all R8
+                Some(ref yuv_planes) => {
+                    write_plane(
+                        queue,
+                        &yuv_textures.tex_y,
+                        plane(yuv_planes.y_offset),
+                        (frame.width, frame.height, 1),
+                        frame.stride,
+                    ) && write_plane(
+                        queue,
+                        &yuv_textures.tex_uv,
+                        plane(yuv_planes.uv_offset),
+                        (chroma_width, chroma_height, 1),
+                        yuv_planes.uv_stride,
+                    ) && (yuv_planes.v_size == 0
+                        || write_plane(
+                            queue,
+                            &yuv_textures.tex_v,
+                            plane(yuv_planes.v_offset),
+                            (chroma_width, chroma_height, 1),
+                            yuv_planes.v_stride,
+                        ))
                 }
-            }
-            // Grayscale: single channel R8 format
-            PixelFormat::Gray8 => {
-                queue.write_texture(
-                    wgpu::ImageCopyTexture {
-                        texture: &yuv_textures.tex_y,
-                        mip_level: 0,
-                        origin: wgpu::Origin3d::ZERO,
-                        aspect: wgpu::TextureAspect::All,
-                    },
-                    buffer_data,
-                    wgpu::ImageDataLayout {
-                        offset: 0,
-                        bytes_per_row: Some(frame.stride),
-                        rows_per_image: None,
-                    },
-                    wgpu::Extent3d {
-                        width: frame.width,
-                        height: frame.height,
-                        depth_or_array_layers: 1,
-                    },
-                );
-            }
+                None => false,
+            },
+            // Grayscale: single channel R8 (Gray8) or RG8 holding 16-bit LE samples (Gray16)
+            PixelFormat::Gray8 | PixelFormat::Gray16 => write_plane(
+                queue,
+                &yuv_textures.tex_y,
+                buffer_data,
+                (frame.width, frame.height, luma_texel_bytes),
+                frame.stride,
+            ),
             // RGB24: Should have been converted to RGBA by GStreamer pipeline
             // If it arrives here, treat similarly to RGBA but with 3 bytes per pixel
             PixelFormat::RGB24 => {
@@ -1221,12 +1521,39 @@ impl VideoPipeline {
                 tracing::warn!("upload_yuv_and_convert called for RGBA frame");
                 return;
             }
+        };
+        if !uploaded {
+            // Keep showing the previous frame rather than converting a partial upload
+            return;
         }
+        let luma_data = match (frame.format, frame.yuv_planes.as_ref()) {
+            (PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420, Some(yuv_planes)) => {
+                plane(yuv_planes.y_offset)
+            }
+            _ => buffer_data,
+        };
+        record_luma_upload(
+            &yuv_textures.tex_y,
+            luma_data,
+            (luma_width, frame.height, luma_texel_bytes),
+            frame.stride,
+        );
+        set_gpu_path(GpuPath::Conversion(frame.format));
 
         // Update uniform buffer with conversion parameters
         // Use the PixelFormat method to get format code
         let format_code = frame.format.gpu_format_code();
 
+        // Gray16 black/white points: the frame's own range when auto-stretching
+        let (gray_min, gray_max) = match frame.format {
+            PixelFormat::Gray16 if frame.gray_mapping.auto_stretch => {
+                gray16_range(buffer_data, frame.width, frame.height, frame.stride)
+                    .map(|(min, max)| (min as f32 / 65535.0, max as f32 / 65535.0))
+                    .unwrap_or((0.0, 1.0))
+            }
+            _ => (0.0, 1.0),
+        };
+
         let params = YuvConvertParams {
             width: frame.width,
             height: frame.height,
@@ -1234,17 +1561,20 @@ impl VideoPipeline {
             y_stride: frame.stride,
             uv_stride: frame.yuv_planes.as_ref().map(|p| p.uv_stride).unwrap_or(0),
             v_stride: frame.yuv_planes.as_ref().map(|p| p.v_stride).unwrap_or(0),
-            _pad: [0, 0],
+            gray_min,
+            gray_max,
+            false_color: frame.gray_mapping.false_color as u32,
+            _pad: [0; 3],
         };
 
         if let Some(ref uniform_buffer) = self.yuv_uniform_buffer {
             queue.write_buffer(uniform_buffer, 0, bytemuck::cast_slice(&[params]));
         }
 
-        // Create output texture view for storage binding
+        // Output texture view (storage binding or render target)
         let output_view = output_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
-        // Create bind group for compute shader
+        // Create bind group for the conversion shader
         let bind_group_layout = match &self.yuv_bind_group_layout {
             Some(layout) => layout,
             None => {
@@ -1253,63 +1583,88 @@ impl VideoPipeline {
             }
         };
 
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&yuv_textures.tex_y_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(&yuv_textures.tex_uv_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::TextureView(&yuv_textures.tex_v_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: self
+                    .yuv_uniform_buffer
+                    .as_ref()
+                    .unwrap()
+                    .as_entire_binding(),
+            },
+        ];
+        if self.yuv_conversion == crate::gpu::ConversionPath::Compute {
+            entries.push(wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(&output_view),
+            });
+        }
         let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("yuv_convert_bind_group"),
             layout: bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&yuv_textures.tex_y_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(&yuv_textures.tex_uv_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::TextureView(&yuv_textures.tex_v_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 3,
-                    resource: wgpu::BindingResource::TextureView(&output_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 4,
-                    resource: self
-                        .yuv_uniform_buffer
-                        .as_ref()
-                        .unwrap()
-                        .as_entire_binding(),
-                },
-            ],
+            entries: &entries,
         });
 
-        // Dispatch compute shader
-        let compute_pipeline = match &self.yuv_compute_pipeline {
-            Some(pipeline) => pipeline,
-            None => {
-                tracing::error!("YUV compute pipeline not initialized");
-                return;
-            }
-        };
-
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("yuv_convert_encoder"),
         });
 
-        {
-            let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("yuv_convert_pass"),
-                timestamp_writes: None,
-            });
+        match self.yuv_conversion {
+            crate::gpu::ConversionPath::Compute => {
+                let Some(compute_pipeline) = &self.yuv_compute_pipeline else {
+                    tracing::error!("YUV compute pipeline not initialized");
+                    return;
+                };
+                let mut compute_pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some("yuv_convert_pass"),
+                    timestamp_writes: None,
+                });
 
-            compute_pass.set_pipeline(compute_pipeline);
-            compute_pass.set_bind_group(0, &bind_group, &[]);
+                compute_pass.set_pipeline(compute_pipeline);
+                compute_pass.set_bind_group(0, &bind_group, &[]);
 
-            // Dispatch: workgroup size is 16x16, so divide and round up
-            let workgroup_x = frame.width.div_ceil(16);
-            let workgroup_y = frame.height.div_ceil(16);
-            compute_pass.dispatch_workgroups(workgroup_x, workgroup_y, 1);
+                // Dispatch: workgroup size is 16x16, so divide and round up
+                let workgroup_x = frame.width.div_ceil(16);
+                let workgroup_y = frame.height.div_ceil(16);
+                compute_pass.dispatch_workgroups(workgroup_x, workgroup_y, 1);
+            }
+            crate::gpu::ConversionPath::Fragment => {
+                let Some(render_pipeline) = &self.yuv_render_pipeline else {
+                    tracing::error!("YUV render pipeline not initialized");
+                    return;
+                };
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("yuv_convert_render_pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &output_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                // One fullscreen triangle covers every output pixel
+                render_pass.set_pipeline(render_pipeline);
+                render_pass.set_bind_group(0, &bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
         }
 
         queue.submit(std::iter::once(encoder.finish()));
@@ -1346,16 +1701,8 @@ impl VideoPipeline {
 
         // Calculate texture dimensions based on format
         let (y_width, y_height) = (width, height);
-        let (uv_width, uv_height) = match format {
-            // Semi-planar 4:2:0 formats: UV plane at half resolution
-            PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => (width / 2, height / 2),
-            // Packed 4:2:2 formats: 2 pixels per texel, no separate UV plane
-            PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
-                (width / 2, height)
-            }
-            // Gray8, RGB24, RGBA: no UV plane (dummy 1x1)
-            PixelFormat::Gray8 | PixelFormat::RGB24 | PixelFormat::RGBA => (1, 1),
-        };
+        // Chroma at half resolution, rounded up for odd sizes (dummy 1x1 without chroma)
+        let (uv_width, uv_height) = upload_layout::chroma_size(format, width, height);
 
         // Y plane texture format
         let y_format = match format {
@@ -1365,6 +1712,8 @@ impl VideoPipeline {
             }
             // RGBA, RGB24: full RGBA texture
             PixelFormat::RGBA | PixelFormat::RGB24 => wgpu::TextureFormat::Rgba8Unorm,
+            // Gray16: low/high bytes in R/G, recombined in the shader
+            PixelFormat::Gray16 => wgpu::TextureFormat::Rg8Unorm,
             // Y plane or grayscale: single channel
             _ => wgpu::TextureFormat::R8Unorm,
         };
@@ -1378,12 +1727,7 @@ impl VideoPipeline {
         };
 
         // Calculate Y texture width (packed formats store 2 pixels per texel)
-        let y_tex_width = match format {
-            PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
-                y_width / 2
-            }
-            _ => y_width,
-        };
+        let (y_tex_width, _) = upload_layout::luma_texels(format, y_width);
 
         // Create Y texture
         let tex_y = device.create_texture(&wgpu::TextureDescriptor {
@@ -1450,6 +1794,7 @@ impl VideoPipeline {
                 format,
             },
         );
+        self.update_texture_memory();
 
         tracing::debug!(
             video_id,
@@ -1463,16 +1808,22 @@ impl VideoPipeline {
     /// Get or create a filter-specific binding for a video
     /// Creates a unique binding per (video_id, filter_mode) combination
     /// This allows sharing the source texture while having different filter uniforms
+    /// A binding made with the other sampler is replaced
     fn get_or_create_binding(
         &mut self,
         device: &wgpu::Device,
         video_id: u64,
         filter_mode: u32,
+        nearest: bool,
     ) -> Option<&FilterBinding> {
         let key = (video_id, filter_mode);
 
         // Check if binding already exists
-        if self.bindings.contains_key(&key) {
+        if self
+            .bindings
+            .get(&key)
+            .is_some_and(|binding| binding.nearest == nearest)
+        {
             return self.bindings.get(&key);
         }
 
@@ -1497,7 +1848,11 @@ impl VideoPipeline {
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    resource: wgpu::BindingResource::Sampler(if nearest {
+                        &self.sampler_nearest
+                    } else {
+                        &self.sampler
+                    }),
                 },
                 wgpu::BindGroupEntry {
                     binding: 2,
@@ -1511,6 +1866,7 @@ impl VideoPipeline {
             FilterBinding {
                 bind_group,
                 viewport_buffer,
+                nearest,
             },
         );
 
@@ -1588,6 +1944,7 @@ impl VideoPipeline {
                     viewport_buffer: viewport_buffer_1,
                     width,
                     height,
+                    bytes: texture_bytes(&texture_1),
                 }));
 
             // Create intermediate texture 2
@@ -1643,15 +2000,40 @@ impl VideoPipeline {
                     viewport_buffer: viewport_buffer_2,
                     width,
                     height,
+                    bytes: texture_bytes(&texture_2),
                 }));
+            self.update_texture_memory();
         }
     }
 
+    /// Publish the memory held by this pipeline's textures for insights
+    fn update_texture_memory(&self) {
+        let sources: u64 = self
+            .textures
+            .values()
+            .map(|tex| texture_bytes(&tex.texture))
+            .sum();
+        let yuv_planes: u64 = self
+            .yuv_textures
+            .values()
+            .map(|yuv| {
+                texture_bytes(&yuv.tex_y) + texture_bytes(&yuv.tex_uv) + texture_bytes(&yuv.tex_v)
+            })
+            .sum();
+        let blur: u64 = [&self.blur_intermediate_1, &self.blur_intermediate_2]
+            .iter()
+            .filter_map(|intermediate| intermediate.borrow().as_ref().map(|i| i.bytes))
+            .sum();
+        TEXTURE_MEMORY.store(sources + yuv_planes + blur, Ordering::Relaxed);
+    }
+
     /// Render the video primitive.
     ///
     /// # Arguments
     /// * `video_id` - Unique identifier for the video source
     /// * `filter_mode` - Filter to apply (0 = none, 1+ = various filters)
+    /// * `color_effect` - Selects the pipeline variant with the color effect stage
+    /// * `scaling` - Selects the bicubic pipeline variants (once they were created)
     /// * `encoder` - GPU command encoder
     /// * `target` - Render target texture view
     /// * `clip_bounds` - Clipped bounds for scissor rect (visible portion after scroll clipping)
@@ -1660,6 +2042,8 @@ impl VideoPipeline {
         &self,
         video_id: u64,
         filter_mode: u32,
+        color_effect: ColorEffect,
+        scaling: ScalingQuality,
         encoder: &mut wgpu::CommandEncoder,
         target: &wgpu::TextureView,
         clip_bounds: &Rectangle<u32>,
@@ -1841,10 +2225,217 @@ impl VideoPipeline {
                     clip_bounds.height,
                 );
 
-                render_pass.set_pipeline(&self.pipeline_rgba);
+                // Without an effect the variant with no effect stage keeps the passthrough cost
+                let effect = color_effect != ColorEffect::None;
+                let pipeline = match (&self.pipelines_rgba_bicubic, scaling) {
+                    (Some([plain, with_effect]), ScalingQuality::Bicubic) => {
+                        if effect {
+                            with_effect
+                        } else {
+                            plain
+                        }
+                    }
+                    _ if effect => &self.pipeline_rgba_effect,
+                    _ => &self.pipeline_rgba,
+                };
+                render_pass.set_pipeline(pipeline);
                 render_pass.set_bind_group(0, &binding.bind_group, &[]);
                 render_pass.draw(0..6, 0..1);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Copy `texture` back to the CPU as tightly packed rows
+    async fn read_texture(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+        texel_bytes: u32,
+    ) -> Vec<u8> {
+        let size = texture.size();
+        let row_bytes = size.width * texel_bytes;
+        // Copies need rows at a 256-byte alignment
+        let padded_row = row_bytes.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback_staging"),
+            size: (padded_row * size.height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("readback_encoder"),
+        });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &staging,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            size,
+        );
+        queue.submit(std::iter::once(encoder.finish()));
+        let slice = staging.slice(..);
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        let _ = device.poll(wgpu::Maintain::Wait);
+        receiver.await.unwrap().expect("readback failed");
+        let data = slice.get_mapped_range();
+        data.chunks(padded_row as usize)
+            .flat_map(|row| &row[..row_bytes as usize])
+            .copied()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_unaligned_frame_is_not_sheared() {
+        // This test requires a GPU, so it may be skipped in CI
+        let (device, queue, _) =
+            match crate::gpu::create_low_priority_compute_device("upload_test").await {
+                Ok(device) => device,
+                Err(e) => {
+                    println!("Skipping test (no GPU): {}", e);
+                    return;
+                }
+            };
+
+        // 642×482 Gray8 with rows padded to 704 bytes; padding bytes are 0xFF
+        let (width, height, stride) = (642u32, 482u32, 704u32);
+        let mut data = vec![0xFF; (stride * height) as usize];
+        for y in 0..height {
+            for x in 0..width {
+                data[(y * stride + x) as usize] = ((x + y) % 251) as u8;
+            }
+        }
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("upload_test"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        assert!(write_plane(
+            &queue,
+            &texture,
+            &data,
+            (width, height, 1),
+            stride
+        ));
+
+        // Every row starts with its own first pixel and carries no padding
+        let uploaded = read_texture(&device, &queue, &texture, 1).await;
+        for (y, row) in uploaded.chunks(width as usize).enumerate() {
+            assert!(
+                row.iter()
+                    .enumerate()
+                    .all(|(x, &v)| v == ((x + y) % 251) as u8),
+                "row {} is sheared",
+                y
+            );
+        }
+    }
+
+    /// A 64×32 frame in `format` with a gradient in every channel
+    fn gradient_frame(format: PixelFormat) -> VideoFrame {
+        let (width, height) = (64u32, 32u32);
+        let (stride, yuv_planes, data) = match format {
+            PixelFormat::NV12 => {
+                let y_size = (width * height) as usize;
+                let uv_size = y_size / 2;
+                let mut data: Vec<u8> = (0..y_size).map(|i| (16 + i % 220) as u8).collect();
+                data.extend((0..uv_size).map(|i| (i * 7 % 256) as u8));
+                let planes = YuvPlanes {
+                    y_offset: 0,
+                    y_size,
+                    uv_offset: y_size,
+                    uv_size,
+                    uv_stride: width,
+                    v_offset: 0,
+                    v_size: 0,
+                    v_stride: 0,
+                };
+                (width, Some(planes), data)
+            }
+            _ => {
+                let stride = width * 2;
+                let data = (0..(stride * height) as usize)
+                    .map(|i| (i * 5 % 256) as u8)
+                    .collect();
+                (stride, None, data)
+            }
+        };
+        VideoFrame {
+            id: 1,
+            width,
+            height,
+            data: FrameData::from_bytes(data.into()),
+            format,
+            stride,
+            yuv_planes,
+            gray_mapping: GrayMapping::default(),
+            captured_at: std::time::Instant::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fragment_conversion_matches_compute() {
+        // This test requires a GPU, so it may be skipped in CI
+        let (device, queue, _) =
+            match crate::gpu::create_low_priority_compute_device("conversion_test").await {
+                Ok(device) => device,
+                Err(e) => {
+                    println!("Skipping test (no GPU): {}", e);
+                    return;
+                }
+            };
+        // The path CAMERA_FORCE_FRAGMENT_CONVERSION selects on any GPU
+        let fragment_path = crate::gpu::conversion_path_for(&device.limits(), true);
+        assert_eq!(fragment_path, crate::gpu::ConversionPath::Fragment);
+
+        let surface_format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut compute = VideoPipeline::with_conversion(
+            &device,
+            surface_format,
+            crate::gpu::ConversionPath::Compute,
+        );
+        let mut fragment = VideoPipeline::with_conversion(&device, surface_format, fragment_path);
+        for format in [PixelFormat::NV12, PixelFormat::YUYV] {
+            let frame = gradient_frame(format);
+            // Slow first uploads (shader compilation) must not skip the next frame
+            compute.last_upload_duration.set(std::time::Duration::ZERO);
+            fragment.last_upload_duration.set(std::time::Duration::ZERO);
+            compute.upload(&device, &queue, frame.clone());
+            fragment.upload(&device, &queue, frame);
+            let expected = read_texture(&device, &queue, &compute.textures[&1].texture, 4).await;
+            let actual = read_texture(&device, &queue, &fragment.textures[&1].texture, 4).await;
+            assert_eq!(expected.len(), actual.len());
+            for (i, (e, a)) in expected.iter().zip(&actual).enumerate() {
+                assert!(
+                    e.abs_diff(*a) <= 1,
+                    "{:?} byte {}: fragment {} vs compute {}",
+                    format,
+                    i,
+                    a,
+                    e
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: GPL-3.0-only
 // GPU shader for direct RGBA texture rendering with object-fit: cover support
 // Filter functions are prepended by the Rust code from shaders/filters.wgsl
+// sample_video() is appended by the Rust code: the sampler alone, or
+// video_shader_bicubic.wgsl for bicubic scaling
 
 @group(0) @binding(0)
 var texture_rgba: texture_2d<f32>;
@@ -20,8 +22,14 @@ struct ViewportUniform {
     crop_uv_max: vec2<f32>,     // Crop UV max (u_max, v_max) - normalized 0-1
     zoom_level: f32,            // Zoom level (1.0 = no zoom, 2.0 = 2x zoom)
     rotation: u32,              // Sensor rotation: 0=None, 1=90CW, 2=180, 3=270CW
+    color_effect: u32,          // Color effect applied after the filter (0 = none)
+    focus_peaking: u32,         // 1 = highlight in-focus edges
 }
 
+// Set only on the pipeline variant used while a color effect is active, so the
+// effect stage is compiled out of the default pipeline
+override color_effects_enabled: bool = false;
+
 @group(0) @binding(2)
 var<uniform> viewport: ViewportUniform;
 
@@ -147,8 +155,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         tex_coords = (tex_coords - vec2<f32>(0.5, 0.5)) * inv_zoom + vec2<f32>(0.5, 0.5);
     }
 
-    // Sample RGBA texture
-    var pixel = textureSample(texture_rgba, sampler_video, tex_coords);
+    // Sample RGBA texture with the selected scaling filter
+    var pixel = sample_video(tex_coords);
     var color = pixel.rgb;
 
     // Apply filter using shared filter function (filters 0-12)
@@ -175,6 +183,18 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         color = vec3<f32>(final_val, final_val, final_val);
     }
 
+    if (color_effects_enabled) {
+        color = apply_color_effect(color, viewport.color_effect);
+    }
+
+    // Focus peaking: tint strong edges of the source image, so the highlight
+    // follows the focus and not the filter
+    if (viewport.focus_peaking == 1u) {
+        let texel_size = 1.0 / vec2<f32>(textureDimensions(texture_rgba));
+        let edge = sobel_edge_rgba(tex_coords, texel_size);
+        color = mix(color, vec3<f32>(1.0, 0.1, 0.1), smoothstep(0.3, 0.6, edge));
+    }
+
     // Calculate alpha for rounded corners
     var alpha = pixel.a;
     if (viewport.corner_radius > 0.0) {
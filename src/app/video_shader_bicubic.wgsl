@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// Catmull-Rom sampling for the preview, appended to video_shader.wgsl when
+// bicubic scaling is selected (the default shader samples with the sampler)
+//
+// The 4x4 texel footprint is read with 9 bilinear taps: the two middle
+// texels of each axis are merged into one tap placed between them by their
+// weights, so the sampler still has to be a linear one.
+
+fn sample_video(uv: vec2<f32>) -> vec4<f32> {
+    let tex_size = vec2<f32>(textureDimensions(texture_rgba));
+    let sample_pos = uv * tex_size;
+    let center = floor(sample_pos - 0.5) + 0.5;
+    let f = sample_pos - center;
+
+    // Catmull-Rom weights of the four texels along each axis
+    let w0 = f * (-0.5 + f * (1.0 - 0.5 * f));
+    let w1 = 1.0 + f * f * (-2.5 + 1.5 * f);
+    let w2 = f * (0.5 + f * (2.0 - 1.5 * f));
+    let w3 = f * f * (-0.5 + 0.5 * f);
+
+    let w12 = w1 + w2;
+    let pos0 = (center - 1.0) / tex_size;
+    let pos12 = (center + w2 / w12) / tex_size;
+    let pos3 = (center + 2.0) / tex_size;
+
+    var result = vec4<f32>(0.0);
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos0.x, pos0.y), 0.0) * w0.x * w0.y;
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos12.x, pos0.y), 0.0) * w12.x * w0.y;
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos3.x, pos0.y), 0.0) * w3.x * w0.y;
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos0.x, pos12.y), 0.0) * w0.x * w12.y;
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos12.x, pos12.y), 0.0) * w12.x * w12.y;
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos3.x, pos12.y), 0.0) * w3.x * w12.y;
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos0.x, pos3.y), 0.0) * w0.x * w3.y;
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos12.x, pos3.y), 0.0) * w12.x * w3.y;
+    result += textureSampleLevel(texture_rgba, sampler_video, vec2<f32>(pos3.x, pos3.y), 0.0) * w3.x * w3.y;
+
+    // The negative lobes overshoot at hard edges
+    return clamp(result, vec4<f32>(0.0), vec4<f32>(1.0));
+}
@@ -8,10 +8,12 @@
 //! 3. Persistent textures across frames
 //! 4. Native RGBA format for simplified processing
 
+use crate::app::pixel_probe::PreviewMapping;
 use crate::app::state::{FilterType, Message};
-use crate::app::video_primitive::{VideoFrame, VideoPrimitive};
+use crate::app::video_primitive::{GrayMapping, VideoFrame, VideoPrimitive};
 use crate::backends::camera::types::{CameraFrame, PixelFormat};
-use cosmic::iced::advanced::widget::Tree;
+use crate::config::{ColorEffect, ScalingQuality};
+use cosmic::iced::advanced::widget::{Tree, tree};
 use cosmic::iced::advanced::{Clipboard, Shell, Widget, layout};
 use cosmic::iced::event::Status;
 use cosmic::iced::mouse;
@@ -50,6 +52,16 @@ pub struct VideoWidgetConfig {
     pub zoom_level: f32,
     /// Whether scroll wheel zoom is enabled
     pub scroll_zoom_enabled: bool,
+    /// Display mapping for grayscale (GREY/Y16) frames
+    pub gray_mapping: GrayMapping,
+    /// Color effect applied on top of the filter
+    pub color_effect: ColorEffect,
+    /// Highlight in-focus edges
+    pub focus_peaking: bool,
+    /// Filter used to scale the frame to the widget
+    pub scaling: ScalingQuality,
+    /// Report the frame pixel under the cursor (pixel probe)
+    pub pixel_probe: bool,
 }
 
 /// Video widget that renders camera frames using a custom GPU primitive
@@ -61,6 +73,16 @@ pub struct VideoWidget {
     content_fit: VideoContentFit,
     /// Enable scroll wheel zoom (only for main camera preview, not filter picker)
     scroll_zoom_enabled: bool,
+    /// Report the frame pixel under the cursor
+    pixel_probe: bool,
+    /// Frame width and height
+    frame_size: (u32, u32),
+}
+
+/// Pixel last reported to the pixel probe, so unchanged positions aren't re-sent
+#[derive(Debug, Default)]
+struct ProbeState {
+    reported: Option<(u32, u32)>,
 }
 
 impl VideoWidget {
@@ -77,6 +99,9 @@ impl VideoWidget {
         primitive.rotation = config.rotation;
         primitive.crop_uv = config.crop_uv;
         primitive.zoom_level = config.zoom_level;
+        primitive.color_effect = config.color_effect;
+        primitive.focus_peaking = config.focus_peaking;
+        primitive.scaling = config.scaling;
 
         // Calculate aspect ratio from frame dimensions, adjusted for crop and rotation
         // For 90° and 270° rotations, swap width and height
@@ -120,6 +145,7 @@ impl VideoWidget {
                     }
                     PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => frame.width, // Y plane stride
                     PixelFormat::Gray8 => frame.width, // 1 byte per pixel
+                    PixelFormat::Gray16 => frame.width * 2, // 2 bytes per pixel
                 }
             };
 
@@ -131,6 +157,8 @@ impl VideoWidget {
                 format: frame.format,
                 stride,
                 yuv_planes: frame.yuv_planes,
+                gray_mapping: config.gray_mapping,
+                captured_at: frame.captured_at,
             };
 
             primitive.update_frame(video_frame);
@@ -143,11 +171,44 @@ impl VideoWidget {
             aspect_ratio,
             content_fit: config.content_fit,
             scroll_zoom_enabled: config.scroll_zoom_enabled,
+            pixel_probe: config.pixel_probe,
+            frame_size: (frame.width, frame.height),
+        }
+    }
+
+    /// Frame pixel under the cursor, if it is over the frame
+    fn probed_pixel(&self, bounds: Rectangle, cursor: mouse::Cursor) -> Option<(u32, u32)> {
+        let position = cursor.position_in(bounds)?;
+        if bounds.width <= 0.0 || bounds.height <= 0.0 {
+            return None;
         }
+        let mapping = PreviewMapping {
+            mirror: self.primitive.mirror_horizontal,
+            rotation: self.primitive.rotation,
+            crop_uv: self.primitive.crop_uv,
+            cover_aspect: match self.content_fit {
+                VideoContentFit::Contain => None,
+                VideoContentFit::Cover => Some(bounds.width / bounds.height),
+            },
+            zoom_level: self.primitive.zoom_level,
+        };
+        mapping.source_pixel(
+            (position.x / bounds.width, position.y / bounds.height),
+            self.frame_size.0,
+            self.frame_size.1,
+        )
     }
 }
 
 impl Widget<crate::app::Message, Theme, Renderer> for VideoWidget {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<ProbeState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(ProbeState::default())
+    }
+
     fn size(&self) -> Size<Length> {
         Size::new(self.width, self.height)
     }
@@ -189,7 +250,7 @@ impl Widget<crate::app::Message, Theme, Renderer> for VideoWidget {
 
     fn on_event(
         &mut self,
-        _tree: &mut Tree,
+        tree: &mut Tree,
         event: Event,
         layout: layout::Layout<'_>,
         cursor: mouse::Cursor,
@@ -198,13 +259,35 @@ impl Widget<crate::app::Message, Theme, Renderer> for VideoWidget {
         shell: &mut Shell<'_, Message>,
         _viewport: &Rectangle,
     ) -> Status {
+        let bounds = layout.bounds();
+
+        // Pixel probe: report the pixel under the cursor, lock it on click
+        if self.pixel_probe {
+            let state = tree.state.downcast_mut::<ProbeState>();
+            match event {
+                Event::Mouse(mouse::Event::CursorMoved { .. } | mouse::Event::CursorLeft) => {
+                    let pixel = self.probed_pixel(bounds, cursor);
+                    if pixel != state.reported {
+                        state.reported = pixel;
+                        shell.publish(Message::PixelProbeHovered(pixel));
+                    }
+                }
+                Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                    if let Some(pixel) = self.probed_pixel(bounds, cursor) {
+                        shell.publish(Message::TogglePixelProbeLock(pixel));
+                        return Status::Captured;
+                    }
+                }
+                _ => {}
+            }
+        }
+
         // Only handle scroll zoom if enabled (photo mode main preview)
         if !self.scroll_zoom_enabled {
             return Status::Ignored;
         }
 
         // Check if cursor is over the widget bounds
-        let bounds = layout.bounds();
         if !cursor.is_over(bounds) {
             return Status::Ignored;
         }
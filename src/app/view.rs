@@ -323,6 +323,8 @@ impl AppModel {
                     self.build_qr_overlay(),
                     // Privacy cover warning overlay (centered)
                     self.build_privacy_warning(),
+                    // Decoder names on each half during a decoder comparison
+                    self.build_decoder_comparison_labels(),
                     // Top bar aligned to top (no extra padding - row has its own padding)
                     widget::container(top_bar)
                         .width(Length::Fill)
@@ -358,11 +360,61 @@ impl AppModel {
                 self.build_qr_overlay(),
                 // Privacy cover warning overlay (centered)
                 self.build_privacy_warning(),
+                // Decoder names on each half during a decoder comparison
+                self.build_decoder_comparison_labels(),
                 widget::container(top_bar)
                     .width(Length::Fill)
                     .align_y(cosmic::iced::alignment::Vertical::Top)
             ];
 
+            // Summary of the recording that just ended (bottom left, until dismissed)
+            if let Some(summary) = self.build_recording_summary() {
+                preview_stack = preview_stack.push(
+                    widget::container(summary)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .align_x(cosmic::iced::alignment::Horizontal::Left)
+                        .align_y(cosmic::iced::alignment::Vertical::Bottom)
+                        .padding(cosmic::theme::spacing().space_s),
+                );
+            }
+
+            // Pixel probe readout (bottom right, clear of the recording summary)
+            if let Some(readout) = self.build_pixel_probe_readout() {
+                preview_stack = preview_stack.push(
+                    widget::container(readout)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .align_x(cosmic::iced::alignment::Horizontal::Right)
+                        .align_y(cosmic::iced::alignment::Vertical::Bottom)
+                        .padding(cosmic::theme::spacing().space_s),
+                );
+            }
+
+            // Frame number and PTS (top left, below the top bar)
+            if let Some(counter) = self.build_frame_counter_overlay() {
+                preview_stack = preview_stack.push(
+                    widget::container(counter)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .align_x(cosmic::iced::alignment::Horizontal::Left)
+                        .align_y(cosmic::iced::alignment::Vertical::Top)
+                        .padding([48, 0, 0, cosmic::theme::spacing().space_s]),
+                );
+            }
+
+            // Photo burst progress (centered, above the zoom label)
+            if let Some(progress) = self.build_still_burst_progress() {
+                preview_stack = preview_stack.push(
+                    widget::container(progress)
+                        .width(Length::Fill)
+                        .height(Length::Fill)
+                        .align_x(cosmic::iced::alignment::Horizontal::Center)
+                        .align_y(cosmic::iced::alignment::Vertical::Bottom)
+                        .padding([0, 0, 48, 0]),
+                );
+            }
+
             // Add zoom label overlapping bottom of preview (centered above capture button)
             if show_zoom_label {
                 preview_stack = preview_stack.push(
@@ -396,6 +448,24 @@ impl AppModel {
         // Wrap content in a stack so we can overlay the picker
         let mut main_stack = cosmic::iced::widget::stack![content];
 
+        // Value set by an exposure or gain shortcut (centered, in every layout)
+        if let Some((_, text)) = &self.control_nudge {
+            let spacing = cosmic::theme::spacing();
+            main_stack = main_stack.push(
+                widget::container(
+                    widget::container(
+                        widget::text(text.as_str())
+                            .size(20)
+                            .font(cosmic::font::bold()),
+                    )
+                    .padding([spacing.space_xs, spacing.space_m])
+                    .style(overlay_container_style),
+                )
+                .center_x(Length::Fill)
+                .center_y(Length::Fill),
+            );
+        }
+
         // Add iOS-style format picker overlay if visible
         if self.format_picker_visible {
             main_stack = main_stack.push(self.build_format_picker());
@@ -421,15 +491,17 @@ impl AppModel {
             main_stack = main_stack.push(self.build_tools_menu());
         }
 
-        // Wrap everything in a themed background container
-        widget::container(main_stack)
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .style(|theme| widget::container::Style {
-                background: Some(Background::Color(theme.cosmic().bg_color().into())),
-                ..Default::default()
-            })
-            .into()
+        // Wrap everything in a themed background container, with toasts on top
+        widget::toaster(
+            &self.toasts,
+            widget::container(main_stack)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(|theme| widget::container::Style {
+                    background: Some(Background::Color(theme.cosmic().bg_color().into())),
+                    ..Default::default()
+                }),
+        )
     }
 
     /// Build the top bar with recording indicator and format button
@@ -768,6 +840,118 @@ impl AppModel {
             .into()
     }
 
+    /// Build the photo burst progress label (None while no burst runs)
+    fn build_still_burst_progress(&self) -> Option<Element<'_, Message>> {
+        let burst = self.still_burst.as_ref()?;
+        let spacing = cosmic::theme::spacing();
+        Some(
+            widget::container(widget::text::body(fl!(
+                "still-burst-progress",
+                taken = burst.taken(),
+                count = burst.count()
+            )))
+            .padding([spacing.space_xxs, spacing.space_s])
+            .style(overlay_container_style)
+            .into(),
+        )
+    }
+
+    /// Build the pixel probe readout (None while the probe is off)
+    ///
+    /// Shows the probed pixel's position, a swatch of its color, its RGB value
+    /// and, for YUV and 16-bit gray frames, what the camera sent.
+    fn build_pixel_probe_readout(&self) -> Option<Element<'_, Message>> {
+        let probe = &self.pixel_probe;
+        if !probe.enabled {
+            return None;
+        }
+        let spacing = cosmic::theme::spacing();
+        let mono = |text: String| widget::text::body(text).font(cosmic::font::mono());
+
+        let Some(sample) = probe.sample else {
+            return Some(
+                widget::container(widget::text::body(fl!("pixel-probe-hint")))
+                    .padding([spacing.space_xxs, spacing.space_s])
+                    .style(overlay_container_style)
+                    .into(),
+            );
+        };
+
+        let [r, g, b] = sample.rgb;
+        let swatch =
+            widget::container(widget::Space::new(Length::Fixed(24.0), Length::Fixed(24.0))).style(
+                move |theme: &cosmic::Theme| widget::container::Style {
+                    background: Some(Background::Color(Color::from_rgb8(r, g, b))),
+                    border: cosmic::iced::Border {
+                        radius: theme.cosmic().corner_radii.radius_xs.into(),
+                        width: 1.0,
+                        color: theme.cosmic().on_bg_color().into(),
+                    },
+                    ..Default::default()
+                },
+            );
+
+        let position = if probe.locked.is_some() {
+            fl!("pixel-probe-locked", x = sample.x, y = sample.y)
+        } else {
+            fl!("pixel-probe-position", x = sample.x, y = sample.y)
+        };
+        let mut values = widget::column()
+            .push(widget::text::caption(position))
+            .push(mono(format!(
+                "RGB {:3} {:3} {:3}  {}",
+                r,
+                g,
+                b,
+                sample.hex()
+            )));
+        if let Some([y, u, v]) = sample.yuv {
+            values = values.push(mono(format!("YUV {:3} {:3} {:3}", y, u, v)));
+        }
+        if let Some(raw) = sample.raw16 {
+            values = values.push(mono(format!("Y16 {:5}", raw)));
+        }
+        if let Some(padding) = sample.padding {
+            values = values.push(mono(fl!(
+                "pixel-probe-padding",
+                bytes = padding.bytes,
+                head = padding.head_hex()
+            )));
+        }
+
+        Some(
+            widget::container(
+                widget::row()
+                    .push(swatch)
+                    .push(values)
+                    .spacing(spacing.space_xs)
+                    .align_y(Alignment::Center),
+            )
+            .padding([spacing.space_xxs, spacing.space_s])
+            .style(overlay_container_style)
+            .into(),
+        )
+    }
+
+    /// Build the frame counter overlay (None while it's off)
+    ///
+    /// Borrows the label kept up to date by the frame handler, so nothing is
+    /// formatted while drawing.
+    fn build_frame_counter_overlay(&self) -> Option<Element<'_, Message>> {
+        if !self.config.frame_counter_overlay || self.frame_counter_label.is_empty() {
+            return None;
+        }
+        let spacing = cosmic::theme::spacing();
+        Some(
+            widget::container(
+                widget::text::title4(self.frame_counter_label.as_str()).font(cosmic::font::mono()),
+            )
+            .padding([spacing.space_xxs, spacing.space_s])
+            .style(overlay_container_style)
+            .into(),
+        )
+    }
+
     /// Build the QR code overlay layer
     ///
     /// This creates an overlay that shows detected QR codes with bounding boxes
@@ -791,8 +975,8 @@ impl AppModel {
             VideoContentFit::Contain
         };
 
-        // File sources should never be mirrored - match the video widget behavior
-        let should_mirror = self.config.mirror_preview && !self.current_frame_is_file_source;
+        // Match the video widget's mirroring
+        let should_mirror = self.preview_mirrored();
 
         build_qr_overlay(
             &self.qr_detections,
@@ -805,7 +989,7 @@ impl AppModel {
 
     /// Build the tools menu overlay
     ///
-    /// Shows timer, aspect ratio, exposure, filter, and theatre mode buttons
+    /// Shows timer, aspect ratio, exposure, filter, copy and theatre mode buttons
     /// in a floating panel aligned to the top-right with large icon buttons in a 2-row grid.
     fn build_tools_menu(&self) -> Element<'_, Message> {
         let spacing = cosmic::theme::spacing();
@@ -895,6 +1079,16 @@ impl AppModel {
             filter_active,
         ));
 
+        // Copy frame button (processed like a saved photo)
+        if self.current_frame.is_some() {
+            buttons.push(self.build_tools_grid_button(
+                icon::from_name("edit-copy-symbolic").symbolic(true),
+                fl!("tools-copy-frame"),
+                Message::CopyFrameToClipboard,
+                false,
+            ));
+        }
+
         // Theatre mode button
         let theatre_icon = if self.theatre.enabled {
             "view-restore-symbolic"
@@ -1103,16 +1297,47 @@ impl AppModel {
 
     /// Build the privacy cover warning overlay
     ///
-    /// Shows a centered warning when the camera's privacy cover is closed.
+    /// Shows a centered warning when the camera's privacy cover is closed,
+    /// why the pipeline failed to start (with a switch to a decodable format
+    /// when the selected one has no decoder), or a dismissible hint when a
+    /// camera without a privacy control only delivers black frames.
     fn build_privacy_warning(&self) -> Element<'_, Message> {
-        if !self.privacy_cover_closed {
+        let (title, hint, dismissible) = if self.privacy_cover_closed {
+            (
+                fl!("privacy-cover-closed"),
+                fl!("privacy-cover-hint"),
+                false,
+            )
+        } else if let Some((missing, _)) = &self.missing_decoder
+            && self.pipeline_error.is_some()
+        {
+            let codec = missing.codec.short_description();
+            (
+                fl!("missing-decoder-title", codec = codec),
+                fl!(
+                    "missing-decoder-hint",
+                    codec = codec,
+                    packages = missing_decoder_packages(&missing.packages)
+                ),
+                false,
+            )
+        } else if let Some(err) = &self.pipeline_error {
+            let mut hint = pipeline_error_hint(err);
+            if self.safe_resolution.is_exhausted() {
+                hint.push('\n');
+                hint.push_str(&fl!("safe-resolution-exhausted"));
+            }
+            (fl!("pipeline-error-title"), hint, false)
+        } else if self.black_frames.show_hint() {
+            (fl!("black-frames-title"), fl!("black-frames-hint"), true)
+        } else {
             return widget::Space::new(Length::Fill, Length::Fill).into();
-        }
+        };
 
         let spacing = cosmic::theme::spacing();
 
         // Warning icon and text
-        let warning_content = widget::column()
+        let mut warning_content = widget::column()
             .push(
                 widget::icon(
                     icon::from_name("dialog-warning-symbolic")
@@ -1121,14 +1346,34 @@ impl AppModel {
                 )
                 .size(48),
             )
-            .push(
-                widget::text(fl!("privacy-cover-closed"))
-                    .size(20)
-                    .font(cosmic::font::bold()),
-            )
-            .push(widget::text(fl!("privacy-cover-hint")).size(14))
+            .push(widget::text(title).size(20).font(cosmic::font::bold()))
+            .push(widget::text(hint).size(14))
             .spacing(spacing.space_s)
             .align_x(Alignment::Center);
+        if dismissible {
+            warning_content = warning_content.push(
+                widget::button::standard(fl!("black-frames-dismiss"))
+                    .on_press(Message::DismissBlackFrameHint),
+            );
+        }
+        // Offer a format the camera also supports that can be decoded
+        if let Some((_, Some(alternative))) = &self.missing_decoder
+            && self.pipeline_error.is_some()
+            && let Some(index) = self
+                .available_formats
+                .iter()
+                .position(|format| format == alternative)
+        {
+            warning_content = warning_content.push(
+                widget::button::suggested(fl!(
+                    "missing-decoder-switch",
+                    codec = crate::media::Codec::from_fourcc(&alternative.pixel_format)
+                        .short_description(),
+                    format = alternative.to_string()
+                ))
+                .on_press(Message::PickerSelectFormat(index)),
+            );
+        }
 
         // Container with semi-transparent background
         let warning_box = widget::container(warning_content)
@@ -1160,6 +1405,56 @@ impl AppModel {
             .into()
     }
 
+    /// Build the decoder comparison labels
+    ///
+    /// Names the decoder of each half of the preview while a decoder
+    /// comparison is running. A mirrored preview shows the halves swapped.
+    fn build_decoder_comparison_labels(&self) -> Element<'_, Message> {
+        let Some(comparison) = crate::media::decoders::active_decoder_comparison() else {
+            return widget::Space::new(Length::Fill, Length::Fill).into();
+        };
+        let (left, right) = if self.preview_mirrored() {
+            (comparison.right, comparison.left)
+        } else {
+            (comparison.left, comparison.right)
+        };
+
+        let spacing = cosmic::theme::spacing();
+        let label = |name: &'static str| {
+            widget::container(widget::text::body(name).font(cosmic::font::mono()))
+                .padding([spacing.space_xxs, spacing.space_xs])
+                .style(|theme: &cosmic::Theme| {
+                    let cosmic = theme.cosmic();
+                    let bg = cosmic.bg_color();
+                    widget::container::Style {
+                        background: Some(Background::Color(Color::from_rgba(
+                            bg.red,
+                            bg.green,
+                            bg.blue,
+                            OVERLAY_BACKGROUND_ALPHA,
+                        ))),
+                        border: cosmic::iced::Border {
+                            radius: cosmic.corner_radii.radius_s.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }
+                })
+        };
+
+        widget::container(
+            widget::row()
+                .push(label(left))
+                .push(widget::horizontal_space())
+                .push(label(right)),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .align_y(cosmic::iced::alignment::Vertical::Center)
+        .padding(spacing.space_s)
+        .into()
+    }
+
     /// Build the burst mode progress overlay
     ///
     /// Shows status text, frame count, and progress bar during burst mode capture/processing.
@@ -1298,3 +1593,48 @@ impl AppModel {
             .into()
     }
 }
+
+/// "a, b or c" for the install hint of a missing decoder
+fn missing_decoder_packages(packages: &[&str]) -> String {
+    match packages {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => fl!(
+            "missing-decoder-packages",
+            packages = rest.join(", "),
+            last = *last
+        ),
+    }
+}
+
+/// What the user can do about a pipeline that failed to start
+fn pipeline_error_hint(err: &crate::media::decoders::PipelineError) -> String {
+    use crate::media::decoders::{PipelineError, plugin_package};
+
+    match err {
+        PipelineError::SourceUnavailable(_) => fl!("pipeline-error-source"),
+        PipelineError::CapsNegotiationFailed(_) => fl!("pipeline-error-caps"),
+        PipelineError::DecoderInstantiationFailed { name } => match plugin_package(name) {
+            Some(package) => fl!(
+                "pipeline-error-decoder-package",
+                decoder = name.as_str(),
+                package = package
+            ),
+            None => fl!("pipeline-error-decoder", decoder = name.as_str()),
+        },
+        PipelineError::ElementMissing { name } => match plugin_package(name) {
+            Some(package) => fl!(
+                "pipeline-error-missing-package",
+                element = name.as_str(),
+                package = package
+            ),
+            None => fl!("pipeline-error-missing", element = name.as_str()),
+        },
+        PipelineError::ParseFailed(_) | PipelineError::StateChangeFailed(_) => {
+            fl!("pipeline-error-other")
+        }
+        PipelineError::FirstFrameTimeout { secs } => {
+            fl!("pipeline-error-timeout", seconds = secs)
+        }
+    }
+}
@@ -1,9 +1,17 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
-//! Audio device enumeration for PipeWire
+//! Audio device enumeration and level monitoring for PipeWire
 
+use gstreamer as gst;
+use gstreamer::prelude::*;
 use std::process::Command;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
+
+/// Quietest level shown on the input meter (dBFS)
+const METER_FLOOR_DB: f64 = -60.0;
+
+/// Interval between level updates
+const LEVEL_INTERVAL_MS: u64 = 100;
 
 /// Represents an audio input device
 #[derive(Debug, Clone)]
@@ -130,3 +138,112 @@ pub fn enumerate_audio_devices() -> Vec<AudioDevice> {
 
     devices
 }
+
+/// Map a dBFS level to a meter position between 0.0 and 1.0
+///
+/// Everything at or below [`METER_FLOOR_DB`] (including digital silence,
+/// reported as -inf) shows as an empty meter.
+pub fn db_to_meter(db: f64) -> f32 {
+    if !db.is_finite() {
+        return 0.0;
+    }
+    ((db - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0) as f32
+}
+
+/// Peak level (dBFS) of the loudest channel in a `level` element message
+pub fn peak_db(structure: &gst::StructureRef) -> Option<f64> {
+    if structure.name() != "level" {
+        return None;
+    }
+    let peaks = structure.get::<gst::glib::ValueArray>("peak").ok()?;
+    peaks
+        .iter()
+        .filter_map(|value| value.get::<f64>().ok())
+        .reduce(f64::max)
+}
+
+/// Live input level of a microphone
+///
+/// Runs `pipewiresrc ! level ! fakesink` and posts `level` element messages on
+/// its bus. Nothing is played back, so the preview stays silent.
+pub struct AudioLevelMonitor {
+    pipeline: gst::Pipeline,
+}
+
+impl AudioLevelMonitor {
+    /// Start monitoring an audio source (`None` = PipeWire default source)
+    pub fn start(serial: Option<&str>) -> Result<Self, String> {
+        gst::init().map_err(|e| format!("Failed to initialize GStreamer: {}", e))?;
+
+        let mut source_builder =
+            gst::ElementFactory::make("pipewiresrc").property("client-name", "camera-level-meter");
+        if let Some(serial) = serial {
+            source_builder = source_builder.property("target-object", serial);
+        }
+        let source = source_builder
+            .build()
+            .map_err(|e| format!("Failed to create audio source: {}", e))?;
+        let convert = gst::ElementFactory::make("audioconvert")
+            .build()
+            .map_err(|e| format!("Failed to create audioconvert: {}", e))?;
+        let level = gst::ElementFactory::make("level")
+            .property("interval", LEVEL_INTERVAL_MS * 1_000_000)
+            .property("post-messages", true)
+            .build()
+            .map_err(|e| format!("Failed to create level element: {}", e))?;
+        let sink = gst::ElementFactory::make("fakesink")
+            .property("sync", false)
+            .build()
+            .map_err(|e| format!("Failed to create fakesink: {}", e))?;
+
+        let pipeline = gst::Pipeline::new();
+        pipeline
+            .add_many([&source, &convert, &level, &sink])
+            .map_err(|e| format!("Failed to add level meter elements: {}", e))?;
+        gst::Element::link_many([&source, &convert, &level, &sink])
+            .map_err(|e| format!("Failed to link level meter elements: {}", e))?;
+        pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|e| format!("Failed to start level meter: {}", e))?;
+
+        info!(serial = ?serial, "Audio level monitor started");
+        Ok(Self { pipeline })
+    }
+
+    /// Bus carrying the `level` messages
+    pub fn bus(&self) -> Option<gst::Bus> {
+        self.pipeline.bus()
+    }
+}
+
+impl Drop for AudioLevelMonitor {
+    fn drop(&mut self) {
+        let _ = self.pipeline.set_state(gst::State::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_db_to_meter_range() {
+        assert_eq!(db_to_meter(0.0), 1.0);
+        assert_eq!(db_to_meter(6.0), 1.0);
+        assert_eq!(db_to_meter(-30.0), 0.5);
+        assert_eq!(db_to_meter(-90.0), 0.0);
+        assert_eq!(db_to_meter(f64::NEG_INFINITY), 0.0);
+    }
+
+    #[test]
+    fn test_peak_db_uses_loudest_channel() {
+        let _ = gst::init();
+        let structure = gst::Structure::builder("level")
+            .field("peak", gst::glib::ValueArray::new([-12.0f64, -6.0f64]))
+            .build();
+        assert_eq!(peak_db(&structure), Some(-6.0));
+
+        let other = gst::Structure::builder("spectrum").build();
+        assert_eq!(peak_db(&other), None);
+    }
+}
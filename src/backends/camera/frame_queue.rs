@@ -0,0 +1,371 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Bounded frame queue with a configurable drop policy
+//!
+//! Sits between the appsink callback (producer) and the preview consumer.
+//! When the consumer can't keep up, frames are discarded according to
+//! [`FrameDropPolicy`] instead of blocking the GStreamer streaming thread.
+//! Frames the appsink itself discarded (see its `max-buffers` and `drop`
+//! settings) never reach the queue.
+//!
+//! The receiver mirrors the `try_next()` / `Stream` API of a futures mpsc
+//! receiver so existing consumers work unchanged.
+
+use cosmic::iced::futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Frames discarded by drop policies across all queues (for insights)
+static POLICY_DROPPED_FRAMES: AtomicU64 = AtomicU64::new(0);
+
+/// Get the number of frames discarded by drop policies
+pub fn get_policy_dropped_count() -> u64 {
+    POLICY_DROPPED_FRAMES.load(Ordering::Relaxed)
+}
+
+/// How frames are discarded when the consumer falls behind
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum FrameDropPolicy {
+    /// Discard incoming frames while the queue is full (keeps queued frames in order)
+    DropNewest,
+    /// Evict the oldest queued frame to make room for the incoming one
+    DropOldest,
+    /// Always hand the consumer the freshest frame, discarding anything older
+    #[default]
+    SkipToLatest,
+}
+
+impl FrameDropPolicy {
+    /// Get display name for this policy
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FrameDropPolicy::DropNewest => "Drop newest",
+            FrameDropPolicy::DropOldest => "Drop oldest",
+            FrameDropPolicy::SkipToLatest => "Skip to latest",
+        }
+    }
+
+    /// Get all available policies
+    pub const ALL: [FrameDropPolicy; 3] = [
+        FrameDropPolicy::DropNewest,
+        FrameDropPolicy::DropOldest,
+        FrameDropPolicy::SkipToLatest,
+    ];
+}
+
+struct QueueState<T> {
+    items: VecDeque<T>,
+    waker: Option<Waker>,
+    receiver_alive: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<QueueState<T>>,
+    capacity: usize,
+    policy: FrameDropPolicy,
+    senders: AtomicUsize,
+    dropped: AtomicU64,
+}
+
+impl<T> Shared<T> {
+    fn record_drops(&self, count: u64) {
+        if count > 0 {
+            self.dropped.fetch_add(count, Ordering::Relaxed);
+            POLICY_DROPPED_FRAMES.fetch_add(count, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Create a bounded frame queue
+///
+/// `capacity` is clamped to at least one frame.
+pub fn channel<T>(capacity: usize, policy: FrameDropPolicy) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(QueueState {
+            items: VecDeque::with_capacity(capacity.max(1)),
+            waker: None,
+            receiver_alive: true,
+        }),
+        capacity: capacity.max(1),
+        policy,
+        senders: AtomicUsize::new(1),
+        dropped: AtomicU64::new(0),
+    });
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+/// Error returned when the receiving side has been dropped
+#[derive(Debug)]
+pub struct SendError<T>(pub T);
+
+/// Error returned by `try_next()` when no frame is queued yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryRecvError;
+
+/// Producer side of a frame queue
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Queue a frame without blocking, applying the drop policy when full
+    ///
+    /// Returns the number of frames discarded to make this call succeed
+    /// (for `DropNewest` that is the incoming frame itself).
+    pub fn try_send(&self, item: T) -> Result<u64, SendError<T>> {
+        let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        if !state.receiver_alive {
+            return Err(SendError(item));
+        }
+
+        let mut dropped = 0;
+        if state.items.len() >= self.shared.capacity {
+            match self.shared.policy {
+                FrameDropPolicy::DropNewest => {
+                    drop(state);
+                    self.shared.record_drops(1);
+                    return Ok(1);
+                }
+                FrameDropPolicy::DropOldest | FrameDropPolicy::SkipToLatest => {
+                    state.items.pop_front();
+                    dropped = 1;
+                }
+            }
+        }
+        state.items.push_back(item);
+        let waker = state.waker.take();
+        drop(state);
+
+        self.shared.record_drops(dropped);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(dropped)
+    }
+
+    /// Policy this queue was created with
+    pub fn policy(&self) -> FrameDropPolicy {
+        self.shared.policy
+    }
+
+    /// Frames discarded by this queue so far
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            // Last sender gone: wake the receiver so it observes the close
+            let waker = self
+                .shared
+                .state
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .waker
+                .take();
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Consumer side of a frame queue
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Take the next frame according to the policy
+    fn pop(&self, state: &mut QueueState<T>) -> Option<T> {
+        if self.shared.policy == FrameDropPolicy::SkipToLatest && state.items.len() > 1 {
+            let stale = state.items.len() - 1;
+            state.items.drain(..stale);
+            self.shared.record_drops(stale as u64);
+        }
+        state.items.pop_front()
+    }
+
+    /// Non-blocking receive
+    ///
+    /// Returns `Ok(Some(frame))` when a frame is available, `Ok(None)` once all
+    /// senders are gone and the queue is empty, and `Err(TryRecvError)` when
+    /// the queue is currently empty.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(item) = self.pop(&mut state) {
+            return Ok(Some(item));
+        }
+        if self.shared.senders.load(Ordering::Acquire) == 0 {
+            Ok(None)
+        } else {
+            Err(TryRecvError)
+        }
+    }
+
+    /// Policy this queue was created with
+    pub fn policy(&self) -> FrameDropPolicy {
+        self.shared.policy
+    }
+
+    /// Frames discarded by this queue so far
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        let mut state = this.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(item) = this.pop(&mut state) {
+            return Poll::Ready(Some(item));
+        }
+        if this.shared.senders.load(Ordering::Acquire) == 0 {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.receiver_alive = false;
+        state.items.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn drain<T>(receiver: &mut Receiver<T>) -> Vec<T> {
+        let mut items = Vec::new();
+        while let Ok(Some(item)) = receiver.try_next() {
+            items.push(item);
+        }
+        items
+    }
+
+    #[test]
+    fn test_drop_newest_keeps_queued_frames() {
+        let (sender, mut receiver) = channel(2, FrameDropPolicy::DropNewest);
+        for i in 0..5 {
+            sender.try_send(i).unwrap();
+        }
+        assert_eq!(drain(&mut receiver), vec![0, 1]);
+        assert_eq!(receiver.dropped(), 3);
+    }
+
+    #[test]
+    fn test_drop_oldest_keeps_recent_frames() {
+        let (sender, mut receiver) = channel(2, FrameDropPolicy::DropOldest);
+        for i in 0..5 {
+            sender.try_send(i).unwrap();
+        }
+        assert_eq!(drain(&mut receiver), vec![3, 4]);
+        assert_eq!(receiver.dropped(), 3);
+    }
+
+    #[test]
+    fn test_skip_to_latest_returns_freshest_frame() {
+        let (sender, mut receiver) = channel(3, FrameDropPolicy::SkipToLatest);
+        for i in 0..5 {
+            sender.try_send(i).unwrap();
+        }
+        assert_eq!(receiver.try_next(), Ok(Some(4)));
+        assert_eq!(receiver.try_next(), Err(TryRecvError));
+        assert_eq!(receiver.dropped(), 4);
+    }
+
+    #[test]
+    fn test_closed_queue_reports_end_and_rejects_sends() {
+        let (sender, mut receiver) = channel(2, FrameDropPolicy::DropOldest);
+        sender.try_send(1).unwrap();
+        drop(sender);
+        assert_eq!(receiver.try_next(), Ok(Some(1)));
+        assert_eq!(receiver.try_next(), Ok(None));
+
+        let (sender, receiver) = channel(2, FrameDropPolicy::DropOldest);
+        drop(receiver);
+        assert!(sender.try_send(1).is_err());
+    }
+
+    /// Feed frames faster than a throttled consumer drains them
+    fn run_overload(policy: FrameDropPolicy) -> (Vec<u32>, u64) {
+        const FRAMES: u32 = 200;
+        let (sender, mut receiver) = channel(4, policy);
+
+        let producer = std::thread::spawn(move || {
+            for i in 0..FRAMES {
+                sender.try_send(i).unwrap();
+                std::thread::sleep(Duration::from_micros(100));
+            }
+        });
+
+        let mut received = Vec::new();
+        loop {
+            match receiver.try_next() {
+                Ok(Some(frame)) => {
+                    received.push(frame);
+                    // Simulated slow render
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                Ok(None) => break,
+                Err(TryRecvError) => std::thread::sleep(Duration::from_micros(200)),
+            }
+        }
+        producer.join().unwrap();
+
+        assert_eq!(received.len() as u64 + receiver.dropped(), FRAMES as u64);
+        assert!(receiver.dropped() > 0, "consumer should have fallen behind");
+        assert!(
+            received.windows(2).all(|w| w[0] < w[1]),
+            "frames stay ordered"
+        );
+        (received, receiver.dropped())
+    }
+
+    #[test]
+    fn test_overload_drop_newest() {
+        let (received, _) = run_overload(FrameDropPolicy::DropNewest);
+        assert_eq!(received.first(), Some(&0));
+    }
+
+    #[test]
+    fn test_overload_drop_oldest() {
+        let (received, _) = run_overload(FrameDropPolicy::DropOldest);
+        assert_eq!(received.last(), Some(&199));
+    }
+
+    #[test]
+    fn test_overload_skip_to_latest() {
+        let (received, _) = run_overload(FrameDropPolicy::SkipToLatest);
+        assert_eq!(received.last(), Some(&199));
+    }
+}
@@ -78,6 +78,12 @@ impl CameraBackendManager {
         state.backend.get_formats(device, video_mode)
     }
 
+    /// Get supported formats and V4L2 controls for a camera
+    pub fn get_capabilities(&self, device: &CameraDevice, video_mode: bool) -> DeviceCapabilities {
+        let state = self.state.lock().unwrap();
+        state.backend.get_capabilities(device, video_mode)
+    }
+
     /// Initialize the backend
     pub fn initialize(&self, device: &CameraDevice, format: &CameraFormat) -> BackendResult<()> {
         info!(device = %device.name, format = %format, "Initializing backend");
@@ -29,11 +29,15 @@
 //!       └────────┘
 //! ```
 
+pub mod frame_queue;
 pub mod manager;
 pub mod pipewire;
+pub mod quirks;
 pub mod types;
+pub mod usb;
 pub mod v4l2_controls;
 
+pub use frame_queue::FrameDropPolicy;
 pub use manager::CameraBackendManager;
 pub use types::*;
 
@@ -60,6 +64,22 @@ pub trait CameraBackend: Send + Sync {
     /// * `video_mode` - If true, only return formats suitable for video recording
     fn get_formats(&self, device: &CameraDevice, video_mode: bool) -> Vec<CameraFormat>;
 
+    /// Get supported formats and V4L2 controls in one probe pass
+    ///
+    /// # Arguments
+    /// * `device` - The camera device to query
+    /// * `video_mode` - If true, only return formats suitable for video recording
+    fn get_capabilities(&self, device: &CameraDevice, video_mode: bool) -> DeviceCapabilities {
+        DeviceCapabilities {
+            formats: self.get_formats(device, video_mode),
+            controls: device
+                .device_info
+                .as_ref()
+                .map(|info| v4l2_controls::probe_controls(&info.path))
+                .unwrap_or_default(),
+        }
+    }
+
     // ===== Lifecycle =====
 
     /// Initialize the backend with a specific camera and format
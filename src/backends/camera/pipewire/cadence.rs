@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Constant vs variable frame rate detection
+//!
+//! Cameras deliver frames at the rate they negotiated, give or take some
+//! timestamp jitter. Virtual cameras fed by screen capture only produce a
+//! frame when something changed, so their negotiated rate is an upper bound
+//! and anything derived from it (the frame rate shown in the insights
+//! drawer, gap-based drop counting) is wrong for them.
+//!
+//! The PTS distances of recent frames are kept here and classified by how
+//! much they vary: the coefficient of variation (standard deviation over
+//! mean) of a constant rate stays far below [`VARIABLE_ABOVE`] even with USB
+//! jitter. A camera that skips frames is still constant rate, so intervals
+//! close to a whole multiple of the median count as the median.
+
+use std::collections::VecDeque;
+
+/// Frame intervals kept for classification
+const WINDOW: usize = 90;
+
+/// Intervals needed before the stream is classified
+const MIN_INTERVALS: usize = 30;
+
+/// Coefficient of variation above which the stream counts as variable
+const VARIABLE_ABOVE: f64 = 0.25;
+
+/// Coefficient of variation below which a variable stream counts as constant again
+const CONSTANT_BELOW: f64 = 0.12;
+
+/// Distance from a whole multiple of the median that still counts as skipped frames
+const SKIP_TOLERANCE: f64 = 0.15;
+
+/// Measured frame rate of the stream and whether it is variable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameCadence {
+    /// Frame intervals vary too much for a fixed frame rate (VFR)
+    pub variable: bool,
+    /// Frame rate over the kept intervals
+    pub average_fps: f64,
+    /// Frame rate from the last interval
+    pub instant_fps: f64,
+}
+
+/// Keeps recent frame intervals and classifies the stream as CFR or VFR
+#[derive(Debug)]
+pub struct CadenceTracker {
+    /// PTS of the previous frame (None after a start or an interruption)
+    last_pts_ns: Option<u64>,
+    intervals_ns: VecDeque<u64>,
+    variable: bool,
+}
+
+impl CadenceTracker {
+    pub const fn new() -> Self {
+        Self {
+            last_pts_ns: None,
+            intervals_ns: VecDeque::new(),
+            variable: false,
+        }
+    }
+
+    /// Note a frame with presentation timestamp `pts_ns`
+    pub fn record(&mut self, pts_ns: u64) {
+        let Some(last) = self.last_pts_ns.replace(pts_ns) else {
+            return;
+        };
+        // PTS going backwards starts a new segment
+        if pts_ns < last {
+            self.intervals_ns.clear();
+            return;
+        }
+        if pts_ns == last {
+            return;
+        }
+        if self.intervals_ns.len() == WINDOW {
+            self.intervals_ns.pop_front();
+        }
+        self.intervals_ns.push_back(pts_ns - last);
+
+        if self.intervals_ns.len() >= MIN_INTERVALS {
+            let variation = variation(&self.intervals_ns);
+            if self.variable {
+                self.variable = variation >= CONSTANT_BELOW;
+            } else {
+                self.variable = variation > VARIABLE_ABOVE;
+            }
+        }
+    }
+
+    /// Forget the previous frame, e.g. while the preview is frozen
+    ///
+    /// The gap until the next frame is not a frame interval.
+    pub fn interrupt(&mut self) {
+        self.last_pts_ns = None;
+    }
+
+    /// The measured cadence, once enough frames were seen
+    pub fn cadence(&self) -> Option<FrameCadence> {
+        if self.intervals_ns.len() < MIN_INTERVALS {
+            return None;
+        }
+        let total_ns: u64 = self.intervals_ns.iter().sum();
+        let last_ns = *self.intervals_ns.back()?;
+        Some(FrameCadence {
+            variable: self.variable,
+            average_fps: self.intervals_ns.len() as f64 * 1e9 / total_ns as f64,
+            instant_fps: 1e9 / last_ns as f64,
+        })
+    }
+}
+
+impl Default for CadenceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Coefficient of variation of `intervals_ns`, ignoring skipped frames
+fn variation(intervals_ns: &VecDeque<u64>) -> f64 {
+    let mut sorted: Vec<u64> = intervals_ns.iter().copied().collect();
+    sorted.sort_unstable();
+    let median = sorted[sorted.len() / 2] as f64;
+    if median == 0.0 {
+        return 0.0;
+    }
+
+    let folded: Vec<f64> = intervals_ns
+        .iter()
+        .map(|&interval| {
+            let interval = interval as f64;
+            let multiple = (interval / median).round();
+            if multiple >= 2.0 && (interval / median - multiple).abs() <= SKIP_TOLERANCE {
+                median
+            } else {
+                interval
+            }
+        })
+        .collect();
+    let mean = folded.iter().sum::<f64>() / folded.len() as f64;
+    let variance = folded
+        .iter()
+        .map(|interval| (interval - mean).powi(2))
+        .sum::<f64>()
+        / folded.len() as f64;
+    variance.sqrt() / mean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed frames `intervals_ms` apart and return the resulting cadence
+    fn cadence_of(intervals_ms: impl IntoIterator<Item = u64>) -> FrameCadence {
+        let mut tracker = CadenceTracker::new();
+        let mut pts_ns = 0;
+        tracker.record(pts_ns);
+        for interval in intervals_ms {
+            pts_ns += interval * 1_000_000;
+            tracker.record(pts_ns);
+        }
+        tracker.cadence().expect("enough frames")
+    }
+
+    #[test]
+    fn test_jitter_and_skipped_frames_stay_constant() {
+        // 30 fps with ±2 ms of jitter and a skipped frame every 15
+        let cadence = cadence_of((0..90).map(|i| match i % 15 {
+            14 => 67,
+            n if n % 2 == 0 => 35,
+            _ => 31,
+        }));
+        assert!(!cadence.variable);
+        assert!((cadence.average_fps - 28.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_screen_capture_is_variable() {
+        // Bursts while something moves, pauses while nothing does
+        let cadence = cadence_of((0..88).map(|i| [16, 17, 16, 250, 16, 90, 400, 33][i % 8]));
+        assert!(cadence.variable);
+        assert!((cadence.instant_fps - 1000.0 / 33.0).abs() < 0.1);
+
+        // Back to a steady rate: constant again once the window has moved on
+        let mut tracker = CadenceTracker::new();
+        let mut pts_ns = 0;
+        for interval in (0..90).map(|i| [16, 250, 90, 400][i % 4]).chain([33; 90]) {
+            pts_ns += interval * 1_000_000;
+            tracker.record(pts_ns);
+        }
+        assert!(!tracker.cadence().unwrap().variable);
+    }
+}
@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Per-frame checksums for reproducing decoder output
+//!
+//! With `--frame-checksums`, every frame leaving the appsink gets a line with
+//! its PTS, size, format and an FNV-1a hash of its visible pixels. Two runs
+//! (or two decoders) fed the same recording should produce the same lines, so
+//! a diff of the logs points at the first frame whose output changed.
+//!
+//! Row padding is skipped, since its content is whatever the decoder left in
+//! the buffer. FNV-1a is used over the std hasher because its output doesn't
+//! change between Rust releases.
+//!
+//! Hashing reads every byte of every frame, so it stays behind an atomic
+//! flag that the frame callback checks first.
+
+use crate::backends::camera::types::{CameraFrame, PixelFormat};
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Checksum file, `None` to write to the tracing log instead
+static OUTPUT: Mutex<Option<LineWriter<File>>> = Mutex::new(None);
+
+/// Start logging frame checksums, to `path` or (without one) the tracing log
+pub fn enable(path: Option<&Path>) -> std::io::Result<()> {
+    let file = path.map(File::create).transpose()?;
+    if let Ok(mut guard) = OUTPUT.lock() {
+        *guard = file.map(LineWriter::new);
+    }
+    ENABLED.store(true, Ordering::Relaxed);
+    info!(?path, "Logging frame checksums");
+    Ok(())
+}
+
+/// Whether frames should be passed to [`record`]
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// `(offset, stride, row bytes, rows)` of each plane's visible pixels
+fn visible_planes(frame: &CameraFrame) -> Vec<(usize, usize, usize, usize)> {
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+    let stride = frame.stride as usize;
+    match (frame.format, frame.yuv_planes) {
+        (PixelFormat::NV12 | PixelFormat::NV21, Some(planes)) => vec![
+            (planes.y_offset, stride, width, height),
+            (
+                planes.uv_offset,
+                planes.uv_stride as usize,
+                chroma_width * 2,
+                chroma_height,
+            ),
+        ],
+        (PixelFormat::I420, Some(planes)) => vec![
+            (planes.y_offset, stride, width, height),
+            (
+                planes.uv_offset,
+                planes.uv_stride as usize,
+                chroma_width,
+                chroma_height,
+            ),
+            (
+                planes.v_offset,
+                planes.v_stride as usize,
+                chroma_width,
+                chroma_height,
+            ),
+        ],
+        (format, _) => {
+            let row_bytes = match format {
+                PixelFormat::RGBA => width * 4,
+                PixelFormat::RGB24 => width * 3,
+                PixelFormat::Gray8 => width,
+                PixelFormat::Gray16
+                | PixelFormat::YUYV
+                | PixelFormat::UYVY
+                | PixelFormat::YVYU
+                | PixelFormat::VYUY => width * 2,
+                // Planar frames without plane offsets: hash the first plane
+                PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => width,
+            };
+            vec![(0, stride, row_bytes, height)]
+        }
+    }
+}
+
+/// FNV-1a hash of the visible pixels of `frame`
+pub fn frame_checksum(frame: &CameraFrame) -> u64 {
+    let data = frame.data_slice();
+    let mut hash = FNV_OFFSET;
+    for (offset, stride, row_bytes, rows) in visible_planes(frame) {
+        for row in 0..rows {
+            let start = offset + row * stride;
+            // A short buffer hashes what is there; the mismatch shows up in the log
+            let Some(bytes) = data.get(start..start + row_bytes) else {
+                break;
+            };
+            for &byte in bytes {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+    }
+    hash
+}
+
+/// Log the checksum of `frame`, the `index`th frame of the session
+pub fn record(index: u64, pts: Option<gstreamer::ClockTime>, frame: &CameraFrame) {
+    let checksum = frame_checksum(frame);
+    let pts_ns = pts.map(|pts| pts.nseconds().to_string());
+    let pts_ns = pts_ns.as_deref().unwrap_or("-");
+    let Ok(mut guard) = OUTPUT.lock() else {
+        return;
+    };
+    match guard.as_mut() {
+        Some(file) => {
+            let line = format!(
+                "{} {} {}x{} {:?} {:016x}",
+                index, pts_ns, frame.width, frame.height, frame.format, checksum
+            );
+            if let Err(err) = writeln!(file, "{}", line) {
+                warn!(?err, "Failed to write frame checksum, stopping");
+                ENABLED.store(false, Ordering::Relaxed);
+            }
+        }
+        None => info!(
+            target: "camera::checksum",
+            index,
+            pts_ns,
+            width = frame.width,
+            height = frame.height,
+            format = ?frame.format,
+            checksum = %format!("{:016x}", checksum),
+            "Frame checksum"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::FrameData;
+
+    fn gray_frame(data: Vec<u8>, width: u32, height: u32, stride: u32) -> CameraFrame {
+        CameraFrame {
+            width,
+            height,
+            data: FrameData::from_bytes(std::sync::Arc::from(data)),
+            format: PixelFormat::Gray8,
+            stride,
+            yuv_planes: None,
+            captured_at: std::time::Instant::now(),
+            stamp: None,
+        }
+    }
+
+    #[test]
+    fn test_padding_is_not_hashed() {
+        let tight = gray_frame(vec![1, 2, 3, 4, 5, 6], 3, 2, 3);
+        let padded = gray_frame(vec![1, 2, 3, 0xaa, 4, 5, 6, 0x55], 3, 2, 4);
+        assert_eq!(frame_checksum(&tight), frame_checksum(&padded));
+
+        let changed = gray_frame(vec![1, 2, 3, 4, 5, 7], 3, 2, 3);
+        assert_ne!(frame_checksum(&tight), frame_checksum(&changed));
+    }
+
+    #[test]
+    fn test_checksum_is_stable() {
+        // FNV-1a of "a", so logs stay comparable across builds
+        let frame = gray_frame(b"a".to_vec(), 1, 1, 1);
+        assert_eq!(frame_checksum(&frame), 0xaf63_dc4c_8601_ec8c);
+    }
+}
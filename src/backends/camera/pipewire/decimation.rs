@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Evenly spaced frame dropping
+//!
+//! Capping 60 fps at 30 should keep every second frame, and 60 at 24 should
+//! alternate between keeping every second and every third. Measuring the
+//! distance to the last kept frame gets this wrong as soon as timestamps
+//! jitter: a frame arriving a little early is dropped and the next one kept,
+//! so the output stutters. Instead, time is cut into slots of one output
+//! frame each, counted from the first frame, and the first frame of every
+//! slot is kept.
+//!
+//! Nothing here assumes the source rate is constant, so variable-rate
+//! sources work too: a frame after a pause lands in a later slot and is
+//! kept right away, and bursts faster than the cap are thinned as usual.
+
+/// Fraction of a slot a frame may arrive early and still count for it
+const EARLY_SLACK_DIVISOR: u64 = 4;
+
+/// Decides which frames to keep for a maximum frame rate
+#[derive(Debug, Default)]
+pub struct FrameDecimator {
+    /// PTS of the frame slot 0 started at (None = no frame yet)
+    origin_ns: Option<u64>,
+    /// Slot length the origin was set for
+    interval_ns: u64,
+    last_slot: u64,
+}
+
+impl FrameDecimator {
+    /// Whether the frame at `pts_ns` should be kept when keeping one frame
+    /// per `interval_ns` (0 = keep all)
+    pub fn keep(&mut self, pts_ns: u64, interval_ns: u64) -> bool {
+        if interval_ns == 0 {
+            self.origin_ns = None;
+            return true;
+        }
+        // Start over on the first frame, a new rate and PTS going backwards (new segment)
+        let origin = match self.origin_ns {
+            Some(origin) if self.interval_ns == interval_ns && pts_ns >= origin => origin,
+            _ => {
+                self.origin_ns = Some(pts_ns);
+                self.interval_ns = interval_ns;
+                self.last_slot = 0;
+                return true;
+            }
+        };
+        let slot = (pts_ns - origin + interval_ns / EARLY_SLACK_DIVISOR) / interval_ns;
+        if slot > self.last_slot {
+            self.last_slot = slot;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gaps (in source frames) between the kept frames of `count` frames
+    fn kept_gaps(source_fps: u64, target_fps: u64, jitter_ns: &[i64], count: usize) -> Vec<usize> {
+        let mut decimator = FrameDecimator::default();
+        let period = 1_000_000_000 / source_fps;
+        let interval = 1_000_000_000 / target_fps;
+        let kept: Vec<usize> = (0..count)
+            .filter(|&n| {
+                let jitter = jitter_ns[n % jitter_ns.len()];
+                let pts = (1_000_000_000 + n as u64 * period).saturating_add_signed(jitter);
+                decimator.keep(pts, interval)
+            })
+            .collect();
+        kept.windows(2).map(|pair| pair[1] - pair[0]).collect()
+    }
+
+    #[test]
+    fn test_spacing_is_even() {
+        assert!(kept_gaps(60, 30, &[0], 120).iter().all(|&gap| gap == 2));
+        // 60 -> 24 alternates 2 and 3 instead of bunching
+        let gaps = kept_gaps(60, 24, &[0], 120);
+        assert!(gaps.windows(2).all(|pair| pair[0] + pair[1] == 5));
+        // A target above the source rate keeps everything
+        assert!(kept_gaps(30, 60, &[0], 60).iter().all(|&gap| gap == 1));
+    }
+
+    #[test]
+    fn test_jitter_does_not_cause_bursts() {
+        // Up to 3 ms early or late on a 16.7 ms period
+        let jitter = [0, -3_000_000, 2_000_000, -1_000_000, 3_000_000, -2_000_000];
+        assert!(kept_gaps(60, 30, &jitter, 240).iter().all(|&gap| gap == 2));
+    }
+
+    #[test]
+    fn test_variable_rate_keeps_frames_after_pauses() {
+        // A screen capture source: bursts of 20 frames at 60 fps, then 700 ms without frames
+        let mut decimator = FrameDecimator::default();
+        let interval = 33_333_333;
+        let mut pts = 0;
+        let mut kept = 0;
+        for _ in 0..5 {
+            for frame in 0..20 {
+                let keep = decimator.keep(pts, interval);
+                // The first frame after a pause is shown right away
+                if frame == 0 {
+                    assert!(keep);
+                }
+                kept += usize::from(keep);
+                pts += 16_666_667;
+            }
+            pts += 700_000_000;
+        }
+        // Bursts are halved like a constant 60 fps source
+        assert_eq!(kept, 50);
+    }
+
+    #[test]
+    fn test_restarts_on_new_segment() {
+        let mut decimator = FrameDecimator::default();
+        let interval = 33_333_333;
+        assert!(decimator.keep(5_000_000_000, interval));
+        assert!(!decimator.keep(5_016_666_666, interval));
+        // PTS went backwards: the first frame of the new segment is kept
+        assert!(decimator.keep(0, interval));
+        assert!(!decimator.keep(16_666_666, interval));
+        assert!(decimator.keep(33_333_333, interval));
+    }
+}
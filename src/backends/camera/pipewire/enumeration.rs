@@ -5,8 +5,12 @@
 //! This module provides camera discovery and format enumeration using PipeWire.
 //! PipeWire handles all camera access, format negotiation, and decoding internally.
 
-use super::super::types::{CameraDevice, CameraFormat, DeviceInfo, Framerate, SensorRotation};
+use super::super::types::{
+    CameraDevice, CameraFacing, CameraFormat, CameraSourceKind, DeviceInfo, Framerate,
+    SensorRotation, UsbId,
+};
 use crate::constants::formats;
+use std::collections::HashMap;
 use tracing::{debug, info, warn};
 
 /// Enumerate cameras using PipeWire
@@ -50,6 +54,9 @@ pub fn enumerate_pipewire_cameras() -> Option<Vec<CameraDevice>> {
         metadata_path: None,
         device_info: None,
         rotation: SensorRotation::None,
+        facing: CameraFacing::Unknown,
+        source_kind: None,
+        alternate_source: None,
     }])
 }
 
@@ -75,6 +82,8 @@ fn try_enumerate_with_pw_cli() -> Option<Vec<CameraDevice>> {
     let mut current_nick: Option<String> = None;
     let mut current_object_path: Option<String> = None;
     let mut is_video_source = false;
+    // Node ID -> object.path, for pairing libcamera and V4L2 nodes
+    let mut object_paths: HashMap<String, String> = HashMap::new();
 
     for line in stdout.lines() {
         let trimmed = line.trim();
@@ -100,17 +109,24 @@ fn try_enumerate_with_pw_cli() -> Option<Vec<CameraDevice>> {
                     let device_info =
                         build_device_info(current_nick.as_deref(), current_object_path.as_deref());
 
-                    // Query rotation from pw-cli info (not available in pw-cli ls output)
-                    let rotation = query_node_rotation(id);
+                    let (rotation, facing) = detect_mounting(id, device_info.as_ref());
 
-                    debug!(id = %id, serial = ?current_serial, name = %name, path = %path, rotation = %rotation, "Found video camera");
+                    debug!(id = %id, serial = ?current_serial, name = %name, path = %path, rotation = %rotation, ?facing, "Found video camera");
                     cameras.push(CameraDevice {
                         name: name.clone(),
                         path,
                         metadata_path: Some(id.clone()), // Store node ID in metadata_path for format enumeration
                         device_info,
                         rotation,
+                        facing,
+                        source_kind: current_object_path
+                            .as_deref()
+                            .and_then(CameraSourceKind::from_object_path),
+                        alternate_source: None,
                     });
+                    if let Some(object_path) = current_object_path.as_ref() {
+                        object_paths.insert(id.clone(), object_path.clone());
+                    }
                 }
             }
 
@@ -188,20 +204,29 @@ fn try_enumerate_with_pw_cli() -> Option<Vec<CameraDevice>> {
             let device_info =
                 build_device_info(current_nick.as_deref(), current_object_path.as_deref());
 
-            // Query rotation from pw-cli info (not available in pw-cli ls output)
-            let rotation = query_node_rotation(id);
+            let (rotation, facing) = detect_mounting(id, device_info.as_ref());
 
-            debug!(id = %id, serial = ?current_serial, name = %name, path = %path, rotation = %rotation, "Found video camera (last)");
+            debug!(id = %id, serial = ?current_serial, name = %name, path = %path, rotation = %rotation, ?facing, "Found video camera (last)");
             cameras.push(CameraDevice {
                 name: name.clone(),
                 path,
                 metadata_path: Some(id.clone()), // Store node ID in metadata_path for format enumeration
                 device_info,
                 rotation,
+                facing,
+                source_kind: current_object_path
+                    .as_deref()
+                    .and_then(CameraSourceKind::from_object_path),
+                alternate_source: None,
             });
+            if let Some(object_path) = current_object_path.as_ref() {
+                object_paths.insert(id.clone(), object_path.clone());
+            }
         }
     }
 
+    let cameras = hide_non_capture_nodes(cameras, query_v4l2_node);
+    let cameras = pair_source_kinds(cameras, &object_paths);
     if cameras.is_empty() {
         debug!("No cameras found via pw-cli");
         None
@@ -211,6 +236,74 @@ fn try_enumerate_with_pw_cli() -> Option<Vec<CameraDevice>> {
     }
 }
 
+/// Merge cameras that PipeWire offers through both libcamera and V4L2
+///
+/// libcamera names UVC cameras by USB port and ID (e.g.
+/// `libcamera:\_SB_.PCI0.XHC_.RHUB.HS07-7:1.0-5986:1193`), which pairs its
+/// node with the V4L2 node of the same camera. Identical cameras can't be
+/// told apart that way, so their nodes stay separate entries. The node of the
+/// preferred stack becomes the entry, the other one its alternate.
+fn pair_source_kinds(
+    cameras: Vec<CameraDevice>,
+    object_paths: &HashMap<String, String>,
+) -> Vec<CameraDevice> {
+    let usb_id = |camera: &CameraDevice| camera.device_info.as_ref().and_then(|info| info.usb_id);
+    let libcamera_ends_with = |camera: &CameraDevice, suffix: &str| {
+        camera.source_kind == Some(CameraSourceKind::Libcamera)
+            && camera
+                .metadata_path
+                .as_ref()
+                .and_then(|id| object_paths.get(id))
+                .is_some_and(|object_path| object_path.ends_with(suffix))
+    };
+
+    let mut pairs = Vec::new();
+    for (v4l2_index, camera) in cameras.iter().enumerate() {
+        let Some(id) =
+            usb_id(camera).filter(|_| camera.source_kind == Some(CameraSourceKind::V4l2))
+        else {
+            continue;
+        };
+        let suffix = format!("-{}", id);
+        let same_id = cameras
+            .iter()
+            .filter(|other| usb_id(other) == Some(id))
+            .count();
+        let mut libcamera = cameras
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| libcamera_ends_with(other, &suffix));
+        if let (Some((libcamera_index, _)), None) = (libcamera.next(), libcamera.next())
+            && same_id == 1
+        {
+            pairs.push((v4l2_index, libcamera_index));
+        }
+    }
+
+    let mut slots: Vec<Option<CameraDevice>> = cameras.into_iter().map(Some).collect();
+    for (v4l2_index, libcamera_index) in pairs {
+        let (Some(v4l2), Some(libcamera)) =
+            (slots[v4l2_index].take(), slots[libcamera_index].take())
+        else {
+            continue;
+        };
+        let preferred = CameraSourceKind::preferred_for(v4l2.device_info.as_ref());
+        debug!(
+            v4l2 = %v4l2.name,
+            libcamera = %libcamera.name,
+            preferred = preferred.display_name(),
+            "Camera offered through both libcamera and V4L2"
+        );
+        let (mut primary, alternate) = match preferred {
+            CameraSourceKind::V4l2 => (v4l2, libcamera),
+            CameraSourceKind::Libcamera => (libcamera, v4l2),
+        };
+        primary.alternate_source = Some(Box::new(alternate));
+        slots[v4l2_index.min(libcamera_index)] = Some(primary);
+    }
+    slots.into_iter().flatten().collect()
+}
+
 /// Extract quoted value from a property line (e.g., 'property = "value"' -> "value")
 fn extract_quoted_value(line: &str) -> Option<String> {
     let start = line.find('"')?;
@@ -218,9 +311,63 @@ fn extract_quoted_value(line: &str) -> Option<String> {
     Some(line[start + 1..start + 1 + end].to_string())
 }
 
-/// Query rotation for a PipeWire node using pw-cli info
-/// This is needed because pw-cli ls Node doesn't include api.libcamera.rotation
-fn query_node_rotation(node_id: &str) -> SensorRotation {
+/// Detect how a camera is mounted: sensor rotation and facing
+///
+/// libcamera publishes both as node properties (not in `pw-cli ls` output, so
+/// they are queried separately). Plain V4L2 devices fall back to the sensor
+/// rotation and orientation controls, which drivers fill in from firmware on
+/// tablets and convertibles. USB cameras without either are judged by their
+/// port: built-in webcams sit on fixed ports and face the user.
+fn detect_mounting(
+    node_id: &str,
+    device_info: Option<&DeviceInfo>,
+) -> (SensorRotation, CameraFacing) {
+    let (rotation, facing) = query_node_mounting(node_id);
+    let rotation = rotation.unwrap_or_else(|| detect_v4l2_rotation(node_id, device_info));
+    let facing = facing.unwrap_or_else(|| detect_v4l2_facing(node_id, device_info));
+    (rotation, facing)
+}
+
+fn detect_v4l2_rotation(node_id: &str, device_info: Option<&DeviceInfo>) -> SensorRotation {
+    let Some(degrees) = device_info.and_then(|info| {
+        crate::backends::camera::v4l2_controls::get_control(
+            &info.path,
+            crate::backends::camera::v4l2_controls::V4L2_CID_CAMERA_SENSOR_ROTATION,
+        )
+    }) else {
+        return SensorRotation::None;
+    };
+    debug!(
+        node_id,
+        degrees, "Found rotation from V4L2 sensor rotation control"
+    );
+    SensorRotation::from_degrees(&degrees.to_string())
+}
+
+fn detect_v4l2_facing(node_id: &str, device_info: Option<&DeviceInfo>) -> CameraFacing {
+    let Some(info) = device_info else {
+        return CameraFacing::Unknown;
+    };
+    if let Some(orientation) = crate::backends::camera::v4l2_controls::get_control(
+        &info.path,
+        crate::backends::camera::v4l2_controls::V4L2_CID_CAMERA_ORIENTATION,
+    ) {
+        debug!(
+            node_id,
+            orientation, "Found facing from V4L2 camera orientation control"
+        );
+        return CameraFacing::from_v4l2_orientation(orientation);
+    }
+    match crate::backends::camera::usb::is_removable(&info.path) {
+        Some(false) => CameraFacing::Front,
+        Some(true) => CameraFacing::External,
+        None => CameraFacing::Unknown,
+    }
+}
+
+/// Query rotation and location for a PipeWire node using pw-cli info
+/// This is needed because pw-cli ls Node doesn't include the api.libcamera.* properties
+fn query_node_mounting(node_id: &str) -> (Option<SensorRotation>, Option<CameraFacing>) {
     let output = match std::process::Command::new("pw-cli")
         .args(["info", node_id])
         .output()
@@ -228,11 +375,13 @@ fn query_node_rotation(node_id: &str) -> SensorRotation {
         Ok(output) if output.status.success() => output,
         _ => {
             debug!(node_id, "Failed to query node info for rotation");
-            return SensorRotation::default();
+            return (None, None);
         }
     };
 
     let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut rotation = None;
+    let mut facing = None;
 
     for line in stdout.lines() {
         let trimmed = line.trim();
@@ -241,11 +390,19 @@ fn query_node_rotation(node_id: &str) -> SensorRotation {
             && let Some(value) = extract_quoted_value(trimmed)
         {
             debug!(node_id, rotation = %value, "Found rotation from pw-cli info");
-            return SensorRotation::from_degrees(&value);
+            rotation = Some(SensorRotation::from_degrees(&value));
+        }
+        // Look for: api.libcamera.location = "front"
+        if trimmed.contains("api.libcamera.location")
+            && let Some(value) = extract_quoted_value(trimmed)
+        {
+            debug!(node_id, location = %value, "Found location from pw-cli info");
+            facing = Some(CameraFacing::from_libcamera_location(&value));
         }
     }
 
-    SensorRotation::default()
+    // A zero rotation is no better than none: the V4L2 control may still know more
+    (rotation.filter(|r| *r != SensorRotation::None), facing)
 }
 
 /// Build DeviceInfo from PipeWire properties and V4L2 device info
@@ -264,27 +421,74 @@ fn build_device_info(nick: Option<&str>, object_path: Option<&str>) -> Option<De
         .unwrap_or_else(|_| v4l2_path.clone());
 
     // Get driver name using V4L2 ioctl
-    let driver = get_v4l2_driver(&v4l2_path).unwrap_or_default();
+    let driver = query_v4l2_node(&v4l2_path)
+        .map(|node| node.driver)
+        .unwrap_or_default();
 
     // Use node.nick as the card name, fallback to empty
     let card = nick.unwrap_or_default().to_string();
 
+    let usb_id = read_usb_id(&v4l2_path);
+
     Some(DeviceInfo {
         card,
         driver,
         path: v4l2_path,
         real_path,
+        usb_id,
     })
 }
 
-/// Get V4L2 driver name using ioctl
-fn get_v4l2_driver(device_path: &str) -> Option<String> {
+/// Read the USB vendor/product ID of a V4L2 device from sysfs
+fn read_usb_id(device_path: &str) -> Option<UsbId> {
+    let device = super::super::usb::usb_device_dir(device_path)?;
+    let read_hex = |name: &str| {
+        let value = std::fs::read_to_string(device.join(name)).ok()?;
+        u16::from_str_radix(value.trim(), 16).ok()
+    };
+    Some(UsbId::new(read_hex("idVendor")?, read_hex("idProduct")?))
+}
+
+/// Capability flags for a node that delivers frames (single- or multi-planar)
+const V4L2_CAP_VIDEO_CAPTURE: u32 = 0x0000_0001;
+const V4L2_CAP_VIDEO_CAPTURE_MPLANE: u32 = 0x0000_1000;
+/// `device_caps` is filled in, `capabilities` covers the whole device
+const V4L2_CAP_DEVICE_CAPS: u32 = 0x8000_0000;
+
+/// What VIDIOC_QUERYCAP reports for one V4L2 node
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct V4l2Node {
+    driver: String,
+    card: String,
+    /// Location of the device (e.g. `usb-0000:00:14.0-5`), shared by its nodes
+    bus_info: String,
+    /// Capabilities of this node, not the whole device
+    caps: u32,
+}
+
+impl V4l2Node {
+    /// Whether the node delivers frames (UVC metadata nodes don't)
+    fn can_capture(&self) -> bool {
+        self.caps & (V4L2_CAP_VIDEO_CAPTURE | V4L2_CAP_VIDEO_CAPTURE_MPLANE) != 0
+    }
+
+    /// Whether both nodes capture from the same device under the same name
+    fn mirrors(&self, other: &V4l2Node) -> bool {
+        self.can_capture()
+            && other.can_capture()
+            && !self.bus_info.is_empty()
+            && self.bus_info == other.bus_info
+            && self.card == other.card
+    }
+}
+
+/// Query a V4L2 node's driver, card and capabilities using ioctl
+fn query_v4l2_node(device_path: &str) -> Option<V4l2Node> {
     use std::os::unix::io::AsRawFd;
 
     // VIDIOC_QUERYCAP ioctl number
     const VIDIOC_QUERYCAP: libc::c_ulong = 0x80685600;
 
-    // V4L2 capability structure (simplified - we only need driver field)
     #[repr(C)]
     struct V4l2Capability {
         driver: [u8; 16],
@@ -323,12 +527,59 @@ fn get_v4l2_driver(device_path: &str) -> Option<String> {
         return None;
     }
 
-    // Convert driver name from null-terminated bytes to String
-    let driver_len = cap.driver.iter().position(|&c| c == 0).unwrap_or(16);
-    let driver = String::from_utf8_lossy(&cap.driver[..driver_len]).to_string();
+    // Null-terminated byte strings
+    let text = |bytes: &[u8]| {
+        let len = bytes.iter().position(|&c| c == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..len]).to_string()
+    };
+    let caps = if cap.capabilities & V4L2_CAP_DEVICE_CAPS != 0 {
+        cap.device_caps
+    } else {
+        cap.capabilities
+    };
+    let node = V4l2Node {
+        driver: text(&cap.driver),
+        card: text(&cap.card),
+        bus_info: text(&cap.bus_info),
+        caps,
+    };
 
-    debug!(device_path, driver = %driver, "Got V4L2 driver name");
-    Some(driver)
+    debug!(device_path, driver = %node.driver, bus_info = %node.bus_info, caps = format_args!("{:#010x}", caps), "Queried V4L2 node");
+    Some(node)
+}
+
+/// Drop V4L2 nodes that can't capture and list mirrored capture nodes once
+///
+/// UVC cameras expose a metadata node next to the capture node, and some
+/// expose the same stream on several capture nodes. Nodes are compared by
+/// what `query` reports for their V4L2 path; the first capture node of a
+/// device is kept. Cameras whose node can't be queried are kept as they are.
+fn hide_non_capture_nodes(
+    cameras: Vec<CameraDevice>,
+    query: impl Fn(&str) -> Option<V4l2Node>,
+) -> Vec<CameraDevice> {
+    let mut kept: Vec<(CameraDevice, Option<V4l2Node>)> = Vec::with_capacity(cameras.len());
+    for camera in cameras {
+        let node = (camera.source_kind == Some(CameraSourceKind::V4l2))
+            .then_some(camera.device_info.as_ref())
+            .flatten()
+            .and_then(|info| query(&info.path));
+        if let Some(node) = node.as_ref() {
+            if !node.can_capture() {
+                debug!(name = %camera.name, caps = format_args!("{:#010x}", node.caps), "Hiding V4L2 node without video capture");
+                continue;
+            }
+            if let Some((first, _)) = kept
+                .iter()
+                .find(|(_, other)| other.as_ref().is_some_and(|other| other.mirrors(node)))
+            {
+                debug!(name = %camera.name, first = %first.path, bus_info = %node.bus_info, "Hiding mirrored capture node");
+                continue;
+            }
+        }
+        kept.push((camera, node));
+    }
+    kept.into_iter().map(|(camera, _)| camera).collect()
 }
 
 /// Try to enumerate cameras using pactl command (PipeWire)
@@ -362,6 +613,9 @@ fn try_enumerate_with_pactl() -> Option<Vec<CameraDevice>> {
                 metadata_path: None,
                 device_info: None,
                 rotation: SensorRotation::None,
+                facing: CameraFacing::Unknown,
+                source_kind: None,
+                alternate_source: None,
             });
         }
     }
@@ -605,3 +859,142 @@ pub fn is_pipewire_available() -> bool {
         .build()
         .is_ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(id: &str, kind: CameraSourceKind, usb_id: Option<UsbId>) -> CameraDevice {
+        CameraDevice {
+            name: format!("Camera {}", id),
+            path: format!("pipewire-serial-{}", id),
+            metadata_path: Some(id.to_string()),
+            device_info: usb_id.map(|usb_id| DeviceInfo {
+                card: String::new(),
+                driver: "uvcvideo".to_string(),
+                path: "/dev/video0".to_string(),
+                real_path: "/dev/video0".to_string(),
+                usb_id: Some(usb_id),
+            }),
+            rotation: SensorRotation::None,
+            facing: CameraFacing::Unknown,
+            source_kind: Some(kind),
+            alternate_source: None,
+        }
+    }
+
+    #[test]
+    fn test_nodes_of_one_camera_are_paired() {
+        let webcam = UsbId::new(0x5986, 0x1193);
+        let cameras = vec![
+            camera("40", CameraSourceKind::Libcamera, None),
+            camera("41", CameraSourceKind::V4l2, Some(webcam)),
+            camera("42", CameraSourceKind::Libcamera, None),
+        ];
+        let object_paths = HashMap::from([
+            (
+                "40".to_string(),
+                "libcamera:\\_SB_.PCI0.XHC_.RHUB.HS07-7:1.0-5986:1193".to_string(),
+            ),
+            ("41".to_string(), "v4l2:/dev/video0".to_string()),
+            (
+                "42".to_string(),
+                "libcamera:/base/soc/i2c0/ov5647@36".to_string(),
+            ),
+        ]);
+
+        let paired = pair_source_kinds(cameras, &object_paths);
+        assert_eq!(paired.len(), 2);
+        // USB camera: V4L2 preferred, libcamera kept as the alternate
+        assert_eq!(paired[0].path, "pipewire-serial-41");
+        assert_eq!(
+            paired[0].source_kinds(),
+            vec![CameraSourceKind::V4l2, CameraSourceKind::Libcamera]
+        );
+        assert_eq!(paired[1].path, "pipewire-serial-42");
+        assert!(paired[1].alternate_source.is_none());
+
+        // Switching stacks swaps the entries and keeps the override key
+        let key = paired[0].source_key().to_string();
+        let libcamera = paired[0].clone().through(CameraSourceKind::Libcamera);
+        assert_eq!(libcamera.path, "pipewire-serial-40");
+        assert_eq!(libcamera.source_key(), key);
+        let v4l2 = libcamera.through(CameraSourceKind::V4l2);
+        assert_eq!(v4l2.path, "pipewire-serial-41");
+    }
+
+    #[test]
+    fn test_identical_cameras_stay_separate() {
+        let webcam = UsbId::new(0x046d, 0x085e);
+        let cameras = vec![
+            camera("50", CameraSourceKind::V4l2, Some(webcam)),
+            camera("51", CameraSourceKind::V4l2, Some(webcam)),
+            camera("52", CameraSourceKind::Libcamera, None),
+        ];
+        let object_paths = HashMap::from([(
+            "52".to_string(),
+            "libcamera:\\_SB_.PCI0.XHC_.RHUB.HS03-3:1.0-046d:085e".to_string(),
+        )]);
+        assert_eq!(pair_source_kinds(cameras, &object_paths).len(), 3);
+    }
+
+    #[test]
+    fn test_only_capture_nodes_are_listed() {
+        // A UVC webcam with a metadata node, a camera mirroring its stream on
+        // two capture nodes, and a node that can't be queried
+        let node = |card: &str, bus_info: &str, caps: u32| V4l2Node {
+            driver: "uvcvideo".to_string(),
+            card: card.to_string(),
+            bus_info: bus_info.to_string(),
+            caps: caps | V4L2_CAP_DEVICE_CAPS,
+        };
+        let nodes = HashMap::from([
+            (
+                "/dev/video0",
+                node("Integrated Camera", "usb-0000:00:14.0-5", 0x0420_0001),
+            ),
+            // V4L2_CAP_META_CAPTURE | V4L2_CAP_STREAMING
+            (
+                "/dev/video1",
+                node("Integrated Camera", "usb-0000:00:14.0-5", 0x0480_0000),
+            ),
+            (
+                "/dev/video2",
+                node("Capture Card", "usb-0000:00:14.0-2", 0x0420_1000),
+            ),
+            (
+                "/dev/video3",
+                node("Capture Card", "usb-0000:00:14.0-2", 0x0420_1000),
+            ),
+            // Same model on another port
+            (
+                "/dev/video4",
+                node("Integrated Camera", "usb-0000:00:14.0-6", 0x0420_0001),
+            ),
+        ]);
+        let cameras: Vec<CameraDevice> = (0..6)
+            .map(|n| {
+                let mut camera = camera(&n.to_string(), CameraSourceKind::V4l2, None);
+                camera.device_info = Some(DeviceInfo {
+                    card: String::new(),
+                    driver: "uvcvideo".to_string(),
+                    path: format!("/dev/video{}", n),
+                    real_path: format!("/dev/video{}", n),
+                    usb_id: None,
+                });
+                camera
+            })
+            .collect();
+
+        let listed = hide_non_capture_nodes(cameras, |path| nodes.get(path).cloned());
+        let paths: Vec<&str> = listed
+            .iter()
+            .filter_map(|camera| camera.device_info.as_ref())
+            .map(|info| info.path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            ["/dev/video0", "/dev/video2", "/dev/video4", "/dev/video5"]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Camera hotplug events
+//!
+//! GStreamer's device monitor (backed by the PipeWire device provider) posts
+//! a message whenever a video source node appears or goes away. The events
+//! only say that something changed: the camera list is enumerated again
+//! once they stop arriving, since a dock reconnect or a camera exposing
+//! several nodes produces a burst of them.
+
+use crate::backends::camera::types::CameraDevice;
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// How often the monitor thread checks whether anyone is still listening
+const BUS_POLL: Duration = Duration::from_millis(500);
+
+/// Start watching for video sources being added or removed
+///
+/// Each device event sends a `()`. Returns `None` if no device provider
+/// could be started, in which case the camera list has to be polled. The
+/// monitor stops once the receiver is dropped.
+pub fn watch_devices() -> Option<mpsc::UnboundedReceiver<()>> {
+    gst::init().ok()?;
+    let monitor = gst::DeviceMonitor::new();
+    monitor.add_filter(Some("Video/Source"), None);
+    if let Err(err) = monitor.start() {
+        warn!(?err, "Failed to start device monitor");
+        return None;
+    }
+    let bus = monitor.bus();
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    let spawned = std::thread::Builder::new()
+        .name("camera-hotplug".to_string())
+        .spawn(move || {
+            let types = [
+                gst::MessageType::DeviceAdded,
+                gst::MessageType::DeviceRemoved,
+                gst::MessageType::DeviceChanged,
+            ];
+            while !sender.is_closed() {
+                let Some(message) = bus.timed_pop_filtered(
+                    gst::ClockTime::from_mseconds(BUS_POLL.as_millis() as u64),
+                    &types,
+                ) else {
+                    continue;
+                };
+                debug!(kind = ?message.type_(), "Device event");
+                if sender.send(()).is_err() {
+                    break;
+                }
+            }
+            monitor.stop();
+            debug!("Device monitor stopped");
+        });
+    if let Err(err) = spawned {
+        warn!(?err, "Failed to start device monitor thread");
+        return None;
+    }
+    info!("Watching for camera hotplug events");
+    Some(receiver)
+}
+
+/// Wait until no event arrived for `quiet`, or `max` passed since the call
+///
+/// Returns `false` if the monitor went away in the meantime.
+pub async fn settle(
+    events: &mut mpsc::UnboundedReceiver<()>,
+    quiet: Duration,
+    max: Duration,
+) -> bool {
+    let deadline = tokio::time::Instant::now() + max;
+    loop {
+        let wait = quiet.min(deadline.saturating_duration_since(tokio::time::Instant::now()));
+        if wait.is_zero() {
+            return true;
+        }
+        match tokio::time::timeout(wait, events.recv()).await {
+            Ok(Some(())) => continue,
+            Ok(None) => return false,
+            Err(_) => return true,
+        }
+    }
+}
+
+/// Whether `new` lists other cameras than `old` (order doesn't matter)
+pub fn device_list_differs(old: &[CameraDevice], new: &[CameraDevice]) -> bool {
+    old.len() != new.len()
+        || !old
+            .iter()
+            .all(|c| new.iter().any(|nc| nc.path == c.path && nc.name == c.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(name: &str, path: &str) -> CameraDevice {
+        CameraDevice {
+            name: name.to_string(),
+            path: path.to_string(),
+            metadata_path: None,
+            device_info: None,
+            rotation: Default::default(),
+            facing: Default::default(),
+            source_kind: None,
+            alternate_source: None,
+        }
+    }
+
+    #[test]
+    fn test_device_list_differs() {
+        let a = camera("Integrated Camera", "pipewire-42");
+        let b = camera("USB Webcam", "pipewire-57");
+        assert!(!device_list_differs(
+            &[a.clone(), b.clone()],
+            &[b.clone(), a.clone()]
+        ));
+        assert!(device_list_differs(&[a.clone(), b.clone()], &[a.clone()]));
+        // Same node id reused by another camera
+        assert!(device_list_differs(
+            &[a.clone()],
+            &[camera("USB Webcam", "pipewire-42")]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_burst_is_settled_once() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let burst = tokio::spawn(async move {
+            for _ in 0..5 {
+                sender.send(()).unwrap();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            sender
+        });
+        receiver.recv().await.unwrap();
+        let quiet = Duration::from_millis(100);
+        assert!(settle(&mut receiver, quiet, Duration::from_secs(5)).await);
+        // The whole burst was swallowed
+        assert!(receiver.try_recv().is_err());
+
+        let sender = burst.await.unwrap();
+        drop(sender);
+        assert!(!settle(&mut receiver, quiet, Duration::from_secs(5)).await);
+    }
+}
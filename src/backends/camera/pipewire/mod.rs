@@ -7,11 +7,19 @@
 //! This backend uses PipeWire for camera enumeration, format detection, and capture.
 //! It's the modern, recommended approach for Linux camera access.
 
+mod cadence;
+pub mod checksum;
+mod decimation;
 mod enumeration;
+pub mod hotplug;
 pub mod pipeline;
+pub mod raw_dump;
+pub mod thumbnail;
 
+pub use cadence::FrameCadence;
 pub use enumeration::{enumerate_pipewire_cameras, get_pipewire_formats, is_pipewire_available};
 pub use pipeline::PipeWirePipeline;
+pub use thumbnail::{DeviceThumbnail, capture_device_thumbnail, capture_device_thumbnails};
 
 use super::CameraBackend;
 use super::types::*;
@@ -62,8 +70,9 @@ impl PipeWireBackend {
             .ok_or_else(|| BackendError::Other("No format set".to_string()))?;
 
         // Create frame channel (small capacity for low latency)
-        let (sender, receiver) = cosmic::iced::futures::channel::mpsc::channel(
+        let (sender, receiver) = crate::backends::camera::frame_queue::channel(
             crate::constants::latency::FRAME_CHANNEL_CAPACITY,
+            crate::backends::camera::FrameDropPolicy::default(),
         );
 
         // Create pipeline
@@ -3,15 +3,18 @@
 //! PipeWire GStreamer pipeline for camera capture
 
 use super::super::types::*;
+use super::super::usb::VideoMode;
 use crate::constants::{pipeline, timing};
+use crate::media::formats::{InterlaceInfo, interlace_from_caps};
 use crate::media::{Codec, PipelineBackend, detect_hw_decoders, try_create_pipeline};
 use gstreamer::prelude::*;
 use gstreamer_app::AppSink;
 use gstreamer_video::VideoInfo;
+use std::collections::{BTreeMap, VecDeque};
 use std::path::PathBuf;
-use std::sync::RwLock;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 static FRAME_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -21,6 +24,52 @@ static DROPPED_FRAMES: AtomicU64 = AtomicU64::new(0);
 static LAST_FRAME_SIZE: AtomicU64 = AtomicU64::new(0);
 static COPY_TIME_US: AtomicU64 = AtomicU64::new(0);
 static OUTPUT_FORMAT: RwLock<Option<String>> = RwLock::new(None);
+static COLORIMETRY: RwLock<Option<String>> = RwLock::new(None);
+static INTERLACE: RwLock<Option<InterlaceInfo>> = RwLock::new(None);
+/// Resolution and frame rate negotiated on the source pad
+static SOURCE_MODE: RwLock<Option<VideoMode>> = RwLock::new(None);
+static BUS_ERRORS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static FROZEN: AtomicBool = AtomicBool::new(false);
+/// Copy frames into owned memory instead of wrapping the mapped buffer (upload benchmark)
+static FORCE_COPY: AtomicBool = AtomicBool::new(false);
+static STEPS_SINCE_FREEZE: AtomicU64 = AtomicU64::new(0);
+/// PTS distance between buffers handed to the appsink (0 = no throttling)
+static THROTTLE_INTERVAL_NS: AtomicU64 = AtomicU64::new(0);
+/// PTS of the last buffer handed to the appsink (u64::MAX = none yet)
+static LAST_PTS_NS: AtomicU64 = AtomicU64::new(u64::MAX);
+/// Frame intervals reaching the appsink, for CFR/VFR classification
+static CADENCE: Mutex<super::cadence::CadenceTracker> =
+    Mutex::new(super::cadence::CadenceTracker::new());
+/// Samples pulled since the current pipeline was created
+static SESSION_FRAMES: AtomicU64 = AtomicU64::new(0);
+/// When the current pipeline was created
+static SESSION_STARTED_AT: RwLock<Option<Instant>> = RwLock::new(None);
+/// When the appsink last delivered a sample (None = none since the pipeline started)
+static LAST_SAMPLE_AT: RwLock<Option<Instant>> = RwLock::new(None);
+/// Size of the last sample, `width << 32 | height` (0 = none since the pipeline started)
+static SAMPLE_SIZE: AtomicU64 = AtomicU64::new(0);
+/// Mid-stream resolution changes seen by the current pipeline
+static RESOLUTION_CHANGES: AtomicU64 = AtomicU64::new(0);
+/// Device quirk applied to the current pipeline
+static APPLIED_QUIRK: RwLock<Option<&'static str>> = RwLock::new(None);
+/// Decoder selected for the current pipeline (None for raw formats)
+static ACTIVE_DECODER: RwLock<Option<String>> = RwLock::new(None);
+/// Decode errors per decoder factory since the current pipeline was created
+static DECODER_ERRORS: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+/// `max-buffers` requested for the preview appsink (0 = automatic)
+static APPSINK_MAX_BUFFERS: AtomicU32 = AtomicU32::new(0);
+/// Whether the preview appsink discards its oldest buffer when full
+static APPSINK_DROP: AtomicBool = AtomicBool::new(true);
+/// `max-buffers` the current pipeline picked for its frame rate
+static APPSINK_AUTOMATIC_BUFFERS: AtomicU32 = AtomicU32::new(pipeline::MAX_BUFFERS);
+/// Smallest `max-buffers` the current device's quirk allows (0 = no minimum)
+static APPSINK_MIN_BUFFERS: AtomicU32 = AtomicU32::new(0);
+/// The current pipeline, for read-only inspection
+static ACTIVE_PIPELINE: Mutex<Option<gstreamer::glib::WeakRef<gstreamer::Pipeline>>> =
+    Mutex::new(None);
+
+/// Number of bus errors/warnings kept for diagnostics
+const BUS_ERROR_HISTORY: usize = 20;
 
 /// Get the decode time in microseconds
 pub fn get_decode_time_us() -> u64 {
@@ -37,6 +86,11 @@ pub fn get_last_frame_size() -> u64 {
     LAST_FRAME_SIZE.load(Ordering::Relaxed)
 }
 
+/// When the appsink last delivered a sample, i.e. the newest decoded frame
+pub fn get_last_sample_at() -> Option<Instant> {
+    LAST_SAMPLE_AT.read().ok().and_then(|guard| *guard)
+}
+
 /// Get the copy time in microseconds
 pub fn get_copy_time_us() -> u64 {
     COPY_TIME_US.load(Ordering::Relaxed)
@@ -47,8 +101,360 @@ pub fn get_output_format() -> Option<String> {
     OUTPUT_FORMAT.read().ok().and_then(|guard| guard.clone())
 }
 
+/// Get the colorimetry declared by the camera source caps (e.g. "bt709", "2:4:7:1")
+///
+/// Taken before any conversion, and updated when the source renegotiates.
+/// `None` when the caps omit the field or the source hasn't negotiated yet.
+pub fn get_colorimetry() -> Option<String> {
+    COLORIMETRY.read().ok().and_then(|guard| guard.clone())
+}
+
+/// Get the interlacing declared by the camera source caps
+///
+/// `None` until the source has negotiated caps.
+pub fn get_interlace_info() -> Option<InterlaceInfo> {
+    INTERLACE.read().ok().and_then(|guard| guard.clone())
+}
+
+/// Get the most recent GStreamer bus errors and warnings, oldest first
+pub fn get_recent_bus_errors() -> Vec<String> {
+    BUS_ERRORS
+        .lock()
+        .map(|guard| guard.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Get the decode errors each decoder reported since the pipeline was created
+///
+/// Keyed by decoder factory name (e.g. "vaapijpegdec"). Decoders that
+/// reported nothing are left out.
+pub fn get_decoder_errors() -> Vec<(String, u64)> {
+    DECODER_ERRORS
+        .lock()
+        .map(|guard| {
+            guard
+                .iter()
+                .map(|(decoder, errors)| (decoder.clone(), *errors))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Count a decode error against `decoder`, or the selected decoder if `None`
+fn record_decoder_error(decoder: Option<&str>) {
+    let active = ACTIVE_DECODER.read().ok().and_then(|guard| guard.clone());
+    let Some(decoder) = decoder.map(str::to_string).or(active) else {
+        return;
+    };
+    if let Ok(mut guard) = DECODER_ERRORS.lock() {
+        *guard.entry(decoder).or_default() += 1;
+    }
+}
+
+/// Factory name of a bus message's source, if that source is a decoder
+fn decoder_factory(src: Option<&gstreamer::Object>) -> Option<String> {
+    let factory = src?.downcast_ref::<gstreamer::Element>()?.factory()?;
+    factory
+        .klass()
+        .contains("Decoder")
+        .then(|| factory.name().to_string())
+}
+
+/// Freeze or resume the preview
+///
+/// Freezing pauses the appsink alone, with its state locked so the pipeline
+/// stays PLAYING: a live camera source stops producing in PAUSED, so a
+/// pipeline paused as a whole could never be stepped. The paused appsink
+/// holds the frame it prerolled and blocks the stream behind it; each step
+/// event lets the next buffer through. Pipelines with a leaky queue keep the
+/// camera itself streaming meanwhile.
+pub fn set_frozen(frozen: bool) {
+    STEPS_SINCE_FREEZE.store(0, Ordering::Relaxed);
+    FROZEN.store(frozen, Ordering::Relaxed);
+    if let Some(appsink) = active_appsink() {
+        set_appsink_paused(&appsink, frozen);
+    }
+    // No samples arrive while frozen; restart the stall clock on resume
+    if !frozen
+        && let Ok(mut guard) = LAST_SAMPLE_AT.write()
+        && guard.is_some()
+    {
+        *guard = Some(Instant::now());
+    }
+    info!(frozen, "Preview freeze changed");
+}
+
+/// Appsink of the current pipeline
+fn active_appsink() -> Option<gstreamer::Element> {
+    get_active_pipeline()?.by_name("sink")
+}
+
+/// Pause the appsink on its own, or hand its state back to the pipeline
+fn set_appsink_paused(appsink: &gstreamer::Element, paused: bool) {
+    if paused {
+        appsink.set_locked_state(true);
+        if let Err(err) = appsink.set_state(gstreamer::State::Paused) {
+            warn!(error = %err, "Failed to pause the appsink");
+        }
+    } else {
+        appsink.set_locked_state(false);
+        if let Err(err) = appsink.sync_state_with_parent() {
+            warn!(error = %err, "Failed to resume the appsink");
+        }
+    }
+}
+
+/// Whether the preview is frozen
+pub fn is_frozen() -> bool {
+    FROZEN.load(Ordering::Relaxed)
+}
+
+/// Copy each frame out of the GStreamer buffer instead of wrapping it
+///
+/// Only used to compare the two upload paths; plane offsets stay valid since
+/// the whole buffer is copied.
+pub fn set_force_copy(force_copy: bool) {
+    FORCE_COPY.store(force_copy, Ordering::Relaxed);
+    info!(force_copy, "Frame upload path changed");
+}
+
+/// Let at most `max_fps` frames per second through to the preview (`None` = all)
+///
+/// Like freezing, this gates decoded buffers at the appsink, so the camera
+/// keeps its mode and resuming is immediate. The frames let through are
+/// evenly spaced.
+pub fn set_throttle(max_fps: Option<u32>) {
+    let interval = max_fps
+        .filter(|&fps| fps > 0)
+        .map_or(0, |fps| 1_000_000_000 / fps as u64);
+    if THROTTLE_INTERVAL_NS.swap(interval, Ordering::Relaxed) != interval {
+        info!(?max_fps, "Preview throttle changed");
+    }
+}
+
+/// Frame rate the preview is throttled to, if any
+pub fn get_throttle() -> Option<u32> {
+    match THROTTLE_INTERVAL_NS.load(Ordering::Relaxed) {
+        0 => None,
+        interval => Some((1_000_000_000 / interval) as u32),
+    }
+}
+
+/// Advance a frozen preview by exactly one decoded frame
+///
+/// Sends a step event to the paused appsink, which drops the frame it holds
+/// and prerolls the next one. The appsink sits after the decoder and any
+/// conversion, so a step always moves one complete frame whatever decoder is
+/// in use.
+pub fn step_frame() {
+    if !is_frozen() {
+        return;
+    }
+    let Some(appsink) = active_appsink() else {
+        return;
+    };
+    let step =
+        gstreamer::event::Step::new(gstreamer::format::Buffers::from_u64(1), 1.0, true, false);
+    if appsink.send_event(step) {
+        STEPS_SINCE_FREEZE.fetch_add(1, Ordering::Relaxed);
+    } else {
+        warn!("Appsink refused the step event");
+    }
+}
+
+/// Number of frames stepped since the preview was frozen
+pub fn get_steps_since_freeze() -> u64 {
+    STEPS_SINCE_FREEZE.load(Ordering::Relaxed)
+}
+
+/// Get the presentation timestamp of the last frame delivered to the preview
+pub fn get_last_pts() -> Option<Duration> {
+    match LAST_PTS_NS.load(Ordering::Relaxed) {
+        u64::MAX => None,
+        ns => Some(Duration::from_nanos(ns)),
+    }
+}
+
+/// Measured frame rate of the current pipeline and whether it is variable
+///
+/// `None` until enough frames have arrived to tell.
+pub fn get_frame_cadence() -> Option<super::FrameCadence> {
+    CADENCE.lock().ok()?.cadence()
+}
+
+/// Get the samples pulled and the start time of the current pipeline
+///
+/// Both reset whenever the pipeline is rebuilt (camera or format change).
+pub fn get_session_stats() -> (u64, Option<Instant>) {
+    let started_at = SESSION_STARTED_AT.read().ok().and_then(|guard| *guard);
+    (SESSION_FRAMES.load(Ordering::Relaxed), started_at)
+}
+
+/// Get how often the source changed resolution while the current pipeline ran
+///
+/// Sources such as virtual or network cameras may renegotiate their caps
+/// mid-stream. Frames keep flowing at the new size and the preview textures
+/// follow it, so this is informational.
+pub fn get_resolution_changes() -> u64 {
+    RESOLUTION_CHANGES.load(Ordering::Relaxed)
+}
+
+/// Note the size of a sample, returning the previous one if it differs
+fn note_sample_size(width: u32, height: u32) -> Option<(u32, u32)> {
+    let packed = (u64::from(width) << 32) | u64::from(height);
+    let previous = SAMPLE_SIZE.swap(packed, Ordering::Relaxed);
+    (previous != 0 && previous != packed).then(|| ((previous >> 32) as u32, previous as u32))
+}
+
+/// Time since the appsink last delivered a sample
+///
+/// `None` until the current pipeline delivered its first sample; a camera
+/// that never starts streaming is left to the open timeout.
+pub fn get_time_since_last_sample() -> Option<Duration> {
+    LAST_SAMPLE_AT
+        .read()
+        .ok()
+        .and_then(|guard| *guard)
+        .map(|at| at.elapsed())
+}
+
 /// PipeWire camera pipeline
 ///
+/// Get the resolution and frame rate negotiated with the camera
+pub fn get_negotiated_mode() -> Option<VideoMode> {
+    SOURCE_MODE.read().ok().and_then(|guard| *guard)
+}
+
+/// Get the name of the device quirk applied to the current pipeline
+pub fn get_applied_quirk() -> Option<&'static str> {
+    APPLIED_QUIRK.read().ok().and_then(|guard| *guard)
+}
+
+/// Get the current pipeline while it is alive (for property inspection)
+pub fn get_active_pipeline() -> Option<gstreamer::Pipeline> {
+    ACTIVE_PIPELINE
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().and_then(|weak| weak.upgrade()))
+}
+
+/// Choose the preview appsink's `max-buffers` (None = automatic) and `drop`
+///
+/// Counts are clamped to `1..=APPSINK_MAX_BUFFERS_LIMIT`. The running
+/// appsink is reconfigured in place; later pipelines pick the values up when
+/// they are built.
+///
+/// The appsink holds decoded frames until its callback has pulled them into
+/// the frame queue, where the frame drop policy applies, so the two act on
+/// different stages: the appsink decides which frames reach the queue, the
+/// policy which of those the preview shows. The appsink only fills up while
+/// its callback is still busy with an earlier frame. Then `drop` discards
+/// the oldest buffer; without it the streaming thread blocks, throttling the
+/// camera instead of losing frames.
+pub fn set_appsink_buffering(max_buffers: Option<u32>, drop: bool) {
+    let requested = max_buffers.map_or(0, |count| {
+        count.clamp(1, pipeline::APPSINK_MAX_BUFFERS_LIMIT)
+    });
+    APPSINK_MAX_BUFFERS.store(requested, Ordering::Relaxed);
+    APPSINK_DROP.store(drop, Ordering::Relaxed);
+
+    if let Some(sink) = get_active_pipeline().and_then(|pipeline| pipeline.by_name("sink")) {
+        let count = appsink_max_buffers(
+            requested_appsink_buffers(),
+            APPSINK_AUTOMATIC_BUFFERS.load(Ordering::Relaxed),
+            APPSINK_MIN_BUFFERS.load(Ordering::Relaxed),
+        );
+        sink.set_property("max-buffers", count);
+        sink.set_property("drop", drop);
+        info!(max_buffers = count, drop, "Reconfigured preview appsink");
+    }
+}
+
+/// Requested `max-buffers` for the preview appsink (None = automatic)
+fn requested_appsink_buffers() -> Option<u32> {
+    Some(APPSINK_MAX_BUFFERS.load(Ordering::Relaxed)).filter(|&count| count > 0)
+}
+
+/// `max-buffers` for the preview appsink
+///
+/// A requested count replaces the automatic one, but never goes below the
+/// minimum a device quirk needs to deliver complete frames.
+fn appsink_max_buffers(requested: Option<u32>, automatic: u32, quirk_min: u32) -> u32 {
+    requested.unwrap_or(automatic).max(quirk_min)
+}
+
+/// `max-buffers` and `drop` of the running preview appsink
+pub fn get_appsink_buffering() -> Option<(u32, bool)> {
+    let sink = get_active_pipeline()?.by_name("sink")?;
+    Some((
+        sink.property::<u32>("max-buffers"),
+        sink.property::<bool>("drop"),
+    ))
+}
+
+/// Graphviz description of the current pipeline, with element states and caps
+///
+/// Taken from the running pipeline, so it shows what decodebin and friends
+/// expanded into and where each element's state is at the moment.
+pub fn get_active_pipeline_dot() -> Option<String> {
+    let pipeline = get_active_pipeline()?;
+    Some(gstreamer::debug_bin_to_dot_data(&pipeline, gstreamer::DebugGraphDetails::ALL).to_string())
+}
+
+/// Stop the current pipeline and check that the camera was released
+///
+/// Used on shutdown, where the subscription owning the pipeline may not get to
+/// drop it before the process exits. Waits for the pipeline to reach NULL,
+/// then re-opens `device_node` (the V4L2 node behind the camera, if any) until
+/// no stream holds its buffers any more. Blocks for up to the stop timeout.
+pub fn release_active_pipeline(device_node: Option<&str>) -> Result<(), String> {
+    release_pipeline(get_active_pipeline().as_ref(), device_node)
+}
+
+/// Stop `pipeline` (if any) and wait until `device_node` is free
+fn release_pipeline(
+    pipeline: Option<&gstreamer::Pipeline>,
+    device_node: Option<&str>,
+) -> Result<(), String> {
+    if let Some(pipeline) = pipeline {
+        info!("Releasing camera pipeline");
+        pipeline
+            .set_state(gstreamer::State::Null)
+            .map_err(|err| format!("Camera pipeline refused to stop: {}", err))?;
+        let (result, state, pending) = pipeline.state(gstreamer::ClockTime::from_seconds(
+            timing::STOP_TIMEOUT_SECS,
+        ));
+        if result.is_err() || state != gstreamer::State::Null {
+            return Err(format!(
+                "Camera pipeline stuck in {:?} (pending {:?})",
+                state, pending
+            ));
+        }
+    }
+
+    let Some(device_node) = device_node else {
+        return Ok(());
+    };
+    // PipeWire frees the device's buffers shortly after our stream goes away
+    let deadline = Instant::now() + Duration::from_secs(timing::STOP_TIMEOUT_SECS);
+    loop {
+        match super::super::v4l2_controls::is_capture_busy(device_node) {
+            Some(false) => {
+                info!(device_node, "Camera device is free");
+                return Ok(());
+            }
+            Some(true) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(timing::RELEASE_POLL_MS));
+            }
+            Some(true) => return Err(format!("{} is still in use", device_node)),
+            None => {
+                debug!(device_node, "Can't check whether the camera device is free");
+                return Ok(());
+            }
+        }
+    }
+}
+
 /// Native GStreamer pipeline implementation using pipewiresrc for camera capture.
 /// Handles preview streaming with hardware-accelerated decoding.
 pub struct PipeWirePipeline {
@@ -56,6 +462,8 @@ pub struct PipeWirePipeline {
     _appsink: AppSink,
     pub decoder: String,
     recording: bool,
+    /// Becomes true once the first valid frame has reached the appsink
+    first_frame: tokio::sync::watch::Receiver<bool>,
 }
 
 impl PipeWirePipeline {
@@ -107,6 +515,26 @@ impl PipeWirePipeline {
         };
 
         info!(?device_path, caps_filter, "Initializing PipeWire camera");
+        // Used instead of PipeWire when a V4L2 io-mode is forced
+        crate::media::decoders::set_v4l2_node(
+            device.device_info.as_ref().map(|info| info.path.clone()),
+        );
+
+        let quirk = super::super::quirks::for_device(device);
+        match quirk {
+            Some(quirk) => info!(
+                quirk = quirk.name,
+                usb_id = %quirk.id,
+                discard_frames = quirk.discard_frames,
+                min_buffers = ?quirk.min_buffers,
+                "Applying device quirk"
+            ),
+            None => debug!("No device quirk for this camera"),
+        }
+        if let Ok(mut guard) = APPLIED_QUIRK.write() {
+            *guard = quirk.map(|quirk| quirk.name);
+        }
+        let discard_frames = quirk.map_or(0, |quirk| u64::from(quirk.discard_frames));
 
         // Check if format needs a decoder
         let needs_decoder = pixel_format
@@ -174,16 +602,24 @@ impl PipeWirePipeline {
             }
             _ => {
                 error!("All decoders failed");
-                return Err(BackendError::InitializationFailed(
-                    last_error
-                        .map(|e| e.to_string())
-                        .unwrap_or_else(|| "No decoders available".to_string()),
-                ));
+                return Err(last_error.map(BackendError::Pipeline).unwrap_or_else(|| {
+                    BackendError::InitializationFailed("No decoders available".to_string())
+                }));
             }
         };
 
         debug!("Pipeline ready");
 
+        // Tap the source for retroactive clips (no-op while the feature is disabled)
+        crate::pipelines::video::retro::attach(&pipeline);
+
+        // Record source interlacing before any deinterlace element rewrites the caps
+        Self::watch_source_caps(&pipeline);
+        Self::watch_bus_errors(&pipeline);
+        if let Ok(mut guard) = ACTIVE_PIPELINE.lock() {
+            *guard = Some(pipeline.downgrade());
+        }
+
         // Get the appsink element
         debug!("Getting appsink element");
         let appsink = pipeline
@@ -195,6 +631,8 @@ impl PipeWirePipeline {
             })?;
         debug!("Got appsink element");
 
+        Self::install_step_gate(&appsink);
+
         // Configure appsink for maximum performance
         debug!("Configuring appsink");
         appsink.set_property("emit-signals", true);
@@ -208,264 +646,383 @@ impl PipeWirePipeline {
         } else {
             pipeline::MAX_BUFFERS
         };
+        let min_buffers = quirk.and_then(|quirk| quirk.min_buffers).unwrap_or(0);
+        APPSINK_AUTOMATIC_BUFFERS.store(buffer_count, Ordering::Relaxed);
+        APPSINK_MIN_BUFFERS.store(min_buffers, Ordering::Relaxed);
+        let buffer_count =
+            appsink_max_buffers(requested_appsink_buffers(), buffer_count, min_buffers);
+        let drop = APPSINK_DROP.load(Ordering::Relaxed);
         appsink.set_property("max-buffers", buffer_count);
-        appsink.set_property("drop", true); // Drop old frames if processing is slow
+        appsink.set_property("drop", drop); // Drop old frames if processing is slow
         appsink.set_property("enable-last-sample", false); // Don't keep last sample in memory
 
         debug!(
             buffer_count,
+            drop,
             fps = fps_int,
             "Appsink configured for maximum performance"
         );
 
-        // Set up callback for new samples with performance tracking
-        debug!("Setting up frame callback");
-        appsink.set_callbacks(
-            gstreamer_app::AppSinkCallbacks::builder()
-                .new_sample(move |appsink| {
-                    let frame_start = Instant::now();
-                    let frame_num = FRAME_COUNTER.fetch_add(1, Ordering::Relaxed);
-
-                    // Pull and decode sample
-                    let sample = match appsink.pull_sample() {
-                        Ok(s) => s,
-                        Err(e) => {
-                            if frame_num.is_multiple_of(30) {
-                                error!(frame = frame_num, error = ?e, "Failed to pull sample");
-                            }
-                            return Err(gstreamer::FlowError::Eos);
-                        }
-                    };
+        SESSION_FRAMES.store(0, Ordering::Relaxed);
+        if let Ok(mut guard) = DECODER_ERRORS.lock() {
+            guard.clear();
+        }
+        if let Ok(mut guard) = ACTIVE_DECODER.write() {
+            *guard = needs_decoder.then(|| decoder_name.clone());
+        }
+        SAMPLE_SIZE.store(0, Ordering::Relaxed);
+        RESOLUTION_CHANGES.store(0, Ordering::Relaxed);
+        if let Ok(mut guard) = SESSION_STARTED_AT.write() {
+            *guard = Some(Instant::now());
+        }
+        if let Ok(mut guard) = LAST_SAMPLE_AT.write() {
+            *guard = None;
+        }
 
-                    let buffer = sample.buffer().ok_or_else(|| {
-                        if frame_num.is_multiple_of(30) {
-                            error!(frame = frame_num, "No buffer in sample");
-                        }
-                        gstreamer::FlowError::Error
-                    })?;
+        // Created before the callbacks are installed so a frame arriving during the
+        // PLAYING transition is never missed by a later wait_for_first_frame()
+        let (first_frame_tx, first_frame) = tokio::sync::watch::channel(false);
 
-                    // Check buffer flags for incomplete/corrupted frames
-                    // This can happen at high framerates when DMA transfers aren't complete
-                    let buffer_flags = buffer.flags();
-                    if buffer_flags.contains(gstreamer::BufferFlags::CORRUPTED) {
+        // Set up callback for new samples with performance tracking
+        debug!("Setting up frame callback");
+        // Shared by both callbacks: stepped frames arrive as preroll while the
+        // appsink is paused
+        let on_sample = std::sync::Arc::new(
+            move |pulled: Result<gstreamer::Sample, gstreamer::glib::BoolError>| {
+                let frame_start = Instant::now();
+                let frame_num = FRAME_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+                // Pull and decode sample
+                let sample = match pulled {
+                    Ok(s) => s,
+                    Err(e) => {
                         if frame_num.is_multiple_of(30) {
-                            warn!(frame = frame_num, "Buffer marked as corrupted, skipping frame");
+                            error!(frame = frame_num, error = ?e, "Failed to pull sample");
                         }
-                        return Err(gstreamer::FlowError::Error);
+                        return Err(gstreamer::FlowError::Eos);
                     }
+                };
+                let session_frame = SESSION_FRAMES.fetch_add(1, Ordering::Relaxed);
+                if let Ok(mut guard) = LAST_SAMPLE_AT.write() {
+                    *guard = Some(frame_start);
+                }
 
-                    let caps = sample.caps().ok_or_else(|| {
-                        if frame_num.is_multiple_of(30) {
-                            error!(frame = frame_num, "No caps in sample");
-                        }
-                        gstreamer::FlowError::Error
-                    })?;
+                // Device quirk: the camera sends unusable frames right after stream-on
+                if session_frame < discard_frames {
+                    debug!(
+                        frame = session_frame,
+                        "Discarding warm-up frame (device quirk)"
+                    );
+                    return Ok(gstreamer::FlowSuccess::Ok);
+                }
 
-                    let video_info = VideoInfo::from_caps(caps).map_err(|e| {
-                        if frame_num.is_multiple_of(30) {
-                            error!(frame = frame_num, error = ?e, "Failed to get video info");
-                        }
-                        gstreamer::FlowError::Error
-                    })?;
-
-                    // Detect pixel format from GStreamer caps
-                    let gst_format = video_info.format();
-                    let pixel_format = match gst_format {
-                        // Semi-planar 4:2:0 formats
-                        gstreamer_video::VideoFormat::Nv12 => PixelFormat::NV12,
-                        gstreamer_video::VideoFormat::Nv21 => PixelFormat::NV21,
-                        // Planar 4:2:0 formats
-                        gstreamer_video::VideoFormat::I420 | gstreamer_video::VideoFormat::Yv12 => PixelFormat::I420,
-                        // Packed 4:2:2 formats
-                        gstreamer_video::VideoFormat::Yuy2 => PixelFormat::YUYV,
-                        gstreamer_video::VideoFormat::Uyvy => PixelFormat::UYVY,
-                        gstreamer_video::VideoFormat::Yvyu => PixelFormat::YVYU,
-                        gstreamer_video::VideoFormat::Vyuy => PixelFormat::VYUY,
-                        // Grayscale
-                        gstreamer_video::VideoFormat::Gray8 => PixelFormat::Gray8,
-                        // RGBA variants
-                        gstreamer_video::VideoFormat::Rgba | gstreamer_video::VideoFormat::Rgbx |
-                        gstreamer_video::VideoFormat::Bgra | gstreamer_video::VideoFormat::Bgrx |
-                        gstreamer_video::VideoFormat::Argb | gstreamer_video::VideoFormat::Abgr |
-                        gstreamer_video::VideoFormat::Xrgb | gstreamer_video::VideoFormat::Xbgr => PixelFormat::RGBA,
-                        // RGB24 variants (should have been converted to RGBA by pipeline)
-                        gstreamer_video::VideoFormat::Rgb | gstreamer_video::VideoFormat::Bgr => PixelFormat::RGB24,
-                        _ => {
-                            // Unknown format - log warning and assume NV12 (fallback should have converted to it)
-                            if frame_num.is_multiple_of(60) {
-                                warn!(frame = frame_num, format = ?gst_format, "Unknown video format, assuming NV12 (fallback conversion)");
-                            }
-                            PixelFormat::NV12
-                        }
-                    };
+                let buffer = sample.buffer().ok_or_else(|| {
+                    if frame_num.is_multiple_of(30) {
+                        error!(frame = frame_num, "No buffer in sample");
+                    }
+                    gstreamer::FlowError::Error
+                })?;
+
+                // Check buffer flags for incomplete/corrupted frames
+                // This can happen at high framerates when DMA transfers aren't complete
+                let buffer_flags = buffer.flags();
+                if buffer_flags.contains(gstreamer::BufferFlags::CORRUPTED) {
+                    record_decoder_error(None);
+                    if frame_num.is_multiple_of(30) {
+                        warn!(
+                            frame = frame_num,
+                            "Buffer marked as corrupted, skipping frame"
+                        );
+                    }
+                    return Err(gstreamer::FlowError::Error);
+                }
 
-                    // Store output format for insights (only on first frame to avoid lock contention)
-                    if frame_num == 0
-                        && let Ok(mut guard) = OUTPUT_FORMAT.write() {
-                            *guard = Some(format!("{:?}", pixel_format));
-                        }
+                let caps = sample.caps().ok_or_else(|| {
+                    if frame_num.is_multiple_of(30) {
+                        error!(frame = frame_num, "No caps in sample");
+                    }
+                    gstreamer::FlowError::Error
+                })?;
 
-                    // Get owned buffer (increments refcount, shares underlying memory)
-                    // then convert to mapped buffer (zero-copy - keeps buffer mapped until dropped)
-                    let owned_buffer = buffer.copy();
-                    let mapped = owned_buffer.into_mapped_buffer_readable().map_err(|_| {
-                        if frame_num.is_multiple_of(30) {
-                            error!(frame = frame_num, "Failed to map buffer for zero-copy");
+                let video_info = VideoInfo::from_caps(caps).map_err(|e| {
+                    if frame_num.is_multiple_of(30) {
+                        error!(frame = frame_num, error = ?e, "Failed to get video info");
+                    }
+                    gstreamer::FlowError::Error
+                })?;
+
+                // Detect pixel format from GStreamer caps
+                let gst_format = video_info.format();
+                let pixel_format = match gst_format {
+                    // Semi-planar 4:2:0 formats
+                    gstreamer_video::VideoFormat::Nv12 => PixelFormat::NV12,
+                    gstreamer_video::VideoFormat::Nv21 => PixelFormat::NV21,
+                    // Planar 4:2:0 formats
+                    gstreamer_video::VideoFormat::I420 | gstreamer_video::VideoFormat::Yv12 => {
+                        PixelFormat::I420
+                    }
+                    // Packed 4:2:2 formats
+                    gstreamer_video::VideoFormat::Yuy2 => PixelFormat::YUYV,
+                    gstreamer_video::VideoFormat::Uyvy => PixelFormat::UYVY,
+                    gstreamer_video::VideoFormat::Yvyu => PixelFormat::YVYU,
+                    gstreamer_video::VideoFormat::Vyuy => PixelFormat::VYUY,
+                    // Grayscale
+                    gstreamer_video::VideoFormat::Gray8 => PixelFormat::Gray8,
+                    gstreamer_video::VideoFormat::Gray16Le => PixelFormat::Gray16,
+                    // RGBA variants
+                    gstreamer_video::VideoFormat::Rgba
+                    | gstreamer_video::VideoFormat::Rgbx
+                    | gstreamer_video::VideoFormat::Bgra
+                    | gstreamer_video::VideoFormat::Bgrx
+                    | gstreamer_video::VideoFormat::Argb
+                    | gstreamer_video::VideoFormat::Abgr
+                    | gstreamer_video::VideoFormat::Xrgb
+                    | gstreamer_video::VideoFormat::Xbgr => PixelFormat::RGBA,
+                    // RGB24 variants (should have been converted to RGBA by pipeline)
+                    gstreamer_video::VideoFormat::Rgb | gstreamer_video::VideoFormat::Bgr => {
+                        PixelFormat::RGB24
+                    }
+                    _ => {
+                        // Unknown format - log warning and assume NV12 (fallback should have converted to it)
+                        if frame_num.is_multiple_of(60) {
+                            warn!(frame = frame_num, format = ?gst_format, "Unknown video format, assuming NV12 (fallback conversion)");
                         }
-                        gstreamer::FlowError::Error
-                    })?;
+                        PixelFormat::NV12
+                    }
+                };
 
-                    let decode_time = frame_start.elapsed();
-                    DECODE_TIME_US.store(decode_time.as_micros() as u64, Ordering::Relaxed);
+                // Store output format for insights (only on first frame to avoid lock contention)
+                if frame_num == 0
+                    && let Ok(mut guard) = OUTPUT_FORMAT.write()
+                {
+                    *guard = Some(format!("{:?}", pixel_format));
+                }
 
-                    // Extract stride and offset information based on format
-                    let width = video_info.width();
-                    let height = video_info.height();
-                    let strides = video_info.stride();
-                    let offsets = video_info.offset();
+                // Get owned buffer (increments refcount, shares underlying memory)
+                // then convert to mapped buffer (zero-copy - keeps buffer mapped until dropped)
+                let owned_buffer = buffer.copy();
+                let mapped = owned_buffer.into_mapped_buffer_readable().map_err(|_| {
+                    if frame_num.is_multiple_of(30) {
+                        error!(frame = frame_num, "Failed to map buffer for zero-copy");
+                    }
+                    gstreamer::FlowError::Error
+                })?;
+
+                let decode_time = frame_start.elapsed();
+                DECODE_TIME_US.store(decode_time.as_micros() as u64, Ordering::Relaxed);
+
+                // Extract stride and offset information based on format
+                let width = video_info.width();
+                let height = video_info.height();
+                let strides = video_info.stride();
+                let offsets = video_info.offset();
+
+                // Caps renegotiated mid-stream: nothing downstream is tied to
+                // the old size, the frame simply carries the new one
+                if let Some((old_width, old_height)) = note_sample_size(width, height) {
+                    RESOLUTION_CHANGES.fetch_add(1, Ordering::Relaxed);
+                    info!(
+                        from = %format!("{}x{}", old_width, old_height),
+                        to = %format!("{}x{}", width, height),
+                        "Source changed resolution mid-stream"
+                    );
+                }
 
-                    // Log format info every 60 frames for debugging
-                    if frame_num.is_multiple_of(60) {
-                        debug!(
-                            frame = frame_num,
-                            width,
-                            height,
-                            format = ?pixel_format,
-                            gst_format = ?gst_format,
-                            strides = ?strides,
-                            offsets = ?offsets,
-                            n_planes = video_info.n_planes(),
-                            "Frame format information"
-                        );
+                // Log format info every 60 frames for debugging
+                if frame_num.is_multiple_of(60) {
+                    debug!(
+                        frame = frame_num,
+                        width,
+                        height,
+                        format = ?pixel_format,
+                        gst_format = ?gst_format,
+                        strides = ?strides,
+                        offsets = ?offsets,
+                        n_planes = video_info.n_planes(),
+                        "Frame format information"
+                    );
+                }
+
+                // Measure frame wrap time (zero-copy: just wraps mapped buffer, no data copy)
+                let copy_start = Instant::now();
+
+                // Extract plane offsets (zero-copy: no data copying, just store offsets)
+                let (frame_data, yuv_planes, stride) = match pixel_format {
+                    PixelFormat::NV12 | PixelFormat::NV21 => {
+                        // NV12/NV21: Y plane + UV/VU interleaved plane (zero-copy with offsets)
+                        let y_stride = strides[0] as u32;
+                        let uv_stride = strides[1] as u32;
+                        let y_offset = offsets[0] as usize;
+                        let uv_offset = offsets[1] as usize;
+                        let y_size = (y_stride as usize) * (height as usize);
+                        let uv_size = (uv_stride as usize) * (height.div_ceil(2) as usize);
+
+                        let yuv = YuvPlanes {
+                            y_offset,
+                            y_size,
+                            uv_offset,
+                            uv_size,
+                            uv_stride,
+                            v_offset: 0,
+                            v_size: 0,
+                            v_stride: 0,
+                        };
+
+                        (FrameData::from_mapped_buffer(mapped), Some(yuv), y_stride)
                     }
+                    PixelFormat::I420 => {
+                        // I420: Y plane + U plane + V plane (zero-copy with offsets)
+                        let y_stride = strides[0] as u32;
+                        let u_stride = strides[1] as u32;
+                        let v_stride = strides[2] as u32;
+                        let y_offset = offsets[0] as usize;
+                        let u_offset = offsets[1] as usize;
+                        let v_offset = offsets[2] as usize;
+                        let y_size = (y_stride as usize) * (height as usize);
+                        let uv_height = height.div_ceil(2) as usize;
+                        let u_size = (u_stride as usize) * uv_height;
+                        let v_size = (v_stride as usize) * uv_height;
+
+                        let yuv = YuvPlanes {
+                            y_offset,
+                            y_size,
+                            uv_offset: u_offset,
+                            uv_size: u_size,
+                            uv_stride: u_stride,
+                            v_offset,
+                            v_size,
+                            v_stride,
+                        };
+
+                        (FrameData::from_mapped_buffer(mapped), Some(yuv), y_stride)
+                    }
+                    PixelFormat::YUYV
+                    | PixelFormat::UYVY
+                    | PixelFormat::YVYU
+                    | PixelFormat::VYUY => {
+                        // Packed 4:2:2 formats: single plane with 2 bytes per pixel
+                        let stride = strides[0] as u32;
+                        (FrameData::from_mapped_buffer(mapped), None, stride)
+                    }
+                    PixelFormat::Gray8 | PixelFormat::Gray16 => {
+                        // Grayscale: single channel, single plane
+                        let stride = strides[0] as u32;
+                        (FrameData::from_mapped_buffer(mapped), None, stride)
+                    }
+                    PixelFormat::RGB24 => {
+                        // RGB24: 3 bytes per pixel, single plane
+                        let stride = strides[0] as u32;
+                        (FrameData::from_mapped_buffer(mapped), None, stride)
+                    }
+                    PixelFormat::RGBA => {
+                        // RGBA: Single plane, direct passthrough
+                        let stride = strides[0] as u32;
+                        (FrameData::from_mapped_buffer(mapped), None, stride)
+                    }
+                };
+
+                let frame_data = if FORCE_COPY.load(Ordering::Relaxed) {
+                    FrameData::from_bytes(std::sync::Arc::from(frame_data.as_ref()))
+                } else {
+                    frame_data
+                };
+
+                let copy_time = copy_start.elapsed();
+
+                let frame = CameraFrame {
+                    width,
+                    height,
+                    data: frame_data,
+                    format: pixel_format,
+                    stride,
+                    yuv_planes,
+                    captured_at: frame_start,
+                    stamp: Some(FrameStamp {
+                        sequence: session_frame,
+                        pts: buffer.pts().map(|pts| Duration::from_nanos(pts.nseconds())),
+                    }),
+                };
+
+                if super::checksum::enabled() {
+                    super::checksum::record(session_frame, buffer.pts(), &frame);
+                }
+                if crate::engine::has_frame_callback() {
+                    crate::engine::deliver(session_frame, buffer, &frame);
+                }
 
-                    // Measure frame wrap time (zero-copy: just wraps mapped buffer, no data copy)
-                    let copy_start = Instant::now();
-
-                    // Extract plane offsets (zero-copy: no data copying, just store offsets)
-                    let (frame_data, yuv_planes, stride) = match pixel_format {
-                        PixelFormat::NV12 | PixelFormat::NV21 => {
-                            // NV12/NV21: Y plane + UV/VU interleaved plane (zero-copy with offsets)
-                            let y_stride = strides[0] as u32;
-                            let uv_stride = strides[1] as u32;
-                            let y_offset = offsets[0] as usize;
-                            let uv_offset = offsets[1] as usize;
-                            let y_size = (y_stride as usize) * (height as usize);
-                            let uv_size = (uv_stride as usize) * (height as usize / 2);
-
-                            let yuv = YuvPlanes {
-                                y_offset,
-                                y_size,
-                                uv_offset,
-                                uv_size,
-                                uv_stride,
-                                v_offset: 0,
-                                v_size: 0,
-                                v_stride: 0,
-                            };
-
-                            (FrameData::from_mapped_buffer(mapped), Some(yuv), y_stride)
-                        }
-                        PixelFormat::I420 => {
-                            // I420: Y plane + U plane + V plane (zero-copy with offsets)
-                            let y_stride = strides[0] as u32;
-                            let u_stride = strides[1] as u32;
-                            let v_stride = strides[2] as u32;
-                            let y_offset = offsets[0] as usize;
-                            let u_offset = offsets[1] as usize;
-                            let v_offset = offsets[2] as usize;
-                            let y_size = (y_stride as usize) * (height as usize);
-                            let uv_height = height as usize / 2;
-                            let u_size = (u_stride as usize) * uv_height;
-                            let v_size = (v_stride as usize) * uv_height;
-
-                            let yuv = YuvPlanes {
-                                y_offset,
-                                y_size,
-                                uv_offset: u_offset,
-                                uv_size: u_size,
-                                uv_stride: u_stride,
-                                v_offset,
-                                v_size,
-                                v_stride,
-                            };
-
-                            (FrameData::from_mapped_buffer(mapped), Some(yuv), y_stride)
+                // Capture size before send (frame is moved)
+                let size_bytes = frame.data.len();
+
+                // Store metrics for insights
+                LAST_FRAME_SIZE.store(size_bytes as u64, Ordering::Relaxed);
+                COPY_TIME_US.store(copy_time.as_micros() as u64, Ordering::Relaxed);
+
+                // Queue frame for the app (non-blocking; the queue's drop policy
+                // decides what to discard when the consumer falls behind)
+                let send_start = Instant::now();
+                match frame_sender.try_send(frame) {
+                    Ok(policy_drops) => {
+                        if policy_drops > 0 && frame_num.is_multiple_of(30) {
+                            debug!(
+                                frame = frame_num,
+                                policy = ?frame_sender.policy(),
+                                total_dropped = frame_sender.dropped(),
+                                "Consumer behind, frame dropped by policy"
+                            );
                         }
-                        PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
-                            // Packed 4:2:2 formats: single plane with 2 bytes per pixel
-                            let stride = strides[0] as u32;
-                            (FrameData::from_mapped_buffer(mapped), None, stride)
-                        }
-                        PixelFormat::Gray8 => {
-                            // Grayscale: single channel, single plane
-                            let stride = strides[0] as u32;
-                            (FrameData::from_mapped_buffer(mapped), None, stride)
-                        }
-                        PixelFormat::RGB24 => {
-                            // RGB24: 3 bytes per pixel, single plane
-                            let stride = strides[0] as u32;
-                            (FrameData::from_mapped_buffer(mapped), None, stride)
-                        }
-                        PixelFormat::RGBA => {
-                            // RGBA: Single plane, direct passthrough
-                            let stride = strides[0] as u32;
-                            (FrameData::from_mapped_buffer(mapped), None, stride)
-                        }
-                    };
 
-                    let copy_time = copy_start.elapsed();
-
-                    let frame = CameraFrame {
-                        width,
-                        height,
-                        data: frame_data,
-                        format: pixel_format,
-                        stride,
-                        yuv_planes,
-                        captured_at: frame_start,
-                    };
-
-                    // Capture size before send (frame is moved)
-                    let size_bytes = frame.data.len();
-
-                    // Store metrics for insights
-                    LAST_FRAME_SIZE.store(size_bytes as u64, Ordering::Relaxed);
-                    COPY_TIME_US.store(copy_time.as_micros() as u64, Ordering::Relaxed);
-
-                    // Send frame to the app (non-blocking using try_send)
-                    let send_start = Instant::now();
-                    let mut sender = frame_sender.clone();
-                    match sender.try_send(frame) {
-                        Ok(_) => {
-                            let send_time = send_start.elapsed();
-                            SEND_TIME_US.store(send_time.as_micros() as u64, Ordering::Relaxed);
-
-                            // Performance stats every N frames
-                            if frame_num.is_multiple_of(timing::FRAME_LOG_INTERVAL) {
-                                let total_time = frame_start.elapsed();
-                                debug!(
-                                    frame = frame_num,
-                                    decode_ms = format!("{:.2}", decode_time.as_micros() as f64 / 1000.0),
-                                    copy_ms = format!("{:.2}", copy_time.as_micros() as f64 / 1000.0),
-                                    send_ms = format!("{:.2}", send_time.as_micros() as f64 / 1000.0),
-                                    total_ms = format!("{:.2}", total_time.as_micros() as f64 / 1000.0),
-                                    width = video_info.width(),
-                                    height = video_info.height(),
-                                    size_mb = format!("{:.1}", size_bytes as f64 / 1_000_000.0),
-                                    "Frame capture (zero-copy)"
-                                );
-                            }
+                        let send_time = send_start.elapsed();
+                        SEND_TIME_US.store(send_time.as_micros() as u64, Ordering::Relaxed);
+
+                        // Performance stats every N frames
+                        if frame_num.is_multiple_of(timing::FRAME_LOG_INTERVAL) {
+                            let total_time = frame_start.elapsed();
+                            debug!(
+                                frame = frame_num,
+                                decode_ms =
+                                    format!("{:.2}", decode_time.as_micros() as f64 / 1000.0),
+                                copy_ms = format!("{:.2}", copy_time.as_micros() as f64 / 1000.0),
+                                send_ms = format!("{:.2}", send_time.as_micros() as f64 / 1000.0),
+                                total_ms = format!("{:.2}", total_time.as_micros() as f64 / 1000.0),
+                                width = video_info.width(),
+                                height = video_info.height(),
+                                size_mb = format!("{:.1}", size_bytes as f64 / 1_000_000.0),
+                                "Frame capture (zero-copy)"
+                            );
                         }
-                        Err(e) => {
-                            DROPPED_FRAMES.fetch_add(1, Ordering::Relaxed);
-                            if frame_num.is_multiple_of(30) {
-                                debug!(frame = frame_num, error = ?e, "Frame dropped (channel full)");
-                            }
+                    }
+                    Err(_) => {
+                        DROPPED_FRAMES.fetch_add(1, Ordering::Relaxed);
+                        if frame_num.is_multiple_of(30) {
+                            debug!(frame = frame_num, "Frame dropped (receiver closed)");
                         }
                     }
+                }
+
+                // Signalled after the send so waiters find the frame already queued
+                first_frame_tx.send_if_modified(|received| {
+                    if *received {
+                        return false;
+                    }
+                    info!(frame = frame_num, "First frame received");
+                    *received = true;
+                    true
+                });
 
-                    Ok(gstreamer::FlowSuccess::Ok)
+                Ok(gstreamer::FlowSuccess::Ok)
+            },
+        );
+        let on_preroll = std::sync::Arc::clone(&on_sample);
+        appsink.set_callbacks(
+            gstreamer_app::AppSinkCallbacks::builder()
+                .new_sample(move |appsink| on_sample(appsink.pull_sample()))
+                .new_preroll(move |appsink| {
+                    // Outside of freezing, a prerolled buffer is rendered as a sample too
+                    if is_frozen() {
+                        on_preroll(appsink.pull_preroll())
+                    } else {
+                        Ok(gstreamer::FlowSuccess::Ok)
+                    }
                 })
                 .build(),
         );
@@ -485,6 +1042,10 @@ impl PipeWirePipeline {
         if state != gstreamer::State::Playing {
             warn!("Pipeline is not in PLAYING state");
         }
+        // Rebuilt while frozen: hold the new pipeline's frames too
+        if is_frozen() {
+            set_appsink_paused(appsink.upcast_ref(), true);
+        }
 
         info!("PipeWire camera initialization complete");
 
@@ -493,9 +1054,229 @@ impl PipeWirePipeline {
             _appsink: appsink,
             decoder: decoder_name,
             recording: false,
+            first_frame,
         })
     }
 
+    /// Whether a valid frame has reached the appsink yet
+    pub fn has_first_frame(&self) -> bool {
+        *self.first_frame.borrow()
+    }
+
+    /// Error to give up with when no frame arrived within `timeout_secs` of `opened_at`
+    ///
+    /// A camera held by another app (or a portal that never answers) may
+    /// start without ever delivering a frame. The stall watchdog only counts
+    /// from the first frame, so this is what ends such a start.
+    pub fn first_frame_timeout(
+        &self,
+        opened_at: Instant,
+        timeout_secs: u32,
+    ) -> Option<crate::media::decoders::PipelineError> {
+        (!self.has_first_frame() && opened_at.elapsed() >= Duration::from_secs(timeout_secs.into()))
+            .then_some(crate::media::decoders::PipelineError::FirstFrameTimeout {
+                secs: timeout_secs,
+            })
+    }
+
+    /// Wait until the first valid frame reaches the appsink
+    ///
+    /// Resolves immediately if a frame already arrived. Fails with
+    /// [`BackendError::Timeout`] if none arrives within `timeout`, or with
+    /// [`BackendError::Crashed`] if the pipeline stops delivering first.
+    pub async fn wait_for_first_frame(&self, timeout: Duration) -> BackendResult<()> {
+        let mut first_frame = self.first_frame.clone();
+        match tokio::time::timeout(timeout, first_frame.wait_for(|received| *received)).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(_)) => Err(BackendError::Crashed(
+                "Pipeline stopped before delivering a frame".to_string(),
+            )),
+            Err(_) => Err(BackendError::Timeout(format!(
+                "no frame within {} ms",
+                timeout.as_millis()
+            ))),
+        }
+    }
+
+    /// Install a probe that records `interlace-mode`, colorimetry and the negotiated mode
+    /// from the source caps, again on every renegotiation
+    fn watch_source_caps(pipeline: &gstreamer::Pipeline) {
+        if let Ok(mut guard) = INTERLACE.write() {
+            *guard = None;
+        }
+        if let Ok(mut guard) = COLORIMETRY.write() {
+            *guard = None;
+        }
+        if let Ok(mut guard) = SOURCE_MODE.write() {
+            *guard = None;
+        }
+
+        let src_pad = pipeline
+            .iterate_sources()
+            .into_iter()
+            .flatten()
+            .find(|e| {
+                e.factory()
+                    .is_some_and(|f| matches!(f.name().as_str(), "pipewiresrc" | "v4l2src"))
+            })
+            .and_then(|e| e.static_pad("src"));
+        let Some(src_pad) = src_pad else {
+            return;
+        };
+
+        src_pad.add_probe(gstreamer::PadProbeType::EVENT_DOWNSTREAM, |_, info| {
+            if let Some(gstreamer::EventView::Caps(caps_event)) = info.event().map(|e| e.view()) {
+                let interlace = interlace_from_caps(caps_event.caps());
+                info!(interlace = %interlace.display(), mode = %interlace.mode, "Source interlacing");
+                crate::media::decoders::note_source_interlacing(interlace.is_interlaced());
+                if let Ok(mut guard) = INTERLACE.write() {
+                    *guard = Some(interlace);
+                }
+                Self::record_source_mode(caps_event.caps());
+                Self::record_colorimetry(caps_event.caps());
+            }
+            gstreamer::PadProbeReturn::Ok
+        });
+
+        // The pipeline is already playing, so caps may have been negotiated before the probe
+        if let Some(caps) = src_pad.current_caps() {
+            let interlace = interlace_from_caps(&caps);
+            crate::media::decoders::note_source_interlacing(interlace.is_interlaced());
+            if let Ok(mut guard) = INTERLACE.write() {
+                *guard = Some(interlace);
+            }
+            Self::record_source_mode(&caps);
+            Self::record_colorimetry(&caps);
+        }
+    }
+
+    fn record_colorimetry(caps: &gstreamer::CapsRef) {
+        let colorimetry = caps
+            .structure(0)
+            .and_then(|s| s.get::<&str>("colorimetry").ok())
+            .map(str::to_string);
+        debug!(?colorimetry, "Source colorimetry");
+        if let Ok(mut guard) = COLORIMETRY.write() {
+            *guard = colorimetry;
+        }
+    }
+
+    fn record_source_mode(caps: &gstreamer::CapsRef) {
+        let Some(structure) = caps.structure(0) else {
+            return;
+        };
+        let (Ok(width), Ok(height)) = (
+            structure.get::<i32>("width"),
+            structure.get::<i32>("height"),
+        ) else {
+            return;
+        };
+        let fps = structure
+            .get::<gstreamer::Fraction>("framerate")
+            .ok()
+            .filter(|fraction| fraction.denom() != 0)
+            .map(|fraction| fraction.numer() as f32 / fraction.denom() as f32);
+        let mode = VideoMode {
+            width: width as u32,
+            height: height as u32,
+            fps,
+        };
+        debug!(?mode, "Negotiated source mode");
+        if let Ok(mut guard) = SOURCE_MODE.write() {
+            *guard = Some(mode);
+        }
+    }
+
+    /// Track cadence and throttle buffers at the appsink sink pad
+    ///
+    /// Frozen, buffers pass untouched: the paused appsink prerolls them one
+    /// step at a time.
+    fn install_step_gate(appsink: &AppSink) {
+        LAST_PTS_NS.store(u64::MAX, Ordering::Relaxed);
+        if let Ok(mut cadence) = CADENCE.lock() {
+            *cadence = super::cadence::CadenceTracker::new();
+        }
+
+        let Some(sink_pad) = appsink.static_pad("sink") else {
+            warn!("Appsink has no sink pad, frame stepping unavailable");
+            return;
+        };
+        let decimator = Mutex::new(super::decimation::FrameDecimator::default());
+        sink_pad.add_probe(gstreamer::PadProbeType::BUFFER, move |_, info| {
+            if FROZEN.load(Ordering::Relaxed) {
+                // The time spent frozen is not a frame interval
+                if let Ok(mut cadence) = CADENCE.lock() {
+                    cadence.interrupt();
+                }
+                if let Some(pts) = info.buffer().and_then(|buffer| buffer.pts()) {
+                    LAST_PTS_NS.store(pts.nseconds(), Ordering::Relaxed);
+                }
+                return gstreamer::PadProbeReturn::Ok;
+            }
+            if let Some(pts) = info.buffer().and_then(|buffer| buffer.pts()) {
+                // Measured before the throttle, so it describes the source
+                if let Ok(mut cadence) = CADENCE.lock() {
+                    cadence.record(pts.nseconds());
+                }
+                let interval = THROTTLE_INTERVAL_NS.load(Ordering::Relaxed);
+                let keep = decimator.lock().map_or(true, |mut decimator| {
+                    decimator.keep(pts.nseconds(), interval)
+                });
+                if !keep {
+                    return gstreamer::PadProbeReturn::Drop;
+                }
+                LAST_PTS_NS.store(pts.nseconds(), Ordering::Relaxed);
+            }
+            gstreamer::PadProbeReturn::Ok
+        });
+    }
+
+    /// Record bus errors and warnings for the diagnostics bundle
+    ///
+    /// Uses a sync handler so messages are captured on the posting thread
+    /// without a GLib main loop. Messages are passed on unchanged. Errors and
+    /// warnings posted by a decoder also count as decode errors for it;
+    /// `GstVideoDecoder` posts a warning for each frame it fails to decode
+    /// until its `max-errors` is reached.
+    fn watch_bus_errors(pipeline: &gstreamer::Pipeline) {
+        let Some(bus) = pipeline.bus() else {
+            return;
+        };
+        bus.set_sync_handler(|_, msg| {
+            let entry = match msg.view() {
+                gstreamer::MessageView::Error(err) => Some(format!(
+                    "ERROR [{}] {}",
+                    err.src().map(|s| s.name().to_string()).unwrap_or_default(),
+                    err.error()
+                )),
+                gstreamer::MessageView::Warning(warning) => Some(format!(
+                    "WARNING [{}] {}",
+                    warning
+                        .src()
+                        .map(|s| s.name().to_string())
+                        .unwrap_or_default(),
+                    warning.error()
+                )),
+                _ => None,
+            };
+            if entry.is_some()
+                && let Some(decoder) = decoder_factory(msg.src())
+            {
+                record_decoder_error(Some(&decoder));
+            }
+            if let Some(entry) = entry
+                && let Ok(mut guard) = BUS_ERRORS.lock()
+            {
+                if guard.len() == BUS_ERROR_HISTORY {
+                    guard.pop_front();
+                }
+                let timestamp = chrono::Local::now().format("%H:%M:%S");
+                guard.push_back(format!("{} {}", timestamp, entry));
+            }
+            gstreamer::BusSyncReply::Pass
+        });
+    }
+
     /// Start the pipeline (already started in new())
     pub fn start(&self) -> BackendResult<()> {
         info!("PipeWire pipeline already started");
@@ -592,3 +1373,85 @@ impl Drop for PipeWirePipeline {
         info!("PipeWire pipeline stopped");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_stops_active_pipeline() {
+        if gstreamer::init().is_err() || gstreamer::ElementFactory::find("videotestsrc").is_none() {
+            println!("Skipping test (no videotestsrc)");
+            return;
+        }
+        let pipeline = gstreamer::parse::launch("videotestsrc is-live=true ! fakesink")
+            .unwrap()
+            .downcast::<gstreamer::Pipeline>()
+            .unwrap();
+        pipeline.set_state(gstreamer::State::Playing).unwrap();
+
+        // A node that can't be re-opened is not reported as busy
+        assert_eq!(
+            release_pipeline(Some(&pipeline), Some("/nonexistent/video0")),
+            Ok(())
+        );
+        assert_eq!(pipeline.current_state(), gstreamer::State::Null);
+        assert_eq!(pipeline.pending_state(), gstreamer::State::VoidPending);
+
+        assert_eq!(release_pipeline(None, None), Ok(()));
+    }
+
+    #[test]
+    fn test_first_frame_timeout_without_frames() {
+        use crate::media::decoders::PipelineError;
+
+        if gstreamer::init().is_err() || gstreamer::ElementFactory::find("appsrc").is_none() {
+            println!("Skipping test (no appsrc)");
+            return;
+        }
+        // A live source that never pushes a buffer, like a camera held elsewhere
+        let pipeline = gstreamer::parse::launch("appsrc is-live=true ! appsink name=sink")
+            .unwrap()
+            .downcast::<gstreamer::Pipeline>()
+            .unwrap();
+        let appsink = pipeline
+            .by_name("sink")
+            .unwrap()
+            .downcast::<AppSink>()
+            .unwrap();
+        pipeline.set_state(gstreamer::State::Playing).unwrap();
+        let (first_frame_tx, first_frame) = tokio::sync::watch::channel(false);
+        let camera = PipeWirePipeline {
+            pipeline,
+            _appsink: appsink,
+            decoder: String::new(),
+            recording: false,
+            first_frame,
+        };
+
+        // Nothing for the stall watchdog to count while no frame arrived
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!camera.has_first_frame());
+        assert_eq!(camera.first_frame_timeout(Instant::now(), 10), None);
+
+        let opened_at = Instant::now().checked_sub(Duration::from_secs(10)).unwrap();
+        assert_eq!(
+            camera.first_frame_timeout(opened_at, 10),
+            Some(PipelineError::FirstFrameTimeout { secs: 10 })
+        );
+
+        // A late first frame means the camera did start
+        first_frame_tx.send(true).unwrap();
+        assert_eq!(camera.first_frame_timeout(opened_at, 10), None);
+    }
+
+    #[test]
+    fn test_appsink_max_buffers_respects_quirk_minimum() {
+        assert_eq!(appsink_max_buffers(None, 3, 0), 3);
+        assert_eq!(appsink_max_buffers(Some(1), 3, 0), 1);
+        assert_eq!(appsink_max_buffers(Some(6), 2, 0), 6);
+        // A device that needs three buffers never gets fewer
+        assert_eq!(appsink_max_buffers(Some(1), 2, 3), 3);
+        assert_eq!(appsink_max_buffers(None, 2, 3), 3);
+    }
+}
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Dump of the encoded frames a camera delivers
+//!
+//! Decoder bugs are easiest to reproduce with the exact bytes the camera
+//! sent. A pad probe on the decoder's sink pad copies every buffer into a
+//! dump for the requested duration, without rebuilding the pipeline. The
+//! layout depends on the codec:
+//!
+//! - MJPEG: a directory with one file per frame, `frame-00001.jpg` and so
+//!   on, each a JPEG exactly as the camera sent it. UVC cameras often leave
+//!   out the Huffman tables; decoders fill in the standard ones, and so do
+//!   most image viewers.
+//! - H.264/H.265: one Annex B elementary stream (`.h264`/`.h265`) that
+//!   starts at the first keyframe. The parser in front of the decoder
+//!   repeats SPS/PPS with every keyframe, so the file plays as-is, e.g.
+//!   with `gst-launch-1.0 filesrc location=dump.h264 ! h264parse ! avdec_h264 ! autovideosink`.
+//!
+//! Raw formats can't be dumped here; their frames are what the appsink
+//! already delivers.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use gstreamer::prelude::*;
+use tracing::{info, warn};
+
+/// How long the insights drawer dumps for
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(10);
+
+/// How the dumped frames are laid out on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpLayout {
+    /// A directory with one `.jpg` per frame
+    JpegFiles,
+    /// A single Annex B elementary stream
+    AnnexB,
+}
+
+/// A finished dump
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawDump {
+    /// Directory (MJPEG) or file (H.264/H.265) the frames were written to
+    pub path: PathBuf,
+    pub layout: DumpLayout,
+    /// Frames written
+    pub frames: u64,
+    /// Bytes written
+    pub bytes: u64,
+}
+
+/// Probe-side state of a running dump
+struct DumpWriter {
+    dump: RawDump,
+    /// The Annex B stream, opened up front
+    stream: Option<BufWriter<File>>,
+    /// A keyframe was seen (frames before it can't be decoded)
+    keyframe_seen: bool,
+    /// First write error; nothing is written after it
+    error: Option<String>,
+}
+
+impl DumpWriter {
+    fn write(&mut self, data: &[u8], keyframe: bool) -> std::io::Result<()> {
+        match self.dump.layout {
+            DumpLayout::JpegFiles => {
+                let name = format!("frame-{:05}.jpg", self.dump.frames + 1);
+                std::fs::write(self.dump.path.join(name), data)?;
+            }
+            DumpLayout::AnnexB => {
+                self.keyframe_seen |= keyframe;
+                if !self.keyframe_seen {
+                    return Ok(());
+                }
+                if let Some(stream) = self.stream.as_mut() {
+                    stream.write_all(data)?;
+                }
+            }
+        }
+        self.dump.frames += 1;
+        self.dump.bytes += data.len() as u64;
+        Ok(())
+    }
+}
+
+/// Layout and file extension for the caps on the decoder's input
+fn layout_for_caps(media_type: &str) -> Option<(DumpLayout, Option<&'static str>)> {
+    match media_type {
+        "image/jpeg" => Some((DumpLayout::JpegFiles, None)),
+        "video/x-h264" => Some((DumpLayout::AnnexB, Some("h264"))),
+        "video/x-h265" => Some((DumpLayout::AnnexB, Some("h265"))),
+        _ => None,
+    }
+}
+
+/// Sink pad of the running pipeline's decoder
+fn decoder_sink_pad(pipeline: &gstreamer::Pipeline) -> Option<gstreamer::Pad> {
+    pipeline
+        .iterate_elements()
+        .into_iter()
+        .flatten()
+        .find(|element| {
+            element
+                .factory()
+                .is_some_and(|factory| factory.klass().contains("Decoder"))
+        })
+        .and_then(|decoder| decoder.static_pad("sink"))
+}
+
+/// Dump the encoded frames of the running pipeline for `duration`
+///
+/// `path` names a directory for MJPEG and a file for H.264/H.265, whose
+/// extension is set to match. Blocks for `duration`, so call it off the UI
+/// thread.
+pub fn dump_raw_stream(path: &Path, duration: Duration) -> Result<RawDump, String> {
+    let pipeline = super::pipeline::get_active_pipeline().ok_or("No camera pipeline is running")?;
+    let pad = decoder_sink_pad(&pipeline)
+        .ok_or("The source is not compressed, there is nothing to dump before decoding")?;
+    let media_type = pad
+        .current_caps()
+        .and_then(|caps| caps.structure(0).map(|s| s.name().to_string()))
+        .ok_or("The decoder has no caps yet")?;
+    let (layout, extension) = layout_for_caps(&media_type)
+        .ok_or_else(|| format!("Dumping {} is not supported", media_type))?;
+
+    let (path, stream) = match extension {
+        None => {
+            std::fs::create_dir_all(path)
+                .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+            (path.to_path_buf(), None)
+        }
+        Some(extension) => {
+            let path = path.with_extension(extension);
+            let file = File::create(&path)
+                .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+            (path, Some(BufWriter::new(file)))
+        }
+    };
+    info!(path = %path.display(), ?layout, ?duration, "Dumping the encoded stream");
+
+    let writer = Arc::new(Mutex::new(DumpWriter {
+        dump: RawDump {
+            path,
+            layout,
+            frames: 0,
+            bytes: 0,
+        },
+        stream,
+        keyframe_seen: false,
+        error: None,
+    }));
+    let probe_writer = Arc::clone(&writer);
+    let probe = pad.add_probe(gstreamer::PadProbeType::BUFFER, move |_, info| {
+        let Some(buffer) = info.buffer() else {
+            return gstreamer::PadProbeReturn::Ok;
+        };
+        let keyframe = !buffer.flags().contains(gstreamer::BufferFlags::DELTA_UNIT);
+        if let (Ok(mut writer), Ok(map)) = (probe_writer.lock(), buffer.map_readable())
+            && writer.error.is_none()
+            && let Err(err) = writer.write(map.as_slice(), keyframe)
+        {
+            writer.error = Some(err.to_string());
+        }
+        gstreamer::PadProbeReturn::Ok
+    });
+
+    std::thread::sleep(duration);
+    if let Some(probe) = probe {
+        pad.remove_probe(probe);
+    }
+
+    let mut writer = writer
+        .lock()
+        .map_err(|_| "The dump was interrupted".to_string())?;
+    if let Some(stream) = writer.stream.as_mut()
+        && let Err(err) = stream.flush()
+    {
+        writer.error.get_or_insert(err.to_string());
+    }
+    if let Some(err) = writer.error.take() {
+        warn!(error = %err, "Failed to write the stream dump");
+        return Err(format!("Failed to write the dump: {}", err));
+    }
+    info!(
+        frames = writer.dump.frames,
+        bytes = writer.dump.bytes,
+        "Stream dump finished"
+    );
+    Ok(writer.dump.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annex_b_dump_starts_at_keyframe() {
+        let path = std::env::temp_dir().join(format!("raw-dump-test-{}.h264", std::process::id()));
+        let mut writer = DumpWriter {
+            dump: RawDump {
+                path: path.clone(),
+                layout: DumpLayout::AnnexB,
+                frames: 0,
+                bytes: 0,
+            },
+            stream: Some(BufWriter::new(File::create(&path).unwrap())),
+            keyframe_seen: false,
+            error: None,
+        };
+        writer.write(&[0, 0, 0, 1, 0x41], false).unwrap();
+        writer
+            .write(&[0, 0, 0, 1, 0x67, 0, 0, 0, 1, 0x65], true)
+            .unwrap();
+        writer.write(&[0, 0, 0, 1, 0x41], false).unwrap();
+        writer.stream.take().unwrap().flush().unwrap();
+
+        assert_eq!(writer.dump.frames, 2);
+        assert_eq!(writer.dump.bytes, 15);
+        let written = std::fs::read(&path).unwrap();
+        assert_eq!(&written[4..5], &[0x67]);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(
+            layout_for_caps("image/jpeg"),
+            Some((DumpLayout::JpegFiles, None))
+        );
+        assert_eq!(layout_for_caps("video/x-raw"), None);
+    }
+}
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Single-frame device thumbnails for the camera picker
+//!
+//! Each device is opened with a short-lived pipeline that lets GStreamer decode,
+//! convert and scale a single frame down to a small RGBA image. Devices are
+//! captured one after another so no more than one camera is open at a time.
+
+use super::super::types::*;
+use crate::constants::{thumbnail, timing};
+use crate::media::decoders::determine_pipewire_path;
+use gstreamer::prelude::*;
+use gstreamer_app::AppSink;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Small RGBA frame captured from a device
+#[derive(Debug, Clone)]
+pub struct DeviceThumbnail {
+    /// Thumbnail width in pixels
+    pub width: u32,
+    /// Thumbnail height in pixels
+    pub height: u32,
+    /// Tightly packed RGBA pixels
+    pub rgba: Arc<Vec<u8>>,
+}
+
+/// Capture a thumbnail from a single device
+///
+/// Fails (instead of blocking) when the device can't be started or delivers no
+/// frame within the timeout, which usually means it is in use elsewhere.
+pub fn capture_device_thumbnail(device: &CameraDevice) -> BackendResult<DeviceThumbnail> {
+    let device_path = (!device.path.is_empty()).then_some(device.path.as_str());
+    let pw_path_prop = determine_pipewire_path(device_path);
+
+    // Let decodebin handle whatever the device negotiates; quality doesn't matter here
+    let pipeline_str = format!(
+        "pipewiresrc {}num-buffers=10 ! decodebin ! videoconvert ! \
+         videoscale add-borders=true ! \
+         video/x-raw,format=RGBA,width={},height={},pixel-aspect-ratio=1/1 ! \
+         appsink name=sink sync=false max-buffers=1 drop=true",
+        pw_path_prop,
+        thumbnail::WIDTH,
+        thumbnail::HEIGHT
+    );
+    debug!(pipeline = %pipeline_str, device = %device.name, "Creating thumbnail pipeline");
+
+    let pipeline = gstreamer::parse::launch(&pipeline_str)
+        .map_err(|e| BackendError::InitializationFailed(e.to_string()))?
+        .downcast::<gstreamer::Pipeline>()
+        .map_err(|_| BackendError::Other("Thumbnail pipeline is not a pipeline".to_string()))?;
+
+    let result = pull_thumbnail(&pipeline);
+
+    let _ = pipeline.set_state(gstreamer::State::Null);
+    let _ = pipeline.state(gstreamer::ClockTime::from_seconds(
+        timing::STOP_TIMEOUT_SECS,
+    ));
+
+    result
+}
+
+/// Start the pipeline and pull the first RGBA sample from its appsink
+fn pull_thumbnail(pipeline: &gstreamer::Pipeline) -> BackendResult<DeviceThumbnail> {
+    let appsink = pipeline
+        .by_name("sink")
+        .and_then(|e| e.downcast::<AppSink>().ok())
+        .ok_or_else(|| BackendError::Other("Thumbnail appsink not found".to_string()))?;
+
+    pipeline
+        .set_state(gstreamer::State::Playing)
+        .map_err(|e| BackendError::DeviceNotFound(format!("Device busy or unavailable: {}", e)))?;
+
+    let timeout = Duration::from_millis(timing::THUMBNAIL_TIMEOUT_MS);
+    let sample = appsink
+        .try_pull_sample(gstreamer::ClockTime::from_mseconds(
+            timeout.as_millis() as u64
+        ))
+        .ok_or_else(|| BackendError::Crashed("No thumbnail frame received".to_string()))?;
+
+    let buffer = sample
+        .buffer()
+        .ok_or_else(|| BackendError::Other("Thumbnail sample has no buffer".to_string()))?;
+    let map = buffer
+        .map_readable()
+        .map_err(|e| BackendError::Other(e.to_string()))?;
+
+    // Strip any row padding so the image handle gets tightly packed rows
+    let row_bytes = (thumbnail::WIDTH * 4) as usize;
+    let stride = sample
+        .caps()
+        .and_then(|caps| gstreamer_video::VideoInfo::from_caps(caps).ok())
+        .map(|info| info.stride()[0] as usize)
+        .unwrap_or(row_bytes);
+    let rgba: Vec<u8> = map
+        .chunks(stride)
+        .take(thumbnail::HEIGHT as usize)
+        .flat_map(|row| &row[..row_bytes.min(row.len())])
+        .copied()
+        .collect();
+
+    if rgba.len() != row_bytes * thumbnail::HEIGHT as usize {
+        return Err(BackendError::FormatNotSupported(
+            "Unexpected thumbnail buffer size".to_string(),
+        ));
+    }
+
+    Ok(DeviceThumbnail {
+        width: thumbnail::WIDTH,
+        height: thumbnail::HEIGHT,
+        rgba: Arc::new(rgba),
+    })
+}
+
+/// Capture thumbnails for a list of devices, strictly one device at a time
+///
+/// Devices whose path is in `skip_paths` (e.g., the camera currently streaming)
+/// and devices that fail to deliver a frame map to `None`, so the caller can
+/// show a placeholder for them.
+pub fn capture_device_thumbnails(
+    devices: &[CameraDevice],
+    skip_paths: &[String],
+) -> Vec<(String, Option<DeviceThumbnail>)> {
+    devices
+        .iter()
+        .map(|device| {
+            if skip_paths.contains(&device.path) {
+                return (device.path.clone(), None);
+            }
+            let thumbnail = match capture_device_thumbnail(device) {
+                Ok(thumbnail) => {
+                    info!(device = %device.name, "Captured device thumbnail");
+                    Some(thumbnail)
+                }
+                Err(e) => {
+                    warn!(device = %device.name, error = %e, "Device thumbnail unavailable");
+                    None
+                }
+            };
+            (device.path.clone(), thumbnail)
+        })
+        .collect()
+}
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Per-device workarounds for UVC cameras
+//!
+//! Some cameras need extra handling while the stream starts before they
+//! deliver usable frames. Quirks are keyed by USB vendor/product ID and
+//! applied during pipeline construction; devices without an entry (and
+//! non-USB cameras) are left untouched.
+//!
+//! To add a device, append a [`DeviceQuirk`] to [`QUIRKS`] with a short
+//! name for logs/insights and a note on the symptom it works around.
+
+use super::types::{CameraDevice, UsbId};
+
+/// Workarounds applied to a specific camera model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceQuirk {
+    /// USB vendor/product ID the quirk applies to
+    pub id: UsbId,
+    /// Short name shown in logs and insights
+    pub name: &'static str,
+    /// Frames to drop after the stream starts (the camera sends garbage first)
+    pub discard_frames: u32,
+    /// Minimum appsink queue depth (the camera stalls with shallow queues)
+    pub min_buffers: Option<u32>,
+}
+
+/// Known cameras and their workarounds
+pub const QUIRKS: &[DeviceQuirk] = &[
+    // Logitech HD Pro Webcam C920: the first MJPEG frames after stream-on are
+    // frequently truncated and decode as grey/smeared images
+    DeviceQuirk {
+        id: UsbId::new(0x046d, 0x082d),
+        name: "Logitech C920: discard initial MJPEG frames",
+        discard_frames: 3,
+        min_buffers: None,
+    },
+    // Microsoft LifeCam HD-3000: sends green frames while auto exposure
+    // settles and drops frames when only one buffer is queued
+    DeviceQuirk {
+        id: UsbId::new(0x045e, 0x0810),
+        name: "LifeCam HD-3000: discard warm-up frames, deeper queue",
+        discard_frames: 5,
+        min_buffers: Some(3),
+    },
+];
+
+/// Look up the quirk for a USB vendor/product ID
+pub fn lookup(id: UsbId) -> Option<&'static DeviceQuirk> {
+    QUIRKS.iter().find(|quirk| quirk.id == id)
+}
+
+/// Quirk for a camera, if it is a USB device with a known entry
+pub fn for_device(device: &CameraDevice) -> Option<&'static DeviceQuirk> {
+    device
+        .device_info
+        .as_ref()
+        .and_then(|info| info.usb_id)
+        .and_then(lookup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_known_and_unknown_devices() {
+        let quirk = lookup(UsbId::new(0x046d, 0x082d)).expect("C920 quirk");
+        assert_eq!(quirk.discard_frames, 3);
+        assert!(lookup(UsbId::new(0x1234, 0x5678)).is_none());
+    }
+
+    #[test]
+    fn test_quirk_ids_are_unique() {
+        for (i, quirk) in QUIRKS.iter().enumerate() {
+            assert!(
+                QUIRKS[i + 1..].iter().all(|other| other.id != quirk.id),
+                "duplicate quirk for {}",
+                quirk.id
+            );
+        }
+    }
+}
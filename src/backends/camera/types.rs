@@ -7,7 +7,7 @@
 use gstreamer::buffer::{MappedBuffer, Readable};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 /// Frame data storage - either pre-copied bytes or zero-copy GStreamer buffer
 ///
@@ -106,6 +106,29 @@ pub struct DeviceInfo {
     pub path: String,
     /// Real device path (resolved symlinks)
     pub real_path: String,
+    /// USB vendor/product ID (None for non-USB cameras)
+    pub usb_id: Option<UsbId>,
+}
+
+/// USB vendor and product ID of a camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UsbId {
+    /// Vendor ID (`idVendor`)
+    pub vendor: u16,
+    /// Product ID (`idProduct`)
+    pub product: u16,
+}
+
+impl UsbId {
+    pub const fn new(vendor: u16, product: u16) -> Self {
+        Self { vendor, product }
+    }
+}
+
+impl std::fmt::Display for UsbId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}:{:04x}", self.vendor, self.product)
+    }
 }
 
 /// Sensor rotation in degrees (clockwise)
@@ -116,7 +139,7 @@ pub struct DeviceInfo {
 ///
 /// The rotation value comes from:
 /// - libcamera's `api.libcamera.rotation` property in PipeWire
-/// - Device tree sensor rotation values
+/// - The V4L2 sensor rotation control (device tree / ACPI values)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SensorRotation {
     /// No rotation (sensor is oriented correctly)
@@ -164,6 +187,16 @@ impl SensorRotation {
         }
     }
 
+    /// Combine with a further clockwise rotation
+    pub fn rotated_by(&self, other: SensorRotation) -> Self {
+        match (self.degrees() + other.degrees()) % 360 {
+            90 => SensorRotation::Rotate90,
+            180 => SensorRotation::Rotate180,
+            270 => SensorRotation::Rotate270,
+            _ => SensorRotation::None,
+        }
+    }
+
     /// Check if rotation swaps width and height
     pub fn swaps_dimensions(&self) -> bool {
         matches!(self, SensorRotation::Rotate90 | SensorRotation::Rotate270)
@@ -186,6 +219,123 @@ impl std::fmt::Display for SensorRotation {
     }
 }
 
+/// Which way a camera faces
+///
+/// The facing comes from:
+/// - libcamera's `api.libcamera.location` property in PipeWire
+/// - The V4L2 camera orientation control (device tree / ACPI values)
+/// - For USB cameras, whether they sit on a fixed (built-in) or removable port
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum CameraFacing {
+    /// Facing couldn't be determined
+    #[default]
+    Unknown,
+    /// Facing the user (laptop bezel, phone front camera)
+    Front,
+    /// Facing away from the user
+    Back,
+    /// Separate camera that can point anywhere (external webcam)
+    External,
+}
+
+impl CameraFacing {
+    /// Parse libcamera's `api.libcamera.location` value
+    pub fn from_libcamera_location(location: &str) -> Self {
+        match location.trim() {
+            "front" => CameraFacing::Front,
+            "back" => CameraFacing::Back,
+            "external" => CameraFacing::External,
+            _ => CameraFacing::Unknown,
+        }
+    }
+
+    /// Parse the V4L2 camera orientation control value
+    pub fn from_v4l2_orientation(value: i32) -> Self {
+        use super::v4l2_controls::{
+            V4L2_CAMERA_ORIENTATION_BACK, V4L2_CAMERA_ORIENTATION_EXTERNAL,
+            V4L2_CAMERA_ORIENTATION_FRONT,
+        };
+        match value {
+            V4L2_CAMERA_ORIENTATION_FRONT => CameraFacing::Front,
+            V4L2_CAMERA_ORIENTATION_BACK => CameraFacing::Back,
+            V4L2_CAMERA_ORIENTATION_EXTERNAL => CameraFacing::External,
+            _ => CameraFacing::Unknown,
+        }
+    }
+
+    /// Whether the preview is mirrored when the mirror setting is on
+    ///
+    /// Cameras facing away from the user show the scene as seen, so only
+    /// they stay unmirrored. Unknown and external cameras usually point at
+    /// the user, as webcams always were treated.
+    pub fn mirrors_preview(&self) -> bool {
+        *self != CameraFacing::Back
+    }
+
+    /// Get display name for this facing
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CameraFacing::Unknown => "Unknown",
+            CameraFacing::Front => "Front",
+            CameraFacing::Back => "Rear",
+            CameraFacing::External => "External",
+        }
+    }
+
+    /// Facings the user can pick as an override
+    pub const ALL: [CameraFacing; 3] = [
+        CameraFacing::Front,
+        CameraFacing::Back,
+        CameraFacing::External,
+    ];
+}
+
+/// Camera stack serving a PipeWire camera node
+///
+/// PipeWire can offer the same camera twice: through libcamera and directly
+/// through V4L2. The two differ in formats, frame rate control and latency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum CameraSourceKind {
+    Libcamera,
+    V4l2,
+}
+
+impl CameraSourceKind {
+    pub const ALL: [CameraSourceKind; 2] = [CameraSourceKind::Libcamera, CameraSourceKind::V4l2];
+
+    /// Stack of a node from its `object.path` (e.g. "v4l2:/dev/video0")
+    pub fn from_object_path(object_path: &str) -> Option<Self> {
+        if object_path.starts_with("libcamera:") {
+            Some(CameraSourceKind::Libcamera)
+        } else if object_path.starts_with("v4l2:") {
+            Some(CameraSourceKind::V4l2)
+        } else {
+            None
+        }
+    }
+
+    /// Get display name for this stack
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CameraSourceKind::Libcamera => "libcamera",
+            CameraSourceKind::V4l2 => "V4L2",
+        }
+    }
+
+    /// Stack used for a camera offered through both, unless overridden
+    ///
+    /// USB (UVC) cameras get every format and frame rate directly from V4L2,
+    /// which libcamera's UVC support only passes through. Other cameras are
+    /// raw sensors that need libcamera's image processing.
+    pub fn preferred_for(device_info: Option<&DeviceInfo>) -> Self {
+        if device_info.is_some_and(|info| info.usb_id.is_some()) {
+            CameraSourceKind::V4l2
+        } else {
+            CameraSourceKind::Libcamera
+        }
+    }
+}
+
 /// Represents a camera device
 #[derive(Debug, Clone)]
 pub struct CameraDevice {
@@ -194,6 +344,54 @@ pub struct CameraDevice {
     pub metadata_path: Option<String>,   // Path to metadata/control device or node ID
     pub device_info: Option<DeviceInfo>, // V4L2 device information (card, driver, path, real_path)
     pub rotation: SensorRotation,        // Sensor rotation from libcamera/device tree
+    pub facing: CameraFacing,            // Front/back from libcamera, V4L2 or USB port type
+    pub source_kind: Option<CameraSourceKind>, // Stack serving this node (None if unknown)
+    /// The same camera through the other stack, when PipeWire offers both
+    pub alternate_source: Option<Box<CameraDevice>>,
+}
+
+impl CameraDevice {
+    /// Stacks this camera can be used through
+    pub fn source_kinds(&self) -> Vec<CameraSourceKind> {
+        self.source_kind
+            .into_iter()
+            .chain(
+                self.alternate_source
+                    .as_ref()
+                    .and_then(|alt| alt.source_kind),
+            )
+            .collect()
+    }
+
+    /// This camera through `kind`, or unchanged if it isn't offered that way
+    ///
+    /// The entry for the other stack becomes the alternate, so switching back
+    /// and forth is lossless.
+    pub fn through(mut self, kind: CameraSourceKind) -> Self {
+        if self.source_kind == Some(kind) {
+            return self;
+        }
+        match self.alternate_source.take() {
+            Some(mut alternate) if alternate.source_kind == Some(kind) => {
+                alternate.alternate_source = Some(Box::new(self));
+                *alternate
+            }
+            other => {
+                self.alternate_source = other;
+                self
+            }
+        }
+    }
+
+    /// Key for per-camera stack overrides, the same whichever stack is in use
+    pub fn source_key(&self) -> &str {
+        match &self.alternate_source {
+            Some(alternate) if alternate.source_kind == Some(CameraSourceKind::V4l2) => {
+                &alternate.path
+            }
+            _ => &self.path,
+        }
+    }
 }
 
 /// Framerate as a fraction (numerator/denominator)
@@ -283,6 +481,25 @@ impl std::fmt::Display for CameraFormat {
     }
 }
 
+/// Formats of a camera together with the controls it offers
+///
+/// Both come from one probe when the camera is selected, so the format
+/// picker and the control panels work from the same snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceCapabilities {
+    /// Supported formats (resolution, frame rate, pixel format)
+    pub formats: Vec<CameraFormat>,
+    /// V4L2 controls and their device-wide ranges (empty for non-V4L2 cameras)
+    pub controls: super::v4l2_controls::DeviceControls,
+}
+
+impl DeviceCapabilities {
+    /// Control ranges as they apply while streaming `format`
+    pub fn controls_for(&self, format: &CameraFormat) -> super::v4l2_controls::DeviceControls {
+        self.controls.for_framerate(format.framerate.as_ref())
+    }
+}
+
 /// Pixel format for camera frames
 ///
 /// Supports both direct RGBA and various YUV formats for GPU conversion.
@@ -308,6 +525,9 @@ pub enum PixelFormat {
     /// Gray8 - 8-bit grayscale (single channel)
     /// Used for monochrome cameras, depth sensors, IR cameras
     Gray8,
+    /// Gray16 - 16-bit little-endian grayscale (Y16, single channel)
+    /// Used for thermal cameras, industrial sensors, depth sensors
+    Gray16,
     /// RGB24 - 24-bit RGB (3 bytes per pixel, no alpha)
     /// Direct RGB without alpha channel
     RGB24,
@@ -337,6 +557,11 @@ impl PixelFormat {
         )
     }
 
+    /// Check if this is a single-channel grayscale format
+    pub fn is_grayscale(&self) -> bool {
+        matches!(self, Self::Gray8 | Self::Gray16)
+    }
+
     /// Get the format code for the GPU compute shader
     pub fn gpu_format_code(&self) -> u32 {
         match self {
@@ -350,6 +575,7 @@ impl PixelFormat {
             Self::NV21 => 7,
             Self::YVYU => 8,
             Self::VYUY => 9,
+            Self::Gray16 => 10,
         }
     }
 
@@ -360,6 +586,7 @@ impl PixelFormat {
             Self::NV12 | Self::NV21 | Self::I420 => 1.5, // 4:2:0 subsampling
             Self::YUYV | Self::UYVY | Self::YVYU | Self::VYUY => 2.0, // 4:2:2 subsampling
             Self::Gray8 => 1.0,                          // Single channel
+            Self::Gray16 => 2.0,                         // Single 16-bit channel
             Self::RGB24 => 3.0,                          // 3 bytes per pixel
         }
     }
@@ -378,12 +605,39 @@ impl PixelFormat {
             "YVYU" => Some(Self::YVYU),
             "VYUY" => Some(Self::VYUY),
             "GRAY8" | "GREY" | "Y8" => Some(Self::Gray8),
+            "GRAY16_LE" | "Y16" => Some(Self::Gray16),
             "RGB" | "BGR" => Some(Self::RGB24),
             _ => None,
         }
     }
 }
 
+/// Sample the min/max intensity of a Gray16 (little-endian) frame
+///
+/// Only every 4th pixel of every 4th row is inspected, which is plenty for
+/// auto-stretching while keeping the cost negligible for large sensors.
+/// Returns None when the frame is flat or too short to sample.
+pub fn gray16_range(data: &[u8], width: u32, height: u32, stride: u32) -> Option<(u16, u16)> {
+    const STEP: usize = 4;
+
+    let mut min = u16::MAX;
+    let mut max = u16::MIN;
+    for y in (0..height as usize).step_by(STEP) {
+        let row_start = y * stride as usize;
+        for x in (0..width as usize).step_by(STEP) {
+            let offset = row_start + x * 2;
+            let Some(bytes) = data.get(offset..offset + 2) else {
+                break;
+            };
+            let value = u16::from_le_bytes([bytes[0], bytes[1]]);
+            min = min.min(value);
+            max = max.max(value);
+        }
+    }
+
+    (max > min).then_some((min, max))
+}
+
 /// YUV plane offsets for multi-plane formats (NV12, I420)
 ///
 /// For planar/semi-planar YUV formats, the planes are stored at different offsets
@@ -429,6 +683,30 @@ impl std::fmt::Debug for YuvPlanes {
 
 /// A single frame from the camera
 ///
+/// Position of a frame in its pipeline session and its source timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStamp {
+    /// Samples pulled from the pipeline before this one (resets on rebuild)
+    pub sequence: u64,
+    /// Presentation timestamp of the buffer, if it had one
+    pub pts: Option<Duration>,
+}
+
+impl FrameStamp {
+    /// Write "#sequence  pts" into `out`, replacing its contents
+    ///
+    /// Reuses the string's buffer, so a label refreshed for every frame
+    /// doesn't allocate once it has grown to size.
+    pub fn write_label(&self, out: &mut String) {
+        use std::fmt::Write;
+        out.clear();
+        let _ = match self.pts {
+            Some(pts) => write!(out, "#{}  {:.3} s", self.sequence, pts.as_secs_f64()),
+            None => write!(out, "#{}  -", self.sequence),
+        };
+    }
+}
+
 /// Supports both RGBA and YUV formats. For YUV formats:
 /// - `data` contains the entire buffer (all planes contiguous, zero-copy)
 /// - `yuv_planes` contains offsets to extract Y, UV, V planes during GPU upload
@@ -446,6 +724,8 @@ pub struct CameraFrame {
     pub yuv_planes: Option<YuvPlanes>,
     /// Timestamp when frame was captured (for latency diagnostics)
     pub captured_at: Instant,
+    /// Sequence number and PTS (None for frames not from a camera pipeline)
+    pub stamp: Option<FrameStamp>,
 }
 
 impl CameraFrame {
@@ -485,15 +765,16 @@ impl CameraFrame {
             stride: self.stride,
             yuv_planes: self.yuv_planes,
             captured_at: self.captured_at,
+            stamp: self.stamp,
         }
     }
 }
 
 /// Frame receiver type for preview streams
-pub type FrameReceiver = cosmic::iced::futures::channel::mpsc::Receiver<CameraFrame>;
+pub type FrameReceiver = super::frame_queue::Receiver<CameraFrame>;
 
 /// Frame sender type for preview streams
-pub type FrameSender = cosmic::iced::futures::channel::mpsc::Sender<CameraFrame>;
+pub type FrameSender = super::frame_queue::Sender<CameraFrame>;
 
 /// Result type for backend operations
 pub type BackendResult<T> = Result<T, BackendError>;
@@ -517,6 +798,10 @@ pub enum BackendError {
     NoRecordingInProgress,
     /// General I/O error
     IoError(String),
+    /// Operation did not complete in time
+    Timeout(String),
+    /// The capture pipeline could not be started
+    Pipeline(crate::media::decoders::PipelineError),
     /// Other errors
     Other(String),
 }
@@ -532,6 +817,8 @@ impl std::fmt::Display for BackendError {
             BackendError::RecordingInProgress => write!(f, "Recording already in progress"),
             BackendError::NoRecordingInProgress => write!(f, "No recording in progress"),
             BackendError::IoError(msg) => write!(f, "I/O error: {}", msg),
+            BackendError::Timeout(msg) => write!(f, "Timed out: {}", msg),
+            BackendError::Pipeline(err) => write!(f, "{}", err),
             BackendError::Other(msg) => write!(f, "Error: {}", msg),
         }
     }
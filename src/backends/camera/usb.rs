@@ -0,0 +1,352 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! USB topology and bandwidth diagnostics for UVC cameras
+//!
+//! Cameras sharing a USB bus compete for isochronous bandwidth. When there
+//! isn't enough, the camera (or its driver) quietly falls back to a smaller
+//! mode or drops frames. This module reads the camera's place in the USB
+//! topology from sysfs and judges whether a mode shortfall is likely caused
+//! by bandwidth, so insights can suggest moving the camera to another port.
+//!
+//! Everything here is best effort: non-USB cameras and systems without
+//! sysfs simply yield `None`.
+
+use crate::backends::camera::types::UsbId;
+use std::path::{Path, PathBuf};
+
+/// Share of the bus an uncompressed stream may use before it is considered tight
+///
+/// Periodic (isochronous) transfers get at most 80% of a USB 2.0 frame, and
+/// protocol overhead takes a further share of that.
+const USABLE_BUS_SHARE: f64 = 0.6;
+
+/// Delivered frame rate below this fraction of the requested rate counts as a shortfall
+const FRAMERATE_SHORTFALL: f32 = 0.8;
+
+/// USB interface class of video devices
+const VIDEO_INTERFACE_CLASS: &str = "0e";
+
+/// USB device class of hubs
+const HUB_DEVICE_CLASS: &str = "09";
+
+/// A camera's position in the USB topology
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsbTopology {
+    /// Negotiated link speed in Mbit/s (1.5, 12, 480, 5000, ...)
+    pub speed_mbps: f64,
+    /// USB bus number
+    pub bus: u32,
+    /// Port path on the bus (e.g. "1.2")
+    pub port_path: String,
+    /// Non-hub devices on the same bus, including the camera
+    pub devices_on_bus: usize,
+    /// Video devices on the same bus, including the camera
+    pub cameras_on_bus: usize,
+}
+
+impl UsbTopology {
+    /// USB generation name for the link speed
+    pub fn speed_name(&self) -> &'static str {
+        match self.speed_mbps {
+            s if s <= 1.5 => "USB 1.0",
+            s if s <= 12.0 => "USB 1.1",
+            s if s <= 480.0 => "USB 2.0",
+            s if s <= 5000.0 => "USB 3.0",
+            _ => "USB 3.1+",
+        }
+    }
+}
+
+/// Width, height and frame rate of a video mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoMode {
+    pub width: u32,
+    pub height: u32,
+    pub fps: Option<f32>,
+}
+
+/// Why the camera is delivering less than was requested
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BandwidthHint {
+    /// A smaller resolution or frame rate was negotiated, likely for bandwidth
+    ModeDowngraded,
+    /// Frames arrive well below the requested rate, likely for bandwidth
+    FramerateShortfall,
+}
+
+/// Sysfs directory of the USB device behind a V4L2 node
+///
+/// `/sys/class/video4linux/videoN/device` points at the USB interface; the
+/// device itself is one or more levels up.
+pub fn usb_device_dir(v4l2_path: &str) -> Option<PathBuf> {
+    let node = Path::new(v4l2_path).file_name()?;
+    let interface = std::fs::canonicalize(
+        Path::new("/sys/class/video4linux")
+            .join(node)
+            .join("device"),
+    )
+    .ok()?;
+    interface
+        .ancestors()
+        .take(3)
+        .find(|dir| dir.join("idVendor").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Whether the USB port the camera is plugged into is user-accessible
+///
+/// Built-in laptop webcams sit on ports the firmware reports as "fixed".
+pub fn is_removable(v4l2_path: &str) -> Option<bool> {
+    match read_trimmed(&usb_device_dir(v4l2_path)?.join("removable"))?.as_str() {
+        "removable" => Some(true),
+        "fixed" => Some(false),
+        _ => None,
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    Some(std::fs::read_to_string(path).ok()?.trim().to_string())
+}
+
+/// Read where the camera behind a V4L2 node sits in the USB topology
+pub fn read_topology(v4l2_path: &str) -> Option<UsbTopology> {
+    let device = usb_device_dir(v4l2_path)?;
+    let speed_mbps = read_trimmed(&device.join("speed"))?.parse().ok()?;
+    let bus: u32 = read_trimmed(&device.join("busnum"))?.parse().ok()?;
+    let port_path = read_trimmed(&device.join("devpath")).unwrap_or_default();
+
+    let mut devices_on_bus = 0;
+    let mut cameras_on_bus = 0;
+    for entry in std::fs::read_dir("/sys/bus/usb/devices").ok()?.flatten() {
+        let dir = entry.path();
+        // Interfaces (e.g. "1-2:1.0") and root hubs ("usb1") have no devpath of their own
+        if entry.file_name().to_string_lossy().contains(':')
+            || read_trimmed(&dir.join("busnum")).and_then(|n| n.parse::<u32>().ok()) != Some(bus)
+            || read_trimmed(&dir.join("bDeviceClass")).as_deref() == Some(HUB_DEVICE_CLASS)
+        {
+            continue;
+        }
+        devices_on_bus += 1;
+        if has_video_interface(&dir) {
+            cameras_on_bus += 1;
+        }
+    }
+
+    Some(UsbTopology {
+        speed_mbps,
+        bus,
+        port_path,
+        devices_on_bus,
+        cameras_on_bus,
+    })
+}
+
+fn has_video_interface(device: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(device) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        entry.file_name().to_string_lossy().contains(':')
+            && read_trimmed(&entry.path().join("bInterfaceClass")).as_deref()
+                == Some(VIDEO_INTERFACE_CLASS)
+    })
+}
+
+/// Stable identifiers of the hardware behind a V4L2 node, for bug reports
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviceIdentity {
+    /// V4L2 node (e.g. /dev/video0)
+    pub node: String,
+    /// Kernel driver bound to the video interface (uvcvideo, ...)
+    pub driver: Option<String>,
+    /// USB vendor and product ID (None for non-USB cameras)
+    pub usb_id: Option<UsbId>,
+    /// USB device name in sysfs, bus and port path (e.g. "3-2.1")
+    pub bus_path: Option<String>,
+    /// USB manufacturer string
+    pub manufacturer: Option<String>,
+    /// USB product string
+    pub product: Option<String>,
+    /// USB serial number
+    pub serial: Option<String>,
+}
+
+/// Read the driver, USB IDs and bus path of the camera behind a V4L2 node
+///
+/// `None` if the node has no sysfs entry. Cameras that aren't on USB get
+/// the driver only.
+pub fn read_identity(v4l2_path: &str) -> Option<DeviceIdentity> {
+    let node = Path::new(v4l2_path).file_name()?;
+    let interface = Path::new("/sys/class/video4linux")
+        .join(node)
+        .join("device");
+    if !interface.exists() {
+        return None;
+    }
+    let driver = std::fs::read_link(interface.join("driver"))
+        .ok()
+        .and_then(|link| Some(link.file_name()?.to_string_lossy().into_owned()));
+
+    let mut identity = DeviceIdentity {
+        node: v4l2_path.to_string(),
+        driver,
+        ..Default::default()
+    };
+    if let Some(device) = usb_device_dir(v4l2_path) {
+        let hex = |name: &str| u16::from_str_radix(&read_trimmed(&device.join(name))?, 16).ok();
+        identity.usb_id = hex("idVendor")
+            .zip(hex("idProduct"))
+            .map(|(vendor, product)| UsbId::new(vendor, product));
+        identity.bus_path = device
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+        identity.manufacturer = read_trimmed(&device.join("manufacturer"));
+        identity.product = read_trimmed(&device.join("product"));
+        identity.serial = read_trimmed(&device.join("serial")).filter(|s| !s.is_empty());
+    }
+    Some(identity)
+}
+
+/// Bandwidth an uncompressed mode needs in Mbit/s
+pub fn raw_bandwidth_mbps(mode: &VideoMode, bytes_per_pixel: f64) -> Option<f64> {
+    let fps = f64::from(mode.fps?);
+    Some(f64::from(mode.width) * f64::from(mode.height) * bytes_per_pixel * fps * 8.0 / 1e6)
+}
+
+/// Judge whether the camera falls short of the requested mode because of USB bandwidth
+///
+/// `required_mbps` is the raw bandwidth of the requested mode (`None` for
+/// compressed formats, whose bitrate isn't known up front). Returns `None`
+/// when there is no shortfall, or when the topology doesn't point at bandwidth.
+pub fn assess(
+    requested: &VideoMode,
+    negotiated: Option<&VideoMode>,
+    delivered_fps: Option<f32>,
+    required_mbps: Option<f64>,
+    topology: &UsbTopology,
+) -> Option<BandwidthHint> {
+    let downgraded = negotiated.is_some_and(|negotiated| {
+        u64::from(negotiated.width) * u64::from(negotiated.height)
+            < u64::from(requested.width) * u64::from(requested.height)
+            || matches!(
+                (negotiated.fps, requested.fps),
+                (Some(got), Some(wanted)) if got < wanted * 0.99
+            )
+    });
+    let short = matches!(
+        (delivered_fps, requested.fps),
+        (Some(got), Some(wanted)) if got < wanted * FRAMERATE_SHORTFALL
+    );
+    if !downgraded && !short {
+        return None;
+    }
+
+    // SuperSpeed links have bandwidth to spare for any single webcam
+    let bandwidth_limited = topology.speed_mbps <= 480.0
+        && (topology.cameras_on_bus > 1
+            || required_mbps.is_some_and(|mbps| mbps > topology.speed_mbps * USABLE_BUS_SHARE));
+    if !bandwidth_limited {
+        return None;
+    }
+
+    Some(if downgraded {
+        BandwidthHint::ModeDowngraded
+    } else {
+        BandwidthHint::FramerateShortfall
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topology(speed_mbps: f64, cameras_on_bus: usize) -> UsbTopology {
+        UsbTopology {
+            speed_mbps,
+            bus: 1,
+            port_path: "2".to_string(),
+            devices_on_bus: cameras_on_bus + 1,
+            cameras_on_bus,
+        }
+    }
+
+    fn mode(width: u32, height: u32, fps: f32) -> VideoMode {
+        VideoMode {
+            width,
+            height,
+            fps: Some(fps),
+        }
+    }
+
+    #[test]
+    fn test_no_hint_when_mode_is_delivered() {
+        let requested = mode(1280, 720, 30.0);
+        assert_eq!(
+            assess(
+                &requested,
+                Some(&requested),
+                Some(29.9),
+                None,
+                &topology(480.0, 2)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_downgrade_on_shared_usb2_bus() {
+        let requested = mode(1920, 1080, 30.0);
+        let negotiated = mode(1280, 720, 30.0);
+        assert_eq!(
+            assess(
+                &requested,
+                Some(&negotiated),
+                None,
+                None,
+                &topology(480.0, 2)
+            ),
+            Some(BandwidthHint::ModeDowngraded)
+        );
+        // Alone on a SuperSpeed bus bandwidth is not the likely cause
+        assert_eq!(
+            assess(
+                &requested,
+                Some(&negotiated),
+                None,
+                None,
+                &topology(5000.0, 2)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_raw_stream_over_usb2_budget() {
+        // YUYV 1280x720 at 30 fps needs ~442 Mbit/s, more than USB 2.0 can carry
+        let requested = mode(1280, 720, 30.0);
+        let required = raw_bandwidth_mbps(&requested, 2.0);
+        assert!(required.is_some_and(|mbps| mbps > 400.0));
+        assert_eq!(
+            assess(
+                &requested,
+                Some(&requested),
+                Some(10.0),
+                required,
+                &topology(480.0, 1)
+            ),
+            Some(BandwidthHint::FramerateShortfall)
+        );
+        // A lone camera with a light stream is not bandwidth limited
+        let small = mode(320, 240, 30.0);
+        assert_eq!(
+            assess(
+                &small,
+                Some(&small),
+                Some(10.0),
+                raw_bandwidth_mbps(&small, 2.0),
+                &topology(480.0, 1)
+            ),
+            None
+        );
+    }
+}
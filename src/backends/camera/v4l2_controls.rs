@@ -7,6 +7,8 @@
 //!
 //! Inspired by [cameractrls](https://github.com/soyersoyer/cameractrls).
 
+use super::types::Framerate;
+use std::collections::HashMap;
 use std::fs::File;
 use std::os::unix::io::AsRawFd;
 use tracing::{debug, warn};
@@ -40,6 +42,8 @@ pub const V4L2_CID_GAIN: u32 = V4L2_CID_BASE + 19;
 pub const V4L2_CID_WHITE_BALANCE_TEMPERATURE: u32 = V4L2_CID_BASE + 26;
 /// Sharpness control
 pub const V4L2_CID_SHARPNESS: u32 = V4L2_CID_BASE + 27;
+/// Power line frequency filter (anti-flicker) menu
+pub const V4L2_CID_POWER_LINE_FREQUENCY: u32 = V4L2_CID_BASE + 24;
 /// Backlight compensation - helps with backlit subjects
 pub const V4L2_CID_BACKLIGHT_COMPENSATION: u32 = V4L2_CID_BASE + 28;
 
@@ -55,6 +59,8 @@ pub const V4L2_CID_EXPOSURE_AUTO_PRIORITY: u32 = V4L2_CID_CAMERA_CLASS_BASE + 3;
 pub const V4L2_CID_FOCUS_ABSOLUTE: u32 = V4L2_CID_CAMERA_CLASS_BASE + 10;
 /// Auto focus enable
 pub const V4L2_CID_FOCUS_AUTO: u32 = V4L2_CID_CAMERA_CLASS_BASE + 12;
+/// Start a single auto focus run (button control)
+pub const V4L2_CID_AUTO_FOCUS_START: u32 = V4L2_CID_CAMERA_CLASS_BASE + 28;
 /// Exposure compensation (EV bias) in 0.001 EV units
 pub const V4L2_CID_AUTO_EXPOSURE_BIAS: u32 = V4L2_CID_CAMERA_CLASS_BASE + 19;
 /// ISO sensitivity value
@@ -65,6 +71,10 @@ pub const V4L2_CID_ISO_SENSITIVITY_AUTO: u32 = V4L2_CID_CAMERA_CLASS_BASE + 24;
 pub const V4L2_CID_EXPOSURE_METERING: u32 = V4L2_CID_CAMERA_CLASS_BASE + 25;
 /// Privacy control - when 1 (TRUE), camera cannot capture (privacy cover closed)
 pub const V4L2_CID_PRIVACY: u32 = V4L2_CID_CAMERA_CLASS_BASE + 16;
+/// Which way the camera faces (read-only menu, from firmware)
+pub const V4L2_CID_CAMERA_ORIENTATION: u32 = V4L2_CID_CAMERA_CLASS_BASE + 34;
+/// Sensor mounting rotation in degrees (read-only, from firmware)
+pub const V4L2_CID_CAMERA_SENSOR_ROTATION: u32 = V4L2_CID_CAMERA_CLASS_BASE + 35;
 
 // ===== V4L2 Control IDs (Camera Class - PTZ) =====
 
@@ -96,6 +106,39 @@ pub const V4L2_CID_TILT_SPEED: u32 = V4L2_CID_CAMERA_CLASS_BASE + 33;
 /// Analogue gain (image source class)
 pub const V4L2_CID_ANALOGUE_GAIN: u32 = V4L2_CID_IMAGE_SOURCE_CLASS_BASE + 3;
 
+/// Controls read by [`probe_controls`]
+pub const PROBED_CONTROLS: &[u32] = &[
+    V4L2_CID_BRIGHTNESS,
+    V4L2_CID_CONTRAST,
+    V4L2_CID_SATURATION,
+    V4L2_CID_HUE,
+    V4L2_CID_AUTO_WHITE_BALANCE,
+    V4L2_CID_AUTOGAIN,
+    V4L2_CID_GAIN,
+    V4L2_CID_WHITE_BALANCE_TEMPERATURE,
+    V4L2_CID_SHARPNESS,
+    V4L2_CID_POWER_LINE_FREQUENCY,
+    V4L2_CID_BACKLIGHT_COMPENSATION,
+    V4L2_CID_EXPOSURE_AUTO,
+    V4L2_CID_EXPOSURE_ABSOLUTE,
+    V4L2_CID_EXPOSURE_AUTO_PRIORITY,
+    V4L2_CID_FOCUS_ABSOLUTE,
+    V4L2_CID_FOCUS_AUTO,
+    V4L2_CID_AUTO_FOCUS_START,
+    V4L2_CID_AUTO_EXPOSURE_BIAS,
+    V4L2_CID_ISO_SENSITIVITY,
+    V4L2_CID_EXPOSURE_METERING,
+    V4L2_CID_PRIVACY,
+    V4L2_CID_PAN_RELATIVE,
+    V4L2_CID_TILT_RELATIVE,
+    V4L2_CID_PAN_RESET,
+    V4L2_CID_TILT_RESET,
+    V4L2_CID_PAN_ABSOLUTE,
+    V4L2_CID_TILT_ABSOLUTE,
+    V4L2_CID_ZOOM_ABSOLUTE,
+    V4L2_CID_ANALOGUE_GAIN,
+];
+
 // ===== V4L2 Exposure Auto Menu Values =====
 
 /// Automatic exposure time and iris
@@ -107,6 +150,15 @@ pub const V4L2_EXPOSURE_SHUTTER_PRIORITY: i32 = 2;
 /// Auto exposure time, manual iris (aperture priority)
 pub const V4L2_EXPOSURE_APERTURE_PRIORITY: i32 = 3;
 
+// ===== V4L2 Camera Orientation Menu Values =====
+
+/// Camera faces the user
+pub const V4L2_CAMERA_ORIENTATION_FRONT: i32 = 0;
+/// Camera faces away from the user
+pub const V4L2_CAMERA_ORIENTATION_BACK: i32 = 1;
+/// Camera is not attached to the device (e.g. USB webcam)
+pub const V4L2_CAMERA_ORIENTATION_EXTERNAL: i32 = 2;
+
 // ===== V4L2 Exposure Metering Menu Values =====
 
 /// Average metering across entire frame
@@ -118,6 +170,17 @@ pub const V4L2_EXPOSURE_METERING_SPOT: i32 = 2;
 /// Matrix/evaluative metering
 pub const V4L2_EXPOSURE_METERING_MATRIX: i32 = 3;
 
+// ===== V4L2 Power Line Frequency Menu Values =====
+
+/// No anti-flicker filtering
+pub const V4L2_CID_POWER_LINE_FREQUENCY_DISABLED: i32 = 0;
+/// Filter 50 Hz mains flicker
+pub const V4L2_CID_POWER_LINE_FREQUENCY_50HZ: i32 = 1;
+/// Filter 60 Hz mains flicker
+pub const V4L2_CID_POWER_LINE_FREQUENCY_60HZ: i32 = 2;
+/// Camera detects the mains frequency itself
+pub const V4L2_CID_POWER_LINE_FREQUENCY_AUTO: i32 = 3;
+
 // ===== V4L2 Control Types =====
 const V4L2_CTRL_TYPE_INTEGER: u32 = 1;
 const V4L2_CTRL_TYPE_BOOLEAN: u32 = 2;
@@ -140,6 +203,13 @@ const VIDIOC_S_CTRL: libc::c_ulong = 0xC008561C;
 const VIDIOC_QUERYCTRL: libc::c_ulong = 0xC0445624;
 /// Query menu item (v4l2_querymenu: 44 bytes)
 const VIDIOC_QUERYMENU: libc::c_ulong = 0xC02C5625;
+/// Request capture buffers (v4l2_requestbuffers: 20 bytes)
+const VIDIOC_REQBUFS: libc::c_ulong = 0xC0145608;
+
+/// Single-planar video capture buffer type
+const V4L2_BUF_TYPE_VIDEO_CAPTURE: u32 = 1;
+/// Memory-mapped buffers
+const V4L2_MEMORY_MMAP: u32 = 1;
 
 // ===== V4L2 ioctl Structures =====
 
@@ -174,6 +244,17 @@ struct V4l2Querymenu {
     reserved: u32,
 }
 
+/// V4L2 buffer request structure
+#[repr(C)]
+struct V4l2Requestbuffers {
+    count: u32,
+    buf_type: u32,
+    memory: u32,
+    capabilities: u32,
+    flags: u8,
+    reserved: [u8; 3],
+}
+
 // ===== Public Types =====
 
 /// Information about a V4L2 control
@@ -223,6 +304,56 @@ impl ControlInfo {
     }
 }
 
+/// Controls read in one probe pass when a camera's formats are enumerated
+///
+/// The format picker and the control panels share this snapshot instead of
+/// opening the device again for every control.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceControls {
+    controls: HashMap<u32, ControlInfo>,
+}
+
+impl DeviceControls {
+    /// Control info, if the device has the control
+    pub fn get(&self, control_id: u32) -> Option<&ControlInfo> {
+        self.controls.get(&control_id)
+    }
+
+    /// Whether the probe found no controls (non-V4L2 device or probe failed)
+    pub fn is_empty(&self) -> bool {
+        self.controls.is_empty()
+    }
+
+    /// Number of controls found
+    pub fn len(&self) -> usize {
+        self.controls.len()
+    }
+
+    /// Ranges as they apply while streaming at `framerate`
+    ///
+    /// The exposure time can't exceed the frame interval, so its maximum
+    /// shrinks as the frame rate goes up.
+    pub fn for_framerate(&self, framerate: Option<&Framerate>) -> DeviceControls {
+        let mut controls = self.clone();
+        if let Some(limit) = framerate.and_then(max_exposure_100us)
+            && let Some(exposure) = controls.controls.get_mut(&V4L2_CID_EXPOSURE_ABSOLUTE)
+            && exposure.maximum > limit
+        {
+            exposure.maximum = limit.max(exposure.minimum);
+            exposure.default_value = exposure.default_value.min(exposure.maximum);
+        }
+        controls
+    }
+}
+
+/// Longest exposure that fits in one frame interval, in 100µs units
+pub fn max_exposure_100us(framerate: &Framerate) -> Option<i32> {
+    if framerate.num == 0 {
+        return None;
+    }
+    i32::try_from(u64::from(framerate.denom) * 10_000 / u64::from(framerate.num)).ok()
+}
+
 /// Menu item for menu-type controls
 #[derive(Debug, Clone)]
 pub struct MenuItem {
@@ -243,8 +374,25 @@ fn extract_name(bytes: &[u8; 32]) -> String {
 /// Query if a control exists and get its information
 pub fn query_control(device_path: &str, control_id: u32) -> Option<ControlInfo> {
     let file = File::open(device_path).ok()?;
+    query_control_fd(file.as_raw_fd(), control_id)
+}
+
+/// Query every control the app uses, opening the device once
+pub fn probe_controls(device_path: &str) -> DeviceControls {
+    let Ok(file) = File::open(device_path) else {
+        debug!(device_path, "Failed to open device for control probe");
+        return DeviceControls::default();
+    };
     let fd = file.as_raw_fd();
+    let controls: HashMap<u32, ControlInfo> = PROBED_CONTROLS
+        .iter()
+        .filter_map(|&id| query_control_fd(fd, id).map(|info| (id, info)))
+        .collect();
+    debug!(device_path, count = controls.len(), "Probed V4L2 controls");
+    DeviceControls { controls }
+}
 
+fn query_control_fd(fd: std::os::unix::io::RawFd, control_id: u32) -> Option<ControlInfo> {
     let mut qctrl = V4l2Queryctrl {
         id: control_id,
         ctrl_type: 0,
@@ -399,6 +547,49 @@ pub fn has_control(device_path: &str, control_id: u32) -> bool {
         .unwrap_or(false)
 }
 
+/// Check whether another file handle is streaming from the device
+///
+/// Re-opens the device and asks it to free its (zero) capture buffers, which
+/// the driver refuses with EBUSY while anyone else owns the buffer queue.
+/// `None` if the device can't be opened or isn't a single-planar capture device.
+pub fn is_capture_busy(device_path: &str) -> Option<bool> {
+    let file = File::options()
+        .read(true)
+        .write(true)
+        .open(device_path)
+        .ok()?;
+    let fd = file.as_raw_fd();
+
+    let mut request = V4l2Requestbuffers {
+        count: 0,
+        buf_type: V4L2_BUF_TYPE_VIDEO_CAPTURE,
+        memory: V4L2_MEMORY_MMAP,
+        capabilities: 0,
+        flags: 0,
+        reserved: [0; 3],
+    };
+
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_ioctl,
+            fd,
+            VIDIOC_REQBUFS,
+            &mut request as *mut V4l2Requestbuffers,
+        )
+    };
+
+    if result < 0 {
+        let errno = std::io::Error::last_os_error();
+        if errno.raw_os_error() == Some(libc::EBUSY) {
+            return Some(true);
+        }
+        debug!(device_path, ?errno, "Failed to request V4L2 buffers");
+        return None;
+    }
+
+    Some(false)
+}
+
 /// Exposure metadata read from camera
 #[derive(Debug, Clone, Default)]
 pub struct ExposureMetadata {
@@ -455,10 +646,19 @@ mod tests {
         assert_eq!(V4L2_CID_EXPOSURE_ABSOLUTE, 0x009a0902);
         assert_eq!(V4L2_CID_AUTO_EXPOSURE_BIAS, 0x009a0913);
         assert_eq!(V4L2_CID_EXPOSURE_METERING, 0x009a0919);
+        assert_eq!(V4L2_CID_POWER_LINE_FREQUENCY, 0x00980918);
         assert_eq!(V4L2_CID_ISO_SENSITIVITY, 0x009a0917);
         assert_eq!(V4L2_CID_GAIN, 0x00980913);
     }
 
+    #[test]
+    fn test_request_buffers_layout() {
+        // VIDIOC_REQBUFS encodes the structure size
+        assert_eq!(std::mem::size_of::<V4l2Requestbuffers>(), 20);
+        assert_eq!((VIDIOC_REQBUFS >> 16) & 0x3fff, 20);
+        assert_eq!(is_capture_busy("/nonexistent/video0"), None);
+    }
+
     #[test]
     fn test_control_type_conversion() {
         assert_eq!(ControlType::from(1), ControlType::Integer);
@@ -466,4 +666,37 @@ mod tests {
         assert_eq!(ControlType::from(3), ControlType::Menu);
         assert_eq!(ControlType::from(99), ControlType::Unknown(99));
     }
+
+    #[test]
+    fn test_exposure_range_follows_framerate() {
+        let exposure = ControlInfo {
+            id: V4L2_CID_EXPOSURE_ABSOLUTE,
+            name: "Exposure Time, Absolute".to_string(),
+            ctrl_type: ControlType::Integer,
+            minimum: 3,
+            maximum: 2047,
+            step: 1,
+            default_value: 250,
+            flags: 0,
+        };
+        let controls = DeviceControls {
+            controls: HashMap::from([(V4L2_CID_EXPOSURE_ABSOLUTE, exposure)]),
+        };
+
+        // 1/30 s = 333 units of 100µs
+        let at_30 = controls.for_framerate(Some(&Framerate::from_int(30)));
+        let range = at_30.get(V4L2_CID_EXPOSURE_ABSOLUTE).unwrap();
+        assert_eq!((range.maximum, range.default_value), (333, 250));
+
+        // 1/5 s is longer than the control's own maximum
+        let at_5 = controls.for_framerate(Some(&Framerate::from_int(5)));
+        assert_eq!(at_5.get(V4L2_CID_EXPOSURE_ABSOLUTE).unwrap().maximum, 2047);
+
+        // Without a frame rate (photo mode) the device range applies
+        let unlimited = controls.for_framerate(None);
+        assert_eq!(
+            unlimited.get(V4L2_CID_EXPOSURE_ABSOLUTE).unwrap().maximum,
+            2047
+        );
+    }
 }
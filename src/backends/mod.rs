@@ -4,7 +4,7 @@
 //!
 //! This module provides platform-specific backend implementations for:
 //! - Camera capture via PipeWire
-//! - Audio device enumeration via PipeWire
+//! - Audio device enumeration and level monitoring via PipeWire
 //! - Virtual camera output via PipeWire
 //!
 //! # Architecture
@@ -32,7 +32,7 @@
 //!
 //! # Modules
 //!
-//! - [`audio`]: Audio device enumeration and selection
+//! - [`audio`]: Audio device enumeration, selection and level metering
 //! - [`camera`]: Camera backend with device enumeration and frame capture
 //! - [`virtual_camera`]: Virtual camera sink for streaming filtered video
 
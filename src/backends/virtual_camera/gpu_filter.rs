@@ -176,6 +176,11 @@ impl GpuFilterRenderer {
         })
     }
 
+    /// Whether this renderer's GPU device was lost
+    pub fn is_device_lost(&self) -> bool {
+        gpu::is_device_lost(&self.device)
+    }
+
     /// Ensure GPU resources are allocated for the given dimensions
     fn ensure_resources(&mut self, width: u32, height: u32) {
         if self.width == width && self.height == height {
@@ -167,6 +167,15 @@ impl VirtualCameraManager {
         // Try to lock without blocking - if locked, skip filtering this frame
         match cell.try_lock() {
             Ok(mut guard) => {
+                // Drop a renderer whose device was lost so it gets rebuilt below
+                if guard
+                    .as_ref()
+                    .is_some_and(GpuFilterRenderer::is_device_lost)
+                {
+                    warn!("GPU device was lost, recreating virtual camera filter renderer");
+                    *guard = None;
+                }
+
                 // Initialize renderer if needed
                 if guard.is_none() {
                     // Create a runtime for initialization only
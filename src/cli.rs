@@ -7,14 +7,16 @@
 //! - Taking photos
 //! - Recording videos
 
+use camera::backends::camera::frame_queue::{self, FrameDropPolicy};
 use camera::backends::camera::pipewire::{
     PipeWirePipeline, enumerate_pipewire_cameras, get_pipewire_formats,
 };
 use camera::backends::camera::types::{CameraFormat, CameraFrame};
+use camera::constants::timing;
+use camera::pipelines::filename::OutputTarget;
 use camera::pipelines::photo::PhotoPipeline;
 use camera::pipelines::video::{EncoderConfig, VideoRecorder, VideoRecorderConfig};
 use chrono::Local;
-use futures::channel::mpsc;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -127,8 +129,14 @@ pub fn take_photo(
 
     // Start camera pipeline
     println!("Capturing...");
-    let (sender, mut receiver) = mpsc::channel(10);
-    let _pipeline = PipeWirePipeline::new(camera, &format, sender)?;
+    let (sender, mut receiver) = frame_queue::channel(10, FrameDropPolicy::SkipToLatest);
+    let pipeline = PipeWirePipeline::new(camera, &format, sender)?;
+
+    // Create async runtime for waiting on the camera and for the photo pipeline
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(
+        pipeline.wait_for_first_frame(Duration::from_secs(timing::FIRST_FRAME_TIMEOUT_SECS)),
+    )?;
 
     // Wait for frames to stabilize (camera warm-up)
     let start = Instant::now();
@@ -157,12 +165,9 @@ pub fn take_photo(
 
     // Use photo pipeline to save the image
     let photo_pipeline = PhotoPipeline::new();
-
-    // Create async runtime for the pipeline
-    let rt = tokio::runtime::Runtime::new()?;
     let output_path = rt.block_on(async {
         photo_pipeline
-            .capture_and_save(Arc::new(frame), output_dir)
+            .capture_and_save(Arc::new(frame), OutputTarget::new(output_dir))
             .await
     })?;
 
@@ -258,6 +263,10 @@ pub fn record_video(
         preview_sender: None, // No preview sender needed for CLI
         encoder_info: None,   // Auto-select encoder
         rotation: camera.rotation,
+        watermark: None,
+        split: None,
+        max_framerate: None,
+        variable_framerate: false,
     })?;
 
     // Start recording
@@ -432,17 +441,19 @@ pub fn process_burst_mode(
         exposure_time: None,
         iso: None,
         gain: None,
+        capture_info: None,
     };
 
     let output_path = rt.block_on(async {
         save_output(
             &result,
-            output_dir,
+            OutputTarget::new(output_dir),
             None,                 // no crop
             EncodingFormat::Jpeg, // Use JPEG for easier viewing
             camera_metadata,
             None,                 // no filter
             SensorRotation::None, // no rotation (CLI doesn't have camera info)
+            None,                 // no watermark
             Some("_HDR+"),        // filename suffix
         )
         .await
@@ -515,6 +526,7 @@ fn load_dng_frame(path: &PathBuf) -> Result<CameraFrame, Box<dyn std::error::Err
         stride: width * 4,
         yuv_planes: None,
         captured_at: Instant::now(),
+        stamp: None,
     })
 }
 
@@ -549,6 +561,7 @@ fn load_burst_mode_frames(
                 stride: width * 4,
                 yuv_planes: None,
                 captured_at: Instant::now(),
+                stamp: None,
             }
         };
 
@@ -4,7 +4,7 @@ use crate::constants::BitratePreset;
 use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
 use cosmic::{Theme, theme};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Photo output format preference
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -14,6 +14,8 @@ pub enum PhotoOutputFormat {
     Jpeg,
     /// PNG format (lossless, larger files)
     Png,
+    /// WebP format (lossless, smaller than PNG)
+    WebP,
     /// DNG format (raw image data)
     Dng,
 }
@@ -24,6 +26,7 @@ impl PhotoOutputFormat {
         match self {
             PhotoOutputFormat::Jpeg => "jpg",
             PhotoOutputFormat::Png => "png",
+            PhotoOutputFormat::WebP => "webp",
             PhotoOutputFormat::Dng => "dng",
         }
     }
@@ -33,14 +36,16 @@ impl PhotoOutputFormat {
         match self {
             PhotoOutputFormat::Jpeg => "JPEG",
             PhotoOutputFormat::Png => "PNG",
+            PhotoOutputFormat::WebP => "WebP",
             PhotoOutputFormat::Dng => "DNG (Raw)",
         }
     }
 
     /// Get all available formats
-    pub const ALL: [PhotoOutputFormat; 3] = [
+    pub const ALL: [PhotoOutputFormat; 4] = [
         PhotoOutputFormat::Jpeg,
         PhotoOutputFormat::Png,
+        PhotoOutputFormat::WebP,
         PhotoOutputFormat::Dng,
     ];
 }
@@ -115,6 +120,811 @@ impl AudioEncoder {
     pub const ALL: [AudioEncoder; 2] = [AudioEncoder::Opus, AudioEncoder::AAC];
 }
 
+/// File name prefix of the per-instance present mode markers
+const PRESENT_MODE_MARKER_PREFIX: &str = "present-mode-pending-";
+
+/// Surface present mode preference for the preview window
+///
+/// Applied through `ICED_PRESENT_MODE` before the renderer creates its surface,
+/// so a change only takes effect on the next launch. The renderer neither
+/// exposes its surface nor takes a new present mode while it is running.
+///
+/// Surfaces don't have to support every mode, and the renderer aborts when the
+/// requested one is missing. Fifo Relaxed and Immediate are requested as
+/// wgpu's automatic modes, which check the surface's capabilities when it is
+/// configured and walk the [fallback chain](PresentMode::fallback_chain)
+/// themselves. Mailbox has no such mode, so it is the only one that can fail:
+/// each instance writes a marker file (named after its process id) before
+/// launching with it and removes it once the window presents its first frame
+/// or the app exits cleanly. A marker left behind by a process that is gone
+/// means Mailbox is unsupported; that launch is lost, and later ones record
+/// it and use Fifo instead.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PresentMode {
+    /// Wait for vertical blank, queue frames (always supported)
+    #[default]
+    Fifo,
+    /// Like Fifo, but present late frames immediately (may tear)
+    FifoRelaxed,
+    /// Replace the queued frame with the newest one (no tearing, lowest vsync latency)
+    Mailbox,
+    /// Present immediately without waiting for vertical blank (may tear)
+    Immediate,
+}
+
+impl PresentMode {
+    /// Value understood by the renderer's `ICED_PRESENT_MODE` environment variable
+    ///
+    /// `vsync` and `no_vsync` select wgpu's automatic modes, which fall back
+    /// along the mode's chain when the surface lacks it.
+    pub fn env_value(&self) -> &'static str {
+        match self {
+            PresentMode::Fifo => "fifo",
+            PresentMode::FifoRelaxed => "vsync",
+            PresentMode::Mailbox => "mailbox",
+            PresentMode::Immediate => "no_vsync",
+        }
+    }
+
+    /// Parse an `ICED_PRESENT_MODE` value (including the renderer's vsync aliases)
+    pub fn from_env_value(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "fifo" => Some(PresentMode::Fifo),
+            "fifo_relaxed" | "vsync" => Some(PresentMode::FifoRelaxed),
+            "mailbox" => Some(PresentMode::Mailbox),
+            "immediate" | "no_vsync" => Some(PresentMode::Immediate),
+            _ => None,
+        }
+    }
+
+    /// Whether the surface may reject this mode instead of falling back
+    ///
+    /// Only Mailbox: Fifo is always supported, and the others are requested
+    /// as automatic modes the surface checks against its capabilities.
+    pub fn needs_confirmation(&self) -> bool {
+        *self == PresentMode::Mailbox
+    }
+
+    /// Modes to try, in order, when this one isn't supported
+    ///
+    /// Every chain ends with Fifo, which all surfaces support. Mailbox falls
+    /// back to Fifo rather than Immediate so choosing it never introduces
+    /// tearing. The chains of Fifo Relaxed and Immediate are those of wgpu's
+    /// automatic modes, which the surface follows on its own.
+    pub fn fallback_chain(&self) -> &'static [PresentMode] {
+        match self {
+            PresentMode::Fifo => &[PresentMode::Fifo],
+            PresentMode::FifoRelaxed => &[PresentMode::FifoRelaxed, PresentMode::Fifo],
+            PresentMode::Mailbox => &[PresentMode::Mailbox, PresentMode::Fifo],
+            PresentMode::Immediate => &[
+                PresentMode::Immediate,
+                PresentMode::Mailbox,
+                PresentMode::Fifo,
+            ],
+        }
+    }
+
+    /// Mode to request from the renderer
+    ///
+    /// Modes the surface checks itself are requested as they are; Mailbox
+    /// becomes the first mode of its chain not known to be unsupported.
+    pub fn resolve(&self, unsupported: &[PresentMode]) -> PresentMode {
+        if !self.needs_confirmation() {
+            return *self;
+        }
+        self.fallback_chain()
+            .iter()
+            .copied()
+            .find(|mode| *mode == PresentMode::Fifo || !unsupported.contains(mode))
+            .unwrap_or(PresentMode::Fifo)
+    }
+
+    fn pending_marker_dir() -> std::path::PathBuf {
+        dirs::runtime_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("camera")
+    }
+
+    /// Marker of the instance with `pid`, so instances never see each other's
+    fn pending_marker_path(dir: &std::path::Path, pid: u32) -> std::path::PathBuf {
+        dir.join(format!("{}{}", PRESENT_MODE_MARKER_PREFIX, pid))
+    }
+
+    /// Record that the renderer is about to try this mode
+    pub fn mark_pending(&self) {
+        let dir = Self::pending_marker_dir();
+        let _ = std::fs::create_dir_all(&dir);
+        let _ = std::fs::write(
+            Self::pending_marker_path(&dir, std::process::id()),
+            self.env_value(),
+        );
+    }
+
+    /// Whether this instance still has to confirm its present mode
+    pub fn is_pending() -> bool {
+        Self::pending_marker_path(&Self::pending_marker_dir(), std::process::id()).exists()
+    }
+
+    /// Mode of a previous launch that never confirmed its surface, if any
+    ///
+    /// Consumes the markers of instances that are gone; those of instances
+    /// still running are left alone, they may yet confirm.
+    pub fn take_failed() -> Option<Self> {
+        Self::take_failed_in(&Self::pending_marker_dir(), |pid| {
+            std::path::Path::new("/proc").join(pid.to_string()).exists()
+        })
+    }
+
+    fn take_failed_in(dir: &std::path::Path, running: impl Fn(u32) -> bool) -> Option<Self> {
+        let mut failed = None;
+        for entry in std::fs::read_dir(dir).ok()?.flatten() {
+            let Some(pid) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix(PRESENT_MODE_MARKER_PREFIX))
+                .and_then(|pid| pid.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            if pid == std::process::id() || running(pid) {
+                continue;
+            }
+            let value = std::fs::read_to_string(entry.path()).unwrap_or_default();
+            let _ = std::fs::remove_file(entry.path());
+            failed = failed.or_else(|| Self::from_env_value(value.trim()));
+        }
+        failed
+    }
+
+    /// Confirm that this instance's surface works with the pending mode
+    pub fn confirm_pending() {
+        let _ = std::fs::remove_file(Self::pending_marker_path(
+            &Self::pending_marker_dir(),
+            std::process::id(),
+        ));
+    }
+
+    /// Present mode requested from the renderer at launch (Fifo when not overridden)
+    ///
+    /// Read once: the renderer only looks at `ICED_PRESENT_MODE` when it
+    /// creates the surface, so later changes to the variable mean nothing.
+    pub fn launch_requested() -> Self {
+        static REQUESTED: std::sync::OnceLock<PresentMode> = std::sync::OnceLock::new();
+        *REQUESTED.get_or_init(|| {
+            std::env::var("ICED_PRESENT_MODE")
+                .ok()
+                .and_then(|value| Self::from_env_value(&value))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Whether presentation is synchronized to the display refresh
+    pub fn is_vsync(&self) -> bool {
+        matches!(self, PresentMode::Fifo | PresentMode::FifoRelaxed)
+    }
+
+    /// Get display name for this present mode
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PresentMode::Fifo => "Fifo",
+            PresentMode::FifoRelaxed => "Fifo (relaxed)",
+            PresentMode::Mailbox => "Mailbox",
+            PresentMode::Immediate => "Immediate",
+        }
+    }
+
+    /// Get all available present modes
+    pub const ALL: [PresentMode; 4] = [
+        PresentMode::Fifo,
+        PresentMode::FifoRelaxed,
+        PresentMode::Mailbox,
+        PresentMode::Immediate,
+    ];
+}
+
+/// Deinterlacing method for interlaced sources (capture cards)
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum DeinterlaceMethod {
+    /// Show raw fields (combing visible on motion)
+    Off,
+    /// Line doubling of each field (fast, halves vertical detail)
+    Bob,
+    /// Linear interpolation of each field
+    Linear,
+    /// Motion-adaptive deinterlacing (best quality, most CPU)
+    #[default]
+    Yadif,
+}
+
+impl DeinterlaceMethod {
+    /// Value for the GStreamer `deinterlace` element's `method` property
+    pub fn gst_method(&self) -> Option<&'static str> {
+        match self {
+            DeinterlaceMethod::Off => None,
+            DeinterlaceMethod::Bob => Some("scalerbob"),
+            DeinterlaceMethod::Linear => Some("linear"),
+            DeinterlaceMethod::Yadif => Some("yadif"),
+        }
+    }
+
+    /// Get display name for this method
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DeinterlaceMethod::Off => "Off",
+            DeinterlaceMethod::Bob => "Bob",
+            DeinterlaceMethod::Linear => "Linear",
+            DeinterlaceMethod::Yadif => "Yadif",
+        }
+    }
+
+    /// Get all available methods
+    pub const ALL: [DeinterlaceMethod; 4] = [
+        DeinterlaceMethod::Off,
+        DeinterlaceMethod::Bob,
+        DeinterlaceMethod::Linear,
+        DeinterlaceMethod::Yadif,
+    ];
+}
+
+/// How frames are captured from cameras with a V4L2 node
+///
+/// PipeWire is the normal path. The other choices open the node directly with
+/// `v4l2src` and force its `io-mode`, for debugging zero-copy capture.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum V4l2IoMode {
+    /// Capture through PipeWire
+    #[default]
+    PipeWire,
+    /// v4l2src picks the io-mode itself
+    Auto,
+    /// Driver buffers mapped into memory
+    Mmap,
+    /// Buffers allocated by the app
+    Userptr,
+    /// Driver buffers exported as DMABUF
+    Dmabuf,
+    /// DMABUF buffers allocated downstream
+    DmabufImport,
+}
+
+impl V4l2IoMode {
+    /// Value for the `v4l2src` element's `io-mode` property (None = PipeWire)
+    pub fn gst_io_mode(&self) -> Option<&'static str> {
+        match self {
+            V4l2IoMode::PipeWire => None,
+            V4l2IoMode::Auto => Some("auto"),
+            V4l2IoMode::Mmap => Some("mmap"),
+            V4l2IoMode::Userptr => Some("userptr"),
+            V4l2IoMode::Dmabuf => Some("dmabuf"),
+            V4l2IoMode::DmabufImport => Some("dmabuf-import"),
+        }
+    }
+
+    /// Get display name for this mode
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            V4l2IoMode::PipeWire => "PipeWire",
+            V4l2IoMode::Auto => "V4L2 (auto)",
+            V4l2IoMode::Mmap => "V4L2 mmap",
+            V4l2IoMode::Userptr => "V4L2 userptr",
+            V4l2IoMode::Dmabuf => "V4L2 DMABUF",
+            V4l2IoMode::DmabufImport => "V4L2 DMABUF import",
+        }
+    }
+
+    /// Get all available modes
+    pub const ALL: [V4l2IoMode; 6] = [
+        V4l2IoMode::PipeWire,
+        V4l2IoMode::Auto,
+        V4l2IoMode::Mmap,
+        V4l2IoMode::Userptr,
+        V4l2IoMode::Dmabuf,
+        V4l2IoMode::DmabufImport,
+    ];
+}
+
+/// Whether decoding and GPU work favor battery life or speed
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PowerPreference {
+    /// Hardware decoders first and the fastest GPU
+    #[default]
+    HighPerformance,
+    /// Software decoders first on CPUs with efficiency cores, and the
+    /// integrated GPU
+    LowPower,
+}
+
+impl PowerPreference {
+    /// Whether the low-power variant is selected
+    pub fn is_low_power(&self) -> bool {
+        *self == PowerPreference::LowPower
+    }
+
+    /// Get display name for this preference
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PowerPreference::HighPerformance => "High performance",
+            PowerPreference::LowPower => "Low power",
+        }
+    }
+
+    /// Get all available preferences
+    pub const ALL: [PowerPreference; 2] =
+        [PowerPreference::HighPerformance, PowerPreference::LowPower];
+}
+
+/// Upper bound on the preview resolution (trades sharpness for GPU/CPU time)
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum PreviewResolutionCap {
+    /// Preview at the camera's native resolution
+    #[default]
+    Off,
+    /// At most 480 lines
+    P480,
+    /// At most 720 lines
+    P720,
+    /// At most 1080 lines
+    P1080,
+}
+
+impl PreviewResolutionCap {
+    /// Maximum preview height in pixels (`None` = uncapped)
+    pub fn max_height(&self) -> Option<u32> {
+        match self {
+            PreviewResolutionCap::Off => None,
+            PreviewResolutionCap::P480 => Some(480),
+            PreviewResolutionCap::P720 => Some(720),
+            PreviewResolutionCap::P1080 => Some(1080),
+        }
+    }
+
+    /// Get display name for this cap
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            PreviewResolutionCap::Off => "Off",
+            PreviewResolutionCap::P480 => "480p",
+            PreviewResolutionCap::P720 => "720p",
+            PreviewResolutionCap::P1080 => "1080p",
+        }
+    }
+
+    /// Get all available caps
+    pub const ALL: [PreviewResolutionCap; 4] = [
+        PreviewResolutionCap::Off,
+        PreviewResolutionCap::P480,
+        PreviewResolutionCap::P720,
+        PreviewResolutionCap::P1080,
+    ];
+}
+
+/// What to do when the scene stays dark at the current frame rate
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum LowLightMode {
+    /// Keep the selected mode
+    #[default]
+    Off,
+    /// Offer a lower frame rate, switch only when the user accepts
+    Suggest,
+    /// Switch to a lower frame rate and back on its own
+    Switch,
+}
+
+impl LowLightMode {
+    /// Get display name for this mode
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LowLightMode::Off => "Off",
+            LowLightMode::Suggest => "Suggest",
+            LowLightMode::Switch => "Switch automatically",
+        }
+    }
+
+    /// Get all available modes
+    pub const ALL: [LowLightMode; 3] = [
+        LowLightMode::Off,
+        LowLightMode::Suggest,
+        LowLightMode::Switch,
+    ];
+}
+
+/// What the preview does while the window is in the background
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BackgroundPreview {
+    /// Keep the preview running at full rate
+    #[default]
+    KeepRunning,
+    /// Let only a few frames per second through to the preview
+    Throttle,
+    /// Stop the camera until the window is focused again
+    Pause,
+}
+
+impl BackgroundPreview {
+    /// Get display name for this behavior
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BackgroundPreview::KeepRunning => "Keep running",
+            BackgroundPreview::Throttle => "Lower frame rate",
+            BackgroundPreview::Pause => "Pause",
+        }
+    }
+
+    /// Get all available behaviors
+    pub const ALL: [BackgroundPreview; 3] = [
+        BackgroundPreview::KeepRunning,
+        BackgroundPreview::Throttle,
+        BackgroundPreview::Pause,
+    ];
+}
+
+/// When the screen is kept from blanking and the system from suspending
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum KeepAwake {
+    /// While previewing, recording or streaming
+    Always,
+    /// Only while a video is being recorded
+    #[default]
+    WhileRecording,
+    /// Leave idle handling to the desktop
+    Never,
+}
+
+impl KeepAwake {
+    /// Get display name for this behavior
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            KeepAwake::Always => "Always",
+            KeepAwake::WhileRecording => "Only while recording",
+            KeepAwake::Never => "Never",
+        }
+    }
+
+    /// Get all available behaviors
+    pub const ALL: [KeepAwake; 3] = [
+        KeepAwake::Always,
+        KeepAwake::WhileRecording,
+        KeepAwake::Never,
+    ];
+}
+
+/// Preview rotation chosen by the user instead of the detected one
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RotationOverride {
+    /// Use the detected sensor rotation and follow the device orientation
+    #[default]
+    Auto,
+    /// Never rotate
+    Rotate0,
+    /// 90 degrees clockwise
+    Rotate90,
+    /// 180 degrees
+    Rotate180,
+    /// 270 degrees clockwise
+    Rotate270,
+}
+
+impl RotationOverride {
+    /// Rotation pinned by the override (`None` = automatic)
+    pub fn rotation(&self) -> Option<crate::backends::camera::types::SensorRotation> {
+        use crate::backends::camera::types::SensorRotation;
+        match self {
+            RotationOverride::Auto => None,
+            RotationOverride::Rotate0 => Some(SensorRotation::None),
+            RotationOverride::Rotate90 => Some(SensorRotation::Rotate90),
+            RotationOverride::Rotate180 => Some(SensorRotation::Rotate180),
+            RotationOverride::Rotate270 => Some(SensorRotation::Rotate270),
+        }
+    }
+
+    /// Get display name for this override
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            RotationOverride::Auto => "Automatic",
+            RotationOverride::Rotate0 => "0°",
+            RotationOverride::Rotate90 => "90°",
+            RotationOverride::Rotate180 => "180°",
+            RotationOverride::Rotate270 => "270°",
+        }
+    }
+
+    /// Get all available overrides
+    pub const ALL: [RotationOverride; 5] = [
+        RotationOverride::Auto,
+        RotationOverride::Rotate0,
+        RotationOverride::Rotate90,
+        RotationOverride::Rotate180,
+        RotationOverride::Rotate270,
+    ];
+}
+
+/// Full-frame color effect applied to the preview on top of the selected filter
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ColorEffect {
+    /// No effect (the preview shader skips the effect stage entirely)
+    #[default]
+    None,
+    /// Luminance only
+    Grayscale,
+    /// Warm brown tint
+    Sepia,
+    /// Inverted colors
+    Negative,
+    /// Steep contrast curve for low-vision use
+    HighContrast,
+}
+
+impl ColorEffect {
+    /// Effect code passed to the preview shader (must match `apply_color_effect` in filters.wgsl)
+    pub fn gpu_code(&self) -> u32 {
+        match self {
+            ColorEffect::None => 0,
+            ColorEffect::Grayscale => 1,
+            ColorEffect::Sepia => 2,
+            ColorEffect::Negative => 3,
+            ColorEffect::HighContrast => 4,
+        }
+    }
+
+    /// Apply the effect to one RGB pixel (CPU path for captured photos)
+    pub fn apply_rgb(&self, [r, g, b]: [u8; 3]) -> [u8; 3] {
+        let luma = || (0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b)) / 255.0;
+        let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        match self {
+            ColorEffect::None => [r, g, b],
+            ColorEffect::Grayscale => {
+                let y = to_u8(luma());
+                [y, y, y]
+            }
+            ColorEffect::Sepia => {
+                let y = luma();
+                [to_u8(y * 1.07), to_u8(y * 0.74), to_u8(y * 0.43)]
+            }
+            ColorEffect::Negative => [255 - r, 255 - g, 255 - b],
+            ColorEffect::HighContrast => {
+                let stretch = |c: u8| to_u8((f32::from(c) / 255.0 - 0.5) * 3.0 + 0.5);
+                [stretch(r), stretch(g), stretch(b)]
+            }
+        }
+    }
+
+    /// Get display name for this effect
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ColorEffect::None => "None",
+            ColorEffect::Grayscale => "Grayscale",
+            ColorEffect::Sepia => "Sepia",
+            ColorEffect::Negative => "Negative",
+            ColorEffect::HighContrast => "High contrast",
+        }
+    }
+
+    /// Get all available effects
+    pub const ALL: [ColorEffect; 5] = [
+        ColorEffect::None,
+        ColorEffect::Grayscale,
+        ColorEffect::Sepia,
+        ColorEffect::Negative,
+        ColorEffect::HighContrast,
+    ];
+}
+
+/// Filter used when the preview is scaled to the window (fit, downscale cap, zoom)
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ScalingQuality {
+    /// Nearest texel, for pixel-exact inspection
+    Nearest,
+    /// Bilinear filtering by the sampler
+    #[default]
+    Linear,
+    /// Catmull-Rom filtering in a separate shader, sharper when upscaling
+    Bicubic,
+}
+
+impl ScalingQuality {
+    /// Get display name for this filter
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ScalingQuality::Nearest => "Nearest",
+            ScalingQuality::Linear => "Linear",
+            ScalingQuality::Bicubic => "Bicubic",
+        }
+    }
+
+    /// Get all available filters
+    pub const ALL: [ScalingQuality; 3] = [
+        ScalingQuality::Nearest,
+        ScalingQuality::Linear,
+        ScalingQuality::Bicubic,
+    ];
+}
+
+/// Corner of the frame the watermark is drawn in
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WatermarkPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl WatermarkPosition {
+    /// Get display name for this position
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            WatermarkPosition::TopLeft => "Top left",
+            WatermarkPosition::TopRight => "Top right",
+            WatermarkPosition::BottomLeft => "Bottom left",
+            WatermarkPosition::BottomRight => "Bottom right",
+        }
+    }
+
+    /// Get all available positions
+    pub const ALL: [WatermarkPosition; 4] = [
+        WatermarkPosition::TopLeft,
+        WatermarkPosition::TopRight,
+        WatermarkPosition::BottomLeft,
+        WatermarkPosition::BottomRight,
+    ];
+}
+
+/// Text burned into saved photos and recordings (see `pipelines::watermark`)
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WatermarkSettings {
+    /// Burn the watermark into captures
+    pub enabled: bool,
+    /// Fixed text (may be empty when only the timestamp is wanted)
+    pub text: String,
+    /// Add the date and time (of the capture for photos, of each frame for recordings)
+    pub timestamp: bool,
+    /// Corner the text is drawn in
+    pub position: WatermarkPosition,
+    /// Text height in percent of the frame height
+    pub font_size_percent: u32,
+    /// Text opacity in percent
+    pub opacity_percent: u32,
+}
+
+impl Default for WatermarkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false, // Captures stay untouched unless the user opts in
+            text: String::new(),
+            timestamp: true,
+            position: WatermarkPosition::default(),
+            font_size_percent: 4,
+            opacity_percent: 80,
+        }
+    }
+}
+
+/// Core action that can be triggered from the keyboard
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum ShortcutAction {
+    /// Capture a photo
+    Snapshot,
+    /// Freeze or resume the live preview
+    ToggleFreeze,
+    /// Take a still burst
+    CaptureBurst,
+    /// Open or close the insights drawer
+    ToggleInsights,
+    /// Start or stop recording
+    ToggleRecording,
+    /// Cycle through the preview overlays
+    CycleOverlay,
+    /// Step through the camera's resolutions
+    CycleResolution,
+    /// Copy the current frame to the clipboard
+    CopyFrame,
+    /// Shorten the manual exposure time by one step
+    ExposureDown,
+    /// Lengthen the manual exposure time by one step
+    ExposureUp,
+    /// Lower the manual gain by one step
+    GainDown,
+    /// Raise the manual gain by one step
+    GainUp,
+}
+
+impl ShortcutAction {
+    /// Key used when the action hasn't been rebound
+    pub fn default_key(&self) -> &'static str {
+        match self {
+            ShortcutAction::Snapshot => "Space",
+            ShortcutAction::CaptureBurst => "B",
+            ShortcutAction::ToggleFreeze => "F",
+            ShortcutAction::ToggleInsights => "I",
+            ShortcutAction::ToggleRecording => "R",
+            ShortcutAction::CycleOverlay => "O",
+            ShortcutAction::CycleResolution => "C",
+            ShortcutAction::CopyFrame => "Y",
+            ShortcutAction::ExposureDown => "[",
+            ShortcutAction::ExposureUp => "]",
+            ShortcutAction::GainDown => "-",
+            ShortcutAction::GainUp => "=",
+        }
+    }
+
+    /// Get display name for this action
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ShortcutAction::Snapshot => "Take photo",
+            ShortcutAction::CaptureBurst => "Take photo burst",
+            ShortcutAction::ToggleFreeze => "Freeze preview",
+            ShortcutAction::ToggleInsights => "Toggle insights",
+            ShortcutAction::ToggleRecording => "Start/stop recording",
+            ShortcutAction::CycleOverlay => "Cycle overlay",
+            ShortcutAction::CycleResolution => "Cycle resolution",
+            ShortcutAction::CopyFrame => "Copy frame to clipboard",
+            ShortcutAction::ExposureDown => "Decrease exposure",
+            ShortcutAction::ExposureUp => "Increase exposure",
+            ShortcutAction::GainDown => "Decrease gain",
+            ShortcutAction::GainUp => "Increase gain",
+        }
+    }
+
+    /// Get all actions
+    pub const ALL: [ShortcutAction; 12] = [
+        ShortcutAction::Snapshot,
+        ShortcutAction::CaptureBurst,
+        ShortcutAction::ToggleFreeze,
+        ShortcutAction::ToggleInsights,
+        ShortcutAction::ToggleRecording,
+        ShortcutAction::CycleOverlay,
+        ShortcutAction::CycleResolution,
+        ShortcutAction::CopyFrame,
+        ShortcutAction::ExposureDown,
+        ShortcutAction::ExposureUp,
+        ShortcutAction::GainDown,
+        ShortcutAction::GainUp,
+    ];
+}
+
+/// Smoothing applied to the live metrics shown in the insights drawer
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum MetricsSmoothing {
+    /// Show each sample as measured
+    #[default]
+    Off,
+    /// Follows changes within a couple of updates
+    Light,
+    /// Settles over roughly five updates
+    Medium,
+    /// Settles over roughly twenty updates
+    Heavy,
+}
+
+impl MetricsSmoothing {
+    /// Weight given to each new sample (`None` = no smoothing)
+    pub fn alpha(&self) -> Option<f64> {
+        match self {
+            MetricsSmoothing::Off => None,
+            MetricsSmoothing::Light => Some(0.5),
+            MetricsSmoothing::Medium => Some(0.2),
+            MetricsSmoothing::Heavy => Some(0.05),
+        }
+    }
+
+    /// Get display name for this smoothing level
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            MetricsSmoothing::Off => "Off",
+            MetricsSmoothing::Light => "Light",
+            MetricsSmoothing::Medium => "Medium",
+            MetricsSmoothing::Heavy => "Heavy",
+        }
+    }
+
+    /// Get all available smoothing levels
+    pub const ALL: [MetricsSmoothing; 4] = [
+        MetricsSmoothing::Off,
+        MetricsSmoothing::Light,
+        MetricsSmoothing::Medium,
+        MetricsSmoothing::Heavy,
+    ];
+}
+
 /// Application theme preference
 #[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum AppTheme {
@@ -162,6 +972,34 @@ pub struct FormatSettings {
 /// Backwards compatibility alias
 pub type VideoSettings = FormatSettings;
 
+/// Named bundle of format and preview settings (see `app::profiles`)
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CaptureProfile {
+    /// Format to switch to (None = keep the current format)
+    pub format: Option<FormatSettings>,
+    /// Preview rotation
+    pub rotation_override: RotationOverride,
+    /// Mirror the preview of cameras that face the user
+    pub mirror_preview: bool,
+    /// Zoom in percent (100 = no zoom)
+    pub zoom_percent: u32,
+    /// Preview filter
+    pub filter: crate::app::FilterType,
+    /// Color effect applied on top of the filter
+    pub color_effect: ColorEffect,
+    /// QR code detection overlay
+    pub qr_detection: bool,
+    /// False-color rendering of grayscale formats
+    pub false_color: bool,
+}
+
+impl CaptureProfile {
+    /// Zoom level of the profile (1.0 = no zoom)
+    pub fn zoom_level(&self) -> f32 {
+        (self.zoom_percent as f32 / 100.0).clamp(1.0, 10.0)
+    }
+}
+
 #[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq, Serialize, Deserialize)]
 #[version = 12]
 pub struct Config {
@@ -169,34 +1007,148 @@ pub struct Config {
     pub app_theme: AppTheme,
     /// Folder name for saving captures (photos go to XDG Pictures, videos go to XDG Videos)
     pub save_folder_name: String,
+    /// Directory for all captures, overriding the Pictures/Videos save folders (None = default)
+    pub output_directory: Option<String>,
+    /// Filename template for captures (see `pipelines::filename` for the tokens)
+    pub filename_template: String,
     /// Last used camera device path
     pub last_camera_path: Option<String>,
     /// Video mode settings per camera (key = camera device path)
     pub video_settings: HashMap<String, FormatSettings>,
     /// Photo mode settings per camera (key = camera device path)
     pub photo_settings: HashMap<String, FormatSettings>,
+    /// Last mode that delivered frames per camera (key = camera device path)
+    pub last_good_formats: HashMap<String, FormatSettings>,
     /// Camera backend to use (PipeWire or V4L2)
     pub backend: crate::backends::camera::CameraBackendType,
     /// Last selected video encoder index
     pub last_video_encoder_index: Option<usize>,
     /// Bug report submission URL (GitHub issues URL)
     pub bug_report_url: String,
-    /// Mirror camera preview horizontally (selfie mode)
+    /// Mirror the preview of cameras that face the user (selfie mode; rear cameras never are)
     pub mirror_preview: bool,
     /// Video encoder bitrate preset (Low, Medium, High)
     pub bitrate_preset: BitratePreset,
     /// Virtual camera feature enabled (disabled by default)
     pub virtual_camera_enabled: bool,
-    /// Photo output format (JPEG, PNG, or DNG)
+    /// Photo output format (JPEG, PNG, WebP, or DNG)
     pub photo_output_format: PhotoOutputFormat,
+    /// JPEG quality for still captures (1-100)
+    pub photo_jpeg_quality: u8,
     /// Save raw burst frames as DNG files (for debugging burst mode pipeline)
     pub save_burst_raw: bool,
     /// Burst mode setting (Off, Auto, or fixed frame count)
     pub burst_mode_setting: BurstModeSetting,
+    /// Photos a still burst takes per trigger
+    pub still_burst_count: u32,
+    /// Time between two photos of a still burst in milliseconds
+    pub still_burst_interval_ms: u64,
     /// Record audio with video
     pub record_audio: bool,
     /// Audio encoder preference (Opus or AAC)
     pub audio_encoder: AudioEncoder,
+    /// Preview surface present mode (applied on next launch)
+    pub present_mode: PresentMode,
+    /// Present modes that failed to start on this display (skipped by the fallback chain)
+    pub unsupported_present_modes: Vec<PresentMode>,
+    /// Embed capture details (device, format, decoder) into saved JPEG/PNG stills
+    pub embed_capture_metadata: bool,
+    /// Save a still of the first frame at launch and after a camera or format change
+    pub first_frame_snapshot: bool,
+    /// Instantiate every registered decoder at startup to detect broken plugins
+    pub validate_decoders_at_startup: bool,
+    /// Decoder that last delivered frames per codec, tried first on the next start
+    pub decoder_cache: crate::media::decoders::DecoderCache,
+    /// Stretch Y16 (16-bit grayscale) previews to each frame's min/max range
+    pub gray16_auto_stretch: bool,
+    /// Render grayscale (GREY/Y16) previews through a false-color palette
+    pub false_color_lut: bool,
+    /// Which frames to discard when the preview can't keep up with the camera
+    pub frame_drop_policy: crate::backends::camera::FrameDropPolicy,
+    /// Frames the preview appsink holds before dropping or blocking (0 = automatic)
+    pub appsink_max_buffers: u32,
+    /// Discard the appsink's oldest frame when it is full instead of blocking the camera
+    pub appsink_drop: bool,
+    /// Skip the GPU upload of preview frames identical to the previous one
+    pub skip_identical_frames: bool,
+    /// Highlight in-focus edges in the preview (focus peaking)
+    pub focus_peaking: bool,
+    /// Draw the frame sequence number and PTS on the preview (for sync debugging)
+    pub frame_counter_overlay: bool,
+    /// Accumulate min/avg/max of latency, decode and upload time per session
+    pub session_stats: bool,
+    /// Anti-flicker power line frequency applied to cameras that offer it
+    /// (None = guess from the locale's region)
+    pub power_line_frequency: Option<crate::app::exposure_picker::PowerLineFrequency>,
+    /// Seconds of recent frames kept for retroactive clips (0 = disabled)
+    pub retro_buffer_seconds: u32,
+    /// Memory cap for the retroactive clip buffer in MiB
+    pub retro_buffer_memory_mb: u32,
+    /// Start a new recording segment after this many minutes (0 = no limit)
+    pub recording_split_minutes: u32,
+    /// Start a new recording segment once a file reaches this many MB (0 = no limit)
+    pub recording_split_mb: u32,
+    /// Deinterlacing method applied to interlaced sources
+    pub deinterlace_method: DeinterlaceMethod,
+    /// Thread cap for software H.264/H.265 decoders (None = automatic)
+    pub decode_threads: Option<u32>,
+    /// DRM render node used for VA-API decoding (None = VA-API default)
+    pub vaapi_render_node: Option<String>,
+    /// Capture path and V4L2 io-mode for cameras with a V4L2 node
+    pub v4l2_io_mode: V4l2IoMode,
+    /// Decoder order and GPU adapter preference
+    pub power_preference: PowerPreference,
+    /// Maximum preview resolution outside photo mode
+    pub preview_resolution_cap: PreviewResolutionCap,
+    /// Lower the frame rate when the scene stays dark, for longer exposures
+    pub low_light_mode: LowLightMode,
+    /// Save power by throttling or pausing the preview while the window is unfocused
+    pub background_preview: BackgroundPreview,
+    /// Keep the screen awake while the camera is in use
+    pub keep_awake: KeepAwake,
+    /// Show and record at most this many frames per second (0 = the camera's rate)
+    pub framerate_cap_fps: u32,
+    /// Listen on a local Unix socket for automation commands
+    pub control_socket_enabled: bool,
+    /// Serve the processed preview as an MJPEG stream over HTTP
+    pub http_stream_enabled: bool,
+    /// Port the MJPEG stream listens on
+    pub http_stream_port: u16,
+    /// Accept MJPEG viewers from other devices, not just this computer
+    pub http_stream_all_interfaces: bool,
+    /// Most MJPEG viewers served at once; more are turned away
+    pub http_stream_max_clients: usize,
+    /// Include device paths and serials in diagnostics bundles
+    pub diagnostics_include_device_ids: bool,
+    /// Smoothing of the live metrics shown in the insights drawer
+    pub metrics_smoothing: MetricsSmoothing,
+    /// Manual preview rotation (Auto = detected sensor rotation + device orientation)
+    pub rotation_override: RotationOverride,
+    /// Seconds without frames before the pipeline is restarted (0 = disabled)
+    pub stall_watchdog_secs: u32,
+    /// Seconds to wait for the first frame before giving up on opening a camera
+    pub camera_open_timeout_secs: u32,
+    /// Color effect applied to the preview
+    pub color_effect: ColorEffect,
+    /// Also apply the color effect to captured photos
+    pub color_effect_on_output: bool,
+    /// Filter used to scale the preview
+    pub scaling_quality: ScalingQuality,
+    /// Rebound keyboard shortcuts (actions not listed use their default key)
+    pub keyboard_shortcuts: HashMap<ShortcutAction, String>,
+    /// Steps an exposure or gain shortcut moves while Shift is held
+    pub nudge_coarse_steps: u32,
+    /// User-set camera facing by camera path (cameras not listed use the detected facing)
+    pub camera_facing_overrides: HashMap<String, crate::backends::camera::types::CameraFacing>,
+    /// Camera paths whose MJPEG is decoded with error resilience (damaged frames are dropped)
+    pub mjpeg_resilience_cameras: HashSet<String>,
+    /// Capture profiles by name
+    pub capture_profiles: BTreeMap<String, CaptureProfile>,
+    /// Stack chosen for cameras offered through both libcamera and V4L2, by
+    /// V4L2 camera path (cameras not listed use the preferred stack)
+    pub camera_source_overrides: HashMap<String, crate::backends::camera::types::CameraSourceKind>,
+    /// Text burned into saved photos and recordings (never shown in the preview)
+    pub watermark: WatermarkSettings,
 }
 
 impl Default for Config {
@@ -204,9 +1156,12 @@ impl Default for Config {
         Self {
             app_theme: AppTheme::default(), // Default to System theme
             save_folder_name: "Camera".to_string(),
+            output_directory: None, // Save folders in Pictures and Videos
+            filename_template: crate::pipelines::filename::DEFAULT_TEMPLATE.to_string(),
             last_camera_path: None,
             video_settings: HashMap::new(),
             photo_settings: HashMap::new(),
+            last_good_formats: HashMap::new(), // Learned when a mode delivers frames
             backend: crate::backends::camera::CameraBackendType::default(),
             last_video_encoder_index: None,
             bug_report_url:
@@ -216,10 +1171,228 @@ impl Default for Config {
             bitrate_preset: BitratePreset::default(), // Default to Medium
             virtual_camera_enabled: false, // Disabled by default
             photo_output_format: PhotoOutputFormat::default(), // Default to JPEG
+            photo_jpeg_quality: 92, // Matches the previous fixed "High" quality
             save_burst_raw: false, // Disabled by default (debugging feature)
             burst_mode_setting: BurstModeSetting::default(), // Default to Auto
-            record_audio: true,   // Enable audio recording by default
+            still_burst_count: crate::constants::still_burst::DEFAULT_COUNT,
+            still_burst_interval_ms: crate::constants::still_burst::DEFAULT_INTERVAL_MS,
+            record_audio: true, // Enable audio recording by default
             audio_encoder: AudioEncoder::default(), // Default to Opus
+            present_mode: PresentMode::default(), // Default to Fifo (vsync)
+            unsupported_present_modes: Vec::new(), // Learned when a mode fails to start
+            embed_capture_metadata: true, // Self-documenting stills; can be disabled for privacy
+            first_frame_snapshot: false, // Monitoring aid, off by default
+            validate_decoders_at_startup: false, // Off by default (adds startup time)
+            decoder_cache: crate::media::decoders::DecoderCache::default(), // Filled by the first working pipeline
+            gray16_auto_stretch: true, // Dim thermal/IR frames are unreadable without it
+            false_color_lut: false,    // Plain grayscale by default
+            frame_drop_policy: crate::backends::camera::FrameDropPolicy::default(), // Default to SkipToLatest (lowest latency)
+            appsink_max_buffers: 0, // Picked from the frame rate and device quirks
+            appsink_drop: true,     // Lowest latency
+            skip_identical_frames: false, // Off by default (costs a sampled compare per frame)
+            focus_peaking: false,   // Plain preview by default
+            frame_counter_overlay: false, // Debug aid, off by default
+            session_stats: false,   // Costs a few atomic loads per frame
+            power_line_frequency: None, // Mains frequency of the locale's region
+            retro_buffer_seconds: 0, // Disabled by default (costs memory)
+            retro_buffer_memory_mb: crate::constants::retro_buffer::DEFAULT_MEMORY_CAP_MB,
+            recording_split_minutes: 0, // One file per recording
+            recording_split_mb: 0,      // One file per recording
+            deinterlace_method: DeinterlaceMethod::default(), // Default to Yadif (only acts on interlaced caps)
+            decode_threads: None,                             // One thread per CPU
+            vaapi_render_node: None, // Let VA-API pick the first render node
+            v4l2_io_mode: V4l2IoMode::default(), // Capture through PipeWire
+            power_preference: PowerPreference::default(), // Hardware decoders and the fastest GPU
+            preview_resolution_cap: PreviewResolutionCap::default(), // Native resolution by default
+            low_light_mode: LowLightMode::default(), // Keep the selected frame rate
+            background_preview: BackgroundPreview::default(), // Preview keeps running unfocused
+            keep_awake: KeepAwake::default(), // Recordings aren't interrupted by blanking
+            framerate_cap_fps: 0,    // Frames arrive at the camera's rate
+            control_socket_enabled: false, // Off unless the user opts in
+            http_stream_enabled: false, // Off unless the user opts in
+            http_stream_port: crate::constants::http_stream::DEFAULT_PORT,
+            http_stream_all_interfaces: false, // Only this computer can watch
+            http_stream_max_clients: crate::constants::http_stream::DEFAULT_MAX_CLIENTS,
+            diagnostics_include_device_ids: false, // Redact device identifiers by default
+            metrics_smoothing: MetricsSmoothing::default(), // Raw values by default
+            rotation_override: RotationOverride::default(), // Follow the detected rotation
+            stall_watchdog_secs: crate::constants::stall_watchdog::DEFAULT_THRESHOLD_SECS,
+            camera_open_timeout_secs: crate::constants::camera_open::DEFAULT_TIMEOUT_SECS,
+            color_effect: ColorEffect::default(), // No effect by default
+            color_effect_on_output: false, // Effects are a viewing aid; captures stay untouched
+            scaling_quality: ScalingQuality::default(), // Bilinear, the cheapest smooth filter
+            keyboard_shortcuts: HashMap::new(), // Default keys for every action
+            nudge_coarse_steps: crate::constants::exposure_nudge::DEFAULT_COARSE_STEPS,
+            camera_facing_overrides: HashMap::new(), // Trust the detected facing
+            mjpeg_resilience_cameras: HashSet::new(), // Show every frame as decoded
+            capture_profiles: BTreeMap::new(),       // Created by the user
+            camera_source_overrides: HashMap::new(), // Preferred stack per camera
+            watermark: WatermarkSettings::default(), // Disabled by default
         }
     }
 }
+
+impl Config {
+    /// Defaults for every setting, keeping what isn't a preference
+    ///
+    /// Capture profiles are kept because the user created them, the last
+    /// camera because it's where the app was, and present modes the GPU
+    /// failed with because they'd fail again. Photos and recordings on disk
+    /// are never touched.
+    pub fn reset(&self) -> Self {
+        Self {
+            last_camera_path: self.last_camera_path.clone(),
+            unsupported_present_modes: self.unsupported_present_modes.clone(),
+            last_good_formats: self.last_good_formats.clone(),
+            capture_profiles: self.capture_profiles.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_present_mode_markers_are_per_instance() {
+        let dir = std::env::temp_dir().join(format!("camera-present-mode-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let marker = |pid: u32, mode: PresentMode| {
+            std::fs::write(
+                PresentMode::pending_marker_path(&dir, pid),
+                mode.env_value(),
+            )
+            .unwrap();
+        };
+        marker(u32::MAX - 1, PresentMode::Mailbox);
+        marker(u32::MAX - 2, PresentMode::Immediate);
+
+        // A running instance may still confirm; only the crashed one failed
+        let running = |pid: u32| pid == u32::MAX - 2;
+        assert_eq!(
+            PresentMode::take_failed_in(&dir, running),
+            Some(PresentMode::Mailbox)
+        );
+        assert_eq!(PresentMode::take_failed_in(&dir, running), None);
+        assert!(PresentMode::pending_marker_path(&dir, u32::MAX - 2).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_present_mode_fallback_picks_supported_mode() {
+        assert_eq!(PresentMode::Mailbox.resolve(&[]), PresentMode::Mailbox);
+        assert_eq!(
+            PresentMode::Mailbox.resolve(&[PresentMode::Mailbox]),
+            PresentMode::Fifo
+        );
+        // The surface checks these itself, so stale records don't matter
+        assert_eq!(
+            PresentMode::Immediate.resolve(&[PresentMode::Immediate, PresentMode::Mailbox]),
+            PresentMode::Immediate
+        );
+        assert_eq!(
+            PresentMode::FifoRelaxed.resolve(&[PresentMode::FifoRelaxed]),
+            PresentMode::FifoRelaxed
+        );
+        // Fifo is always supported, even if it was somehow recorded as failing
+        assert_eq!(
+            PresentMode::Fifo.resolve(&[PresentMode::Fifo]),
+            PresentMode::Fifo
+        );
+    }
+
+    #[test]
+    fn test_reset_keeps_only_user_data() {
+        let mut config = Config {
+            last_camera_path: Some("/dev/video2".to_string()),
+            rotation_override: RotationOverride::Rotate90,
+            color_effect: ColorEffect::Negative,
+            mirror_preview: false,
+            vaapi_render_node: Some("/dev/dri/renderD129".to_string()),
+            deinterlace_method: DeinterlaceMethod::Bob,
+            v4l2_io_mode: V4l2IoMode::Dmabuf,
+            decode_threads: Some(2),
+            power_preference: PowerPreference::LowPower,
+            ..Config::default()
+        };
+        config.capture_profiles.insert(
+            "Night".to_string(),
+            CaptureProfile {
+                format: None,
+                rotation_override: RotationOverride::Rotate180,
+                mirror_preview: false,
+                zoom_percent: 200,
+                filter: crate::app::FilterType::Standard,
+                color_effect: ColorEffect::Grayscale,
+                qr_detection: false,
+                false_color: false,
+            },
+        );
+        config
+            .keyboard_shortcuts
+            .insert(ShortcutAction::Snapshot, "P".to_string());
+
+        let reset = config.reset();
+        // Transforms are back to identity and decoders to the built-in order
+        assert_eq!(reset.rotation_override, RotationOverride::Auto);
+        assert_eq!(reset.color_effect, ColorEffect::None);
+        assert!(reset.mirror_preview);
+        assert_eq!(reset.vaapi_render_node, None);
+        assert_eq!(reset.decode_threads, None);
+        assert_eq!(reset.power_preference, PowerPreference::HighPerformance);
+        assert_eq!(reset.deinterlace_method, DeinterlaceMethod::default());
+        assert_eq!(reset.v4l2_io_mode, V4l2IoMode::PipeWire);
+        assert!(reset.keyboard_shortcuts.is_empty());
+        // User data survives
+        assert_eq!(reset.last_camera_path, config.last_camera_path);
+        assert_eq!(reset.capture_profiles, config.capture_profiles);
+        assert_eq!(
+            reset,
+            Config {
+                last_camera_path: config.last_camera_path.clone(),
+                capture_profiles: config.capture_profiles.clone(),
+                ..Config::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_present_mode_env_values_round_trip() {
+        for mode in PresentMode::ALL {
+            assert_eq!(PresentMode::from_env_value(mode.env_value()), Some(mode));
+        }
+        // Only Mailbox is requested without a fallback of its own
+        assert_eq!(PresentMode::FifoRelaxed.env_value(), "vsync");
+        assert_eq!(PresentMode::Immediate.env_value(), "no_vsync");
+        assert!(PresentMode::Mailbox.needs_confirmation());
+        assert!(!PresentMode::Immediate.needs_confirmation());
+    }
+
+    #[test]
+    fn test_fallback_chains_end_with_fifo() {
+        for mode in PresentMode::ALL {
+            let chain = mode.fallback_chain();
+            assert_eq!(chain.first(), Some(&mode));
+            assert_eq!(chain.last(), Some(&PresentMode::Fifo));
+        }
+    }
+
+    #[test]
+    fn test_color_effects_on_pixels() {
+        let pixel = [200, 100, 50];
+        assert_eq!(ColorEffect::None.apply_rgb(pixel), pixel);
+        assert_eq!(ColorEffect::Negative.apply_rgb(pixel), [55, 155, 205]);
+        let [r, g, b] = ColorEffect::Grayscale.apply_rgb(pixel);
+        assert!(r == g && g == b);
+        assert_eq!(
+            ColorEffect::HighContrast.apply_rgb([0, 128, 255]),
+            [0, 129, 255]
+        );
+        assert_eq!(
+            ColorEffect::HighContrast.apply_rgb([64, 160, 200]),
+            [0, 225, 255]
+        );
+    }
+}
@@ -241,6 +241,12 @@ pub mod pipeline {
     /// Maximum buffer queue size (keep small for low latency)
     pub const MAX_BUFFERS: u32 = 2;
 
+    /// Largest `max-buffers` the preview appsink can be set to
+    pub const APPSINK_MAX_BUFFERS_LIMIT: u32 = 8;
+
+    /// Preview appsink `max-buffers` choices (0 = automatic)
+    pub const APPSINK_BUFFER_OPTIONS: &[u32] = &[0, 1, 2, 3, 4, 6, 8];
+
     /// Get number of threads for videoconvert based on available CPU threads
     pub fn videoconvert_threads() -> u32 {
         std::thread::available_parallelism()
@@ -251,6 +257,9 @@ pub mod pipeline {
     /// Output pixel format for appsink
     /// RGBA uses 4 bytes/pixel - native RGB for simplified GPU processing
     pub const OUTPUT_FORMAT: &str = "RGBA";
+
+    /// Decode error rate above which a decoder is flagged as unreliable
+    pub const DECODER_ERROR_RATE_WARNING: f64 = 0.05;
 }
 
 /// Timing constants
@@ -265,8 +274,39 @@ pub mod timing {
     /// Pipeline state change timeout on stop
     pub const STOP_TIMEOUT_SECS: u64 = 2;
 
+    /// Time a stopped recording gets for EOS to reach the file (muxer finalized)
+    pub const RECORDING_EOS_TIMEOUT_SECS: u64 = 3;
+
+    /// Time the window close waits for the recording and virtual camera to stop
+    /// before forcing exit
+    pub const SHUTDOWN_TIMEOUT_SECS: u64 = 5;
+
+    /// Interval between checks that the camera device was released on shutdown
+    pub const RELEASE_POLL_MS: u64 = 100;
+
+    /// Time a shutdown problem stays on screen before the app exits
+    pub const SHUTDOWN_ERROR_DISPLAY_MS: u64 = 4000;
+
     /// Pipeline playing state timeout on start
     pub const START_TIMEOUT_SECS: u64 = 5;
+
+    /// Default time to wait for the first frame after opening a camera
+    pub const FIRST_FRAME_TIMEOUT_SECS: u64 = 5;
+
+    /// Maximum time to wait for a device thumbnail frame before treating the device as busy
+    pub const THUMBNAIL_TIMEOUT_MS: u64 = 2000;
+
+    /// Time given to a one-shot autofocus run before the focus position is locked/read
+    pub const ONE_SHOT_AUTOFOCUS_SETTLE_MS: u64 = 1000;
+}
+
+/// Device picker thumbnail constants
+pub mod thumbnail {
+    /// Thumbnail width in pixels
+    pub const WIDTH: u32 = 160;
+
+    /// Thumbnail height in pixels
+    pub const HEIGHT: u32 = 90;
 }
 
 /// Frame latency optimization constants
@@ -286,6 +326,134 @@ pub mod latency {
     pub const PIPELINE_CLEANUP_DELAY_MS: u64 = 20;
 }
 
+/// Retroactive recording ("save the last N seconds") constants
+pub mod retro_buffer {
+    /// Selectable buffer durations in seconds (0 = disabled)
+    pub const DURATION_OPTIONS_SECS: [u32; 5] = [0, 5, 10, 30, 60];
+
+    /// Default memory cap for buffered frames in MiB
+    pub const DEFAULT_MEMORY_CAP_MB: u32 = 256;
+}
+
+/// Splitting long recordings into segments
+pub mod recording_split {
+    /// Selectable segment lengths in minutes (0 = no limit)
+    pub const DURATION_OPTIONS_MINS: [u32; 6] = [0, 5, 10, 15, 30, 60];
+
+    /// Selectable segment sizes in MB (0 = no limit)
+    ///
+    /// 4000 MB keeps segments below the FAT32 file size limit, since the
+    /// split waits for the next keyframe after the size is reached.
+    pub const SIZE_OPTIONS_MB: [u32; 5] = [0, 500, 1000, 2000, 4000];
+}
+
+/// Stall watchdog constants
+pub mod stall_watchdog {
+    /// Selectable stall thresholds in seconds (0 = disabled)
+    pub const THRESHOLD_OPTIONS_SECS: [u32; 5] = [0, 3, 5, 10, 30];
+
+    /// Default stall threshold in seconds
+    pub const DEFAULT_THRESHOLD_SECS: u32 = 5;
+
+    /// How often the watchdog checks for new frames
+    pub const CHECK_INTERVAL_MS: u64 = 1000;
+
+    /// Restarts in a row before the watchdog gives up on a pipeline
+    pub const MAX_RESTARTS_IN_A_ROW: u32 = 4;
+}
+
+/// Waiting for a camera to start streaming
+pub mod camera_open {
+    /// Selectable times to wait for the first frame, in seconds
+    pub const TIMEOUT_OPTIONS_SECS: [u32; 5] = [5, 10, 20, 30, 60];
+
+    /// Default time to wait for the first frame
+    ///
+    /// Longer than the CLI's, since a portal may first ask for permission.
+    pub const DEFAULT_TIMEOUT_SECS: u32 = 10;
+
+    /// Pause before a camera that didn't start is tried again
+    pub const RETRY_DELAY_SECS: u64 = 5;
+}
+
+/// Preview while the window is in the background
+pub mod background_preview {
+    /// Frame rate the preview is throttled to
+    pub const THROTTLED_FPS: u32 = 2;
+}
+
+/// Software frame rate cap for the preview and recordings
+pub mod framerate_cap {
+    /// Selectable caps in frames per second (0 = the camera's rate)
+    pub const OPTIONS_FPS: [u32; 6] = [0, 10, 15, 24, 30, 60];
+}
+
+/// Built-in MJPEG HTTP stream
+pub mod http_stream {
+    /// Port listened on unless configured
+    pub const DEFAULT_PORT: u16 = 8090;
+
+    /// Lowest port that can be chosen (lower ones need root)
+    pub const MIN_PORT: u16 = 1024;
+
+    /// Most frames encoded per second, however fast the camera is
+    pub const MAX_FPS: u32 = 15;
+
+    /// Viewers served at once unless configured
+    pub const DEFAULT_MAX_CLIENTS: usize = 4;
+
+    /// Selectable limits on viewers served at once
+    pub const MAX_CLIENTS_OPTIONS: [usize; 5] = [1, 2, 4, 8, 16];
+}
+
+/// Exposure and gain shortcuts
+pub mod exposure_nudge {
+    /// Selectable numbers of control steps per press with Shift held
+    pub const COARSE_STEP_OPTIONS: [u32; 4] = [5, 10, 20, 50];
+
+    /// Steps per press with Shift held unless configured
+    pub const DEFAULT_COARSE_STEPS: u32 = 10;
+
+    /// How long the new value stays on screen
+    pub const INDICATOR_MS: u64 = 1500;
+}
+
+/// Still burst capture (several photos from one trigger)
+pub mod still_burst {
+    /// Selectable numbers of photos per burst
+    pub const COUNT_OPTIONS: [u32; 4] = [3, 5, 10, 20];
+
+    /// Photos per burst unless configured
+    pub const DEFAULT_COUNT: u32 = 5;
+
+    /// Selectable times between two photos in milliseconds
+    pub const INTERVAL_OPTIONS_MS: [u64; 5] = [100, 200, 500, 1000, 2000];
+
+    /// Time between two photos unless configured, in milliseconds
+    pub const DEFAULT_INTERVAL_MS: u64 = 200;
+}
+
+/// Pixel probe readout
+pub mod pixel_probe {
+    /// Minimum time between two samples of the probed pixel
+    pub const REFRESH_MS: u64 = 100;
+}
+
+/// Camera hotplug detection
+pub mod hotplug {
+    /// Quiet time after a device event before the camera list is read again
+    pub const DEBOUNCE_MS: u64 = 500;
+
+    /// Longest a burst of device events can delay the update
+    pub const MAX_DEBOUNCE_MS: u64 = 3000;
+
+    /// Camera list polling interval when no device monitor is available
+    pub const POLL_INTERVAL_SECS: u64 = 2;
+
+    /// Camera list polling interval next to the device monitor, in case it misses an event
+    pub const FALLBACK_POLL_INTERVAL_SECS: u64 = 30;
+}
+
 /// Resolution labels for format picker
 pub fn get_resolution_label(width: u32) -> Option<&'static str> {
     match width {
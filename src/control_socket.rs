@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Local control socket for automation
+//!
+//! When enabled in settings, the app listens on a Unix domain socket at
+//! `$XDG_RUNTIME_DIR/camera/control.sock`. The directory is created with mode
+//! 0700 and the socket with mode 0600, so only the current user can connect.
+//!
+//! # Command grammar
+//!
+//! One command per line; tokens are separated by whitespace and command
+//! names are case-insensitive:
+//!
+//! ```text
+//! command         = snapshot | start-recording | stop-recording | set-zoom | status
+//! snapshot        = "snapshot"
+//! start-recording = "start-recording" [ path ]     ; absolute path of the output file
+//! stop-recording  = "stop-recording"
+//! set-zoom        = "set-zoom" factor              ; 1.0 to 10.0
+//! status          = "status"
+//! ```
+//!
+//! Each command gets exactly one JSON line in reply, e.g.
+//! `{"ok":true,"command":"snapshot"}` or
+//! `{"ok":false,"error":"unknown command: foo"}`. `ok` means the command was
+//! accepted and dispatched to the app; results (like the saved photo path)
+//! show up in the app's log as usual.
+//!
+//! # Example
+//!
+//! ```text
+//! $ echo "set-zoom 2.0" | socat - UNIX-CONNECT:$XDG_RUNTIME_DIR/camera/control.sock
+//! {"ok":true,"command":"set-zoom"}
+//! ```
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Smallest zoom factor accepted by `set-zoom`
+const MIN_ZOOM: f32 = 1.0;
+
+/// Largest zoom factor accepted by `set-zoom`
+const MAX_ZOOM: f32 = 10.0;
+
+/// A parsed control command
+#[derive(Debug, Clone, PartialEq)]
+pub enum ControlCommand {
+    /// Capture a photo
+    Snapshot,
+    /// Start recording, optionally to a specific file
+    StartRecording(Option<PathBuf>),
+    /// Stop the current recording
+    StopRecording,
+    /// Set the preview/capture zoom factor
+    SetZoom(f32),
+    /// Report that the app is alive
+    Status,
+}
+
+impl ControlCommand {
+    /// Command name as written in the grammar
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlCommand::Snapshot => "snapshot",
+            ControlCommand::StartRecording(_) => "start-recording",
+            ControlCommand::StopRecording => "stop-recording",
+            ControlCommand::SetZoom(_) => "set-zoom",
+            ControlCommand::Status => "status",
+        }
+    }
+}
+
+/// Parse a single command line
+pub fn parse_command(line: &str) -> Result<ControlCommand, String> {
+    let mut tokens = line.split_whitespace();
+    let Some(name) = tokens.next() else {
+        return Err("empty command".to_string());
+    };
+    let args: Vec<&str> = tokens.collect();
+
+    let expect_no_args = |command: ControlCommand| {
+        if args.is_empty() {
+            Ok(command)
+        } else {
+            Err(format!("{} takes no arguments", command.name()))
+        }
+    };
+
+    match name.to_ascii_lowercase().as_str() {
+        "snapshot" => expect_no_args(ControlCommand::Snapshot),
+        "stop-recording" => expect_no_args(ControlCommand::StopRecording),
+        "status" => expect_no_args(ControlCommand::Status),
+        "start-recording" => match args.as_slice() {
+            [] => Ok(ControlCommand::StartRecording(None)),
+            [path] if Path::new(path).is_absolute() => {
+                Ok(ControlCommand::StartRecording(Some(PathBuf::from(path))))
+            }
+            [_] => Err("start-recording path must be absolute".to_string()),
+            _ => Err("start-recording takes at most one path".to_string()),
+        },
+        "set-zoom" => match args.as_slice() {
+            [factor] => {
+                let zoom: f32 = factor
+                    .parse()
+                    .map_err(|_| format!("invalid zoom factor: {}", factor))?;
+                if (MIN_ZOOM..=MAX_ZOOM).contains(&zoom) {
+                    Ok(ControlCommand::SetZoom(zoom))
+                } else {
+                    Err(format!(
+                        "zoom factor must be between {} and {}",
+                        MIN_ZOOM, MAX_ZOOM
+                    ))
+                }
+            }
+            _ => Err("set-zoom takes exactly one factor".to_string()),
+        },
+        other => Err(format!("unknown command: {}", other)),
+    }
+}
+
+/// JSON status line for a command result (without trailing newline)
+pub fn response_line(result: &Result<ControlCommand, String>) -> String {
+    let value = match result {
+        Ok(command) => serde_json::json!({ "ok": true, "command": command.name() }),
+        Err(error) => serde_json::json!({ "ok": false, "error": error }),
+    };
+    value.to_string()
+}
+
+/// Location of the control socket
+pub fn socket_path() -> PathBuf {
+    let runtime_dir = dirs::runtime_dir().unwrap_or_else(std::env::temp_dir);
+    runtime_dir.join("camera").join("control.sock")
+}
+
+/// Bind the control socket with owner-only permissions
+///
+/// A stale socket left by a previous run is removed first.
+pub fn bind(path: &Path) -> std::io::Result<tokio::net::UnixListener> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+    if path.exists() {
+        warn!(path = %path.display(), "Removing stale control socket");
+        std::fs::remove_file(path)?;
+    }
+
+    let listener = tokio::net::UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    info!(path = %path.display(), "Control socket listening");
+    Ok(listener)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_snapshot() {
+        assert_eq!(parse_command("snapshot"), Ok(ControlCommand::Snapshot));
+        assert_eq!(parse_command("  SNAPSHOT \n"), Ok(ControlCommand::Snapshot));
+        assert!(parse_command("snapshot now").is_err());
+    }
+
+    #[test]
+    fn test_parse_start_recording() {
+        assert_eq!(
+            parse_command("start-recording"),
+            Ok(ControlCommand::StartRecording(None))
+        );
+        assert_eq!(
+            parse_command("start-recording /tmp/clip.mp4"),
+            Ok(ControlCommand::StartRecording(Some(PathBuf::from(
+                "/tmp/clip.mp4"
+            ))))
+        );
+        assert!(parse_command("start-recording clip.mp4").is_err());
+        assert!(parse_command("start-recording /a.mp4 /b.mp4").is_err());
+    }
+
+    #[test]
+    fn test_parse_stop_recording() {
+        assert_eq!(
+            parse_command("stop-recording"),
+            Ok(ControlCommand::StopRecording)
+        );
+        assert!(parse_command("stop-recording now").is_err());
+    }
+
+    #[test]
+    fn test_parse_set_zoom() {
+        assert_eq!(
+            parse_command("set-zoom 2.0"),
+            Ok(ControlCommand::SetZoom(2.0))
+        );
+        assert_eq!(
+            parse_command("set-zoom 1"),
+            Ok(ControlCommand::SetZoom(1.0))
+        );
+        assert!(parse_command("set-zoom").is_err());
+        assert!(parse_command("set-zoom fast").is_err());
+        assert!(parse_command("set-zoom 0.5").is_err());
+        assert!(parse_command("set-zoom 11").is_err());
+    }
+
+    #[test]
+    fn test_parse_status_and_unknown() {
+        assert_eq!(parse_command("status"), Ok(ControlCommand::Status));
+        assert_eq!(
+            parse_command("reboot"),
+            Err("unknown command: reboot".to_string())
+        );
+        assert_eq!(parse_command("   "), Err("empty command".to_string()));
+    }
+
+    #[test]
+    fn test_response_lines_are_json() {
+        let ok: serde_json::Value =
+            serde_json::from_str(&response_line(&parse_command("snapshot"))).unwrap();
+        assert_eq!(ok["ok"], true);
+        assert_eq!(ok["command"], "snapshot");
+
+        let err: serde_json::Value =
+            serde_json::from_str(&response_line(&parse_command("bogus"))).unwrap();
+        assert_eq!(err["ok"], false);
+        assert_eq!(err["error"], "unknown command: bogus");
+    }
+
+    #[tokio::test]
+    async fn test_socket_is_owner_only() {
+        let dir = std::env::temp_dir().join(format!("camera-control-test-{}", std::process::id()));
+        let path = dir.join("control.sock");
+        let _listener = bind(&path).expect("bind socket");
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let dir_mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(dir_mode & 0o777, 0o700);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
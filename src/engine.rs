@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Capture and decode without the COSMIC UI
+//!
+//! The preview pipeline (camera source, decoder, appsink) doesn't depend on
+//! the app: [`Capture`] runs it on its own, and a callback registered with
+//! [`set_frame_callback`] sees every decoded frame in place.
+//!
+//! ```ignore
+//! camera::engine::set_frame_callback(|frame| {
+//!     println!("{}x{} {:?}, {} bytes", frame.width, frame.height, frame.format, frame.data.len());
+//! });
+//! let capture = camera::engine::Capture::start(&camera, &format)?;
+//! ```
+//!
+//! # Threading and lifetimes
+//!
+//! The callback runs on the GStreamer streaming thread, once per frame and
+//! before the frame is queued for the preview. The pipeline waits for it, so
+//! it should return quickly; slow work belongs on another thread.
+//!
+//! [`FrameView`] borrows the mapped GStreamer buffer and is only valid
+//! during the call. Copy the pixels out to keep them. Buffers in DMABUF
+//! memory are mapped like any other; the GStreamer buffer is passed along
+//! for callers that want to look at its memory themselves.
+//!
+//! There is one callback per process. Registering or clearing it from
+//! inside the callback is allowed and takes effect from the next frame.
+
+use crate::backends::camera::frame_queue::{self, FrameDropPolicy};
+use crate::backends::camera::pipewire::PipeWirePipeline;
+use crate::backends::camera::types::{
+    BackendResult, CameraDevice, CameraFormat, CameraFrame, PixelFormat, YuvPlanes,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+type FrameCallback = Arc<dyn Fn(&FrameView<'_>) + Send + Sync>;
+
+static CALLBACK: RwLock<Option<FrameCallback>> = RwLock::new(None);
+
+/// Mirrors `CALLBACK.is_some()` so the frame path can skip the lock
+static HAS_CALLBACK: AtomicBool = AtomicBool::new(false);
+
+/// A decoded frame, borrowed for the duration of the callback
+#[derive(Debug, Clone, Copy)]
+pub struct FrameView<'a> {
+    /// Number of the frame since the pipeline started, from 0
+    pub index: u64,
+    /// Presentation timestamp of the buffer
+    pub pts: Option<Duration>,
+    pub width: u32,
+    pub height: u32,
+    pub format: PixelFormat,
+    /// Bytes per row of the first plane (rows may be padded)
+    pub stride: u32,
+    /// Plane offsets and strides of planar YUV formats
+    pub yuv_planes: Option<YuvPlanes>,
+    /// Pixels of all planes, laid out as described by `stride` and `yuv_planes`
+    pub data: &'a [u8],
+    /// The GStreamer buffer the pixels were mapped from
+    pub buffer: &'a gstreamer::BufferRef,
+}
+
+impl FrameView<'_> {
+    /// Rows of the luma plane (or the only plane) without row padding
+    ///
+    /// For packed formats a row holds all channels of its pixels.
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        let row_bytes = match self.format {
+            PixelFormat::RGBA => self.width as usize * 4,
+            PixelFormat::RGB24 => self.width as usize * 3,
+            PixelFormat::Gray16
+            | PixelFormat::YUYV
+            | PixelFormat::UYVY
+            | PixelFormat::YVYU
+            | PixelFormat::VYUY => self.width as usize * 2,
+            PixelFormat::Gray8 | PixelFormat::NV12 | PixelFormat::NV21 | PixelFormat::I420 => {
+                self.width as usize
+            }
+        };
+        let offset = self.yuv_planes.map_or(0, |planes| planes.y_offset);
+        let stride = (self.stride as usize).max(row_bytes);
+        (0..self.height as usize).map_while(move |row| {
+            let start = offset + row * stride;
+            self.data.get(start..start + row_bytes)
+        })
+    }
+}
+
+/// Call `callback` with every decoded frame, replacing any previous callback
+pub fn set_frame_callback<F>(callback: F)
+where
+    F: Fn(&FrameView<'_>) + Send + Sync + 'static,
+{
+    let mut slot = CALLBACK.write().unwrap_or_else(|e| e.into_inner());
+    *slot = Some(Arc::new(callback));
+    HAS_CALLBACK.store(true, Ordering::Release);
+}
+
+/// Stop calling the registered frame callback
+pub fn clear_frame_callback() {
+    let mut slot = CALLBACK.write().unwrap_or_else(|e| e.into_inner());
+    *slot = None;
+    HAS_CALLBACK.store(false, Ordering::Release);
+}
+
+/// Whether a frame callback is registered
+#[inline]
+pub fn has_frame_callback() -> bool {
+    HAS_CALLBACK.load(Ordering::Acquire)
+}
+
+/// Hand `frame`, mapped from `buffer`, to the registered callback
+pub(crate) fn deliver(index: u64, buffer: &gstreamer::BufferRef, frame: &CameraFrame) {
+    // Called without holding the lock, so the callback may replace itself
+    let Some(callback) = CALLBACK.read().unwrap_or_else(|e| e.into_inner()).clone() else {
+        return;
+    };
+    callback(&FrameView {
+        index,
+        pts: buffer.pts().map(|pts| Duration::from_nanos(pts.nseconds())),
+        width: frame.width,
+        height: frame.height,
+        format: frame.format,
+        stride: frame.stride,
+        yuv_planes: frame.yuv_planes,
+        data: frame.data_slice(),
+        buffer,
+    });
+}
+
+/// A running camera pipeline, without any UI
+///
+/// Frames go to the registered callback and, owned, to [`Capture::frames`].
+/// Dropping the capture stops the pipeline and releases the camera.
+pub struct Capture {
+    pipeline: PipeWirePipeline,
+    frames: frame_queue::Receiver<CameraFrame>,
+}
+
+impl Capture {
+    /// Open `camera` in `format` and start streaming
+    pub fn start(camera: &CameraDevice, format: &CameraFormat) -> BackendResult<Self> {
+        // Only the newest owned frame is kept, callers that don't read them lose nothing
+        let (sender, frames) = frame_queue::channel(1, FrameDropPolicy::SkipToLatest);
+        let pipeline = PipeWirePipeline::new(camera, format, sender)?;
+        Ok(Self { pipeline, frames })
+    }
+
+    /// Wait until the camera delivered its first frame
+    pub async fn wait_for_first_frame(&self, timeout: Duration) -> BackendResult<()> {
+        self.pipeline.wait_for_first_frame(timeout).await
+    }
+
+    /// Owned frames, for consumers that need them after the callback returned
+    pub fn frames(&mut self) -> &mut frame_queue::Receiver<CameraFrame> {
+        &mut self.frames
+    }
+
+    /// GStreamer element that decodes the camera's format
+    pub fn decoder(&self) -> &str {
+        self.pipeline.decoder_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::camera::types::FrameData;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_callback_sees_unpadded_rows() {
+        let _ = gstreamer::init();
+        let buffer = gstreamer::Buffer::new();
+        let frame = CameraFrame {
+            width: 2,
+            height: 2,
+            data: FrameData::from_bytes(Arc::from(vec![10u8, 20, 0xff, 30, 40, 0xff])),
+            format: PixelFormat::Gray8,
+            stride: 3,
+            yuv_planes: None,
+            captured_at: std::time::Instant::now(),
+            stamp: None,
+        };
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        set_frame_callback(move |view| {
+            let rows: Vec<Vec<u8>> = view.rows().map(<[u8]>::to_vec).collect();
+            sink.lock().unwrap().push((view.index, rows));
+        });
+        assert!(has_frame_callback());
+        deliver(7, &buffer, &frame);
+        clear_frame_callback();
+        deliver(8, &buffer, &frame);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(*seen, vec![(7, vec![vec![10, 20], vec![30, 40]])]);
+    }
+}
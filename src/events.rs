@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Observer hooks for frame and state changes
+//!
+//! Embedders and automation (e.g. motion-triggered recording) can follow what
+//! the camera is doing without being wired into the app's update loop:
+//!
+//! ```ignore
+//! let events = camera::events::hub().subscribe(64);
+//! std::thread::spawn(move || {
+//!     for event in events {
+//!         println!("{:?}", event);
+//!     }
+//! });
+//! ```
+//!
+//! Events are delivered over bounded channels. [`EventHub::emit`] never
+//! blocks: a subscriber that doesn't keep up misses events (counted in
+//! [`EventHub::dropped`]) instead of stalling the frame path or the pipeline,
+//! and callbacks registered with [`EventHub::on`] run on their own thread, so
+//! they may emit or subscribe themselves without deadlocking.
+
+use crate::backends::camera::types::{CameraFrame, PixelFormat};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use tracing::warn;
+
+/// Size and layout of decoded frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameInfo {
+    pub width: u32,
+    pub height: u32,
+    pub format: PixelFormat,
+}
+
+impl From<&CameraFrame> for FrameInfo {
+    fn from(frame: &CameraFrame) -> Self {
+        Self {
+            width: frame.width,
+            height: frame.height,
+            format: frame.format,
+        }
+    }
+}
+
+/// Something observers may want to react to
+#[derive(Debug, Clone, PartialEq)]
+pub enum CameraEvent {
+    /// A frame reached the app (metadata only, the pixels stay with the preview)
+    FrameDecoded(FrameInfo),
+    /// Frames arrive with a different size or pixel format than before
+    FormatChanged(FrameInfo),
+    /// The pipeline uses a decoder other than the preferred one
+    DecoderFallback {
+        /// First decoder in the fallback chain
+        preferred: &'static str,
+        /// GStreamer element actually used
+        selected: String,
+    },
+    /// Recording started, writing to the given path
+    RecordingStarted(String),
+    /// Recording stopped, with the saved path or the error
+    RecordingStopped(Result<String, String>),
+}
+
+/// Fan-out of [`CameraEvent`]s to any number of subscribers
+#[derive(Debug, Default)]
+pub struct EventHub {
+    subscribers: Mutex<Vec<SyncSender<CameraEvent>>>,
+    /// Mirrors `subscribers.len()` so emitters can skip building events nobody receives
+    subscriber_count: AtomicUsize,
+    dropped: AtomicU64,
+}
+
+static HUB: EventHub = EventHub::new();
+
+/// Process-wide hub the app emits its events to
+pub fn hub() -> &'static EventHub {
+    &HUB
+}
+
+impl EventHub {
+    pub const fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+            subscriber_count: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Receive events from now on, buffering up to `capacity` unread ones
+    ///
+    /// Dropping the receiver unsubscribes.
+    pub fn subscribe(&self, capacity: usize) -> Receiver<CameraEvent> {
+        let (sender, receiver) = mpsc::sync_channel(capacity.max(1));
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        subscribers.push(sender);
+        self.subscriber_count
+            .store(subscribers.len(), Ordering::Release);
+        receiver
+    }
+
+    /// Call `callback` for every event, on a dedicated thread
+    ///
+    /// The callback runs until the hub stops emitting (i.e. for the life of
+    /// the process when registered on [`hub`]).
+    pub fn on<F>(&self, callback: F)
+    where
+        F: FnMut(CameraEvent) + Send + 'static,
+    {
+        let events = self.subscribe(64);
+        if let Err(err) = std::thread::Builder::new()
+            .name("camera-events".to_string())
+            .spawn(move || events.into_iter().for_each(callback))
+        {
+            warn!(?err, "Failed to start event callback thread");
+        }
+    }
+
+    /// Whether anyone is listening
+    pub fn has_subscribers(&self) -> bool {
+        self.subscriber_count.load(Ordering::Acquire) > 0
+    }
+
+    /// Deliver `event` to every subscriber without blocking
+    pub fn emit(&self, event: CameraEvent) {
+        if !self.has_subscribers() {
+            return;
+        }
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        subscribers.retain(|sender| match sender.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+        self.subscriber_count
+            .store(subscribers.len(), Ordering::Release);
+    }
+
+    /// Events not delivered because a subscriber's buffer was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Emit [`CameraEvent::FrameDecoded`], plus [`CameraEvent::FormatChanged`]
+/// when the frame differs in size or format from `previous`
+pub fn emit_frame(previous: Option<FrameInfo>, frame: FrameInfo) {
+    if previous != Some(frame) {
+        hub().emit(CameraEvent::FormatChanged(frame));
+    }
+    hub().emit(CameraEvent::FrameDecoded(frame));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(width: u32) -> FrameInfo {
+        FrameInfo {
+            width,
+            height: 480,
+            format: PixelFormat::NV12,
+        }
+    }
+
+    #[test]
+    fn test_events_reach_every_subscriber() {
+        let hub = EventHub::new();
+        assert!(!hub.has_subscribers());
+        let a = hub.subscribe(4);
+        let b = hub.subscribe(4);
+        hub.emit(CameraEvent::RecordingStarted("/tmp/a.mp4".to_string()));
+        let expected = CameraEvent::RecordingStarted("/tmp/a.mp4".to_string());
+        assert_eq!(a.try_recv().ok(), Some(expected.clone()));
+        assert_eq!(b.try_recv().ok(), Some(expected));
+    }
+
+    #[test]
+    fn test_full_subscriber_does_not_block() {
+        let hub = EventHub::new();
+        let slow = hub.subscribe(1);
+        for width in [640, 1280, 1920] {
+            hub.emit(CameraEvent::FrameDecoded(frame(width)));
+        }
+        assert_eq!(hub.dropped(), 2);
+        assert_eq!(
+            slow.try_recv().ok(),
+            Some(CameraEvent::FrameDecoded(frame(640)))
+        );
+    }
+
+    #[test]
+    fn test_dropped_receiver_unsubscribes() {
+        let hub = EventHub::new();
+        drop(hub.subscribe(4));
+        assert!(hub.has_subscribers());
+        hub.emit(CameraEvent::RecordingStopped(Ok("/tmp/a.mp4".to_string())));
+        assert!(!hub.has_subscribers());
+    }
+
+    #[test]
+    fn test_callback_runs_off_the_emitting_thread() {
+        let hub: &'static EventHub = Box::leak(Box::new(EventHub::new()));
+        let (done, finished) = mpsc::channel();
+        let emitter = std::thread::current().id();
+        hub.on(move |event| {
+            // Emitting from a callback must not deadlock
+            if matches!(event, CameraEvent::RecordingStarted(_)) {
+                hub.emit(CameraEvent::RecordingStopped(Ok("/tmp/b.mp4".to_string())));
+                let _ = done.send((event, std::thread::current().id() != emitter));
+            }
+        });
+        hub.emit(CameraEvent::RecordingStarted("/tmp/b.mp4".to_string()));
+        let (event, other_thread) = finished
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .expect("callback ran");
+        assert_eq!(
+            event,
+            CameraEvent::RecordingStarted("/tmp/b.mp4".to_string())
+        );
+        assert!(other_thread);
+    }
+}
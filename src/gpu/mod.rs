@@ -4,13 +4,262 @@
 //!
 //! This module provides helpers for creating wgpu devices for compute operations.
 //! Uses the same wgpu instance as libcosmic's UI rendering.
+//!
+//! Device loss (driver reset, GPU hang, eGPU unplug) is tracked per device:
+//! cached pipelines check whether their own device was lost and rebuild on
+//! next use. Recordings run entirely in GStreamer and don't
+//! touch these devices, so they keep going while the GPU side recovers.
+//!
+//! Some backends (GL-only drivers, software rasterizers) have no compute
+//! shaders. The preview then converts frames with a fragment shader instead,
+//! and compute-only features such as the exposure histogram stay disabled.
+//! Set `CAMERA_FORCE_FRAGMENT_CONVERSION=1` to take that path on any GPU.
 
-use std::sync::Arc;
-use tracing::{debug, info};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use tracing::{debug, error, info, warn};
 
 /// Re-export wgpu types from cosmic for use in compute pipelines
 pub use cosmic::iced_wgpu::wgpu;
 
+/// Description of the most recently selected compute adapter (for diagnostics)
+static ADAPTER_SUMMARY: RwLock<Option<String>> = RwLock::new(None);
+
+/// Get a one-line description of the GPU adapter used for compute
+///
+/// `None` until a compute device has been created.
+pub fn adapter_summary() -> Option<String> {
+    ADAPTER_SUMMARY.read().ok().and_then(|guard| guard.clone())
+}
+
+/// Whether compute devices created from now on ask for a low-power adapter
+static LOW_POWER_ADAPTER: AtomicBool = AtomicBool::new(false);
+
+/// Ask for the low-power adapter (usually the integrated GPU) for compute
+/// devices created from now on
+///
+/// Devices that already exist keep their adapter.
+pub fn set_low_power_adapter(enabled: bool) {
+    LOW_POWER_ADAPTER.store(enabled, Ordering::Relaxed);
+}
+
+/// Adapter power preference requested for new compute devices
+pub fn adapter_power_preference() -> wgpu::PowerPreference {
+    if LOW_POWER_ADAPTER.load(Ordering::Relaxed) {
+        wgpu::PowerPreference::LowPower
+    } else {
+        wgpu::PowerPreference::HighPerformance
+    }
+}
+
+/// Environment variable that forces the fragment-shader fallback
+const FORCE_FRAGMENT_ENV: &str = "CAMERA_FORCE_FRAGMENT_CONVERSION";
+
+/// How the preview converts camera frames to RGBA
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConversionPath {
+    /// Compute shader writing to a storage texture
+    #[default]
+    Compute,
+    /// Fullscreen fragment shader rendering to the output texture
+    Fragment,
+}
+
+impl ConversionPath {
+    /// Short description for insights
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConversionPath::Compute => "compute shader",
+            ConversionPath::Fragment => "fragment shader",
+        }
+    }
+}
+
+/// Conversion path the preview picked (`None` until the preview is created)
+static CONVERSION_PATH: RwLock<Option<ConversionPath>> = RwLock::new(None);
+
+/// Set once an adapter was found to lack compute shader support
+static COMPUTE_UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the fragment-shader fallback is forced through the environment
+pub fn fragment_fallback_forced() -> bool {
+    std::env::var(FORCE_FRAGMENT_ENV).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Whether `limits` allow the 16×16 compute workgroups and storage texture
+/// writes the conversion and analysis shaders use
+pub fn compute_supported(limits: &wgpu::Limits) -> bool {
+    limits.max_compute_workgroup_size_x >= 16
+        && limits.max_compute_workgroup_size_y >= 16
+        && limits.max_compute_invocations_per_workgroup >= 256
+        && limits.max_compute_workgroups_per_dimension > 0
+        && limits.max_storage_textures_per_shader_stage > 0
+}
+
+/// Pick the preview's conversion path for a device with `limits`
+///
+/// The choice is remembered for [`conversion_path`]; choosing the fragment
+/// path also marks compute-dependent features as unavailable.
+pub fn choose_conversion_path(limits: &wgpu::Limits) -> ConversionPath {
+    let forced = fragment_fallback_forced();
+    let path = conversion_path_for(limits, forced);
+    if forced {
+        info!(
+            "Fragment-shader conversion forced by {}",
+            FORCE_FRAGMENT_ENV
+        );
+    } else if path == ConversionPath::Fragment {
+        warn!("GPU has no usable compute shader support, converting frames in a fragment shader");
+    }
+    if path == ConversionPath::Fragment {
+        COMPUTE_UNAVAILABLE.store(true, Ordering::Relaxed);
+    }
+    if let Ok(mut guard) = CONVERSION_PATH.write() {
+        *guard = Some(path);
+    }
+    path
+}
+
+/// Conversion path for a device with `limits`, with the fragment path `forced` or not
+pub fn conversion_path_for(limits: &wgpu::Limits, forced: bool) -> ConversionPath {
+    if !forced && compute_supported(limits) {
+        ConversionPath::Compute
+    } else {
+        ConversionPath::Fragment
+    }
+}
+
+/// Conversion path in use by the preview
+pub fn conversion_path() -> Option<ConversionPath> {
+    CONVERSION_PATH.read().ok().and_then(|guard| *guard)
+}
+
+/// Whether compute-dependent features (histogram, GPU filters) can run
+pub fn compute_available() -> bool {
+    !COMPUTE_UNAVAILABLE.load(Ordering::Relaxed)
+}
+
+/// Device-loss bookkeeping, keyed by device id
+///
+/// wgpu keeps a single lost callback per device and replacing it drops the
+/// previous one, so each device gets one dispatcher that runs every watcher
+/// registered for it. Only devices we create are watched; the device iced
+/// renders with belongs to the renderer, and the preview notices its loss by
+/// iced handing over a new device.
+#[derive(Debug)]
+struct LossRegistry<K> {
+    /// Labels of the watchers registered per device, run in order on loss
+    watchers: HashMap<K, Vec<String>>,
+    /// Devices lost for real (a lost device never comes back)
+    lost: HashSet<K>,
+}
+
+impl<K: Copy + Eq + Hash> LossRegistry<K> {
+    fn new() -> Self {
+        Self {
+            watchers: HashMap::new(),
+            lost: HashSet::new(),
+        }
+    }
+
+    /// Add a watcher for `device`
+    ///
+    /// Returns true for the first watcher, which must install the dispatcher.
+    fn watch(&mut self, device: K, label: &str) -> bool {
+        let watchers = self.watchers.entry(device).or_default();
+        watchers.push(label.to_string());
+        watchers.len() == 1
+    }
+
+    /// Record a real loss of `device`, returning the watchers to notify
+    fn lose(&mut self, device: K) -> Vec<String> {
+        self.lost.insert(device);
+        self.watchers.get(&device).cloned().unwrap_or_default()
+    }
+
+    /// Forget a device that was destroyed or dropped on purpose
+    fn release(&mut self, device: K) -> Vec<String> {
+        self.lost.remove(&device);
+        self.watchers.remove(&device).unwrap_or_default()
+    }
+
+    fn is_lost(&self, device: K) -> bool {
+        self.lost.contains(&device)
+    }
+}
+
+static LOSS_REGISTRY: LazyLock<Mutex<LossRegistry<wgpu::Id<wgpu::Device>>>> =
+    LazyLock::new(|| Mutex::new(LossRegistry::new()));
+
+/// Number of GPU device losses seen so far (for diagnostics)
+static DEVICE_LOSSES: AtomicU64 = AtomicU64::new(0);
+
+/// Set on device loss, cleared once the preview has rendered again
+static RECOVERING: AtomicBool = AtomicBool::new(false);
+
+/// Total number of device losses across all devices
+pub fn device_losses() -> u64 {
+    DEVICE_LOSSES.load(Ordering::Acquire)
+}
+
+/// Whether `device` was lost
+///
+/// Cached pipelines check their own device and rebuild themselves (dropping
+/// the old device and all its resources) once it is lost. A loss of another
+/// device leaves them alone.
+pub fn is_device_lost(device: &wgpu::Device) -> bool {
+    LOSS_REGISTRY
+        .lock()
+        .is_ok_and(|registry| registry.is_lost(device.global_id()))
+}
+
+/// Whether a device loss is still being recovered from
+pub fn is_recovering() -> bool {
+    RECOVERING.load(Ordering::Relaxed)
+}
+
+/// Mark recovery from a device loss as complete
+pub fn mark_recovered() {
+    if RECOVERING.swap(false, Ordering::Relaxed) {
+        info!(losses = device_losses(), "GPU recovered after device loss");
+    }
+}
+
+/// Register `label` as a watcher of `device`'s loss
+///
+/// Only real losses (driver reset, GPU hang, eGPU unplug) count; destroying or
+/// dropping the device on purpose just forgets it. Watching a device again
+/// adds to its watchers instead of replacing the callback.
+pub fn watch_device_loss(device: &wgpu::Device, label: &str) {
+    let id = device.global_id();
+    let first = match LOSS_REGISTRY.lock() {
+        Ok(mut registry) => registry.watch(id, label),
+        Err(_) => return,
+    };
+    if !first {
+        return;
+    }
+    device.set_device_lost_callback(move |reason, message| {
+        let Ok(mut registry) = LOSS_REGISTRY.lock() else {
+            return;
+        };
+        if !matches!(reason, wgpu::DeviceLostReason::Unknown) {
+            let watchers = registry.release(id);
+            debug!(?watchers, ?reason, "GPU device released");
+            return;
+        }
+        let watchers = registry.lose(id);
+        drop(registry);
+        let losses = DEVICE_LOSSES.fetch_add(1, Ordering::AcqRel) + 1;
+        RECOVERING.store(true, Ordering::Relaxed);
+        for label in &watchers {
+            error!(label = %label, losses, %message, "GPU device lost, recovering");
+        }
+    });
+}
+
 /// Information about the created GPU device
 #[derive(Debug)]
 pub struct GpuDeviceInfo {
@@ -45,7 +294,7 @@ pub async fn create_low_priority_compute_device(
 
     let adapter = instance
         .request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::HighPerformance,
+            power_preference: adapter_power_preference(),
             compatible_surface: None,
             force_fallback_adapter: false,
         })
@@ -55,9 +304,24 @@ pub async fn create_low_priority_compute_device(
     let adapter_info = adapter.get_info();
     let adapter_limits = adapter.limits();
 
+    if fragment_fallback_forced()
+        || !adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+        || !compute_supported(&adapter_limits)
+    {
+        COMPUTE_UNAVAILABLE.store(true, Ordering::Relaxed);
+        return Err(format!(
+            "GPU adapter {} has no compute shader support",
+            adapter_info.name
+        ));
+    }
+
     info!(
         adapter = %adapter_info.name,
         backend = ?adapter_info.backend,
+        power_preference = ?adapter_power_preference(),
         "GPU adapter selected for compute"
     );
 
@@ -66,6 +330,17 @@ pub async fn create_low_priority_compute_device(
         "Using standard device creation"
     );
 
+    if let Ok(mut guard) = ADAPTER_SUMMARY.write() {
+        *guard = Some(format!(
+            "{} ({:?}, {:?}, driver: {} {})",
+            adapter_info.name,
+            adapter_info.backend,
+            adapter_info.device_type,
+            adapter_info.driver,
+            adapter_info.driver_info
+        ));
+    }
+
     let (device, queue) = adapter
         .request_device(
             &wgpu::DeviceDescriptor {
@@ -79,6 +354,8 @@ pub async fn create_low_priority_compute_device(
         .await
         .map_err(|e| format!("Failed to create GPU device: {}", e))?;
 
+    watch_device_loss(&device, label);
+
     let info = GpuDeviceInfo {
         adapter_name: adapter_info.name.clone(),
         backend: adapter_info.backend,
@@ -92,6 +369,49 @@ pub async fn create_low_priority_compute_device(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compute_support_from_limits() {
+        assert!(compute_supported(&wgpu::Limits::default()));
+        assert!(compute_supported(&wgpu::Limits::downlevel_defaults()));
+        // WebGL2-class backends (e.g. GLES without compute) report no workgroups
+        assert!(!compute_supported(
+            &wgpu::Limits::downlevel_webgl2_defaults()
+        ));
+    }
+
+    #[test]
+    fn test_forced_fragment_path_overrides_compute_support() {
+        let limits = wgpu::Limits::default();
+        assert_eq!(conversion_path_for(&limits, false), ConversionPath::Compute);
+        assert_eq!(conversion_path_for(&limits, true), ConversionPath::Fragment);
+        assert_eq!(
+            conversion_path_for(&wgpu::Limits::downlevel_webgl2_defaults(), false),
+            ConversionPath::Fragment
+        );
+    }
+
+    #[test]
+    fn test_device_loss_is_tracked_per_device() {
+        let mut registry = LossRegistry::new();
+        // The first watcher installs the dispatcher, later ones chain onto it
+        assert!(registry.watch(1, "convert"));
+        assert!(!registry.watch(1, "histogram"));
+        assert!(registry.watch(2, "filter"));
+
+        assert_eq!(registry.lose(1), ["convert", "histogram"]);
+        assert!(registry.is_lost(1));
+        // An unrelated device keeps its pipelines
+        assert!(!registry.is_lost(2));
+        // The replacement device starts out healthy
+        assert!(registry.watch(3, "convert"));
+        assert!(!registry.is_lost(3));
+
+        // Dropping a device on purpose forgets it
+        assert_eq!(registry.release(1), ["convert", "histogram"]);
+        assert!(!registry.is_lost(1));
+        assert!(registry.release(1).is_empty());
+    }
+
     #[tokio::test]
     async fn test_create_low_priority_device() {
         // This test requires a GPU, so it may be skipped in CI
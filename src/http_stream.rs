@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Built-in MJPEG HTTP stream
+//!
+//! When enabled in settings, the app serves what it would save as a photo
+//! (filter, color effect, zoom and rotation applied) as
+//! `multipart/x-mixed-replace` MJPEG, which browsers and most IP camera
+//! viewers play as live video:
+//!
+//! ```text
+//! $ curl -s http://127.0.0.1:8090/ | head -c 200
+//! --camera-frame
+//! Content-Type: image/jpeg
+//! Content-Length: 48213
+//! ...
+//! ```
+//!
+//! The server only listens on localhost unless binding to all interfaces is
+//! turned on. There is no authentication, so anyone who can reach the port
+//! can watch; viewers beyond the configured limit get `503 Service
+//! Unavailable`. Frames are only encoded while a viewer is connected.
+//!
+//! Each viewer gets its own task that ends when the viewer closes the
+//! connection, stops reading for [`WRITE_TIMEOUT`], or the server is turned
+//! off, so viewers that vanish never hold on to a connection.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+/// Separator between the JPEG parts of the stream
+pub const BOUNDARY: &str = "camera-frame";
+
+/// Longest a viewer may take to accept a frame before it's dropped
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest a client may take to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest request head accepted (the request line and headers)
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Latest JPEG frame, while the server is running
+static FRAMES: Mutex<Option<watch::Sender<Option<Arc<[u8]>>>>> = Mutex::new(None);
+
+/// Viewers currently connected
+static CLIENTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Most viewers served at once
+static MAX_CLIENTS: AtomicUsize =
+    AtomicUsize::new(crate::constants::http_stream::DEFAULT_MAX_CLIENTS);
+
+/// Address to listen on (localhost unless `all_interfaces`)
+pub fn bind_address(port: u16, all_interfaces: bool) -> SocketAddr {
+    let ip = if all_interfaces {
+        Ipv4Addr::UNSPECIFIED
+    } else {
+        Ipv4Addr::LOCALHOST
+    };
+    SocketAddr::new(IpAddr::V4(ip), port)
+}
+
+/// URL viewers open for a listening address
+///
+/// When listening on all interfaces the host name stands in for the
+/// address, since that's what other devices on the network use.
+pub fn stream_url(addr: SocketAddr) -> String {
+    if addr.ip().is_unspecified() {
+        let host = std::fs::read_to_string("/proc/sys/kernel/hostname")
+            .map(|name| name.trim().to_string())
+            .ok()
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "localhost".to_string());
+        format!("http://{}:{}/", host, addr.port())
+    } else {
+        format!("http://{}/", addr)
+    }
+}
+
+/// Number of viewers currently connected
+pub fn client_count() -> usize {
+    CLIENTS.load(Ordering::Relaxed)
+}
+
+/// Most viewers served at once
+pub fn max_clients() -> usize {
+    MAX_CLIENTS.load(Ordering::Relaxed)
+}
+
+/// Limit the viewers served at once
+///
+/// Viewers already connected stay; new ones are refused while over the limit.
+pub fn set_max_clients(max: usize) {
+    MAX_CLIENTS.store(max.max(1), Ordering::Relaxed);
+}
+
+/// Whether a frame published now would reach anyone
+pub fn wants_frames() -> bool {
+    client_count() > 0 && FRAMES.lock().is_ok_and(|frames| frames.is_some())
+}
+
+/// Send a JPEG frame to every connected viewer
+pub fn publish(jpeg: Vec<u8>) {
+    if let Ok(frames) = FRAMES.lock()
+        && let Some(sender) = frames.as_ref()
+    {
+        sender.send_replace(Some(Arc::from(jpeg)));
+    }
+}
+
+/// Start listening for viewers
+pub async fn bind(port: u16, all_interfaces: bool) -> std::io::Result<TcpListener> {
+    let addr = bind_address(port, all_interfaces);
+    let listener = TcpListener::bind(addr).await?;
+    info!(%addr, "MJPEG stream listening");
+    Ok(listener)
+}
+
+/// Clears the published frames when the server stops, which ends every viewer's stream
+struct ServerGuard;
+
+impl Drop for ServerGuard {
+    fn drop(&mut self) {
+        if let Ok(mut frames) = FRAMES.lock() {
+            *frames = None;
+        }
+        info!("MJPEG stream stopped");
+    }
+}
+
+/// Counts a viewer for as long as it's connected
+struct ClientGuard;
+
+impl ClientGuard {
+    /// Count a viewer, `None` when the limit is reached
+    fn try_new() -> Option<Self> {
+        CLIENTS
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+                (count < max_clients()).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self)
+    }
+}
+
+impl Drop for ClientGuard {
+    fn drop(&mut self) {
+        CLIENTS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Serve viewers until the returned future is dropped
+pub async fn serve(listener: TcpListener) {
+    let (sender, receiver) = watch::channel(None);
+    if let Ok(mut frames) = FRAMES.lock() {
+        *frames = Some(sender);
+    }
+    let _guard = ServerGuard;
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                tokio::spawn(serve_client(stream, peer, receiver.clone()));
+            }
+            Err(e) => {
+                // Usually out of file descriptors; back off instead of spinning
+                warn!(error = %e, "Failed to accept MJPEG viewer");
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+}
+
+/// Path of a GET request (None for anything else)
+fn request_path(head: &str) -> Option<&str> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    let method = parts.next()?;
+    let path = parts.next()?;
+    (method == "GET").then(|| path.split('?').next().unwrap_or(path))
+}
+
+/// Response head that starts the stream
+fn stream_head() -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: multipart/x-mixed-replace; boundary={BOUNDARY}\r\n\
+         Cache-Control: no-cache, no-store\r\n\
+         Pragma: no-cache\r\n\
+         Connection: close\r\n\r\n"
+    )
+}
+
+/// Headers in front of one JPEG frame
+fn part_head(len: usize) -> String {
+    format!("--{BOUNDARY}\r\nContent-Type: image/jpeg\r\nContent-Length: {len}\r\n\r\n")
+}
+
+/// Read the request line and headers
+async fn read_request_head(stream: &mut TcpStream) -> Option<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await.ok()?;
+        if n == 0 || head.len() + n > MAX_REQUEST_BYTES {
+            return None;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    String::from_utf8(head).ok()
+}
+
+/// Write with [`WRITE_TIMEOUT`]; false when the viewer is gone or stuck
+async fn write_all(writer: &mut (impl AsyncWriteExt + Unpin), data: &[u8]) -> bool {
+    matches!(
+        tokio::time::timeout(WRITE_TIMEOUT, writer.write_all(data)).await,
+        Ok(Ok(()))
+    )
+}
+
+async fn serve_client(
+    mut stream: TcpStream,
+    peer: SocketAddr,
+    mut frames: watch::Receiver<Option<Arc<[u8]>>>,
+) {
+    let head = match tokio::time::timeout(REQUEST_TIMEOUT, read_request_head(&mut stream)).await {
+        Ok(Some(head)) => head,
+        _ => {
+            debug!(%peer, "Dropped MJPEG client without a request");
+            return;
+        }
+    };
+    match request_path(&head) {
+        Some("/") => {}
+        path => {
+            // Browsers also ask for /favicon.ico; only "/" is a viewer
+            let status = if path.is_some() {
+                "404 Not Found"
+            } else {
+                "405 Method Not Allowed"
+            };
+            let response =
+                format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+            write_all(&mut stream, response.as_bytes()).await;
+            return;
+        }
+    }
+
+    let Some(_client) = ClientGuard::try_new() else {
+        info!(%peer, max = max_clients(), "Refused MJPEG viewer over the limit");
+        let response = "HTTP/1.1 503 Service Unavailable\r\n\
+                        Content-Length: 0\r\n\
+                        Retry-After: 5\r\n\
+                        Connection: close\r\n\r\n";
+        write_all(&mut stream, response.as_bytes()).await;
+        return;
+    };
+    info!(%peer, viewers = client_count(), "MJPEG viewer connected");
+
+    let (mut reader, mut writer) = stream.into_split();
+    if !write_all(&mut writer, stream_head().as_bytes()).await {
+        return;
+    }
+    let mut discard = [0u8; 256];
+    loop {
+        tokio::select! {
+            changed = frames.changed() => {
+                // The server stopped
+                if changed.is_err() {
+                    break;
+                }
+                let Some(jpeg) = frames.borrow_and_update().clone() else {
+                    continue;
+                };
+                if !write_all(&mut writer, part_head(jpeg.len()).as_bytes()).await
+                    || !write_all(&mut writer, &jpeg).await
+                    || !write_all(&mut writer, b"\r\n").await
+                {
+                    break;
+                }
+            }
+            // Viewers don't send anything after the request; a read only
+            // returns when the connection closes (even while no frames arrive)
+            read = reader.read(&mut discard) => {
+                if matches!(read, Ok(0) | Err(_)) {
+                    break;
+                }
+            }
+        }
+    }
+    info!(%peer, viewers = client_count() - 1, "MJPEG viewer disconnected");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_path() {
+        assert_eq!(
+            request_path("GET / HTTP/1.1\r\nHost: localhost\r\n\r\n"),
+            Some("/")
+        );
+        assert_eq!(request_path("GET /?t=123 HTTP/1.1\r\n\r\n"), Some("/"));
+        assert_eq!(
+            request_path("GET /favicon.ico HTTP/1.1\r\n\r\n"),
+            Some("/favicon.ico")
+        );
+        assert_eq!(request_path("POST / HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(request_path(""), None);
+    }
+
+    #[test]
+    fn test_localhost_by_default() {
+        assert!(bind_address(8090, false).ip().is_loopback());
+        assert!(bind_address(8090, true).ip().is_unspecified());
+        assert_eq!(
+            stream_url(bind_address(8090, false)),
+            "http://127.0.0.1:8090/"
+        );
+        assert!(part_head(42).starts_with(&format!("--{BOUNDARY}\r\n")));
+        assert!(part_head(42).ends_with("Content-Length: 42\r\n\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_disconnected_viewers_are_released() {
+        let listener = bind(0, false).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve(listener));
+
+        let mut viewer = TcpStream::connect(addr).await.unwrap();
+        viewer
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut head = [0u8; 12];
+        viewer.read_exact(&mut head).await.unwrap();
+        assert_eq!(&head, b"HTTP/1.1 200");
+        assert_eq!(client_count(), 1);
+
+        // A second viewer is over the limit and turned away
+        set_max_clients(1);
+        let mut refused = TcpStream::connect(addr).await.unwrap();
+        refused
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        refused.read_exact(&mut head).await.unwrap();
+        assert_eq!(&head, b"HTTP/1.1 503");
+        assert_eq!(client_count(), 1);
+        set_max_clients(crate::constants::http_stream::DEFAULT_MAX_CLIENTS);
+
+        drop(viewer);
+        for _ in 0..100 {
+            if client_count() == 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(client_count(), 0);
+        server.abort();
+    }
+}
@@ -0,0 +1,258 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Keeping the screen awake while the camera is in use
+//!
+//! Watching a live feed involves no input, so the compositor blanks the
+//! screen after a while and may even suspend in the middle of a recording.
+//! The desktop portal's Inhibit interface blocks both for as long as the
+//! request it returns stays open. Desktops without the portal get the
+//! `org.freedesktop.ScreenSaver` inhibition, which only covers blanking.
+//!
+//! Either inhibition ends when the [`IdleInhibitor`] is dropped: it is
+//! released explicitly when possible, and the session bus drops it anyway
+//! once the connection closes, including when the app exits.
+
+use crate::config::KeepAwake;
+use std::collections::HashMap;
+use tracing::{debug, info, warn};
+use zbus::zvariant::{OwnedObjectPath, Value};
+
+const PORTAL_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const PORTAL_INHIBIT_INTERFACE: &str = "org.freedesktop.portal.Inhibit";
+const PORTAL_REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+/// Portal inhibit flags: suspend (4) and idle (8)
+const PORTAL_INHIBIT_SUSPEND_AND_IDLE: u32 = 4 | 8;
+
+const SCREENSAVER_NAME: &str = "org.freedesktop.ScreenSaver";
+const SCREENSAVER_PATH: &str = "/org/freedesktop/ScreenSaver";
+
+/// What the camera is busy with right now
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Activity {
+    /// Frames are being shown in the preview
+    pub previewing: bool,
+    /// A video is being recorded
+    pub recording: bool,
+    /// The virtual camera or the MJPEG stream is feeding someone
+    pub streaming: bool,
+}
+
+/// Why the screen should stay awake, `None` when it may sleep
+///
+/// The reason is shown by the desktop, e.g. in its list of inhibitors.
+pub fn inhibit_reason(mode: KeepAwake, activity: Activity) -> Option<&'static str> {
+    match mode {
+        KeepAwake::Never => None,
+        KeepAwake::WhileRecording => activity.recording.then_some("Recording video"),
+        KeepAwake::Always => {
+            if activity.recording {
+                Some("Recording video")
+            } else if activity.streaming {
+                Some("Streaming the camera")
+            } else if activity.previewing {
+                Some("Showing the camera preview")
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Desktop interface an inhibition was taken with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InhibitBackend {
+    /// Desktop portal: blanking and suspend
+    Portal,
+    /// `org.freedesktop.ScreenSaver`: blanking only
+    ScreenSaver,
+}
+
+impl InhibitBackend {
+    /// Get display name for this backend
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            InhibitBackend::Portal => "Desktop portal",
+            InhibitBackend::ScreenSaver => "Screen saver",
+        }
+    }
+}
+
+/// Handle needed to end an inhibition
+#[derive(Debug)]
+enum Inhibition {
+    /// Portal request to close
+    Portal(OwnedObjectPath),
+    /// Screen saver cookie
+    ScreenSaver(u32),
+}
+
+/// Held screen blanking (and suspend) inhibition, released when dropped
+pub struct IdleInhibitor {
+    /// Dedicated session bus connection the inhibition is tied to
+    connection: zbus::Connection,
+    inhibition: Option<Inhibition>,
+}
+
+impl IdleInhibitor {
+    /// Keep the screen awake, giving `reason` to the desktop
+    ///
+    /// Tries the portal first and falls back to the screen saver interface.
+    pub async fn acquire(reason: &str) -> Result<Self, String> {
+        // A connection of its own, so closing it ends only this inhibition
+        let connection = zbus::Connection::session()
+            .await
+            .map_err(|e| format!("Failed to connect to session D-Bus: {}", e))?;
+
+        let inhibition = match inhibit_portal(&connection, reason).await {
+            Ok(handle) => Inhibition::Portal(handle),
+            Err(portal_err) => {
+                debug!(error = %portal_err, "Inhibit portal unavailable, trying the screen saver");
+                let cookie = inhibit_screensaver(&connection, reason)
+                    .await
+                    .map_err(|e| format!("{}; {}", portal_err, e))?;
+                Inhibition::ScreenSaver(cookie)
+            }
+        };
+        info!(reason, inhibition = ?inhibition, "Keeping the screen awake");
+        Ok(Self {
+            connection,
+            inhibition: Some(inhibition),
+        })
+    }
+
+    /// Interface the inhibition was taken with
+    pub fn backend(&self) -> InhibitBackend {
+        match self.inhibition {
+            Some(Inhibition::ScreenSaver(_)) => InhibitBackend::ScreenSaver,
+            _ => InhibitBackend::Portal,
+        }
+    }
+}
+
+impl Drop for IdleInhibitor {
+    fn drop(&mut self) {
+        let Some(inhibition) = self.inhibition.take() else {
+            return;
+        };
+        // Without a runtime (shutdown), closing the connection releases it
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let connection = self.connection.clone();
+        runtime.spawn(async move {
+            if let Err(err) = release(&connection, &inhibition).await {
+                warn!(error = %err, "Failed to release the idle inhibition");
+            } else {
+                info!("Screen may sleep again");
+            }
+        });
+    }
+}
+
+async fn inhibit_portal(
+    connection: &zbus::Connection,
+    reason: &str,
+) -> Result<OwnedObjectPath, String> {
+    let proxy = zbus::Proxy::new(
+        connection,
+        PORTAL_NAME,
+        PORTAL_PATH,
+        PORTAL_INHIBIT_INTERFACE,
+    )
+    .await
+    .map_err(|e| format!("Failed to create inhibit portal proxy: {}", e))?;
+    let options = HashMap::from([("reason", Value::from(reason))]);
+    proxy
+        .call("Inhibit", &("", PORTAL_INHIBIT_SUSPEND_AND_IDLE, options))
+        .await
+        .map_err(|e| format!("Inhibit portal call failed: {}", e))
+}
+
+async fn inhibit_screensaver(connection: &zbus::Connection, reason: &str) -> Result<u32, String> {
+    let proxy = zbus::Proxy::new(
+        connection,
+        SCREENSAVER_NAME,
+        SCREENSAVER_PATH,
+        SCREENSAVER_NAME,
+    )
+    .await
+    .map_err(|e| format!("Failed to create screen saver proxy: {}", e))?;
+    proxy
+        .call("Inhibit", &("Camera", reason))
+        .await
+        .map_err(|e| format!("Screen saver inhibit failed: {}", e))
+}
+
+async fn release(connection: &zbus::Connection, inhibition: &Inhibition) -> Result<(), String> {
+    match inhibition {
+        Inhibition::Portal(handle) => {
+            let request = zbus::Proxy::new(
+                connection,
+                PORTAL_NAME,
+                handle.clone(),
+                PORTAL_REQUEST_INTERFACE,
+            )
+            .await
+            .map_err(|e| format!("Failed to create portal request proxy: {}", e))?;
+            request
+                .call::<_, _, ()>("Close", &())
+                .await
+                .map_err(|e| format!("Failed to close inhibit request: {}", e))
+        }
+        Inhibition::ScreenSaver(cookie) => {
+            let proxy = zbus::Proxy::new(
+                connection,
+                SCREENSAVER_NAME,
+                SCREENSAVER_PATH,
+                SCREENSAVER_NAME,
+            )
+            .await
+            .map_err(|e| format!("Failed to create screen saver proxy: {}", e))?;
+            proxy
+                .call::<_, _, ()>("UnInhibit", &(*cookie,))
+                .await
+                .map_err(|e| format!("Screen saver uninhibit failed: {}", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inhibit_reason_follows_mode() {
+        let previewing = Activity {
+            previewing: true,
+            ..Activity::default()
+        };
+        let recording = Activity {
+            recording: true,
+            ..previewing
+        };
+        let streaming = Activity {
+            streaming: true,
+            ..Activity::default()
+        };
+        assert_eq!(
+            inhibit_reason(KeepAwake::Always, previewing),
+            Some("Showing the camera preview")
+        );
+        assert_eq!(
+            inhibit_reason(KeepAwake::Always, recording),
+            Some("Recording video")
+        );
+        assert_eq!(
+            inhibit_reason(KeepAwake::Always, streaming),
+            Some("Streaming the camera")
+        );
+        assert_eq!(inhibit_reason(KeepAwake::Always, Activity::default()), None);
+        assert_eq!(inhibit_reason(KeepAwake::WhileRecording, previewing), None);
+        assert_eq!(
+            inhibit_reason(KeepAwake::WhileRecording, recording),
+            Some("Recording video")
+        );
+        assert_eq!(inhibit_reason(KeepAwake::Never, recording), None);
+    }
+}
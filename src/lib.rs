@@ -14,6 +14,8 @@
 //! - [`media`]: Media encoding, decoding, and color conversion
 //! - [`pipelines`]: Photo and video capture pipelines
 //! - [`config`]: User configuration handling
+//! - [`engine`]: Capture without the UI, with a per-frame callback for embedding
+//! - [`events`]: Observer hooks for frame and state changes
 //! - [`storage`]: File storage and thumbnail management
 //!
 //! # Example
@@ -28,11 +30,17 @@ pub mod backends;
 pub mod bug_report;
 pub mod config;
 pub mod constants;
+pub mod control_socket;
+pub mod engine;
 pub mod errors;
+pub mod events;
 pub mod gpu;
+pub mod http_stream;
 pub mod i18n;
+pub mod idle_inhibit;
 pub mod media;
 pub mod network_manager;
+pub mod orientation;
 pub mod pipelines;
 pub mod shaders;
 pub mod storage;
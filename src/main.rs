@@ -21,6 +21,11 @@ struct Cli {
     /// Supported formats: PNG, JPG, JPEG, WEBP (images) or MP4, WEBM, MKV (videos)
     #[arg(long, value_name = "FILE")]
     preview_source: Option<PathBuf>,
+
+    /// Log a checksum of every decoded frame, to FILE or the log (RUST_LOG=info).
+    /// For comparing decoder output between runs.
+    #[arg(long, value_name = "FILE", num_args = 0..=1, require_equals = true, hide = true)]
+    frame_checksums: Option<Option<PathBuf>>,
 }
 
 #[derive(Subcommand)]
@@ -83,6 +88,15 @@ enum ProcessMode {
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
+    // Must come before anything else, see `export_gui_environment`
+    let failed_present_mode = if cli.command.is_none() {
+        export_gui_environment()
+    } else {
+        None
+    };
+
     // Initialize logging
     // Set RUST_LOG environment variable to control log level
     // Examples: RUST_LOG=debug, RUST_LOG=camera=debug, RUST_LOG=info
@@ -95,7 +109,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_level(true)
         .init();
 
-    let cli = Cli::parse();
+    if let Some(path) = &cli.frame_checksums {
+        camera::backends::camera::pipewire::checksum::enable(path.as_deref())?;
+    }
 
     match cli.command {
         Some(Commands::Terminal) => camera::terminal::run(),
@@ -110,11 +126,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Commands::Process { mode }) => match mode {
             ProcessMode::BurstMode { input, output } => cli::process_burst_mode(input, output),
         },
-        None => run_gui(cli.preview_source),
+        None => run_gui(cli.preview_source, failed_present_mode),
     }
 }
 
-fn run_gui(preview_source: Option<PathBuf>) -> Result<(), Box<dyn std::error::Error>> {
+fn run_gui(
+    preview_source: Option<PathBuf>,
+    failed_present_mode: Option<camera::config::PresentMode>,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Get the system's preferred languages.
     let requested_languages = i18n_embed::DesktopLanguageRequester::requested_languages();
 
@@ -122,11 +141,15 @@ fn run_gui(preview_source: Option<PathBuf>) -> Result<(), Box<dyn std::error::Er
     i18n::init(&requested_languages);
 
     // Settings for configuring the application window and iced runtime.
-    let mut settings = cosmic::app::Settings::default().size_limits(
-        cosmic::iced::Limits::NONE
-            .min_width(360.0)
-            .min_height(180.0),
-    );
+    // Closing the window finalizes recordings and releases the camera first
+    // (see `Message::CloseRequested`), which then exits the app
+    let mut settings = cosmic::app::Settings::default()
+        .size_limits(
+            cosmic::iced::Limits::NONE
+                .min_width(360.0)
+                .min_height(180.0),
+        )
+        .exit_on_close(false);
 
     // When preview source is provided, set optimal window size for Flathub screenshots
     // Flathub recommends 1000x700 or smaller for standard displays
@@ -134,6 +157,10 @@ fn run_gui(preview_source: Option<PathBuf>) -> Result<(), Box<dyn std::error::Er
         settings = settings.size(cosmic::iced::Size::new(900.0, 700.0));
     }
 
+    if let Some(failed) = failed_present_mode {
+        record_unsupported_present_mode(failed);
+    }
+
     // Create app flags with optional preview source
     let flags = camera::app::AppFlags { preview_source };
 
@@ -142,3 +169,74 @@ fn run_gui(preview_source: Option<PathBuf>) -> Result<(), Box<dyn std::error::Er
 
     Ok(())
 }
+
+/// Export the environment variables the GUI's libraries read from the config
+///
+/// `std::env::set_var` is only sound while no other thread may read the
+/// environment, so this runs first thing in `main`: before logging, the
+/// config watcher or any GStreamer and renderer thread exists. Nothing here
+/// can log for the same reason; returns the present mode the previous launch
+/// failed with, for `run_gui` to record.
+fn export_gui_environment() -> Option<camera::config::PresentMode> {
+    use camera::Config;
+    use cosmic::Application;
+    use cosmic::cosmic_config::{self, CosmicConfigEntry};
+
+    let mut config = cosmic_config::Config::new(AppModel::APP_ID, Config::VERSION)
+        .ok()
+        .map(|handler| Config::get_entry(&handler).unwrap_or_else(|(_, config)| config))
+        .unwrap_or_default();
+
+    // The renderer reads its present mode when creating the window surface
+    let failed_present_mode = if std::env::var_os("ICED_PRESENT_MODE").is_none() {
+        let failed = camera::config::PresentMode::take_failed();
+        config.unsupported_present_modes.extend(failed);
+        let present_mode = config
+            .present_mode
+            .resolve(&config.unsupported_present_modes);
+        // Only Mailbox can be rejected by the surface instead of falling back
+        if present_mode.needs_confirmation() {
+            present_mode.mark_pending();
+        }
+        // SAFETY: no other thread exists yet (see above)
+        unsafe { std::env::set_var("ICED_PRESENT_MODE", present_mode.env_value()) };
+        failed
+    } else {
+        None
+    };
+
+    // The legacy vaapi plugin reads its DRM device when it opens a display
+    if std::env::var_os("GST_VAAPI_DRM_DEVICE").is_none()
+        && let Some(path) = config
+            .vaapi_render_node
+            .filter(|path| std::path::Path::new(path).exists())
+    {
+        // SAFETY: no other thread exists yet (see above)
+        unsafe { std::env::set_var("GST_VAAPI_DRM_DEVICE", path) };
+    }
+
+    failed_present_mode
+}
+
+/// Remember a present mode that never confirmed its surface, so it isn't retried
+fn record_unsupported_present_mode(failed: camera::config::PresentMode) {
+    use camera::Config;
+    use cosmic::Application;
+    use cosmic::cosmic_config::{self, CosmicConfigEntry};
+
+    tracing::warn!(
+        ?failed,
+        "Present mode failed on the last launch, falling back"
+    );
+    let Ok(handler) = cosmic_config::Config::new(AppModel::APP_ID, Config::VERSION) else {
+        return;
+    };
+    let mut config = Config::get_entry(&handler).unwrap_or_else(|(_, config)| config);
+    if config.unsupported_present_modes.contains(&failed) {
+        return;
+    }
+    config.unsupported_present_modes.push(failed);
+    if let Err(err) = config.write_entry(&handler) {
+        tracing::error!(?err, "Failed to record unsupported present mode");
+    }
+}
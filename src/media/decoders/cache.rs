@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Decoder that last worked for each codec
+//!
+//! Picking a decoder walks the codec's whole chain through the registry
+//! (and, with startup validation, loads every candidate first), yet the
+//! winner rarely changes on a given machine. The decoder that last
+//! delivered frames is remembered per codec in the config and tried first
+//! when the next pipeline is built, once it is confirmed to be registered
+//! and not found broken.
+//!
+//! The cache never stands in the way of the fallback: it is dropped when
+//! the GStreamer version changes, since plugins may have come or gone, and a
+//! cached decoder whose pipeline fails to start is forgotten, so the next
+//! attempt probes the full chain again.
+
+use super::definitions::{DecoderDef, is_decoder_available};
+use crate::media::Codec;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use tracing::info;
+
+/// Decoders that last worked, as saved in the config
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DecoderCache {
+    /// GStreamer version the decoders were picked with
+    pub gstreamer_version: String,
+    /// Codec FourCC (e.g. "H264") and the decoder element that worked for it
+    pub decoders: Vec<(String, String)>,
+}
+
+impl DecoderCache {
+    /// The cache if it was filled under `version`, an empty one otherwise
+    fn for_version(self, version: &str) -> Self {
+        if self.gstreamer_version == version {
+            return self;
+        }
+        if !self.decoders.is_empty() {
+            info!(
+                cached = %self.gstreamer_version,
+                current = version,
+                "GStreamer version changed, forgetting cached decoders"
+            );
+        }
+        Self {
+            gstreamer_version: version.to_string(),
+            decoders: Vec::new(),
+        }
+    }
+
+    /// Decoder cached for `codec`
+    fn decoder(&self, codec: Codec) -> Option<&str> {
+        self.decoders
+            .iter()
+            .find(|(fourcc, _)| fourcc == codec.fourcc())
+            .map(|(_, decoder)| decoder.as_str())
+    }
+
+    /// Cache `decoder` for `codec`; returns false if it already was
+    fn remember(&mut self, codec: Codec, decoder: &str) -> bool {
+        if self.decoder(codec) == Some(decoder) {
+            return false;
+        }
+        self.forget(codec);
+        self.decoders
+            .push((codec.fourcc().to_string(), decoder.to_string()));
+        true
+    }
+
+    /// Drop the entry of `codec`; returns false if there was none
+    fn forget(&mut self, codec: Codec) -> bool {
+        let len = self.decoders.len();
+        self.decoders.retain(|(fourcc, _)| fourcc != codec.fourcc());
+        self.decoders.len() != len
+    }
+}
+
+/// How the running pipeline's decoder was chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecoderSource {
+    /// The decoder that last worked for the codec
+    Cache,
+    /// The first available decoder of the chain
+    Probed,
+}
+
+/// Cache in effect, `None` until loaded from the config
+static CACHE: RwLock<Option<DecoderCache>> = RwLock::new(None);
+
+/// Decoder of the pipeline built last and how it was chosen
+static ACTIVE: RwLock<Option<(Codec, &'static str, DecoderSource)>> = RwLock::new(None);
+
+/// Use the cache saved in the config, unless GStreamer changed since
+pub fn set_decoder_cache(cache: DecoderCache) {
+    let cache = cache.for_version(&gstreamer::version_string());
+    if let Ok(mut guard) = CACHE.write() {
+        *guard = Some(cache);
+    }
+}
+
+/// Forget every cached decoder (e.g. after the decoder preference changed)
+///
+/// Returns the emptied cache to save.
+pub fn clear_decoder_cache() -> DecoderCache {
+    let cache = DecoderCache::default().for_version(&gstreamer::version_string());
+    if let Ok(mut guard) = CACHE.write() {
+        *guard = Some(cache.clone());
+    }
+    set_active_decoder(None);
+    cache
+}
+
+/// The cached decoder of `codec`, if it's in `decoders` and still usable
+pub(super) fn cached_decoder(
+    codec: Codec,
+    decoders: &'static [DecoderDef],
+) -> Option<&'static DecoderDef> {
+    let guard = CACHE.read().ok()?;
+    let name = guard.as_ref()?.decoder(codec)?;
+    decoders
+        .iter()
+        .find(|decoder| decoder.name == name && is_decoder_available(decoder))
+}
+
+pub(super) fn set_active_decoder(active: Option<(Codec, &'static str, DecoderSource)>) {
+    if let Ok(mut guard) = ACTIVE.write() {
+        *guard = active;
+    }
+}
+
+/// How the decoder of the pipeline built last was chosen
+pub fn active_decoder_source() -> Option<DecoderSource> {
+    ACTIVE
+        .read()
+        .ok()
+        .and_then(|guard| guard.map(|(_, _, source)| source))
+}
+
+/// Remember the active decoder once its pipeline delivers frames
+///
+/// Returns the updated cache to save, `None` when nothing changed.
+pub fn remember_active_decoder() -> Option<DecoderCache> {
+    let (codec, decoder, _) = (*ACTIVE.read().ok()?)?;
+    let mut guard = CACHE.write().ok()?;
+    let cache = guard.as_mut()?;
+    cache.remember(codec, decoder).then(|| {
+        info!(codec = %codec, decoder, "Caching decoder for the next start");
+        cache.clone()
+    })
+}
+
+/// Forget the active decoder if it came from the cache and failed to start
+///
+/// Returns the updated cache to save, `None` when nothing changed.
+pub fn forget_failed_decoder() -> Option<DecoderCache> {
+    let (codec, decoder, source) = (*ACTIVE.read().ok()?)?;
+    if source != DecoderSource::Cache {
+        return None;
+    }
+    let mut guard = CACHE.write().ok()?;
+    let cache = guard.as_mut()?;
+    cache.forget(codec).then(|| {
+        info!(codec = %codec, decoder, "Cached decoder failed, probing the chain next time");
+        cache.clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_remembers_per_codec_and_version() {
+        let mut cache = DecoderCache::default().for_version("GStreamer 1.24.0");
+        assert!(cache.remember(Codec::H264, "vah264dec"));
+        assert!(!cache.remember(Codec::H264, "vah264dec"));
+        assert!(cache.remember(Codec::MJPEG, "jpegdec"));
+        assert!(cache.remember(Codec::H264, "avdec_h264"));
+        assert_eq!(cache.decoder(Codec::H264), Some("avdec_h264"));
+        assert_eq!(cache.decoders.len(), 2);
+
+        assert!(cache.forget(Codec::H264));
+        assert!(!cache.forget(Codec::H264));
+        assert_eq!(cache.decoder(Codec::H264), None);
+
+        // Kept under the same version, dropped after an upgrade
+        let kept = cache.clone().for_version("GStreamer 1.24.0");
+        assert_eq!(kept.decoder(Codec::MJPEG), Some("jpegdec"));
+        let upgraded = cache.for_version("GStreamer 1.26.0");
+        assert_eq!(upgraded.decoder(Codec::MJPEG), None);
+        assert_eq!(upgraded.gstreamer_version, "GStreamer 1.26.0");
+    }
+}
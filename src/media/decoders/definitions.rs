@@ -5,12 +5,17 @@
 //! This module provides a single source of truth for decoder preferences,
 //! used by both pipeline construction and the Insights diagnostic display.
 
+use std::sync::OnceLock;
+
+/// Decoders that are registered but failed to instantiate during startup validation
+static BROKEN_DECODERS: OnceLock<Vec<&'static str>> = OnceLock::new();
+
 /// Decoder definition with all metadata needed for pipeline construction and display
 #[derive(Debug, Clone, Copy)]
 pub struct DecoderDef {
     /// GStreamer element name (e.g., "jpegdec", "vah264dec")
     pub name: &'static str,
-    /// Human-readable description for UI display
+    /// English description for logs, and the UI fallback when no translation exists
     pub description: &'static str,
     /// Optional GStreamer properties (e.g., "max-errors=-1")
     pub props: Option<&'static str>,
@@ -46,11 +51,33 @@ impl DecoderDef {
         }
     }
 
+    /// Fluent ID of the translated description (e.g., "decoder-vah264dec")
+    pub fn fluent_id(&self) -> String {
+        format!("decoder-{}", self.name)
+    }
+
+    /// Description in the UI language
+    ///
+    /// Falls back to the English `description` when the localization has no
+    /// entry for this decoder.
+    pub fn localized_description(&self) -> String {
+        let loader = &*crate::i18n::LANGUAGE_LOADER;
+        let id = self.fluent_id();
+        if loader.has(&id) {
+            loader.get(&id)
+        } else {
+            self.description.to_string()
+        }
+    }
+
     /// Format as GStreamer element string (e.g., "jpegdec max-errors=-1")
+    ///
+    /// VA-API elements are mapped to the selected render node.
     pub fn as_gst_element(&self) -> String {
+        let name = super::resolve_decoder_element(self.name);
         match self.props {
-            Some(p) => format!("{} {}", self.name, p),
-            None => self.name.to_string(),
+            Some(p) => format!("{} {}", name, p),
+            None => name.to_string(),
         }
     }
 }
@@ -108,23 +135,134 @@ pub const H265_DECODERS: &[DecoderDef] = &[
     ),
 ];
 
+/// Whether a decoder is registered and passed startup validation
+pub fn is_decoder_available(decoder: &DecoderDef) -> bool {
+    let element = super::resolve_decoder_element(decoder.name);
+    gstreamer::ElementFactory::find(&element).is_some() && !is_decoder_broken(decoder.name)
+}
+
+/// First decoder from a list that's available on the system
+pub fn first_available_decoder(decoders: &'static [DecoderDef]) -> Option<&'static DecoderDef> {
+    decoders
+        .iter()
+        .find(|decoder| is_decoder_available(decoder))
+}
+
 /// Find the first available decoder from a list
 ///
 /// Returns the GStreamer element string for the first decoder that's available
 /// on the system, or "decodebin" as a last resort fallback.
-pub fn find_available_decoder(decoders: &[DecoderDef]) -> String {
-    for decoder in decoders {
-        if gstreamer::ElementFactory::find(decoder.name).is_some() {
-            let kind = if decoder.is_hardware {
-                "hardware"
-            } else {
-                "software"
-            };
-            tracing::info!(decoder = %decoder.name, kind, "Using {} decoder", decoder.description);
-            return decoder.as_gst_element();
-        }
+pub fn find_available_decoder(decoders: &'static [DecoderDef]) -> String {
+    if let Some(decoder) = first_available_decoder(decoders) {
+        return use_decoder(decoders, decoder);
     }
 
     tracing::warn!("No specific decoder found, using decodebin");
+    for problem in super::registry_problems() {
+        tracing::warn!(%problem, "Decoders may be hidden by the GStreamer registry");
+    }
+    emit_fallback(decoders, "decodebin");
     "decodebin".to_string()
 }
+
+/// GStreamer element string for `decoder`, picked from `decoders`
+pub fn use_decoder(decoders: &'static [DecoderDef], decoder: &DecoderDef) -> String {
+    let kind = if decoder.is_hardware {
+        "hardware"
+    } else {
+        "software"
+    };
+    let element = super::resolve_decoder_element(decoder.name);
+    tracing::info!(decoder = %element, kind, "Using {} decoder", decoder.description);
+    emit_fallback(decoders, decoder.name);
+    decoder.as_gst_element()
+}
+
+/// Tell observers when `selected` isn't the first decoder of the chain
+fn emit_fallback(decoders: &'static [DecoderDef], selected: &str) {
+    if let Some(preferred) = decoders.first()
+        && preferred.name != selected
+    {
+        crate::events::hub().emit(crate::events::CameraEvent::DecoderFallback {
+            preferred: preferred.name,
+            selected: selected.to_string(),
+        });
+    }
+}
+
+/// Try to actually instantiate every registered decoder
+///
+/// `ElementFactory::find` only proves a plugin is registered. Creating the
+/// element and bringing it to READY additionally loads its runtime libraries
+/// and opens hardware devices, which is where broken installs fail. Decoders
+/// that fail are skipped by [`find_available_decoder`] from then on.
+///
+/// Runs at most once per process; returns the names of broken decoders.
+pub fn validate_decoders() -> &'static [&'static str] {
+    use gstreamer::prelude::*;
+
+    BROKEN_DECODERS.get_or_init(|| {
+        let mut broken = Vec::new();
+        for decoder in MJPEG_DECODERS
+            .iter()
+            .chain(H264_DECODERS)
+            .chain(H265_DECODERS)
+        {
+            if gstreamer::ElementFactory::find(decoder.name).is_none()
+                || broken.contains(&decoder.name)
+            {
+                continue;
+            }
+
+            let instantiated = gstreamer::ElementFactory::make(decoder.name)
+                .build()
+                .map_err(|e| e.to_string())
+                .and_then(|element| {
+                    let result = element
+                        .set_state(gstreamer::State::Ready)
+                        .map(|_| ())
+                        .map_err(|e| e.to_string());
+                    let _ = element.set_state(gstreamer::State::Null);
+                    result
+                });
+
+            if let Err(e) = instantiated {
+                tracing::warn!(decoder = %decoder.name, error = %e, "Decoder is registered but failed to instantiate");
+                broken.push(decoder.name);
+            }
+        }
+        tracing::info!(broken = broken.len(), "Decoder validation complete");
+        broken
+    })
+}
+
+/// Whether startup validation found this decoder to be registered but unusable
+///
+/// Always false when validation hasn't run.
+pub fn is_decoder_broken(name: &str) -> bool {
+    BROKEN_DECODERS
+        .get()
+        .is_some_and(|broken| broken.iter().any(|b| *b == name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_descriptions_match_fallbacks() {
+        let loader = &*crate::i18n::LANGUAGE_LOADER;
+        for decoder in MJPEG_DECODERS
+            .iter()
+            .chain(H264_DECODERS)
+            .chain(H265_DECODERS)
+        {
+            assert!(
+                loader.has(&decoder.fluent_id()),
+                "missing {}",
+                decoder.fluent_id()
+            );
+            assert_eq!(decoder.localized_description(), decoder.description);
+        }
+    }
+}
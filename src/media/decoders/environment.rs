@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! GStreamer installation the app runs against
+//!
+//! Two machines with the same packages can still see different decoders
+//! when one of them loads plugins from somewhere else: a Flatpak runtime, a
+//! GStreamer built from source, or a `GST_PLUGIN_PATH` left over in the
+//! shell profile. The linked version, the directories plugins were actually
+//! loaded from and the environment variables that redirect the plugin scan
+//! are gathered once at startup for the insights drawer and the diagnostics
+//! bundle.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Gathered once per process (none of it changes while the app runs)
+static ENVIRONMENT: OnceLock<GstEnvironment> = OnceLock::new();
+
+/// Environment variables that change where plugins are scanned or cached
+const OVERRIDE_VARS: &[&str] = &[
+    "GST_PLUGIN_PATH_1_0",
+    "GST_PLUGIN_PATH",
+    "GST_PLUGIN_SYSTEM_PATH_1_0",
+    "GST_PLUGIN_SYSTEM_PATH",
+    "GST_REGISTRY_1_0",
+    "GST_REGISTRY",
+];
+
+/// Version and plugin locations of the linked GStreamer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GstEnvironment {
+    /// Version string of the linked library (e.g. "GStreamer 1.24.8")
+    pub version: String,
+    /// Plugin path and registry variables that are set, with their values
+    pub overrides: Vec<(&'static str, String)>,
+    /// Directories the registered plugins were loaded from, sorted
+    pub plugin_dirs: Vec<String>,
+}
+
+impl GstEnvironment {
+    /// Whether the plugin scan was redirected away from the system directories
+    pub fn uses_custom_plugin_path(&self) -> bool {
+        self.overrides
+            .iter()
+            .any(|(name, _)| name.starts_with("GST_PLUGIN"))
+    }
+}
+
+/// Shorten paths under the home directory to `~/...`
+fn tilde(path: &str, home: Option<&str>) -> String {
+    match home.and_then(|home| path.strip_prefix(home)) {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => format!("~{}", rest),
+        _ => path.to_string(),
+    }
+}
+
+/// Distinct directories of plugin files, sorted
+fn plugin_dirs<'a>(files: impl IntoIterator<Item = &'a Path>, home: Option<&str>) -> Vec<String> {
+    files
+        .into_iter()
+        .filter_map(Path::parent)
+        .map(|dir| tilde(&dir.to_string_lossy(), home))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Gather the GStreamer version and plugin locations
+///
+/// Runs at most once per process; later calls return the first result.
+pub fn detect_environment() -> &'static GstEnvironment {
+    ENVIRONMENT.get_or_init(|| {
+        let _ = gstreamer::init();
+        let home = std::env::var("HOME").ok();
+        let home = home.as_deref().filter(|home| !home.is_empty());
+
+        let overrides: Vec<(&'static str, String)> = OVERRIDE_VARS
+            .iter()
+            .filter_map(|&name| {
+                let value = std::env::var(name).ok()?;
+                let value = value
+                    .split(':')
+                    .map(|path| tilde(path, home))
+                    .collect::<Vec<_>>()
+                    .join(":");
+                Some((name, value))
+            })
+            .collect();
+
+        let files: Vec<_> = gstreamer::Registry::get()
+            .plugins()
+            .into_iter()
+            .filter_map(|plugin| plugin.filename())
+            .collect();
+        let environment = GstEnvironment {
+            version: gstreamer::version_string().to_string(),
+            overrides,
+            plugin_dirs: plugin_dirs(files.iter().map(|path| path.as_path()), home),
+        };
+
+        tracing::info!(
+            version = %environment.version,
+            plugin_dirs = ?environment.plugin_dirs,
+            overrides = ?environment.overrides,
+            "GStreamer environment"
+        );
+        if environment.uses_custom_plugin_path() {
+            tracing::warn!("GStreamer plugin path is overridden by the environment");
+        }
+        environment
+    })
+}
+
+/// Result of [`detect_environment`] (None when it hasn't run)
+pub fn environment() -> Option<&'static GstEnvironment> {
+    ENVIRONMENT.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_dirs_are_distinct_and_shortened() {
+        let files = [
+            Path::new("/usr/lib64/gstreamer-1.0/libgstlibav.so"),
+            Path::new("/home/user/gst/lib/gstreamer-1.0/libgstva.so"),
+            Path::new("/usr/lib64/gstreamer-1.0/libgstjpeg.so"),
+        ];
+        assert_eq!(
+            plugin_dirs(files, Some("/home/user")),
+            vec!["/usr/lib64/gstreamer-1.0", "~/gst/lib/gstreamer-1.0"]
+        );
+        // Only whole path components of the home directory are shortened
+        assert_eq!(
+            tilde("/home/username/gst", Some("/home/user")),
+            "/home/username/gst"
+        );
+    }
+
+    #[test]
+    fn test_custom_plugin_path_ignores_registry_override() {
+        let mut environment = GstEnvironment {
+            version: "GStreamer 1.24.8".to_string(),
+            overrides: vec![("GST_REGISTRY_1_0", "/tmp/registry.bin".to_string())],
+            plugin_dirs: Vec::new(),
+        };
+        assert!(!environment.uses_custom_plugin_path());
+        environment
+            .overrides
+            .push(("GST_PLUGIN_PATH", "~/gst/lib/gstreamer-1.0".to_string()));
+        assert!(environment.uses_custom_plugin_path());
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Pipeline construction errors
+//!
+//! GStreamer reports most start-up failures as a bus message from whichever
+//! element gave up, with an error domain and a debug string. These are
+//! sorted into the few cases the user can act on: a missing plugin, a
+//! decoder that exists but won't start, a camera that can't be opened and a
+//! format the camera refuses.
+
+use gstreamer as gst;
+use gstreamer::glib;
+use std::fmt;
+
+/// Why the capture pipeline could not be started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipelineError {
+    /// The camera source could not be opened (busy, gone or refused)
+    SourceUnavailable(String),
+    /// The camera and the rest of the pipeline agreed on no format
+    CapsNegotiationFailed(String),
+    /// A decoder is installed but failed to start (e.g. a broken VA-API driver)
+    DecoderInstantiationFailed { name: String },
+    /// A GStreamer element the pipeline needs is not installed
+    ElementMissing { name: String },
+    /// The pipeline description could not be parsed
+    ParseFailed(String),
+    /// The pipeline didn't reach PLAYING for another reason
+    StateChangeFailed(String),
+    /// The pipeline started but no frame arrived in time
+    FirstFrameTimeout { secs: u32 },
+}
+
+impl PipelineError {
+    /// Classify an error posted on the bus while the pipeline started
+    ///
+    /// `factory` and `klass` describe the element that posted it (klass as
+    /// in the factory metadata, e.g. "Codec/Decoder/Video").
+    pub fn from_bus_error(
+        error: &glib::Error,
+        debug: Option<&str>,
+        factory: Option<&str>,
+        klass: &str,
+    ) -> Self {
+        let message = error.message().to_string();
+        let not_negotiated = error.matches(gst::CoreError::Negotiation)
+            || error.matches(gst::StreamError::Format)
+            || debug.is_some_and(|debug| debug.contains("not-negotiated"));
+
+        if not_negotiated {
+            PipelineError::CapsNegotiationFailed(message)
+        } else if klass.contains("Decoder")
+            && let Some(name) = factory
+        {
+            PipelineError::DecoderInstantiationFailed {
+                name: name.to_string(),
+            }
+        } else if klass.contains("Source") || error.kind::<gst::ResourceError>().is_some() {
+            PipelineError::SourceUnavailable(message)
+        } else {
+            PipelineError::StateChangeFailed(message)
+        }
+    }
+
+    /// Whether trying again may help (PipeWire can briefly refuse a camera
+    /// while it switches modes, a missing plugin stays missing)
+    pub fn is_transient(&self) -> bool {
+        !matches!(
+            self,
+            PipelineError::ElementMissing { .. } | PipelineError::ParseFailed(_)
+        )
+    }
+
+    /// Whether another resolution may start where this one failed
+    ///
+    /// Bandwidth and driver limits show up as these; a busy camera or a
+    /// broken decoder fails the same way at any resolution.
+    pub fn may_depend_on_mode(&self) -> bool {
+        matches!(
+            self,
+            PipelineError::CapsNegotiationFailed(_)
+                | PipelineError::StateChangeFailed(_)
+                | PipelineError::FirstFrameTimeout { .. }
+        )
+    }
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineError::SourceUnavailable(msg) => {
+                write!(f, "Camera source unavailable: {}", msg)
+            }
+            PipelineError::CapsNegotiationFailed(msg) => {
+                write!(f, "Caps negotiation failed: {}", msg)
+            }
+            PipelineError::DecoderInstantiationFailed { name } => {
+                write!(f, "Decoder {} failed to start", name)
+            }
+            PipelineError::ElementMissing { name } => {
+                write!(f, "GStreamer element {} is not installed", name)
+            }
+            PipelineError::ParseFailed(msg) => write!(f, "Invalid pipeline: {}", msg),
+            PipelineError::StateChangeFailed(msg) => write!(f, "Pipeline failed to start: {}", msg),
+            PipelineError::FirstFrameTimeout { secs } => {
+                write!(f, "No frame within {} s", secs)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+/// Upstream module that ships a GStreamer element, for install hints
+///
+/// Distributions name the packages after these modules (e.g.
+/// gstreamer1-plugins-bad, gstreamer1.0-plugins-bad).
+pub fn plugin_package(element: &str) -> Option<&'static str> {
+    let package = match element {
+        "pipewiresrc" | "pipewiresink" => "pipewire-gstreamer",
+        name if name.starts_with("vaapi") => "gstreamer-vaapi",
+        name if name.starts_with("avdec_") => "gst-libav",
+        name if name.starts_with("va") || name.starts_with("nv") => "gst-plugins-bad",
+        "jpegparse" | "h264parse" | "h265parse" | "openh264dec" | "bayer2rgb" => "gst-plugins-bad",
+        "jpegdec" | "deinterlace" | "videocrop" | "videomixer" => "gst-plugins-good",
+        "videoconvert" | "videoscale" | "videorate" | "appsink" | "textoverlay"
+        | "clockoverlay" | "compositor" => "gst-plugins-base",
+        _ => return None,
+    };
+    Some(package)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bus_errors_are_classified() {
+        let _ = gst::init();
+        let busy = glib::Error::new(gst::ResourceError::Busy, "Device is busy");
+        assert_eq!(
+            PipelineError::from_bus_error(&busy, None, Some("pipewiresrc"), "Source/Video"),
+            PipelineError::SourceUnavailable("Device is busy".to_string())
+        );
+
+        let flow = glib::Error::new(gst::StreamError::Failed, "Internal data stream error.");
+        let debug = "streaming stopped, reason not-negotiated (-4)";
+        assert!(matches!(
+            PipelineError::from_bus_error(&flow, Some(debug), Some("pipewiresrc"), "Source/Video"),
+            PipelineError::CapsNegotiationFailed(_)
+        ));
+
+        let init = glib::Error::new(gst::LibraryError::Init, "Could not initialize VA");
+        assert_eq!(
+            PipelineError::from_bus_error(&init, None, Some("vah264dec"), "Codec/Decoder/Video"),
+            PipelineError::DecoderInstantiationFailed {
+                name: "vah264dec".to_string()
+            }
+        );
+        assert!(matches!(
+            PipelineError::from_bus_error(&init, None, Some("videoconvert"), "Filter/Converter"),
+            PipelineError::StateChangeFailed(_)
+        ));
+    }
+
+    #[test]
+    fn test_install_hints() {
+        assert_eq!(plugin_package("vaapijpegdec"), Some("gstreamer-vaapi"));
+        assert_eq!(plugin_package("vajpegdec"), Some("gst-plugins-bad"));
+        assert_eq!(plugin_package("avdec_mjpeg"), Some("gst-libav"));
+        assert_eq!(plugin_package("pipewiresrc"), Some("pipewire-gstreamer"));
+        assert_eq!(plugin_package("queue"), None);
+    }
+}
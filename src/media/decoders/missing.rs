@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Compressed formats no installed decoder can handle
+//!
+//! When every decoder of a codec's chain is missing, the pipeline falls
+//! back to decodebin, which finds nothing either and fails with an error
+//! that doesn't name the codec. Checking the chain after a failed start
+//! tells that case apart, so the preview can say which codec needs a
+//! decoder and which plugin sets provide one.
+
+use super::definitions::{DecoderDef, H264_DECODERS, H265_DECODERS, MJPEG_DECODERS};
+use super::{plugin_package, resolve_decoder_element};
+use crate::media::Codec;
+
+/// A compressed format without any installed decoder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingDecoder {
+    pub codec: Codec,
+    /// Plugin sets that ship a decoder for it, most useful first
+    pub packages: Vec<&'static str>,
+}
+
+/// Decoder chain tried for `codec`, `None` for raw formats
+pub fn decoder_chain(codec: Codec) -> Option<&'static [DecoderDef]> {
+    match codec {
+        Codec::MJPEG => Some(MJPEG_DECODERS),
+        Codec::H264 => Some(H264_DECODERS),
+        Codec::H265 => Some(H265_DECODERS),
+        _ => None,
+    }
+}
+
+/// The missing decoder for `pixel_format`, if nothing installed decodes it
+///
+/// Decoders that are installed but failed validation don't count as
+/// missing; their failure is reported as such.
+pub fn missing_decoder(pixel_format: &str) -> Option<MissingDecoder> {
+    let codec = Codec::from_fourcc(pixel_format);
+    let chain = decoder_chain(codec)?;
+    let installed = chain.iter().any(|decoder| {
+        gstreamer::ElementFactory::find(&resolve_decoder_element(decoder.name)).is_some()
+    });
+    (!installed).then(|| MissingDecoder {
+        codec,
+        packages: install_packages(chain),
+    })
+}
+
+/// Plugin sets providing the decoders of `chain`
+///
+/// Software decoders come first: they work without a GPU driver, so
+/// installing their package is the safest suggestion.
+fn install_packages(chain: &[DecoderDef]) -> Vec<&'static str> {
+    let mut packages = Vec::new();
+    let software = chain.iter().filter(|decoder| !decoder.is_hardware);
+    let hardware = chain.iter().filter(|decoder| decoder.is_hardware);
+    for decoder in software.chain(hardware) {
+        if let Some(package) = plugin_package(decoder.name)
+            && !packages.contains(&package)
+        {
+            packages.push(package);
+        }
+    }
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_packages_put_software_first() {
+        assert_eq!(
+            install_packages(H265_DECODERS),
+            vec!["gst-libav", "gst-plugins-bad", "gstreamer-vaapi"]
+        );
+        assert_eq!(
+            install_packages(MJPEG_DECODERS),
+            vec![
+                "gst-plugins-good",
+                "gst-libav",
+                "gstreamer-vaapi",
+                "gst-plugins-bad"
+            ]
+        );
+        assert!(decoder_chain(Codec::YUYV).is_none());
+        assert_eq!(missing_decoder("YUYV"), None);
+    }
+}
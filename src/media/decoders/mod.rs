@@ -5,13 +5,46 @@
 //! This module provides utilities for detecting and managing video decoders,
 //! particularly hardware-accelerated decoders for formats like MJPEG, H.264, etc.
 
+mod cache;
 mod definitions;
+mod environment;
+mod error;
 mod hardware;
+mod missing;
 mod pipeline;
+mod power;
+mod registry;
+mod render_node;
 
-pub use definitions::{DecoderDef, H264_DECODERS, H265_DECODERS, MJPEG_DECODERS};
+pub use cache::{
+    DecoderCache, DecoderSource, active_decoder_source, clear_decoder_cache, forget_failed_decoder,
+    remember_active_decoder, set_decoder_cache,
+};
+pub use definitions::{
+    DecoderDef, H264_DECODERS, H265_DECODERS, MJPEG_DECODERS, is_decoder_broken, validate_decoders,
+};
+pub use environment::{GstEnvironment, detect_environment, environment};
+pub use error::{PipelineError, plugin_package};
 pub use hardware::detect_hw_decoders;
-pub use pipeline::{get_full_pipeline_string, try_create_pipeline};
+pub use missing::{MissingDecoder, decoder_chain, missing_decoder};
+pub use pipeline::{
+    DecoderComparison, V4l2Capture, active_decode_threads, active_decoder_comparison,
+    active_mjpeg_resilience, active_parser, active_v4l2_capture, allow_size_changes,
+    compare_decoder, decode_threads, deinterlace_needs_rebuild, determine_pipewire_path,
+    forced_caps, get_full_pipeline_string, mjpeg_resilience, negotiated_dmabuf,
+    negotiated_forced_caps, note_source_interlacing, parse_forced_caps, preview_max_height,
+    set_compare_decoder, set_decode_threads, set_deinterlace_method, set_forced_caps,
+    set_mjpeg_resilience, set_preview_max_height, set_v4l2_io_mode, set_v4l2_node,
+    try_create_pipeline,
+};
+pub use power::{
+    efficiency_cores, low_power_decode, preferred_order, set_low_power_decode,
+    software_first_decode,
+};
+pub use registry::{RegistryProblem, check_registry, registry_problems};
+pub use render_node::{
+    RenderNode, list_render_nodes, render_node, resolve_decoder_element, set_render_node,
+};
 
 /// Pipeline backend selector
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -6,10 +6,15 @@
 //! with appropriate decoder selection and format negotiation.
 
 use super::PipelineBackend;
+use super::PipelineError;
+use super::cache::DecoderSource;
+use super::definitions::{DecoderDef, H264_DECODERS, H265_DECODERS, MJPEG_DECODERS};
 use crate::constants::{pipeline, timing};
+use crate::media::Codec;
 use gstreamer::prelude::*;
 use std::sync::RwLock;
-use tracing::{error, info, warn};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use tracing::{debug, error, info, warn};
 
 /// Format category for pipeline construction
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,6 +58,7 @@ fn get_format_category(pixel_format: Option<&str>) -> FormatCategory {
 
         // Shader-supported grayscale
         Some("GRAY8") | Some("GREY") | Some("Y8") => FormatCategory::ShaderSupported,
+        Some("GRAY16_LE") | Some("Y16") => FormatCategory::ShaderSupported,
 
         // Shader-supported RGB24 (no alpha)
         Some("RGB") | Some("BGR") => FormatCategory::ShaderSupported,
@@ -69,6 +75,747 @@ fn get_format_category(pixel_format: Option<&str>) -> FormatCategory {
 /// Full GStreamer pipeline string (for insights)
 static FULL_PIPELINE_STRING: RwLock<Option<String>> = RwLock::new(None);
 
+/// Deinterlace method for new pipelines (`None` = deinterlacing disabled)
+static DEINTERLACE_METHOD: RwLock<Option<&'static str>> = RwLock::new(Some("yadif"));
+
+/// Set the `deinterlace` method used by pipelines created from now on
+///
+/// `None` disables deinterlacing so raw fields reach the preview.
+pub fn set_deinterlace_method(method: Option<&'static str>) {
+    if let Ok(mut guard) = DEINTERLACE_METHOD.write() {
+        *guard = method;
+    }
+}
+
+/// PipeWire sources (their path property) whose caps declared interlacing
+static INTERLACED_SOURCES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Source of the pipeline built last
+static ACTIVE_SOURCE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Whether the pipeline built last deinterlaces
+static ACTIVE_DEINTERLACE: AtomicBool = AtomicBool::new(false);
+
+/// Record whether the source of the pipeline built last declares interlacing
+///
+/// Called from the source caps; pipelines for that source built from now on
+/// get a deinterlace stage only while it does.
+pub fn note_source_interlacing(interlaced: bool) {
+    let Some(source) = ACTIVE_SOURCE.read().ok().and_then(|guard| guard.clone()) else {
+        return;
+    };
+    if let Ok(mut guard) = INTERLACED_SOURCES.write() {
+        guard.retain(|known| *known != source);
+        if interlaced {
+            guard.push(source);
+        }
+    }
+}
+
+/// Whether the running pipeline lacks the deinterlace stage its source needs
+///
+/// True on the first pipeline of an interlaced source, since interlacing is
+/// only known once its caps are negotiated.
+pub fn deinterlace_needs_rebuild() -> bool {
+    let interlaced = ACTIVE_SOURCE
+        .read()
+        .ok()
+        .and_then(|guard| guard.clone())
+        .is_some_and(|source| is_interlaced_source(&source));
+    interlaced
+        && !ACTIVE_DEINTERLACE.load(Ordering::Relaxed)
+        && deinterlace_method().is_some_and(deinterlace_available)
+}
+
+fn is_interlaced_source(source: &str) -> bool {
+    INTERLACED_SOURCES
+        .read()
+        .is_ok_and(|guard| guard.iter().any(|known| known == source))
+}
+
+fn deinterlace_method() -> Option<&'static str> {
+    DEINTERLACE_METHOD.read().ok().and_then(|guard| *guard)
+}
+
+/// Whether `factory` is installed and its enum `property` knows `nick`
+fn enum_value_available(factory: &str, property: &str, nick: &str) -> bool {
+    let Ok(element) = gstreamer::ElementFactory::make(factory).build() else {
+        return false;
+    };
+    element
+        .find_property(property)
+        .and_then(|pspec| {
+            pspec
+                .downcast_ref::<gstreamer::glib::ParamSpecEnum>()
+                .map(|spec| spec.enum_class().value_by_nick(nick).is_some())
+        })
+        .unwrap_or(false)
+}
+
+/// Whether the `deinterlace` element is installed and knows `method`
+///
+/// The element lives in gst-plugins-good and older releases lack some
+/// methods; naming a missing one would make every raw pipeline fail to parse.
+fn deinterlace_available(method: &str) -> bool {
+    enum_value_available("deinterlace", "method", method)
+}
+
+/// V4L2 `io-mode` for new pipelines (None = capture through PipeWire)
+static V4L2_IO_MODE: RwLock<Option<&'static str>> = RwLock::new(None);
+
+/// V4L2 node of the camera new pipelines are built for
+static V4L2_NODE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Direct V4L2 capture of the pipeline built last (None = PipeWire)
+static ACTIVE_V4L2_CAPTURE: RwLock<Option<V4l2Capture>> = RwLock::new(None);
+
+/// Direct V4L2 capture with `v4l2src`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V4l2Capture {
+    /// io-mode chosen in the settings
+    pub requested: &'static str,
+    /// io-mode the source runs with (`auto` after a fallback)
+    pub io_mode: &'static str,
+}
+
+impl V4l2Capture {
+    /// Whether the requested io-mode was replaced by `auto`
+    pub fn fell_back(&self) -> bool {
+        self.requested != self.io_mode
+    }
+}
+
+/// Capture with `v4l2src` in `io_mode` for pipelines created from now on
+///
+/// `None` captures through PipeWire.
+pub fn set_v4l2_io_mode(io_mode: Option<&'static str>) {
+    if let Ok(mut guard) = V4L2_IO_MODE.write() {
+        *guard = io_mode;
+    }
+}
+
+/// Set the V4L2 node of the camera the next pipeline is built for
+pub fn set_v4l2_node(node: Option<String>) {
+    if let Ok(mut guard) = V4L2_NODE.write() {
+        *guard = node;
+    }
+}
+
+/// Direct V4L2 capture of the current pipeline (None = PipeWire)
+pub fn active_v4l2_capture() -> Option<V4l2Capture> {
+    ACTIVE_V4L2_CAPTURE.read().ok().and_then(|guard| *guard)
+}
+
+fn set_active_v4l2_capture(capture: Option<V4l2Capture>) {
+    if let Ok(mut guard) = ACTIVE_V4L2_CAPTURE.write() {
+        *guard = capture;
+    }
+}
+
+/// Source element of new pipelines, `pipewire_source` unless V4L2 is forced
+///
+/// With an io-mode chosen and a V4L2 node known, `v4l2src` opens the node
+/// directly. An io-mode the installed `v4l2src` doesn't know falls back to
+/// `auto`; without a node or `v4l2src` the camera stays on PipeWire.
+fn source_stage(
+    pipewire_source: &str,
+    io_mode: Option<&'static str>,
+    node: Option<&str>,
+) -> (String, Option<V4l2Capture>) {
+    let Some(requested) = io_mode else {
+        return (pipewire_source.to_string(), None);
+    };
+    let Some(node) = node else {
+        warn!(
+            io_mode = requested,
+            "Camera has no V4L2 node, capturing through PipeWire"
+        );
+        return (pipewire_source.to_string(), None);
+    };
+    if gstreamer::ElementFactory::find("v4l2src").is_none() {
+        warn!(
+            io_mode = requested,
+            "v4l2src not installed, capturing through PipeWire"
+        );
+        return (pipewire_source.to_string(), None);
+    }
+    let io_mode = if enum_value_available("v4l2src", "io-mode", requested) {
+        requested
+    } else {
+        warn!(
+            io_mode = requested,
+            "v4l2src doesn't know this io-mode, falling back to auto"
+        );
+        "auto"
+    };
+    info!(node, io_mode, "Capturing directly with v4l2src");
+    (
+        format!("v4l2src device={} io-mode={} ", node, io_mode),
+        Some(V4l2Capture { requested, io_mode }),
+    )
+}
+
+/// Pipeline string with the V4L2 io-mode of `capture` reset to `auto`
+///
+/// Used when the driver refuses the requested io-mode at startup. `None` when
+/// the pipeline doesn't capture with a forced io-mode.
+fn v4l2_io_mode_fallback(
+    pipeline_str: &str,
+    capture: Option<V4l2Capture>,
+) -> Option<(String, V4l2Capture)> {
+    let capture = capture.filter(|capture| capture.io_mode != "auto")?;
+    Some((
+        pipeline_str.replacen(&format!("io-mode={} ", capture.io_mode), "io-mode=auto ", 1),
+        V4l2Capture {
+            io_mode: "auto",
+            ..capture
+        },
+    ))
+}
+
+/// Deinterlace stage for raw YUV pipelines of `source`
+///
+/// Only added for sources whose caps declared interlacing, and only when the
+/// element and the chosen method are available; otherwise frames reach the
+/// preview as they are.
+fn deinterlace_stage(source: &str) -> String {
+    let Some(method) = deinterlace_method() else {
+        return String::new();
+    };
+    if !is_interlaced_source(source) {
+        return String::new();
+    }
+    if !deinterlace_available(method) {
+        warn!(
+            method,
+            "Deinterlacing unavailable (missing element or method), showing raw fields"
+        );
+        return String::new();
+    }
+    ACTIVE_DEINTERLACE.store(true, Ordering::Relaxed);
+    format!(
+        "deinterlace name=deinterlace method={} mode=auto ! ",
+        method
+    )
+}
+
+/// Maximum preview height for new pipelines (0 = uncapped)
+static PREVIEW_MAX_HEIGHT: AtomicU32 = AtomicU32::new(0);
+
+/// Cap the height of decoded preview frames for pipelines created from now on
+///
+/// Sources already at or below the cap pass through `videoscale` untouched.
+pub fn set_preview_max_height(max_height: Option<u32>) {
+    PREVIEW_MAX_HEIGHT.store(max_height.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Get the preview height cap in effect for new pipelines
+pub fn preview_max_height() -> Option<u32> {
+    match PREVIEW_MAX_HEIGHT.load(Ordering::Relaxed) {
+        0 => None,
+        height => Some(height),
+    }
+}
+
+/// Downscale stage for decoded preview frames
+///
+/// `videoscale` fixates the width so the display aspect ratio is kept.
+fn downscale_stage() -> String {
+    match preview_max_height() {
+        Some(height) => format!(
+            "videoscale name=preview-scale ! \
+             video/x-raw,height=(int)[1,{}],pixel-aspect-ratio=(fraction)1/1 ! ",
+            height
+        ),
+        None => String::new(),
+    }
+}
+
+/// Caps forced in front of the appsink for new pipelines (debugging escape hatch)
+static FORCED_CAPS: RwLock<Option<String>> = RwLock::new(None);
+
+/// Name of the capsfilter inserted for forced caps
+const FORCED_CAPS_ELEMENT: &str = "forced-caps";
+
+/// Raw formats the appsink callback can hand to the GPU
+const PREVIEW_FORMATS: &[&str] = &[
+    "NV12",
+    "NV21",
+    "I420",
+    "YV12",
+    "YUY2",
+    "UYVY",
+    "YVYU",
+    "VYUY",
+    "GRAY8",
+    "GRAY16_LE",
+    "RGBA",
+    "RGBx",
+    "BGRx",
+    "BGRA",
+    "ARGB",
+    "ABGR",
+    "xRGB",
+    "xBGR",
+    "RGB",
+    "BGR",
+];
+
+/// Parse and validate a caps string for [`set_forced_caps`]
+///
+/// Only `video/x-raw` caps are accepted, and a `format` field (if any) must be
+/// one the preview can display, since the caps sit directly in front of the appsink.
+pub fn parse_forced_caps(text: &str) -> Result<gstreamer::Caps, String> {
+    use std::str::FromStr;
+
+    let caps = gstreamer::Caps::from_str(text.trim())
+        .map_err(|_| format!("invalid caps: {}", text.trim()))?;
+    if caps.is_any() || caps.is_empty() {
+        return Err("caps must describe a format".to_string());
+    }
+    for structure in caps.iter() {
+        if structure.name().as_str() != "video/x-raw" {
+            return Err(format!(
+                "{} is not supported here, use video/x-raw",
+                structure.name()
+            ));
+        }
+        if let Ok(value) = structure.value("format") {
+            for format in caps_formats(value)? {
+                if !PREVIEW_FORMATS.contains(&format.as_str()) {
+                    return Err(format!("format {} can't be shown by the preview", format));
+                }
+            }
+        }
+    }
+    Ok(caps)
+}
+
+/// Formats named by a caps `format` field, a single string or a `{..}` list
+fn caps_formats(value: &gstreamer::glib::SendValue) -> Result<Vec<String>, String> {
+    if let Ok(format) = value.get::<String>() {
+        return Ok(vec![format]);
+    }
+    let list = value
+        .get::<gstreamer::List>()
+        .map_err(|_| "format must be a format name or a list of them".to_string())?;
+    list.iter()
+        .map(|entry| {
+            entry
+                .get::<String>()
+                .map_err(|_| "format list must only contain format names".to_string())
+        })
+        .collect()
+}
+
+/// Force caps in front of the appsink for pipelines created from now on
+///
+/// `None` restores automatic negotiation.
+pub fn set_forced_caps(caps: Option<&gstreamer::Caps>) {
+    if let Ok(mut guard) = FORCED_CAPS.write() {
+        *guard = caps.map(|caps| caps.to_string());
+    }
+}
+
+/// Caps currently forced for new pipelines
+pub fn forced_caps() -> Option<String> {
+    FORCED_CAPS.read().ok().and_then(|guard| guard.clone())
+}
+
+/// Conversion and capsfilter stage for forced caps
+fn forced_caps_stage() -> String {
+    match forced_caps() {
+        Some(caps) => format!(
+            "videoconvert n-threads={} ! videoscale ! capsfilter name={} caps=\"{}\" ! ",
+            pipeline::videoconvert_threads(),
+            FORCED_CAPS_ELEMENT,
+            caps.replace('"', "\\\"")
+        ),
+        None => String::new(),
+    }
+}
+
+/// Caps negotiated on the forced-caps filter, if the pipeline has one
+pub fn negotiated_forced_caps(pipeline: &gstreamer::Pipeline) -> Option<String> {
+    pipeline
+        .by_name(FORCED_CAPS_ELEMENT)?
+        .static_pad("src")?
+        .current_caps()
+        .map(|caps| caps.to_string())
+}
+
+/// Whether the appsink negotiated DMABUF memory, i.e. frames could reach the
+/// GPU without passing through CPU memory
+pub fn negotiated_dmabuf(pipeline: &gstreamer::Pipeline) -> bool {
+    pipeline
+        .by_name("sink")
+        .and_then(|sink| sink.static_pad("sink"))
+        .and_then(|pad| pad.current_caps())
+        .and_then(|caps| {
+            caps.features(0)
+                .map(|features| features.contains("memory:DMABuf"))
+        })
+        .unwrap_or(false)
+}
+
+/// Decoder compared against the selected one in new pipelines (diagnostic)
+static COMPARE_DECODER: RwLock<Option<&'static str>> = RwLock::new(None);
+
+/// Comparison built into the most recently created pipeline
+static ACTIVE_COMPARISON: RwLock<Option<DecoderComparison>> = RwLock::new(None);
+
+/// Names of the tee and compositor of a decoder comparison
+const COMPARE_TEE_ELEMENT: &str = "compare-tee";
+const COMPARE_MIX_ELEMENT: &str = "compare-mix";
+
+/// Two decoders decoding the same stream, each shown on one half of the frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecoderComparison {
+    /// Decoder of the left half (the one normally selected)
+    pub left: &'static str,
+    /// Decoder of the right half
+    pub right: &'static str,
+}
+
+/// Compare `decoder` against the selected decoder in pipelines created from now on
+///
+/// Only pipelines whose codec `decoder` handles are affected. `None` ends
+/// the comparison.
+pub fn set_compare_decoder(decoder: Option<&'static str>) {
+    if let Ok(mut guard) = COMPARE_DECODER.write() {
+        *guard = decoder;
+    }
+}
+
+/// Decoder set up for comparison, if any
+pub fn compare_decoder() -> Option<&'static str> {
+    COMPARE_DECODER.read().ok().and_then(|guard| *guard)
+}
+
+/// Comparison the running pipeline was built with, if any
+pub fn active_decoder_comparison() -> Option<DecoderComparison> {
+    ACTIVE_COMPARISON.read().ok().and_then(|guard| *guard)
+}
+
+fn set_active_comparison(comparison: Option<DecoderComparison>) {
+    if let Ok(mut guard) = ACTIVE_COMPARISON.write() {
+        *guard = comparison;
+    }
+}
+
+/// Whether new MJPEG pipelines decode with error resilience
+static MJPEG_RESILIENCE: AtomicBool = AtomicBool::new(false);
+
+/// Resilience properties the running pipeline's MJPEG decoder was built with
+static ACTIVE_MJPEG_RESILIENCE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Decode MJPEG with error resilience in pipelines created from now on
+///
+/// Meant for webcams whose MJPEG has missing or misplaced restart markers.
+/// Such frames decode with the rest of the image after the damage filled
+/// in green or gray; with resilience on they're dropped instead, so the
+/// preview holds the previous frame for a moment.
+pub fn set_mjpeg_resilience(enabled: bool) {
+    MJPEG_RESILIENCE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether new MJPEG pipelines decode with error resilience
+pub fn mjpeg_resilience() -> bool {
+    MJPEG_RESILIENCE.load(Ordering::Relaxed)
+}
+
+/// Resilience properties set on the running pipeline's decoder, if any
+pub fn active_mjpeg_resilience() -> Option<String> {
+    ACTIVE_MJPEG_RESILIENCE
+        .read()
+        .ok()
+        .and_then(|guard| guard.clone())
+}
+
+fn set_active_mjpeg_resilience(props: Option<String>) {
+    if let Ok(mut guard) = ACTIVE_MJPEG_RESILIENCE.write() {
+        *guard = props;
+    }
+}
+
+/// Error-resilience properties for an MJPEG decoder, most effective first
+///
+/// `jpegparse` always sits in front of the decoder: it re-frames the stream
+/// on SOI/EOI markers, so split or concatenated buffers reach the decoder as
+/// whole images. What it can't fix is damage inside the entropy-coded data,
+/// which is where missing restart markers leave the decoder. Combinations:
+///
+/// - `avdec_mjpeg output-corrupt=false`: FFmpeg flags frames it had to
+///   conceal (truncated scans, restart markers out of sequence) and they're
+///   dropped. This is the combination that removes the green blocks.
+/// - `discard-corrupted-frames=true` (any `GstVideoDecoder`, GStreamer
+///   1.20+): drops frames the decoder marks as corrupted. It only helps as
+///   far as the decoder detects damage; `jpegdec` with `max-errors=-1` keeps
+///   decoding past libjpeg warnings without marking the frame, so fewer bad
+///   frames are caught than with `avdec_mjpeg`.
+/// - `jpegdec max-errors=-1` alone (the default without resilience) keeps the
+///   stream running through broken frames but shows them as decoded.
+fn resilience_props(decoder: &str) -> &'static [&'static str] {
+    match decoder {
+        "avdec_mjpeg" => &["output-corrupt=false", "discard-corrupted-frames=true"],
+        _ => &["discard-corrupted-frames=true"],
+    }
+}
+
+/// Append the resilience properties `decoder` supports to its element string
+///
+/// Properties the installed element lacks (e.g. on GStreamer older than
+/// 1.20) are left out, since unknown properties fail the whole launch line.
+fn with_resilience(element: String, decoder: &DecoderDef) -> String {
+    let resolved = super::resolve_decoder_element(decoder.name);
+    let Ok(instance) = gstreamer::ElementFactory::make(&resolved).build() else {
+        return element;
+    };
+    let supported: Vec<&str> = resilience_props(decoder.name)
+        .iter()
+        .copied()
+        .filter(|prop| {
+            prop.split_once('=')
+                .is_some_and(|(name, _)| instance.find_property(name).is_some())
+        })
+        .collect();
+    if supported.is_empty() {
+        return element;
+    }
+    let props = supported.join(" ");
+    info!(decoder = %resolved, %props, "MJPEG error resilience enabled");
+    set_active_mjpeg_resilience(Some(props.clone()));
+    format!("{} {}", element, props)
+}
+
+/// Thread cap for software decoders in new pipelines (0 = automatic)
+static DECODE_THREADS: AtomicU32 = AtomicU32::new(0);
+
+/// `max-threads` of the running pipeline's software decoder (0 = automatic)
+static ACTIVE_DECODE_THREADS: RwLock<Option<u32>> = RwLock::new(None);
+
+/// Decoder property capping the decode threads (FFmpeg decoders)
+const THREADS_PROPERTY: &str = "max-threads";
+
+/// Cap the threads of software decoders in pipelines created from now on
+///
+/// `None` lets the decoder pick (one thread per CPU). Only decoders with a
+/// thread setting in their definition (`avdec_h264`, `avdec_h265`) are affected.
+pub fn set_decode_threads(threads: Option<u32>) {
+    DECODE_THREADS.store(threads.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Thread cap for software decoders in new pipelines (`None` = automatic)
+pub fn decode_threads() -> Option<u32> {
+    Some(DECODE_THREADS.load(Ordering::Relaxed)).filter(|&threads| threads > 0)
+}
+
+/// `max-threads` the running pipeline's decoder was built with (0 = automatic)
+///
+/// `None` when the running pipeline has no thread-capable software decoder.
+pub fn active_decode_threads() -> Option<u32> {
+    ACTIVE_DECODE_THREADS.read().ok().and_then(|guard| *guard)
+}
+
+fn set_active_decode_threads(threads: Option<u32>) {
+    if let Ok(mut guard) = ACTIVE_DECODE_THREADS.write() {
+        *guard = threads;
+    }
+}
+
+/// Whether the installed `element` accepts `threads` for its thread property
+fn threads_in_range(element: &str, threads: u32) -> bool {
+    let Ok(instance) = gstreamer::ElementFactory::make(element).build() else {
+        return false;
+    };
+    let Some(pspec) = instance.find_property(THREADS_PROPERTY) else {
+        return false;
+    };
+    let Some(range) = pspec.downcast_ref::<gstreamer::glib::ParamSpecInt>() else {
+        return false;
+    };
+    i32::try_from(threads)
+        .is_ok_and(|threads| (range.minimum()..=range.maximum()).contains(&threads))
+}
+
+/// Apply the decode thread cap to `decoder`'s element string
+///
+/// The cap is checked against the installed element's property range first;
+/// an out-of-range value would fail the whole launch line, so the decoder
+/// then keeps its automatic thread count.
+fn with_thread_limit(element: String, decoder: &DecoderDef) -> String {
+    let default = format!("{}=0", THREADS_PROPERTY);
+    if !decoder.props.is_some_and(|props| props.contains(&default)) {
+        return element;
+    }
+    let threads = match decode_threads() {
+        Some(threads) if threads_in_range(decoder.name, threads) => threads,
+        Some(threads) => {
+            warn!(
+                decoder = decoder.name,
+                threads, "Decode thread cap out of the decoder's range, using automatic"
+            );
+            0
+        }
+        None => 0,
+    };
+    set_active_decode_threads(Some(threads));
+    if threads == 0 {
+        return element;
+    }
+    info!(decoder = decoder.name, threads, "Decode threads capped");
+    element.replace(&default, &format!("{}={}", THREADS_PROPERTY, threads))
+}
+
+/// Integer field of a caps filter string (e.g. `width` in "width=(int)1280,...")
+fn caps_int(caps_filter: &str, field: &str) -> Option<u32> {
+    caps_filter.split(',').find_map(|part| {
+        part.trim()
+            .strip_prefix(field)?
+            .strip_prefix("=(int)")?
+            .parse()
+            .ok()
+    })
+}
+
+/// Parser stage of the running pipeline (`None` when it has none)
+static ACTIVE_PARSER: RwLock<Option<&'static str>> = RwLock::new(None);
+
+/// Parser stage that goes directly ahead of the decoder for `codec`
+///
+/// H.264 and H.265 from some sources arrive packetized (avc/hvc1) or split
+/// at NAL units rather than whole access units, which hardware decoders in
+/// particular don't accept. The parser turns the stream into AU-aligned
+/// byte-stream and repeats SPS/PPS before each keyframe, so the decoder can
+/// start mid-stream. Raw formats and MJPEG get no parser here.
+fn parser_stage(codec: Codec) -> Option<&'static str> {
+    match codec {
+        Codec::H264 => Some(
+            "h264parse config-interval=-1 ! video/x-h264,stream-format=byte-stream,alignment=au",
+        ),
+        Codec::H265 => Some(
+            "h265parse config-interval=-1 ! video/x-h265,stream-format=byte-stream,alignment=au",
+        ),
+        _ => None,
+    }
+}
+
+/// Parser stage ahead of the running pipeline's decoder
+///
+/// `None` when the running pipeline decodes a format without a parser stage.
+pub fn active_parser() -> Option<&'static str> {
+    ACTIVE_PARSER.read().ok().and_then(|guard| *guard)
+}
+
+fn set_active_parser(parser: Option<&'static str>) {
+    if let Ok(mut guard) = ACTIVE_PARSER.write() {
+        *guard = parser;
+    }
+}
+
+/// `decoder_chain` with the parser `codec` needs ahead of it, if any
+fn with_parser(codec: Codec, decoder_chain: &str) -> String {
+    let parser = parser_stage(codec);
+    set_active_parser(parser);
+    match parser {
+        Some(parser) => format!("{} ! {}", parser, decoder_chain),
+        None => decoder_chain.to_string(),
+    }
+}
+
+/// Decode stage for an encoded stream
+///
+/// Normally just the first available decoder, in the order the power
+/// preference asks for. While a decoder of this codec is set up for
+/// comparison, returns a split-screen stage instead, plus the branch to
+/// append after the main chain (empty otherwise). `resilient` adds the
+/// decoders' error-resilience properties (MJPEG only).
+fn decode_stage(
+    codec: Codec,
+    decoders: &'static [DecoderDef],
+    caps_filter: &str,
+    resilient: bool,
+) -> (String, String) {
+    let decoders = super::power::preferred_order(decoders);
+    let element = |decoder: &DecoderDef, element: String| {
+        let element = with_thread_limit(element, decoder);
+        if resilient {
+            with_resilience(element, decoder)
+        } else {
+            element
+        }
+    };
+    // The decoder that last worked skips probing the chain
+    let cached = super::cache::cached_decoder(codec, decoders);
+    let selected = cached.or_else(|| super::definitions::first_available_decoder(decoders));
+    super::cache::set_active_decoder(selected.map(|decoder| {
+        let source = if cached.is_some() {
+            DecoderSource::Cache
+        } else {
+            DecoderSource::Probed
+        };
+        (codec, decoder.name, source)
+    }));
+    let compared = compare_decoder()
+        .and_then(|name| decoders.iter().find(|decoder| decoder.name == name))
+        .filter(|decoder| super::definitions::is_decoder_available(decoder));
+    match (selected, compared, caps_int(caps_filter, "width")) {
+        (Some(left), Some(right), Some(width)) if left.name != right.name => {
+            info!(
+                left = left.name,
+                right = right.name,
+                "Decoder comparison: decoding the stream twice"
+            );
+            set_active_comparison(Some(DecoderComparison {
+                left: left.name,
+                right: right.name,
+            }));
+            comparison_stage(
+                &element(left, left.as_gst_element()),
+                &element(right, right.as_gst_element()),
+                width,
+            )
+        }
+        _ => {
+            let chain = match selected {
+                Some(decoder) => {
+                    element(decoder, super::definitions::use_decoder(decoders, decoder))
+                }
+                None => super::definitions::find_available_decoder(decoders),
+            };
+            (chain, String::new())
+        }
+    }
+}
+
+/// Split-screen stage decoding the stream with two decoders
+///
+/// The parsed stream is teed to both decoders. The left decoder's output is
+/// cropped to the left half and the right decoder's to the right half, and the
+/// halves are composited back into one frame of the original size. The right
+/// branch joins the compositor by name, so it's returned separately.
+fn comparison_stage(left: &str, right: &str, width: u32) -> (String, String) {
+    // Even split so 4:2:0 chroma isn't cropped mid-sample
+    let half = (width / 2) & !1;
+    let threads = pipeline::videoconvert_threads();
+    let stage = format!(
+        "tee name={tee} ! queue ! {left} ! videoconvert n-threads={threads} ! \
+         videocrop right={right_crop} ! \
+         compositor name={mix} sink_1::xpos={half} ! \
+         videoconvert n-threads={threads} ! video/x-raw,format=NV12",
+        tee = COMPARE_TEE_ELEMENT,
+        mix = COMPARE_MIX_ELEMENT,
+        right_crop = width - half,
+    );
+    let branch = format!(
+        " {tee}. ! queue ! {right} ! videoconvert n-threads={threads} ! \
+         videocrop left={half} ! {mix}.",
+        tee = COMPARE_TEE_ELEMENT,
+        mix = COMPARE_MIX_ELEMENT,
+    );
+    (stage, branch)
+}
+
 /// Get the full GStreamer pipeline string
 pub fn get_full_pipeline_string() -> Option<String> {
     FULL_PIPELINE_STRING
@@ -94,14 +841,14 @@ pub fn get_full_pipeline_string() -> Option<String> {
 ///
 /// # Returns
 /// * `Ok(Pipeline)` - Successfully created and started pipeline
-/// * `Err` - Pipeline creation or startup failed
+/// * `Err` - Why creation or startup failed
 pub fn try_create_pipeline(
     device_path: Option<&str>,
     caps_filter: &str,
     _decoder: &str,
     pixel_format: Option<&str>,
     _backend: PipelineBackend,
-) -> Result<gstreamer::Pipeline, Box<dyn std::error::Error>> {
+) -> Result<gstreamer::Pipeline, PipelineError> {
     try_create_pipewire_pipeline(device_path, caps_filter, pixel_format)
 }
 
@@ -115,11 +862,13 @@ fn try_create_pipewire_pipeline(
     device_path: Option<&str>,
     caps_filter: &str,
     pixel_format: Option<&str>,
-) -> Result<gstreamer::Pipeline, Box<dyn std::error::Error>> {
+) -> Result<gstreamer::Pipeline, PipelineError> {
     // Check if PipeWire source is available
-    gstreamer::ElementFactory::make("pipewiresrc")
-        .build()
-        .map_err(|e| format!("pipewiresrc not available: {}", e))?;
+    if gstreamer::ElementFactory::find("pipewiresrc").is_none() {
+        return Err(PipelineError::ElementMissing {
+            name: "pipewiresrc".to_string(),
+        });
+    }
 
     info!("✓ PipeWire available - creating camera pipeline");
 
@@ -131,20 +880,68 @@ fn try_create_pipewire_pipeline(
 
     // Build PipeWire pipeline based on pixel format
     // Note: Rotation is handled by the GPU shader for better performance
+    set_active_comparison(None);
+    set_active_mjpeg_resilience(None);
+    set_active_decode_threads(None);
+    set_active_parser(None);
+    super::cache::set_active_decoder(None);
+    ACTIVE_DEINTERLACE.store(false, Ordering::Relaxed);
+    if let Ok(mut guard) = ACTIVE_SOURCE.write() {
+        *guard = Some(pw_path_prop.clone());
+    }
     let pipewire_pipeline =
         build_pipewire_pipeline_string(&pw_path_prop, caps_filter, pixel_format);
 
+    // Forcing a V4L2 io-mode swaps PipeWire for v4l2src on the camera's node
+    let pipewire_source = format!("pipewiresrc {}", pw_path_prop);
+    let io_mode = V4L2_IO_MODE.read().ok().and_then(|guard| *guard);
+    let node = V4L2_NODE.read().ok().and_then(|guard| guard.clone());
+    let (source, capture) = source_stage(&pipewire_source, io_mode, node.as_deref());
+    set_active_v4l2_capture(capture);
+    let pipewire_pipeline = pipewire_pipeline.replacen(&pipewire_source, &source, 1);
+
+    // Forced caps override whatever the format-specific pipeline would hand the appsink
+    let forced_caps = forced_caps_stage();
+    let pipewire_pipeline = if forced_caps.is_empty() {
+        pipewire_pipeline
+    } else {
+        warn!(stage = %forced_caps, "Forcing caps in front of the appsink");
+        pipewire_pipeline.replacen(
+            "appsink name=sink",
+            &format!("{}appsink name=sink", forced_caps),
+            1,
+        )
+    };
+
     // Store full pipeline string for insights
     if let Ok(mut guard) = FULL_PIPELINE_STRING.write() {
         *guard = Some(pipewire_pipeline.clone());
     }
 
     // Try launching with retries to handle PipeWire race conditions
+    let mut pipewire_pipeline = pipewire_pipeline;
     let mut last_error = None;
     for attempt in 1..=PIPELINE_CREATE_RETRIES {
         info!(pipeline = %pipewire_pipeline, attempt, "Attempting to launch pipeline");
         match try_launch_pipeline_with_bus_errors(&pipewire_pipeline) {
             Ok(pipeline) => return Ok(pipeline),
+            Err(e) if !e.is_transient() => {
+                // The driver may refuse a forced io-mode; let v4l2src pick one
+                if let Some((fallback, capture)) =
+                    v4l2_io_mode_fallback(&pipewire_pipeline, active_v4l2_capture())
+                {
+                    warn!(error = %e, "V4L2 io-mode refused, falling back to auto");
+                    set_active_v4l2_capture(Some(capture));
+                    pipewire_pipeline = fallback;
+                    if let Ok(mut guard) = FULL_PIPELINE_STRING.write() {
+                        *guard = Some(pipewire_pipeline.clone());
+                    }
+                    last_error = Some(e);
+                    continue;
+                }
+                error!(error = %e, "Pipeline launch failed, not retrying");
+                return Err(e);
+            }
             Err(e) => {
                 if attempt < PIPELINE_CREATE_RETRIES {
                     warn!(
@@ -161,11 +958,12 @@ fn try_create_pipewire_pipeline(
         }
     }
 
-    Err(last_error.unwrap_or_else(|| "Pipeline creation failed".into()))
+    Err(last_error
+        .unwrap_or_else(|| PipelineError::StateChangeFailed("no launch attempted".to_string())))
 }
 
 /// Determine PipeWire path property from device path
-fn determine_pipewire_path(device_path: Option<&str>) -> String {
+pub fn determine_pipewire_path(device_path: Option<&str>) -> String {
     if let Some(dev_path) = device_path {
         if dev_path.is_empty() {
             // Empty path = PipeWire auto-select default camera
@@ -265,7 +1063,12 @@ fn build_pipewire_pipeline_string(
             (FormatCategory::Encoded, Some("MJPG") | Some("MJPEG")) => {
                 // MJPEG: decode to native YUV format (GPU will convert to RGBA)
                 // Prefer CPU decoders (jpegdec, avdec_mjpeg) for reliability
-                let decoder_chain = build_mjpeg_decoder_chain();
+                let (decoder_chain, compare_branch) = decode_stage(
+                    Codec::MJPEG,
+                    MJPEG_DECODERS,
+                    caps_filter,
+                    mjpeg_resilience(),
+                );
                 info!(decoder = %decoder_chain, "MJPEG pipeline: native YUV output (GPU conversion)");
                 format!(
                     "pipewiresrc {}do-timestamp=true ! \
@@ -274,48 +1077,60 @@ fn build_pipewire_pipeline_string(
                     image/jpeg,{} ! \
                     jpegparse ! \
                     {} ! \
-                    queue max-size-buffers={} leaky=downstream ! \
-                    appsink name=sink",
+                    {}queue max-size-buffers={} leaky=downstream ! \
+                    appsink name=sink{}",
                     pw_path_prop,
                     caps_filter,
                     decoder_chain,
-                    pipeline::MAX_BUFFERS
+                    downscale_stage(),
+                    pipeline::MAX_BUFFERS,
+                    compare_branch
                 )
             }
 
             // Encoded formats - H264
             (FormatCategory::Encoded, Some("H264")) => {
                 // H264: decode to native YUV format with hardware acceleration preference
-                // h264parse config-interval=-1 inserts SPS/PPS before each keyframe for decoder robustness
+                // The parser sits right before the decoder (see `parser_stage`)
                 // Try hardware decoders first (VA-API), fall back to software (avdec_h264) only as last resort
-                let decoder_chain = build_h264_decoder_chain();
+                let (decoder_chain, compare_branch) =
+                    decode_stage(Codec::H264, H264_DECODERS, caps_filter, false);
                 info!(decoder = %decoder_chain, "H264 pipeline: native YUV output (GPU conversion)");
+                let decoder_chain = with_parser(Codec::H264, &decoder_chain);
                 format!(
                     "pipewiresrc {}do-timestamp=true ! video/x-h264,{} ! \
-                     h264parse config-interval=-1 ! \
                      queue max-size-buffers=0 max-size-bytes=0 max-size-time=0 ! \
                      {} ! \
                      video/x-raw ! \
-                     queue max-size-buffers=8 leaky=downstream ! \
-                     appsink name=sink sync=false",
-                    pw_path_prop, caps_filter, decoder_chain
+                     {}queue max-size-buffers=8 leaky=downstream ! \
+                     appsink name=sink sync=false{}",
+                    pw_path_prop,
+                    caps_filter,
+                    decoder_chain,
+                    downscale_stage(),
+                    compare_branch
                 )
             }
 
             // Encoded formats - H265/HEVC
             (FormatCategory::Encoded, Some("H265") | Some("HEVC")) => {
                 // H265: decode to native YUV format with hardware acceleration preference
-                let decoder_chain = build_h265_decoder_chain();
+                let (decoder_chain, compare_branch) =
+                    decode_stage(Codec::H265, H265_DECODERS, caps_filter, false);
                 info!(decoder = %decoder_chain, "H265 pipeline: native YUV output (GPU conversion)");
+                let decoder_chain = with_parser(Codec::H265, &decoder_chain);
                 format!(
                     "pipewiresrc {}do-timestamp=true ! video/x-h265,{} ! \
-                     h265parse config-interval=-1 ! \
                      queue max-size-buffers=0 max-size-bytes=0 max-size-time=0 ! \
                      {} ! \
                      video/x-raw ! \
-                     queue max-size-buffers=8 leaky=downstream ! \
-                     appsink name=sink sync=false",
-                    pw_path_prop, caps_filter, decoder_chain
+                     {}queue max-size-buffers=8 leaky=downstream ! \
+                     appsink name=sink sync=false{}",
+                    pw_path_prop,
+                    caps_filter,
+                    decoder_chain,
+                    downscale_stage(),
+                    compare_branch
                 )
             }
 
@@ -330,8 +1145,10 @@ fn build_pipewire_pipeline_string(
                      video/x-bayer,{} ! \
                      bayer2rgb ! \
                      video/x-raw,format=RGBA ! \
-                     appsink name=sink",
-                    pw_path_prop, caps_filter
+                     {}appsink name=sink",
+                    pw_path_prop,
+                    caps_filter,
+                    downscale_stage()
                 )
             }
 
@@ -349,8 +1166,12 @@ fn build_pipewire_pipeline_string(
                 format!(
                     "pipewiresrc {}do-timestamp=true ! \
                     video/x-raw,format={},{} ! \
-                    appsink name=sink",
-                    pw_path_prop, gst_fmt, caps_filter
+                    {}{}appsink name=sink",
+                    pw_path_prop,
+                    gst_fmt,
+                    caps_filter,
+                    deinterlace_stage(pw_path_prop),
+                    downscale_stage()
                 )
             }
 
@@ -363,8 +1184,12 @@ fn build_pipewire_pipeline_string(
                 format!(
                     "pipewiresrc {}do-timestamp=true ! \
                     video/x-raw,format={},{} ! \
-                    appsink name=sink",
-                    pw_path_prop, fmt, caps_filter
+                    {}{}appsink name=sink",
+                    pw_path_prop,
+                    fmt,
+                    caps_filter,
+                    deinterlace_stage(pw_path_prop),
+                    downscale_stage()
                 )
             }
 
@@ -374,8 +1199,23 @@ fn build_pipewire_pipeline_string(
                 format!(
                     "pipewiresrc {}do-timestamp=true ! \
                     video/x-raw,format=GRAY8,{} ! \
-                    appsink name=sink",
-                    pw_path_prop, caps_filter
+                    {}appsink name=sink",
+                    pw_path_prop,
+                    caps_filter,
+                    downscale_stage()
+                )
+            }
+
+            // Shader-supported 16-bit grayscale (Y16, normalized on the GPU)
+            (FormatCategory::ShaderSupported, Some("GRAY16_LE") | Some("Y16")) => {
+                info!("Gray16 pipeline: native passthrough (GPU conversion)");
+                format!(
+                    "pipewiresrc {}do-timestamp=true ! \
+                    video/x-raw,format=GRAY16_LE,{} ! \
+                    {}appsink name=sink",
+                    pw_path_prop,
+                    caps_filter,
+                    downscale_stage()
                 )
             }
 
@@ -390,11 +1230,12 @@ fn build_pipewire_pipeline_string(
                     video/x-raw,format={},{} ! \
                     videoconvert n-threads={} ! \
                     video/x-raw,format=RGBA ! \
-                    appsink name=sink",
+                    {}appsink name=sink",
                     pw_path_prop,
                     fmt,
                     caps_filter,
-                    pipeline::videoconvert_threads()
+                    pipeline::videoconvert_threads(),
+                    downscale_stage()
                 )
             }
 
@@ -409,8 +1250,11 @@ fn build_pipewire_pipeline_string(
                 format!(
                     "pipewiresrc {}do-timestamp=true ! \
                     video/x-raw,format={},{} ! \
-                    appsink name=sink",
-                    pw_path_prop, fmt, caps_filter
+                    {}appsink name=sink",
+                    pw_path_prop,
+                    fmt,
+                    caps_filter,
+                    downscale_stage()
                 )
             }
 
@@ -425,11 +1269,13 @@ fn build_pipewire_pipeline_string(
                     video/x-raw,format={},{} ! \
                     videoconvert n-threads={} ! \
                     video/x-raw,format=NV12 ! \
-                    appsink name=sink",
+                    {}{}appsink name=sink",
                     pw_path_prop,
                     fmt,
                     caps_filter,
-                    pipeline::videoconvert_threads()
+                    pipeline::videoconvert_threads(),
+                    deinterlace_stage(pw_path_prop),
+                    downscale_stage()
                 )
             }
 
@@ -438,10 +1284,11 @@ fn build_pipewire_pipeline_string(
                 info!("Generic pipeline with auto-negotiation");
                 format!(
                     "pipewiresrc {}do-timestamp=true ! video/x-raw,{} ! \
-                     videoconvert n-threads={} ! video/x-raw,format=NV12 ! appsink name=sink",
+                     videoconvert n-threads={} ! video/x-raw,format=NV12 ! {}appsink name=sink",
                     pw_path_prop,
                     caps_filter,
-                    pipeline::videoconvert_threads()
+                    pipeline::videoconvert_threads(),
+                    downscale_stage()
                 )
             }
         }
@@ -450,9 +1297,10 @@ fn build_pipewire_pipeline_string(
         info!("No format specified: using decodebin with NV12 output");
         format!(
             "pipewiresrc {}do-timestamp=true ! decodebin ! \
-             videoconvert n-threads={} ! video/x-raw,format=NV12 ! appsink name=sink",
+             videoconvert n-threads={} ! video/x-raw,format=NV12 ! {}appsink name=sink",
             pw_path_prop,
-            pipeline::videoconvert_threads()
+            pipeline::videoconvert_threads(),
+            downscale_stage()
         )
     }
 }
@@ -460,17 +1308,23 @@ fn build_pipewire_pipeline_string(
 /// Try to launch pipeline and check bus for detailed error messages
 fn try_launch_pipeline_with_bus_errors(
     pipeline_str: &str,
-) -> Result<gstreamer::Pipeline, Box<dyn std::error::Error>> {
+) -> Result<gstreamer::Pipeline, PipelineError> {
     info!(pipeline = %pipeline_str, "Attempting to launch pipeline");
 
-    match gstreamer::parse::launch(pipeline_str) {
+    let mut context = gstreamer::ParseContext::new();
+    match gstreamer::parse::launch_full(
+        pipeline_str,
+        Some(&mut context),
+        gstreamer::ParseFlags::empty(),
+    ) {
         Ok(p) => {
             info!("Pipeline parsed successfully");
-            let pipeline = p
-                .dynamic_cast::<gstreamer::Pipeline>()
-                .map_err(|_| "Failed to cast to pipeline")?;
+            let pipeline = p.dynamic_cast::<gstreamer::Pipeline>().map_err(|_| {
+                PipelineError::ParseFailed("description is not a pipeline".to_string())
+            })?;
 
             info!("Cast to Pipeline successful");
+            allow_size_changes(&pipeline);
 
             // Try to set to PLAYING to validate it works
             info!("Setting pipeline to PLAYING state");
@@ -509,75 +1363,331 @@ fn try_launch_pipeline_with_bus_errors(
                             ?pending,
                             "✗ Pipeline failed to reach PLAYING"
                         );
-                        check_bus_for_errors(&pipeline);
+                        let bus_error = check_bus_for_errors(&pipeline);
                         let _ = pipeline.set_state(gstreamer::State::Null);
-                        Err(format!(
-                            "Pipeline failed to start (state: {:?}, result: {:?})",
-                            state, result
-                        )
-                        .into())
+                        Err(bus_error.unwrap_or_else(|| {
+                            PipelineError::StateChangeFailed(format!(
+                                "state: {:?}, result: {:?}",
+                                state, result
+                            ))
+                        }))
                     }
                 }
                 Err(e) => {
                     error!(error = %e, "✗ Failed to set pipeline to PLAYING state");
                     // Check bus for the actual error reason
-                    check_bus_for_errors(&pipeline);
+                    let bus_error = check_bus_for_errors(&pipeline);
                     let _ = pipeline.set_state(gstreamer::State::Null);
-                    Err(format!("Failed to set pipeline to PLAYING: {}", e).into())
+                    Err(bus_error
+                        .unwrap_or_else(|| PipelineError::StateChangeFailed(e.to_string())))
                 }
             }
         }
         Err(e) => {
             error!(error = %e, pipeline = %pipeline_str, "✗ Failed to parse pipeline");
-            Err(Box::new(e))
+            Err(match context.missing_elements().into_iter().next() {
+                Some(name) => PipelineError::ElementMissing {
+                    name: name.to_string(),
+                },
+                None => PipelineError::ParseFailed(e.to_string()),
+            })
         }
     }
 }
 
-/// Check bus for error messages
-fn check_bus_for_errors(pipeline: &gstreamer::Pipeline) {
-    info!("Checking GStreamer bus for error messages");
-    if let Some(bus) = pipeline.bus()
-        && let Some(msg) = bus.timed_pop_filtered(
-            gstreamer::ClockTime::from_mseconds(100),
-            &[
-                gstreamer::MessageType::Error,
-                gstreamer::MessageType::Warning,
-            ],
-        )
-    {
-        match msg.view() {
-            gstreamer::MessageView::Error(err) => {
-                error!(
-                    error = %err.error(),
-                    debug = ?err.debug(),
-                    source = ?err.src().map(|s| s.name()),
-                    "GStreamer ERROR during pipeline start"
-                );
-            }
-            gstreamer::MessageView::Warning(warn_msg) => {
-                warn!(
-                    warning = %warn_msg.error(),
-                    debug = ?warn_msg.debug(),
-                    "GStreamer WARNING during pipeline start"
-                );
+/// Let the source change resolution mid-stream
+///
+/// A capsfilter pinning `width`/`height` refuses new caps when the camera
+/// switches size on its own, and the pipeline fails with not-negotiated.
+/// Such filters keep the selected mode as their first, preferred structure
+/// and gain a copy without size, so the selected mode is still negotiated
+/// first but a later renegotiation may leave it. Caps forced by the user stay
+/// as they are; the scaler in front of them absorbs size changes.
+pub fn allow_size_changes(pipeline: &gstreamer::Pipeline) {
+    for element in pipeline.iterate_recurse().into_iter().flatten() {
+        if !element.factory().is_some_and(|f| f.name() == "capsfilter")
+            || element.name() == FORCED_CAPS_ELEMENT
+        {
+            continue;
+        }
+        let caps = element.property::<gstreamer::Caps>("caps");
+        let relaxed: Vec<gstreamer::Structure> = caps
+            .iter()
+            .filter(|structure| {
+                structure.get::<i32>("width").is_ok() || structure.get::<i32>("height").is_ok()
+            })
+            .map(|structure| {
+                let mut structure = structure.to_owned();
+                structure.remove_field("width");
+                structure.remove_field("height");
+                structure
+            })
+            .collect();
+        if relaxed.is_empty() {
+            continue;
+        }
+        let mut caps = caps.copy();
+        {
+            let caps = caps.make_mut();
+            for structure in relaxed {
+                caps.append_structure(structure);
             }
-            _ => {}
         }
+        debug!(element = %element.name(), %caps, "Allowing mid-stream size changes");
+        element.set_property("caps", &caps);
     }
 }
 
-/// Build the MJPEG decoder chain using shared definitions
-fn build_mjpeg_decoder_chain() -> String {
-    super::definitions::find_available_decoder(super::definitions::MJPEG_DECODERS)
+/// Check bus for error messages, classifying the first error found
+fn check_bus_for_errors(pipeline: &gstreamer::Pipeline) -> Option<PipelineError> {
+    info!("Checking GStreamer bus for error messages");
+    let bus = pipeline.bus()?;
+    let msg = bus.timed_pop_filtered(
+        gstreamer::ClockTime::from_mseconds(100),
+        &[
+            gstreamer::MessageType::Error,
+            gstreamer::MessageType::Warning,
+        ],
+    )?;
+    match msg.view() {
+        gstreamer::MessageView::Error(err) => {
+            error!(
+                error = %err.error(),
+                debug = ?err.debug(),
+                source = ?err.src().map(|s| s.name()),
+                "GStreamer ERROR during pipeline start"
+            );
+            let factory = err
+                .src()
+                .and_then(|src| src.downcast_ref::<gstreamer::Element>())
+                .and_then(|element| element.factory());
+            let name = factory.as_ref().map(|f| f.name().to_string());
+            let klass = factory
+                .as_ref()
+                .map(|f| f.klass().to_string())
+                .unwrap_or_default();
+            let debug = err.debug().map(|debug| debug.to_string());
+            Some(PipelineError::from_bus_error(
+                &err.error(),
+                debug.as_deref(),
+                name.as_deref(),
+                &klass,
+            ))
+        }
+        gstreamer::MessageView::Warning(warn_msg) => {
+            warn!(
+                warning = %warn_msg.error(),
+                debug = ?warn_msg.debug(),
+                "GStreamer WARNING during pipeline start"
+            );
+            None
+        }
+        _ => None,
+    }
 }
 
-/// Build the H264 decoder chain using shared definitions
-fn build_h264_decoder_chain() -> String {
-    super::definitions::find_available_decoder(super::definitions::H264_DECODERS)
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resilience_props() {
+        // FFmpeg's own corrupt-frame flag comes first, it catches the most
+        assert_eq!(
+            resilience_props("avdec_mjpeg"),
+            &["output-corrupt=false", "discard-corrupted-frames=true"]
+        );
+        assert_eq!(
+            resilience_props("jpegdec"),
+            &["discard-corrupted-frames=true"]
+        );
+        assert!(
+            MJPEG_DECODERS
+                .iter()
+                .flat_map(|decoder| resilience_props(decoder.name))
+                .all(|prop| prop.split_once('=').is_some())
+        );
+    }
+
+    #[test]
+    fn test_thread_limit_only_touches_threaded_decoders() {
+        let _ = gstreamer::init();
+        set_decode_threads(Some(2));
+        let jpegdec = &MJPEG_DECODERS[0];
+        assert_eq!(
+            with_thread_limit(jpegdec.as_gst_element(), jpegdec),
+            jpegdec.as_gst_element()
+        );
+        let avdec = H264_DECODERS
+            .iter()
+            .find(|decoder| decoder.name == "avdec_h264")
+            .unwrap();
+        let element = with_thread_limit(avdec.as_gst_element(), avdec);
+        if gstreamer::ElementFactory::find("avdec_h264").is_some() {
+            assert_eq!(element, "avdec_h264 max-threads=2");
+        } else {
+            // The range can't be checked without the element, so it stays automatic
+            assert_eq!(element, "avdec_h264 max-threads=0");
+        }
+        set_decode_threads(None);
+    }
+
+    #[test]
+    fn test_deinterlace_only_for_interlaced_sources() {
+        let _ = gstreamer::init();
+        let source = "target-object=interlace-test ";
+        assert!(!deinterlace_available("no-such-method"));
+        assert!(deinterlace_stage(source).is_empty());
+
+        if let Ok(mut guard) = INTERLACED_SOURCES.write() {
+            guard.push(source.to_string());
+        }
+        let stage = deinterlace_stage(source);
+        if deinterlace_available("yadif") {
+            assert!(stage.contains("method=yadif"));
+        } else {
+            // Without the plugin the pipeline is built without the stage
+            assert!(stage.is_empty());
+        }
+        assert!(deinterlace_stage("target-object=progressive-test ").is_empty());
+    }
+
+    #[test]
+    fn test_v4l2_io_mode_falls_back_to_auto() {
+        let _ = gstreamer::init();
+        let pipewire = "pipewiresrc path=v4l2:/dev/video0 ";
+        let node = Some("/dev/video0");
+
+        // PipeWire unless an io-mode is forced
+        assert_eq!(
+            source_stage(pipewire, None, node),
+            (pipewire.to_string(), None)
+        );
+
+        let (source, capture) = source_stage(pipewire, Some("mmap"), node);
+        if gstreamer::ElementFactory::find("v4l2src").is_some() {
+            assert_eq!(source, "v4l2src device=/dev/video0 io-mode=mmap ");
+            let pipeline_str = format!("{}! fakesink", source);
+            let (fallback, capture) = v4l2_io_mode_fallback(&pipeline_str, capture).unwrap();
+            assert_eq!(
+                fallback,
+                "v4l2src device=/dev/video0 io-mode=auto ! fakesink"
+            );
+            assert_eq!(capture.requested, "mmap");
+            assert!(capture.fell_back());
+            // Nothing left to fall back from
+            assert_eq!(v4l2_io_mode_fallback(&fallback, Some(capture)), None);
 
-/// Build the H265/HEVC decoder chain using shared definitions
-fn build_h265_decoder_chain() -> String {
-    super::definitions::find_available_decoder(super::definitions::H265_DECODERS)
+            // A mode v4l2src doesn't know starts out on auto
+            let (source, capture) = source_stage(pipewire, Some("not-a-mode"), node);
+            assert_eq!(source, "v4l2src device=/dev/video0 io-mode=auto ");
+            assert!(capture.unwrap().fell_back());
+        } else {
+            assert_eq!((source, capture), (pipewire.to_string(), None));
+        }
+
+        // Without a node the camera stays on PipeWire
+        assert_eq!(
+            source_stage(pipewire, Some("mmap"), None),
+            (pipewire.to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_parse_forced_caps() {
+        let _ = gstreamer::init();
+        assert!(parse_forced_caps("video/x-raw,format=NV12,width=1280,height=720").is_ok());
+        assert!(parse_forced_caps("  video/x-raw,width=640  ").is_ok());
+        assert!(parse_forced_caps("not caps at all !").is_err());
+        assert!(parse_forced_caps("image/jpeg,width=1280").is_err());
+        assert!(parse_forced_caps("video/x-raw,format=P010_10LE").is_err());
+        assert!(parse_forced_caps("video/x-raw,format={NV12,I420}").is_ok());
+        assert!(parse_forced_caps("video/x-raw,format={NV12,P010_10LE}").is_err());
+        assert!(parse_forced_caps("ANY").is_err());
+    }
+
+    #[test]
+    fn test_size_changes_keep_forced_caps() {
+        if gstreamer::init().is_err() || gstreamer::ElementFactory::find("videotestsrc").is_none() {
+            println!("Skipping test (no videotestsrc)");
+            return;
+        }
+        let pipeline = gstreamer::parse::launch(&format!(
+            "videotestsrc ! capsfilter name=mode caps=video/x-raw,width=640,height=480,framerate=30/1 ! \
+             videoscale ! capsfilter name={} caps=video/x-raw,width=320,height=240 ! fakesink",
+            FORCED_CAPS_ELEMENT
+        ))
+        .unwrap()
+        .downcast::<gstreamer::Pipeline>()
+        .unwrap();
+
+        allow_size_changes(&pipeline);
+
+        let caps = |name: &str| {
+            pipeline
+                .by_name(name)
+                .unwrap()
+                .property::<gstreamer::Caps>("caps")
+        };
+        let mode = caps("mode");
+        assert_eq!(mode.size(), 2);
+        let relaxed = mode.structure(1).unwrap();
+        assert!(!relaxed.has_field("width") && !relaxed.has_field("height"));
+        assert!(relaxed.has_field("framerate"));
+        assert_eq!(caps(FORCED_CAPS_ELEMENT).size(), 1);
+    }
+
+    #[test]
+    fn test_missing_element_is_reported() {
+        let _ = gstreamer::init();
+        let result = try_launch_pipeline_with_bus_errors("fakesrc ! notarealdecoder ! fakesink");
+        assert_eq!(
+            result.err(),
+            Some(PipelineError::ElementMissing {
+                name: "notarealdecoder".to_string()
+            })
+        );
+        assert!(
+            !try_launch_pipeline_with_bus_errors("fakesrc ! ! fakesink")
+                .unwrap_err()
+                .is_transient()
+        );
+    }
+
+    #[test]
+    fn test_parser_goes_right_before_decoder() {
+        let _ = gstreamer::init();
+        let caps = "width=(int)1280,height=(int)720";
+        let decoder = super::super::definitions::first_available_decoder(H264_DECODERS)
+            .map_or("decodebin", |decoder| decoder.name);
+        let pipeline = build_pipewire_pipeline_string("", caps, Some("H264"));
+        let parser = parser_stage(Codec::H264).unwrap();
+        assert!(parser.starts_with("h264parse "));
+        assert!(
+            pipeline.contains(&format!("{} ! {}", parser, decoder)),
+            "{}",
+            pipeline
+        );
+        // MJPEG and raw formats keep their pipelines
+        assert_eq!(parser_stage(Codec::MJPEG), None);
+        assert_eq!(parser_stage(Codec::YUYV), None);
+        let mjpeg = build_pipewire_pipeline_string("", caps, Some("MJPG"));
+        assert!(!mjpeg.contains("h264parse"));
+    }
+
+    #[test]
+    fn test_comparison_stage_splits_frame() {
+        assert_eq!(
+            caps_int("width=(int)1281,height=(int)720", "width"),
+            Some(1281)
+        );
+        let (stage, branch) = comparison_stage("jpegdec", "avdec_mjpeg", 1281);
+        // The left half is 640 wide, the right half 641 wide starting at x=640
+        assert!(stage.contains("jpegdec ! "));
+        assert!(stage.contains("videocrop right=641 "));
+        assert!(stage.contains("sink_1::xpos=640 "));
+        assert!(branch.starts_with(" compare-tee. ! queue ! avdec_mjpeg ! "));
+        assert!(branch.contains("videocrop left=640 "));
+        assert!(branch.ends_with("compare-mix."));
+    }
 }
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Power preference for decoder ordering
+//!
+//! H.264 and H.265 normally prefer hardware decoders. On a CPU with
+//! efficiency cores a webcam stream decodes in software at a few watts,
+//! while a hardware decoder keeps the GPU's video engine and memory awake
+//! for every frame. With the low-power preference those lists are tried
+//! software first on such CPUs. MJPEG is already software first, and CPUs
+//! without efficiency cores keep the hardware-first order either way.
+
+use super::definitions::DecoderDef;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Whether pipelines created from now on prefer low-power decoding
+static LOW_POWER: AtomicBool = AtomicBool::new(false);
+
+/// Efficiency cores found at startup (`None` = not a hybrid CPU)
+static EFFICIENCY_CORES: OnceLock<Option<usize>> = OnceLock::new();
+
+/// Software-first copies of the hardware-first lists, keyed by their first decoder
+static SOFTWARE_FIRST: Mutex<Vec<(&'static str, &'static [DecoderDef])>> = Mutex::new(Vec::new());
+
+/// Prefer low-power decoding for pipelines created from now on
+pub fn set_low_power_decode(enabled: bool) {
+    LOW_POWER.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether low-power decoding is preferred
+pub fn low_power_decode() -> bool {
+    LOW_POWER.load(Ordering::Relaxed)
+}
+
+/// Number of CPUs in a sysfs CPU list such as "0-3,8,10-11"
+fn cpu_list_len(list: &str) -> usize {
+    list.trim()
+        .split(',')
+        .filter(|range| !range.is_empty())
+        .map(|range| match range.split_once('-') {
+            Some((first, last)) => match (first.parse::<usize>(), last.parse::<usize>()) {
+                (Ok(first), Ok(last)) if last >= first => last - first + 1,
+                _ => 0,
+            },
+            None => usize::from(range.parse::<usize>().is_ok()),
+        })
+        .sum()
+}
+
+/// CPUs below the highest capacity (big.LITTLE and similar designs)
+fn cores_below_max_capacity(capacities: &[u32]) -> Option<usize> {
+    let max = capacities.iter().copied().max()?;
+    let efficient = capacities
+        .iter()
+        .filter(|&&capacity| capacity < max)
+        .count();
+    (efficient > 0).then_some(efficient)
+}
+
+/// Count the efficiency cores of this CPU
+///
+/// Intel hybrid CPUs list their E-cores under `cpu_atom`; other hybrid
+/// designs report a lower `cpu_capacity` for their small cores.
+fn detect_efficiency_cores() -> Option<usize> {
+    if let Ok(list) = std::fs::read_to_string("/sys/devices/cpu_atom/cpus") {
+        let cores = cpu_list_len(&list);
+        if cores > 0 {
+            return Some(cores);
+        }
+    }
+
+    let capacities: Vec<u32> = std::fs::read_dir("/sys/devices/system/cpu")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .is_some_and(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
+        })
+        .filter_map(|entry| {
+            std::fs::read_to_string(entry.path().join("cpu_capacity"))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+        })
+        .collect();
+    cores_below_max_capacity(&capacities)
+}
+
+/// Efficiency cores of this CPU (`None` when it has none or can't tell)
+pub fn efficiency_cores() -> Option<usize> {
+    *EFFICIENCY_CORES.get_or_init(|| {
+        let cores = detect_efficiency_cores();
+        tracing::info!(?cores, "Efficiency cores");
+        cores
+    })
+}
+
+/// Whether hardware-first decoder lists are currently tried software first
+pub fn software_first_decode() -> bool {
+    low_power_decode() && efficiency_cores().is_some()
+}
+
+/// `decoders` with the software decoders moved to the front
+///
+/// Both groups keep their relative order.
+fn software_first(decoders: &[DecoderDef]) -> Vec<DecoderDef> {
+    let (software, hardware): (Vec<_>, Vec<_>) =
+        decoders.iter().partition(|decoder| !decoder.is_hardware);
+    software.into_iter().chain(hardware).copied().collect()
+}
+
+/// `decoders` in the order the power preference asks for
+///
+/// Returns the list unchanged unless [`software_first_decode`] applies and
+/// the list starts with a hardware decoder.
+pub fn preferred_order(decoders: &'static [DecoderDef]) -> &'static [DecoderDef] {
+    let Some(first) = decoders.first() else {
+        return decoders;
+    };
+    if !first.is_hardware || !software_first_decode() {
+        return decoders;
+    }
+    let Ok(mut cache) = SOFTWARE_FIRST.lock() else {
+        return decoders;
+    };
+    if let Some((_, ordered)) = cache.iter().find(|(name, _)| *name == first.name) {
+        return ordered;
+    }
+    // One copy per decoder list, kept for the life of the process
+    let ordered: &'static [DecoderDef] = Box::leak(software_first(decoders).into_boxed_slice());
+    cache.push((first.name, ordered));
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::decoders::H264_DECODERS;
+
+    #[test]
+    fn test_efficiency_core_detection() {
+        assert_eq!(cpu_list_len("16-23\n"), 8);
+        assert_eq!(cpu_list_len("0-3,8,10-11"), 7);
+        assert_eq!(cpu_list_len(""), 0);
+        // Four little cores next to four big ones
+        assert_eq!(
+            cores_below_max_capacity(&[446, 446, 446, 446, 1024, 1024, 1024, 1024]),
+            Some(4)
+        );
+        assert_eq!(cores_below_max_capacity(&[1024, 1024]), None);
+        assert_eq!(cores_below_max_capacity(&[]), None);
+    }
+
+    #[test]
+    fn test_software_first_keeps_relative_order() {
+        let ordered = software_first(H264_DECODERS);
+        let names: Vec<_> = ordered.iter().map(|decoder| decoder.name).collect();
+        assert_eq!(&names[..2], ["avdec_h264", "openh264dec"]);
+        assert_eq!(names[2], "vah264dec");
+        assert_eq!(names.len(), H264_DECODERS.len());
+    }
+}
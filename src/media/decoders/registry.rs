@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! GStreamer registry health check
+//!
+//! GStreamer caches the elements of every plugin in a registry file and only
+//! rescans plugins whose files changed. A registry written while plugins were
+//! half-installed, or a plugin that crashed while being scanned, leaves
+//! decoders missing even though their packages are installed, and
+//! [`super::definitions::find_available_decoder`] then quietly falls back to
+//! decodebin. This check runs once at startup and only reports what a user
+//! can act on.
+
+use gstreamer::prelude::*;
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Problems found by the startup check
+static PROBLEMS: OnceLock<Vec<RegistryProblem>> = OnceLock::new();
+
+/// Elements every GStreamer install has; missing ones mean a broken registry
+const CORE_ELEMENTS: &[&str] = &["appsink", "videoconvert", "queue"];
+
+/// Plugins (by file stem) that provide the elements camera pipelines use
+const CAMERA_PLUGINS: &[&str] = &[
+    "pipewire",
+    "jpeg",
+    "jpegformat",
+    "libav",
+    "va",
+    "vaapi",
+    "nvcodec",
+    "openh264",
+    "video4linux2",
+    "videoparsersbad",
+    "d3d11",
+    "app",
+    "videoconvertscale",
+];
+
+/// Something in the registry that hides installed decoders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryProblem {
+    /// Core elements aren't registered: the registry is empty or out of date
+    Stale,
+    /// Plugins providing camera elements failed to load and were blacklisted
+    Blacklisted(Vec<String>),
+}
+
+impl fmt::Display for RegistryProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryProblem::Stale => write!(
+                f,
+                "GStreamer registry is empty or stale; run gst-inspect-1.0 to rebuild it"
+            ),
+            RegistryProblem::Blacklisted(plugins) => write!(
+                f,
+                "GStreamer plugins blacklisted: {}; see gst-inspect-1.0 -b, or delete \
+                 ~/.cache/gstreamer-1.0 to rescan",
+                plugins.join(", ")
+            ),
+        }
+    }
+}
+
+/// Name of a plugin from its file path (`.../libgstlibav.so` -> `libav`)
+fn plugin_stem(path: &str) -> &str {
+    let file = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path);
+    let file = file.strip_prefix("libgst").unwrap_or(file);
+    file.split('.').next().unwrap_or(file)
+}
+
+/// Whether the plugin at `path` provides elements the camera pipelines use
+fn is_camera_plugin(path: &str) -> bool {
+    CAMERA_PLUGINS.contains(&plugin_stem(path))
+}
+
+/// Check the registry for stale entries and blacklisted camera plugins
+///
+/// Runs at most once per process (the registry doesn't change while the
+/// app runs); results are also logged.
+pub fn check_registry() -> &'static [RegistryProblem] {
+    PROBLEMS.get_or_init(|| {
+        if gstreamer::init().is_err() {
+            return Vec::new();
+        }
+        let mut problems = Vec::new();
+
+        let missing: Vec<&str> = CORE_ELEMENTS
+            .iter()
+            .copied()
+            .filter(|name| gstreamer::ElementFactory::find(name).is_none())
+            .collect();
+        if !missing.is_empty() {
+            tracing::warn!(?missing, "Core GStreamer elements are not registered");
+            problems.push(RegistryProblem::Stale);
+        }
+
+        let blacklisted: Vec<String> = gstreamer::Registry::get()
+            .plugins()
+            .into_iter()
+            .filter(|plugin| {
+                plugin
+                    .plugin_flags()
+                    .contains(gstreamer::PluginFlags::BLACKLISTED)
+            })
+            .map(|plugin| {
+                plugin
+                    .filename()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_else(|| plugin.plugin_name().to_string())
+            })
+            .filter(|path| is_camera_plugin(path))
+            .collect();
+        if !blacklisted.is_empty() {
+            tracing::warn!(plugins = ?blacklisted, "Camera-related GStreamer plugins are blacklisted");
+            problems.push(RegistryProblem::Blacklisted(
+                blacklisted
+                    .iter()
+                    .map(|path| plugin_stem(path).to_string())
+                    .collect(),
+            ));
+        }
+
+        for problem in &problems {
+            tracing::warn!(%problem, "GStreamer registry problem");
+        }
+        problems
+    })
+}
+
+/// Problems found by [`check_registry`] (empty when it hasn't run)
+pub fn registry_problems() -> &'static [RegistryProblem] {
+    PROBLEMS.get().map_or(&[], Vec::as_slice)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_camera_plugins_are_reported() {
+        assert_eq!(
+            plugin_stem("/usr/lib64/gstreamer-1.0/libgstlibav.so"),
+            "libav"
+        );
+        assert_eq!(plugin_stem("libgstva.so"), "va");
+        assert!(is_camera_plugin("/usr/lib/gstreamer-1.0/libgstvaapi.so"));
+        assert!(is_camera_plugin("/usr/lib/gstreamer-1.0/libgstpipewire.so"));
+        assert!(!is_camera_plugin("/usr/lib/gstreamer-1.0/libgstopus.so"));
+        // "va" must not match every plugin starting with it
+        assert!(!is_camera_plugin("/usr/lib/gstreamer-1.0/libgstvalve.so"));
+    }
+}
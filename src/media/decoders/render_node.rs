@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! VA-API render node selection
+//!
+//! On multi-GPU systems VA-API binds to the first DRM render node, which may
+//! not be the GPU with the best (or any) decode support.
+//!
+//! The `va` plugin registers one set of elements per render node: the first
+//! node gets the plain names (`vah264dec`) and every other node gets the node
+//! embedded in the name (`varenderD129h264dec`). Selecting a node therefore
+//! means swapping element names when pipelines are built. The legacy `vaapi`
+//! plugin instead reads `GST_VAAPI_DRM_DEVICE` when it opens its display, so
+//! it only follows the selection from the next launch.
+
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::RwLock;
+use tracing::{info, warn};
+
+/// Directory holding DRM device nodes
+const DRI_DIR: &str = "/dev/dri";
+
+/// Selected render node (`None` = VA-API default)
+static RENDER_NODE: RwLock<Option<String>> = RwLock::new(None);
+
+/// A DRM render node available for VA-API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderNode {
+    /// Device path (e.g., "/dev/dri/renderD128")
+    pub path: String,
+    /// Kernel driver bound to the GPU (e.g., "i915", "amdgpu")
+    pub driver: Option<String>,
+}
+
+impl RenderNode {
+    /// Label for the settings dropdown, e.g. "/dev/dri/renderD129 (amdgpu)"
+    pub fn display_name(&self) -> String {
+        match &self.driver {
+            Some(driver) => format!("{} ({})", self.path, driver),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Enumerate DRM render nodes, sorted by path
+pub fn list_render_nodes() -> Vec<RenderNode> {
+    let Ok(entries) = std::fs::read_dir(DRI_DIR) else {
+        return Vec::new();
+    };
+
+    let mut nodes: Vec<RenderNode> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with("renderD") {
+                return None;
+            }
+            let driver = std::fs::read_link(format!("/sys/class/drm/{}/device/driver", name))
+                .ok()
+                .and_then(|link| link.file_name().map(|n| n.to_string_lossy().to_string()));
+            Some(RenderNode {
+                path: format!("{}/{}", DRI_DIR, name),
+                driver,
+            })
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.path.cmp(&b.path));
+    nodes
+}
+
+/// Select the render node used for VA-API decoding
+///
+/// Falls back to the VA-API default when the path isn't an existing render
+/// node. Returns the node that is actually in effect.
+pub fn set_render_node(requested: Option<&str>) -> Option<String> {
+    let effective = requested.and_then(|path| {
+        let is_render_node = path.starts_with(&format!("{}/renderD", DRI_DIR));
+        if is_render_node && Path::new(path).exists() {
+            Some(path.to_string())
+        } else {
+            warn!(path, "VA-API render node is not available, using default");
+            None
+        }
+    });
+
+    info!(node = ?effective, "VA-API render node selected");
+    if let Ok(mut guard) = RENDER_NODE.write() {
+        *guard = effective.clone();
+    }
+    effective
+}
+
+/// Get the render node in effect (`None` = VA-API default)
+pub fn render_node() -> Option<String> {
+    RENDER_NODE.read().ok().and_then(|guard| guard.clone())
+}
+
+/// Name of the `va` plugin element registered for a non-default render node
+///
+/// Returns `None` for decoders that aren't `va` plugin elements.
+fn va_element_name(decoder: &str, node_path: &str) -> Option<String> {
+    if decoder.starts_with("vaapi") {
+        return None;
+    }
+    let suffix = decoder.strip_prefix("va")?;
+    let node = Path::new(node_path).file_name()?.to_str()?;
+    Some(format!("va{}{}", node, suffix))
+}
+
+/// Element name to use for a decoder given the selected render node
+///
+/// The plain name is kept when no node is selected or the selected node is the
+/// one the `va` plugin registered under plain names.
+pub fn resolve_decoder_element(decoder: &'static str) -> Cow<'static, str> {
+    let Some(node) = render_node() else {
+        return Cow::Borrowed(decoder);
+    };
+    match va_element_name(decoder, &node) {
+        Some(name) if gstreamer::ElementFactory::find(&name).is_some() => Cow::Owned(name),
+        _ => Cow::Borrowed(decoder),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_va_element_name_embeds_node() {
+        assert_eq!(
+            va_element_name("vah264dec", "/dev/dri/renderD129").as_deref(),
+            Some("varenderD129h264dec")
+        );
+        assert_eq!(
+            va_element_name("vajpegdec", "/dev/dri/renderD130").as_deref(),
+            Some("varenderD130jpegdec")
+        );
+    }
+
+    #[test]
+    fn test_va_element_name_ignores_other_plugins() {
+        assert_eq!(va_element_name("vaapih264dec", "/dev/dri/renderD129"), None);
+        assert_eq!(va_element_name("nvh264dec", "/dev/dri/renderD129"), None);
+        assert_eq!(va_element_name("avdec_h264", "/dev/dri/renderD129"), None);
+    }
+
+    #[test]
+    fn test_invalid_node_falls_back_to_default() {
+        assert_eq!(set_render_node(Some("/dev/null")), None);
+        assert_eq!(set_render_node(Some("/dev/dri/renderD999")), None);
+        assert_eq!(render_node(), None);
+    }
+}
@@ -5,6 +5,7 @@
 //! Codec metadata and utilities for video pixel formats
 
 use std::fmt;
+use std::str::FromStr;
 
 /// Supported video codec/pixel format types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -74,12 +75,25 @@ pub enum Codec {
     Unknown,
 }
 
+/// Broad class of a pixel format, used to pick the processing path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FormatClass {
+    /// Needs a decoder before frames can be displayed (MJPEG, H.264, H.265)
+    Compressed,
+    /// Packed or planar YUV, converted on the GPU
+    RawYuv,
+    /// RGB or Bayer sensor data (Bayer is demosaiced to RGB)
+    RawRgb,
+    /// Single-channel grayscale, depth or IR
+    Mono,
+}
+
 impl Codec {
     /// Parse codec from FourCC string
     pub fn from_fourcc(fourcc: &str) -> Self {
         match fourcc.to_uppercase().as_str() {
             // Compressed
-            "MJPG" | "JPEG" => Self::MJPEG,
+            "MJPG" | "MJPEG" | "JPEG" => Self::MJPEG,
             "H264" | "AVC1" => Self::H264,
             "H265" | "HEVC" => Self::H265,
 
@@ -115,13 +129,39 @@ impl Codec {
             // Depth/IR/Grayscale
             "Y10B" => Self::Y10B,
             "IR10" => Self::IR10,
-            "Y16" | "Y16 " => Self::Y16,
+            "Y16" | "Y16 " | "GRAY16_LE" => Self::Y16,
             "GREY" | "GRAY8" | "Y8" | "Y800" => Self::GREY,
 
             _ => Self::Unknown,
         }
     }
 
+    /// Classify the format, or `None` for unknown fourccs
+    pub fn class(&self) -> Option<FormatClass> {
+        match self {
+            Self::MJPEG | Self::H264 | Self::H265 => Some(FormatClass::Compressed),
+            Self::YUYV
+            | Self::UYVY
+            | Self::YUY2
+            | Self::YVYU
+            | Self::VYUY
+            | Self::NV12
+            | Self::NV21
+            | Self::YV12
+            | Self::I420 => Some(FormatClass::RawYuv),
+            Self::RGB24
+            | Self::RGB32
+            | Self::BGR24
+            | Self::BGR32
+            | Self::BayerGRBG
+            | Self::BayerRGGB
+            | Self::BayerBGGR
+            | Self::BayerGBRG => Some(FormatClass::RawRgb),
+            Self::Y10B | Self::IR10 | Self::Y16 | Self::GREY => Some(FormatClass::Mono),
+            Self::Unknown => None,
+        }
+    }
+
     /// Get the FourCC code for this codec
     pub fn fourcc(&self) -> &'static str {
         match self {
@@ -244,6 +284,21 @@ impl Codec {
         }
     }
 
+    /// Byte order of a packed 4:2:2 pair of pixels (`None` for other formats)
+    ///
+    /// Both pixels share one U and one V sample. Reading one layout as
+    /// another swaps luma with chroma or U with V, so the colors come out
+    /// wrong rather than the image failing outright.
+    pub fn packed_layout(&self) -> Option<&'static str> {
+        match self {
+            Self::YUYV | Self::YUY2 => Some("Y0 U Y1 V"),
+            Self::UYVY => Some("U Y0 V Y1"),
+            Self::YVYU => Some("Y0 V Y1 U"),
+            Self::VYUY => Some("V Y0 U Y1"),
+            _ => None,
+        }
+    }
+
     /// Check if this is a raw/uncompressed format
     pub fn is_raw(&self) -> bool {
         matches!(
@@ -380,9 +435,24 @@ impl Codec {
     }
 }
 
+/// Writes the canonical FourCC, which parses back to the same codec
+///
+/// `YUY2` is the one exception: it is an alias of `YUYV` and parses as such.
 impl fmt::Display for Codec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.short_description())
+        f.write_str(self.fourcc())
+    }
+}
+
+impl FromStr for Codec {
+    type Err = String;
+
+    /// Strict variant of [`Codec::from_fourcc`] that rejects unknown fourccs
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Codec::from_fourcc(s.trim()) {
+            Codec::Unknown => Err(format!("unknown fourcc: {}", s)),
+            codec => Ok(codec),
+        }
     }
 }
 
@@ -416,6 +486,67 @@ mod tests {
         assert_eq!(Codec::from_fourcc("XBGR"), Codec::BGR32);
         // GStreamer grayscale
         assert_eq!(Codec::from_fourcc("GRAY8"), Codec::GREY);
+        assert_eq!(Codec::from_fourcc("GRAY16_LE"), Codec::Y16);
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let codecs = [
+            Codec::MJPEG,
+            Codec::H264,
+            Codec::H265,
+            Codec::YUYV,
+            Codec::UYVY,
+            Codec::YVYU,
+            Codec::VYUY,
+            Codec::NV12,
+            Codec::NV21,
+            Codec::YV12,
+            Codec::I420,
+            Codec::RGB24,
+            Codec::RGB32,
+            Codec::BGR24,
+            Codec::BGR32,
+            Codec::BayerGRBG,
+            Codec::BayerRGGB,
+            Codec::BayerBGGR,
+            Codec::BayerGBRG,
+            Codec::Y10B,
+            Codec::IR10,
+            Codec::Y16,
+            Codec::GREY,
+        ];
+        for codec in codecs {
+            assert_eq!(codec.to_string().parse::<Codec>(), Ok(codec), "{:?}", codec);
+        }
+        assert_eq!("YUY2".parse::<Codec>(), Ok(Codec::YUYV));
+        assert_eq!(" mjpeg ".parse::<Codec>(), Ok(Codec::MJPEG));
+        assert_eq!(
+            "VP80".parse::<Codec>(),
+            Err("unknown fourcc: VP80".to_string())
+        );
+        assert!("UNKN".parse::<Codec>().is_err());
+    }
+
+    #[test]
+    fn test_format_class() {
+        assert_eq!(
+            Codec::from_fourcc("JPEG").class(),
+            Some(FormatClass::Compressed)
+        );
+        assert_eq!(
+            Codec::from_fourcc("HEVC").class(),
+            Some(FormatClass::Compressed)
+        );
+        assert_eq!(Codec::YUYV.class(), Some(FormatClass::RawYuv));
+        assert_eq!(Codec::NV12.class(), Some(FormatClass::RawYuv));
+        assert_eq!(Codec::BGR32.class(), Some(FormatClass::RawRgb));
+        assert_eq!(Codec::BayerRGGB.class(), Some(FormatClass::RawRgb));
+        assert_eq!(
+            Codec::from_fourcc("GRAY16_LE").class(),
+            Some(FormatClass::Mono)
+        );
+        assert_eq!(Codec::Unknown.class(), None);
     }
 
     #[test]
@@ -452,6 +583,24 @@ mod tests {
         assert!(!Codec::NV12.needs_decoder());
     }
 
+    #[test]
+    fn test_packed_layouts_are_distinct() {
+        let packed = [Codec::YUYV, Codec::UYVY, Codec::YVYU, Codec::VYUY];
+        let layouts: std::collections::HashSet<_> = packed
+            .iter()
+            .filter_map(|codec| codec.packed_layout())
+            .collect();
+        assert_eq!(layouts.len(), packed.len());
+        // YUY2 is GStreamer's name for YUYV
+        assert_eq!(
+            Codec::from_fourcc("YUY2").packed_layout(),
+            Codec::YUYV.packed_layout()
+        );
+        assert_eq!(Codec::NV12.packed_layout(), None);
+        // Raw formats skip the decoder chain
+        assert!(packed.iter().all(|codec| !codec.needs_decoder()));
+    }
+
     #[test]
     fn test_preference_ranking() {
         assert!(Codec::BayerGRBG.preference_rank() < Codec::YUYV.preference_rank());
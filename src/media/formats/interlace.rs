@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Interlace detection from GStreamer caps
+//!
+//! Capture cards fed by analog or broadcast sources often deliver interlaced
+//! frames. The caps carry `interlace-mode` and, for interleaved content, an
+//! optional `field-order`.
+
+/// Field order of interlaced content
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FieldOrder {
+    /// Not declared by the source
+    #[default]
+    Unknown,
+    /// Top field first
+    TopFieldFirst,
+    /// Bottom field first
+    BottomFieldFirst,
+}
+
+impl FieldOrder {
+    /// Short label used in diagnostics ("tff" / "bff")
+    pub fn short_name(&self) -> Option<&'static str> {
+        match self {
+            FieldOrder::Unknown => None,
+            FieldOrder::TopFieldFirst => Some("tff"),
+            FieldOrder::BottomFieldFirst => Some("bff"),
+        }
+    }
+}
+
+/// Interlacing declared by the source caps
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterlaceInfo {
+    /// Raw `interlace-mode` value ("progressive" when absent)
+    pub mode: String,
+    /// Field order, if declared
+    pub field_order: FieldOrder,
+}
+
+impl InterlaceInfo {
+    /// Whether the source delivers fields that need deinterlacing
+    pub fn is_interlaced(&self) -> bool {
+        !self.mode.is_empty() && self.mode != "progressive"
+    }
+
+    /// Human readable description, e.g. "Interlaced (tff)" or "Progressive"
+    pub fn display(&self) -> String {
+        if !self.is_interlaced() {
+            return "Progressive".to_string();
+        }
+        match self.field_order.short_name() {
+            Some(order) => format!("Interlaced ({})", order),
+            None => "Interlaced".to_string(),
+        }
+    }
+}
+
+/// Read interlace mode and field order from caps
+pub fn interlace_from_caps(caps: &gstreamer::CapsRef) -> InterlaceInfo {
+    let Some(structure) = caps.structure(0) else {
+        return InterlaceInfo::default();
+    };
+
+    let mode = structure
+        .get::<&str>("interlace-mode")
+        .unwrap_or("progressive")
+        .to_string();
+    let field_order = match structure.get::<&str>("field-order") {
+        Ok("top-field-first") => FieldOrder::TopFieldFirst,
+        Ok("bottom-field-first") => FieldOrder::BottomFieldFirst,
+        _ => FieldOrder::Unknown,
+    };
+
+    InterlaceInfo { mode, field_order }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn parse(caps: &str) -> InterlaceInfo {
+        let _ = gstreamer::init();
+        let caps = gstreamer::Caps::from_str(caps).expect("valid caps");
+        interlace_from_caps(&caps)
+    }
+
+    #[test]
+    fn test_interleaved_caps_detected() {
+        let info = parse(
+            "video/x-raw,format=YUY2,width=720,height=576,\
+             interlace-mode=interleaved,field-order=top-field-first",
+        );
+        assert!(info.is_interlaced());
+        assert_eq!(info.field_order, FieldOrder::TopFieldFirst);
+        assert_eq!(info.display(), "Interlaced (tff)");
+    }
+
+    #[test]
+    fn test_interleaved_without_field_order() {
+        let info = parse("video/x-raw,format=UYVY,interlace-mode=interleaved");
+        assert!(info.is_interlaced());
+        assert_eq!(info.display(), "Interlaced");
+    }
+
+    #[test]
+    fn test_progressive_and_missing_mode() {
+        assert!(!parse("video/x-raw,format=NV12,interlace-mode=progressive").is_interlaced());
+
+        let info = parse("video/x-raw,format=NV12,width=1920,height=1080");
+        assert!(!info.is_interlaced());
+        assert_eq!(info.display(), "Progressive");
+    }
+}
@@ -7,5 +7,7 @@
 
 pub mod codec;
 pub mod conversions;
+pub mod interlace;
 
-pub use codec::Codec;
+pub use codec::{Codec, FormatClass};
+pub use interlace::{FieldOrder, InterlaceInfo, interlace_from_caps};
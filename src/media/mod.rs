@@ -30,4 +30,4 @@ pub mod formats;
 pub use decoders::{
     PipelineBackend, detect_hw_decoders, get_full_pipeline_string, try_create_pipeline,
 };
-pub use formats::Codec;
+pub use formats::{Codec, FormatClass};
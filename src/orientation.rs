@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Device orientation from iio-sensor-proxy
+//!
+//! On tablets and convertibles the compositor rotates the display to follow
+//! the accelerometer, but the camera turns with the device, so the preview
+//! has to be counter-rotated to stay upright. iio-sensor-proxy publishes the
+//! orientation on the system bus as `AccelerometerOrientation`.
+
+use crate::backends::camera::types::SensorRotation;
+use futures::{Stream, StreamExt};
+use tracing::{debug, info};
+
+const SENSOR_PROXY_NAME: &str = "net.hadess.SensorProxy";
+const SENSOR_PROXY_PATH: &str = "/net/hadess/SensorProxy";
+const ORIENTATION_PROPERTY: &str = "AccelerometerOrientation";
+
+/// Extra clockwise preview rotation for an iio-sensor-proxy orientation
+///
+/// Returns `None` for "undefined" (device lying flat or not yet known), in
+/// which case the previous orientation should be kept.
+pub fn orientation_rotation(orientation: &str) -> Option<SensorRotation> {
+    match orientation {
+        "normal" => Some(SensorRotation::None),
+        // Turned a quarter clockwise: the left edge of the screen points up
+        "left-up" => Some(SensorRotation::Rotate90),
+        "bottom-up" => Some(SensorRotation::Rotate180),
+        "right-up" => Some(SensorRotation::Rotate270),
+        _ => None,
+    }
+}
+
+/// Claimed accelerometer; released when dropped with the bus connection
+pub struct Accelerometer {
+    proxy: zbus::Proxy<'static>,
+}
+
+impl Accelerometer {
+    /// Connect to iio-sensor-proxy and claim the accelerometer
+    ///
+    /// Fails on machines without iio-sensor-proxy or without an accelerometer.
+    pub async fn claim() -> Result<Self, String> {
+        let connection = zbus::Connection::system()
+            .await
+            .map_err(|e| format!("Failed to connect to system D-Bus: {}", e))?;
+        let proxy = zbus::Proxy::new(
+            &connection,
+            SENSOR_PROXY_NAME,
+            SENSOR_PROXY_PATH,
+            SENSOR_PROXY_NAME,
+        )
+        .await
+        .map_err(|e| format!("Failed to create sensor proxy: {}", e))?;
+
+        let has_accelerometer: bool = proxy
+            .get_property("HasAccelerometer")
+            .await
+            .map_err(|e| format!("iio-sensor-proxy unavailable: {}", e))?;
+        if !has_accelerometer {
+            return Err("No accelerometer".to_string());
+        }
+
+        proxy
+            .call_method("ClaimAccelerometer", &())
+            .await
+            .map_err(|e| format!("Failed to claim accelerometer: {}", e))?;
+        info!("Claimed accelerometer for preview orientation");
+        Ok(Self { proxy })
+    }
+
+    /// Current orientation followed by every change
+    pub async fn rotations(&self) -> impl Stream<Item = SensorRotation> + '_ {
+        let initial = self
+            .proxy
+            .get_property::<String>(ORIENTATION_PROPERTY)
+            .await
+            .ok();
+        let changes = self
+            .proxy
+            .receive_property_changed::<String>(ORIENTATION_PROPERTY)
+            .await
+            .then(|change| async move { change.get().await.ok() });
+
+        futures::stream::iter([initial])
+            .chain(changes)
+            .filter_map(|orientation| async move {
+                let orientation = orientation?;
+                debug!(orientation = %orientation, "Accelerometer orientation");
+                orientation_rotation(&orientation)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orientation_rotation() {
+        assert_eq!(orientation_rotation("normal"), Some(SensorRotation::None));
+        assert_eq!(
+            orientation_rotation("left-up"),
+            Some(SensorRotation::Rotate90)
+        );
+        assert_eq!(
+            orientation_rotation("bottom-up"),
+            Some(SensorRotation::Rotate180)
+        );
+        assert_eq!(
+            orientation_rotation("right-up"),
+            Some(SensorRotation::Rotate270)
+        );
+        assert_eq!(orientation_rotation("undefined"), None);
+    }
+
+    #[test]
+    fn test_orientation_composes_with_sensor_rotation() {
+        let mounted = SensorRotation::Rotate270;
+        assert_eq!(
+            mounted.rotated_by(SensorRotation::Rotate90),
+            SensorRotation::None
+        );
+        assert_eq!(
+            mounted.rotated_by(SensorRotation::Rotate180),
+            SensorRotation::Rotate90
+        );
+    }
+}
@@ -0,0 +1,424 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Output filenames for captured photos, recordings and clips
+//!
+//! Every capture is named from a user-configurable template such as
+//! `{kind}_{date}_{time}`. The template only names the file: it can't contain
+//! path separators, and the extension is always appended for the actual
+//! output format. When a rendered name is already taken, `{seq}` counts up
+//! until a free name is found; templates without `{seq}` get `_2`, `_3`, ...
+//! appended instead, so a capture never overwrites an earlier one.
+//!
+//! Recordings split into segments use `{seq}` for the segment number
+//! instead; templates without it get the number appended.
+
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+
+/// Template used when none is configured (e.g. "IMG_20250101_120000")
+pub const DEFAULT_TEMPLATE: &str = "{kind}_{date}_{time}";
+
+/// Tokens a template may contain, for the settings UI
+pub const TOKENS: &[&str] = &[
+    "{kind}", "{date}", "{time}", "{device}", "{format}", "{seq}",
+];
+
+/// What is being saved (expands `{kind}`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureKind {
+    /// Still photo
+    Photo,
+    /// Video recording
+    Video,
+    /// Clip saved from the retro buffer
+    Retro,
+}
+
+impl CaptureKind {
+    /// Filename prefix for this kind of capture
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            CaptureKind::Photo => "IMG",
+            CaptureKind::Video => "VID",
+            CaptureKind::Retro => "RETRO",
+        }
+    }
+}
+
+/// Why a template can't be used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// Nothing to name the file with
+    Empty,
+    /// Contains `/` or `\`, which could write outside the output directory
+    PathSeparator,
+    /// `{name}` is not a known token
+    UnknownToken(String),
+    /// A `{` without its closing `}`
+    UnclosedToken,
+    /// Ends with `.` or a media extension (the extension is added automatically)
+    Extension,
+    /// Starts with `.`, which would hide the file
+    Hidden,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Kind,
+    Date,
+    Time,
+    Device,
+    Format,
+    Seq,
+}
+
+impl Token {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "kind" => Some(Token::Kind),
+            "date" => Some(Token::Date),
+            "time" => Some(Token::Time),
+            "device" => Some(Token::Device),
+            "format" => Some(Token::Format),
+            "seq" => Some(Token::Seq),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Token(Token),
+}
+
+/// Values the tokens of a template expand to
+#[derive(Debug, Clone)]
+pub struct FilenameContext<'a> {
+    /// Expands `{kind}`
+    pub kind: CaptureKind,
+    /// Expands `{date}` (YYYYMMDD) and `{time}` (HHMMSS)
+    pub time: DateTime<Local>,
+    /// Expands `{device}` (camera name)
+    pub device: &'a str,
+    /// File extension without the dot; also expands `{format}`
+    pub extension: &'a str,
+}
+
+/// A validated filename template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameTemplate {
+    source: String,
+    parts: Vec<Part>,
+}
+
+impl Default for FilenameTemplate {
+    fn default() -> Self {
+        Self::parse(DEFAULT_TEMPLATE).expect("default template is valid")
+    }
+}
+
+impl FilenameTemplate {
+    /// Parse and validate a template
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        let source = template.trim();
+        if source.is_empty() {
+            return Err(TemplateError::Empty);
+        }
+        if source.contains(['/', '\\']) {
+            return Err(TemplateError::PathSeparator);
+        }
+        if source.starts_with('.') {
+            return Err(TemplateError::Hidden);
+        }
+
+        let mut parts = Vec::new();
+        let mut rest = source;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .ok_or(TemplateError::UnclosedToken)?
+                + start;
+            let name = &rest[start + 1..end];
+            let token = Token::from_name(name)
+                .ok_or_else(|| TemplateError::UnknownToken(name.to_string()))?;
+            parts.push(Part::Token(token));
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+
+        // The extension comes from the output format; a literal one would be doubled
+        if let Some(Part::Literal(tail)) = parts.last()
+            && let Some((_, extension)) = tail.rsplit_once('.')
+            && (extension.is_empty()
+                || crate::constants::file_formats::is_image_extension(extension)
+                || crate::constants::file_formats::is_video_extension(extension)
+                || extension.eq_ignore_ascii_case("dng"))
+        {
+            return Err(TemplateError::Extension);
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            parts,
+        })
+    }
+
+    /// The template as written
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    fn has_seq(&self) -> bool {
+        self.parts.contains(&Part::Token(Token::Seq))
+    }
+
+    /// Filename without extension, with `{seq}` expanded to `seq`
+    pub fn render(&self, context: &FilenameContext<'_>, seq: u32) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Literal(text) => text.clone(),
+                Part::Token(Token::Kind) => context.kind.prefix().to_string(),
+                Part::Token(Token::Date) => context.time.format("%Y%m%d").to_string(),
+                Part::Token(Token::Time) => context.time.format("%H%M%S").to_string(),
+                Part::Token(Token::Device) => sanitize(context.device),
+                Part::Token(Token::Format) => sanitize(context.extension),
+                Part::Token(Token::Seq) => format!("{:03}", seq),
+            })
+            .collect()
+    }
+
+    /// First path in `dir` that `exists` reports as free
+    ///
+    /// `suffix` is appended to the rendered name, before the extension.
+    pub fn unique_path(
+        &self,
+        dir: &Path,
+        context: &FilenameContext<'_>,
+        suffix: &str,
+        exists: impl Fn(&Path) -> bool,
+    ) -> PathBuf {
+        let mut seq = 1;
+        loop {
+            let stem = self.render(context, seq);
+            let name = if self.has_seq() || seq == 1 {
+                format!("{}{}.{}", stem, suffix, context.extension)
+            } else {
+                format!("{}_{}{}.{}", stem, seq, suffix, context.extension)
+            };
+            let path = dir.join(name);
+            if !exists(&path) {
+                return path;
+            }
+            seq += 1;
+        }
+    }
+
+    /// Path of segment `segment` (1-based) of a split recording
+    ///
+    /// `{seq}` expands to the segment number, templates without it get
+    /// `_001`, `_002`, ... appended. A taken name gets a counter after the
+    /// segment number.
+    pub fn segment_path(
+        &self,
+        dir: &Path,
+        context: &FilenameContext<'_>,
+        segment: u32,
+        exists: impl Fn(&Path) -> bool,
+    ) -> PathBuf {
+        let mut stem = self.render(context, segment);
+        if !self.has_seq() {
+            stem = format!("{}_{:03}", stem, segment);
+        }
+        let mut path = dir.join(format!("{}.{}", stem, context.extension));
+        let mut counter = 2;
+        while exists(&path) {
+            path = dir.join(format!("{}_{}.{}", stem, counter, context.extension));
+            counter += 1;
+        }
+        path
+    }
+}
+
+/// Make a token value safe to use inside a filename
+fn sanitize(value: &str) -> String {
+    let value: String = value
+        .trim()
+        .chars()
+        .map(|c| {
+            if c == '/' || c == '\\' || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if value.is_empty() {
+        "camera".to_string()
+    } else {
+        value
+    }
+}
+
+/// Where and under which name a capture is saved
+#[derive(Debug, Clone)]
+pub struct OutputTarget {
+    /// Directory the file is written to
+    pub dir: PathBuf,
+    /// Template the filename is rendered from
+    pub template: FilenameTemplate,
+    /// Camera name for `{device}`
+    pub device: String,
+    /// Capture time for `{date}` and `{time}`
+    pub time: DateTime<Local>,
+}
+
+impl OutputTarget {
+    /// Save to `dir` with the default template, timestamped now
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            template: FilenameTemplate::default(),
+            device: String::new(),
+            time: Local::now(),
+        }
+    }
+
+    /// Name files from `template`
+    pub fn with_template(mut self, template: FilenameTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    /// Camera name for `{device}`
+    pub fn with_device(mut self, device: impl Into<String>) -> Self {
+        self.device = device.into();
+        self
+    }
+
+    /// Unused path for a capture of `kind` saved with `extension`
+    pub fn path(&self, kind: CaptureKind, extension: &str) -> PathBuf {
+        self.path_with_suffix(kind, "", extension)
+    }
+
+    /// Unused path with `suffix` appended to the name (e.g. "_HDR+")
+    pub fn path_with_suffix(&self, kind: CaptureKind, suffix: &str, extension: &str) -> PathBuf {
+        let context = FilenameContext {
+            kind,
+            time: self.time,
+            device: &self.device,
+            extension,
+        };
+        self.template
+            .unique_path(&self.dir, &context, suffix, |path| path.exists())
+    }
+
+    /// Unused path for segment `segment` (1-based) of a split recording
+    pub fn segment_path(&self, kind: CaptureKind, extension: &str, segment: u32) -> PathBuf {
+        let context = FilenameContext {
+            kind,
+            time: self.time,
+            device: &self.device,
+            extension,
+        };
+        self.template
+            .segment_path(&self.dir, &context, segment, |path| path.exists())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::collections::HashSet;
+
+    fn context(extension: &str) -> FilenameContext<'_> {
+        FilenameContext {
+            kind: CaptureKind::Photo,
+            time: Local.with_ymd_and_hms(2025, 3, 7, 9, 5, 2).unwrap(),
+            device: "USB Camera: HD/Pro",
+            extension,
+        }
+    }
+
+    #[test]
+    fn test_token_expansion() {
+        let template = FilenameTemplate::parse("{kind}-{date}-{time} {device} {format}").unwrap();
+        assert_eq!(
+            template.render(&context("jpg"), 1),
+            "IMG-20250307-090502 USB Camera: HD_Pro jpg"
+        );
+        assert_eq!(
+            FilenameTemplate::default().render(&context("jpg"), 1),
+            "IMG_20250307_090502"
+        );
+    }
+
+    #[test]
+    fn test_collisions_count_up_seq() {
+        let template = FilenameTemplate::parse("shot_{seq}").unwrap();
+        let taken: HashSet<PathBuf> = ["/out/shot_001.png", "/out/shot_002.png"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+        let path = template.unique_path(Path::new("/out"), &context("png"), "", |path| {
+            taken.contains(path)
+        });
+        assert_eq!(path, PathBuf::from("/out/shot_003.png"));
+    }
+
+    #[test]
+    fn test_collisions_without_seq_get_a_counter() {
+        let template = FilenameTemplate::default();
+        let taken: HashSet<PathBuf> = [
+            "/out/IMG_20250307_090502_HDR+.jpg",
+            "/out/IMG_20250307_090502_2_HDR+.jpg",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+        let path = template.unique_path(Path::new("/out"), &context("jpg"), "_HDR+", |path| {
+            taken.contains(path)
+        });
+        assert_eq!(path, PathBuf::from("/out/IMG_20250307_090502_3_HDR+.jpg"));
+    }
+
+    #[test]
+    fn test_segment_names() {
+        let dir = Path::new("/out");
+        let template = FilenameTemplate::parse("{kind}_{date}_part{seq}").unwrap();
+        assert_eq!(
+            template.segment_path(dir, &context("mp4"), 2, |_| false),
+            PathBuf::from("/out/IMG_20250307_part002.mp4")
+        );
+        let taken = PathBuf::from("/out/IMG_20250307_090502_001.mp4");
+        assert_eq!(
+            FilenameTemplate::default().segment_path(dir, &context("mp4"), 1, |path| path == taken),
+            PathBuf::from("/out/IMG_20250307_090502_001_2.mp4")
+        );
+    }
+
+    #[test]
+    fn test_rejected_templates() {
+        let error = |template| FilenameTemplate::parse(template).unwrap_err();
+        assert_eq!(error("  "), TemplateError::Empty);
+        assert_eq!(error("../{date}"), TemplateError::PathSeparator);
+        assert_eq!(error("photos\\{time}"), TemplateError::PathSeparator);
+        assert_eq!(error(".{date}"), TemplateError::Hidden);
+        assert_eq!(error("{date}.jpg"), TemplateError::Extension);
+        assert_eq!(error("{date}."), TemplateError::Extension);
+        assert_eq!(error("{date"), TemplateError::UnclosedToken);
+        assert_eq!(
+            error("{camera}"),
+            TemplateError::UnknownToken("camera".to_string())
+        );
+        // A dot inside the name is fine
+        assert!(FilenameTemplate::parse("v1.{seq}_{date}").is_ok());
+    }
+}
@@ -35,6 +35,10 @@
 //!
 //! - [`photo`]: Async photo capture with filters and JPEG encoding
 //! - [`video`]: Video recording with GStreamer and hardware acceleration
+//! - [`filename`]: Output filenames rendered from the user's template
+//! - [`watermark`]: Text and timestamp burned into saved photos and recordings
 
+pub mod filename;
 pub mod photo;
 pub mod video;
+pub mod watermark;
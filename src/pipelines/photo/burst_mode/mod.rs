@@ -174,8 +174,8 @@ async fn convert_frame_to_rgba(frame: &CameraFrame) -> Result<Vec<u8>, String> {
                 v_stride: 0,
             }
         }
-        // Single-plane formats: Gray8, RGB24
-        PixelFormat::Gray8 | PixelFormat::RGB24 => GpuFrameInput {
+        // Single-plane formats: Gray8, Gray16, RGB24
+        PixelFormat::Gray8 | PixelFormat::Gray16 | PixelFormat::RGB24 => GpuFrameInput {
             format: frame.format,
             width: frame.width,
             height: frame.height,
@@ -252,6 +252,8 @@ pub struct BurstModeConfig {
     pub camera_metadata: super::CameraMetadata,
     /// Sensor rotation to correct the image orientation
     pub rotation: SensorRotation,
+    /// Watermark burned into the saved photos (None = disabled)
+    pub watermark: Option<crate::pipelines::watermark::StillWatermark>,
 }
 
 impl Default for BurstModeConfig {
@@ -268,6 +270,7 @@ impl Default for BurstModeConfig {
             encoding_format: super::EncodingFormat::Jpeg, // Default to JPEG
             camera_metadata: super::CameraMetadata::default(),
             rotation: SensorRotation::None, // No rotation by default
+            watermark: None,                // Captures stay untouched by default
         }
     }
 }
@@ -2584,44 +2587,32 @@ pub async fn process_burst_mode(
 ///
 /// # Arguments
 /// * `frame` - The merged frame to save
-/// * `output_dir` - Directory to save the image
+/// * `target` - Directory and filename template to save the image with
 /// * `crop_rect` - Optional crop rectangle (x, y, width, height) for aspect ratio
 /// * `encoding_format` - Output format (JPEG, PNG, or DNG)
 /// * `camera_metadata` - Optional camera metadata for DNG encoding
 /// * `filter` - Optional filter to apply to the image (None or Standard = no filter)
 /// * `rotation` - Sensor rotation to correct the image orientation
+/// * `watermark` - Optional watermark burned into the image after rotation
 /// * `filename_suffix` - Optional suffix for filename (e.g., "_HDR+"), None for no suffix
 #[allow(clippy::too_many_arguments)]
 pub async fn save_output(
     frame: &MergedFrame,
-    output_dir: std::path::PathBuf,
+    target: crate::pipelines::filename::OutputTarget,
     crop_rect: Option<(u32, u32, u32, u32)>,
     encoding_format: super::EncodingFormat,
     camera_metadata: super::CameraMetadata,
     filter: Option<crate::app::FilterType>,
     rotation: SensorRotation,
+    watermark: Option<&crate::pipelines::watermark::StillWatermark>,
     filename_suffix: Option<&str>,
 ) -> Result<std::path::PathBuf, String> {
     use super::{EncodingQuality, PhotoEncoder};
+    use crate::pipelines::filename::CaptureKind;
     use crate::shaders::apply_filter_gpu_rgba;
     use image::{ImageBuffer, Rgba};
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0);
 
-    let suffix = filename_suffix.unwrap_or("");
-    let filename = format!(
-        "IMG_{}{}.{}",
-        timestamp,
-        suffix,
-        encoding_format.extension()
-    );
-    let output_path = output_dir.join(&filename);
-
-    tokio::fs::create_dir_all(&output_dir)
+    tokio::fs::create_dir_all(&target.dir)
         .await
         .map_err(|e| format!("Failed to create output directory: {}", e))?;
 
@@ -2685,6 +2676,14 @@ pub async fn save_output(
         (rgb_img, w, h)
     };
 
+    // Burn in the watermark last, so nothing alters the text
+    let rgb_img = match watermark.cloned() {
+        Some(watermark) => tokio::task::spawn_blocking(move || watermark.burn_into(rgb_img))
+            .await
+            .map_err(|e| format!("Watermark task error: {}", e))??,
+        None => rgb_img,
+    };
+
     // Create a PhotoEncoder for the selected format
     let mut encoder = PhotoEncoder::new();
     encoder.set_format(encoding_format);
@@ -2702,6 +2701,11 @@ pub async fn save_output(
     let encoded = encoder.encode(processed).await?;
 
     // Save the encoded data
+    let output_path = target.path_with_suffix(
+        CaptureKind::Photo,
+        filename_suffix.unwrap_or(""),
+        encoding_format.extension(),
+    );
     let output_path_clone = output_path.clone();
     let data = encoded.data;
     tokio::task::spawn_blocking(move || {
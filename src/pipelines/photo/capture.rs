@@ -118,6 +118,7 @@ mod tests {
             stride: 1920 * 4, // RGBA stride
             yuv_planes: None,
             captured_at: std::time::Instant::now(),
+            stamp: None,
         };
 
         let captured = PhotoCapture::capture_from_frame(frame).unwrap();
@@ -5,10 +5,14 @@
 //! This module handles encoding processed images to various formats:
 //! - JPEG (with quality control)
 //! - PNG (lossless)
+//! - WebP (lossless)
+//!
+//! JPEG and PNG output can carry capture metadata (see [`super::metadata`]).
 //!
 //! All encoding operations run asynchronously to avoid blocking.
 
 use super::processing::ProcessedImage;
+use crate::pipelines::filename::{CaptureKind, OutputTarget};
 use image::RgbImage;
 use std::path::PathBuf;
 use tracing::{debug, error, info};
@@ -20,6 +24,8 @@ pub enum EncodingFormat {
     Jpeg,
     /// PNG format (lossless compression)
     Png,
+    /// WebP format (lossless compression)
+    WebP,
     /// DNG format (raw image data)
     Dng,
 }
@@ -30,6 +36,7 @@ impl EncodingFormat {
         match self {
             EncodingFormat::Jpeg => "jpg",
             EncodingFormat::Png => "png",
+            EncodingFormat::WebP => "webp",
             EncodingFormat::Dng => "dng",
         }
     }
@@ -40,11 +47,49 @@ impl From<crate::config::PhotoOutputFormat> for EncodingFormat {
         match format {
             crate::config::PhotoOutputFormat::Jpeg => EncodingFormat::Jpeg,
             crate::config::PhotoOutputFormat::Png => EncodingFormat::Png,
+            crate::config::PhotoOutputFormat::WebP => EncodingFormat::WebP,
             crate::config::PhotoOutputFormat::Dng => EncodingFormat::Dng,
         }
     }
 }
 
+/// Still capture output format together with its encoder parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StillFormat {
+    /// JPEG with quality 1-100 (passed to the encoder's quality setting)
+    Jpeg { quality: u8 },
+    /// Lossless PNG
+    Png,
+    /// Lossless WebP
+    WebP,
+    /// DNG raw
+    Dng,
+}
+
+impl StillFormat {
+    /// Build from the configured output format and JPEG quality
+    pub fn from_config(format: crate::config::PhotoOutputFormat, jpeg_quality: u8) -> Self {
+        match format {
+            crate::config::PhotoOutputFormat::Jpeg => StillFormat::Jpeg {
+                quality: jpeg_quality.clamp(1, 100),
+            },
+            crate::config::PhotoOutputFormat::Png => StillFormat::Png,
+            crate::config::PhotoOutputFormat::WebP => StillFormat::WebP,
+            crate::config::PhotoOutputFormat::Dng => StillFormat::Dng,
+        }
+    }
+
+    /// Container format written for this still format
+    pub fn encoding_format(&self) -> EncodingFormat {
+        match self {
+            StillFormat::Jpeg { .. } => EncodingFormat::Jpeg,
+            StillFormat::Png => EncodingFormat::Png,
+            StillFormat::WebP => EncodingFormat::WebP,
+            StillFormat::Dng => EncodingFormat::Dng,
+        }
+    }
+}
+
 /// Encoding quality settings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EncodingQuality {
@@ -91,12 +136,14 @@ pub struct CameraMetadata {
     pub iso: Option<u32>,
     /// Gain value (camera-specific units)
     pub gain: Option<i32>,
+    /// Capture pipeline details to embed into JPEG/PNG stills (None = don't embed)
+    pub capture_info: Option<super::metadata::CaptureInfo>,
 }
 
 /// Photo encoder
 pub struct PhotoEncoder {
     format: EncodingFormat,
-    quality: EncodingQuality,
+    jpeg_quality: u8,
     camera_metadata: CameraMetadata,
 }
 
@@ -105,7 +152,7 @@ impl PhotoEncoder {
     pub fn new() -> Self {
         Self {
             format: EncodingFormat::Jpeg,
-            quality: EncodingQuality::High,
+            jpeg_quality: EncodingQuality::High.jpeg_quality(),
             camera_metadata: CameraMetadata::default(),
         }
     }
@@ -117,7 +164,15 @@ impl PhotoEncoder {
 
     /// Set encoding quality (only affects JPEG)
     pub fn set_quality(&mut self, quality: EncodingQuality) {
-        self.quality = quality;
+        self.jpeg_quality = quality.jpeg_quality();
+    }
+
+    /// Set format and JPEG quality from a still format
+    pub fn set_still_format(&mut self, still: StillFormat) {
+        self.format = still.encoding_format();
+        if let StillFormat::Jpeg { quality } = still {
+            self.jpeg_quality = quality.clamp(1, 100);
+        }
     }
 
     /// Set camera metadata for DNG encoding
@@ -136,7 +191,7 @@ impl PhotoEncoder {
     /// * `Ok(EncodedImage)` - Encoded image data
     /// * `Err(String)` - Error message
     pub async fn encode(&self, processed: ProcessedImage) -> Result<EncodedImage, String> {
-        info!(
+        debug!(
             width = processed.width,
             height = processed.height,
             format = ?self.format,
@@ -144,14 +199,27 @@ impl PhotoEncoder {
         );
 
         let format = self.format;
-        let quality = self.quality;
+        let quality = self.jpeg_quality;
         let camera_metadata = self.camera_metadata.clone();
 
         // Run encoding in background task (CPU-bound)
         tokio::task::spawn_blocking(move || {
             let data = match format {
-                EncodingFormat::Jpeg => Self::encode_jpeg(processed.image, quality)?,
-                EncodingFormat::Png => Self::encode_png(processed.image)?,
+                EncodingFormat::Jpeg => {
+                    let data = Self::encode_jpeg(processed.image, quality)?;
+                    match &camera_metadata.capture_info {
+                        Some(info) => super::metadata::embed_in_jpeg(&data, info)?,
+                        None => data,
+                    }
+                }
+                EncodingFormat::Png => {
+                    let data = Self::encode_png(processed.image)?;
+                    match &camera_metadata.capture_info {
+                        Some(info) => super::metadata::embed_in_png(&data, info)?,
+                        None => data,
+                    }
+                }
+                EncodingFormat::WebP => Self::encode_webp(processed.image)?,
                 EncodingFormat::Dng => Self::encode_dng(
                     &processed.image,
                     processed.width,
@@ -175,11 +243,11 @@ impl PhotoEncoder {
 
     /// Save encoded image to disk asynchronously
     ///
-    /// Generates a timestamped filename and saves to the specified directory.
+    /// Names the file from the target's template and saves it to the target's directory.
     ///
     /// # Arguments
     /// * `encoded` - Encoded image data
-    /// * `output_dir` - Directory to save the photo
+    /// * `target` - Directory and filename template to save the photo with
     ///
     /// # Returns
     /// * `Ok(PathBuf)` - Path to saved file
@@ -187,8 +255,9 @@ impl PhotoEncoder {
     pub async fn save(
         &self,
         encoded: EncodedImage,
-        output_dir: PathBuf,
+        target: OutputTarget,
     ) -> Result<PathBuf, String> {
+        let output_dir = &target.dir;
         debug!(
             output_dir = %output_dir.display(),
             format = ?encoded.format,
@@ -197,7 +266,7 @@ impl PhotoEncoder {
         );
 
         // Ensure output directory exists
-        if let Err(e) = tokio::fs::create_dir_all(&output_dir).await {
+        if let Err(e) = tokio::fs::create_dir_all(output_dir).await {
             error!(
                 output_dir = %output_dir.display(),
                 error = %e,
@@ -210,10 +279,7 @@ impl PhotoEncoder {
             ));
         }
 
-        // Generate filename with timestamp
-        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let filename = format!("IMG_{}.{}", timestamp, encoded.format.extension());
-        let filepath = output_dir.join(&filename);
+        let filepath = target.path(CaptureKind::Photo, encoded.format.extension());
 
         info!(path = %filepath.display(), "Saving photo");
 
@@ -253,13 +319,13 @@ impl PhotoEncoder {
     }
 
     /// Encode image as JPEG
-    fn encode_jpeg(image: RgbImage, quality: EncodingQuality) -> Result<Vec<u8>, String> {
+    fn encode_jpeg(image: RgbImage, quality: u8) -> Result<Vec<u8>, String> {
         let mut buffer = Vec::new();
         let mut cursor = std::io::Cursor::new(&mut buffer);
 
         // Create JPEG encoder with quality setting
         let mut encoder =
-            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut cursor, quality.jpeg_quality());
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut cursor, quality.clamp(1, 100));
 
         encoder
             .encode(
@@ -287,6 +353,22 @@ impl PhotoEncoder {
         Ok(buffer)
     }
 
+    /// Encode image as lossless WebP
+    fn encode_webp(image: RgbImage) -> Result<Vec<u8>, String> {
+        let mut buffer = Vec::new();
+
+        image::codecs::webp::WebPEncoder::new_lossless(&mut buffer)
+            .encode(
+                image.as_raw(),
+                image.width(),
+                image.height(),
+                image::ExtendedColorType::Rgb8,
+            )
+            .map_err(|e| format!("WebP encoding failed: {}", e))?;
+
+        Ok(buffer)
+    }
+
     /// Encode image as DNG (Digital Negative raw format)
     ///
     /// Creates a simple linear DNG file with RGB data stored as strips.
@@ -433,9 +515,88 @@ mod tests {
     fn test_format_extensions() {
         assert_eq!(EncodingFormat::Jpeg.extension(), "jpg");
         assert_eq!(EncodingFormat::Png.extension(), "png");
+        assert_eq!(EncodingFormat::WebP.extension(), "webp");
         assert_eq!(EncodingFormat::Dng.extension(), "dng");
     }
 
+    /// Noisy gradient so JPEG quality has something to throw away
+    fn test_image() -> RgbImage {
+        RgbImage::from_fn(128, 96, |x, y| {
+            let noise = ((x * 7919 + y * 104_729) % 61) as u8;
+            image::Rgb([
+                (x * 2) as u8 ^ noise,
+                (y * 2) as u8,
+                ((x + y) as u8).wrapping_mul(3) ^ noise,
+            ])
+        })
+    }
+
+    async fn encode(still: StillFormat) -> EncodedImage {
+        let image = test_image();
+        let mut encoder = PhotoEncoder::new();
+        encoder.set_still_format(still);
+        encoder
+            .encode(ProcessedImage {
+                width: image.width(),
+                height: image.height(),
+                image,
+            })
+            .await
+            .expect("encoding succeeds")
+    }
+
+    #[tokio::test]
+    async fn test_jpeg_quality_affects_file_size() {
+        let low = encode(StillFormat::Jpeg { quality: 10 }).await;
+        let high = encode(StillFormat::Jpeg { quality: 95 }).await;
+        assert!(
+            high.data.len() > low.data.len() * 2,
+            "quality 95 ({} bytes) should be much larger than quality 10 ({} bytes)",
+            high.data.len(),
+            low.data.len()
+        );
+
+        for encoded in [low, high] {
+            let decoded =
+                image::load_from_memory_with_format(&encoded.data, image::ImageFormat::Jpeg)
+                    .expect("JPEG decodes");
+            assert_eq!((decoded.width(), decoded.height()), (128, 96));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lossless_formats_round_trip() {
+        let original = test_image();
+        for (still, format) in [
+            (StillFormat::Png, image::ImageFormat::Png),
+            (StillFormat::WebP, image::ImageFormat::WebP),
+        ] {
+            let encoded = encode(still).await;
+            assert_eq!(encoded.format, still.encoding_format());
+            let decoded = image::load_from_memory_with_format(&encoded.data, format)
+                .expect("image decodes")
+                .to_rgb8();
+            assert_eq!(decoded, original, "{:?} should be lossless", still);
+        }
+    }
+
+    #[test]
+    fn test_still_format_from_config_clamps_quality() {
+        use crate::config::PhotoOutputFormat;
+        assert_eq!(
+            StillFormat::from_config(PhotoOutputFormat::Jpeg, 0),
+            StillFormat::Jpeg { quality: 1 }
+        );
+        assert_eq!(
+            StillFormat::from_config(PhotoOutputFormat::Jpeg, 250),
+            StillFormat::Jpeg { quality: 100 }
+        );
+        assert_eq!(
+            StillFormat::from_config(PhotoOutputFormat::WebP, 50),
+            StillFormat::WebP
+        );
+    }
+
     #[test]
     fn test_jpeg_quality_values() {
         assert_eq!(EncodingQuality::Low.jpeg_quality(), 60);
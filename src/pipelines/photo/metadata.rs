@@ -0,0 +1,314 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Capture metadata embedding for saved stills
+//!
+//! Writes a minimal EXIF block (ImageDescription, Make, Software) into JPEG
+//! files as an APP1 segment and into PNG files as an `eXIf` chunk. PNG files
+//! additionally get human-readable `tEXt` chunks with the same information.
+
+/// Capture pipeline details embedded into saved stills
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaptureInfo {
+    /// Camera device name
+    pub device: String,
+    /// Capture resolution (e.g., "1920x1080")
+    pub resolution: String,
+    /// Capture framerate (e.g., "30 fps")
+    pub framerate: String,
+    /// Native camera format (e.g., "MJPG", "YUYV")
+    pub native_format: String,
+    /// Decoder element used for this format, if any (e.g., "vaapijpegdec")
+    pub decoder: Option<String>,
+}
+
+impl CaptureInfo {
+    /// Single-line summary used for the EXIF ImageDescription tag
+    pub fn description(&self) -> String {
+        format!(
+            "Resolution: {}; Framerate: {}; Format: {}; Decoder: {}",
+            self.resolution,
+            self.framerate,
+            self.native_format,
+            self.decoder.as_deref().unwrap_or("none")
+        )
+    }
+
+    /// Key/value pairs written as PNG text chunks
+    fn text_entries(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Camera", self.device.clone()),
+            ("Resolution", self.resolution.clone()),
+            ("Framerate", self.framerate.clone()),
+            ("Format", self.native_format.clone()),
+            (
+                "Decoder",
+                self.decoder.clone().unwrap_or_else(|| "none".to_string()),
+            ),
+        ]
+    }
+}
+
+/// EXIF tag IDs used in IFD0
+const TAG_IMAGE_DESCRIPTION: u16 = 0x010E;
+const TAG_MAKE: u16 = 0x010F;
+const TAG_SOFTWARE: u16 = 0x0131;
+/// EXIF ASCII field type
+const TYPE_ASCII: u16 = 2;
+
+/// Build a little-endian TIFF/EXIF block describing the capture
+pub fn build_exif(info: &CaptureInfo) -> Vec<u8> {
+    let software = format!("Camera v{}", env!("CARGO_PKG_VERSION"));
+    // Entries must be sorted by tag
+    let entries: [(u16, String); 3] = [
+        (TAG_IMAGE_DESCRIPTION, info.description()),
+        (TAG_MAKE, info.device.clone()),
+        (TAG_SOFTWARE, software),
+    ];
+
+    // Header (8) + entry count (2) + entries (12 each) + next IFD offset (4)
+    let data_start = 8 + 2 + entries.len() * 12 + 4;
+    let mut ifd = Vec::new();
+    let mut data = Vec::new();
+
+    for (tag, value) in &entries {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        ifd.extend_from_slice(&tag.to_le_bytes());
+        ifd.extend_from_slice(&TYPE_ASCII.to_le_bytes());
+        ifd.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        if bytes.len() <= 4 {
+            bytes.resize(4, 0);
+            ifd.extend_from_slice(&bytes);
+        } else {
+            let offset = (data_start + data.len()) as u32;
+            ifd.extend_from_slice(&offset.to_le_bytes());
+            data.extend_from_slice(&bytes);
+            // Values start on word boundaries
+            if data.len() % 2 == 1 {
+                data.push(0);
+            }
+        }
+    }
+
+    let mut tiff = Vec::with_capacity(data_start + data.len());
+    tiff.extend_from_slice(b"II*\0");
+    tiff.extend_from_slice(&8u32.to_le_bytes());
+    tiff.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    tiff.extend_from_slice(&ifd);
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    tiff.extend_from_slice(&data);
+    tiff
+}
+
+/// Insert an EXIF APP1 segment after the JFIF APP0 segment
+///
+/// JFIF requires its APP0 to come first, so the EXIF segment goes right after
+/// it, or right after the SOI marker when the stream has no APP0.
+pub fn embed_in_jpeg(jpeg: &[u8], info: &CaptureInfo) -> Result<Vec<u8>, String> {
+    if jpeg.len() < 2 || jpeg[0..2] != [0xFF, 0xD8] {
+        return Err("Not a JPEG stream".to_string());
+    }
+    let insert_at = jfif_app0_end(jpeg)?.unwrap_or(2);
+
+    let exif = build_exif(info);
+    let segment_len = 2 + 6 + exif.len();
+    if segment_len > u16::MAX as usize {
+        return Err("EXIF metadata too large".to_string());
+    }
+
+    let mut out = Vec::with_capacity(jpeg.len() + segment_len + 2);
+    out.extend_from_slice(&jpeg[..insert_at]);
+    out.extend_from_slice(&[0xFF, 0xE1]);
+    out.extend_from_slice(&(segment_len as u16).to_be_bytes());
+    out.extend_from_slice(b"Exif\0\0");
+    out.extend_from_slice(&exif);
+    out.extend_from_slice(&jpeg[insert_at..]);
+    Ok(out)
+}
+
+/// End offset of the APP0 segment following the SOI marker, if there is one
+fn jfif_app0_end(jpeg: &[u8]) -> Result<Option<usize>, String> {
+    if jpeg.get(2..4) != Some(&[0xFF, 0xE0]) {
+        return Ok(None);
+    }
+    let length = jpeg
+        .get(4..6)
+        .map(|len| u16::from_be_bytes([len[0], len[1]]) as usize)
+        .ok_or("Truncated APP0 segment")?;
+    let end = 4 + length;
+    if length < 2 || end > jpeg.len() {
+        return Err("Truncated APP0 segment".to_string());
+    }
+    Ok(Some(end))
+}
+
+/// Insert `eXIf` and `tEXt` chunks right after the PNG IHDR chunk
+pub fn embed_in_png(png: &[u8], info: &CaptureInfo) -> Result<Vec<u8>, String> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    // Signature (8) + IHDR length (4) + type (4) + data (13) + CRC (4)
+    const IHDR_END: usize = 33;
+
+    if png.len() < IHDR_END || &png[0..8] != SIGNATURE || &png[12..16] != b"IHDR" {
+        return Err("Not a PNG stream".to_string());
+    }
+
+    let mut out = Vec::with_capacity(png.len() + 512);
+    out.extend_from_slice(&png[..IHDR_END]);
+    write_png_chunk(&mut out, b"eXIf", &build_exif(info));
+    for (keyword, value) in info.text_entries() {
+        // tEXt is Latin-1; keep it to printable ASCII
+        let value: String = value
+            .chars()
+            .map(|c| {
+                if c.is_ascii() && !c.is_ascii_control() {
+                    c
+                } else {
+                    '?'
+                }
+            })
+            .collect();
+        let mut payload = keyword.as_bytes().to_vec();
+        payload.push(0);
+        payload.extend_from_slice(value.as_bytes());
+        write_png_chunk(&mut out, b"tEXt", &payload);
+    }
+    out.extend_from_slice(&png[IHDR_END..]);
+    Ok(out)
+}
+
+/// Append a PNG chunk (length, type, data, CRC)
+fn write_png_chunk(out: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(chunk_type);
+    out.extend_from_slice(data);
+    let crc = crc32(chunk_type.iter().chain(data.iter()).copied());
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// CRC-32 (IEEE) as used by PNG chunks
+fn crc32(bytes: impl Iterator<Item = u8>) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::ImageDecoder;
+    use std::io::Cursor;
+
+    fn sample_info() -> CaptureInfo {
+        CaptureInfo {
+            device: "Test Webcam".to_string(),
+            resolution: "1920x1080".to_string(),
+            framerate: "30 fps".to_string(),
+            native_format: "MJPG".to_string(),
+            decoder: Some("jpegdec".to_string()),
+        }
+    }
+
+    fn sample_image() -> image::RgbImage {
+        image::RgbImage::from_pixel(8, 8, image::Rgb([120, 60, 30]))
+    }
+
+    fn contains(haystack: &[u8], needle: &str) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle.as_bytes())
+    }
+
+    #[test]
+    fn test_crc32_known_value() {
+        // CRC of the IEND chunk type is a well-known constant
+        assert_eq!(crc32(b"IEND".iter().copied()), 0xAE42_6082);
+    }
+
+    #[test]
+    fn test_jpeg_exif_round_trip() {
+        let mut jpeg = Vec::new();
+        sample_image()
+            .write_to(&mut Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+            .unwrap();
+        let tagged = embed_in_jpeg(&jpeg, &sample_info()).unwrap();
+
+        let mut decoder = image::codecs::jpeg::JpegDecoder::new(Cursor::new(&tagged)).unwrap();
+        let exif = decoder
+            .exif_metadata()
+            .unwrap()
+            .expect("EXIF block present");
+        assert!(contains(&exif, "Test Webcam"));
+        assert!(contains(&exif, "Decoder: jpegdec"));
+        assert!(contains(&exif, "Resolution: 1920x1080"));
+
+        // Image data is still decodable
+        let decoded = image::load_from_memory(&tagged).unwrap();
+        assert_eq!(decoded.width(), 8);
+    }
+
+    #[test]
+    fn test_jpeg_exif_follows_jfif_app0() {
+        let mut encoded = Vec::new();
+        sample_image()
+            .write_to(&mut Cursor::new(&mut encoded), image::ImageFormat::Jpeg)
+            .unwrap();
+        // Make sure the input is JFIF, whatever the encoder wrote
+        let body = match jfif_app0_end(&encoded).unwrap() {
+            Some(end) => &encoded[end..],
+            None => &encoded[2..],
+        };
+        let app0: [u8; 18] = [
+            0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F', 0x00, 0x01, 0x01, 0x00, 0x00, 0x01,
+            0x00, 0x01, 0x00, 0x00,
+        ];
+        let mut jfif = vec![0xFF, 0xD8];
+        jfif.extend_from_slice(&app0);
+        jfif.extend_from_slice(body);
+
+        let tagged = embed_in_jpeg(&jfif, &sample_info()).unwrap();
+        // SOI, then the untouched APP0, then the EXIF APP1
+        assert_eq!(&tagged[..20], &jfif[..20]);
+        assert_eq!(&tagged[20..22], &[0xFF, 0xE1]);
+        assert_eq!(&tagged[24..30], b"Exif\0\0");
+        assert!(image::load_from_memory(&tagged).is_ok());
+
+        // Without APP0 the segment goes right after SOI
+        let mut bare = vec![0xFF, 0xD8];
+        bare.extend_from_slice(body);
+        let tagged = embed_in_jpeg(&bare, &sample_info()).unwrap();
+        assert_eq!(&tagged[2..4], &[0xFF, 0xE1]);
+    }
+
+    #[test]
+    fn test_png_exif_round_trip() {
+        let mut png = Vec::new();
+        sample_image()
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let tagged = embed_in_png(&png, &sample_info()).unwrap();
+
+        let mut decoder = image::codecs::png::PngDecoder::new(Cursor::new(&tagged)).unwrap();
+        let exif = decoder
+            .exif_metadata()
+            .unwrap()
+            .expect("eXIf chunk present");
+        assert!(contains(&exif, "Test Webcam"));
+        assert!(contains(&exif, "Format: MJPG"));
+        assert!(contains(&tagged, "Framerate\u{0}30 fps"));
+
+        let decoded = image::load_from_memory(&tagged).unwrap();
+        assert_eq!(decoded.width(), 8);
+    }
+
+    #[test]
+    fn test_rejects_wrong_container() {
+        assert!(embed_in_jpeg(b"\x89PNG", &sample_info()).is_err());
+        assert!(embed_in_png(&[0xFF, 0xD8, 0xFF], &sample_info()).is_err());
+    }
+}
@@ -29,12 +29,15 @@
 pub mod burst_mode;
 pub mod capture;
 pub mod encoding;
+pub mod metadata;
 pub mod processing;
 
-pub use encoding::{CameraMetadata, EncodingFormat, EncodingQuality, PhotoEncoder};
+pub use encoding::{CameraMetadata, EncodingFormat, EncodingQuality, PhotoEncoder, StillFormat};
+pub use metadata::CaptureInfo;
 pub use processing::{PostProcessingConfig, PostProcessor};
 
 use crate::backends::camera::types::CameraFrame;
+use crate::pipelines::filename::OutputTarget;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -81,7 +84,7 @@ impl PhotoPipeline {
     ///
     /// # Arguments
     /// * `frame` - Raw camera frame (RGBA format)
-    /// * `output_dir` - Directory to save the photo
+    /// * `target` - Directory and filename template to save the photo with
     ///
     /// # Returns
     /// * `Ok(PathBuf)` - Path to saved photo
@@ -89,7 +92,7 @@ impl PhotoPipeline {
     pub async fn capture_and_save(
         &self,
         frame: Arc<CameraFrame>,
-        output_dir: PathBuf,
+        target: OutputTarget,
     ) -> Result<PathBuf, String> {
         // Stage 1: Post-process (async, CPU-bound)
         let processed = self.post_processor.process(frame).await?;
@@ -98,7 +101,7 @@ impl PhotoPipeline {
         let encoded = self.encoder.encode(processed).await?;
 
         // Stage 3: Save to disk (async, I/O-bound)
-        let output_path = self.encoder.save(encoded, output_dir).await?;
+        let output_path = self.encoder.save(encoded, target).await?;
 
         Ok(output_path)
     }
@@ -109,12 +112,12 @@ impl PhotoPipeline {
     ///
     /// # Arguments
     /// * `frame` - Raw camera frame
-    /// * `output_dir` - Directory to save the photo
+    /// * `target` - Directory and filename template to save the photo with
     /// * `progress` - Callback for progress updates (0.0 - 1.0)
     pub async fn capture_and_save_with_progress<F>(
         &self,
         frame: Arc<CameraFrame>,
-        output_dir: PathBuf,
+        target: OutputTarget,
         mut progress: F,
     ) -> Result<PathBuf, String>
     where
@@ -131,7 +134,7 @@ impl PhotoPipeline {
         progress(0.66);
 
         // Save
-        let output_path = self.encoder.save(encoded, output_dir).await?;
+        let output_path = self.encoder.save(encoded, target).await?;
         progress(1.0);
 
         Ok(output_path)
@@ -152,6 +155,11 @@ impl PhotoPipeline {
         self.encoder.set_quality(quality);
     }
 
+    /// Update encoding format and JPEG quality together
+    pub fn set_still_format(&mut self, still: StillFormat) {
+        self.encoder.set_still_format(still);
+    }
+
     /// Set camera metadata for DNG encoding
     pub fn set_camera_metadata(&mut self, metadata: CameraMetadata) {
         self.encoder.set_camera_metadata(metadata);
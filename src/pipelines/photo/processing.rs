@@ -7,16 +7,19 @@
 //! - RGBA to RGB conversion (drop alpha channel)
 //! - Sharpening
 //! - Brightness/contrast adjustments
+//! - Watermark burn-in (last, so no other step alters the text)
 //!
 //! The pipeline is optimized to apply filters on RGBA data before RGB conversion,
 //! avoiding unnecessary format conversions.
 
 use crate::app::FilterType;
 use crate::backends::camera::types::{CameraFrame, PixelFormat, SensorRotation};
+use crate::config::ColorEffect;
+use crate::pipelines::watermark::StillWatermark;
 use crate::shaders::{GpuFrameInput, apply_filter_gpu_rgba, get_gpu_convert_pipeline};
 use image::RgbImage;
 use std::sync::Arc;
-use tracing::{debug, info, warn};
+use tracing::{debug, warn};
 
 /// Post-processing configuration
 #[derive(Debug, Clone)]
@@ -33,12 +36,16 @@ pub struct PostProcessingConfig {
     pub saturation: f32,
     /// Filter type to apply
     pub filter_type: FilterType,
+    /// Preview color effect carried into the photo (None unless enabled for output)
+    pub color_effect: ColorEffect,
     /// Crop rectangle (x, y, width, height) - None means no cropping
     pub crop_rect: Option<(u32, u32, u32, u32)>,
     /// Zoom level (1.0 = no zoom, 2.0 = 2x zoom center crop)
     pub zoom_level: f32,
     /// Sensor rotation to correct the image orientation
     pub rotation: SensorRotation,
+    /// Watermark burned into the photo (None = disabled)
+    pub watermark: Option<StillWatermark>,
 }
 
 impl Default for PostProcessingConfig {
@@ -50,9 +57,11 @@ impl Default for PostProcessingConfig {
             contrast: 1.0,
             saturation: 1.0,
             filter_type: FilterType::Standard,
+            color_effect: ColorEffect::None,
             crop_rect: None,
             zoom_level: 1.0,
             rotation: SensorRotation::None,
+            watermark: None,
         }
     }
 }
@@ -87,7 +96,7 @@ impl PostProcessor {
     /// * `Ok(ProcessedImage)` - Processed RGB image
     /// * `Err(String)` - Error message
     pub async fn process(&self, frame: Arc<CameraFrame>) -> Result<ProcessedImage, String> {
-        info!(
+        debug!(
             width = frame.width,
             height = frame.height,
             format = ?frame.format,
@@ -95,6 +104,7 @@ impl PostProcessor {
         );
 
         let config = self.config.clone();
+        let watermark = config.watermark.clone();
         let frame_width = frame.width;
         let frame_height = frame.height;
 
@@ -130,6 +140,13 @@ impl PostProcessor {
             rgba_data
         };
 
+        // Step 1.5: Apply the color effect on top of the filter, as the preview does
+        let filtered_rgba = if config.color_effect != ColorEffect::None {
+            Self::apply_color_effect(filtered_rgba, config.color_effect)
+        } else {
+            filtered_rgba
+        };
+
         // Step 2: Apply aspect ratio cropping if configured
         let (cropped_rgba, current_width, current_height) = if let Some((x, y, w, h)) =
             config.crop_rect
@@ -189,6 +206,15 @@ impl PostProcessor {
             rgb_image
         };
 
+        // Step 7: Burn in the watermark
+        let rgb_image = if let Some(watermark) = watermark {
+            tokio::task::spawn_blocking(move || watermark.burn_into(rgb_image))
+                .await
+                .map_err(|e| format!("Watermark task error: {}", e))??
+        } else {
+            rgb_image
+        };
+
         debug!("Post-processing complete");
 
         Ok(ProcessedImage {
@@ -260,8 +286,8 @@ impl PostProcessor {
                     v_stride: 0,
                 }
             }
-            // Single-plane formats: Gray8, RGB24
-            PixelFormat::Gray8 | PixelFormat::RGB24 => GpuFrameInput {
+            // Single-plane formats: Gray8, Gray16, RGB24
+            PixelFormat::Gray8 | PixelFormat::Gray16 | PixelFormat::RGB24 => GpuFrameInput {
                 format: frame.format,
                 width: frame.width,
                 height: frame.height,
@@ -443,6 +469,15 @@ impl PostProcessor {
         }
     }
 
+    /// Apply a color effect to RGBA data, leaving alpha untouched
+    fn apply_color_effect(mut rgba: Vec<u8>, effect: ColorEffect) -> Vec<u8> {
+        for pixel in rgba.chunks_exact_mut(4) {
+            let [r, g, b] = effect.apply_rgb([pixel[0], pixel[1], pixel[2]]);
+            pixel[..3].copy_from_slice(&[r, g, b]);
+        }
+        rgba
+    }
+
     /// Apply unsharp mask sharpening
     ///
     /// This is a simple 3x3 kernel sharpening filter.
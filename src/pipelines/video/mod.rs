@@ -11,10 +11,14 @@
 pub mod encoder_selection;
 pub mod muxer;
 pub mod recorder;
+pub mod retro;
 
 // Re-export commonly used types
 pub use encoder_selection::EncoderConfig;
-pub use recorder::{VideoRecorder, VideoRecorderConfig, check_available_encoders};
+pub use recorder::{
+    RecordingSplit, RecordingSummary, VideoRecorder, VideoRecorderConfig, active_segment,
+    check_available_encoders, set_audio_muted,
+};
 
 // Re-export encoder types for convenience
 pub use crate::media::encoders::{AudioChannels, AudioQuality, VideoQuality};
@@ -3,9 +3,15 @@
 //! Audio/video muxing logic
 //!
 //! This module handles muxing audio and video streams into a container format.
+//!
+//! Recordings split into segments go through `splitmuxsink` instead of a
+//! muxer and filesink: it wraps the muxer, and only starts a new file at a
+//! video keyframe so every segment plays on its own.
 
 use gstreamer as gst;
 use gstreamer::prelude::*;
+use std::path::PathBuf;
+use std::time::Duration;
 use tracing::{debug, info};
 
 /// Muxer configuration
@@ -67,6 +73,51 @@ pub fn create_muxer(
     })
 }
 
+/// Create a `splitmuxsink` that writes segments through `muxer`
+///
+/// A new segment starts at the first keyframe after `max_duration` or
+/// `max_bytes` is reached. With only a duration limit the encoder is asked
+/// for a keyframe at that point, so segments come out close to the limit.
+/// `location` names segment `n` (1-based) when it is opened.
+pub fn create_split_sink(
+    muxer: gst::Element,
+    max_duration: Option<Duration>,
+    max_bytes: Option<u64>,
+    location: impl Fn(u32) -> PathBuf + Send + Sync + 'static,
+) -> Result<gst::Element, String> {
+    if muxer.has_property("streamable") {
+        muxer.set_property("streamable", false);
+    }
+
+    let max_time_ns = max_duration.map_or(0, |duration| duration.as_nanos() as u64);
+    let max_bytes = max_bytes.unwrap_or(0);
+    let split_sink = gst::ElementFactory::make("splitmuxsink")
+        .property("muxer", &muxer)
+        .property("max-size-time", max_time_ns)
+        .property("max-size-bytes", max_bytes)
+        // Keyframe requests only take effect without a size limit
+        .property("send-keyframe-requests", max_time_ns > 0 && max_bytes == 0)
+        .build()
+        .map_err(|e| format!("Failed to create splitmuxsink: {}", e))?;
+
+    split_sink.connect("format-location", false, move |args| {
+        let fragment = args.get(1).and_then(|v| v.get::<u32>().ok()).unwrap_or(0);
+        let path = location(fragment + 1);
+        info!(segment = fragment + 1, path = %path.display(), "Starting recording segment");
+        Some(path.to_string_lossy().to_string().to_value())
+    });
+
+    info!(?max_duration, max_bytes, "Created segmenting muxer");
+    Ok(split_sink)
+}
+
+/// Whether `element` is a `splitmuxsink`, whose sink pads must be requested by name
+fn is_split_sink(element: &gst::Element) -> bool {
+    element
+        .factory()
+        .is_some_and(|factory| factory.name() == "splitmuxsink")
+}
+
 /// Link video encoder to muxer
 ///
 /// # Arguments
@@ -78,7 +129,7 @@ pub fn create_muxer(
 /// * `Err(String)` - Error message
 pub fn link_video_to_muxer(encoder: &gst::Element, muxer: &gst::Element) -> Result<(), String> {
     encoder
-        .link(muxer)
+        .link_pads(None, muxer, is_split_sink(muxer).then_some("video"))
         .map_err(|_| "Failed to link video encoder to muxer".to_string())?;
 
     debug!("Video encoder linked to muxer");
@@ -96,7 +147,7 @@ pub fn link_video_to_muxer(encoder: &gst::Element, muxer: &gst::Element) -> Resu
 /// * `Err(String)` - Error message
 pub fn link_audio_to_muxer(encoder: &gst::Element, muxer: &gst::Element) -> Result<(), String> {
     encoder
-        .link(muxer)
+        .link_pads(None, muxer, is_split_sink(muxer).then_some("audio_%u"))
         .map_err(|_| "Failed to link audio encoder to muxer".to_string())?;
 
     debug!("Audio encoder linked to muxer");
@@ -7,17 +7,168 @@
 //! - Preview continues during recording (tee-based pipeline)
 //! - Audio integration
 //! - Quality presets
+//!
+//! The microphone is often a different device than the camera, each with its
+//! own clock. Recordings with audio therefore run on the system clock, both
+//! sources timestamp against it, and `audiorate` inserts or drops samples so
+//! the audio track stays continuous when the two clocks drift apart.
+//!
+//! Stopping a recording always yields a [`RecordingSummary`], also when the
+//! pipeline failed along the way and the file is incomplete.
+//!
+//! Long recordings can be split into segments by duration or size (see
+//! [`RecordingSplit`]). Stopping finalizes the segment being written like
+//! any other file.
 
 use super::encoder_selection::{EncoderConfig, select_encoders};
-use super::muxer::{create_muxer, link_audio_to_muxer, link_muxer_to_sink, link_video_to_muxer};
+use super::muxer::{
+    create_muxer, create_split_sink, link_audio_to_muxer, link_muxer_to_sink, link_video_to_muxer,
+};
 use crate::backends::camera::types::{CameraFrame, FrameData, SensorRotation};
+use crate::pipelines::filename::{CaptureKind, OutputTarget};
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+/// Whether recorded audio is muted (applies to the next and the active recording)
+static AUDIO_MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Volume element of the recording in progress, for live muting
+static ACTIVE_AUDIO_VOLUME: Mutex<Option<gst::Element>> = Mutex::new(None);
+
+/// Segment being written by a split recording (0 = not splitting)
+static ACTIVE_SEGMENT: AtomicU32 = AtomicU32::new(0);
+
+/// Segment (1-based) the running recording writes to, if it is split
+pub fn active_segment() -> Option<u32> {
+    match ACTIVE_SEGMENT.load(Ordering::Relaxed) {
+        0 => None,
+        segment => Some(segment),
+    }
+}
+
+/// Mute or unmute recorded audio
+///
+/// Takes effect immediately on a running recording; muted stretches are
+/// recorded as silence so audio and video stay in sync.
+pub fn set_audio_muted(muted: bool) {
+    AUDIO_MUTED.store(muted, Ordering::Relaxed);
+    if let Ok(guard) = ACTIVE_AUDIO_VOLUME.lock()
+        && let Some(volume) = guard.as_ref()
+    {
+        volume.set_property("mute", muted);
+    }
+    info!(muted, "Recording audio mute changed");
+}
+
+/// What ended up in a recording, produced when it is finalized
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingSummary {
+    /// Output file (the first segment of a split recording)
+    pub path: PathBuf,
+    /// Every file of a split recording, in order (empty when not split)
+    pub segments: Vec<PathBuf>,
+    /// Recorded duration
+    pub duration: Duration,
+    /// Size of the output file(s) in bytes (0 if nothing was written)
+    pub file_size: u64,
+    /// Encoded width
+    pub width: u32,
+    /// Encoded height
+    pub height: u32,
+    /// Video encoder element (e.g. "vah264enc")
+    pub encoder: String,
+    /// Frames missing from the camera's stream while recording
+    pub dropped_frames: u64,
+    /// Why the recording failed; the file, if any, is partial
+    pub error: Option<String>,
+}
+
+impl RecordingSummary {
+    /// Average bitrate of the file in kbit/s
+    pub fn average_bitrate_kbps(&self) -> Option<f64> {
+        let secs = self.duration.as_secs_f64();
+        (secs > 0.0 && self.file_size > 0).then(|| self.file_size as f64 * 8.0 / secs / 1000.0)
+    }
+}
+
+/// When a recording starts a new file, and how the files are named
+#[derive(Debug, Clone)]
+pub struct RecordingSplit {
+    /// Longest a segment may run
+    pub max_duration: Option<Duration>,
+    /// Largest a segment may grow, give or take a keyframe interval
+    pub max_bytes: Option<u64>,
+    /// Names the segments, `{seq}` expanding to the segment number
+    pub target: OutputTarget,
+}
+
+impl RecordingSplit {
+    /// Split policy for the configured limits, `None` if neither is set
+    pub fn from_limits(minutes: u32, megabytes: u32, target: OutputTarget) -> Option<Self> {
+        let max_duration = (minutes > 0).then(|| Duration::from_secs(u64::from(minutes) * 60));
+        let max_bytes = (megabytes > 0).then(|| u64::from(megabytes) * 1_000_000);
+        (max_duration.is_some() || max_bytes.is_some()).then_some(Self {
+            max_duration,
+            max_bytes,
+            target,
+        })
+    }
+}
+
+/// Frames missing between two buffers `gap_ns` apart, at `frame_ns` per frame
+///
+/// Gaps are rounded to whole frames, so timestamp jitter doesn't count as drops.
+fn missed_frames(gap_ns: u64, frame_ns: u64) -> u64 {
+    if frame_ns == 0 {
+        return 0;
+    }
+    ((gap_ns + frame_ns / 2) / frame_ns).saturating_sub(1)
+}
+
+/// Counts frames missing from the camera's stream, from gaps in buffer timestamps
+#[derive(Debug)]
+struct DropCounter {
+    frame_ns: u64,
+    /// Timestamp of the previous buffer (`u64::MAX` before the first)
+    last_pts_ns: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl DropCounter {
+    fn new(framerate: u32) -> Self {
+        Self {
+            frame_ns: 1_000_000_000 / u64::from(framerate.max(1)),
+            last_pts_ns: AtomicU64::new(u64::MAX),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// A counter that never counts drops (for variable frame rate sources)
+    fn disabled() -> Self {
+        Self {
+            frame_ns: 0,
+            last_pts_ns: AtomicU64::new(u64::MAX),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    fn on_buffer(&self, pts_ns: u64) {
+        let last = self.last_pts_ns.swap(pts_ns, Ordering::Relaxed);
+        if last != u64::MAX && pts_ns > last {
+            self.dropped.fetch_add(
+                missed_frames(pts_ns - last, self.frame_ns),
+                Ordering::Relaxed,
+            );
+        }
+    }
+}
+
 /// Configuration for creating a video recorder
 pub struct VideoRecorderConfig<'a> {
     /// Camera device path
@@ -46,6 +197,14 @@ pub struct VideoRecorderConfig<'a> {
     pub encoder_info: Option<&'a crate::media::encoders::video::EncoderInfo>,
     /// Sensor rotation to correct video orientation
     pub rotation: SensorRotation,
+    /// Watermark burned into the recording (not into the preview)
+    pub watermark: Option<crate::config::WatermarkSettings>,
+    /// Split the recording into segments (`output_path` is then unused)
+    pub split: Option<RecordingSplit>,
+    /// Drop frames evenly down to this rate (None = the camera's rate)
+    pub max_framerate: Option<u32>,
+    /// The source delivers a variable frame rate, so timestamp gaps aren't drops
+    pub variable_framerate: bool,
 }
 
 /// Video recorder using the new pipeline architecture
@@ -53,6 +212,13 @@ pub struct VideoRecorderConfig<'a> {
 pub struct VideoRecorder {
     pipeline: gst::Pipeline,
     file_path: PathBuf,
+    created_at: Instant,
+    width: u32,
+    height: u32,
+    encoder_name: String,
+    drops: Arc<DropCounter>,
+    /// Files opened so far by a split recording
+    segments: Arc<Mutex<Vec<PathBuf>>>,
     #[allow(dead_code)]
     _preview_task: Option<tokio::task::JoinHandle<()>>,
 }
@@ -81,6 +247,10 @@ impl VideoRecorder {
             preview_sender,
             encoder_info,
             rotation,
+            watermark,
+            split,
+            max_framerate,
+            variable_framerate,
         } = config;
 
         info!(
@@ -171,6 +341,26 @@ impl VideoRecorder {
             .build()
             .map_err(|e| format!("Failed to create videoscale: {}", e))?;
 
+        // Framerate cap: videorate picks the frame closest to each output slot
+        let output_framerate = max_framerate
+            .filter(|&cap| cap > 0 && cap < framerate)
+            .unwrap_or(framerate);
+        let videorate = if output_framerate < framerate {
+            info!(
+                source = framerate,
+                output = output_framerate,
+                "Adding videorate to cap the recording framerate"
+            );
+            Some(
+                gst::ElementFactory::make("videorate")
+                    .property("drop-only", true)
+                    .build()
+                    .map_err(|e| format!("Failed to create videorate: {}", e))?,
+            )
+        } else {
+            None
+        };
+
         // Account for dimension swap when rotation is 90° or 270°
         let (base_width, base_height) = if rotation.swaps_dimensions() {
             (height, width) // Swap dimensions for rotated video
@@ -218,7 +408,7 @@ impl VideoRecorder {
         let output_caps = gst::Caps::builder("video/x-raw")
             .field("width", final_width as i32)
             .field("height", final_height as i32)
-            .field("framerate", gst::Fraction::new(framerate as i32, 1))
+            .field("framerate", gst::Fraction::new(output_framerate as i32, 1))
             .build();
 
         let capsfilter = gst::ElementFactory::make("capsfilter")
@@ -236,15 +426,70 @@ impl VideoRecorder {
             .build()
             .map_err(|e| format!("Failed to create record queue: {}", e))?;
 
+        // Watermark in the recording branch only, so the preview stays clean
+        let watermark_overlay = match &watermark {
+            Some(settings) => {
+                crate::pipelines::watermark::recording_overlay(settings, final_height)?
+            }
+            None => None,
+        };
+
         // Preview branch (if enabled)
         let preview_elements = Self::create_preview_branch(preview_sender.as_ref())?;
 
         // Get encoder elements
         let video_encoder = encoders.video.encoder;
         let video_parser = encoders.video.parser;
+        let encoder_name = video_encoder
+            .factory()
+            .map(|f| f.name().to_string())
+            .unwrap_or_default();
+
+        // Count frames the camera skipped, from timestamp gaps after rate/size negotiation
+        let drops = Arc::new(if variable_framerate {
+            DropCounter::disabled()
+        } else {
+            DropCounter::new(output_framerate)
+        });
+        if let Some(pad) = capsfilter.static_pad("src") {
+            let drops = Arc::clone(&drops);
+            pad.add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+                if let Some(pts) = info.buffer().and_then(|buffer| buffer.pts()) {
+                    drops.on_buffer(pts.nseconds());
+                }
+                gst::PadProbeReturn::Ok
+            });
+        }
 
-        // Create muxer
-        let muxer_config = create_muxer(encoders.video.muxer, output_path.clone())?;
+        // Create muxer, wrapped in a segmenting sink when splitting
+        let segments = Arc::new(Mutex::new(Vec::new()));
+        let (muxer, filesink) = match split {
+            Some(split) => {
+                let extension = encoders.video.extension;
+                let opened = Arc::clone(&segments);
+                let location = move |segment| {
+                    let path = split
+                        .target
+                        .segment_path(CaptureKind::Video, extension, segment);
+                    if let Ok(mut guard) = opened.lock() {
+                        guard.push(path.clone());
+                    }
+                    ACTIVE_SEGMENT.store(segment, Ordering::Relaxed);
+                    path
+                };
+                let sink = create_split_sink(
+                    encoders.video.muxer,
+                    split.max_duration,
+                    split.max_bytes,
+                    location,
+                )?;
+                (sink, None)
+            }
+            None => {
+                let muxer_config = create_muxer(encoders.video.muxer, output_path.clone())?;
+                (muxer_config.muxer, Some(muxer_config.filesink))
+            }
+        };
 
         // Audio branch (if enabled)
         let audio_elements = if let Some(audio_encoder_config) = encoders.audio {
@@ -266,20 +511,26 @@ impl VideoRecorder {
             elements.push(flip);
         }
 
-        elements.extend_from_slice(&[
-            &videoscale,
-            &capsfilter,
-            &tee,
-            &record_queue,
-            &video_encoder,
-        ]);
+        elements.push(&videoscale);
+
+        if let Some(ref rate) = videorate {
+            elements.push(rate);
+        }
+
+        elements.extend_from_slice(&[&capsfilter, &tee, &record_queue, &video_encoder]);
+
+        if let Some(ref overlay) = watermark_overlay {
+            elements.push(overlay);
+        }
 
         if let Some(ref parser) = video_parser {
             elements.push(parser);
         }
 
-        elements.push(&muxer_config.muxer);
-        elements.push(&muxer_config.filesink);
+        elements.push(&muxer);
+        if let Some(ref filesink) = filesink {
+            elements.push(filesink);
+        }
 
         if let Some((ref preview_queue, ref appsink)) = preview_elements {
             elements.push(preview_queue);
@@ -293,6 +544,7 @@ impl VideoRecorder {
             elements.push(&audio_branch.limiter);
             elements.push(&audio_branch.convert);
             elements.push(&audio_branch.resample);
+            elements.push(&audio_branch.rate);
             elements.push(&audio_branch.encoder);
         }
 
@@ -307,6 +559,7 @@ impl VideoRecorder {
             &videoconvert,
             videoflip.as_ref(),
             &videoscale,
+            videorate.as_ref(),
             &capsfilter,
             &tee,
         )?;
@@ -315,13 +568,16 @@ impl VideoRecorder {
         Self::link_recording_branch(
             &tee,
             &record_queue,
+            watermark_overlay.as_ref(),
             &video_encoder,
             video_parser.as_ref(),
-            &muxer_config.muxer,
+            &muxer,
         )?;
 
-        // Link muxer to filesink
-        link_muxer_to_sink(&muxer_config.muxer, &muxer_config.filesink)?;
+        // Link muxer to filesink (the segmenting sink writes its own files)
+        if let Some(ref filesink) = filesink {
+            link_muxer_to_sink(&muxer, filesink)?;
+        }
 
         // Link preview branch if enabled
         let preview_task = Self::link_preview_branch(&tee, preview_elements, preview_sender)?;
@@ -329,12 +585,25 @@ impl VideoRecorder {
         // Link audio branch if enabled
         if let Some(audio_branch) = audio_elements {
             Self::link_audio_chain(&audio_branch)?;
-            link_audio_to_muxer(&audio_branch.encoder, &muxer_config.muxer)?;
+            link_audio_to_muxer(&audio_branch.encoder, &muxer)?;
+
+            // Don't let either device's clock drive the pipeline
+            pipeline.use_clock(Some(&gst::SystemClock::obtain()));
+
+            if let Ok(mut guard) = ACTIVE_AUDIO_VOLUME.lock() {
+                *guard = Some(audio_branch.volume.clone());
+            }
         }
 
         Ok(VideoRecorder {
             pipeline,
             file_path: output_path,
+            created_at: Instant::now(),
+            width: final_width,
+            height: final_height,
+            encoder_name,
+            drops,
+            segments,
             _preview_task: preview_task,
         })
     }
@@ -454,6 +723,7 @@ impl VideoRecorder {
         // Users should adjust input volume in COSMIC Sound Settings if needed
         // (Enable over-amplification there for up to 150% if mic is too quiet)
         volume.set_property("volume", 1.0f64);
+        volume.set_property("mute", AUDIO_MUTED.load(Ordering::Relaxed));
         debug!("Configured audio volume: 1.0x (unity gain, adjust in Sound Settings if needed)");
 
         // Add audio limiter to prevent clipping and overly loud audio
@@ -473,6 +743,12 @@ impl VideoRecorder {
             .build()
             .map_err(|e| format!("Failed to create audioresample: {}", e))?;
 
+        // Keep the audio track continuous when the mic clock drifts from the camera
+        let rate = gst::ElementFactory::make("audiorate")
+            .property("skip-to-first", true)
+            .build()
+            .map_err(|e| format!("Failed to create audiorate: {}", e))?;
+
         let encoder = audio_encoder_config.encoder;
 
         Ok(Some(AudioBranch {
@@ -482,6 +758,7 @@ impl VideoRecorder {
             limiter,
             convert,
             resample,
+            rate,
             encoder,
         }))
     }
@@ -493,6 +770,7 @@ impl VideoRecorder {
         videoconvert: &gst::Element,
         videoflip: Option<&gst::Element>,
         videoscale: &gst::Element,
+        videorate: Option<&gst::Element>,
         capsfilter: &gst::Element,
         tee: &gst::Element,
     ) -> Result<(), String> {
@@ -522,9 +800,17 @@ impl VideoRecorder {
                 .map_err(|_| "Failed to link videoconvert to videoscale")?;
         }
 
-        videoscale
-            .link(capsfilter)
-            .map_err(|_| "Failed to link videoscale to capsfilter")?;
+        if let Some(rate) = videorate {
+            videoscale
+                .link(rate)
+                .map_err(|_| "Failed to link videoscale to videorate")?;
+            rate.link(capsfilter)
+                .map_err(|_| "Failed to link videorate to capsfilter")?;
+        } else {
+            videoscale
+                .link(capsfilter)
+                .map_err(|_| "Failed to link videoscale to capsfilter")?;
+        }
         capsfilter
             .link(tee)
             .map_err(|_| "Failed to link capsfilter to tee")?;
@@ -536,15 +822,25 @@ impl VideoRecorder {
     fn link_recording_branch(
         tee: &gst::Element,
         record_queue: &gst::Element,
+        watermark: Option<&gst::Element>,
         encoder: &gst::Element,
         parser: Option<&gst::Element>,
         muxer: &gst::Element,
     ) -> Result<(), String> {
         tee.link(record_queue)
             .map_err(|_| "Failed to link tee to record_queue")?;
-        record_queue
-            .link(encoder)
-            .map_err(|_| "Failed to link record_queue to encoder")?;
+        if let Some(overlay) = watermark {
+            record_queue
+                .link(overlay)
+                .map_err(|_| "Failed to link record_queue to watermark")?;
+            overlay
+                .link(encoder)
+                .map_err(|_| "Failed to link watermark to encoder")?;
+        } else {
+            record_queue
+                .link(encoder)
+                .map_err(|_| "Failed to link record_queue to encoder")?;
+        }
 
         if let Some(parser) = parser {
             encoder
@@ -609,6 +905,7 @@ impl VideoRecorder {
                                     stride,
                                     yuv_planes: None,
                                     captured_at: std::time::Instant::now(),
+                                    stamp: None,
                                 };
 
                                 let _ = preview_sender.send(frame).await;
@@ -647,8 +944,12 @@ impl VideoRecorder {
             .map_err(|_| "Failed to link audioconvert to audioresample")?;
         audio_branch
             .resample
+            .link(&audio_branch.rate)
+            .map_err(|_| "Failed to link audioresample to audiorate")?;
+        audio_branch
+            .rate
             .link(&audio_branch.encoder)
-            .map_err(|_| "Failed to link audioresample to encoder")?;
+            .map_err(|_| "Failed to link audiorate to encoder")?;
 
         Ok(())
     }
@@ -691,28 +992,121 @@ impl VideoRecorder {
         Ok(())
     }
 
-    /// Stop recording and finalize the file
-    pub fn stop(self) -> Result<PathBuf, String> {
+    /// First error the pipeline posted since the last check, if any
+    fn pending_error(&self) -> Option<String> {
+        let message = self
+            .pipeline
+            .bus()?
+            .pop_filtered(&[gst::MessageType::Error])?;
+        match message.view() {
+            gst::MessageView::Error(err) => {
+                error!(
+                    error = %err.error(),
+                    debug = ?err.debug(),
+                    source = ?err.src().map(|s| s.name()),
+                    "GStreamer error during recording"
+                );
+                Some(err.error().to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Stop recording, finalize the file and summarize what was recorded
+    ///
+    /// Errors don't prevent the summary: the file written so far is finalized
+    /// as far as possible and the error is reported in it.
+    pub fn stop(self) -> RecordingSummary {
         info!("Stopping video recording");
 
+        // Errors posted while recording, e.g. a failed encoder or a full disk
+        let mut error = self.pending_error();
+        let duration = self
+            .pipeline
+            .query_position::<gst::ClockTime>()
+            .map(|position| Duration::from_nanos(position.nseconds()))
+            .unwrap_or_else(|| self.created_at.elapsed());
+
         // Send EOS to trigger graceful shutdown
         info!("Sending EOS to pipeline");
         if !self.pipeline.send_event(gst::event::Eos::new()) {
             warn!("Failed to send EOS event to pipeline");
         }
 
-        // Give pipeline a brief moment to start processing EOS
-        // This is crucial for WebM muxer to write duration metadata
-        std::thread::sleep(std::time::Duration::from_millis(500));
+        // Wait for EOS to reach the sink: the muxer has then written its
+        // trailer (e.g. the WebM duration). Bounded so a stuck element can't
+        // hold up the stop.
+        let timeout =
+            gst::ClockTime::from_seconds(crate::constants::timing::RECORDING_EOS_TIMEOUT_SECS);
+        match self.pipeline.bus().and_then(|bus| {
+            bus.timed_pop_filtered(timeout, &[gst::MessageType::Eos, gst::MessageType::Error])
+        }) {
+            Some(message) => match message.view() {
+                gst::MessageView::Error(err) => {
+                    error.get_or_insert(err.error().to_string());
+                }
+                _ => debug!("EOS reached the file, recording finalized"),
+            },
+            None => {
+                warn!(
+                    ?timeout,
+                    "EOS didn't reach the file in time, stopping anyway"
+                );
+                error.get_or_insert_with(|| {
+                    "The recording wasn't finalized in time and may be incomplete".to_string()
+                });
+            }
+        }
 
         // Set pipeline to NULL state - this will trigger final cleanup
         info!("Setting pipeline to NULL state");
-        self.pipeline
-            .set_state(gst::State::Null)
-            .map_err(|e| format!("Failed to stop pipeline: {}", e))?;
+        if let Err(e) = self.pipeline.set_state(gst::State::Null) {
+            error.get_or_insert(format!("Failed to stop pipeline: {}", e));
+        }
 
-        info!(path = %self.file_path.display(), "Recording saved");
-        Ok(self.file_path.clone())
+        let segments = self
+            .segments
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default();
+        let path = segments
+            .first()
+            .cloned()
+            .unwrap_or_else(|| self.file_path.clone());
+        let file_size = if segments.is_empty() {
+            std::slice::from_ref(&self.file_path)
+        } else {
+            segments.as_slice()
+        }
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum::<u64>();
+        if file_size == 0 {
+            error.get_or_insert_with(|| "No data was written to the file".to_string());
+        }
+
+        let summary = RecordingSummary {
+            path,
+            segments,
+            duration,
+            file_size,
+            width: self.width,
+            height: self.height,
+            encoder: self.encoder_name.clone(),
+            dropped_frames: self.drops.dropped.load(Ordering::Relaxed),
+            error,
+        };
+        match &summary.error {
+            None => info!(
+                path = %summary.path.display(),
+                segments = summary.segments.len(),
+                ?summary,
+                "Recording saved"
+            ),
+            Some(error) => warn!(path = %summary.path.display(), %error, "Recording incomplete"),
+        }
+        summary
     }
 }
 
@@ -720,6 +1114,16 @@ impl Drop for VideoRecorder {
     fn drop(&mut self) {
         // Ensure pipeline is properly stopped to avoid GStreamer warnings
         let _ = self.pipeline.set_state(gst::State::Null);
+        ACTIVE_SEGMENT.store(0, Ordering::Relaxed);
+
+        // Forget our volume element so muting doesn't touch a dead pipeline
+        if let Ok(mut guard) = ACTIVE_AUDIO_VOLUME.lock()
+            && guard
+                .as_ref()
+                .is_some_and(|volume| volume.has_as_ancestor(&self.pipeline))
+        {
+            *guard = None;
+        }
     }
 }
 
@@ -731,6 +1135,7 @@ struct AudioBranch {
     limiter: gst::Element,
     convert: gst::Element,
     resample: gst::Element,
+    rate: gst::Element,
     encoder: gst::Element,
 }
 
@@ -738,3 +1143,60 @@ struct AudioBranch {
 pub fn check_available_encoders() {
     crate::media::encoders::log_available_encoders();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missed_frames_from_timestamp_gaps() {
+        let frame_ns = 1_000_000_000 / 30;
+        assert_eq!(missed_frames(frame_ns, frame_ns), 0);
+        // Jitter within half a frame is not a drop
+        assert_eq!(missed_frames(frame_ns * 14 / 10, frame_ns), 0);
+        assert_eq!(missed_frames(frame_ns * 2, frame_ns), 1);
+        assert_eq!(missed_frames(frame_ns * 5, frame_ns), 4);
+    }
+
+    #[test]
+    fn test_drop_counter_accumulates() {
+        let counter = DropCounter::new(30);
+        for frame in [0, 1, 2, 5, 6, 9] {
+            counter.on_buffer(frame * counter.frame_ns);
+        }
+        assert_eq!(counter.dropped.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_average_bitrate() {
+        let summary = RecordingSummary {
+            path: PathBuf::from("/tmp/VID.mp4"),
+            segments: Vec::new(),
+            duration: Duration::from_secs(10),
+            file_size: 5_000_000,
+            width: 1920,
+            height: 1080,
+            encoder: "x264enc".to_string(),
+            dropped_frames: 0,
+            error: None,
+        };
+        assert_eq!(summary.average_bitrate_kbps(), Some(4000.0));
+        let empty = RecordingSummary {
+            file_size: 0,
+            ..summary
+        };
+        assert_eq!(empty.average_bitrate_kbps(), None);
+    }
+
+    #[test]
+    fn test_split_limits() {
+        let target = OutputTarget::new("/tmp");
+        assert!(RecordingSplit::from_limits(0, 0, target.clone()).is_none());
+        let split = RecordingSplit::from_limits(10, 0, target.clone()).unwrap();
+        assert_eq!(split.max_duration, Some(Duration::from_secs(600)));
+        assert_eq!(split.max_bytes, None);
+        let split = RecordingSplit::from_limits(0, 4000, target).unwrap();
+        assert_eq!(split.max_duration, None);
+        assert_eq!(split.max_bytes, Some(4_000_000_000));
+    }
+}
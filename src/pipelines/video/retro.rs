@@ -0,0 +1,511 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Retroactive recording ("save the last N seconds")
+//!
+//! A buffer probe on the camera source pad hands a reference to every buffer
+//! to a worker thread, which copies the bytes out, lets go of the buffer and
+//! keeps the copies in a ring trimmed by both duration and a memory cap. The
+//! probe itself never copies, so preview latency is unaffected, and at most
+//! `WORKER_QUEUE_DEPTH` source buffers are held at a time, so the camera's
+//! buffer pool isn't drained. If the worker falls behind, buffers are
+//! skipped, and for compressed sources every delta frame after that too
+//! until the next keyframe, so a saved clip never has a broken GOP.
+//!
+//! Compressed sources (MJPEG, H.264, H.265) are stored as the encoded
+//! buffers the camera sent and remuxed into Matroska on save without
+//! re-encoding. Raw sources are stored as-is and encoded on save with the
+//! regular encoder selection.
+
+use super::encoder_selection::{EncoderConfig, select_encoders};
+use super::muxer::{create_muxer, link_muxer_to_sink, link_video_to_muxer};
+use crate::pipelines::filename::{CaptureKind, OutputTarget};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// Frames in flight between the source probe and the ring worker
+const WORKER_QUEUE_DEPTH: usize = 4;
+
+/// Maximum time to wait for a saved clip to finish writing
+const SAVE_TIMEOUT_SECS: u64 = 30;
+
+/// A single buffered frame (encoded or raw, depending on the source caps)
+#[derive(Debug, Clone)]
+pub struct RetroFrame {
+    /// Buffer contents
+    pub data: Arc<[u8]>,
+    /// Presentation timestamp from the source
+    pub pts: Duration,
+    /// Whether the frame can be decoded on its own (always true for raw/MJPEG)
+    pub keyframe: bool,
+}
+
+/// Current fill level of the retro buffer (for insights)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetroFill {
+    /// Number of frames held
+    pub frames: usize,
+    /// Bytes held
+    pub bytes: usize,
+    /// Time span covered by the held frames
+    pub duration: Duration,
+    /// Configured duration limit
+    pub max_duration: Duration,
+    /// Configured memory limit in bytes
+    pub max_bytes: usize,
+}
+
+/// Rolling frame ring bounded by duration and memory
+#[derive(Debug, Default)]
+pub struct FrameRing {
+    frames: VecDeque<RetroFrame>,
+    bytes: usize,
+    max_duration: Duration,
+    max_bytes: usize,
+}
+
+impl FrameRing {
+    /// Create a ring holding at most `max_duration` of frames in `max_bytes`
+    pub fn new(max_duration: Duration, max_bytes: usize) -> Self {
+        Self {
+            frames: VecDeque::new(),
+            bytes: 0,
+            max_duration,
+            max_bytes,
+        }
+    }
+
+    /// Change the limits, trimming immediately if they shrank
+    pub fn set_limits(&mut self, max_duration: Duration, max_bytes: usize) {
+        self.max_duration = max_duration;
+        self.max_bytes = max_bytes;
+        self.trim();
+    }
+
+    /// Append a frame and evict the oldest ones that fall outside the limits
+    pub fn push(&mut self, frame: RetroFrame) {
+        // Timestamps going backwards means the source restarted
+        if self.frames.back().is_some_and(|last| frame.pts < last.pts) {
+            self.clear();
+        }
+        self.bytes += frame.data.len();
+        self.frames.push_back(frame);
+        self.trim();
+    }
+
+    /// Drop all frames
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.bytes = 0;
+    }
+
+    /// Time span between the oldest and newest frame
+    pub fn duration(&self) -> Duration {
+        match (self.frames.front(), self.frames.back()) {
+            (Some(first), Some(last)) => last.pts.saturating_sub(first.pts),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Current fill level
+    pub fn fill(&self) -> RetroFill {
+        RetroFill {
+            frames: self.frames.len(),
+            bytes: self.bytes,
+            duration: self.duration(),
+            max_duration: self.max_duration,
+            max_bytes: self.max_bytes,
+        }
+    }
+
+    /// Copy out the frames, starting at the first keyframe
+    pub fn snapshot(&self) -> Vec<RetroFrame> {
+        self.frames
+            .iter()
+            .skip_while(|frame| !frame.keyframe)
+            .cloned()
+            .collect()
+    }
+
+    fn trim(&mut self) {
+        // Always keep the newest frame, even if it alone exceeds the memory cap
+        while self.frames.len() > 1
+            && (self.bytes > self.max_bytes || self.duration() > self.max_duration)
+        {
+            if let Some(evicted) = self.frames.pop_front() {
+                self.bytes -= evicted.data.len();
+            }
+        }
+    }
+}
+
+struct RetroState {
+    ring: FrameRing,
+    caps: Option<gst::Caps>,
+}
+
+struct RetroBuffer {
+    enabled: AtomicBool,
+    state: Mutex<RetroState>,
+    sender: SyncSender<(gst::Buffer, gst::Caps)>,
+    /// Buffers sent to the worker and not yet copied into the ring
+    in_flight: AtomicUsize,
+    /// A buffer was skipped; skip delta frames until the next keyframe
+    resync: AtomicBool,
+}
+
+static RETRO: LazyLock<RetroBuffer> = LazyLock::new(|| {
+    let (sender, receiver) = sync_channel(WORKER_QUEUE_DEPTH);
+    if let Err(e) = std::thread::Builder::new()
+        .name("retro-buffer".to_string())
+        .spawn(move || run_worker(receiver))
+    {
+        error!(error = %e, "Failed to spawn retro buffer worker");
+    }
+    RetroBuffer {
+        enabled: AtomicBool::new(false),
+        state: Mutex::new(RetroState {
+            ring: FrameRing::default(),
+            caps: None,
+        }),
+        sender,
+        in_flight: AtomicUsize::new(0),
+        resync: AtomicBool::new(false),
+    }
+});
+
+fn lock_state() -> std::sync::MutexGuard<'static, RetroState> {
+    RETRO.state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Copy a source buffer out so the source gets it back right away
+///
+/// Runs on the worker thread, never in the probe.
+fn copy_frame(buffer: &gst::BufferRef) -> Option<RetroFrame> {
+    let pts = buffer.pts()?;
+    let map = buffer.map_readable().ok()?;
+    Some(RetroFrame {
+        data: Arc::from(map.as_slice()),
+        pts: Duration::from_nanos(pts.nseconds()),
+        keyframe: !buffer.flags().contains(gst::BufferFlags::DELTA_UNIT),
+    })
+}
+
+/// Whether a source buffer goes to the worker
+///
+/// Once a buffer is skipped because the worker is behind, delta frames are
+/// skipped too until the next keyframe: a clip missing one would be corrupt
+/// until then. Raw and MJPEG buffers are all keyframes.
+fn admit(resync: &AtomicBool, worker_behind: bool, keyframe: bool) -> bool {
+    if worker_behind {
+        resync.store(true, Ordering::Relaxed);
+        return false;
+    }
+    if resync.load(Ordering::Relaxed) {
+        if !keyframe {
+            return false;
+        }
+        resync.store(false, Ordering::Relaxed);
+    }
+    true
+}
+
+fn run_worker(receiver: Receiver<(gst::Buffer, gst::Caps)>) {
+    for (buffer, caps) in receiver {
+        let frame = copy_frame(&buffer);
+        drop(buffer);
+        RETRO.in_flight.fetch_sub(1, Ordering::Relaxed);
+        let Some(frame) = frame else {
+            continue;
+        };
+        let mut state = lock_state();
+        if state.caps.as_ref() != Some(&caps) {
+            debug!(caps = %caps, "Retro buffer source caps changed, clearing");
+            state.ring.clear();
+            state.caps = Some(caps);
+        }
+        state.ring.push(frame);
+    }
+}
+
+/// Enable, resize, or disable (with `seconds == 0`) the retro buffer
+pub fn configure(seconds: u32, memory_cap_mb: u32) {
+    let enabled = seconds > 0;
+    let previous = RETRO.enabled.swap(enabled, Ordering::Relaxed);
+    let mut state = lock_state();
+    state.ring.set_limits(
+        Duration::from_secs(seconds as u64),
+        memory_cap_mb as usize * 1024 * 1024,
+    );
+    if !enabled {
+        state.ring.clear();
+        state.caps = None;
+    }
+    if previous != enabled {
+        info!(seconds, memory_cap_mb, enabled, "Retro buffer configured");
+    }
+}
+
+/// Whether the retro buffer is collecting frames
+pub fn is_enabled() -> bool {
+    RETRO.enabled.load(Ordering::Relaxed)
+}
+
+/// Current fill level of the retro buffer
+pub fn fill() -> RetroFill {
+    lock_state().ring.fill()
+}
+
+/// Attach the retro buffer probe to a camera pipeline's source element
+///
+/// The probe is always installed and checks the enabled flag per buffer, so
+/// toggling the feature doesn't require rebuilding the pipeline.
+pub fn attach(pipeline: &gst::Pipeline) {
+    let source = pipeline.iterate_sources().into_iter().flatten().find(|e| {
+        e.factory()
+            .is_some_and(|f| f.name() == "pipewiresrc" || f.name() == "v4l2src")
+    });
+    let Some(src_pad) = source.and_then(|e| e.static_pad("src")) else {
+        warn!("No camera source pad found, retro buffer unavailable for this pipeline");
+        return;
+    };
+
+    // New pipeline: frames from the previous one can't be stitched onto these
+    {
+        let mut state = lock_state();
+        state.ring.clear();
+        state.caps = None;
+    }
+    RETRO.resync.store(false, Ordering::Relaxed);
+
+    let sender = RETRO.sender.clone();
+    src_pad.add_probe(gst::PadProbeType::BUFFER, move |pad, info| {
+        if !RETRO.enabled.load(Ordering::Relaxed) {
+            return gst::PadProbeReturn::Ok;
+        }
+        let Some(buffer) = info.buffer() else {
+            return gst::PadProbeReturn::Ok;
+        };
+        let keyframe = !buffer.flags().contains(gst::BufferFlags::DELTA_UNIT);
+        let behind = RETRO.in_flight.load(Ordering::Relaxed) >= WORKER_QUEUE_DEPTH;
+        if !admit(&RETRO.resync, behind, keyframe) {
+            return gst::PadProbeReturn::Ok;
+        }
+        if let Some(caps) = pad.current_caps() {
+            RETRO.in_flight.fetch_add(1, Ordering::Relaxed);
+            // A new reference, not a copy; the worker copies the bytes
+            if let Err(err) = sender.try_send((buffer.to_owned(), caps)) {
+                RETRO.in_flight.fetch_sub(1, Ordering::Relaxed);
+                RETRO.resync.store(true, Ordering::Relaxed);
+                if let TrySendError::Disconnected(_) = err {
+                    warn!("Retro buffer worker stopped");
+                }
+            }
+        }
+        gst::PadProbeReturn::Ok
+    });
+    debug!("Retro buffer probe attached to camera source");
+}
+
+/// Write the buffered frames to a new file named and placed by `target`
+///
+/// Blocks until the file is finalized. Returns the path of the saved clip.
+pub fn save_clip(target: &OutputTarget, encoder_config: EncoderConfig) -> Result<PathBuf, String> {
+    let (caps, frames) = {
+        let state = lock_state();
+        let caps = state
+            .caps
+            .clone()
+            .ok_or_else(|| "Retro buffer is empty".to_string())?;
+        (caps, state.ring.snapshot())
+    };
+    let (Some(first), Some(last)) = (frames.first(), frames.last()) else {
+        return Err("Retro buffer is empty".to_string());
+    };
+    let base_pts = first.pts;
+    let clip_duration = last.pts.saturating_sub(first.pts);
+
+    gst::init().map_err(|e| format!("Failed to initialize GStreamer: {}", e))?;
+
+    let structure_name = caps
+        .structure(0)
+        .map(|s| s.name().to_string())
+        .unwrap_or_default();
+    let pipeline = gst::Pipeline::new();
+    let appsrc = gst_app::AppSrc::builder()
+        .caps(&caps)
+        .format(gst::Format::Time)
+        .is_live(false)
+        .build();
+
+    let make = |name: &str| {
+        gst::ElementFactory::make(name)
+            .build()
+            .map_err(|e| format!("Failed to create {}: {}", name, e))
+    };
+
+    // Encoded sources are remuxed as-is; raw sources go through an encoder
+    let (chain, muxer, output_path) = match structure_name.as_str() {
+        "image/jpeg" | "video/x-h264" | "video/x-h265" => {
+            let parser = match structure_name.as_str() {
+                "image/jpeg" => make("jpegparse")?,
+                "video/x-h264" => make("h264parse")?,
+                _ => make("h265parse")?,
+            };
+            let output_path = target.path(CaptureKind::Retro, "mkv");
+            (vec![parser], make("matroskamux")?, output_path)
+        }
+        "video/x-raw" => {
+            let encoders = select_encoders(&encoder_config, false)?;
+            let mut chain = vec![make("videoconvert")?, encoders.video.encoder];
+            chain.extend(encoders.video.parser);
+            let output_path = target.path(CaptureKind::Retro, encoders.video.extension);
+            (chain, encoders.video.muxer, output_path)
+        }
+        other => return Err(format!("Unsupported retro buffer format: {}", other)),
+    };
+    let muxer_config = create_muxer(muxer, output_path.clone())?;
+
+    let mut elements: Vec<&gst::Element> = vec![appsrc.upcast_ref()];
+    elements.extend(chain.iter());
+    elements.push(&muxer_config.muxer);
+    elements.push(&muxer_config.filesink);
+    pipeline
+        .add_many(&elements)
+        .map_err(|e| format!("Failed to add elements to pipeline: {}", e))?;
+    gst::Element::link_many(&elements[..elements.len() - 2])
+        .map_err(|e| format!("Failed to link retro clip chain: {}", e))?;
+    let last_in_chain = elements[elements.len() - 3];
+    link_video_to_muxer(last_in_chain, &muxer_config.muxer)?;
+    link_muxer_to_sink(&muxer_config.muxer, &muxer_config.filesink)?;
+
+    pipeline
+        .set_state(gst::State::Playing)
+        .map_err(|e| format!("Failed to start retro clip pipeline: {}", e))?;
+
+    info!(
+        frames = frames.len(),
+        duration_ms = clip_duration.as_millis() as u64,
+        format = %structure_name,
+        output = %output_path.display(),
+        "Saving retro clip"
+    );
+
+    for frame in &frames {
+        let mut buffer = gst::Buffer::from_slice(frame.data.clone());
+        if let Some(buffer) = buffer.get_mut() {
+            let pts = frame.pts.saturating_sub(base_pts);
+            buffer.set_pts(gst::ClockTime::from_nseconds(pts.as_nanos() as u64));
+            if !frame.keyframe {
+                buffer.set_flags(gst::BufferFlags::DELTA_UNIT);
+            }
+        }
+        if let Err(e) = appsrc.push_buffer(buffer) {
+            let _ = pipeline.set_state(gst::State::Null);
+            return Err(format!("Failed to push retro frame: {:?}", e));
+        }
+    }
+    let _ = appsrc.end_of_stream();
+
+    let bus = pipeline
+        .bus()
+        .ok_or_else(|| "Retro clip pipeline has no bus".to_string())?;
+    let result = match bus.timed_pop_filtered(
+        gst::ClockTime::from_seconds(SAVE_TIMEOUT_SECS),
+        &[gst::MessageType::Eos, gst::MessageType::Error],
+    ) {
+        Some(msg) => match msg.view() {
+            gst::MessageView::Error(err) => Err(format!("Retro clip error: {}", err.error())),
+            _ => Ok(output_path),
+        },
+        None => Err("Timed out writing retro clip".to_string()),
+    };
+    let _ = pipeline.set_state(gst::State::Null);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(pts_ms: u64, size: usize, keyframe: bool) -> RetroFrame {
+        RetroFrame {
+            data: Arc::from(vec![0u8; size]),
+            pts: Duration::from_millis(pts_ms),
+            keyframe,
+        }
+    }
+
+    #[test]
+    fn test_ring_trims_by_duration() {
+        let mut ring = FrameRing::new(Duration::from_secs(1), usize::MAX);
+        for i in 0..60 {
+            ring.push(frame(i * 33, 10, true));
+        }
+        let fill = ring.fill();
+        assert!(fill.duration <= Duration::from_secs(1));
+        assert_eq!(fill.frames, 31);
+        assert_eq!(fill.bytes, 310);
+    }
+
+    #[test]
+    fn test_ring_trims_by_memory() {
+        let mut ring = FrameRing::new(Duration::from_secs(60), 1000);
+        for i in 0..20 {
+            ring.push(frame(i * 33, 100, true));
+        }
+        assert_eq!(ring.fill().frames, 10);
+        assert_eq!(ring.fill().bytes, 1000);
+
+        // A single oversized frame is still kept
+        ring.push(frame(1000, 5000, true));
+        assert_eq!(ring.fill().frames, 1);
+    }
+
+    #[test]
+    fn test_ring_clears_when_timestamps_restart() {
+        let mut ring = FrameRing::new(Duration::from_secs(10), usize::MAX);
+        ring.push(frame(500, 10, true));
+        ring.push(frame(533, 10, true));
+        ring.push(frame(0, 10, true));
+        assert_eq!(ring.fill().frames, 1);
+    }
+
+    #[test]
+    fn test_skipped_buffer_waits_for_keyframe() {
+        let resync = AtomicBool::new(false);
+        assert!(admit(&resync, false, true));
+        assert!(admit(&resync, false, false));
+
+        // The worker fell behind: the following delta frames would be undecodable
+        assert!(!admit(&resync, true, false));
+        assert!(!admit(&resync, false, false));
+        assert!(!admit(&resync, false, false));
+        assert!(admit(&resync, false, true));
+        assert!(admit(&resync, false, false));
+
+        // Raw and MJPEG buffers are all keyframes, so nothing more is skipped
+        assert!(!admit(&resync, true, true));
+        assert!(admit(&resync, false, true));
+    }
+
+    #[test]
+    fn test_snapshot_starts_at_keyframe() {
+        let mut ring = FrameRing::new(Duration::from_secs(10), usize::MAX);
+        ring.push(frame(0, 10, false));
+        ring.push(frame(33, 10, false));
+        ring.push(frame(66, 10, true));
+        ring.push(frame(100, 10, false));
+        let snapshot = ring.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot[0].keyframe);
+        assert_eq!(snapshot[0].pts, Duration::from_millis(66));
+    }
+}
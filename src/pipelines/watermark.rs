@@ -0,0 +1,341 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Watermark and timestamp burn-in
+//!
+//! Unlike the preview overlays (grid, QR boxes, filter label), the watermark
+//! becomes part of the saved pixels. Photos and recordings both render it
+//! with GStreamer's Pango text overlay so the two look the same:
+//!
+//! - Recordings get the overlay in the recording branch, after the tee that
+//!   feeds the preview, so the preview never shows it. With a timestamp,
+//!   `clockoverlay` renders the wall-clock time of each frame.
+//! - Photos push the finished image through a one-buffer pipeline with the
+//!   same overlay, as the last step before encoding. The timestamp is the
+//!   capture time, rendered once.
+//!
+//! The text size follows the frame height, so a watermark set up on one
+//! resolution looks the same on another.
+//!
+//! The overlay is not drawn with WGPU: recordings never pass through the GPU
+//! pipelines (see [`crate::gpu`]), so a WGPU draw would need a download and
+//! re-upload of every recorded frame, and would stop working whenever the
+//! preview device is lost. Pango also shapes any script the text is typed
+//! in, which no text renderer in the WGPU code here does.
+
+use crate::config::{WatermarkPosition, WatermarkSettings};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use gstreamer_app as gst_app;
+use image::RgbImage;
+use tracing::{debug, info};
+
+/// Date and time format of the timestamp (matches `clockoverlay`'s `time-format`)
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Smallest text height in pixels, whatever the frame size
+const MIN_FONT_PIXELS: u32 = 8;
+
+/// How long a photo may take to pass through the overlay pipeline
+const STILL_TIMEOUT_SECS: u64 = 5;
+
+impl WatermarkPosition {
+    /// `halignment` and `valignment` of the text overlay
+    fn alignment(&self) -> (&'static str, &'static str) {
+        match self {
+            WatermarkPosition::TopLeft => ("left", "top"),
+            WatermarkPosition::TopRight => ("right", "top"),
+            WatermarkPosition::BottomLeft => ("left", "bottom"),
+            WatermarkPosition::BottomRight => ("right", "bottom"),
+        }
+    }
+}
+
+/// Text of one photo's watermark, `None` if there is nothing to draw
+///
+/// The timestamp follows the fixed text, as `clockoverlay` places it.
+pub fn still_text(
+    settings: &WatermarkSettings,
+    time: chrono::DateTime<chrono::Local>,
+) -> Option<String> {
+    if !settings.enabled {
+        return None;
+    }
+    let text = settings.text.trim();
+    let stamp = settings
+        .timestamp
+        .then(|| time.format(TIMESTAMP_FORMAT).to_string());
+    match (text.is_empty(), stamp) {
+        (true, None) => None,
+        (true, Some(stamp)) => Some(stamp),
+        (false, None) => Some(text.to_string()),
+        (false, Some(stamp)) => Some(format!("{} {}", text, stamp)),
+    }
+}
+
+/// Set position, size and opacity on a `textoverlay` or `clockoverlay`
+fn configure(overlay: &gst::Element, settings: &WatermarkSettings, frame_height: u32) {
+    let font_pixels =
+        (frame_height * settings.font_size_percent.clamp(1, 100) / 100).max(MIN_FONT_PIXELS);
+    let alpha = settings.opacity_percent.min(100) * 255 / 100;
+    let (halignment, valignment) = settings.position.alignment();
+
+    overlay.set_property_from_str("halignment", halignment);
+    overlay.set_property_from_str("valignment", valignment);
+    overlay.set_property("font-desc", format!("Sans Bold {}px", font_pixels));
+    // ARGB: white text with a black outline, both at the chosen opacity
+    overlay.set_property("color", (alpha << 24) | 0x00ff_ffff);
+    overlay.set_property("outline-color", alpha << 24);
+    overlay.set_property("draw-shadow", false);
+    let padding = (font_pixels / 2) as i32;
+    overlay.set_property("xpad", padding);
+    overlay.set_property("ypad", padding);
+}
+
+/// Overlay element for the recording branch, `None` if there is nothing to draw
+///
+/// Fails if the Pango plugin is missing, so a recording never silently lacks
+/// a watermark the user asked for.
+pub fn recording_overlay(
+    settings: &WatermarkSettings,
+    frame_height: u32,
+) -> Result<Option<gst::Element>, String> {
+    if !settings.enabled {
+        return Ok(None);
+    }
+    let text = settings.text.trim();
+    let overlay = if settings.timestamp {
+        gst::ElementFactory::make("clockoverlay")
+            .property("time-format", TIMESTAMP_FORMAT)
+            .property("text", text)
+            .build()
+            .map_err(|e| format!("Failed to create clockoverlay for the watermark: {}", e))?
+    } else if !text.is_empty() {
+        gst::ElementFactory::make("textoverlay")
+            .property("text", text)
+            .build()
+            .map_err(|e| format!("Failed to create textoverlay for the watermark: {}", e))?
+    } else {
+        return Ok(None);
+    };
+    configure(&overlay, settings, frame_height);
+    info!(
+        text,
+        timestamp = settings.timestamp,
+        position = settings.position.display_name(),
+        "Burning watermark into recording"
+    );
+    Ok(Some(overlay))
+}
+
+/// Watermark of one photo: the settings and the text rendered for it
+#[derive(Debug, Clone)]
+pub struct StillWatermark {
+    settings: WatermarkSettings,
+    text: String,
+}
+
+impl StillWatermark {
+    /// Watermark of a photo captured at `time`, `None` if disabled or empty
+    pub fn new(
+        settings: &WatermarkSettings,
+        time: chrono::DateTime<chrono::Local>,
+    ) -> Option<Self> {
+        still_text(settings, time).map(|text| Self {
+            settings: settings.clone(),
+            text,
+        })
+    }
+
+    /// Add `extra` after the text (e.g. the frame counter of the photo)
+    pub fn append_text(&mut self, extra: &str) {
+        self.text.push(' ');
+        self.text.push_str(extra);
+    }
+
+    /// Draw the watermark into `image` (blocking)
+    pub fn burn_into(&self, image: RgbImage) -> Result<RgbImage, String> {
+        gst::init().map_err(|e| format!("Failed to initialize GStreamer: {}", e))?;
+        let (width, height) = image.dimensions();
+        let info =
+            gstreamer_video::VideoInfo::builder(gstreamer_video::VideoFormat::Rgb, width, height)
+                .fps(gst::Fraction::new(0, 1))
+                .build()
+                .map_err(|e| format!("Invalid watermark frame size: {}", e))?;
+        let caps = info
+            .to_caps()
+            .map_err(|e| format!("Invalid watermark caps: {}", e))?;
+
+        let make = |name: &str| {
+            gst::ElementFactory::make(name)
+                .build()
+                .map_err(|e| format!("Failed to create {} for the watermark: {}", name, e))
+        };
+        let overlay = make("textoverlay")?;
+        overlay.set_property("text", &self.text);
+        configure(&overlay, &self.settings, height);
+
+        let appsrc = gst_app::AppSrc::builder()
+            .caps(&caps)
+            .format(gst::Format::Time)
+            .is_live(false)
+            .build();
+        let appsink = gst_app::AppSink::builder().caps(&caps).sync(false).build();
+        let (convert_in, convert_out) = (make("videoconvert")?, make("videoconvert")?);
+
+        let pipeline = gst::Pipeline::new();
+        let elements = [
+            appsrc.upcast_ref::<gst::Element>(),
+            &convert_in,
+            &overlay,
+            &convert_out,
+            appsink.upcast_ref(),
+        ];
+        pipeline
+            .add_many(elements)
+            .map_err(|e| format!("Failed to add watermark elements: {}", e))?;
+        gst::Element::link_many(elements)
+            .map_err(|e| format!("Failed to link watermark pipeline: {}", e))?;
+
+        let result = Self::run(&pipeline, &appsrc, &appsink, &info, &image);
+        let _ = pipeline.set_state(gst::State::Null);
+        let image = result?;
+        debug!(width, height, text = %self.text, "Watermark burned into photo");
+        Ok(image)
+    }
+
+    /// Push `image` through the pipeline and read the overlaid frame back
+    fn run(
+        pipeline: &gst::Pipeline,
+        appsrc: &gst_app::AppSrc,
+        appsink: &gst_app::AppSink,
+        info: &gstreamer_video::VideoInfo,
+        image: &RgbImage,
+    ) -> Result<RgbImage, String> {
+        let (width, height) = image.dimensions();
+        let row_bytes = width as usize * 3;
+        let stride = info.stride()[0] as usize;
+
+        let mut buffer = gst::Buffer::with_size(info.size())
+            .map_err(|e| format!("Failed to allocate watermark buffer: {}", e))?;
+        {
+            let buffer = buffer.get_mut().ok_or("Watermark buffer is shared")?;
+            buffer.set_pts(gst::ClockTime::ZERO);
+            let mut map = buffer
+                .map_writable()
+                .map_err(|e| format!("Failed to map watermark buffer: {}", e))?;
+            for (dst, src) in map.chunks_mut(stride).zip(image.as_raw().chunks(row_bytes)) {
+                dst[..row_bytes].copy_from_slice(src);
+            }
+        }
+
+        pipeline
+            .set_state(gst::State::Playing)
+            .map_err(|e| format!("Failed to start watermark pipeline: {}", e))?;
+        appsrc
+            .push_buffer(buffer)
+            .map_err(|e| format!("Failed to push photo to watermark pipeline: {}", e))?;
+        let _ = appsrc.end_of_stream();
+
+        let Some(sample) =
+            appsink.try_pull_sample(gst::ClockTime::from_seconds(STILL_TIMEOUT_SECS))
+        else {
+            let error = pipeline
+                .bus()
+                .and_then(|bus| bus.pop_filtered(&[gst::MessageType::Error]))
+                .and_then(|message| match message.view() {
+                    gst::MessageView::Error(err) => Some(err.error().to_string()),
+                    _ => None,
+                });
+            return Err(format!(
+                "Watermark pipeline produced no frame: {}",
+                error.unwrap_or_else(|| "timed out".to_string())
+            ));
+        };
+        let buffer = sample.buffer().ok_or("Watermark sample has no buffer")?;
+        let map = buffer
+            .map_readable()
+            .map_err(|e| format!("Failed to map watermarked frame: {}", e))?;
+
+        let mut data = Vec::with_capacity(row_bytes * height as usize);
+        for row in map.chunks(stride).take(height as usize) {
+            data.extend_from_slice(row.get(..row_bytes).ok_or("Watermarked frame too short")?);
+        }
+        RgbImage::from_raw(width, height, data).ok_or_else(|| "Watermarked frame too short".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn settings(text: &str, timestamp: bool) -> WatermarkSettings {
+        WatermarkSettings {
+            enabled: true,
+            text: text.to_string(),
+            timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_still_text() {
+        let time = chrono::Local
+            .with_ymd_and_hms(2026, 3, 14, 9, 26, 53)
+            .unwrap();
+        assert_eq!(
+            still_text(&settings(" Lab 4 ", true), time).as_deref(),
+            Some("Lab 4 2026-03-14 09:26:53")
+        );
+        assert_eq!(
+            still_text(&settings("", true), time).as_deref(),
+            Some("2026-03-14 09:26:53")
+        );
+        assert_eq!(still_text(&settings("  ", false), time), None);
+        let disabled = WatermarkSettings {
+            enabled: false,
+            ..settings("Lab 4", true)
+        };
+        assert_eq!(still_text(&disabled, time), None);
+    }
+
+    #[test]
+    fn test_burned_text_is_in_saved_pixels() {
+        let _ = gst::init();
+        if gst::ElementFactory::find("textoverlay").is_none() {
+            // Needs the Pango plugin from gst-plugins-base
+            return;
+        }
+        let settings = WatermarkSettings {
+            position: WatermarkPosition::BottomRight,
+            font_size_percent: 15,
+            opacity_percent: 100,
+            ..settings("WATERMARK", false)
+        };
+        let watermark = StillWatermark::new(&settings, chrono::Local::now()).unwrap();
+        // Odd width, so rows are padded in the GStreamer buffer
+        let (width, height) = (321, 240);
+        let image = watermark
+            .burn_into(RgbImage::new(width, height))
+            .expect("watermark should be burned in");
+
+        // Round-trip through PNG, as the photo is saved
+        let mut png = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut png, image::ImageFormat::Png)
+            .expect("PNG encoding");
+        let saved = image::load_from_memory(png.get_ref()).unwrap().to_rgb8();
+
+        let bright = |x0: u32, y0: u32| {
+            (y0..y0 + height / 2)
+                .flat_map(|y| (x0..x0 + width / 2).map(move |x| (x, y)))
+                .filter(|&(x, y)| saved.get_pixel(x, y).0.iter().all(|&c| c > 200))
+                .count()
+        };
+        assert!(
+            bright(width / 2, height / 2) > 50,
+            "no text in bottom right"
+        );
+        assert_eq!(bright(0, 0), 0, "text outside the chosen corner");
+    }
+}
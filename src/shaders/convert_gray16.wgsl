@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// GPU compute shader for Gray16 to RGBA conversion
+//
+// Gray16: 16-bit little-endian luminance (Y16)
+// Texture is uploaded as RG8 where: R=low byte, G=high byte
+// The value is stretched from [range_min, range_max] to [0, 1]
+
+struct ConvertParams {
+    width: u32,
+    height: u32,
+    range_min: f32,
+    range_max: f32,
+}
+
+@group(0) @binding(0) var tex_gray: texture_2d<f32>;
+@group(0) @binding(1) var output: texture_storage_2d<rgba8unorm, write>;
+@group(0) @binding(2) var<uniform> params: ConvertParams;
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    if (x >= params.width || y >= params.height) {
+        return;
+    }
+
+    let pos = vec2(x, y);
+    let texel = textureLoad(tex_gray, pos, 0);
+    let value = (texel.r * 255.0 + texel.g * 255.0 * 256.0) / 65535.0;
+    let range = max(params.range_max - params.range_min, 1.0 / 65535.0);
+    let gray = clamp((value - params.range_min) / range, 0.0, 1.0);
+    textureStore(output, pos, vec4(gray, gray, gray, 1.0));
+}
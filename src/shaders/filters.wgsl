@@ -113,3 +113,24 @@ fn apply_filter(color: vec3<f32>, filter_mode: u32, tex_coords: vec2<f32>) -> ve
 
     return result;
 }
+
+// Apply full-frame color effect (effect 0-4, see ColorEffect::gpu_code)
+// Runs after the filter; must match ColorEffect::apply_rgb for captured photos
+fn apply_color_effect(color: vec3<f32>, effect: u32) -> vec3<f32> {
+    if (effect == 1u) {
+        // Grayscale
+        let lum = luminance(color);
+        return vec3<f32>(lum, lum, lum);
+    } else if (effect == 2u) {
+        // Sepia
+        let lum = luminance(color);
+        return clamp(lum * vec3<f32>(1.07, 0.74, 0.43), vec3<f32>(0.0), vec3<f32>(1.0));
+    } else if (effect == 3u) {
+        // Negative
+        return vec3<f32>(1.0) - color;
+    } else if (effect == 4u) {
+        // High contrast
+        return clamp((color - vec3<f32>(0.5)) * 3.0 + vec3<f32>(0.5), vec3<f32>(0.0), vec3<f32>(1.0));
+    }
+    return color;
+}
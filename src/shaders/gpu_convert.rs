@@ -9,22 +9,25 @@
 //! - I420: Planar 4:2:0
 //! - YUYV/UYVY/YVYU/VYUY: Packed 4:2:2
 //! - Gray8: 8-bit grayscale
+//! - Gray16: 16-bit grayscale (stretched to the frame's intensity range)
 //! - RGBA: Passthrough (no conversion needed)
 
-use crate::backends::camera::types::PixelFormat;
+use crate::backends::camera::types::{PixelFormat, gray16_range};
 use crate::gpu::{self, wgpu};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
-/// Simple conversion parameters (width and height only)
+/// Simple conversion parameters (width and height, plus the Gray16 range)
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 struct ConvertParams {
     width: u32,
     height: u32,
-    _pad0: u32,
-    _pad1: u32,
+    /// Gray16 black point in [0,1] (padding for other formats)
+    range_min: f32,
+    /// Gray16 white point in [0,1] (padding for other formats)
+    range_max: f32,
 }
 
 /// Input frame data for conversion
@@ -136,6 +139,10 @@ impl GpuConvertPipeline {
                 self.create_packed_pipeline(include_str!("convert_vyuy.wgsl"), "vyuy")
             }
             PixelFormat::Gray8 => self.create_gray8_pipeline(),
+            // Gray16 shares the packed layout (single texture, output, params)
+            PixelFormat::Gray16 => {
+                self.create_packed_pipeline(include_str!("convert_gray16.wgsl"), "gray16")
+            }
             PixelFormat::RGBA | PixelFormat::RGB24 => {
                 // RGBA doesn't need conversion, but create a dummy pipeline for API consistency
                 self.create_nv12_pipeline() // Fallback, shouldn't be used
@@ -576,7 +583,9 @@ impl GpuConvertPipeline {
             PixelFormat::YUYV | PixelFormat::UYVY | PixelFormat::YVYU | PixelFormat::VYUY => {
                 (width / 2, height)
             }
-            PixelFormat::Gray8 | PixelFormat::RGBA | PixelFormat::RGB24 => (1, 1),
+            PixelFormat::Gray8 | PixelFormat::Gray16 | PixelFormat::RGBA | PixelFormat::RGB24 => {
+                (1, 1)
+            }
         };
 
         // Y plane texture format and dimensions
@@ -585,6 +594,8 @@ impl GpuConvertPipeline {
                 (wgpu::TextureFormat::Rgba8Unorm, width / 2)
             }
             PixelFormat::RGBA | PixelFormat::RGB24 => (wgpu::TextureFormat::Rgba8Unorm, width),
+            // Gray16: low/high bytes in R/G
+            PixelFormat::Gray16 => (wgpu::TextureFormat::Rg8Unorm, width),
             _ => (wgpu::TextureFormat::R8Unorm, width),
         };
 
@@ -694,11 +705,20 @@ impl GpuConvertPipeline {
         self.upload_textures(input, tex_y, tex_uv, tex_v)?;
 
         // Update uniform buffer
+        // Stills from Gray16 sources are always stretched so the saved image is viewable
+        let (range_min, range_max) = match input.format {
+            PixelFormat::Gray16 => {
+                gray16_range(input.y_data, input.width, input.height, input.y_stride)
+                    .map(|(min, max)| (min as f32 / 65535.0, max as f32 / 65535.0))
+                    .unwrap_or((0.0, 1.0))
+            }
+            _ => (0.0, 0.0),
+        };
         let params = ConvertParams {
             width: input.width,
             height: input.height,
-            _pad0: 0,
-            _pad1: 0,
+            range_min,
+            range_max,
         };
         self.queue
             .write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&params));
@@ -915,8 +935,8 @@ impl GpuConvertPipeline {
                 }
             }
 
-            // Gray8: single channel
-            PixelFormat::Gray8 => {
+            // Gray8/Gray16: single channel (Gray16 as RG8)
+            PixelFormat::Gray8 | PixelFormat::Gray16 => {
                 self.queue.write_texture(
                     wgpu::ImageCopyTexture {
                         texture: tex_y,
@@ -1011,12 +1031,13 @@ impl GpuConvertPipeline {
                 ],
             }),
 
-            // Packed formats and Gray8: tex_packed/tex_gray, output, params
+            // Packed formats and grayscale: tex_packed/tex_gray, output, params
             PixelFormat::YUYV
             | PixelFormat::UYVY
             | PixelFormat::YVYU
             | PixelFormat::VYUY
-            | PixelFormat::Gray8 => self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            | PixelFormat::Gray8
+            | PixelFormat::Gray16 => self.device.create_bind_group(&wgpu::BindGroupDescriptor {
                 label: Some("packed_bind_group"),
                 layout,
                 entries: &[
@@ -1164,6 +1185,14 @@ pub async fn get_gpu_convert_pipeline()
     let lock = GPU_CONVERT_PIPELINE.get_or_init(|| tokio::sync::Mutex::new(None));
     let mut guard = lock.lock().await;
 
+    if guard
+        .as_ref()
+        .is_some_and(|pipeline| gpu::is_device_lost(&pipeline.device))
+    {
+        warn!("GPU device was lost, recreating convert pipeline");
+        *guard = None;
+    }
+
     if guard.is_none() {
         match GpuConvertPipeline::new().await {
             Ok(pipeline) => {
@@ -1178,3 +1207,76 @@ pub async fn get_gpu_convert_pipeline()
 
     Ok(guard)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BT.601 limited range, as in the conversion shaders
+    fn bt601(y: u8, u: u8, v: u8) -> [u8; 3] {
+        let y = (y as f32 - 16.0) / 219.0;
+        let u = u as f32 / 255.0 - 0.5;
+        let v = v as f32 / 255.0 - 0.5;
+        let to_byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [
+            to_byte(y + 1.402 * v),
+            to_byte(y - 0.344136 * u - 0.714136 * v),
+            to_byte(y + 1.772 * u),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_packed_formats_convert_to_known_colors() {
+        // This test requires a GPU, so it may be skipped in CI
+        let mut pipeline = match GpuConvertPipeline::new().await {
+            Ok(pipeline) => pipeline,
+            Err(e) => {
+                println!("Skipping test (no GPU): {}", e);
+                return;
+            }
+        };
+
+        // Two pixels sharing red chroma: Y0 is pure red, Y1 a lighter red.
+        // A swapped U/V turns them blue, swapped lumas trade the two pixels.
+        let (y0, u, y1, v) = (81, 90, 145, 240);
+        let cases = [
+            (PixelFormat::YUYV, [y0, u, y1, v]),
+            (PixelFormat::UYVY, [u, y0, v, y1]),
+            (PixelFormat::YVYU, [y0, v, y1, u]),
+            (PixelFormat::VYUY, [v, y0, u, y1]),
+        ];
+        for (format, bytes) in cases {
+            let input = GpuFrameInput {
+                width: 2,
+                height: 1,
+                format,
+                y_data: &bytes,
+                y_stride: 4,
+                uv_data: None,
+                uv_stride: 0,
+                v_data: None,
+                v_stride: 0,
+            };
+            pipeline.convert(&input).expect("conversion failed");
+            let rgba = pipeline
+                .read_rgba_to_cpu(2, 1)
+                .await
+                .expect("readback failed");
+            for (pixel, luma) in [y0, y1].into_iter().enumerate() {
+                let expected = bt601(luma, u, v);
+                let actual = &rgba[pixel * 4..pixel * 4 + 3];
+                assert!(
+                    actual
+                        .iter()
+                        .zip(expected)
+                        .all(|(&a, e)| a.abs_diff(e) <= 2),
+                    "{:?} pixel {}: got {:?}, expected {:?}",
+                    format,
+                    pixel,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+}
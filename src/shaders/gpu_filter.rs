@@ -361,6 +361,14 @@ pub async fn get_gpu_filter_pipeline()
     let lock = GPU_FILTER_PIPELINE.get_or_init(|| tokio::sync::Mutex::new(None));
     let mut guard = lock.lock().await;
 
+    if guard
+        .as_ref()
+        .is_some_and(|pipeline| gpu::is_device_lost(&pipeline.device))
+    {
+        warn!("GPU device was lost, recreating filter pipeline");
+        *guard = None;
+    }
+
     if guard.is_none() {
         match GpuFilterPipeline::new().await {
             Ok(pipeline) => {
@@ -402,7 +402,18 @@ fn get_histogram_pipeline() -> Option<std::sync::MutexGuard<'static, Option<Hist
             }
         });
 
-    let guard = mutex.lock().ok()?;
+    let mut guard = mutex.lock().ok()?;
+    if guard
+        .as_ref()
+        .is_some_and(|pipeline| gpu::is_device_lost(&pipeline.device))
+    {
+        warn!("GPU device was lost, recreating histogram pipeline");
+        // Drop the old pipeline (and its device) before creating the new one
+        *guard = None;
+        *guard = pollster::block_on(HistogramPipeline::new())
+            .inspect_err(|e| warn!("Failed to recreate GPU histogram pipeline: {}", e))
+            .ok();
+    }
     if guard.is_some() { Some(guard) } else { None }
 }
 
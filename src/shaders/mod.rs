@@ -24,3 +24,40 @@ pub use histogram_pipeline::{BrightnessMetrics, analyze_brightness_gpu};
 /// Contains: luminance(), hash(), apply_filter()
 /// Used by: preview shaders, photo capture, virtual camera
 pub const FILTER_FUNCTIONS: &str = include_str!("filters.wgsl");
+
+/// YUV→RGBA conversion as a compute shader (entry point `main`)
+pub const YUV_CONVERT_COMPUTE: &str = concat!(
+    include_str!("yuv_convert.wgsl"),
+    include_str!("yuv_convert_compute.wgsl")
+);
+
+/// YUV→RGBA conversion as a fullscreen fragment shader (`vs_main`/`fs_main`),
+/// for GPUs without compute shaders
+pub const YUV_CONVERT_FRAGMENT: &str = concat!(
+    include_str!("yuv_convert.wgsl"),
+    include_str!("yuv_convert_fragment.wgsl")
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate_shader(name: &str, source: &str) {
+        let module = naga::front::wgsl::parse_str(source)
+            .unwrap_or_else(|e| panic!("Shader '{}' parse failed: {:?}", name, e));
+        if let Err(e) = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .validate(&module)
+        {
+            panic!("Shader '{}' validation failed: {:?}", name, e);
+        }
+    }
+
+    #[test]
+    fn test_yuv_convert_variants_validate() {
+        validate_shader("yuv_convert_compute", YUV_CONVERT_COMPUTE);
+        validate_shader("yuv_convert_fragment", YUV_CONVERT_FRAGMENT);
+    }
+}
@@ -1,25 +1,32 @@
 // SPDX-License-Identifier: GPL-3.0-only
-// GPU compute shader for YUV to RGBA conversion
+// YUV to RGBA conversion shared by the compute and fragment entry points
+// (yuv_convert_compute.wgsl, yuv_convert_fragment.wgsl)
 //
 // Supports multiple YUV formats:
 // - NV12: Semi-planar 4:2:0 (Y plane + interleaved UV plane)
 // - I420: Planar 4:2:0 (Y + U + V separate planes)
-// - YUYV: Packed 4:2:2 (Y0 U Y1 V interleaved)
+// - YUYV/UYVY/YVYU/VYUY: Packed 4:2:2 (two pixels sharing U and V, in the
+//   byte order the name spells out)
+// - Gray8/Gray16: Single channel grayscale (optionally false-colored)
 //
 // Uses BT.601 color matrix (standard for webcams and JPEG)
 
 struct ConvertParams {
     width: u32,
     height: u32,
-    format: u32,      // 0=RGBA (passthrough), 1=NV12, 2=I420, 3=YUYV
+    format: u32,      // See convert_pixel for the format codes
     y_stride: u32,    // Y plane stride in texels (for stride-aware sampling)
     uv_stride: u32,   // UV plane stride in texels
     v_stride: u32,    // V plane stride in texels (I420 only)
+    gray_min: f32,    // Grayscale black point in [0,1] (Gray16 auto-stretch)
+    gray_max: f32,    // Grayscale white point in [0,1] (Gray16 auto-stretch)
+    false_color: u32, // 1 = map grayscale through the false-color palette
     _pad0: u32,       // Padding for 16-byte alignment
     _pad1: u32,
+    _pad2: u32,
 }
 
-// Y plane texture (R8 for planar, RG8 for YUYV packed)
+// Y plane texture (R8 for planar, RGBA8 for packed 4:2:2)
 @group(0) @binding(0) var tex_y: texture_2d<f32>;
 
 // UV texture: RG8 for NV12 (interleaved UV), R8 for I420 (U plane only)
@@ -28,8 +35,7 @@ struct ConvertParams {
 // V texture: R8 for I420 only (V plane)
 @group(0) @binding(2) var tex_v: texture_2d<f32>;
 
-// Output RGBA texture (storage texture for compute shader write)
-@group(0) @binding(3) var output: texture_storage_2d<rgba8unorm, write>;
+// Binding 3 is the storage output of the compute entry point
 
 // Conversion parameters
 @group(0) @binding(4) var<uniform> params: ConvertParams;
@@ -184,11 +190,45 @@ fn convert_nv21(pos: vec2<u32>) -> vec3<f32> {
     return yuv_to_rgb_bt601(y, vu.g, vu.r);
 }
 
+// Map a normalized intensity through an ironbow-style palette
+// (black -> purple -> red -> orange -> pale yellow), common for thermal imagery
+fn false_color_palette(t: f32) -> vec3<f32> {
+    var stops = array<vec3<f32>, 5>(
+        vec3(0.0, 0.0, 0.0),
+        vec3(0.5, 0.0, 0.6),
+        vec3(0.9, 0.2, 0.1),
+        vec3(1.0, 0.65, 0.0),
+        vec3(1.0, 1.0, 0.85),
+    );
+    let scaled = clamp(t, 0.0, 1.0) * 4.0;
+    let i = min(u32(scaled), 3u);
+    return mix(stops[i], stops[i + 1u], scaled - f32(i));
+}
+
+// Output a grayscale intensity as RGB, optionally false-colored
+fn gray_to_rgb(gray: f32) -> vec3<f32> {
+    if (params.false_color == 1u) {
+        return false_color_palette(gray);
+    }
+    return vec3(gray, gray, gray);
+}
+
 // Convert Gray8 pixel at given position
 // Gray8: Single channel luminance, output as grayscale RGB
 fn convert_gray8(pos: vec2<u32>) -> vec3<f32> {
     let gray = textureLoad(tex_y, pos, 0).r;
-    return vec3(gray, gray, gray);
+    return gray_to_rgb(gray);
+}
+
+// Convert Gray16 pixel at given position
+// Gray16: 16-bit little-endian luminance uploaded as RG8 (R = low byte, G = high byte)
+// The value is stretched from [gray_min, gray_max] to [0, 1]
+fn convert_gray16(pos: vec2<u32>) -> vec3<f32> {
+    let texel = textureLoad(tex_y, pos, 0);
+    let value = (texel.r * 255.0 + texel.g * 255.0 * 256.0) / 65535.0;
+    let range = max(params.gray_max - params.gray_min, 1.0 / 65535.0);
+    let gray = clamp((value - params.gray_min) / range, 0.0, 1.0);
+    return gray_to_rgb(gray);
 }
 
 // Convert RGB24 pixel at given position
@@ -204,21 +244,12 @@ fn passthrough_rgba(pos: vec2<u32>) -> vec4<f32> {
     return textureLoad(tex_y, pos, 0);
 }
 
-@compute @workgroup_size(16, 16)
-fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
-    let x = global_id.x;
-    let y = global_id.y;
-
-    // Bounds check
-    if (x >= params.width || y >= params.height) {
-        return;
-    }
-
-    let pos = vec2(x, y);
+// Convert the pixel at `pos` (inside params.width × params.height) to RGBA
+fn convert_pixel(pos: vec2<u32>) -> vec4<f32> {
     var color: vec4<f32>;
 
     // Select conversion based on format
-    // Format codes: 0=RGBA, 1=NV12, 2=I420, 3=YUYV, 4=UYVY, 5=Gray8, 6=RGB24, 7=NV21, 8=YVYU, 9=VYUY
+    // Format codes: 0=RGBA, 1=NV12, 2=I420, 3=YUYV, 4=UYVY, 5=Gray8, 6=RGB24, 7=NV21, 8=YVYU, 9=VYUY, 10=Gray16
     switch params.format {
         case 1u: {
             // NV12
@@ -256,12 +287,15 @@ fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
             // VYUY
             color = vec4(convert_vyuy(pos), 1.0);
         }
+        case 10u: {
+            // Gray16
+            color = vec4(convert_gray16(pos), 1.0);
+        }
         default: {
             // RGBA passthrough (format 0 or unknown)
             color = passthrough_rgba(pos);
         }
     }
 
-    // Write to output texture
-    textureStore(output, pos, color);
+    return color;
 }
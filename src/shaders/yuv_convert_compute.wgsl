@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// Compute entry point: one invocation per output pixel in 16x16 workgroups
+
+// Output RGBA texture (storage texture for compute shader write)
+@group(0) @binding(3) var output: texture_storage_2d<rgba8unorm, write>;
+
+@compute @workgroup_size(16, 16)
+fn main(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let x = global_id.x;
+    let y = global_id.y;
+
+    // Bounds check
+    if (x >= params.width || y >= params.height) {
+        return;
+    }
+
+    let pos = vec2(x, y);
+    textureStore(output, pos, convert_pixel(pos));
+}
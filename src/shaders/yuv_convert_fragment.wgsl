@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: GPL-3.0-only
+// Fragment entry point for GPUs without compute shaders: a fullscreen
+// triangle rendered into the output texture, one fragment per pixel
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    // Covers clip space with a single triangle: (-1,-1), (3,-1), (-1,3)
+    let uv = vec2(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.position = vec4(uv * 2.0 - 1.0, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Fragment positions are pixel centers; truncating gives the pixel index
+    let pos = vec2<u32>(in.position.xy);
+    return convert_pixel(pos);
+}
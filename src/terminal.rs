@@ -5,17 +5,17 @@
 //! Renders camera feed to the terminal using Unicode half-block characters
 //! for improved vertical resolution.
 
+use crate::backends::camera::frame_queue::{self, FrameDropPolicy};
 use crate::backends::camera::pipewire::{
     PipeWirePipeline, enumerate_pipewire_cameras, get_pipewire_formats,
 };
-use crate::backends::camera::types::{CameraDevice, CameraFormat, CameraFrame};
+use crate::backends::camera::types::{CameraDevice, CameraFormat, CameraFrame, FrameReceiver};
 
 use crossterm::{
     event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use futures::channel::mpsc;
 use ratatui::{
     Terminal, backend::CrosstermBackend, buffer::Buffer, layout::Rect, style::Color,
     widgets::Widget,
@@ -49,7 +49,7 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 
 struct CameraPipeline {
     _pipeline: PipeWirePipeline,
-    receiver: mpsc::Receiver<CameraFrame>,
+    receiver: FrameReceiver,
 }
 
 impl CameraPipeline {
@@ -57,7 +57,7 @@ impl CameraPipeline {
         device: &CameraDevice,
         format: &CameraFormat,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        let (sender, receiver) = mpsc::channel(10);
+        let (sender, receiver) = frame_queue::channel(10, FrameDropPolicy::SkipToLatest);
         let pipeline = PipeWirePipeline::new(device, format, sender)?;
         Ok(Self {
             _pipeline: pipeline,
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Integration tests that need a real camera
+//!
+//! Ignored by default; run with `cargo test --test camera_tests -- --ignored`
+//! on a machine with PipeWire and at least one camera.
+
+use camera::backends::camera::frame_queue::{self, FrameDropPolicy};
+use camera::backends::camera::pipewire::{
+    PipeWirePipeline, enumerate_pipewire_cameras, get_pipewire_formats,
+};
+use std::time::Duration;
+
+#[tokio::test]
+#[ignore = "requires a camera"]
+async fn test_open_await_frame_snapshot_close() {
+    gstreamer::init().expect("GStreamer init");
+    let cameras = enumerate_pipewire_cameras().unwrap_or_default();
+    let camera = cameras.first().expect("no camera available");
+    let formats = get_pipewire_formats(&camera.path, camera.metadata_path.as_deref());
+    let format = formats.first().expect("camera reports no formats");
+
+    let (sender, mut receiver) = frame_queue::channel(4, FrameDropPolicy::SkipToLatest);
+    let pipeline = PipeWirePipeline::new(camera, format, sender).expect("create pipeline");
+
+    pipeline
+        .wait_for_first_frame(Duration::from_secs(5))
+        .await
+        .expect("first frame");
+    assert!(pipeline.has_first_frame());
+
+    let frame = receiver.try_next().ok().flatten().expect("frame queued");
+    assert!(frame.width > 0 && frame.height > 0);
+
+    pipeline.stop().expect("stop pipeline");
+}
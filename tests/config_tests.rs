@@ -25,3 +25,18 @@ fn test_config_bug_report_url() {
         "Bug report URL should not be empty"
     );
 }
+
+#[test]
+fn test_present_mode_env_round_trip() {
+    use camera::config::PresentMode;
+
+    for mode in PresentMode::ALL {
+        assert_eq!(PresentMode::from_env_value(mode.env_value()), Some(mode));
+    }
+    assert_eq!(
+        PresentMode::from_env_value("no_vsync"),
+        Some(PresentMode::Immediate)
+    );
+    assert!(PresentMode::default().is_vsync());
+    assert!(!PresentMode::Mailbox.is_vsync());
+}